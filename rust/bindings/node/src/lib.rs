@@ -0,0 +1,80 @@
+// bindings/node/src/lib.rs
+// ✅ 通过 napi-rs 把 Rust 上传工作流暴露给 Node.js / TypeScript
+
+use std::path::PathBuf;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rust::backend::{CliBackend, IpfsBackend};
+use rust::manifest::Manifest;
+use rust::workflow::{self, BatchOptions, SingleOptions};
+
+const DEFAULT_OUTPUT_ROOT: &str = "output";
+
+fn to_napi_error(err: anyhow::Error) -> Error {
+    Error::from_reason(format!("{:#}", err))
+}
+
+// 工作流是同步的 (会调用 ipfs 命令行)，放到阻塞线程池中执行，避免卡住 Node 事件循环
+async fn run_blocking<T, F>(task: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| Error::from_reason(e.to_string()))?
+        .map_err(to_napi_error)
+}
+
+fn manifest_to_json(manifest: Manifest) -> Result<serde_json::Value> {
+    serde_json::to_value(manifest).map_err(|e| Error::from_reason(e.to_string()))
+}
+
+// 上传单个文件或文件夹，返回根 CID
+#[napi]
+pub async fn upload_path(path: String) -> Result<String> {
+    run_blocking(move || CliBackend::default().add_path(&PathBuf::from(path))).await
+}
+
+// 工作流一：处理单个 NFT，返回 manifest 对象
+#[napi(ts_return_type = "Promise<Record<string, any>>")]
+pub async fn process_single_nft(
+    image_path: String,
+    output_root: Option<String>,
+) -> Result<serde_json::Value> {
+    let output_root = PathBuf::from(output_root.unwrap_or_else(|| DEFAULT_OUTPUT_ROOT.into()));
+    let manifest = run_blocking(move || {
+        workflow::process_single_nft(
+            &CliBackend::default(),
+            &PathBuf::from(image_path),
+            &output_root,
+            &SingleOptions::default(),
+        )
+    })
+    .await?;
+    manifest_to_json(manifest)
+}
+
+// 工作流二：处理批量 NFT 集合，返回 manifest 对象
+#[napi(ts_return_type = "Promise<Record<string, any>>")]
+pub async fn process_batch_collection(
+    images_dir: String,
+    output_root: Option<String>,
+    allow_empty: Option<bool>,
+) -> Result<serde_json::Value> {
+    let output_root = PathBuf::from(output_root.unwrap_or_else(|| DEFAULT_OUTPUT_ROOT.into()));
+    let manifest = run_blocking(move || {
+        workflow::process_batch_collection(
+            &CliBackend::default(),
+            &PathBuf::from(images_dir),
+            &output_root,
+            &BatchOptions {
+                allow_empty: allow_empty.unwrap_or(false),
+                ..BatchOptions::default()
+            },
+        )
+    })
+    .await?;
+    manifest_to_json(manifest)
+}
@@ -0,0 +1,91 @@
+// bindings/wasm/src/lib.rs
+// ✅ 浏览器可用的子集：元数据生成 + Pinning Service API，不依赖本地 daemon 和 std::process
+
+use rust::metadata::{build_batch_metadata, build_single_metadata, metadata_file_name};
+use rust::pinning::{PinRequest, PinStatus, pins_endpoint};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestInit, Response};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildMetadataOptions {
+    image_cid: String,
+    image_filename: String,
+    // 提供 tokenId 时按批量集合生成 (图片链接为 ipfs://<文件夹 CID>/<文件名>)
+    #[serde(default)]
+    token_id: Option<u64>,
+    #[serde(default)]
+    use_json_suffix: bool,
+}
+
+fn to_js_error(err: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+// 返回 { fileName, metadata }，与原生工作流写入 output/ 的内容一致
+#[wasm_bindgen(js_name = buildMetadata)]
+pub fn build_metadata(options: JsValue) -> Result<JsValue, JsValue> {
+    let options: BuildMetadataOptions = serde_wasm_bindgen::from_value(options)?;
+    let (metadata, stem) = match options.token_id {
+        Some(token_id) => (
+            build_batch_metadata(token_id, &options.image_cid, &options.image_filename),
+            token_id.to_string(),
+        ),
+        None => {
+            let metadata = build_single_metadata(&options.image_filename, &options.image_cid);
+            let stem = metadata.name.clone();
+            (metadata, stem)
+        }
+    };
+
+    let result = serde_json::json!({
+        "fileName": metadata_file_name(&stem, options.use_json_suffix),
+        "metadata": metadata,
+    });
+    result
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(to_js_error)
+}
+
+// 通过 Pinning Service API 固定一个 CID，返回服务端的 PinStatus
+#[wasm_bindgen(js_name = pinToService)]
+pub async fn pin_to_service(
+    service_url: String,
+    access_token: String,
+    cid: String,
+    name: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let body = serde_json::to_string(&PinRequest::new(cid, name)).map_err(to_js_error)?;
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_body(&JsValue::from_str(&body));
+    let request = Request::new_with_str_and_init(&pins_endpoint(&service_url), &init)?;
+    request
+        .headers()
+        .set("Authorization", &format!("Bearer {}", access_token))?;
+    request.headers().set("Content-Type", "application/json")?;
+
+    let window = web_sys::window().ok_or_else(|| to_js_error("当前环境没有 window 对象"))?;
+    let response: Response = JsFuture::from(window.fetch_with_request(&request))
+        .await?
+        .dyn_into()?;
+    let text = JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default();
+    if !response.ok() {
+        return Err(to_js_error(format!(
+            "❌ Pin 请求失败 ({}): {}",
+            response.status(),
+            text
+        )));
+    }
+
+    let status: PinStatus = serde_json::from_str(&text).map_err(to_js_error)?;
+    status
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(to_js_error)
+}
@@ -79,10 +79,12 @@ fn process_single_nft(image_path: &Path) -> Result<()> {
         name: image_name_without_ext.to_string(),
         description: format!("这是一个为图片 {} 动态生成的元数据。", image_filename),
         image: format!("ipfs://{}", image_cid),
+        animation_url: None,
         attributes: vec![Attribute {
             trait_type: "类型".to_string(),
             value: serde_json::Value::String("单件艺术品".to_string()),
         }],
+        extra: serde_json::Map::new(),
     };
 
     let metadata_cid = upload_json_str_to_ipfs(&metadata)?;
@@ -143,10 +145,12 @@ fn process_batch_collection(images_input_dir: &Path) -> Result<()> {
             name: format!("MetaCore #{}", token_id),
             description: "MetaCore 集合中的一个独特成员。".to_string(),
             image: format!("ipfs://{}/{}", images_folder_cid, image_filename),
+            animation_url: None,
             attributes: vec![Attribute {
                 trait_type: "ID".to_string(),
                 value: token_id.into(),
             }],
+            extra: serde_json::Map::new(),
         };
         let file_name = if USE_JSON_SUFFIX {
             format!("{}.json", token_id_str)
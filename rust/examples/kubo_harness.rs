@@ -0,0 +1,54 @@
+// examples/kubo_harness.rs
+
+// 启动一次性的 Kubo 节点跑批量工作流，并用它的结果校验内存后端
+// cargo run --example kubo_harness --features kubo-harness
+use rust::backend::{IpfsBackend, MemoryBackend};
+use rust::demo::generate_demo_images;
+use rust::harness::{HarnessOptions, KuboHarness};
+use rust::workflow::{BatchOptions, process_batch_collection};
+
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs;
+
+fn main() -> Result<()> {
+    let root = env::temp_dir().join(format!("kubo-harness-example-{}", std::process::id()));
+    let images_dir = root.join("images");
+    generate_demo_images(&images_dir, 3)?;
+
+    // 节点在 harness 离开作用域时关闭，临时仓库随之删除
+    let harness = KuboHarness::start(HarnessOptions::default())?;
+    let kubo = harness.backend()?;
+    let from_kubo = process_batch_collection(
+        &kubo,
+        &images_dir,
+        &root.join("kubo"),
+        &BatchOptions::default(),
+    )?;
+    if !kubo.is_pinned(&from_kubo.metadata_cid)? {
+        return Err(anyhow!("元数据目录 {} 没有被固定", from_kubo.metadata_cid));
+    }
+
+    let memory = MemoryBackend::new();
+    let from_memory = process_batch_collection(
+        &memory,
+        &images_dir,
+        &root.join("memory"),
+        &BatchOptions::default(),
+    )?;
+
+    println!("\n--- Kubo 与内存后端对比 ---");
+    for (label, a, b) in [
+        ("图片", &from_kubo.image_cid, &from_memory.image_cid),
+        ("元数据", &from_kubo.metadata_cid, &from_memory.metadata_cid),
+    ] {
+        if a != b {
+            return Err(anyhow!("{} CID 不一致: Kubo {} / 内存 {}", label, a, b));
+        }
+        println!("✅ {} CID 一致: {}", label, a);
+    }
+
+    drop(harness);
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
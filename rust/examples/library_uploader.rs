@@ -1,17 +1,20 @@
 // examples/library_uploader.rs
 
 // 从我们自己的库中导入共享的结构体和函数
+use rust::backend::ApiEndpoint;
 use rust::{Attribute, NftMetadata, copy_directory};
 
 use anyhow::{Result, anyhow};
 use chrono::Utc;
 use ipfs_api_backend_hyper::{IpfsApi, IpfsClient, TryFromUri};
+use std::env;
 use std::fs::{self, File};
 use std::io::{Cursor, Write};
 use std::path::{Path, PathBuf};
 
 const USE_JSON_SUFFIX: bool = false;
-const IPFS_API_URL: &str = "http://localhost:5001";
+// 未设置环境变量 IPFS_API 时连接的节点
+const DEFAULT_IPFS_API: &str = "http://localhost:5001";
 
 // --- 核心上传函数 ---
 
@@ -75,10 +78,12 @@ async fn process_single_nft(client: &IpfsClient, image_path: &Path) -> Result<()
         name: image_name_without_ext.to_string(),
         description: format!("这是一个为图片 {} 动态生成的元数据。", image_filename),
         image: format!("ipfs://{}", image_cid),
+        animation_url: None,
         attributes: vec![Attribute {
             trait_type: "类型".to_string(),
             value: serde_json::Value::String("单件艺术品".to_string()),
         }],
+        extra: serde_json::Map::new(),
     };
 
     let metadata_cid = upload_json_str_to_ipfs(client, &metadata).await?;
@@ -139,10 +144,12 @@ async fn process_batch_collection(client: &IpfsClient, images_input_dir: &Path)
             name: format!("MetaCore #{}", token_id),
             description: "MetaCore 集合中的一个独特成员。".to_string(),
             image: format!("ipfs://{}/{}", images_folder_cid, image_filename),
+            animation_url: None,
             attributes: vec![Attribute {
                 trait_type: "ID".to_string(),
                 value: token_id.into(),
             }],
+            extra: serde_json::Map::new(),
         };
         let file_name = if USE_JSON_SUFFIX {
             format!("{}.json", token_id_str)
@@ -169,8 +176,15 @@ async fn process_batch_collection(client: &IpfsClient, images_input_dir: &Path)
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let client = IpfsClient::from_multiaddr_str(IPFS_API_URL)
-        .map_err(|e| anyhow!("创建 IPFS 客户端失败: {}", e))?;
+    // IPFS_API 的写法与命令行的 --api 相同 (URL 或 multiaddr)
+    let api = env::var("IPFS_API").unwrap_or_else(|_| DEFAULT_IPFS_API.to_string());
+    let url = match ApiEndpoint::parse(&api)? {
+        ApiEndpoint::Http(url) => url,
+        ApiEndpoint::Unix(path) => {
+            return Err(anyhow!("❌ 本示例不支持 unix socket: {:?}", path));
+        }
+    };
+    let client = IpfsClient::from_str(&url).map_err(|e| anyhow!("创建 IPFS 客户端失败: {}", e))?;
 
     if client.version().await.is_err() {
         eprintln!("❌ 连接 IPFS 节点失败。请确保 ipfs daemon 正在运行。");
@@ -0,0 +1,48 @@
+// examples/memory_backend.rs
+
+// 不需要 IPFS 节点：用内存中的后端跑完整的批量工作流，再从同一个后端读回元数据
+use rust::backend::{IpfsBackend, MemoryBackend};
+use rust::demo::generate_demo_images;
+use rust::workflow::{BatchOptions, process_batch_collection};
+
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs;
+
+fn main() -> Result<()> {
+    let root = env::temp_dir().join(format!("memory-backend-example-{}", std::process::id()));
+    let images_dir = root.join("images");
+    generate_demo_images(&images_dir, 3)?;
+
+    // clone 出的副本共享同一份块，工作流结束后仍然可以读取
+    let backend = MemoryBackend::new();
+    let manifest = process_batch_collection(
+        &backend,
+        &images_dir,
+        &root.join("output"),
+        &BatchOptions::default(),
+    )?;
+
+    println!("\n--- 从内存后端读回 ---");
+    for entry in backend.ls(&manifest.metadata_cid)? {
+        let json = backend.cat(&format!("{}/{}", manifest.metadata_cid, entry.name))?;
+        let metadata: serde_json::Value = serde_json::from_slice(&json)?;
+        let image = metadata["image"]
+            .as_str()
+            .and_then(|uri| uri.strip_prefix("ipfs://"))
+            .ok_or_else(|| anyhow!("元数据 {} 中没有 ipfs:// 图片链接", entry.name))?;
+        let image_size = backend.cat(image)?.len();
+        println!(
+            "📄 {} -> {} ({} 字节)",
+            entry.name, metadata["name"], image_size
+        );
+    }
+    println!(
+        "✅ 共 {} 个块，已固定: {}",
+        backend.block_count(),
+        backend.pins().join(", ")
+    );
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{keccak256, parse_address, to_hex_prefixed};
+
+pub const ALLOWLIST_FILE_NAME: &str = "allowlist_proofs.json";
+
+// 叶子的编码方式，合约中按同样的方式计算
+pub const LEAF_ENCODING: &str = "keccak256(abi.encodePacked(address))";
+
+// ✅ 读取地址 CSV：每行第一列是地址，可以有表头、空行和 # 注释，重复的地址只保留一个
+pub fn parse_allowlist_csv(content: &str) -> Result<Vec<[u8; 20]>> {
+    let mut seen = BTreeSet::new();
+    let mut addresses = Vec::new();
+    // 表头只可能是第一行有内容的行，前面可以有空行和注释
+    let mut first = true;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = line
+            .split(',')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches('"');
+        if std::mem::take(&mut first) && !field.starts_with("0x") {
+            continue;
+        }
+        let address = parse_address(field)
+            .map_err(|_| anyhow!("❌ 第 {} 行不是有效的地址: {}", index + 1, field))?;
+        if seen.insert(address) {
+            addresses.push(address);
+        }
+    }
+    if addresses.is_empty() {
+        return Err(anyhow!("❌ 地址列表为空"));
+    }
+    Ok(addresses)
+}
+
+// 两个节点按大小排序后拼接再哈希，与 OpenZeppelin 的 MerkleProof.verify 一致
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(low);
+    data[32..].copy_from_slice(high);
+    keccak256(&data)
+}
+
+pub fn leaf_hash(address: &[u8; 20]) -> [u8; 32] {
+    keccak256(address)
+}
+
+// ✅ keccak256 Merkle 树；叶子按哈希排序，奇数个节点时最后一个直接进入上一层
+pub struct MerkleTree {
+    // 第 0 层是叶子，最后一层只有根
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(addresses: &[[u8; 20]]) -> Self {
+        let mut leaves: Vec<[u8; 32]> = addresses.iter().map(leaf_hash).collect();
+        leaves.sort();
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers
+                .last()
+                .map(|layer| {
+                    layer
+                        .chunks(2)
+                        .map(|pair| match pair {
+                            [a, b] => hash_pair(a, b),
+                            [a] => *a,
+                            _ => unreachable!(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // 叶子的证明路径，叶子不在树中时返回 None
+    pub fn proof(&self, leaf: &[u8; 32]) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.layers.first()?.binary_search(leaf).ok()?;
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+pub fn verify_proof(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(*leaf, |hash, sibling| hash_pair(&hash, sibling))
+        == *root
+}
+
+// ✅ 写入 allowlist_proofs.json 的内容：地址 (小写) -> 证明
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AllowlistProofs {
+    pub root: String,
+    pub leaf_encoding: String,
+    pub count: usize,
+    pub proofs: BTreeMap<String, Vec<String>>,
+}
+
+impl AllowlistProofs {
+    pub fn build(addresses: &[[u8; 20]]) -> Result<Self> {
+        let tree = MerkleTree::new(addresses);
+        let root = tree.root();
+        let mut proofs = BTreeMap::new();
+        for address in addresses {
+            let leaf = leaf_hash(address);
+            let proof = tree
+                .proof(&leaf)
+                .filter(|proof| verify_proof(&leaf, proof, &root))
+                .ok_or_else(|| anyhow!("❌ 地址 {} 的证明校验失败", to_hex_prefixed(address)))?;
+            proofs.insert(
+                to_hex_prefixed(address),
+                proof.iter().map(|hash| to_hex_prefixed(hash)).collect(),
+            );
+        }
+        Ok(Self {
+            root: to_hex_prefixed(&root),
+            leaf_encoding: LEAF_ENCODING.to_string(),
+            count: addresses.len(),
+            proofs,
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(ALLOWLIST_FILE_NAME);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    // 目录中已有的证明文件，没有时返回 None
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(ALLOWLIST_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+}
+
+// 读取 CSV，生成证明并写入目录
+pub fn write_allowlist(csv: &Path, dir: &Path) -> Result<(AllowlistProofs, PathBuf)> {
+    let content =
+        fs::read_to_string(csv).map_err(|e| anyhow!("❌ 读取地址列表 {:?} 失败: {}", csv, e))?;
+    let proofs = AllowlistProofs::build(&parse_allowlist_csv(&content)?)?;
+    let path = proofs.save(dir)?;
+    Ok((proofs, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "0x1111111111111111111111111111111111111111";
+    const B: &str = "0x2222222222222222222222222222222222222222";
+
+    // 表头前面有注释和空行时同样跳过
+    #[test]
+    fn header_after_comments_and_blank_lines() {
+        let csv = format!(
+            "# 白名单\n\n  \naddress,note\n{},a\n\n# 第二批\n{},b\n",
+            A, B
+        );
+        let addresses = parse_allowlist_csv(&csv).unwrap();
+        assert_eq!(
+            addresses,
+            [parse_address(A).unwrap(), parse_address(B).unwrap()]
+        );
+    }
+
+    // 没有表头时第一行地址不会被跳过，重复的地址只保留一个
+    #[test]
+    fn without_header() {
+        let csv = format!("# 注释\n{}\n{}\n{}\n", A, B, A);
+        assert_eq!(parse_allowlist_csv(&csv).unwrap().len(), 2);
+    }
+
+    // 只有第一行有内容的行可以是表头，之后的非地址行报错并给出行号
+    #[test]
+    fn invalid_line_after_header() {
+        let csv = format!("address\n{}\nwallet\n", A);
+        let error = parse_allowlist_csv(&csv).unwrap_err().to_string();
+        assert!(error.contains("第 3 行"), "{}", error);
+    }
+}
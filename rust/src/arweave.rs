@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::Manifest;
+
+pub const DEFAULT_IRYS_NODE: &str = "https://uploader.irys.xyz";
+pub const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net";
+pub const DEFAULT_CURRENCY: &str = "ethereum";
+
+// ✅ config.toml 中的 [arweave] 段：除了 IPFS，再把图片和元数据镜像到 Arweave (通过 Irys / Bundlr 节点)
+//
+// [arweave]
+// private_key = "${ARWEAVE_ETH_KEY}"
+// currency = "base-eth"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ArweaveConfig {
+    // Irys (原 Bundlr) 上传节点
+    pub node: String,
+    // 在 Irys 上付款的币种，需要先用同一私钥充值 (如 ethereum、base-eth、matic)
+    pub currency: String,
+    // 以太坊系私钥 (十六进制)，签名上传的 data item；可以写成 "${ARWEAVE_ETH_KEY}"
+    pub private_key: String,
+    // 生成网关地址使用的网关
+    pub gateway: String,
+    // 单件和批量流程完成后自动镜像
+    pub auto: bool,
+}
+
+impl Default for ArweaveConfig {
+    fn default() -> Self {
+        Self {
+            node: DEFAULT_IRYS_NODE.to_string(),
+            currency: DEFAULT_CURRENCY.to_string(),
+            private_key: String::new(),
+            gateway: DEFAULT_ARWEAVE_GATEWAY.to_string(),
+            auto: false,
+        }
+    }
+}
+
+impl ArweaveConfig {
+    // 检查 [arweave] 中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.private_key.is_empty() {
+            problems.push("需要 private_key (可以写成 \"${ARWEAVE_ETH_KEY}\")".to_string());
+        }
+        for (name, url) in [("node", &self.node), ("gateway", &self.gateway)] {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                problems.push(format!("{} 应以 http:// 或 https:// 开头: {:?}", name, url));
+            }
+        }
+        if self.currency.is_empty() {
+            problems.push("需要付款币种 currency".to_string());
+        }
+        problems
+    }
+
+    // ar://<id> 转换为网关地址
+    pub fn gateway_url(&self, uri: &str) -> String {
+        match uri.strip_prefix("ar://") {
+            Some(id) => format!("{}/{}", self.gateway.trim_end_matches('/'), id),
+            None => uri.to_string(),
+        }
+    }
+}
+
+// 一个 token 在 Arweave 上的镜像：与 IPFS 上的内容相同，缺少的部分下次镜像时补上
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArweaveUris {
+    // image_file 的 ar://<交易 ID>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_uri: Option<String>,
+    // 元数据文件的 ar://<交易 ID>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_uri: Option<String>,
+}
+
+pub fn ar_uri(id: &str) -> String {
+    format!("ar://{}", id)
+}
+
+// ✅ 去掉元数据的 Arweave 镜像记录 (元数据改写后镜像不再对应)，图片的镜像保留；返回去掉的数量
+pub fn clear_metadata_mirrors(manifest: &mut Manifest) -> usize {
+    manifest
+        .tokens
+        .iter_mut()
+        .filter_map(|token| token.arweave.as_mut()?.metadata_uri.take())
+        .count()
+}
@@ -0,0 +1,350 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use super::http::{parse_ls, parse_providers};
+use super::{DirEntry, IpfsBackend};
+use crate::compat::{NodeCapabilities, REQUIRED, adapt_add_config, warn_if_outdated};
+use crate::exit_code::ExitCode;
+use crate::filter::IGNORE_FILE;
+use crate::ipld::DagCodec;
+use crate::timeout::{Operation, TimeoutConfig, TimeoutError};
+use crate::unixfs::{ADD_OPTIONS, AddConfig};
+
+// 通过本地 `ipfs` 命令行上传 (使用 std::process::Command)
+#[derive(Debug, Clone)]
+pub struct CliBackend {
+    pub binary: String,
+    // 只输出根 CID 的参数；老版本节点没有 -Q 时退回 -q 并取最后一行
+    quiet_flag: &'static str,
+    timeouts: TimeoutConfig,
+    add: AddConfig,
+    // 启动检查时读到的节点版本，写入清单
+    node_version: Option<String>,
+}
+
+impl Default for CliBackend {
+    fn default() -> Self {
+        Self {
+            binary: "ipfs".to_string(),
+            quiet_flag: "-Q",
+            timeouts: TimeoutConfig::default(),
+            add: AddConfig::default(),
+            node_version: None,
+        }
+    }
+}
+
+// 在后台线程中读完子进程的输出，避免管道写满后子进程阻塞
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+impl CliBackend {
+    pub fn with_timeouts(mut self, timeouts: TimeoutConfig) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    // [add] 中的高级导入参数
+    pub fn with_add_config(mut self, add: AddConfig) -> Self {
+        self.add = add;
+        self
+    }
+
+    // ✅ 运行 ipfs 子命令并收集输出；超过该操作的超时后结束子进程，返回 TimeoutError
+    fn output(&self, args: &[&str], stdin: Option<&[u8]>, operation: Operation) -> Result<Output> {
+        trace!("🔗 {} {}", self.binary, args.join(" "));
+        let mut child = Command::new(&self.binary)
+            .args(args)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let writer = match (child.stdin.take(), stdin) {
+            (Some(mut pipe), Some(data)) => {
+                let data = data.to_vec();
+                Some(thread::spawn(move || pipe.write_all(&data)))
+            }
+            _ => None,
+        };
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let limit = self.timeouts.get(operation);
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if let Some(limit) = limit
+                && started.elapsed() > limit
+            {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(TimeoutError {
+                    operation,
+                    limit,
+                    target: format!("`{} {}`", self.binary, args.join(" ")),
+                }
+                .into());
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+        if let Some(writer) = writer {
+            writer
+                .join()
+                .map_err(|_| anyhow!("❌ 写入 ipfs 标准输入的线程异常退出"))??;
+        }
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
+    fn run(&self, args: &[&str], operation: Operation) -> Result<String> {
+        let output = self.output(args, None, operation)?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ 执行 `{} {}` 失败: {}",
+                self.binary,
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+// 固定的导入参数 (如 --chunker=size-262144) 加上 [add] 中开启的参数
+fn add_options(extra: &[(&str, String)]) -> Vec<String> {
+    ADD_OPTIONS
+        .iter()
+        .map(|(key, value)| (*key, *value))
+        .chain(extra.iter().map(|(key, value)| (*key, value.as_str())))
+        .map(|(key, value)| format!("--{}={}", key, value))
+        .collect()
+}
+
+// `-q` 会逐行输出每个文件的 CID，根 CID 在最后一行
+fn parse_root_cid(stdout: Vec<u8>) -> Result<String> {
+    let stdout = String::from_utf8(stdout)?;
+    stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("❌ 节点没有返回 CID"))
+}
+
+impl IpfsBackend for CliBackend {
+    fn name(&self) -> &str {
+        "cli"
+    }
+
+    // 启动时检查节点版本和支持的命令，必要时调整参数，避免批量处理到一半才失败
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        // 前置检查：确认 IPFS 节点正在运行
+        let status = self.output(&["id"], None, Operation::Connect)?.status;
+        if !status.success() {
+            return Err(ExitCode::NodeUnreachable.wrap(anyhow!(
+                "❌ 连接 IPFS 节点失败。请确保你的 IPFS 节点正在运行 (命令: ipfs daemon)，或加上 --autostart-daemon 自动启动。"
+            )));
+        }
+
+        let version = self.run(&["version", "-n"], Operation::Request)?;
+        let commands = self.run(&["commands", "--enc=json"], Operation::Request)?;
+        let capabilities = NodeCapabilities::from_commands_json(&version, &commands)?;
+        warn_if_outdated(&capabilities.version);
+        self.node_version = Some(capabilities.version.clone());
+        capabilities.ensure(REQUIRED)?;
+        adapt_add_config(&capabilities, &mut self.add);
+
+        if !capabilities.supports("add", Some("quieter"))
+            && capabilities.supports("add", Some("quiet"))
+        {
+            println!("⚠️  节点不支持 `ipfs add -Q`，改用 `-q` 并读取最后一行作为根 CID");
+            self.quiet_flag = "-q";
+        }
+        Ok(Some(capabilities))
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        if !target_path.exists() {
+            return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+        }
+
+        let path_str = target_path
+            .to_str()
+            .ok_or_else(|| anyhow!("无效的文件路径"))?;
+        // 文件夹中有 .ipfsignore 时交给节点按同样的规则跳过文件
+        let ignore_file = target_path.join(IGNORE_FILE);
+        let ignore_args: Vec<String> = if target_path.is_dir() && ignore_file.is_file() {
+            vec![
+                "--ignore-rules-path".to_string(),
+                ignore_file.to_string_lossy().into_owned(),
+            ]
+        } else {
+            Vec::new()
+        };
+        println!(
+            "\n--- 正在执行上传命令: {} add -r {} --cid-version 1 {}{} ---",
+            self.binary,
+            self.quiet_flag,
+            ignore_args
+                .iter()
+                .map(|arg| format!("{} ", arg))
+                .collect::<String>(),
+            path_str
+        );
+
+        let options = add_options(&self.add.path_options(target_path.is_file()));
+        let mut args = vec!["add", "-r", self.quiet_flag, "--cid-version", "1"];
+        args.extend(options.iter().map(String::as_str));
+        args.extend(ignore_args.iter().map(String::as_str));
+        args.push(path_str);
+        let output = self.output(&args, None, Operation::Add)?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ 上传失败: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let cid = parse_root_cid(output.stdout)?;
+        println!("✅ 上传成功!");
+        println!(
+            "   - 名称: {}",
+            target_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or(path_str)
+        );
+        println!("   - CID: {}", cid);
+        Ok(cid)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let options = add_options(&self.add.bytes_options());
+        let mut args = vec!["add", self.quiet_flag, "--cid-version", "1"];
+        args.extend(options.iter().map(String::as_str));
+        // 数据通过子进程的标准输入传入
+        let output = self.output(&args, Some(data), Operation::Add)?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ 上传失败: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        parse_root_cid(output.stdout)
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        let store_codec = format!("--store-codec={}", codec);
+        let args = [
+            "dag",
+            "put",
+            "--input-codec=dag-json",
+            store_codec.as_str(),
+            "--hash=sha2-256",
+            "--pin=true",
+        ];
+        let output = self.output(&args, Some(&serde_json::to_vec(node)?), Operation::Add)?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ 发布 IPLD 节点失败: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        parse_root_cid(output.stdout)
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.add.clone()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.node_version.clone()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        let output = self.output(
+            &["pin", "ls", "--type=recursive", "-q", cid],
+            None,
+            Operation::Request,
+        )?;
+        if output.status.success() {
+            return Ok(true);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not pinned") {
+            return Ok(false);
+        }
+        Err(anyhow!("❌ 查询 pin 状态失败: {}", stderr))
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        self.run(&["pin", "add", "--recursive", cid], Operation::Pin)?;
+        Ok(())
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.run(&["pin", "rm", "--recursive", cid], Operation::Pin)?;
+        Ok(())
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        parse_ls(&self.run(
+            &["ls", "--resolve-type", "--size", "--enc=json", path],
+            Operation::Request,
+        )?)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        let output = self.output(&["cat", path], None, Operation::Request)?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ 读取 {} 失败: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(output.stdout)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        let mut args = vec!["routing", "provide"];
+        if recursive {
+            args.push("--recursive");
+        }
+        args.push(cid);
+        self.run(&args, Operation::Pin)?;
+        Ok(())
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        let limit = format!("--num-providers={}", limit);
+        parse_providers(&self.run(
+            &["routing", "findprovs", "--enc=json", &limit, cid],
+            Operation::Request,
+        )?)
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use reqwest::blocking::{Client, RequestBuilder, Response, multipart};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::IpfsBackend;
+use super::http::{bytes_part, directory_form, encode_file_name, file_part};
+use crate::compat::NodeCapabilities;
+use crate::exit_code::ExitCode;
+use crate::throttle::Throttle;
+use crate::timeout::{Operation, TimeoutConfig};
+use crate::unixfs::ADD_OPTIONS;
+
+// ✅ config.toml 中的 [cluster] 段，集群地址通过 --cluster 指定
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClusterConfig {
+    // 最少 / 最多复制到多少个 peer；-1 表示所有 peer，不设置时使用集群的默认值
+    pub replication_min: Option<i32>,
+    pub replication_max: Option<i32>,
+    // REST API 开启 basic_auth_credentials 时使用，格式 "用户名:密码"
+    pub basic_auth: Option<String>,
+    // 按操作覆盖 [timeouts]
+    pub timeouts: TimeoutConfig,
+}
+
+// 某个 peer 上的 pin 状态
+#[derive(Debug, Clone)]
+pub struct PeerPinStatus {
+    pub peer_id: String,
+    pub peer_name: String,
+    // pinned、pinning、pin_queued、pin_error、remote 等
+    pub status: String,
+    pub error: String,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct PeerId {
+    #[serde(default)]
+    peername: String,
+    #[serde(default)]
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct AddedOutput {
+    cid: Value,
+}
+
+#[derive(Deserialize)]
+struct GlobalPinInfo {
+    #[serde(default)]
+    peer_map: BTreeMap<String, PeerInfo>,
+}
+
+#[derive(Deserialize)]
+struct PeerInfo {
+    #[serde(default)]
+    peername: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    error: String,
+}
+
+// 新版本返回 "bafy..."，旧版本返回 {"/": "bafy..."}
+fn cid_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(cid) => Some(cid.clone()),
+        Value::Object(map) => map.get("/").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+// 通过 ipfs-cluster REST API (默认端口 9094) 上传并按复制因子固定到多个 peer
+#[derive(Debug, Clone)]
+pub struct ClusterBackend {
+    pub api: String,
+    pub config: ClusterConfig,
+    client: Client,
+    throttle: Throttle,
+}
+
+impl ClusterBackend {
+    pub fn new(api: &str, config: ClusterConfig) -> Result<Self> {
+        let api = api.trim().trim_end_matches('/');
+        if !api.starts_with("http://") && !api.starts_with("https://") {
+            return Err(anyhow!(
+                "❌ ipfs-cluster API 地址需要以 http(s):// 开头: {}",
+                api
+            ));
+        }
+        if let Some(auth) = &config.basic_auth
+            && !auth.contains(':')
+        {
+            return Err(anyhow!("❌ cluster.basic_auth 格式应为 \"用户名:密码\""));
+        }
+        let mut builder = Client::builder().timeout(None);
+        if let Some(limit) = config.timeouts.get(Operation::Connect) {
+            builder = builder.connect_timeout(limit);
+        }
+        Ok(Self {
+            api: api.to_string(),
+            config,
+            client: builder.build()?,
+            throttle: Throttle::default(),
+        })
+    }
+
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    fn request(&self, builder: RequestBuilder, operation: Operation) -> Result<Response> {
+        // 带流式请求体的请求无法复制，只在 -vv 时输出能复制的请求
+        if let Some(request) = builder.try_clone().and_then(|builder| builder.build().ok()) {
+            trace!("🔗 {} {}", request.method(), request.url());
+        }
+        let builder = self.config.timeouts.apply(builder, operation);
+        let builder = match self
+            .config
+            .basic_auth
+            .as_deref()
+            .and_then(|auth| auth.split_once(':'))
+        {
+            Some((user, password)) => builder.basic_auth(user, Some(password)),
+            None => builder,
+        };
+        let response = builder
+            .send()
+            .map_err(|e| self.config.timeouts.check(e, operation, &self.api))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        let message = serde_json::from_str::<ApiError>(&body)
+            .map(|e| e.message)
+            .unwrap_or(body);
+        Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+            "❌ ipfs-cluster 请求失败 ({}): {}",
+            status,
+            message
+        )))
+    }
+
+    fn replication_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(min) = self.config.replication_min {
+            query.push(("replication-min", min.to_string()));
+        }
+        if let Some(max) = self.config.replication_max {
+            query.push(("replication-max", max.to_string()));
+        }
+        query
+    }
+
+    fn add_form(&self, form: multipart::Form) -> Result<String> {
+        let mut query = self.replication_query();
+        query.push(("cid-version", "1".to_string()));
+        query.extend(ADD_OPTIONS.map(|(key, value)| (key, value.to_string())));
+        query.push(("stream-channels", "false".to_string()));
+        let url = format!("{}/add", self.api);
+        let body = self
+            .request(
+                self.client.post(url).query(&query).multipart(form),
+                Operation::Add,
+            )?
+            .text()
+            .map_err(|e| self.config.timeouts.check(e, Operation::Add, &self.api))?;
+        // stream-channels=false 时返回一个数组，根目录在最后；兼容逐行输出的旧版本
+        let outputs: Vec<AddedOutput> = serde_json::from_str(&body).unwrap_or_else(|_| {
+            body.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        });
+        outputs
+            .iter()
+            .filter_map(|output| cid_string(&output.cid))
+            .next_back()
+            .ok_or_else(|| anyhow!("❌ ipfs-cluster 没有返回 CID: {}", body))
+    }
+
+    // ✅ 查询一个 CID 在每个集群 peer 上的 pin 状态
+    pub fn peer_status(&self, cid: &str) -> Result<Vec<PeerPinStatus>> {
+        let url = format!("{}/pins/{}", self.api, cid);
+        let info: GlobalPinInfo = self
+            .request(self.client.get(url), Operation::Request)?
+            .json()?;
+        Ok(info
+            .peer_map
+            .into_iter()
+            .map(|(peer_id, peer)| PeerPinStatus {
+                peer_id,
+                peer_name: peer.peername,
+                status: peer.status,
+                error: peer.error,
+            })
+            .collect())
+    }
+
+    // 打印集合根 CID 的逐 peer 状态，复制数不足 replication_min 时给出警告
+    pub fn report_roots(&self, roots: &[(&str, &str)]) -> Result<()> {
+        println!("\n--- ipfs-cluster pin 状态 ---");
+        for (label, cid) in roots.iter().filter(|(_, cid)| !cid.is_empty()) {
+            let peers = self.peer_status(cid)?;
+            let pinned = peers.iter().filter(|p| p.status == "pinned").count();
+            println!(
+                "📌 {} {} ({}/{} 个 peer 已固定)",
+                label,
+                cid,
+                pinned,
+                peers.len()
+            );
+            for peer in &peers {
+                let name = if peer.peer_name.is_empty() {
+                    &peer.peer_id
+                } else {
+                    &peer.peer_name
+                };
+                let icon = match peer.status.as_str() {
+                    "pinned" => "✅",
+                    "pinning" | "pin_queued" => "⏳",
+                    "remote" => "➖",
+                    _ => "❌",
+                };
+                if peer.error.is_empty() {
+                    println!("   {} {}: {}", icon, name, peer.status);
+                } else {
+                    println!("   {} {}: {} ({})", icon, name, peer.status, peer.error);
+                }
+            }
+            if let Some(min) = self.config.replication_min
+                && min > 0
+                && pinned < min as usize
+            {
+                println!(
+                    "⚠️  {} 目前只有 {} 个 peer 完成固定，低于 replication_min = {}，其余 peer 可能仍在同步",
+                    label, pinned, min
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IpfsBackend for ClusterBackend {
+    fn name(&self) -> &str {
+        "cluster"
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        let url = format!("{}/id", self.api);
+        let id: PeerId = self
+            .request(self.client.get(url), Operation::Request)
+            .map_err(|e| {
+                ExitCode::connect_failure(&e).wrap(anyhow!(
+                    "❌ 无法连接 ipfs-cluster {}: {:#}",
+                    self.api,
+                    e
+                ))
+            })?
+            .json()?;
+        println!(
+            "✅ 已连接 ipfs-cluster peer {} (版本: {})",
+            id.peername, id.version
+        );
+        Ok(None)
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        if !target_path.exists() {
+            return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+        }
+        println!("\n--- 正在上传(ipfs-cluster): {:?} ---", target_path);
+        let connection = self.throttle.connection();
+        let form = if target_path.is_dir() {
+            directory_form(target_path, &connection, false)?
+        } else {
+            let name = target_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| anyhow!("无效的文件路径"))?;
+            multipart::Form::new().part(
+                "file",
+                file_part(target_path, &connection)?.file_name(encode_file_name(name)),
+            )
+        };
+        let cid = self.add_form(form)?;
+        println!("✅ 上传成功! CID: {}", cid);
+        Ok(cid)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let part = bytes_part(data, &self.throttle.connection()).file_name("data");
+        self.add_form(multipart::Form::new().part("file", part))
+    }
+
+    // 至少一个 peer 已固定即视为已固定，逐 peer 的详情见 peer_status
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        Ok(self
+            .peer_status(cid)?
+            .iter()
+            .any(|peer| peer.status == "pinned"))
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        let url = format!("{}/pins/{}", self.api, cid);
+        self.request(
+            self.client.post(url).query(&self.replication_query()),
+            Operation::Pin,
+        )?;
+        Ok(())
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        let url = format!("{}/pins/{}", self.api, cid);
+        self.request(self.client.delete(url), Operation::Pin)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::exit_code::ExitCode;
+use crate::ipld::DagCodec;
+use crate::unixfs::AddConfig;
+
+// ✅ config.toml 中的 [failover] 段：按顺序尝试的节点列表
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FailoverConfig {
+    // --api 的写法，或 [[api_endpoints]] 中的名称
+    pub nodes: Vec<String>,
+    // 上传成功后同时在其它节点上 pin
+    pub mirror_pins: bool,
+    // 单个请求的超时时间 (秒)，超时即切换到下一个节点；[[api_endpoints]] 中的 timeout_secs 优先
+    pub timeout_secs: Option<u64>,
+}
+
+// 按顺序使用多个节点：当前节点上传失败或超时时切换到下一个，之后一直使用新节点
+pub struct FailoverBackend {
+    nodes: Vec<(String, Box<dyn IpfsBackend>)>,
+    mirror_pins: bool,
+    active: AtomicUsize,
+    // CID -> 实际完成上传的节点
+    served_by: Mutex<BTreeMap<String, String>>,
+}
+
+impl FailoverBackend {
+    pub fn new(nodes: Vec<(String, Box<dyn IpfsBackend>)>, mirror_pins: bool) -> Result<Self> {
+        if nodes.is_empty() {
+            return Err(anyhow!("❌ [failover] 中至少需要配置一个节点"));
+        }
+        Ok(Self {
+            nodes,
+            mirror_pins,
+            active: AtomicUsize::new(0),
+            served_by: Mutex::default(),
+        })
+    }
+
+    // 从当前节点开始依次尝试，直到有一个成功
+    fn with_failover<T>(
+        &self,
+        operation: &str,
+        f: impl Fn(&dyn IpfsBackend) -> Result<T>,
+    ) -> Result<(usize, T)> {
+        let start = self.active.load(Ordering::SeqCst);
+        let mut errors = Vec::new();
+        for offset in 0..self.nodes.len() {
+            let index = (start + offset) % self.nodes.len();
+            let (label, node) = &self.nodes[index];
+            match f(node.as_ref()) {
+                Ok(value) => {
+                    if index != start {
+                        println!("🔀 已切换到节点 {}", label);
+                        self.active.store(index, Ordering::SeqCst);
+                    }
+                    return Ok((index, value));
+                }
+                Err(e) => {
+                    eprintln!("⚠️  节点 {} {} 失败: {:#}", label, operation, e);
+                    errors.push(format!("{}: {:#}", label, e));
+                }
+            }
+        }
+        Err(anyhow!(
+            "❌ 所有节点 {} 均失败:\n  {}",
+            operation,
+            errors.join("\n  ")
+        ))
+    }
+
+    // 在其它节点上也 pin 一份，失败只打印警告
+    fn mirror(&self, served: usize, cid: &str) {
+        if !self.mirror_pins {
+            return;
+        }
+        for (index, (label, node)) in self.nodes.iter().enumerate() {
+            if index == served {
+                continue;
+            }
+            match node.pin(cid) {
+                Ok(()) => println!("🪞 已在节点 {} 上 pin {}", label, cid),
+                Err(e) => eprintln!("⚠️  节点 {} 镜像 pin {} 失败: {:#}", label, cid, e),
+            }
+        }
+    }
+
+    fn record(&self, served: usize, cid: &str) {
+        self.served_by
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(cid.to_string(), self.nodes[served].0.clone());
+        self.mirror(served, cid);
+    }
+}
+
+impl IpfsBackend for FailoverBackend {
+    fn name(&self) -> &str {
+        "failover"
+    }
+
+    // 跳过连接不上的节点，从第一个可用的节点开始
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        let mut first = None;
+        for (index, (label, node)) in self.nodes.iter_mut().enumerate() {
+            match node.check_compatibility() {
+                Ok(capabilities) => {
+                    println!("✅ 节点 {} 可用", label);
+                    if first.is_none() {
+                        first = Some((index, capabilities));
+                    }
+                }
+                Err(e) => eprintln!("⚠️  节点 {} 不可用: {:#}", label, e),
+            }
+        }
+        let (index, capabilities) = first.ok_or_else(|| {
+            ExitCode::NodeUnreachable.wrap(anyhow!("❌ [failover] 中的节点都无法连接"))
+        })?;
+        self.active.store(index, Ordering::SeqCst);
+        Ok(capabilities)
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        let (served, cid) = self.with_failover("上传", |node| node.add_path(target_path))?;
+        self.record(served, &cid);
+        Ok(cid)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let (served, cid) = self.with_failover("上传", |node| node.add_bytes(data))?;
+        self.record(served, &cid);
+        Ok(cid)
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        let (served, cid) = self.with_failover("发布 IPLD 节点", |node_backend| {
+            node_backend.dag_put(node, codec)
+        })?;
+        self.record(served, &cid);
+        Ok(cid)
+    }
+
+    // 所有节点使用同样的 [add] 参数
+    fn add_config(&self) -> AddConfig {
+        self.nodes
+            .first()
+            .map(|(_, node)| node.add_config())
+            .unwrap_or_default()
+    }
+
+    // 任意一个节点上已固定即可；所有节点都查询失败时才返回错误
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        let mut result = Err(anyhow!("❌ 没有可查询的节点"));
+        for (_, node) in &self.nodes {
+            match node.is_pinned(cid) {
+                Ok(true) => return Ok(true),
+                Ok(false) => result = Ok(false),
+                Err(e) if result.is_err() => result = Err(e),
+                Err(_) => {}
+            }
+        }
+        result
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        let (served, ()) = self.with_failover("pin", |node| node.pin(cid))?;
+        self.mirror(served, cid);
+        Ok(())
+    }
+
+    // 镜像固定时每个节点上都可能有，逐个取消；至少一个节点成功即可
+    fn unpin(&self, cid: &str) -> Result<()> {
+        let mut result = Err(anyhow!("❌ 没有可以取消 pin 的节点"));
+        for (_, node) in &self.nodes {
+            match node.unpin(cid) {
+                Ok(()) => result = Ok(()),
+                Err(e) if result.is_err() => result = Err(e),
+                Err(_) => {}
+            }
+        }
+        result
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        Ok(self.with_failover("读取", |node| node.ls(path))?.1)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        Ok(self.with_failover("读取", |node| node.cat(path))?.1)
+    }
+
+    // 镜像固定时每个节点都宣告自己；至少一个节点成功即可
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        let mut result = Err(anyhow!("❌ 没有可以宣告内容的节点"));
+        for (_, node) in &self.nodes {
+            match node.provide(cid, recursive) {
+                Ok(()) => result = Ok(()),
+                Err(e) if result.is_err() => result = Err(e),
+                Err(_) => {}
+            }
+        }
+        result
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        Ok(self
+            .with_failover("查找提供者", |node| node.find_providers(cid, limit))?
+            .1)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.served_by
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(cid)
+            .cloned()
+    }
+
+    // 当前使用的节点的版本
+    fn node_version(&self) -> Option<String> {
+        self.nodes
+            .get(self.active.load(Ordering::SeqCst))
+            .and_then(|(_, node)| node.node_version())
+    }
+}
@@ -0,0 +1,162 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::IpfsBackend;
+use super::s3::S3Client;
+use crate::car::write_car;
+use crate::compat::NodeCapabilities;
+use crate::hashing::to_hex;
+use crate::throttle::Throttle;
+use crate::timeout::TimeoutConfig;
+use crate::unixfs::ImportOptions;
+
+pub const FILEBASE_ENDPOINT: &str = "https://s3.filebase.com";
+
+// ✅ config.toml 中的 [filebase] 段，bucket 通过 --filebase 指定
+// 密钥也可以用环境变量 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY 提供 (优先于配置文件)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct FilebaseConfig {
+    pub endpoint: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    // 对象 key 的前缀，例如 "collections/"
+    pub prefix: String,
+    // 按操作覆盖 [timeouts]
+    pub timeouts: TimeoutConfig,
+}
+
+impl Default for FilebaseConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: FILEBASE_ENDPOINT.to_string(),
+            region: "us-east-1".to_string(),
+            access_key: String::new(),
+            secret_key: String::new(),
+            prefix: String::new(),
+            timeouts: TimeoutConfig::default(),
+        }
+    }
+}
+
+// 通过 Filebase 的 S3 兼容 API 上传到 IPFS bucket，CID 从对象元数据 x-amz-meta-cid 读取
+#[derive(Debug, Clone)]
+pub struct FilebaseBackend {
+    s3: S3Client,
+    prefix: String,
+}
+
+impl FilebaseBackend {
+    pub fn new(bucket: &str, config: &FilebaseConfig) -> Result<Self> {
+        let access_key = env::var("FILEBASE_ACCESS_KEY").unwrap_or(config.access_key.clone());
+        let secret_key = env::var("FILEBASE_SECRET_KEY").unwrap_or(config.secret_key.clone());
+        if access_key.is_empty() || secret_key.is_empty() {
+            return Err(anyhow!(
+                "❌ 缺少 Filebase 密钥，请设置 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY 或在 config.toml 的 [filebase] 中填写"
+            ));
+        }
+        Ok(Self {
+            s3: S3Client::new(
+                &config.endpoint,
+                &config.region,
+                bucket,
+                &access_key,
+                &secret_key,
+            )?
+            .with_timeouts(config.timeouts)?,
+            prefix: config.prefix.clone(),
+        })
+    }
+
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.s3 = self.s3.with_throttle(throttle);
+        self
+    }
+
+    // PUT 的响应头里通常已经带有 CID，没有时再 HEAD 一次
+    fn read_cid(&self, key: &str, headers: HeaderMap) -> Result<String> {
+        let headers = if headers.contains_key("x-amz-meta-cid") {
+            headers
+        } else {
+            self.s3.head(key)?
+        };
+        headers
+            .get("x-amz-meta-cid")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                anyhow!(
+                    "❌ Filebase 没有返回 {} 的 CID，请确认 bucket {} 是 IPFS 类型",
+                    key,
+                    self.s3.bucket
+                )
+            })
+    }
+
+    // 文件夹先在本地打包成 CAR，再让 Filebase 按 CAR 导入，保留目录结构
+    fn add_directory(&self, dir: &Path, name: &str) -> Result<String> {
+        let car_path = env::temp_dir().join(format!("{}-{}.car", name, std::process::id()));
+        let result = write_car(dir, &car_path, &ImportOptions::default()).and_then(|summary| {
+            let key = format!("{}{}.car", self.prefix, name);
+            let headers = self.s3.put_file(&key, &car_path, &[("import", "car")])?;
+            let cid = self.read_cid(&key, headers)?;
+            if cid != summary.root.to_string() {
+                println!(
+                    "⚠️  Filebase 返回的 CID {} 与本地计算的 {} 不一致",
+                    cid, summary.root
+                );
+            }
+            Ok(cid)
+        });
+        let _ = fs::remove_file(&car_path);
+        result
+    }
+}
+
+impl IpfsBackend for FilebaseBackend {
+    fn name(&self) -> &str {
+        "filebase"
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        println!(
+            "✅ 使用 Filebase bucket {} ({})",
+            self.s3.bucket, self.s3.endpoint
+        );
+        Ok(None)
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        if !target_path.exists() {
+            return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+        }
+        let name = target_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("无效的文件路径"))?;
+        println!("\n--- 正在上传(Filebase): {:?} ---", target_path);
+        let cid = if target_path.is_dir() {
+            self.add_directory(target_path, name)?
+        } else {
+            let key = format!("{}{}", self.prefix, name);
+            let headers = self.s3.put_file(&key, target_path, &[])?;
+            self.read_cid(&key, headers)?
+        };
+        println!("✅ 上传成功! CID: {}", cid);
+        Ok(cid)
+    }
+
+    // 内存数据用内容哈希作为 key，重复上传同样的内容不会产生新对象
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let key = format!("{}{}", self.prefix, to_hex(&Sha256::digest(data)));
+        let headers = self.s3.put_bytes(&key, data, &[])?;
+        self.read_cid(&key, headers)
+    }
+}
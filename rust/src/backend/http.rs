@@ -0,0 +1,1297 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Cursor;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use anyhow::{Result, anyhow};
+use reqwest::blocking::{Client, RequestBuilder, Response, multipart};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use super::{DirEntry, IpfsBackend};
+use crate::cancel;
+use crate::compat::{NodeCapabilities, REQUIRED, adapt_add_config, warn_if_outdated};
+use crate::estimate::format_bytes;
+use crate::exit_code::ExitCode;
+use crate::filter::FileFilter;
+use crate::ipld::DagCodec;
+use crate::throttle::{ConnectionThrottle, Throttle};
+use crate::timeout::{Operation, TimeoutConfig};
+use crate::unixfs::{
+    ADD_OPTIONS, AddConfig, Block, Cid, Link, directory_block, needs_sharding, symlink_block,
+};
+
+// ✅ Kubo RPC 的地址：普通 HTTP(S) 或 unix domain socket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiEndpoint {
+    Http(String),
+    Unix(PathBuf),
+}
+
+impl ApiEndpoint {
+    // 支持以下几种写法:
+    //   http://127.0.0.1:5001            https://ipfs.example.com
+    //   /ip4/127.0.0.1/tcp/5001          /dns/ipfs.example.com/tcp/443/https
+    //   /unix/var/run/kubo/api.sock      unix:///var/run/kubo/api.sock
+    //   127.0.0.1:5001
+    // 末尾多写的 /api/v0 会被去掉
+    pub fn parse(addr: &str) -> Result<Self> {
+        let addr = addr.trim();
+        if addr.is_empty() {
+            return Err(invalid_api(addr, "地址为空"));
+        }
+        if let Some(path) = addr.strip_prefix("unix://").or(addr.strip_prefix("unix:")) {
+            if path.is_empty() {
+                return Err(invalid_api(addr, "缺少 socket 文件路径"));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        if addr.starts_with('/') {
+            return parse_multiaddr(addr);
+        }
+        match addr.split_once("://") {
+            Some(("http" | "https", _)) => parse_url(addr),
+            Some((scheme, _)) => Err(invalid_api(
+                addr,
+                &format!(
+                    "不支持 {}://，Kubo RPC 只能通过 http:// 或 https:// 访问",
+                    scheme
+                ),
+            )),
+            None if addr.contains(':') => parse_url(&format!("http://{}", addr)),
+            None => Err(invalid_api(
+                addr,
+                "缺少端口 (Kubo RPC 默认端口是 5001，如 127.0.0.1:5001)，也不是 [[api_endpoints]] 中的名称",
+            )),
+        }
+    }
+}
+
+// 地址无效时列出支持的写法
+fn invalid_api(addr: &str, reason: &str) -> anyhow::Error {
+    anyhow!(
+        "❌ 无效的 IPFS API 地址 {:?}: {}\n   可以写成 http://127.0.0.1:5001、/ip4/127.0.0.1/tcp/5001、/dns/ipfs.example.com/tcp/443/https 或 unix:///var/run/kubo/api.sock",
+        addr,
+        reason
+    )
+}
+
+fn parse_url(addr: &str) -> Result<ApiEndpoint> {
+    let url = reqwest::Url::parse(addr).map_err(|e| invalid_api(addr, &e.to_string()))?;
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(invalid_api(addr, "缺少主机名"));
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(invalid_api(
+            addr,
+            "不要把用户名和密码写在地址里，请在 [[api_endpoints]] 中配置 basic_auth",
+        ));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid_api(addr, "地址中不能带 ? 或 # 部分"));
+    }
+    // Url 会省略协议的默认端口，按原样保留用户写的路径 (反向代理可能挂在子路径下)
+    let base = url.as_str().trim_end_matches('/');
+    let base = base.strip_suffix("/api/v0").unwrap_or(base);
+    Ok(ApiEndpoint::Http(base.to_string()))
+}
+
+fn parse_multiaddr(addr: &str) -> Result<ApiEndpoint> {
+    let parts: Vec<&str> = addr
+        .trim_start_matches('/')
+        .trim_end_matches('/')
+        .split('/')
+        .collect();
+    match parts.as_slice() {
+        ["unix", rest @ ..] if !rest.is_empty() => Ok(ApiEndpoint::Unix(PathBuf::from(format!(
+            "/{}",
+            rest.join("/")
+        )))),
+        [proto, host, "tcp", port, tail @ ..] => {
+            let scheme = match tail {
+                [] | ["http"] => "http",
+                ["https"] | ["tls", "http"] => "https",
+                _ => {
+                    return Err(invalid_api(
+                        addr,
+                        &format!(
+                            "不支持的后缀 /{} (可以是 /http、/https 或 /tls/http)",
+                            tail.join("/")
+                        ),
+                    ));
+                }
+            };
+            let host = match *proto {
+                "ip4" => {
+                    host.parse::<Ipv4Addr>().map_err(|_| {
+                        invalid_api(addr, &format!("{} 不是有效的 IPv4 地址", host))
+                    })?;
+                    host.to_string()
+                }
+                "ip6" => {
+                    host.parse::<Ipv6Addr>().map_err(|_| {
+                        invalid_api(addr, &format!("{} 不是有效的 IPv6 地址", host))
+                    })?;
+                    format!("[{}]", host)
+                }
+                "dns" | "dns4" | "dns6" => host.to_string(),
+                _ => {
+                    return Err(invalid_api(
+                        addr,
+                        &format!(
+                            "不支持的协议 /{} (可以是 /ip4、/ip6、/dns、/dns4、/dns6 或 /unix)",
+                            proto
+                        ),
+                    ));
+                }
+            };
+            let port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| invalid_api(addr, &format!("{} 不是有效的端口", port)))?;
+            Ok(ApiEndpoint::Http(format!("{}://{}:{}", scheme, host, port)))
+        }
+        _ => Err(invalid_api(
+            addr,
+            "multiaddr 应为 /<ip4|ip6|dns>/<主机>/tcp/<端口>[/https] 或 /unix/<路径>",
+        )),
+    }
+}
+
+// ✅ config.toml 中的 [[api_endpoints]]：远程 / 托管节点 (如 Infura) 的认证信息
+// --api 可以填写 name，也可以填写与 url 相同的地址
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ApiEndpointConfig {
+    pub name: Option<String>,
+    pub url: String,
+    // Basic 认证，格式 "用户名:密码" (Infura 为 "项目 ID:项目密钥")
+    pub basic_auth: Option<String>,
+    // 每个请求都会附带的自定义头
+    pub headers: BTreeMap<String, String>,
+    // 单个请求的超时时间 (秒)，同时限制上传、固定和其它请求；timeouts 中的设置优先
+    pub timeout_secs: Option<u64>,
+    // 按操作覆盖 [timeouts]
+    pub timeouts: TimeoutConfig,
+}
+
+// 按名称或地址找到 --api 对应的配置
+pub fn find_endpoint_config<'a>(
+    api: &str,
+    endpoints: &'a [ApiEndpointConfig],
+) -> Option<&'a ApiEndpointConfig> {
+    let target = ApiEndpoint::parse(api).ok();
+    endpoints.iter().find(|endpoint| {
+        endpoint.name.as_deref() == Some(api)
+            || (target.is_some() && ApiEndpoint::parse(&endpoint.url).ok() == target)
+    })
+}
+
+// Kubo 返回的错误结构
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AddEvent {
+    hash: Option<String>,
+    // 子 DAG 所有块的累计大小 (字符串)，拼目录节点时作为链接的 tsize
+    size: Option<String>,
+}
+
+// `block put` 的输出
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlockPutOutput {
+    key: String,
+}
+
+// `dag put` 的输出: {"Cid": {"/": "<cid>"}}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DagPutOutput {
+    cid: DagLink,
+}
+
+#[derive(Deserialize)]
+struct DagLink {
+    #[serde(rename = "/")]
+    link: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct VersionInfo {
+    version: String,
+}
+
+// 通过 Kubo HTTP RPC (/api/v0) 上传，可以连接远程节点或 unix socket
+#[derive(Debug, Clone)]
+pub struct HttpBackend {
+    pub endpoint: ApiEndpoint,
+    client: Client,
+    base_url: String,
+    basic_auth: Option<(String, String)>,
+    throttle: Throttle,
+    timeouts: TimeoutConfig,
+    add: AddConfig,
+    // 启动检查时读到的节点版本，写入清单
+    node_version: Option<String>,
+    // 节点支持 `block put --cid-codec` 时，文件夹逐个文件并发上传，目录节点在本地拼出
+    block_put: bool,
+}
+
+// RPC 命令对应的超时类别
+fn operation_of(command: &str) -> Operation {
+    match command {
+        "add" | "dag/put" | "block/put" => Operation::Add,
+        "pin/add" | "pin/rm" | "pin/update" | "routing/provide" => Operation::Pin,
+        _ => Operation::Request,
+    }
+}
+
+impl HttpBackend {
+    pub fn new(api: &str) -> Result<Self> {
+        Self::connect(api, &[])
+    }
+
+    // 使用 [[api_endpoints]] 中匹配的认证信息和自定义头连接
+    pub fn connect(api: &str, endpoints: &[ApiEndpointConfig]) -> Result<Self> {
+        Self::connect_with_timeouts(api, endpoints, &TimeoutConfig::default())
+    }
+
+    // 同上，并指定各操作的超时；[[api_endpoints]] 中的 timeouts、timeout_secs 优先
+    pub fn connect_with_timeouts(
+        api: &str,
+        endpoints: &[ApiEndpointConfig],
+        timeouts: &TimeoutConfig,
+    ) -> Result<Self> {
+        let config = find_endpoint_config(api, endpoints);
+        let endpoint = ApiEndpoint::parse(config.map_or(api, |c| c.url.as_str()))?;
+        let mut headers = HeaderMap::new();
+        let mut basic_auth = None;
+        if let Some(config) = config {
+            for (name, value) in &config.headers {
+                headers.insert(
+                    HeaderName::from_bytes(name.as_bytes())
+                        .map_err(|e| anyhow!("❌ 无效的请求头名称 {}: {}", name, e))?,
+                    HeaderValue::from_str(value)
+                        .map_err(|e| anyhow!("❌ 请求头 {} 的值无效: {}", name, e))?,
+                );
+            }
+            if let Some(auth) = &config.basic_auth {
+                let (user, password) = auth
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("❌ basic_auth 格式应为 \"用户名:密码\""))?;
+                basic_auth = Some((user.to_string(), password.to_string()));
+            }
+        }
+        let timeouts = match config {
+            Some(config) => config.timeouts.or(&config
+                .timeout_secs
+                .map(TimeoutConfig::uniform)
+                .unwrap_or_default()
+                .or(timeouts)),
+            None => *timeouts,
+        };
+        // 整体超时按操作在每个请求上设置
+        let mut builder = Client::builder().timeout(None).default_headers(headers);
+        if let Some(limit) = timeouts.get(Operation::Connect) {
+            builder = builder.connect_timeout(limit);
+        }
+        let (client, base_url) = match &endpoint {
+            ApiEndpoint::Http(url) => (builder.build()?, url.clone()),
+            // 走 unix socket 时主机名不会被解析，只用来拼接请求路径
+            ApiEndpoint::Unix(path) => (
+                builder.unix_socket(path.as_path()).build()?,
+                "http://localhost".to_string(),
+            ),
+        };
+        Ok(Self {
+            endpoint,
+            client,
+            base_url,
+            basic_auth,
+            throttle: Throttle::default(),
+            timeouts,
+            add: AddConfig::default(),
+            node_version: None,
+            block_put: false,
+        })
+    }
+
+    // 上传请求体按限速发送
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    // [add] 中的高级导入参数
+    pub fn with_add_config(mut self, add: AddConfig) -> Self {
+        self.add = add;
+        self
+    }
+
+    fn post(&self, command: &str, query: &[(&str, &str)]) -> RequestBuilder {
+        let url = format!("{}/api/v0/{}", self.base_url, command);
+        let params: Vec<String> = query
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        if params.is_empty() {
+            trace!("🔗 POST {}", url);
+        } else {
+            trace!("🔗 POST {}?{}", url, params.join("&"));
+        }
+        let request = self
+            .timeouts
+            .apply(self.client.post(url).query(query), operation_of(command));
+        match &self.basic_auth {
+            Some((user, password)) => request.basic_auth(user, Some(password)),
+            None => request,
+        }
+    }
+
+    fn rpc(&self, command: &str, query: &[(&str, &str)]) -> Result<Response> {
+        let response = self
+            .post(command, query)
+            .send()
+            .map_err(|e| self.timeout_error(e, command))?;
+        check_response(response)
+    }
+
+    fn rpc_with_form(
+        &self,
+        command: &str,
+        query: &[(&str, &str)],
+        form: multipart::Form,
+    ) -> Result<Response> {
+        let response = self
+            .post(command, query)
+            .multipart(form)
+            .send()
+            .map_err(|e| self.timeout_error(e, command))?;
+        check_response(response)
+    }
+
+    fn timeout_error(&self, error: reqwest::Error, command: &str) -> anyhow::Error {
+        let target = format!("{}/api/v0/{}", self.base_url, command);
+        self.timeouts.check(error, operation_of(command), &target)
+    }
+
+    fn add_form(&self, form: multipart::Form, extra: &[(&str, String)]) -> Result<String> {
+        Ok(self.add_request(form, extra, true)?.0)
+    }
+
+    // 返回根 CID 和它的累计大小 (节点没有返回时为 None)
+    fn add_request(
+        &self,
+        form: multipart::Form,
+        extra: &[(&str, String)],
+        pin: bool,
+    ) -> Result<(String, Option<u64>)> {
+        let pin = if pin { "true" } else { "false" };
+        let mut query = vec![("cid-version", "1"), ("pin", pin), ("progress", "false")];
+        query.extend(ADD_OPTIONS);
+        query.extend(extra.iter().map(|(key, value)| (*key, value.as_str())));
+        let body = self
+            .rpc_with_form("add", &query, form)?
+            .text()
+            .map_err(|e| self.timeout_error(e, "add"))?;
+        // 每个文件一行 JSON，根目录在最后一行
+        body.lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<AddEvent>(line).ok())
+            .filter_map(|event| {
+                let size = event.size.and_then(|size| size.parse().ok());
+                event.hash.map(|hash| (hash, size))
+            })
+            .next_back()
+            .ok_or_else(|| anyhow!("❌ 节点没有返回 CID: {}", body))
+    }
+
+    // 逐个文件上传的前提：节点能按指定的 codec 写入块，导入参数不会改变目录节点，目录不需要分片
+    fn directory_plan(&self, dir: &Path) -> Result<Option<DirectoryPlan>> {
+        if !dir.is_dir() || !self.block_put || self.add.inline {
+            return Ok(None);
+        }
+        let plan = DirectoryPlan::scan(dir)?;
+        if let Some(sharded) = plan.sharded_dir() {
+            verbose!(
+                "📁 文件夹 {:?} 的条目较多，Kubo 会使用 HAMT 分片目录，整个文件夹作为一个请求上传",
+                dir.join(sharded)
+            );
+            return Ok(None);
+        }
+        Ok(Some(plan))
+    }
+
+    // ✅ 多个文件同时上传 (不固定)，目录节点在本地拼出后写入节点，最后递归固定根目录；
+    // 导入参数相同，根 CID 与整个文件夹一次上传时一致
+    fn add_directory(&self, plan: &DirectoryPlan) -> Result<String> {
+        let total = plan.files.len();
+        let workers = self.add.concurrency.clamp(1, total.max(1));
+        println!(
+            "📦 {} 个文件 ({})，同时上传 {} 个",
+            total,
+            format_bytes(plan.files.iter().map(|file| file.size).sum()),
+            workers
+        );
+        let next = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let added: Mutex<Vec<Option<(Cid, u64)>>> = Mutex::new(vec![None; total]);
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| -> Result<()> {
+                        // 一个文件失败后其它线程不再开始新的文件
+                        while !failed.load(Ordering::SeqCst) {
+                            let index = next.fetch_add(1, Ordering::SeqCst);
+                            let Some(file) = plan.files.get(index) else {
+                                break;
+                            };
+                            let result = cancel::check().and_then(|_| self.add_file(file));
+                            let (cid, tsize) = result.inspect_err(|_| {
+                                failed.store(true, Ordering::SeqCst);
+                            })?;
+                            added.lock().unwrap_or_else(|e| e.into_inner())[index] =
+                                Some((cid, tsize));
+                            let count = done.fetch_add(1, Ordering::SeqCst) + 1;
+                            println!(
+                                "   [{}/{}] {} ({})",
+                                count,
+                                total,
+                                file.relative,
+                                format_bytes(file.size)
+                            );
+                        }
+                        Ok(())
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(anyhow!("❌ 上传文件的线程异常退出")))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let added = added.into_inner().unwrap_or_else(|e| e.into_inner());
+
+        // 从最深的目录开始拼目录节点，拼好的目录作为链接加到上一级
+        let mut children: BTreeMap<&str, Vec<Link>> = BTreeMap::new();
+        for (relative, target) in &plan.symlinks {
+            let block = symlink_block(target);
+            self.put_block(&block)?;
+            let (parent, name) = split_relative(relative);
+            children.entry(parent).or_default().push(Link {
+                name: name.to_string(),
+                cid: block.cid,
+                tsize: block.data.len() as u64,
+            });
+        }
+        for (file, entry) in plan.files.iter().zip(added) {
+            let (cid, tsize) =
+                entry.ok_or_else(|| anyhow!("❌ 文件没有上传: {}", file.relative))?;
+            let (parent, name) = split_relative(&file.relative);
+            children.entry(parent).or_default().push(Link {
+                name: name.to_string(),
+                cid,
+                tsize,
+            });
+        }
+        let mut dirs: Vec<&str> = plan.dirs.iter().map(String::as_str).collect();
+        dirs.sort_by_key(|dir| std::cmp::Reverse(depth(dir)));
+        let mut root = None;
+        for dir in dirs {
+            let mut links = children.remove(dir).unwrap_or_default();
+            links.sort_by(|a, b| a.name.as_bytes().cmp(b.name.as_bytes()));
+            let block = directory_block(&links);
+            self.put_block(&block)?;
+            let tsize = block.data.len() as u64 + links.iter().map(|link| link.tsize).sum::<u64>();
+            if dir.is_empty() {
+                root = Some(block.cid);
+                break;
+            }
+            let (parent, name) = split_relative(dir);
+            children.entry(parent).or_default().push(Link {
+                name: name.to_string(),
+                cid: block.cid,
+                tsize,
+            });
+        }
+        let root = root
+            .ok_or_else(|| anyhow!("❌ 没有生成根目录节点"))?
+            .to_string();
+        // 在固定根目录之前，已上传的块可能被节点的垃圾回收删除 (需要开启 --enable-gc)
+        self.rpc("pin/add", &[("arg", &root), ("recursive", "true")])?;
+        Ok(root)
+    }
+
+    // 上传文件夹中的一个文件，返回它的 CID 和累计大小
+    fn add_file(&self, file: &PlannedFile) -> Result<(Cid, u64)> {
+        let mut part = file_part(&file.path, &self.throttle.connection())?;
+        if self.add.nocopy {
+            part = with_abspath(part, &file.path)?;
+        }
+        let name = split_relative(&file.relative).1;
+        let form = multipart::Form::new().part("file", part.file_name(encode_file_name(name)));
+        let (cid, size) = self.add_request(form, &self.add.path_options(false), false)?;
+        let size = size.ok_or_else(|| anyhow!("❌ 节点没有返回 {} 的大小", file.relative))?;
+        Ok((cid.parse()?, size))
+    }
+
+    // 写入本地拼出的目录节点，并确认节点算出的 CID 相同
+    fn put_block(&self, block: &Block) -> Result<()> {
+        let part = bytes_part(&block.data, &self.throttle.connection()).file_name("block");
+        let output: BlockPutOutput = self
+            .rpc_with_form(
+                "block/put",
+                &[("cid-codec", "dag-pb"), ("mhtype", "sha2-256")],
+                multipart::Form::new().part("file", part),
+            )?
+            .json()?;
+        let expected = block.cid.to_string();
+        if output.key != expected {
+            return Err(anyhow!(
+                "❌ 节点返回的目录 CID {} 与本地计算的 {} 不一致",
+                output.key,
+                expected
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LsLink {
+    name: String,
+    hash: String,
+    #[serde(default)]
+    size: u64,
+    // UnixFS 类型：1 目录，2 文件，5 分片目录
+    #[serde(rename = "Type", default)]
+    kind: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LsObject {
+    #[serde(default)]
+    links: Vec<LsLink>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LsOutput {
+    objects: Vec<LsObject>,
+}
+
+// routing 命令输出的一个查询事件
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RoutingEvent {
+    #[serde(rename = "Type")]
+    kind: i32,
+    #[serde(default)]
+    responses: Option<Vec<RoutingPeer>>,
+    #[serde(default)]
+    extra: String,
+}
+
+#[derive(Deserialize)]
+struct RoutingPeer {
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+// 事件类型：3 查询出错，4 找到提供者
+const ROUTING_QUERY_ERROR: i32 = 3;
+const ROUTING_PROVIDER: i32 = 4;
+
+// `routing findprovs --enc=json` 的输出 (HTTP API 和命令行相同)：每行一个事件，返回去重后的节点 ID
+pub(super) fn parse_providers(body: &str) -> Result<Vec<String>> {
+    let mut providers: Vec<String> = Vec::new();
+    let mut last_error = None;
+    for line in body.lines().filter(|line| !line.trim().is_empty()) {
+        let event: RoutingEvent = serde_json::from_str(line)
+            .map_err(|e| anyhow!("❌ 无法解析 findprovs 的输出: {}", e))?;
+        match event.kind {
+            ROUTING_PROVIDER => {
+                for peer in event.responses.unwrap_or_default() {
+                    if !providers.contains(&peer.id) {
+                        providers.push(peer.id);
+                    }
+                }
+            }
+            ROUTING_QUERY_ERROR => last_error = Some(event.extra),
+            _ => {}
+        }
+    }
+    // 只有查询出错且一个提供者都没有找到时才算失败
+    match last_error {
+        Some(error) if providers.is_empty() => Err(anyhow!("❌ 查找提供者失败: {}", error)),
+        _ => Ok(providers),
+    }
+}
+
+// `ls --enc=json` 的输出 (HTTP API 和命令行相同)；文件的分块没有名称，不算目录项
+pub(super) fn parse_ls(body: &str) -> Result<Vec<DirEntry>> {
+    let mut entries = Vec::new();
+    // 大目录会分多行输出
+    for line in body.lines().filter(|line| !line.trim().is_empty()) {
+        let output: LsOutput =
+            serde_json::from_str(line).map_err(|e| anyhow!("❌ 无法解析 ls 的输出: {}", e))?;
+        for link in output.objects.into_iter().flat_map(|object| object.links) {
+            if link.name.is_empty() {
+                continue;
+            }
+            entries.push(DirEntry {
+                name: link.name,
+                cid: link.hash,
+                size: link.size,
+                is_dir: matches!(link.kind, 1 | 5),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+fn check_response(response: Response) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    let message = serde_json::from_str::<RpcError>(&body)
+        .map(|e| e.message)
+        .unwrap_or(body);
+    Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+        "❌ IPFS API 请求失败 ({}): {}",
+        status,
+        message
+    )))
+}
+
+// multipart 中的文件名需要 URL 编码，Kubo 会按 QueryUnescape 解码
+pub(super) fn encode_file_name(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// 不限速时直接使用文件；限速时按 ConnectionThrottle 读取
+pub(super) fn file_part(path: &Path, connection: &ConnectionThrottle) -> Result<multipart::Part> {
+    if connection.is_unlimited() {
+        return Ok(multipart::Part::file(path)?);
+    }
+    let file = File::open(path)?;
+    let length = file.metadata()?.len();
+    Ok(multipart::Part::reader_with_length(
+        connection.reader(file),
+        length,
+    ))
+}
+
+pub(super) fn bytes_part(data: &[u8], connection: &ConnectionThrottle) -> multipart::Part {
+    if connection.is_unlimited() {
+        return multipart::Part::bytes(data.to_vec());
+    }
+    multipart::Part::reader_with_length(
+        connection.reader(Cursor::new(data.to_vec())),
+        data.len() as u64,
+    )
+}
+
+// --nocopy 时节点按 Abspath 头记录文件位置，只有节点能读到同一路径 (同一台机器) 时才有效
+fn with_abspath(part: multipart::Part, path: &Path) -> Result<multipart::Part> {
+    let path = fs::canonicalize(path)?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        HeaderName::from_static("abspath"),
+        HeaderValue::from_bytes(path.to_string_lossy().as_bytes())?,
+    );
+    Ok(part.headers(headers))
+}
+
+// 逐个上传的文件：相对于上传文件夹的路径 (用 / 分隔) 和大小
+struct PlannedFile {
+    relative: String,
+    path: PathBuf,
+    size: u64,
+}
+
+// 逐个文件上传时的文件夹结构，与 directory_form 选择的文件相同；根目录的相对路径为空字符串
+// 符号链接和 `ipfs add -r`、unixfs::import_path 一样保存为 symlink 节点 (目标路径)，不上传目标的内容
+struct DirectoryPlan {
+    dirs: Vec<String>,
+    files: Vec<PlannedFile>,
+    symlinks: Vec<(String, String)>,
+}
+
+impl DirectoryPlan {
+    fn scan(dir: &Path) -> Result<Self> {
+        let filter = FileFilter::from_ignore_file(dir)?;
+        let walker = WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                filter.allows_path(dir, entry.path(), entry.file_type().is_dir())
+            });
+        let mut plan = Self {
+            dirs: Vec::new(),
+            files: Vec::new(),
+            symlinks: Vec::new(),
+        };
+        for entry in walker {
+            let entry = entry?;
+            let relative: Vec<String> = entry
+                .path()
+                .strip_prefix(dir)?
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let relative = relative.join("/");
+            if entry.path_is_symlink() && entry.depth() > 0 {
+                let target = fs::read_link(entry.path())?;
+                plan.symlinks
+                    .push((relative, target.to_string_lossy().into_owned()));
+            } else if entry.file_type().is_dir() {
+                plan.dirs.push(relative);
+            } else {
+                plan.files.push(PlannedFile {
+                    relative,
+                    path: entry.path().to_path_buf(),
+                    size: fs::metadata(entry.path())?.len(),
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    // 条目多到 Kubo 会改用分片目录的第一个文件夹
+    fn sharded_dir(&self) -> Option<&str> {
+        let mut names: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let entries = self
+            .dirs
+            .iter()
+            .filter(|dir| !dir.is_empty())
+            .chain(self.files.iter().map(|file| &file.relative))
+            .chain(self.symlinks.iter().map(|(relative, _)| relative));
+        for relative in entries {
+            let (parent, name) = split_relative(relative);
+            names.entry(parent).or_default().push(name);
+        }
+        names
+            .into_iter()
+            .find(|(_, names)| needs_sharding(names.iter().copied()))
+            .map(|(dir, _)| dir)
+    }
+}
+
+// "a/b/c.png" -> ("a/b", "c.png")，第一层的条目父目录为空字符串
+fn split_relative(relative: &str) -> (&str, &str) {
+    relative.rsplit_once('/').unwrap_or(("", relative))
+}
+
+fn depth(relative: &str) -> usize {
+    if relative.is_empty() {
+        0
+    } else {
+        relative.matches('/').count() + 1
+    }
+}
+
+// 和 `ipfs add -r` 一样：保留最外层文件夹名，跳过隐藏文件和 .ipfsignore 中忽略的文件
+pub(super) fn directory_form(
+    dir: &Path,
+    connection: &ConnectionThrottle,
+    nocopy: bool,
+) -> Result<multipart::Form> {
+    let root_name = dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的文件夹名: {:?}", dir))?;
+    let filter = FileFilter::from_ignore_file(dir)?;
+    let mut form = multipart::Form::new();
+    let walker = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| filter.allows_path(dir, entry.path(), entry.file_type().is_dir()));
+    for entry in walker {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(dir)?;
+        let name = Path::new(root_name).join(relative);
+        let name = encode_file_name(&name.to_string_lossy());
+        let part = if entry.depth() > 0 && entry.path_is_symlink() {
+            // 与 DirectoryPlan 和 unixfs 一致：符号链接上传为 symlink 节点，内容是链接目标，不读取指向的文件
+            let target = fs::read_link(entry.path())?;
+            multipart::Part::bytes(target.to_string_lossy().into_owned().into_bytes())
+                .file_name(name)
+                .mime_str("application/symlink")?
+        } else if entry.file_type().is_dir() {
+            multipart::Part::bytes(Vec::new())
+                .file_name(name)
+                .mime_str("application/x-directory")?
+        } else if nocopy {
+            with_abspath(file_part(entry.path(), connection)?, entry.path())?.file_name(name)
+        } else {
+            file_part(entry.path(), connection)?.file_name(name)
+        };
+        form = form.part("file", part);
+    }
+    Ok(form)
+}
+
+impl IpfsBackend for HttpBackend {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        let version: VersionInfo = self
+            .rpc("version", &[])
+            .map_err(|e| {
+                ExitCode::connect_failure(&e).wrap(anyhow!(
+                    "❌ 无法连接 IPFS API {:?}: {:#}",
+                    self.endpoint,
+                    e
+                ))
+            })?
+            .json()?;
+        warn_if_outdated(&version.version);
+        self.node_version = Some(version.version.clone());
+        // 托管服务 (如 Infura) 通常只开放部分接口，没有 commands 时跳过兼容性检查
+        let commands = match self.rpc("commands", &[]) {
+            Ok(response) => response.text()?,
+            Err(e) => {
+                println!(
+                    "⚠️  节点 (版本: {}) 不提供 commands 接口，跳过兼容性检查: {:#}",
+                    version.version, e
+                );
+                return Ok(None);
+            }
+        };
+        let capabilities = NodeCapabilities::from_commands_json(&version.version, &commands)?;
+        capabilities.ensure(REQUIRED)?;
+        adapt_add_config(&capabilities, &mut self.add);
+        self.block_put = capabilities.supports("block/put", Some("cid-codec"));
+        Ok(Some(capabilities))
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        if !target_path.exists() {
+            return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+        }
+        println!("\n--- 正在上传(HTTP API): {:?} ---", target_path);
+        if let Some(plan) = self.directory_plan(target_path)? {
+            let cid = self.add_directory(&plan)?;
+            println!("✅ 上传成功! CID: {}", cid);
+            return Ok(cid);
+        }
+        let connection = self.throttle.connection();
+        let form = if target_path.is_dir() {
+            directory_form(target_path, &connection, self.add.nocopy)?
+        } else {
+            let name = target_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| anyhow!("无效的文件路径"))?;
+            let mut part = file_part(target_path, &connection)?;
+            if self.add.nocopy {
+                part = with_abspath(part, target_path)?;
+            }
+            multipart::Form::new().part("file", part.file_name(encode_file_name(name)))
+        };
+        let cid = self.add_form(form, &self.add.path_options(target_path.is_file()))?;
+        println!("✅ 上传成功! CID: {}", cid);
+        Ok(cid)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let part = bytes_part(data, &self.throttle.connection()).file_name("data");
+        self.add_form(
+            multipart::Form::new().part("file", part),
+            &self.add.bytes_options(),
+        )
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        let part =
+            bytes_part(&serde_json::to_vec(node)?, &self.throttle.connection()).file_name("node");
+        let query = [
+            ("input-codec", "dag-json"),
+            ("store-codec", codec.as_str()),
+            ("hash", "sha2-256"),
+            ("pin", "true"),
+        ];
+        let output: DagPutOutput = self
+            .rpc_with_form("dag/put", &query, multipart::Form::new().part("file", part))?
+            .json()?;
+        Ok(output.cid.link)
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.add.clone()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.node_version.clone()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        match self.rpc("pin/ls", &[("arg", cid), ("type", "recursive")]) {
+            Ok(_) => Ok(true),
+            Err(e) if e.to_string().contains("not pinned") => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        self.rpc("pin/add", &[("arg", cid), ("recursive", "true")])?;
+        Ok(())
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.rpc("pin/rm", &[("arg", cid), ("recursive", "true")])?;
+        Ok(())
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        let body = self
+            .rpc(
+                "ls",
+                &[("arg", path), ("resolve-type", "true"), ("size", "true")],
+            )?
+            .text()?;
+        parse_ls(&body)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        Ok(self.rpc("cat", &[("arg", path)])?.bytes()?.to_vec())
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        let recursive = if recursive { "true" } else { "false" };
+        // 响应是流式的，读完才表示宣告结束
+        self.rpc("routing/provide", &[("arg", cid), ("recursive", recursive)])?
+            .text()
+            .map_err(|e| self.timeout_error(e, "routing/provide"))?;
+        Ok(())
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        let body = self
+            .rpc(
+                "routing/findprovs",
+                &[("arg", cid), ("num-providers", &limit.to_string())],
+            )?
+            .text()
+            .map_err(|e| self.timeout_error(e, "routing/findprovs"))?;
+        parse_providers(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::unixfs::{DAG_PB, ImportOptions, compute_cid, import_file};
+
+    // multipart 的一个部分：文件名 (已解码)、Content-Type 和内容
+    type FormPart = (String, String, Vec<u8>);
+    type Received = Arc<Mutex<Vec<FormPart>>>;
+
+    // 只实现上传用到的接口的假节点：单个文件的 add 按 unixfs::import_file 计算 CID，block/put 按 dag-pb 计算；
+    // 整个文件夹的 add 只记录收到的各个部分，返回固定的根 CID
+    fn fake_kubo() -> (String, Received) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let received = Received::default();
+        let shared = received.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let received = shared.clone();
+                thread::spawn(move || serve(stream, received));
+            }
+        });
+        (format!("http://{}", addr), received)
+    }
+
+    const FAKE_ROOT: &str = "bafybeifakeroot";
+
+    fn serve(stream: TcpStream, received: Received) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let add_options: Vec<Value> = ["recursive", "cid-version", "chunker", "raw-leaves", "hash"]
+            .iter()
+            .map(|name| json!({ "Names": [name] }))
+            .collect();
+        while let Some((path, content_type, body)) = read_request(&mut reader) {
+            let command = path.split('?').next().unwrap_or_default();
+            let response = match command {
+                "/api/v0/version" => json!({ "Version": "0.30.0" }),
+                "/api/v0/commands" => json!({
+                    "Name": "ipfs",
+                    "Subcommands": [
+                        {
+                            "Name": "add",
+                            "Options": add_options,
+                        },
+                        {
+                            "Name": "block",
+                            "Subcommands": [{ "Name": "put", "Options": [{ "Names": ["cid-codec"] }] }],
+                        },
+                    ],
+                }),
+                "/api/v0/add" => {
+                    let parts = multipart_parts(&content_type, &body);
+                    if let [(_, _, data)] = parts.as_slice() {
+                        let added = import_file(
+                            data.as_slice(),
+                            &ImportOptions::default(),
+                            &mut |_| Ok(()),
+                        )
+                        .unwrap();
+                        json!({ "Name": "file", "Hash": added.cid.to_string(), "Size": added.tsize.to_string() })
+                    } else {
+                        received.lock().unwrap().extend(parts);
+                        json!({ "Name": "collection", "Hash": FAKE_ROOT })
+                    }
+                }
+                "/api/v0/block/put" => {
+                    let data = multipart_file(&content_type, &body);
+                    json!({ "Key": Block::new(DAG_PB, data.to_vec()).cid.to_string(), "Size": data.len() })
+                }
+                "/api/v0/pin/add" => json!({ "Pins": [] }),
+                _ => json!({ "Message": format!("unsupported {}", command) }),
+            };
+            let body = response.to_string();
+            write!(
+                writer,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    }
+
+    // 读一个请求：路径、Content-Type 和请求体 (支持 Content-Length 和 chunked)
+    fn read_request(reader: &mut impl BufRead) -> Option<(String, String, Vec<u8>)> {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let path = line.split_whitespace().nth(1)?.to_string();
+        let (mut content_type, mut length, mut chunked) = (String::new(), 0, false);
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).ok()?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header.split_once(':')?;
+            match name.to_ascii_lowercase().as_str() {
+                "content-type" => content_type = value.trim().to_string(),
+                "content-length" => length = value.trim().parse().ok()?,
+                "transfer-encoding" => chunked = value.contains("chunked"),
+                _ => {}
+            }
+        }
+        let mut body = Vec::new();
+        if chunked {
+            loop {
+                let mut size = String::new();
+                reader.read_line(&mut size).ok()?;
+                let size = usize::from_str_radix(size.trim(), 16).ok()?;
+                let mut chunk = vec![0; size + 2];
+                reader.read_exact(&mut chunk).ok()?;
+                if size == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..size]);
+            }
+        } else {
+            body.resize(length, 0);
+            reader.read_exact(&mut body).ok()?;
+        }
+        Some((path, content_type, body))
+    }
+
+    // multipart 请求体中唯一一个部分的内容
+    fn multipart_file<'a>(content_type: &str, body: &'a [u8]) -> &'a [u8] {
+        let boundary = content_type.split("boundary=").nth(1).unwrap();
+        let delimiter = format!("\r\n--{}", boundary);
+        let start = find(body, b"\r\n\r\n") + 4;
+        let end = start + find(&body[start..], delimiter.as_bytes());
+        &body[start..end]
+    }
+
+    // 按 boundary 拆出所有部分，文件名按 encode_file_name 的规则解码
+    fn multipart_parts(content_type: &str, body: &[u8]) -> Vec<FormPart> {
+        let boundary = content_type.split("boundary=").nth(1).unwrap();
+        let delimiter = format!("--{}", boundary);
+        let mut parts = Vec::new();
+        let mut rest = &body[find(body, delimiter.as_bytes()) + delimiter.len()..];
+        while !rest.starts_with(b"--") {
+            let header_end = find(rest, b"\r\n\r\n");
+            let headers = String::from_utf8_lossy(&rest[..header_end]).into_owned();
+            let content = &rest[header_end + 4..];
+            let end = find(content, format!("\r\n{}", delimiter).as_bytes());
+            let name = headers
+                .split("filename=\"")
+                .nth(1)
+                .and_then(|value| value.split('"').next())
+                .unwrap_or_default();
+            let mime = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Type: "))
+                .unwrap_or_default();
+            parts.push((
+                percent_decode(name),
+                mime.to_string(),
+                content[..end].to_vec(),
+            ));
+            rest = &content[end + 2 + delimiter.len()..];
+        }
+        parts
+    }
+
+    fn percent_decode(text: &str) -> String {
+        let bytes = text.as_bytes();
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                out.push(u8::from_str_radix(&text[i + 1..i + 3], 16).unwrap());
+                i += 3;
+            } else {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .unwrap()
+    }
+
+    // 逐个文件上传拼出的根 CID 与本地按 `ipfs add -r` 规则计算的相同 (包括嵌套文件夹、空文件夹和符号链接)
+    #[cfg(unix)]
+    #[test]
+    fn directory_upload_matches_local_cid() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("collection");
+        fs::create_dir_all(root.join("nested/deeper")).unwrap();
+        fs::create_dir_all(root.join("empty")).unwrap();
+        fs::write(root.join("a.txt"), "hello").unwrap();
+        fs::write(root.join("empty.txt"), "").unwrap();
+        fs::write(root.join("nested/b.json"), r#"{"name":"b"}"#).unwrap();
+        // 跨越多个分块的文件
+        let large: Vec<u8> = (0..700_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(root.join("nested/deeper/large.bin"), large).unwrap();
+        std::os::unix::fs::symlink("nested/b.json", root.join("link.json")).unwrap();
+        std::os::unix::fs::symlink("../nested", root.join("empty/to-dir")).unwrap();
+
+        let (api, _) = fake_kubo();
+        let mut backend = HttpBackend::new(&api).unwrap();
+        backend.check_compatibility().unwrap();
+        assert!(backend.block_put);
+        assert_eq!(DirectoryPlan::scan(&root).unwrap().symlinks.len(), 2);
+
+        let cid = backend.add_path(&root).unwrap();
+        let local = compute_cid(&root, &ImportOptions::default()).unwrap();
+        assert_eq!(cid, local.to_string());
+    }
+
+    // 与 Kubo 的已知结果对比，不依赖本地的导入代码：空文件夹是 `ipfs add -r --cid-version 1` 的固定 CID
+    #[test]
+    fn empty_directory_matches_kubo() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("empty");
+        fs::create_dir(&root).unwrap();
+        let (api, _) = fake_kubo();
+        let mut backend = HttpBackend::new(&api).unwrap();
+        backend.check_compatibility().unwrap();
+        assert_eq!(
+            backend.add_path(&root).unwrap(),
+            "bafybeiczsscdsbs7ffqz55asqdf3smv6klcw3gofszvwlyarci47bgf354"
+        );
+    }
+
+    // 没有做兼容性检查时整个文件夹作为一个 add 请求上传：符号链接是 application/symlink 部分，
+    // 内容是链接目标；指向文件夹的链接不会被展开，指向文件的链接不会带上目标文件的内容
+    #[cfg(unix)]
+    #[test]
+    fn directory_form_sends_symlinks_as_symlink_parts() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("collection");
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("a.txt"), "hello").unwrap();
+        fs::write(root.join("nested/b.json"), r#"{"name":"b"}"#).unwrap();
+        std::os::unix::fs::symlink("nested/b.json", root.join("link.json")).unwrap();
+        std::os::unix::fs::symlink("nested", root.join("to-dir")).unwrap();
+
+        let (api, received) = fake_kubo();
+        let backend = HttpBackend::new(&api).unwrap();
+        assert!(!backend.block_put);
+        assert_eq!(backend.add_path(&root).unwrap(), FAKE_ROOT);
+
+        let parts = received.lock().unwrap().clone();
+        let summary: Vec<(&str, &str, &[u8])> = parts
+            .iter()
+            .map(|(name, mime, data)| (name.as_str(), mime.as_str(), data.as_slice()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("collection/", "application/x-directory", &b""[..]),
+                ("collection/a.txt", "text/plain", b"hello"),
+                (
+                    "collection/link.json",
+                    "application/symlink",
+                    b"nested/b.json"
+                ),
+                ("collection/nested", "application/x-directory", b""),
+                (
+                    "collection/nested/b.json",
+                    "application/json",
+                    br#"{"name":"b"}"#
+                ),
+                ("collection/to-dir", "application/symlink", b"nested"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use super::{DirEntry, IpfsBackend};
+use crate::ipld::{DagCodec, encode_dag_cbor};
+use crate::unixfs::{
+    Block, BlockSource, Cid, DAG_PB, ImportOptions, NodeKind, RAW, decode_pb_node,
+    decode_unixfs_data, import_file, import_path, read_file, resolve_path,
+};
+
+// ✅ 内存中的后端：CID 与 `ipfs add --cid-version 1` 一致，块保存在 HashMap 中，进程结束后丢弃
+// 不需要节点和网络，用于测试、示例和离线试运行；clone 出的副本共享同一份块和 pin
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    options: ImportOptions,
+    blocks: Arc<Mutex<HashMap<Cid, Vec<u8>>>>,
+    pins: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 使用不同的分块大小和每个节点的最大链接数
+    pub fn with_options(options: ImportOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    fn put_block(&self, block: Block) -> Result<()> {
+        let mut blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        blocks.entry(block.cid).or_insert(block.data);
+        Ok(())
+    }
+
+    pub fn get_block(&self, cid: &Cid) -> Result<Vec<u8>> {
+        let blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        blocks
+            .get(cid)
+            .cloned()
+            .ok_or_else(|| anyhow!("❌ 内存中没有块 {}", cid))
+    }
+
+    // 已保存的块数
+    pub fn block_count(&self) -> usize {
+        self.blocks.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    // 已固定的 CID
+    pub fn pins(&self) -> Vec<String> {
+        let pins = self.pins.lock().unwrap_or_else(|e| e.into_inner());
+        pins.iter().cloned().collect()
+    }
+
+    fn add_pin(&self, cid: &Cid) {
+        let mut pins = self.pins.lock().unwrap_or_else(|e| e.into_inner());
+        pins.insert(cid.to_string());
+    }
+
+    // 检查 DAG 中的块都在内存中
+    fn check_dag(&self, cid: &Cid) -> Result<()> {
+        let data = self.get_block(cid)?;
+        if cid.codec == DAG_PB {
+            let (links, _) = decode_pb_node(&data)?;
+            for link in links {
+                self.check_dag(&link.cid)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ls 中一项的类型和大小 (文件为内容大小，目录为 0)
+fn entry_info(get: &BlockSource, cid: &Cid) -> Result<(bool, u64)> {
+    let data = get(cid)?;
+    if cid.codec == RAW {
+        return Ok((false, data.len() as u64));
+    }
+    let (_, node_data) = decode_pb_node(&data)?;
+    let unixfs = decode_unixfs_data(&node_data)?;
+    Ok(match unixfs.kind {
+        NodeKind::Directory => (true, 0),
+        _ => (false, unixfs.filesize),
+    })
+}
+
+// ✅ 直接读取内存中的块完成 ls；与 Kubo 一致，文件的 ls 结果中没有带名称的目录项
+fn ls_blocks(get: &BlockSource, path: &str) -> Result<Vec<DirEntry>> {
+    let cid = resolve_path(get, path)?;
+    if cid.codec != DAG_PB {
+        return Ok(Vec::new());
+    }
+    let (links, data) = decode_pb_node(&get(&cid)?)?;
+    if decode_unixfs_data(&data)?.kind != NodeKind::Directory {
+        return Ok(Vec::new());
+    }
+    links
+        .into_iter()
+        .map(|link| {
+            let (is_dir, size) = entry_info(get, &link.cid)?;
+            Ok(DirEntry {
+                name: link.name,
+                cid: link.cid.to_string(),
+                size,
+                is_dir,
+            })
+        })
+        .collect()
+}
+
+impl IpfsBackend for MemoryBackend {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        if !target_path.exists() {
+            return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+        }
+        println!("\n--- 正在上传(内存): {:?} ---", target_path);
+        let root = import_path(target_path, &self.options, &mut |block| {
+            self.put_block(block)
+        })?;
+        self.add_pin(&root.cid);
+        println!("✅ 上传成功! CID: {}", root.cid);
+        Ok(root.cid.to_string())
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let root = import_file(data, &self.options, &mut |block| self.put_block(block))?;
+        self.add_pin(&root.cid);
+        Ok(root.cid.to_string())
+    }
+
+    // 只能在本地编码 dag-cbor；dag-json 的规范编码交给 Kubo
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        if codec != DagCodec::DagCbor {
+            return Err(anyhow!("❌ 内存后端只支持 dag-cbor 编码的 IPLD 节点"));
+        }
+        let block = Block::new(codec.code(), encode_dag_cbor(node)?);
+        let cid = block.cid;
+        self.put_block(block)?;
+        self.add_pin(&cid);
+        Ok(cid.to_string())
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        let pins = self.pins.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(pins.contains(cid))
+    }
+
+    // 不会从网络获取内容，只能固定内存中已有的 DAG
+    fn pin(&self, cid: &str) -> Result<()> {
+        let cid: Cid = cid.parse()?;
+        self.check_dag(&cid)?;
+        self.add_pin(&cid);
+        Ok(())
+    }
+
+    // 只删除 pin 记录，块仍保留
+    fn unpin(&self, cid: &str) -> Result<()> {
+        let mut pins = self.pins.lock().unwrap_or_else(|e| e.into_inner());
+        pins.remove(cid);
+        Ok(())
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        ls_blocks(&|cid: &Cid| self.get_block(cid), path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        let get = |cid: &Cid| self.get_block(cid);
+        let cid = resolve_path(&get, path)?;
+        read_file(&get, &cid)
+    }
+}
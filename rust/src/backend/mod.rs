@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use crate::compat::NodeCapabilities;
+use crate::ipld::DagCodec;
+use crate::manifest::EncryptionInfo;
+use crate::unixfs::AddConfig;
+
+mod cli;
+mod cluster;
+mod failover;
+mod filebase;
+mod http;
+mod memory;
+mod s3;
+
+pub use cli::CliBackend;
+pub use cluster::{ClusterBackend, ClusterConfig, PeerPinStatus};
+pub use failover::{FailoverBackend, FailoverConfig};
+pub use filebase::{FILEBASE_ENDPOINT, FilebaseBackend, FilebaseConfig};
+pub use http::{ApiEndpoint, ApiEndpointConfig, HttpBackend, find_endpoint_config};
+pub use memory::MemoryBackend;
+pub use s3::{S3Client, S3Object};
+
+// `ls` 返回的一个目录项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: String,
+    pub cid: String,
+    pub size: u64,
+    pub is_dir: bool,
+}
+
+// ✅ 上传后端的统一接口，工作流只依赖这个 trait
+pub trait IpfsBackend: Send + Sync {
+    // 后端名称，用于日志和清单记录
+    fn name(&self) -> &str;
+
+    // 启动前的连通性和兼容性检查；不是 Kubo 节点的后端返回 None
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        Ok(None)
+    }
+
+    // 上传单个文件或整个文件夹，返回根 CID
+    fn add_path(&self, target_path: &Path) -> Result<String>;
+
+    // 上传内存中的数据（例如 JSON 元数据），返回 CID
+    fn add_bytes(&self, data: &[u8]) -> Result<String>;
+
+    // 以 IPLD 节点发布 dag-json 形式的文档 ({"/": cid} 为链接)，按 codec 存储并固定，返回节点 CID
+    fn dag_put(&self, _node: &Value, _codec: DagCodec) -> Result<String> {
+        Err(anyhow!("❌ {} 后端不支持发布 IPLD 节点", self.name()))
+    }
+
+    // 上传时使用的 [add] 高级导入参数；工作流按它决定链接写法和是否原地写入输出目录
+    fn add_config(&self) -> AddConfig {
+        AddConfig::default()
+    }
+
+    // 检查 CID 是否已被递归固定 (pin)
+    fn is_pinned(&self, _cid: &str) -> Result<bool> {
+        Err(anyhow!("❌ {} 后端不支持查询 pin 状态", self.name()))
+    }
+
+    // 递归固定一个 CID
+    fn pin(&self, _cid: &str) -> Result<()> {
+        Err(anyhow!("❌ {} 后端不支持 pin", self.name()))
+    }
+
+    // 取消固定，之后节点可以在垃圾回收时删除这些块
+    fn unpin(&self, _cid: &str) -> Result<()> {
+        Err(anyhow!("❌ {} 后端不支持取消 pin", self.name()))
+    }
+
+    // 列出目录内容，路径可以是 CID 或 `<CID>/<子路径>`
+    fn ls(&self, _path: &str) -> Result<Vec<DirEntry>> {
+        Err(anyhow!("❌ {} 后端不支持列出目录", self.name()))
+    }
+
+    // 读取文件内容
+    fn cat(&self, _path: &str) -> Result<Vec<u8>> {
+        Err(anyhow!("❌ {} 后端不支持读取文件", self.name()))
+    }
+
+    // 向 DHT 宣告本节点提供这个 CID；recursive 时宣告 DAG 中的所有块
+    fn provide(&self, _cid: &str, _recursive: bool) -> Result<()> {
+        Err(anyhow!("❌ {} 后端不支持向 DHT 宣告内容", self.name()))
+    }
+
+    // 在 DHT 中查找这个 CID 的提供者，最多 limit 个，返回节点 ID
+    fn find_providers(&self, _cid: &str, _limit: usize) -> Result<Vec<String>> {
+        Err(anyhow!("❌ {} 后端不支持查找提供者", self.name()))
+    }
+
+    // 实际完成这次上传的节点；只有在多个节点之间切换的后端才需要记录
+    fn served_by(&self, _cid: &str) -> Option<String> {
+        None
+    }
+
+    // 上传前加密内容的后端返回算法和密钥指纹，写入清单
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        None
+    }
+
+    // 启动检查时读到的 Kubo 版本，写入清单方便以后用同样的节点版本重现 CID
+    fn node_version(&self) -> Option<String> {
+        None
+    }
+}
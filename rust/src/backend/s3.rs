@@ -0,0 +1,351 @@
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use reqwest::StatusCode;
+use reqwest::blocking::{Body, Client, RequestBuilder, Response};
+use reqwest::header::HeaderMap;
+use sha2::{Digest, Sha256};
+
+use crate::exit_code::ExitCode;
+use crate::hashing::{HashAlgorithm, hash_file, to_hex};
+use crate::throttle::Throttle;
+use crate::timeout::{Operation, TimeoutConfig};
+
+// ✅ 最小的 S3 客户端：path-style 地址 + AWS Signature V4，只实现上传、备份和读取远程来源需要的操作
+#[derive(Debug, Clone)]
+pub struct S3Client {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    access_key: String,
+    secret_key: String,
+    client: Client,
+    throttle: Throttle,
+    timeouts: TimeoutConfig,
+}
+
+impl S3Client {
+    pub fn new(
+        endpoint: &str,
+        region: &str,
+        bucket: &str,
+        access_key: &str,
+        secret_key: &str,
+    ) -> Result<Self> {
+        if bucket.is_empty() || access_key.is_empty() || secret_key.is_empty() {
+            return Err(anyhow!(
+                "❌ S3 的 bucket、access key 和 secret key 都不能为空"
+            ));
+        }
+        Ok(Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            region: region.to_string(),
+            bucket: bucket.to_string(),
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+            client: Client::builder().timeout(None).build()?,
+            throttle: Throttle::default(),
+            timeouts: TimeoutConfig::default(),
+        })
+    }
+
+    // 连接超时在创建客户端时设置，其余按请求设置
+    pub fn with_timeouts(mut self, timeouts: TimeoutConfig) -> Result<Self> {
+        let mut builder = Client::builder().timeout(None);
+        if let Some(limit) = timeouts.get(Operation::Connect) {
+            builder = builder.connect_timeout(limit);
+        }
+        self.client = builder.build()?;
+        self.timeouts = timeouts;
+        Ok(self)
+    }
+
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    // S3 的 PUT 需要 Content-Length，所以用带长度的请求体
+    fn body<R: Read + Send + 'static>(&self, reader: R, length: u64) -> Body {
+        Body::sized(self.throttle.connection().reader(reader), length)
+    }
+
+    // 上传本地文件，metadata 会作为 x-amz-meta-* 头发送，返回响应头
+    pub fn put_file(&self, key: &str, path: &Path, metadata: &[(&str, &str)]) -> Result<HeaderMap> {
+        let payload_hash = hash_file(path, HashAlgorithm::Sha256)?;
+        let payload_hash = payload_hash.trim_start_matches("sha256:");
+        let file = File::open(path)?;
+        let length = file.metadata()?.len();
+        let body = self.body(file, length);
+        let response = self.send("PUT", key, payload_hash, metadata, Some(body))?;
+        Ok(response.headers().clone())
+    }
+
+    pub fn put_bytes(
+        &self,
+        key: &str,
+        data: &[u8],
+        metadata: &[(&str, &str)],
+    ) -> Result<HeaderMap> {
+        let payload_hash = to_hex(&Sha256::digest(data));
+        let body = self.body(Cursor::new(data.to_vec()), data.len() as u64);
+        let response = self.send("PUT", key, &payload_hash, metadata, Some(body))?;
+        Ok(response.headers().clone())
+    }
+
+    pub fn head(&self, key: &str) -> Result<HeaderMap> {
+        let empty_hash = to_hex(&Sha256::digest(b""));
+        let response = self.send("HEAD", key, &empty_hash, &[], None)?;
+        Ok(response.headers().clone())
+    }
+
+    // 对象不存在时返回 None
+    pub fn head_if_exists(&self, key: &str) -> Result<Option<HeaderMap>> {
+        let empty_hash = to_hex(&Sha256::digest(b""));
+        let response = self.request("HEAD", key, &[], &empty_hash, &[], None)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(self.check(response, key)?.headers().clone()))
+    }
+
+    // 读取对象的用户元数据 (S3 为 x-amz-meta-*，GCS 的兼容接口返回 x-goog-meta-*)
+    pub fn user_metadata<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+        ["x-amz-meta-", "x-goog-meta-"]
+            .iter()
+            .find_map(|prefix| headers.get(format!("{}{}", prefix, name)))
+            .and_then(|value| value.to_str().ok())
+    }
+
+    // 列出 prefix 下的所有对象 (ListObjectsV2，自动翻页)
+    pub fn list(&self, prefix: &str) -> Result<Vec<S3Object>> {
+        let empty_hash = to_hex(&Sha256::digest(b""));
+        let mut objects = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+            if let Some(token) = &token {
+                query.push(("continuation-token", token));
+            }
+            let response = self.request("GET", "", &query, &empty_hash, &[], None)?;
+            let body = self.check(response, prefix)?.text()?;
+            objects.extend(body.split("<Contents>").skip(1).filter_map(|entry| {
+                Some(S3Object {
+                    key: xml_unescape(xml_value(entry, "Key")?),
+                    size: xml_value(entry, "Size")?.parse().ok()?,
+                    etag: xml_unescape(xml_value(entry, "ETag").unwrap_or_default())
+                        .trim_matches('"')
+                        .to_string(),
+                })
+            }));
+            match (
+                xml_value(&body, "IsTruncated"),
+                xml_value(&body, "NextContinuationToken"),
+            ) {
+                (Some("true"), Some(next)) => token = Some(xml_unescape(next)),
+                _ => return Ok(objects),
+            }
+        }
+    }
+
+    // 下载对象，响应体可以直接写入文件
+    pub fn get(&self, key: &str) -> Result<Response> {
+        let empty_hash = to_hex(&Sha256::digest(b""));
+        self.send("GET", key, &empty_hash, &[], None)
+    }
+
+    fn send(
+        &self,
+        method: &str,
+        key: &str,
+        payload_hash: &str,
+        metadata: &[(&str, &str)],
+        body: Option<Body>,
+    ) -> Result<Response> {
+        let response = self.request(method, key, &[], payload_hash, metadata, body)?;
+        self.check(response, key)
+    }
+
+    // 签名并发送请求，不检查状态码
+    fn request(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(&str, &str)],
+        payload_hash: &str,
+        metadata: &[(&str, &str)],
+        body: Option<Body>,
+    ) -> Result<Response> {
+        let path = format!("/{}/{}", uri_encode(&self.bucket), uri_encode(key));
+        // 查询参数按名称排序，签名和请求使用同一个字符串
+        let mut query: Vec<String> = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", query_encode(name), query_encode(value)))
+            .collect();
+        query.sort();
+        let query = query.join("&");
+        let url = match query.as_str() {
+            "" => format!("{}{}", self.endpoint, path),
+            query => format!("{}{}?{}", self.endpoint, path, query),
+        };
+        let host = self
+            .endpoint
+            .split_once("://")
+            .map(|(_, host)| host)
+            .unwrap_or(&self.endpoint);
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        // 参与签名的头，按名称排序
+        let mut headers: Vec<(String, String)> = vec![
+            ("host".to_string(), host.to_string()),
+            ("x-amz-content-sha256".to_string(), payload_hash.to_string()),
+            ("x-amz-date".to_string(), amz_date.clone()),
+        ];
+        for (name, value) in metadata {
+            headers.push((
+                format!("x-amz-meta-{}", name.to_lowercase()),
+                value.trim().to_string(),
+            ));
+        }
+        headers.sort();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method, path, query, canonical_headers, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            to_hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let mut signing_key = format!("AWS4{}", self.secret_key).into_bytes();
+        for part in [date.as_str(), self.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part.as_bytes());
+        }
+        let signature = to_hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        let (mut request, operation): (RequestBuilder, _) = match method {
+            "PUT" => (self.client.put(&url), Operation::Add),
+            "HEAD" => (self.client.head(&url), Operation::Request),
+            "GET" if key.is_empty() => (self.client.get(&url), Operation::Request),
+            "GET" => (self.client.get(&url), Operation::Gateway),
+            _ => return Err(anyhow!("不支持的 S3 方法: {}", method)),
+        };
+        request = self.timeouts.apply(request, operation);
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.header(name, value);
+        }
+        request = request.header("authorization", authorization);
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        request
+            .send()
+            .map_err(|e| self.timeouts.check(e, operation, &url))
+    }
+
+    fn check(&self, response: Response, key: &str) -> Result<Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+            "❌ S3 请求失败 ({} {}/{}): {}",
+            status,
+            self.bucket,
+            key,
+            error_message(&body)
+        )))
+    }
+}
+
+// 列表中的一个对象
+#[derive(Debug, Clone)]
+pub struct S3Object {
+    pub key: String,
+    pub size: u64,
+    pub etag: String,
+}
+
+// 取出 <name>...</name> 的内容 (第一个)
+fn xml_value<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let (_, rest) = xml.split_once(&format!("<{}>", name))?;
+    let (value, _) = rest.split_once(&format!("</{}>", name))?;
+    Some(value)
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+// S3 错误是 XML，只取出 <Message> 部分
+fn error_message(body: &str) -> &str {
+    body.split_once("<Message>")
+        .and_then(|(_, rest)| rest.split_once("</Message>"))
+        .map(|(message, _)| message)
+        .unwrap_or(body)
+}
+
+// SigV4 要求的 URI 编码：保留 unreserved 字符和路径分隔符
+fn uri_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// 查询参数的编码：与路径相同，但 / 也要编码
+fn query_encode(value: &str) -> String {
+    uri_encode(value).replace('/', "%2F")
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
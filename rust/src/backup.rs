@@ -0,0 +1,209 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::backend::S3Client;
+use crate::hashing::{HashAlgorithm, hash_file};
+use crate::layout::relative_path;
+use crate::manifest::MANIFEST_FILE_NAME;
+
+// 对象元数据中记录的内容哈希，同步时用来判断是否需要重新上传
+const SHA256_META: &str = "sha256";
+
+// ✅ 备份目标：S3 兼容存储，或 GCS 的 S3 兼容接口 (HMAC 密钥)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupProvider {
+    #[default]
+    S3,
+    Gcs,
+}
+
+impl BackupProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::S3 => "s3",
+            Self::Gcs => "gcs",
+        }
+    }
+
+    // AWS 按区域使用不同的地址；GCS 只有一个地址，区域填 auto
+    fn default_endpoint(&self, region: &str) -> String {
+        match self {
+            Self::S3 => format!("https://s3.{}.amazonaws.com", region),
+            Self::Gcs => "https://storage.googleapis.com".to_string(),
+        }
+    }
+
+    fn default_region(&self) -> &'static str {
+        match self {
+            Self::S3 => "us-east-1",
+            Self::Gcs => "auto",
+        }
+    }
+
+    fn scheme(&self) -> &'static str {
+        match self {
+            Self::S3 => "s3",
+            Self::Gcs => "gs",
+        }
+    }
+}
+
+impl fmt::Display for BackupProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BackupProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "s3" => Ok(Self::S3),
+            "gcs" | "gs" => Ok(Self::Gcs),
+            _ => Err(anyhow!("❌ 未知的备份目标: {} (可选 s3、gcs)", s)),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [backup] 段：把运行目录 (图片、元数据、清单) 同步到 bucket，IPFS 之外再留一份原始内容
+//
+// [backup]
+// provider = "s3"
+// bucket = "my-nft-backups"
+// region = "eu-west-1"
+// access_key = "${AWS_ACCESS_KEY_ID}"
+// secret_key = "${AWS_SECRET_ACCESS_KEY}"
+// prefix = "collections/"
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub provider: BackupProvider,
+    // 留空时按 provider 和 region 生成 (MinIO、R2 等 S3 兼容存储需要填写)
+    pub endpoint: String,
+    // 留空时 s3 为 us-east-1，gcs 为 auto
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    // 对象 key 的前缀，运行目录放在 <prefix><目录名>/ 下
+    pub prefix: String,
+    // 单件和批量流程完成后自动备份
+    pub auto: bool,
+}
+
+impl BackupConfig {
+    fn region(&self) -> &str {
+        match self.region.as_str() {
+            "" => self.provider.default_region(),
+            region => region,
+        }
+    }
+
+    pub fn endpoint(&self) -> String {
+        match self.endpoint.as_str() {
+            "" => self.provider.default_endpoint(self.region()),
+            endpoint => endpoint.to_string(),
+        }
+    }
+
+    pub fn client(&self) -> Result<S3Client> {
+        S3Client::new(
+            &self.endpoint(),
+            self.region(),
+            &self.bucket,
+            &self.access_key,
+            &self.secret_key,
+        )
+    }
+
+    // 检查 [backup] 中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.bucket.is_empty() {
+            problems.push("需要 bucket".to_string());
+        }
+        if self.access_key.is_empty() || self.secret_key.is_empty() {
+            problems.push(
+                "需要 access_key 和 secret_key (可以写成 \"${AWS_ACCESS_KEY_ID}\")".to_string(),
+            );
+        }
+        if !self.prefix.is_empty() && !self.prefix.ends_with('/') {
+            problems.push(format!("prefix 应以 / 结尾: {:?}", self.prefix));
+        }
+        problems
+    }
+}
+
+// 同步结果
+#[derive(Debug, Clone, Default)]
+pub struct BackupReport {
+    // s3://<bucket>/<prefix><目录名>/ 或 gs://...
+    pub location: String,
+    pub uploaded: usize,
+    pub unchanged: usize,
+    pub bytes: u64,
+}
+
+// 运行目录中的所有文件 (相对路径)，跳过隐藏文件；清单放在最后，备份中有清单就说明内容已经完整
+fn backup_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !entry.file_name().to_string_lossy().starts_with('.'));
+    for entry in walker {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(relative_path(entry.path(), dir)?);
+        }
+    }
+    files.sort_by_key(|file| file == MANIFEST_FILE_NAME);
+    Ok(files)
+}
+
+// ✅ 把运行目录同步到 bucket：内容哈希与已有对象相同的文件跳过，只上传新增和变化的文件
+pub fn backup_directory(config: &BackupConfig, dir: &Path, dry_run: bool) -> Result<BackupReport> {
+    let name = dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("❌ 无效的运行目录: {:?}", dir))?;
+    let root = format!("{}{}/", config.prefix, name);
+    let client = config.client()?;
+    let mut report = BackupReport {
+        location: format!("{}://{}/{}", config.provider.scheme(), config.bucket, root),
+        ..BackupReport::default()
+    };
+    println!("\n--- 正在备份 {:?} -> {} ---", dir, report.location);
+    for file in backup_files(dir)? {
+        let path = dir.join(&file);
+        let key = format!("{}{}", root, file);
+        let hash = hash_file(&path, HashAlgorithm::Sha256)?;
+        let hash = hash.trim_start_matches("sha256:");
+        let existing = client.head_if_exists(&key)?;
+        let remote = existing
+            .as_ref()
+            .and_then(|headers| S3Client::user_metadata(headers, SHA256_META));
+        if remote == Some(hash) {
+            report.unchanged += 1;
+            continue;
+        }
+        let size = path.metadata()?.len();
+        if dry_run {
+            println!("📝 将上传 {} ({} 字节)", file, size);
+        } else {
+            client.put_file(&key, &path, &[(SHA256_META, hash)])?;
+            println!("☁️  {} ({} 字节)", file, size);
+        }
+        report.uploaded += 1;
+        report.bytes += size;
+    }
+    Ok(report)
+}
@@ -0,0 +1,493 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use k256::ecdsa::SigningKey;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256, Sha384};
+
+use crate::arweave::{ArweaveConfig, ArweaveUris, ar_uri};
+use crate::cid::Multibase;
+use crate::contract::keccak256;
+use crate::eth::{signer_address, signing_key};
+use crate::exit_code::ExitCode;
+use crate::links::token_uri;
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::patch::metadata_path;
+use crate::unixfs::put_varint;
+
+// ANS-104 data item 的签名类型：以太坊 (secp256k1，EIP-191)
+const ETHEREUM_SIGNATURE_TYPE: u16 = 3;
+const APP_NAME: &str = "polyglot-ipfs-uploader";
+
+// Arweave 的 deep hash：字节串和列表分别带上 "blob" / "list" 与长度，逐层用 SHA-384 汇总
+enum DeepHashChunk<'a> {
+    Blob(&'a [u8]),
+    List(Vec<DeepHashChunk<'a>>),
+}
+
+fn sha384(parts: &[&[u8]]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn deep_hash(chunk: &DeepHashChunk) -> [u8; 48] {
+    match chunk {
+        DeepHashChunk::Blob(data) => {
+            let tag = sha384(&[format!("blob{}", data.len()).as_bytes()]);
+            sha384(&[&tag, &sha384(&[data])])
+        }
+        DeepHashChunk::List(items) => {
+            let mut acc = sha384(&[format!("list{}", items.len()).as_bytes()]);
+            for item in items {
+                acc = sha384(&[&acc, &deep_hash(item)]);
+            }
+            acc
+        }
+    }
+}
+
+// Avro long：zigzag 后按 varint 写入
+fn put_avro_long(out: &mut Vec<u8>, value: i64) {
+    put_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn put_avro_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    put_avro_long(out, bytes.len() as i64);
+    out.extend_from_slice(bytes);
+}
+
+// data item 中的标签：Avro 编码的 [{name, value}] 数组
+fn encode_tags(tags: &[(&str, String)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    if tags.is_empty() {
+        return out;
+    }
+    put_avro_long(&mut out, tags.len() as i64);
+    for (name, value) in tags {
+        put_avro_bytes(&mut out, name.as_bytes());
+        put_avro_bytes(&mut out, value.as_bytes());
+    }
+    put_avro_long(&mut out, 0);
+    out
+}
+
+// ✅ 签名后的 ANS-104 data item，id 是签名的 SHA-256 (base64url)
+pub struct DataItem {
+    pub id: String,
+    pub bytes: Vec<u8>,
+}
+
+// data item 中被签名的内容：各字段的 deep hash，没有的 target / anchor 为空
+fn signing_message(owner: &[u8], anchor: &[u8], raw_tags: &[u8], data: &[u8]) -> [u8; 48] {
+    let signature_type = ETHEREUM_SIGNATURE_TYPE.to_string();
+    deep_hash(&DeepHashChunk::List(vec![
+        DeepHashChunk::Blob(b"dataitem"),
+        DeepHashChunk::Blob(b"1"),
+        DeepHashChunk::Blob(signature_type.as_bytes()),
+        DeepHashChunk::Blob(owner),
+        DeepHashChunk::Blob(&[]),
+        DeepHashChunk::Blob(anchor),
+        DeepHashChunk::Blob(raw_tags),
+        DeepHashChunk::Blob(data),
+    ]))
+}
+
+// EIP-191 personal_sign 的哈希，与 Irys 的 EthereumSigner 一致
+fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(&prefixed)
+}
+
+// 用以太坊私钥签名一个 data item (没有 target 和 anchor)
+pub fn sign_data_item(key: &SigningKey, data: &[u8], tags: &[(&str, String)]) -> Result<DataItem> {
+    let owner = key.verifying_key().to_encoded_point(false);
+    let owner = owner.as_bytes();
+    let raw_tags = encode_tags(tags);
+    let message = signing_message(owner, &[], &raw_tags, data);
+    let (signature, recovery) = key
+        .sign_prehash_recoverable(&personal_message_hash(&message))
+        .map_err(|e| anyhow!("❌ 签名 data item 失败: {}", e))?;
+    let mut signature = signature.to_bytes().to_vec();
+    signature.push(27 + recovery.to_byte());
+
+    let mut bytes = Vec::with_capacity(data.len() + raw_tags.len() + 160);
+    bytes.extend_from_slice(&ETHEREUM_SIGNATURE_TYPE.to_le_bytes());
+    bytes.extend_from_slice(&signature);
+    bytes.extend_from_slice(owner);
+    // target 和 anchor 都不带
+    bytes.extend_from_slice(&[0, 0]);
+    bytes.extend_from_slice(&(tags.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&(raw_tags.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&raw_tags);
+    bytes.extend_from_slice(data);
+
+    Ok(DataItem {
+        id: Multibase::Base64Url.encode(&Sha256::digest(&signature)),
+        bytes,
+    })
+}
+
+#[derive(Deserialize)]
+struct UploadResponse {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct BalanceResponse {
+    balance: String,
+}
+
+// ✅ Irys (Bundlr) 上传节点：余额、报价和上传 data item
+pub struct IrysClient<'a> {
+    config: &'a ArweaveConfig,
+    key: SigningKey,
+    client: Client,
+}
+
+impl<'a> IrysClient<'a> {
+    pub fn new(config: &'a ArweaveConfig) -> Result<Self> {
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(None)
+            .build()?;
+        Ok(Self {
+            config,
+            key: signing_key(&config.private_key)?,
+            client,
+        })
+    }
+
+    pub fn address(&self) -> String {
+        signer_address(&self.key)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.config.node.trim_end_matches('/'), path)
+    }
+
+    fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request.send()?;
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            return Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+                "❌ Irys 请求失败 ({}): {}",
+                status,
+                body.trim()
+            )));
+        }
+        serde_json::from_str(&body).map_err(|e| anyhow!("❌ Irys 返回无法解析: {}", e))
+    }
+
+    // 账户余额 (币种的最小单位)
+    pub fn balance(&self) -> Result<u128> {
+        let url = self.url(&format!("account/balance/{}", self.config.currency));
+        let response: BalanceResponse =
+            self.send(self.client.get(url).query(&[("address", self.address())]))?;
+        response
+            .balance
+            .parse()
+            .map_err(|_| anyhow!("❌ Irys 返回的余额无法解析: {}", response.balance))
+    }
+
+    // 上传 bytes 字节的价格 (币种的最小单位)
+    pub fn price(&self, bytes: u64) -> Result<u128> {
+        let url = self.url(&format!("price/{}/{}", self.config.currency, bytes));
+        let price: serde_json::Value = self.send(self.client.get(url))?;
+        match &price {
+            serde_json::Value::Number(n) => n.as_u64().map(u128::from),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow!("❌ Irys 返回的价格无法解析: {}", price))
+    }
+
+    // 签名并上传，返回 Arweave 交易 ID
+    pub fn upload(&self, data: &[u8], tags: &[(&str, String)]) -> Result<String> {
+        let item = sign_data_item(&self.key, data, tags)?;
+        let url = self.url(&format!("tx/{}", self.config.currency));
+        let request = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/octet-stream")
+            .body(item.bytes);
+        let response: UploadResponse = self.send(request)?;
+        if response.id != item.id {
+            return Err(anyhow!(
+                "❌ Irys 返回的 ID {} 与本地计算的 {} 不一致",
+                response.id,
+                item.id
+            ));
+        }
+        Ok(response.id)
+    }
+}
+
+// 镜像结果
+#[derive(Debug, Clone, Default)]
+pub struct MirrorReport {
+    pub uploaded: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub bytes: u64,
+}
+
+// 一个要上传的文件：token 序号、是否为元数据、本地路径和对应的 IPFS 地址
+struct MirrorFile {
+    index: usize,
+    metadata: bool,
+    path: PathBuf,
+    ipfs_uri: String,
+    content_type: String,
+}
+
+fn pending_files(manifest: &Manifest, dir: &Path, force: bool) -> Vec<MirrorFile> {
+    let images_dir = match manifest.kind {
+        CollectionKind::Single => dir.to_path_buf(),
+        _ => dir.join("images"),
+    };
+    let mirrored = |token: &TokenEntry, metadata: bool| {
+        let uris = token.arweave.as_ref();
+        let uri = match metadata {
+            true => uris.and_then(|u| u.metadata_uri.as_ref()),
+            false => uris.and_then(|u| u.image_uri.as_ref()),
+        };
+        uri.is_some() && !force
+    };
+    let mut files = Vec::new();
+    for (index, token) in manifest.tokens.iter().enumerate() {
+        if !mirrored(token, false) {
+            files.push(MirrorFile {
+                index,
+                metadata: false,
+                path: images_dir.join(&token.image_file),
+                ipfs_uri: token
+                    .animation_uri
+                    .clone()
+                    .unwrap_or_else(|| token.image_uri.clone()),
+                content_type: token
+                    .mime_type
+                    .clone()
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+            });
+        }
+        if !mirrored(token, true) {
+            files.push(MirrorFile {
+                index,
+                metadata: true,
+                path: metadata_path(dir, manifest.kind, token),
+                ipfs_uri: token_uri(manifest, token),
+                content_type: "application/json".to_string(),
+            });
+        }
+    }
+    files
+}
+
+// ✅ 把清单中每个 token 的图片 (主文件) 和元数据镜像到 Arweave，ar:// 地址写回清单
+// 已经镜像过的文件跳过，force 时重新上传；上传前按报价检查 Irys 余额
+pub fn mirror_manifest(
+    config: &ArweaveConfig,
+    manifest_path: &Path,
+    force: bool,
+) -> Result<(Manifest, MirrorReport)> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let client = IrysClient::new(config)?;
+    let files = pending_files(&manifest, dir, force);
+    let mut report = MirrorReport {
+        skipped: manifest.tokens.len() * 2 - files.len(),
+        ..MirrorReport::default()
+    };
+    if files.is_empty() {
+        return Ok((manifest, report));
+    }
+
+    let total: u64 = files
+        .iter()
+        .map(|file| fs::metadata(&file.path).map(|m| m.len()))
+        .sum::<std::io::Result<u64>>()
+        .map_err(|e| anyhow!("❌ 读取要镜像的文件失败: {}", e))?;
+    let (price, balance) = (client.price(total)?, client.balance()?);
+    println!(
+        "\n--- 正在镜像到 Arweave ({}，{} 个文件，{} 字节) ---",
+        config.node,
+        files.len(),
+        total
+    );
+    println!(
+        "   - 付款地址: {} ({})，报价 {}，余额 {}",
+        client.address(),
+        config.currency,
+        price,
+        balance
+    );
+    if balance < price {
+        return Err(anyhow!(
+            "❌ Irys 余额不足：需要 {}，当前 {} ({})，请先为 {} 充值",
+            price,
+            balance,
+            config.currency,
+            client.address()
+        ));
+    }
+
+    for file in files {
+        let token = &manifest.tokens[file.index];
+        let data = fs::read(&file.path)?;
+        let tags = [
+            ("Content-Type", file.content_type.clone()),
+            ("App-Name", APP_NAME.to_string()),
+            ("IPFS-URI", file.ipfs_uri.clone()),
+        ];
+        match client.upload(&data, &tags) {
+            Ok(id) => {
+                println!("🗄️  {} -> {}", file.path.display(), ar_uri(&id));
+                let uris = manifest.tokens[file.index]
+                    .arweave
+                    .get_or_insert_with(ArweaveUris::default);
+                match file.metadata {
+                    true => uris.metadata_uri = Some(ar_uri(&id)),
+                    false => uris.image_uri = Some(ar_uri(&id)),
+                }
+                report.uploaded += 1;
+                report.bytes += data.len() as u64;
+            }
+            Err(e) => {
+                report.failed += 1;
+                eprintln!(
+                    "❌ {} ({}) 镜像失败: {:#}",
+                    token.name,
+                    file.path.display(),
+                    e
+                );
+            }
+        }
+    }
+    manifest.save(dir)?;
+    Ok((manifest, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    use super::*;
+    use crate::contract::parse_hex;
+
+    // bundlr-sdk 0.5.0 的 res/test_bundles/ethereum_sig：arbundles 的 EthereumSigner 生成的 bundle，
+    // 其中唯一的 data item 带一个 Content-Type 标签和 anchor
+    const ARBUNDLES_ITEM_ID: &str =
+        "e7912d3831cfeca1abd8ac4e02e55f4a4245f5b76e7fc8fc9f83d269074593fc";
+    const ARBUNDLES_ITEM: &str = concat!(
+        "03007c254d3dbf653c9f1d95c8419c987987cb104534da55f2977c6258202dbd2b105c2b4b7020f61bda612832789454",
+        "224d9427024a5eec5cb914d14183ceab06831b0418ab373fe4c8a3e7eadd5bd9abdaaac0408083ee0cf54ca702ca5c6a",
+        "963493bdb9be29da34cf9d24aae084e53032f95b995b23bb4d2d0165cd43047ce3b04e4f0001643478477152707a5032",
+        "776c307a49525464593473624a34635130766341757801000000000000001a000000000000000218436f6e74656e742d",
+        "5479706514746578742f706c61696e0048656c6c6f2c2042756e646c7221",
+    );
+
+    struct ParsedItem<'a> {
+        signature: &'a [u8],
+        owner: &'a [u8],
+        anchor: &'a [u8],
+        tag_count: u64,
+        raw_tags: &'a [u8],
+        data: &'a [u8],
+    }
+
+    // 按 ANS-104 的布局拆开以太坊签名的 data item
+    fn parse_item(bytes: &[u8]) -> ParsedItem<'_> {
+        assert_eq!(bytes[..2], ETHEREUM_SIGNATURE_TYPE.to_le_bytes());
+        let (signature, rest) = bytes[2..].split_at(65);
+        let (owner, rest) = rest.split_at(65);
+        let optional = |rest: &[u8]| -> (usize, usize) {
+            match rest[0] {
+                0 => (0, 1),
+                1 => (32, 33),
+                b => panic!("invalid presence byte {}", b),
+            }
+        };
+        let (_, skip) = optional(rest);
+        let rest = &rest[skip..];
+        let (len, skip) = optional(rest);
+        let anchor = &rest[1..1 + len];
+        let rest = &rest[skip..];
+        let tag_count = u64::from_le_bytes(rest[..8].try_into().unwrap());
+        let tags_len = u64::from_le_bytes(rest[8..16].try_into().unwrap()) as usize;
+        let (raw_tags, data) = rest[16..].split_at(tags_len);
+        ParsedItem {
+            signature,
+            owner,
+            anchor,
+            tag_count,
+            raw_tags,
+            data,
+        }
+    }
+
+    // 用签名恢复出的公钥必须就是 owner
+    fn assert_signed_by_owner(item: &ParsedItem) {
+        let message = signing_message(item.owner, item.anchor, item.raw_tags, item.data);
+        let signature = Signature::from_slice(&item.signature[..64]).unwrap();
+        let recovery = RecoveryId::from_byte(item.signature[64] - 27).unwrap();
+        let key = VerifyingKey::recover_from_prehash(
+            &personal_message_hash(&message),
+            &signature,
+            recovery,
+        )
+        .unwrap();
+        assert_eq!(key.to_encoded_point(false).as_bytes(), item.owner);
+    }
+
+    #[test]
+    fn verifies_arbundles_data_item() {
+        let bytes = parse_hex(ARBUNDLES_ITEM).unwrap();
+        let item = parse_item(&bytes);
+        assert_eq!(item.anchor, b"d4xGqRpzP2wl0zIRTdY4sbJ4cQ0vcAux");
+        assert_eq!(item.tag_count, 1);
+        assert_eq!(
+            item.raw_tags,
+            encode_tags(&[("Content-Type", "text/plain".to_string())])
+        );
+        assert_eq!(item.data, b"Hello, Bundlr!");
+        assert_signed_by_owner(&item);
+        assert_eq!(
+            Sha256::digest(item.signature).as_slice(),
+            parse_hex(ARBUNDLES_ITEM_ID).unwrap()
+        );
+    }
+
+    // 固定私钥、标签和数据：签名是确定性的 (RFC 6979)，id 作为回归值固定下来
+    #[test]
+    fn signs_data_item_with_fixed_key() {
+        let key = signing_key("0x4646464646464646464646464646464646464646464646464646464646464646")
+            .unwrap();
+        let tags = [
+            ("Content-Type", "application/json".to_string()),
+            ("App-Name", APP_NAME.to_string()),
+        ];
+        let data = br#"{"name":"MetaCore #1"}"#;
+        let signed = sign_data_item(&key, data, &tags).unwrap();
+
+        let item = parse_item(&signed.bytes);
+        assert!(item.anchor.is_empty());
+        assert_eq!(item.tag_count, 2);
+        assert_eq!(item.raw_tags, encode_tags(&tags));
+        assert_eq!(item.data, data);
+        assert_signed_by_owner(&item);
+        assert_eq!(
+            Multibase::Base64Url.encode(&Sha256::digest(item.signature)),
+            signed.id
+        );
+        assert_eq!(signed.id, "AOy1afSA3XGnL5VqB9gcCFbbozAAwYqyB_ffXIagyXI");
+    }
+}
@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::ipld::DagCodec;
+use crate::manifest::EncryptionInfo;
+use crate::unixfs::AddConfig;
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+// 当前上传阶段是否已经有上传完成，失败时据此返回 PartialUpload 退出码
+pub(crate) static UPLOADED: AtomicBool = AtomicBool::new(false);
+
+// 第二次中断时的退出码 (128 + SIGINT)
+const FORCED_EXIT_CODE: i32 = 130;
+
+// ✅ 监听 Ctrl-C (SIGINT) 和 SIGTERM：第一次只设置取消标记，让正在进行的上传完成；第二次立即退出
+pub fn install_handler() -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    thread::Builder::new()
+        .name("signal".to_string())
+        .spawn(move || {
+            runtime.block_on(async {
+                #[cfg(unix)]
+                let mut terminate =
+                    match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                        Ok(signal) => signal,
+                        Err(e) => {
+                            eprintln!("⚠️  无法监听 SIGTERM: {}", e);
+                            return;
+                        }
+                    };
+                loop {
+                    #[cfg(unix)]
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {}
+                        _ = terminate.recv() => {}
+                    }
+                    #[cfg(not(unix))]
+                    if tokio::signal::ctrl_c().await.is_err() {
+                        return;
+                    }
+
+                    if CANCELLED.swap(true, Ordering::SeqCst) {
+                        eprintln!("\n🛑 再次收到中断信号，立即退出");
+                        std::process::exit(FORCED_EXIT_CODE);
+                    }
+                    eprintln!(
+                        "\n⚠️  收到中断信号：不再开始新的上传，正在进行的上传完成后退出 (再按一次 Ctrl-C 立即退出)"
+                    );
+                }
+            });
+        })?;
+    Ok(())
+}
+
+pub fn is_cancelled() -> bool {
+    CANCELLED.load(Ordering::SeqCst)
+}
+
+pub fn has_uploads() -> bool {
+    UPLOADED.load(Ordering::SeqCst)
+}
+
+// ✅ 上传阶段成功结束后调用：之后的步骤 (写报告、设置 Base URI 等) 失败不再算部分上传
+pub fn finish_uploads() {
+    UPLOADED.store(false, Ordering::SeqCst);
+}
+
+// 在开始一项新工作前调用
+pub fn check() -> Result<()> {
+    if is_cancelled() {
+        return Err(anyhow!("❌ 已取消：不再开始新的上传"));
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct CancelledUpload {
+    // 上传的文件或文件夹；add_bytes 上传的内容 (如元数据 JSON) 为空
+    source: Option<PathBuf>,
+    cid: String,
+}
+
+// 中断后写入输出目录的记录：已经完成的上传，以及其中被取消固定的 CID
+#[derive(Serialize, Debug)]
+struct CancelReport {
+    cancelled_at: String,
+    uploads: Vec<CancelledUpload>,
+    unpinned: Vec<String>,
+}
+
+// ✅ 记录每次成功的上传；收到中断信号后拒绝新的上传，
+// 程序退出时把已完成的上传写入 cancelled_<时间>.json，并按 --unpin-on-cancel 取消固定这些内容
+pub struct CancellableBackend {
+    inner: Box<dyn IpfsBackend>,
+    report_dir: PathBuf,
+    unpin_on_cancel: bool,
+    uploads: Mutex<Vec<CancelledUpload>>,
+}
+
+impl CancellableBackend {
+    pub fn new(inner: Box<dyn IpfsBackend>, report_dir: &Path, unpin_on_cancel: bool) -> Self {
+        Self {
+            inner,
+            report_dir: report_dir.to_path_buf(),
+            unpin_on_cancel,
+            uploads: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, source: Option<&Path>, cid: &str) {
+        UPLOADED.store(true, Ordering::SeqCst);
+        self.uploads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(CancelledUpload {
+                source: source.map(Path::to_path_buf),
+                cid: cid.to_string(),
+            });
+    }
+
+    fn finish(&self) -> Result<PathBuf> {
+        let uploads = std::mem::take(&mut *self.uploads.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut unpinned = Vec::new();
+        if self.unpin_on_cancel {
+            // 后上传的文件夹引用先上传的文件，倒序取消
+            for upload in uploads.iter().rev() {
+                match self.inner.unpin(&upload.cid) {
+                    Ok(()) => unpinned.push(upload.cid.clone()),
+                    Err(e) => eprintln!("⚠️  取消固定 {} 失败: {:#}", upload.cid, e),
+                }
+            }
+            println!("🧹 已取消固定 {} 个部分上传的内容", unpinned.len());
+        }
+        let now = Local::now();
+        let report = CancelReport {
+            cancelled_at: now.to_rfc3339(),
+            uploads,
+            unpinned,
+        };
+        fs::create_dir_all(&self.report_dir)?;
+        let path = self
+            .report_dir
+            .join(format!("cancelled_{}.json", now.format("%Y%m%d_%H%M%S")));
+        fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+        Ok(path)
+    }
+}
+
+impl Drop for CancellableBackend {
+    fn drop(&mut self) {
+        if !is_cancelled() {
+            return;
+        }
+        match self.finish() {
+            Ok(path) => println!("📄 已完成的上传记录在: {:?}", path),
+            Err(e) => eprintln!("⚠️  写入中断记录失败: {:#}", e),
+        }
+    }
+}
+
+impl IpfsBackend for CancellableBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        self.inner.check_compatibility()
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        check()?;
+        let cid = self.inner.add_path(target_path)?;
+        self.record(Some(target_path), &cid);
+        Ok(cid)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        check()?;
+        let cid = self.inner.add_bytes(data)?;
+        self.record(None, &cid);
+        Ok(cid)
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        check()?;
+        let cid = self.inner.dag_put(node, codec)?;
+        self.record(None, &cid);
+        Ok(cid)
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.inner.add_config()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        self.inner.is_pinned(cid)
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        check()?;
+        self.inner.pin(cid)
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.inner.unpin(cid)
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        self.inner.ls(path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.cat(path)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        self.inner.provide(cid, recursive)
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        self.inner.find_providers(cid, limit)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.inner.served_by(cid)
+    }
+
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        self.inner.encryption()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.inner.node_version()
+    }
+}
@@ -0,0 +1,170 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::unixfs::{Cid, ImportOptions, import_file, import_path, put_varint};
+
+// CAR 打包结果
+#[derive(Debug, Clone)]
+pub struct CarSummary {
+    pub root: Cid,
+    pub blocks: u64,
+    pub bytes: u64,
+}
+
+// CARv1 头部: dag-cbor 编码的 {"roots": [root], "version": 1}
+fn car_header(root: &Cid) -> Vec<u8> {
+    let mut cid = vec![0x00];
+    cid.extend(root.to_bytes());
+
+    let mut header = vec![0xa2];
+    header.push(0x65);
+    header.extend_from_slice(b"roots");
+    header.extend_from_slice(&[0x81, 0xd8, 0x2a, 0x58, cid.len() as u8]);
+    header.extend(cid);
+    header.push(0x67);
+    header.extend_from_slice(b"version");
+    header.push(0x01);
+
+    let mut out = Vec::new();
+    put_varint(&mut out, header.len() as u64);
+    out.extend(header);
+    out
+}
+
+// 一个块: varint(CID 长度 + 数据长度) | CID | 数据
+fn write_car_block(writer: &mut impl Write, cid: &Cid, data: &[u8]) -> io::Result<()> {
+    let cid = cid.to_bytes();
+    let mut prefix = Vec::with_capacity(10);
+    put_varint(&mut prefix, (cid.len() + data.len()) as u64);
+    writer.write_all(&prefix)?;
+    writer.write_all(&cid)?;
+    writer.write_all(data)
+}
+
+// ✅ 流式生成 CAR 文件：块边生成边写入临时文件，内存中只保留少量链接
+// CAR 头部需要根 CID，所以最后再把头部和临时文件拼成最终的 CAR
+pub fn write_car(source: &Path, car_path: &Path, options: &ImportOptions) -> Result<CarSummary> {
+    let spill_path = PathBuf::from(format!("{}.blocks.tmp", car_path.display()));
+    let result = write_car_with_spill(source, car_path, &spill_path, options);
+    let _ = fs::remove_file(&spill_path);
+    result
+}
+
+fn write_car_with_spill(
+    source: &Path,
+    car_path: &Path,
+    spill_path: &Path,
+    options: &ImportOptions,
+) -> Result<CarSummary> {
+    if let Some(parent) = car_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut spill = BufWriter::new(File::create(spill_path)?);
+    let (mut blocks, mut bytes) = (0u64, 0u64);
+    let root = import_path(source, options, &mut |block| {
+        write_car_block(&mut spill, &block.cid, &block.data)?;
+        blocks += 1;
+        bytes += block.data.len() as u64;
+        Ok(())
+    })?;
+    spill.flush()?;
+    drop(spill);
+
+    let mut car = BufWriter::new(File::create(car_path)?);
+    car.write_all(&car_header(&root.cid))?;
+    io::copy(&mut File::open(spill_path)?, &mut car)?;
+    car.flush()?;
+
+    Ok(CarSummary {
+        root: root.cid,
+        blocks,
+        bytes,
+    })
+}
+
+// 把内存中的数据 (如单件的元数据 JSON) 作为一个文件打包成 CAR，块不多，直接在内存中收集
+pub fn write_car_bytes(
+    data: &[u8],
+    car_path: &Path,
+    options: &ImportOptions,
+) -> Result<CarSummary> {
+    let mut blocks = Vec::new();
+    let root = import_file(data, options, &mut |block| {
+        blocks.push(block);
+        Ok(())
+    })?;
+    if let Some(parent) = car_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut car = BufWriter::new(File::create(car_path)?);
+    car.write_all(&car_header(&root.cid))?;
+    let mut bytes = 0u64;
+    for block in &blocks {
+        write_car_block(&mut car, &block.cid, &block.data)?;
+        bytes += block.data.len() as u64;
+    }
+    car.flush()?;
+
+    Ok(CarSummary {
+        root: root.cid,
+        blocks: blocks.len() as u64,
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::unixfs::{Block, compute_cid, read_varint};
+
+    // 8 字节一个分块、每块内容不同：12 万个叶子加上 695 个中间节点，超过 10 万个块
+    const CHUNKS: u64 = 120_000;
+
+    #[test]
+    fn streams_more_than_100k_blocks() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("synthetic.bin");
+        let mut writer = BufWriter::new(File::create(&source).unwrap());
+        for i in 0..CHUNKS {
+            writer.write_all(&i.to_le_bytes()).unwrap();
+        }
+        writer.into_inner().unwrap();
+        let options = ImportOptions {
+            chunk_size: 8,
+            max_links: 174,
+        };
+
+        let car_path = temp.path().join("out/synthetic.car");
+        let summary = write_car(&source, &car_path, &options).unwrap();
+        assert_eq!(summary.root, compute_cid(&source, &options).unwrap());
+        // 叶子 120000 个，按 174 个一组：690 个、4 个、1 个根节点
+        assert_eq!(summary.blocks, CHUNKS + 690 + 4 + 1);
+        assert!(summary.bytes > CHUNKS * 8);
+        assert!(!PathBuf::from(format!("{}.blocks.tmp", car_path.display())).exists());
+
+        // 头部：varint 长度 + 58 字节 dag-cbor (根 CID 36 字节)
+        let car = fs::read(&car_path).unwrap();
+        let header = car_header(&summary.root);
+        assert_eq!(header.len(), 59);
+        assert_eq!(&car[..header.len()], header.as_slice());
+
+        // 逐块读回：每个 CID 与内容一致，数量与 summary 相同，根节点在其中
+        let mut reader = &car[header.len()..];
+        let mut cids = HashSet::new();
+        while !reader.is_empty() {
+            let len = read_varint(&mut reader).unwrap() as usize;
+            let (section, rest) = reader.split_at(len);
+            let cid = Cid::from_bytes(&section[..36]).unwrap();
+            assert_eq!(Block::new(cid.codec, section[36..].to_vec()).cid, cid);
+            cids.insert(cid);
+            reader = rest;
+        }
+        assert_eq!(cids.len() as u64, summary.blocks);
+        assert!(cids.contains(&summary.root));
+    }
+}
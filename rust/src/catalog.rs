@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::expiry::ExpiryRecord;
+use crate::filecoin::FilecoinArchive;
+
+pub const CATALOG_FILE_NAME: &str = "catalog.json";
+
+// 一次 pin 状态查询的结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinRecord {
+    pub pinned: bool,
+    pub checked_at: String,
+}
+
+// ✅ 输出目录下的 catalog.json：跨多次运行共享的本地状态
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Catalog {
+    // CID -> 最近一次 pin 状态查询
+    pub pin_status: BTreeMap<String, PinRecord>,
+    // 根 CID -> Filecoin 归档和交易状态，同一内容不会重复提交
+    pub filecoin: BTreeMap<String, FilecoinArchive>,
+    // 运行目录 -> 过期时间和要清理的 CID (--expires、`pins expire`)
+    pub expiry: BTreeMap<String, ExpiryRecord>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Catalog {
+    // 文件不存在时返回空的 catalog
+    pub fn open(output_root: &Path) -> Result<Self> {
+        let path = output_root.join(CATALOG_FILE_NAME);
+        let mut catalog: Self = if path.is_file() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Self::default()
+        };
+        catalog.path = path;
+        Ok(catalog)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // 在 TTL 内的缓存结果
+    pub fn cached_pin_status(&self, cid: &str, ttl: Duration) -> Option<bool> {
+        let record = self.pin_status.get(cid)?;
+        let checked_at = DateTime::parse_from_rfc3339(&record.checked_at).ok()?;
+        let age = (Utc::now() - checked_at.with_timezone(&Utc))
+            .to_std()
+            .ok()?;
+        (age < ttl).then_some(record.pinned)
+    }
+
+    pub fn record_pin_status(&mut self, cid: &str, pinned: bool) {
+        self.pin_status.insert(
+            cid.to_string(),
+            PinRecord {
+                pinned,
+                checked_at: Utc::now().to_rfc3339(),
+            },
+        );
+    }
+}
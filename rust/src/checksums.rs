@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::gallery::GALLERY_DIR;
+use crate::hashing::{HashAlgorithm, hash_file};
+use crate::layout::relative_path;
+use crate::manifest::MANIFEST_FILE_NAME;
+use crate::report::{REPORT_HTML_FILE, REPORT_MARKDOWN_FILE};
+use crate::runbook::RUNBOOK_FILE_NAME;
+use crate::signing::SIGNATURE_FILE_NAME;
+use crate::storage_deal::CAR_DIR;
+
+// 与 `sha256sum -c` / `b3sum -c` 兼容的校验文件
+pub const SHA256_FILE: &str = "CHECKSUMS.sha256";
+pub const BLAKE3_FILE: &str = "CHECKSUMS.b3";
+
+// ✅ config.toml 中的 [checksums] 段：单件和批量流程在输出目录中写入校验文件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ChecksumConfig {
+    // 写入 CHECKSUMS.sha256
+    pub enabled: bool,
+    // 另外写入 CHECKSUMS.b3 (BLAKE3)
+    pub blake3: bool,
+}
+
+impl Default for ChecksumConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            blake3: false,
+        }
+    }
+}
+
+impl ChecksumConfig {
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        let mut algorithms = Vec::new();
+        if self.enabled {
+            algorithms.push(HashAlgorithm::Sha256);
+            if self.blake3 {
+                algorithms.push(HashAlgorithm::Blake3);
+            }
+        }
+        algorithms
+    }
+}
+
+fn checksum_file(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Sha256 => SHA256_FILE,
+        HashAlgorithm::Blake3 => BLAKE3_FILE,
+    }
+}
+
+// 校验文件、清单、签名、报告、操作手册、预览页面和 Filecoin 归档的 CAR 不在校验范围内
+// (之后可能被其它命令更新或生成)
+fn is_covered(relative: &str) -> bool {
+    !matches!(
+        relative,
+        SHA256_FILE
+            | BLAKE3_FILE
+            | MANIFEST_FILE_NAME
+            | SIGNATURE_FILE_NAME
+            | REPORT_MARKDOWN_FILE
+            | REPORT_HTML_FILE
+            | RUNBOOK_FILE_NAME
+    ) && !Path::new(relative).starts_with(CAR_DIR)
+        && !Path::new(relative).starts_with(GALLERY_DIR)
+}
+
+// 输出目录中需要校验的所有文件 (相对路径，按路径排序)，跳过隐藏文件
+fn covered_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !entry.file_name().to_string_lossy().starts_with('.'));
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = relative_path(entry.path(), dir)?;
+        if is_covered(&relative) {
+            files.push(relative);
+        }
+    }
+    Ok(files)
+}
+
+fn hex_digest(path: &Path, algorithm: HashAlgorithm) -> Result<String> {
+    let hash = hash_file(path, algorithm)?;
+    Ok(hash
+        .split_once(':')
+        .map_or(hash.as_str(), |(_, hex)| hex)
+        .to_string())
+}
+
+// ✅ 为输出目录中的每个图片和元数据文件写入校验文件，格式为 "<十六进制>  <相对路径>"
+pub fn write_checksums(dir: &Path, config: &ChecksumConfig) -> Result<Vec<PathBuf>> {
+    write_checksum_files(dir, &config.algorithms())
+}
+
+// ✅ 改写了输出目录中的文件后，按已有的校验文件重新生成 (没有校验文件时不写入)
+pub fn refresh_checksums(dir: &Path) -> Result<Vec<PathBuf>> {
+    let algorithms: Vec<HashAlgorithm> = [HashAlgorithm::Sha256, HashAlgorithm::Blake3]
+        .into_iter()
+        .filter(|algorithm| dir.join(checksum_file(*algorithm)).is_file())
+        .collect();
+    write_checksum_files(dir, &algorithms)
+}
+
+fn write_checksum_files(dir: &Path, algorithms: &[HashAlgorithm]) -> Result<Vec<PathBuf>> {
+    if algorithms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let files = covered_files(dir)?;
+    let mut written = Vec::new();
+    for &algorithm in algorithms {
+        let mut content = String::new();
+        for file in &files {
+            content.push_str(&format!(
+                "{}  {}\n",
+                hex_digest(&dir.join(file), algorithm)?,
+                file
+            ));
+        }
+        let path = dir.join(checksum_file(algorithm));
+        fs::write(&path, content).map_err(|e| anyhow!("❌ 写入 {:?} 失败: {}", path, e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// 读取校验文件：相对路径 -> 十六进制哈希值
+fn read_checksums(path: &Path) -> Result<BTreeMap<String, String>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?;
+    let mut entries = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // 二进制模式下文件名前是 "*"
+        let (hex, file) = line
+            .split_once("  ")
+            .or_else(|| line.split_once(" *"))
+            .ok_or_else(|| anyhow!("❌ {:?} 第 {} 行格式错误: {}", path, index + 1, line))?;
+        entries.insert(file.to_string(), hex.trim().to_lowercase());
+    }
+    Ok(entries)
+}
+
+// 校验的结果，路径都相对于输出目录
+#[derive(Debug, Clone, Default)]
+pub struct ChecksumReport {
+    // 使用的校验文件
+    pub sources: Vec<String>,
+    pub checked: usize,
+    // 内容与记录不一致的文件
+    pub mismatched: Vec<String>,
+    // 记录中有、目录中没有的文件
+    pub missing: Vec<String>,
+    // 目录中有、记录中没有的文件
+    pub unlisted: Vec<String>,
+}
+
+impl ChecksumReport {
+    pub fn is_ok(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty() && self.unlisted.is_empty()
+    }
+}
+
+// ✅ 按输出目录中的校验文件重新检查每个文件 (有两个校验文件时都检查)
+pub fn verify_checksums(dir: &Path) -> Result<ChecksumReport> {
+    let mut report = ChecksumReport::default();
+    let present = covered_files(dir)?;
+    let mut checked = BTreeSet::new();
+    for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+        let path = dir.join(checksum_file(algorithm));
+        if !path.is_file() {
+            continue;
+        }
+        report.sources.push(checksum_file(algorithm).to_string());
+        let expected = read_checksums(&path)?;
+        for (file, hex) in &expected {
+            let local = dir.join(file);
+            if !local.is_file() {
+                if !report.missing.contains(file) {
+                    report.missing.push(file.clone());
+                }
+                continue;
+            }
+            checked.insert(file.clone());
+            if hex_digest(&local, algorithm)? != *hex && !report.mismatched.contains(file) {
+                report.mismatched.push(file.clone());
+            }
+        }
+        for file in &present {
+            if !expected.contains_key(file) && !report.unlisted.contains(file) {
+                report.unlisted.push(file.clone());
+            }
+        }
+    }
+    report.checked = checked.len();
+    if report.sources.is_empty() {
+        return Err(anyhow!(
+            "❌ {:?} 中没有 {} 或 {}",
+            dir,
+            SHA256_FILE,
+            BLAKE3_FILE
+        ));
+    }
+    Ok(report)
+}
+
+pub fn print_checksum_report(report: &ChecksumReport) {
+    println!(
+        "🔎 已按 {} 检查 {} 个文件",
+        report.sources.join("、"),
+        report.checked
+    );
+    for file in &report.mismatched {
+        println!("❌ 内容不一致: {}", file);
+    }
+    for file in &report.missing {
+        println!("❌ 文件缺失: {}", file);
+    }
+    for file in &report.unlisted {
+        println!("⚠️  未记录的文件: {}", file);
+    }
+}
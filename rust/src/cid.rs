@@ -0,0 +1,389 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+
+use crate::unixfs::{DAG_PB, put_varint, read_varint};
+
+// multihash 中的 sha2-256
+const SHA2_256: u64 = 0x12;
+
+// CID 字符串使用的 multibase 编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multibase {
+    Base32,
+    Base32Upper,
+    Base36,
+    Base58btc,
+    Base16,
+    Base64,
+    Base64Url,
+}
+
+const BASE32: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE36: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE16: &[u8] = b"0123456789abcdef";
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Multibase {
+    pub const ALL: [Multibase; 7] = [
+        Self::Base32,
+        Self::Base32Upper,
+        Self::Base36,
+        Self::Base58btc,
+        Self::Base16,
+        Self::Base64,
+        Self::Base64Url,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base32 => "base32",
+            Self::Base32Upper => "base32upper",
+            Self::Base36 => "base36",
+            Self::Base58btc => "base58btc",
+            Self::Base16 => "base16",
+            Self::Base64 => "base64",
+            Self::Base64Url => "base64url",
+        }
+    }
+
+    // 字符串开头的前缀字符
+    pub fn prefix(&self) -> char {
+        match self {
+            Self::Base32 => 'b',
+            Self::Base32Upper => 'B',
+            Self::Base36 => 'k',
+            Self::Base58btc => 'z',
+            Self::Base16 => 'f',
+            Self::Base64 => 'm',
+            Self::Base64Url => 'u',
+        }
+    }
+
+    fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            'B' => Some(Self::Base32Upper),
+            // 大写的 base36 / base16 按小写解码
+            'K' => Some(Self::Base36),
+            'F' => Some(Self::Base16),
+            _ => Self::ALL.into_iter().find(|base| base.prefix() == prefix),
+        }
+    }
+
+    pub fn encode(&self, data: &[u8]) -> String {
+        match self {
+            Self::Base32 => encode_bits(data, BASE32, 5),
+            Self::Base32Upper => encode_bits(data, BASE32, 5).to_uppercase(),
+            Self::Base36 => encode_radix(data, BASE36),
+            Self::Base58btc => encode_radix(data, BASE58),
+            Self::Base16 => encode_bits(data, BASE16, 4),
+            Self::Base64 => encode_bits(data, BASE64, 6),
+            Self::Base64Url => encode_bits(data, BASE64_URL, 6),
+        }
+    }
+
+    pub fn decode(&self, encoded: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Base32 | Self::Base32Upper => {
+                decode_bits(&encoded.to_ascii_lowercase(), BASE32, 5)
+            }
+            Self::Base36 => decode_radix(&encoded.to_ascii_lowercase(), BASE36),
+            Self::Base58btc => decode_radix(encoded, BASE58),
+            Self::Base16 => decode_bits(&encoded.to_ascii_lowercase(), BASE16, 4),
+            Self::Base64 => decode_bits(encoded, BASE64, 6),
+            Self::Base64Url => decode_bits(encoded, BASE64_URL, 6),
+        }
+    }
+}
+
+impl fmt::Display for Multibase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Multibase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|base| base.as_str() == s)
+            .ok_or_else(|| {
+                anyhow!(
+                    "❌ 不支持的编码: {} (可选 {})",
+                    s,
+                    Self::ALL.map(|base| base.as_str()).join("、")
+                )
+            })
+    }
+}
+
+// 2 的幂进制 (base16 / base32 / base64)，不补 =
+fn encode_bits(data: &[u8], alphabet: &[u8], width: u32) -> String {
+    let mask = (1u32 << width) - 1;
+    let mut out = String::with_capacity(data.len() * 8 / width as usize + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= width {
+            bits -= width;
+            out.push(alphabet[((buffer >> bits) & mask) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(alphabet[((buffer << (width - bits)) & mask) as usize] as char);
+    }
+    out
+}
+
+fn decode_bits(encoded: &str, alphabet: &[u8], width: u32) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() * width as usize / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in encoded.trim_end_matches('=').bytes() {
+        let value = alphabet.iter().position(|&a| a == c)? as u32;
+        buffer = ((buffer << width) | value) & 0xffff;
+        bits += width;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+// 任意进制 (base36 / base58)：开头的 0 字节写成字母表的第一个字符
+fn encode_radix(data: &[u8], alphabet: &[u8]) -> String {
+    let radix = alphabet.len() as u32;
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+        while carry > 0 {
+            digits.push((carry % radix) as u8);
+            carry /= radix;
+        }
+    }
+    std::iter::repeat_n(alphabet[0] as char, zeros)
+        .chain(digits.iter().rev().map(|&d| alphabet[d as usize] as char))
+        .collect()
+}
+
+fn decode_radix(encoded: &str, alphabet: &[u8]) -> Option<Vec<u8>> {
+    let radix = alphabet.len() as u32;
+    let zeros = encoded.bytes().take_while(|&c| c == alphabet[0]).count();
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in encoded.bytes().skip(zeros) {
+        let mut carry = alphabet.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * radix;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    Some(
+        std::iter::repeat_n(0u8, zeros)
+            .chain(bytes.into_iter().rev())
+            .collect(),
+    )
+}
+
+// 常见的 codec 名称
+pub fn codec_name(code: u64) -> &'static str {
+    match code {
+        0x55 => "raw",
+        0x70 => "dag-pb",
+        0x71 => "dag-cbor",
+        0x72 => "libp2p-key",
+        0x78 => "git-raw",
+        0x0129 => "dag-json",
+        0x0200 => "json",
+        _ => "未知",
+    }
+}
+
+// 常见的 multihash 名称
+pub fn hash_name(code: u64) -> &'static str {
+    match code {
+        0x00 => "identity",
+        0x11 => "sha1",
+        0x12 => "sha2-256",
+        0x13 => "sha2-512",
+        0x16 => "sha3-256",
+        0x1b => "keccak-256",
+        0x1e => "blake3",
+        0xb220 => "blake2b-256",
+        _ => "未知",
+    }
+}
+
+// ✅ 任意版本、codec 和哈希的 CID；工作流中使用的是只支持 sha2-256 CIDv1 的 unixfs::Cid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CidInfo {
+    pub version: u64,
+    pub codec: u64,
+    pub hash: u64,
+    pub digest: Vec<u8>,
+    // 解析时的编码；CIDv0 固定为 base58btc (没有前缀)
+    pub base: Multibase,
+}
+
+impl CidInfo {
+    fn multihash(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.digest.len() + 4);
+        put_varint(&mut bytes, self.hash);
+        put_varint(&mut bytes, self.digest.len() as u64);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.version == 0 {
+            return self.multihash();
+        }
+        let mut bytes = Vec::new();
+        put_varint(&mut bytes, self.version);
+        put_varint(&mut bytes, self.codec);
+        bytes.extend(self.multihash());
+        bytes
+    }
+
+    pub fn to_v1(&self) -> Self {
+        Self {
+            version: 1,
+            base: if self.version == 0 {
+                Multibase::Base32
+            } else {
+                self.base
+            },
+            ..self.clone()
+        }
+    }
+
+    // 只有 dag-pb + sha2-256 的 CID 能写成 Qm… 形式
+    pub fn to_v0(&self) -> Result<Self> {
+        if self.codec != DAG_PB || self.hash != SHA2_256 || self.digest.len() != 32 {
+            return Err(anyhow!(
+                "❌ 只有 dag-pb + sha2-256 的 CID 能转换为 v0，这个 CID 是 {} + {}",
+                codec_name(self.codec),
+                hash_name(self.hash)
+            ));
+        }
+        Ok(Self {
+            version: 0,
+            base: Multibase::Base58btc,
+            ..self.clone()
+        })
+    }
+
+    // 用指定的编码写成字符串；CIDv0 只能是 base58btc
+    pub fn encode(&self, base: Multibase) -> Result<String> {
+        if self.version == 0 {
+            if base != Multibase::Base58btc {
+                return Err(anyhow!(
+                    "❌ CIDv0 只能使用 base58btc，{} 需要先转换为 v1",
+                    base
+                ));
+            }
+            return Ok(Multibase::Base58btc.encode(&self.to_bytes()));
+        }
+        Ok(format!(
+            "{}{}",
+            base.prefix(),
+            base.encode(&self.to_bytes())
+        ))
+    }
+
+    fn parse_bytes(bytes: &[u8], base: Multibase) -> Result<Self> {
+        let mut reader = bytes;
+        let version = read_varint(&mut reader)?;
+        if version != 1 {
+            return Err(anyhow!("❌ 不支持的 CID 版本: {}", version));
+        }
+        let codec = read_varint(&mut reader)?;
+        let hash = read_varint(&mut reader)?;
+        let len = read_varint(&mut reader)? as usize;
+        if reader.len() != len {
+            return Err(anyhow!(
+                "❌ multihash 长度不符: 声明 {} 字节，实际 {} 字节",
+                len,
+                reader.len()
+            ));
+        }
+        Ok(Self {
+            version,
+            codec,
+            hash,
+            digest: reader.to_vec(),
+            base,
+        })
+    }
+}
+
+impl FromStr for CidInfo {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        // CIDv0：46 个字符的 base58btc multihash
+        if s.len() == 46 && s.starts_with("Qm") {
+            let bytes = Multibase::Base58btc
+                .decode(s)
+                .ok_or_else(|| anyhow!("❌ 无效的 CIDv0: {}", s))?;
+            if bytes.len() != 34 || bytes[0] != SHA2_256 as u8 || bytes[1] != 32 {
+                return Err(anyhow!("❌ 无效的 CIDv0: {}", s));
+            }
+            return Ok(Self {
+                version: 0,
+                codec: DAG_PB,
+                hash: SHA2_256,
+                digest: bytes[2..].to_vec(),
+                base: Multibase::Base58btc,
+            });
+        }
+        let mut chars = s.chars();
+        let prefix = chars.next().ok_or_else(|| anyhow!("❌ CID 不能为空"))?;
+        let base = Multibase::from_prefix(prefix)
+            .ok_or_else(|| anyhow!("❌ 无法识别的 multibase 前缀 '{}': {}", prefix, s))?;
+        let bytes = base
+            .decode(chars.as_str())
+            .ok_or_else(|| anyhow!("❌ 不是有效的 {} 字符串: {}", base, s))?;
+        Self::parse_bytes(&bytes, base).map_err(|e| anyhow!("{} ({})", e, s))
+    }
+}
+
+// 按解析时的编码写回
+impl fmt::Display for CidInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = if self.version == 0 {
+            Multibase::Base58btc
+        } else {
+            self.base
+        };
+        match self.encode(base) {
+            Ok(s) => f.write_str(&s),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+// 比较两个 CID 是否指向同一内容 (忽略版本和编码)
+pub fn same_content(a: &str, b: &str) -> bool {
+    match (a.parse::<CidInfo>(), b.parse::<CidInfo>()) {
+        (Ok(a), Ok(b)) => a.codec == b.codec && a.hash == b.hash && a.digest == b.digest,
+        _ => a == b,
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::backend::IpfsBackend;
+use crate::hashing::{HashAlgorithm, hash_file};
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::standards::detect_file_mime_type;
+
+// clone 的可选项
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    // 清单中图片哈希使用的算法
+    pub hash_algorithm: HashAlgorithm,
+}
+
+// ✅ 一次 clone 的结果
+#[derive(Debug, Clone)]
+pub struct CloneReport {
+    pub manifest: Manifest,
+    pub manifest_path: PathBuf,
+    pub images: usize,
+    // 跳过的条目和原因
+    pub skipped: Vec<String>,
+}
+
+// 接受 ipfs://<cid>、/ipfs/<cid> 或裸 CID，返回 `<cid>[/<子路径>]`
+pub fn parse_ipfs_path(uri: &str) -> Result<String> {
+    let path = uri
+        .trim()
+        .strip_prefix("ipfs://")
+        .or_else(|| uri.trim().strip_prefix("/ipfs/"))
+        .unwrap_or(uri.trim())
+        .trim_matches('/');
+    if path.is_empty() {
+        return Err(anyhow!("❌ 无效的 IPFS 地址: {}", uri));
+    }
+    Ok(path.to_string())
+}
+
+// ipfs:// 或网关地址转换为 IPFS 路径；网关地址取 /ipfs/ 之后的部分，其它协议无法下载
+pub fn uri_ipfs_path(uri: &str) -> Option<String> {
+    if uri.starts_with("ipfs://") {
+        return parse_ipfs_path(uri).ok();
+    }
+    if uri.starts_with("http://") || uri.starts_with("https://") {
+        let (_, path) = uri.split_once("/ipfs/")?;
+        return parse_ipfs_path(path.split(['?', '#']).next()?).ok();
+    }
+    None
+}
+
+// 元数据文件名 (去掉 .json) 是数字时作为 token ID
+fn token_id(file_name: &str) -> Option<u64> {
+    file_name
+        .strip_suffix(".json")
+        .unwrap_or(file_name)
+        .parse()
+        .ok()
+}
+
+// ✅ 从元数据文件夹的 CID 重建本地的 images/ + metadata/ 目录和 manifest.json
+pub fn clone_collection(
+    backend: &dyn IpfsBackend,
+    uri: &str,
+    out: &Path,
+    options: &CloneOptions,
+) -> Result<CloneReport> {
+    let root = parse_ipfs_path(uri)?;
+    if out.exists() && fs::read_dir(out)?.next().is_some() {
+        return Err(anyhow!("❌ 输出目录 {:?} 不为空", out));
+    }
+
+    println!("\n--- 正在读取元数据文件夹: {} ---", root);
+    let entries = backend.ls(&root)?;
+    let mut skipped = Vec::new();
+    let files: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| {
+            if entry.is_dir {
+                skipped.push(format!("{}: 子文件夹", entry.name));
+            }
+            !entry.is_dir
+        })
+        .collect();
+    if files.is_empty() {
+        return Err(anyhow!(
+            "❌ {} 不是元数据文件夹 (没有找到文件)，请使用批量集合的 Base URI",
+            root
+        ));
+    }
+
+    let metadata_dir = out.join("metadata");
+    let images_dir = out.join("images");
+    fs::create_dir_all(&metadata_dir)?;
+    fs::create_dir_all(&images_dir)?;
+
+    let mut tokens = Vec::new();
+    // 图片路径 -> 本地文件名，多个 token 引用同一张图片时只下载一次
+    let mut downloaded: BTreeMap<String, String> = BTreeMap::new();
+    // 图片所在文件夹的 CID，全部相同时记录到清单中
+    let mut image_roots: Vec<String> = Vec::new();
+    for (index, entry) in files.iter().enumerate() {
+        println!("📥 [{}/{}] {}", index + 1, files.len(), entry.name);
+        let data = backend.cat(&format!("{}/{}", root, entry.name))?;
+        fs::write(metadata_dir.join(&entry.name), &data)?;
+
+        let Ok(metadata) = serde_json::from_slice::<Value>(&data) else {
+            skipped.push(format!("{}: 不是 JSON", entry.name));
+            continue;
+        };
+        let image = metadata
+            .get("image")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let Some(image_path) = uri_ipfs_path(&image) else {
+            skipped.push(format!("{}: 无法下载的图片地址 `{}`", entry.name, image));
+            continue;
+        };
+
+        let image_file = match downloaded.get(&image_path) {
+            Some(file) => file.clone(),
+            None => {
+                let (image_root, file) = match image_path.split_once('/') {
+                    Some((cid, rest)) => (cid, rest.rsplit('/').next().unwrap_or(rest)),
+                    None => (image_path.as_str(), entry.name.as_str()),
+                };
+                if !image_roots.iter().any(|r| r == image_root) {
+                    image_roots.push(image_root.to_string());
+                }
+                fs::write(images_dir.join(file), backend.cat(&image_path)?)?;
+                downloaded.insert(image_path.clone(), file.to_string());
+                file.to_string()
+            }
+        };
+
+        tokens.push(TokenEntry {
+            token_id: token_id(&entry.name),
+            name: metadata
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            image_hash: Some(hash_file(
+                &images_dir.join(&image_file),
+                options.hash_algorithm,
+            )?),
+            mime_type: Some(detect_file_mime_type(&images_dir.join(&image_file))?),
+            animation_uri: metadata
+                .get("animation_url")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            image_file,
+            original_uri: None,
+            image_uri: image,
+            metadata_file: entry.name.clone(),
+            metadata_cid: None,
+            arweave: None,
+        });
+    }
+    tokens.sort_by_key(|token| token.token_id);
+
+    let manifest = Manifest {
+        kind: CollectionKind::Batch,
+        created_at: Utc::now().to_rfc3339(),
+        backend: backend.name().to_string(),
+        node_version: backend.node_version(),
+        output_dir: out.to_path_buf(),
+        image_cid: match image_roots.as_slice() {
+            [single] => single.clone(),
+            _ => String::new(),
+        },
+        posters_cid: None,
+        previews_cid: None,
+        watermarked_cid: None,
+        localization_cid: None,
+        metadata_cid: root.clone(),
+        token_uri: format!("ipfs://{}/", root),
+        tokens,
+        served_by: Default::default(),
+        urls: Default::default(),
+        encryption: None,
+        ipld: None,
+        filecoin: BTreeMap::new(),
+        remote_pins: BTreeMap::new(),
+    };
+    let manifest_path = manifest.save(out)?;
+    Ok(CloneReport {
+        manifest,
+        manifest_path,
+        images: downloaded.len(),
+        skipped,
+    })
+}
+
+pub fn print_clone_report(report: &CloneReport) {
+    println!("\n✅ 集合已下载到: {:?}", report.manifest.output_dir);
+    println!(
+        "   - 元数据: {} 个 token，图片: {} 个文件",
+        report.manifest.tokens.len(),
+        report.images
+    );
+    if report.manifest.image_cid.is_empty() {
+        println!("   - ⚠️  图片不在同一个文件夹中，清单的 image_cid 为空");
+    } else {
+        println!("   - 图片 CID: {}", report.manifest.image_cid);
+    }
+    println!("   - 清单: {:?}", report.manifest_path);
+    if !report.skipped.is_empty() {
+        println!("⚠️  跳过了 {} 个条目:", report.skipped.len());
+        for reason in &report.skipped {
+            println!("   - {}", reason);
+        }
+    }
+}
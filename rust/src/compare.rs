@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+
+// 一次运行：清单和它所在的目录 (本地文件按这个目录查找，清单里的 output_dir 可能是相对路径)
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub dir: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Run {
+    pub fn new(manifest_path: &Path, manifest: Manifest) -> Self {
+        let dir = manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Self { dir, manifest }
+    }
+
+    fn image_path(&self, token: &TokenEntry) -> PathBuf {
+        match self.manifest.kind {
+            CollectionKind::Single => self.dir.join(&token.image_file),
+            _ => self.dir.join("images").join(&token.image_file),
+        }
+    }
+
+    fn metadata_path(&self, token: &TokenEntry) -> PathBuf {
+        match self.manifest.kind {
+            CollectionKind::Single => self.dir.join(&token.metadata_file),
+            _ => self.dir.join("metadata").join(&token.metadata_file),
+        }
+    }
+}
+
+// 同一个字段在两次运行中的值
+#[derive(Debug, Clone)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub a: String,
+    pub b: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenDiff {
+    pub key: String,
+    pub diffs: Vec<FieldDiff>,
+}
+
+// ✅ 两次运行的对比结果
+#[derive(Debug, Clone)]
+pub struct RunComparison {
+    pub root_diffs: Vec<FieldDiff>,
+    pub token_counts: (usize, usize),
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub token_diffs: Vec<TokenDiff>,
+    // 两边都有、且本地元数据文件内容完全相同的 token 数
+    pub identical_metadata: usize,
+}
+
+impl RunComparison {
+    // 元数据 CID 相同时，两次运行的 Base URI / Token URI 可以互相替换
+    pub fn interchangeable(&self) -> bool {
+        !self.root_diffs.iter().any(|d| d.field == "metadata_cid")
+    }
+}
+
+fn token_key(token: &TokenEntry) -> String {
+    match token.token_id {
+        Some(id) => format!("#{}", id),
+        None => token.image_file.clone(),
+    }
+}
+
+fn file_size(path: &Path) -> String {
+    fs::metadata(path)
+        .map(|m| format!("{} 字节", m.len()))
+        .unwrap_or_else(|_| "缺失".to_string())
+}
+
+fn push_diff(diffs: &mut Vec<FieldDiff>, field: &'static str, a: &str, b: &str) {
+    if a != b {
+        diffs.push(FieldDiff {
+            field,
+            a: a.to_string(),
+            b: b.to_string(),
+        });
+    }
+}
+
+pub fn compare_runs(a: &Run, b: &Run) -> RunComparison {
+    let (ma, mb) = (&a.manifest, &b.manifest);
+    let mut root_diffs = Vec::new();
+    push_diff(
+        &mut root_diffs,
+        "kind",
+        &format!("{:?}", ma.kind),
+        &format!("{:?}", mb.kind),
+    );
+    push_diff(&mut root_diffs, "image_cid", &ma.image_cid, &mb.image_cid);
+    push_diff(
+        &mut root_diffs,
+        "metadata_cid",
+        &ma.metadata_cid,
+        &mb.metadata_cid,
+    );
+    push_diff(&mut root_diffs, "token_uri", &ma.token_uri, &mb.token_uri);
+
+    let tokens_a: BTreeMap<String, &TokenEntry> =
+        ma.tokens.iter().map(|t| (token_key(t), t)).collect();
+    let tokens_b: BTreeMap<String, &TokenEntry> =
+        mb.tokens.iter().map(|t| (token_key(t), t)).collect();
+    let only_in_a = tokens_a
+        .keys()
+        .filter(|key| !tokens_b.contains_key(*key))
+        .cloned()
+        .collect();
+    let only_in_b = tokens_b
+        .keys()
+        .filter(|key| !tokens_a.contains_key(*key))
+        .cloned()
+        .collect();
+
+    let mut token_diffs = Vec::new();
+    let mut identical_metadata = 0;
+    for (key, ta) in &tokens_a {
+        let Some(tb) = tokens_b.get(key) else {
+            continue;
+        };
+        let mut diffs = Vec::new();
+        push_diff(&mut diffs, "name", &ta.name, &tb.name);
+        push_diff(&mut diffs, "image_file", &ta.image_file, &tb.image_file);
+        // 哈希算法不同时无法直接比较
+        if let (Some(ha), Some(hb)) = (&ta.image_hash, &tb.image_hash)
+            && ha.split_once(':').map(|(algo, _)| algo) == hb.split_once(':').map(|(algo, _)| algo)
+        {
+            push_diff(&mut diffs, "image_hash", ha, hb);
+        }
+        push_diff(&mut diffs, "image_uri", &ta.image_uri, &tb.image_uri);
+        push_diff(
+            &mut diffs,
+            "image_size",
+            &file_size(&a.image_path(ta)),
+            &file_size(&b.image_path(tb)),
+        );
+        let (meta_a, meta_b) = (a.metadata_path(ta), b.metadata_path(tb));
+        push_diff(
+            &mut diffs,
+            "metadata_size",
+            &file_size(&meta_a),
+            &file_size(&meta_b),
+        );
+        let cid_a = ta.metadata_cid.as_deref().unwrap_or("");
+        let cid_b = tb.metadata_cid.as_deref().unwrap_or("");
+        push_diff(&mut diffs, "metadata_cid", cid_a, cid_b);
+        if let (Ok(content_a), Ok(content_b)) = (fs::read(&meta_a), fs::read(&meta_b))
+            && content_a == content_b
+        {
+            identical_metadata += 1;
+        }
+        if !diffs.is_empty() {
+            token_diffs.push(TokenDiff {
+                key: key.clone(),
+                diffs,
+            });
+        }
+    }
+
+    RunComparison {
+        root_diffs,
+        token_counts: (ma.tokens.len(), mb.tokens.len()),
+        only_in_a,
+        only_in_b,
+        token_diffs,
+        identical_metadata,
+    }
+}
+
+// 最多列出的 token 数，避免大集合刷屏
+const MAX_LISTED_TOKENS: usize = 50;
+
+pub fn render_comparison(a: &Run, b: &Run, comparison: &RunComparison) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# 运行对比\n");
+    for (label, run) in [("A", a), ("B", b)] {
+        let _ = writeln!(
+            out,
+            "- **{}**: `{}` ({}，后端 {}，{} 个 token)",
+            label,
+            run.dir.display(),
+            run.manifest.created_at,
+            run.manifest.backend,
+            run.manifest.tokens.len()
+        );
+    }
+
+    let _ = writeln!(out, "\n## 结论\n");
+    if comparison.interchangeable() {
+        let _ = writeln!(
+            out,
+            "✅ 元数据 CID 相同，两次运行的 Base URI 可以互相替换: `{}`",
+            a.manifest.token_uri
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "❌ 元数据 CID 不同，Base URI **不能**互相替换，合约中只能使用其中一个。"
+        );
+        if comparison.root_diffs.iter().any(|d| d.field == "image_cid") {
+            let _ = writeln!(
+                out,
+                "\n图片文件夹 CID 也不同，元数据中的 image 链接随之不同。"
+            );
+        }
+        let shared = comparison.token_counts.0 - comparison.only_in_a.len();
+        if shared > 0
+            && comparison.identical_metadata == shared
+            && comparison.only_in_a.is_empty()
+            && comparison.only_in_b.is_empty()
+        {
+            let _ = writeln!(
+                out,
+                "\n所有 token 的元数据文件内容完全相同，CID 不同通常是 CID 版本、分块或 raw-leaves 等上传参数不一致。"
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "\n{}/{} 个共有 token 的元数据文件内容完全相同。",
+                comparison.identical_metadata, shared
+            );
+        }
+    }
+
+    let _ = writeln!(out, "\n## 集合\n");
+    if comparison.root_diffs.is_empty() {
+        let _ = writeln!(out, "根 CID 和 Token URI 均相同。");
+    } else {
+        let _ = writeln!(out, "| 字段 | A | B |\n| --- | --- | --- |");
+        for diff in &comparison.root_diffs {
+            let _ = writeln!(out, "| {} | `{}` | `{}` |", diff.field, diff.a, diff.b);
+        }
+    }
+
+    let _ = writeln!(out, "\n## Token\n");
+    let (count_a, count_b) = comparison.token_counts;
+    let _ = writeln!(out, "- 数量: A {} 个，B {} 个", count_a, count_b);
+    for (label, keys) in [
+        ("只在 A 中", &comparison.only_in_a),
+        ("只在 B 中", &comparison.only_in_b),
+    ] {
+        if !keys.is_empty() {
+            let listed: Vec<&str> = keys
+                .iter()
+                .take(MAX_LISTED_TOKENS)
+                .map(String::as_str)
+                .collect();
+            let more = keys.len().saturating_sub(MAX_LISTED_TOKENS);
+            let _ = write!(out, "- {}: {}", label, listed.join(", "));
+            if more > 0 {
+                let _ = write!(out, " 等 (另有 {} 个)", more);
+            }
+            out.push('\n');
+        }
+    }
+    if comparison.token_diffs.is_empty() {
+        let _ = writeln!(out, "- 两边都有的 token 完全一致");
+    } else {
+        let _ = writeln!(
+            out,
+            "- 有差异的 token: {} 个\n\n| Token | 字段 | A | B |\n| --- | --- | --- | --- |",
+            comparison.token_diffs.len()
+        );
+        for token in comparison.token_diffs.iter().take(MAX_LISTED_TOKENS) {
+            for diff in &token.diffs {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | `{}` | `{}` |",
+                    token.key, diff.field, diff.a, diff.b
+                );
+            }
+        }
+        if comparison.token_diffs.len() > MAX_LISTED_TOKENS {
+            let _ = writeln!(
+                out,
+                "\n另有 {} 个 token 有差异未列出。",
+                comparison.token_diffs.len() - MAX_LISTED_TOKENS
+            );
+        }
+    }
+    out
+}
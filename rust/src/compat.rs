@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+
+use crate::unixfs::AddConfig;
+
+// ✅ `ipfs commands --enc=json` 返回的命令树
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommandNode {
+    name: String,
+    #[serde(default)]
+    subcommands: Vec<CommandNode>,
+    #[serde(default)]
+    options: Vec<OptionNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OptionNode {
+    names: Vec<String>,
+}
+
+// 工作流依赖的命令和参数
+#[derive(Debug, Clone, Copy)]
+pub struct Requirement {
+    pub command: &'static str,
+    pub flag: Option<&'static str>,
+}
+
+pub const REQUIRED: &[Requirement] = &[
+    Requirement {
+        command: "add",
+        flag: None,
+    },
+    Requirement {
+        command: "add",
+        flag: Some("recursive"),
+    },
+    Requirement {
+        command: "add",
+        flag: Some("cid-version"),
+    },
+    // 固定的导入参数 (unixfs::ADD_OPTIONS) 决定 CID，节点不支持时不能省略
+    Requirement {
+        command: "add",
+        flag: Some("chunker"),
+    },
+    Requirement {
+        command: "add",
+        flag: Some("raw-leaves"),
+    },
+    Requirement {
+        command: "add",
+        flag: Some("hash"),
+    },
+];
+
+// ✅ 低于这个版本时只给出警告：Kubo 0.14 之前还叫 go-ipfs，部分命令的参数和输出格式不同
+pub const MIN_KUBO_VERSION: (u64, u64, u64) = (0, 14, 0);
+
+// 从 "0.29.0"、"0.29.0-dev"、"ipfs version 0.4.23" 中取出主、次、修订版本号
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let number = version
+        .split_whitespace()
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+    let mut parts = number.split('.').map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u64>()
+    });
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+// 节点版本低于 MIN_KUBO_VERSION 时提示升级；解析不出版本号时不提示
+pub fn warn_if_outdated(version: &str) {
+    let Some(parsed) = parse_version(version) else {
+        return;
+    };
+    if parsed < MIN_KUBO_VERSION {
+        let (major, minor, patch) = MIN_KUBO_VERSION;
+        println!(
+            "⚠️  节点版本 {} 太旧 (低于 Kubo {}.{}.{})，部分功能可能不可用，建议升级",
+            version.trim(),
+            major,
+            minor,
+            patch
+        );
+    }
+}
+
+// ✅ [add] 中开启的导入参数是可选的：节点不支持时关闭这个参数并警告，而不是拒绝上传
+pub fn adapt_add_config(capabilities: &NodeCapabilities, add: &mut AddConfig) {
+    for flag in add.flags() {
+        // 关闭 nocopy、inline 时 fscache、inline-limit 会跟着关闭，不再重复提示
+        if capabilities.supports("add", Some(flag)) || !add.flags().contains(&flag) {
+            continue;
+        }
+        println!(
+            "⚠️  节点 (版本 {}) 不支持 `ipfs add --{}`，已关闭 [add] 中的这个参数",
+            capabilities.version, flag
+        );
+        add.disable(flag);
+    }
+}
+
+// 节点版本以及它支持的命令和参数
+#[derive(Debug, Clone)]
+pub struct NodeCapabilities {
+    pub version: String,
+    // 命令路径 (如 "add"、"pin/add") -> 参数名 (含短名)
+    commands: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl NodeCapabilities {
+    pub fn from_commands_json(version: &str, commands_json: &str) -> Result<Self> {
+        let root: CommandNode = serde_json::from_str(commands_json)
+            .map_err(|e| anyhow!("❌ 无法解析节点的命令列表: {}", e))?;
+        let mut commands = BTreeMap::new();
+        for sub in &root.subcommands {
+            collect_commands(sub, "", &mut commands);
+        }
+        Ok(Self {
+            version: version.trim().to_string(),
+            commands,
+        })
+    }
+
+    pub fn supports(&self, command: &str, flag: Option<&str>) -> bool {
+        match (self.commands.get(command), flag) {
+            (Some(flags), Some(flag)) => flags.contains(flag),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    // 返回缺失的命令/参数，格式如 `ipfs add --cid-version`
+    pub fn missing(&self, requirements: &[Requirement]) -> Vec<String> {
+        requirements
+            .iter()
+            .filter(|req| !self.supports(req.command, req.flag))
+            .map(|req| {
+                let command = format!("ipfs {}", req.command.replace('/', " "));
+                match req.flag {
+                    Some(flag) => format!("{} --{}", command, flag),
+                    None => command,
+                }
+            })
+            .collect()
+    }
+
+    pub fn ensure(&self, requirements: &[Requirement]) -> Result<()> {
+        let missing = self.missing(requirements);
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "❌ 当前 IPFS 节点 (版本 {}) 不支持以下命令或参数:\n   - {}\n请升级 Kubo 后重试。",
+            self.version,
+            missing.join("\n   - ")
+        ))
+    }
+}
+
+fn collect_commands(
+    node: &CommandNode,
+    prefix: &str,
+    out: &mut BTreeMap<String, BTreeSet<String>>,
+) {
+    let path = if prefix.is_empty() {
+        node.name.clone()
+    } else {
+        format!("{}/{}", prefix, node.name)
+    };
+    let flags = node
+        .options
+        .iter()
+        .flat_map(|opt| opt.names.iter().cloned())
+        .collect();
+    out.insert(path.clone(), flags);
+    for sub in &node.subcommands {
+        collect_commands(sub, &path, out);
+    }
+}
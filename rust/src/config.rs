@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::arweave::ArweaveConfig;
+use crate::backend::{ApiEndpointConfig, ClusterConfig, FailoverConfig, FilebaseConfig};
+use crate::backup::BackupConfig;
+use crate::checksums::ChecksumConfig;
+use crate::contract::ContractConfig;
+use crate::copy::CopyOptions;
+use crate::encryption::EncryptionConfig;
+use crate::estimate::PricingTier;
+use crate::extra_fields::ExtraFieldsConfig;
+use crate::filecoin::FilecoinConfig;
+use crate::gallery::GalleryConfig;
+use crate::hashing::HashAlgorithm;
+use crate::hooks::HookConfig;
+use crate::ipld::IpldConfig;
+use crate::layout::{LayoutConfig, NamingConfig, OutputLayout};
+use crate::localization::LocalizationConfig;
+use crate::metadata::{CollectionConfig, JsonStyle, JsonSuffix};
+use crate::pinning::PinningServiceConfig;
+use crate::preview::PreviewConfig;
+use crate::provide::ProvideConfig;
+use crate::remote_source::RemoteSourceConfig;
+use crate::report::ReportConfig;
+use crate::secrets::{interpolate, load_credential};
+use crate::signing::SigningConfig;
+use crate::standards::{Cip25Config, MetadataStandard, MetaplexConfig, Tzip21Config};
+use crate::throttle::ThrottleConfig;
+use crate::timeout::TimeoutConfig;
+use crate::unixfs::AddConfig;
+use crate::urls::UrlConfig;
+use crate::validate::ValidationConfig;
+use crate::watermark::WatermarkConfig;
+use crate::webhook::WebhookConfig;
+use crate::window::ScheduleWindow;
+
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+fn interpolate_value(value: &mut Value) -> Result<()> {
+    match value {
+        Value::String(s) => *s = interpolate(s)?,
+        Value::Array(items) => {
+            for item in items {
+                interpolate_value(item)?;
+            }
+        }
+        Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                interpolate_value(item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// ✅ config.toml 的内容，所有字段都有默认值
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    // 清单和去重缓存使用的哈希算法: "blake3" (默认) 或 "sha256"
+    pub hash_algorithm: HashAlgorithm,
+    // 元数据文件名后缀: "none" (默认)、"json"，或 "auto" 按 [contract] 中合约已有的 tokenURI 判断
+    pub json_suffix: JsonSuffix,
+    // 元数据 JSON 的写法: "pretty" (默认) 或 "canonical" (RFC 8785，键排序、无空白，内容相同则 CID 相同)
+    pub json_style: JsonStyle,
+    // 单件元数据包在目录中上传 (metadata/<名称>.json)，token URI 为 ipfs://<目录>/<名称>.json，也可以用 single --wrap 开启
+    pub wrap_single_metadata: bool,
+    // 元数据标准: "erc721" (默认)、"metaplex"、"tzip21" 或 "cip25"
+    pub metadata_standard: MetadataStandard,
+    // 集合名称、描述模板和默认的图片文件夹，`init` 向导会生成这一段
+    pub collection: CollectionConfig,
+    // metadata_standard = "metaplex" 时使用的 symbol、版税和创作者
+    pub metaplex: MetaplexConfig,
+    // metadata_standard = "tzip21" 时使用的 symbol、创作者和标签
+    pub tzip21: Tzip21Config,
+    // metadata_standard = "cip25" 时使用的策略 ID 和资产名前缀
+    pub cip25: Cip25Config,
+    // 每个 token 元数据中额外的顶层字段 (如 dna、edition)：集合共用的 fields，以及按 token ID 的 CSV
+    pub extra_fields: ExtraFieldsConfig,
+    // OpenSea 多语言扩展: default 语言和其它语言的翻译 (CSV 或文件夹)，单件和批量流程写入 localization 字段
+    pub localization: LocalizationConfig,
+    // 大图的预览图: 最大宽高、格式 (webp / jpeg) 和 JPEG 质量
+    pub preview: PreviewConfig,
+    // 保留图片中的 EXIF / XMP 等信息 (GPS 位置、设备型号)；默认上传前删除
+    pub keep_exif: bool,
+    // 上传前检查文件的规则: 允许的格式、宽高范围、单文件大小上限、正方形、CMYK
+    pub validation: ValidationConfig,
+    // 加水印的公开预览: 水印 PNG 或文字、位置、不透明度和大小
+    pub watermark: WatermarkConfig,
+    // 批量模式读取子文件夹 (每个 <ID>/ 是一个 token) 以及选择 token 图片的规则
+    pub layout: LayoutConfig,
+    // 元数据文件名的补零宽度、前缀和小写，以及是否把图片重命名为同样的名称
+    pub naming: NamingConfig,
+    // 运行目录的命名模板 (如 "{collection}/{date}/{time}")，以及图片和元数据额外的输出位置
+    pub output: OutputLayout,
+    // 批量模式复制输入文件夹时对符号链接、权限、硬链接和特殊文件的处理
+    pub copy: CopyOptions,
+    // 输出目录中的校验文件: CHECKSUMS.sha256 (默认写入)，以及可选的 CHECKSUMS.b3
+    pub checksums: ChecksumConfig,
+    // 批量上传完成后在运行目录中写入的报告: formats = ["markdown", "html"] (默认)，为空表示不生成
+    pub report: ReportConfig,
+    // `gallery` 预览页面显示图片的网关，或 local = true 直接引用本地图片
+    pub gallery: GalleryConfig,
+    // 清单签名：sign 命令的默认方式和私钥，auto = true 时上传完成后自动签名
+    pub signing: SigningConfig,
+    // 上传前用集合密钥加密图片和元数据 (AES-256-GCM)，也可以用 --encrypt 开启
+    pub encryption: EncryptionConfig,
+    // 工作流完成或失败时通知的 webhook 列表
+    pub webhooks: Vec<WebhookConfig>,
+    // 图片上传后、元数据上传后和出错时运行的命令或 HTTP 回调
+    pub hooks: Vec<HookConfig>,
+    // 上传后递归固定根 CID 并确认已向 DHT 宣告，也可以用 --provide 开启
+    pub provide: ProvideConfig,
+    // 清单中记录并在运行结束时打印的 ipfs://、路径网关、子域名网关和自定义网关链接
+    pub urls: UrlConfig,
+    // 元数据以 IPLD 节点 (dag-cbor / dag-json) 发布，图片为真正的 CID 链接 (单件和批量)
+    pub ipld: IpldConfig,
+    // 只在该时间段内传输，例如 "01:00-06:00"
+    pub schedule_window: Option<ScheduleWindow>,
+    // 远程 Pinning 服务 (Pinning Service API)
+    pub pinning_services: Vec<PinningServiceConfig>,
+    // 把图片和元数据镜像到 Arweave (Irys 节点、付款币种和私钥)，auto = true 时上传完成后自动镜像
+    pub arweave: ArweaveConfig,
+    // Filecoin 存储交易 (Storacha 或 Lotus)：`filecoin submit` 打包 CAR 并提交，auto = true 时上传完成后自动提交
+    pub filecoin: FilecoinConfig,
+    // 把运行目录同步到 S3 兼容存储或 GCS：`backup` 命令只上传变化的文件，auto = true 时上传完成后自动备份
+    pub backup: BackupConfig,
+    // batch --urls / --s3 的下载缓存目录和 S3 认证
+    pub remote_source: RemoteSourceConfig,
+    // 远程 / 托管节点的认证信息和自定义请求头，按 --api 匹配
+    pub api_endpoints: Vec<ApiEndpointConfig>,
+    // 使用 --cluster 时的复制因子和认证
+    pub cluster: ClusterConfig,
+    // 使用 --filebase 时的 S3 地址、密钥和 key 前缀
+    pub filebase: FilebaseConfig,
+    // 未指定 --api 时按顺序使用的多个节点，上传失败或超时时切换到下一个
+    pub failover: FailoverConfig,
+    // 服务商套餐的限额和价格，`estimate` 和批量上传前用来检查
+    pub pricing_tiers: Vec<PricingTier>,
+    // HTTP 后端的上传限速，--upload-limit 会覆盖全局限速
+    pub throttle: ThrottleConfig,
+    // 连接、上传、固定、其它节点请求和网关读取的超时；各后端的 timeouts 可以单独覆盖
+    pub timeouts: TimeoutConfig,
+    // 命令行和 HTTP API 后端的高级导入参数：包一层目录、filestore (nocopy)、内联小块
+    pub add: AddConfig,
+    // 部署后的合约：export-urls 生成市场链接，batch --set-base-uri 发送交易
+    pub contract: ContractConfig,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("❌ 读取配置文件 {:?} 失败: {}", path, e))?;
+        let mut table: Table = toml::from_str(&content)
+            .map_err(|e| anyhow!("❌ 配置文件 {:?} 格式错误: {}", path, e))?;
+        // 密钥不写明文：字符串中的 ${VAR} / ${keyring:<提供方>} 在这里替换
+        for (_, value) in table.iter_mut() {
+            interpolate_value(value)?;
+        }
+        let mut config: Self = table
+            .try_into()
+            .map_err(|e| anyhow!("❌ 配置文件 {:?} 格式错误: {}", path, e))?;
+        config.resolve_credentials();
+        Ok(config)
+    }
+
+    // 没有填写 access_token 的 Pinning 服务，从系统钥匙串按服务名读取
+    fn resolve_credentials(&mut self) {
+        for service in &mut self.pinning_services {
+            if !service.access_token.is_empty() {
+                continue;
+            }
+            match load_credential(&service.name) {
+                Ok(Some(token)) => service.access_token = token,
+                Ok(None) => eprintln!(
+                    "⚠️  Pinning 服务 {} 没有 access_token，可以运行 `credentials set {}` 保存",
+                    service.name, service.name
+                ),
+                Err(e) => eprintln!("⚠️  {:#}", e),
+            }
+        }
+    }
+
+    // 显式指定的配置文件必须存在；否则只在当前目录有 config.toml 时读取
+    pub fn load_or_default(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_FILE).is_file() => Path::new(DEFAULT_CONFIG_FILE),
+            None => {
+                verbose!("⚙️  没有配置文件，使用默认配置");
+                return Ok(Self::default());
+            }
+        };
+        verbose!("⚙️  读取配置文件: {:?}", path);
+        Self::load(path)
+    }
+}
@@ -0,0 +1,226 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::hashing::to_hex;
+
+// 默认的设置 Base URI 的函数
+pub const DEFAULT_SET_BASE_URI_FUNCTION: &str = "setBaseURI(string)";
+
+// ✅ config.toml 中的 [contract] 段：部署后的合约
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ContractConfig {
+    // 链名称，如 ethereum、polygon、base、sepolia
+    pub chain: String,
+    // 合约地址 (0x...)
+    pub address: String,
+    // JSON-RPC 地址，发送交易和读取链上数据时使用
+    pub rpc_url: String,
+    // 发送交易的账户私钥，建议写成 "${DEPLOYER_KEY}" 或 "${keyring:deployer}"
+    pub private_key: String,
+    // 不填时从 RPC 读取
+    pub chain_id: Option<u64>,
+    // 设置 Base URI 的函数签名，参数必须是一个 string
+    pub set_base_uri_function: String,
+}
+
+impl Default for ContractConfig {
+    fn default() -> Self {
+        Self {
+            chain: String::new(),
+            address: String::new(),
+            rpc_url: String::new(),
+            private_key: String::new(),
+            chain_id: None,
+            set_base_uri_function: DEFAULT_SET_BASE_URI_FUNCTION.to_string(),
+        }
+    }
+}
+
+impl ContractConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.chain.is_empty() && !self.address.is_empty()
+    }
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+// 0x 开头的十六进制
+pub fn to_hex_prefixed(bytes: &[u8]) -> String {
+    format!("0x{}", to_hex(bytes))
+}
+
+pub fn parse_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(anyhow!("❌ 十六进制长度不是偶数: {}", s));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| anyhow!("❌ 无效的十六进制: {}", s))
+        })
+        .collect()
+}
+
+// 20 字节的地址
+pub fn parse_address(s: &str) -> Result<[u8; 20]> {
+    parse_hex(s)?
+        .try_into()
+        .map_err(|_| anyhow!("❌ 无效的合约地址: {}", s))
+}
+
+// ABI 调用参数，只支持本工具用到的类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    Uint(u128),
+    String(String),
+}
+
+impl AbiValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Uint(_) => "uint256",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+// 拆分 `name(type1,type2)`，去掉空白
+pub fn parse_signature(signature: &str) -> Result<(String, Vec<String>)> {
+    let signature: String = signature.chars().filter(|c| !c.is_whitespace()).collect();
+    let (name, rest) = signature
+        .split_once('(')
+        .filter(|(name, rest)| !name.is_empty() && rest.ends_with(')'))
+        .ok_or_else(|| anyhow!("❌ 无效的函数签名: {}", signature))?;
+    let params = &rest[..rest.len() - 1];
+    let types = if params.is_empty() {
+        Vec::new()
+    } else {
+        params.split(',').map(str::to_string).collect()
+    };
+    Ok((name.to_string(), types))
+}
+
+fn word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+// ✅ 按函数签名编码调用数据 (4 字节选择器 + 参数)，参数类型必须与签名一致
+pub fn encode_call(signature: &str, args: &[AbiValue]) -> Result<Vec<u8>> {
+    let (name, types) = parse_signature(signature)?;
+    let expected: Vec<&str> = args.iter().map(AbiValue::type_name).collect();
+    let types: Vec<&str> = types
+        .iter()
+        .map(|t| if t == "uint" { "uint256" } else { t.as_str() })
+        .collect();
+    if types != expected {
+        return Err(anyhow!(
+            "❌ 函数 {} 的参数应为 ({})，签名中是 ({})",
+            name,
+            expected.join(","),
+            types.join(",")
+        ));
+    }
+
+    let canonical = format!("{}({})", name, types.join(","));
+    let mut data = keccak256(canonical.as_bytes())[..4].to_vec();
+    let mut tail = Vec::new();
+    let head_size = 32 * args.len();
+    for arg in args {
+        match arg {
+            AbiValue::Uint(value) => data.extend_from_slice(&word(*value)),
+            AbiValue::String(value) => {
+                data.extend_from_slice(&word((head_size + tail.len()) as u128));
+                tail.extend_from_slice(&word(value.len() as u128));
+                tail.extend_from_slice(value.as_bytes());
+                tail.resize(tail.len().div_ceil(32) * 32, 0);
+            }
+        }
+    }
+    data.extend_from_slice(&tail);
+    Ok(data)
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<usize> {
+    let word = offset
+        .checked_add(32)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| anyhow!("❌ 返回数据太短"))?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return Err(anyhow!("❌ 返回数据中的长度过大"));
+    }
+    Ok(u64::from_be_bytes(word[24..].try_into()?) as usize)
+}
+
+// 解码返回值中的一个 string (如 tokenURI 的返回)
+pub fn decode_string(data: &[u8]) -> Result<String> {
+    let offset = read_word(data, 0)?;
+    let len = read_word(data, offset)?;
+    // offset 和 len 来自返回数据，不能直接相加，否则可能溢出
+    let start = offset
+        .checked_add(32)
+        .ok_or_else(|| anyhow!("❌ 返回数据太短"))?;
+    let bytes = start
+        .checked_add(len)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| anyhow!("❌ 返回数据太短"))?;
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+// ✅ 根据已有 token 的 tokenURI 判断合约是否在 token ID 后加 .json；
+// 不是 "<Base URI><token ID>" 的形式时返回 None
+pub fn suffix_from_token_uri(uri: &str, token_id: u64) -> Option<bool> {
+    let id = token_id.to_string();
+    let ends_with_id = |s: &str| {
+        s.strip_suffix(&id)
+            .is_some_and(|base| !base.ends_with(|c: char| c.is_ascii_digit()))
+    };
+    match uri.strip_suffix(".json") {
+        Some(stem) if ends_with_id(stem) => Some(true),
+        _ if ends_with_id(uri) => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: u64) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    #[test]
+    fn decode_string_reads_abi_encoded_string() {
+        let call = encode_call(
+            "setBaseURI(string)",
+            &[AbiValue::String("ipfs://bafy/".to_string())],
+        )
+        .unwrap();
+        assert_eq!(decode_string(&call[4..]).unwrap(), "ipfs://bafy/");
+    }
+
+    #[test]
+    fn decode_string_rejects_out_of_range_offsets_and_lengths() {
+        // 偏移量接近 usize::MAX
+        let data = [word(u64::MAX - 8), word(0)].concat();
+        assert!(decode_string(&data).is_err());
+
+        // 长度加上偏移量溢出
+        let data = [word(32), word(u64::MAX - 16)].concat();
+        assert!(decode_string(&data).is_err());
+
+        // 长度超出返回数据
+        let data = [word(32), word(64), word(0)].concat();
+        assert!(decode_string(&data).is_err());
+    }
+}
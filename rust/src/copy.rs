@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::filter::FileFilter;
+
+// ✅ 符号链接的处理方式：follow (默认，复制链接指向的内容)、preserve (保留为链接) 或 skip (跳过)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkMode {
+    #[default]
+    Follow,
+    Preserve,
+    Skip,
+}
+
+impl SymlinkMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Follow => "follow",
+            Self::Preserve => "preserve",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+impl fmt::Display for SymlinkMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SymlinkMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "follow" => Ok(Self::Follow),
+            "preserve" | "keep" => Ok(Self::Preserve),
+            "skip" => Ok(Self::Skip),
+            other => Err(format!(
+                "❌ 不支持的符号链接处理方式: {} (可选: follow, preserve, skip)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [copy] 段：复制输入文件夹时如何处理链接、权限和特殊文件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CopyOptions {
+    pub symlinks: SymlinkMode,
+    // 保留文件和文件夹的权限 (unix 的 mode)；默认副本都是可写的，方便之后清理 EXIF、重命名
+    pub preserve_permissions: bool,
+    // 输入中互为硬链接的文件在副本中也建立硬链接，不重复占用磁盘
+    pub hardlinks: bool,
+    // 跳过管道、套接字、设备等特殊文件；关闭时遇到特殊文件返回错误
+    pub skip_special: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            symlinks: SymlinkMode::default(),
+            preserve_permissions: false,
+            hardlinks: false,
+            skip_special: true,
+        }
+    }
+}
+
+// 复制的结果，路径都相对于源文件夹
+#[derive(Debug, Clone, Default)]
+pub struct CopyReport {
+    pub files: usize,
+    // 被过滤规则跳过的文件和文件夹
+    pub filtered: Vec<String>,
+    // symlinks = "skip" 时跳过的链接
+    pub skipped_links: Vec<String>,
+    // 跳过的特殊文件
+    pub special: Vec<String>,
+    // 建立为硬链接的文件
+    pub hardlinked: usize,
+}
+
+fn relative_name(src: &Path, path: &Path) -> String {
+    path.strip_prefix(src)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+// 把 walkdir 的错误转换为带路径的错误，符号链接形成循环时指出循环的位置
+fn walk_error(error: walkdir::Error) -> anyhow::Error {
+    let path = error.path().map(Path::to_path_buf).unwrap_or_default();
+    match error.loop_ancestor() {
+        Some(ancestor) => anyhow!(
+            "❌ 符号链接形成循环: {:?} 指向上层文件夹 {:?}",
+            path,
+            ancestor
+        ),
+        None => anyhow!("❌ 读取 {:?} 失败: {}", path, error),
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path, _is_dir: bool) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path, is_dir: bool) -> std::io::Result<()> {
+    if is_dir {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_symlink(_target: &Path, _link: &Path, _is_dir: bool) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "当前平台不支持符号链接",
+    ))
+}
+
+// 保留的链接必须是指向输入文件夹内部的相对路径，否则副本和上传后的链接都会失效
+fn check_preserved_link(src: &Path, link: &Path, target: &Path) -> Result<()> {
+    if target.is_absolute() {
+        return Err(anyhow!(
+            "❌ 符号链接 {:?} 指向绝对路径 {:?}，symlinks = \"preserve\" 时只能保留相对路径的链接",
+            link,
+            target
+        ));
+    }
+    let resolved = link.parent().unwrap_or(src).join(target);
+    let inside = match (resolved.canonicalize(), src.canonicalize()) {
+        (Ok(resolved), Ok(root)) => resolved.starts_with(root),
+        _ => false,
+    };
+    if !inside {
+        return Err(anyhow!(
+            "❌ 符号链接 {:?} 指向输入文件夹之外或不存在的 {:?}，保留后会失效，请改用 symlinks = \"follow\"",
+            link,
+            target
+        ));
+    }
+    Ok(())
+}
+
+// 硬链接的身份：(设备号, inode)，只有链接数大于 1 的文件才需要记录
+#[cfg(unix)]
+fn hardlink_key(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn hardlink_key(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// 不保留权限时让副本可写 (fs::copy 会复制源文件的权限)
+fn make_writable(path: &Path) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(0o644);
+    }
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+// ✅ 按过滤规则和 [copy] 的选项复制文件夹，被过滤的文件夹整个跳过；出错时指出出错的路径
+pub fn copy_tree(
+    src: &Path,
+    dst: &Path,
+    filter: &FileFilter,
+    options: &CopyOptions,
+) -> Result<CopyReport> {
+    fs::create_dir_all(dst).map_err(|e| anyhow!("❌ 创建 {:?} 失败: {}", dst, e))?;
+    let mut report = CopyReport::default();
+    let mut filtered = Vec::new();
+    // 文件夹的权限在内容复制完成后再设置，避免只读文件夹无法写入
+    let mut dir_permissions: Vec<(PathBuf, fs::Permissions)> = Vec::new();
+    let mut linked: BTreeMap<(u64, u64), PathBuf> = BTreeMap::new();
+
+    let walker = WalkDir::new(src)
+        .follow_links(options.symlinks == SymlinkMode::Follow)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let allowed = filter.allows_path(src, entry.path(), entry.file_type().is_dir());
+            if !allowed {
+                filtered.push(relative_name(src, entry.path()));
+            }
+            allowed
+        });
+    for entry in walker {
+        let entry = entry.map_err(walk_error)?;
+        let path = entry.path();
+        let dest_path = dst.join(path.strip_prefix(src)?);
+        let file_type = entry.file_type();
+
+        if file_type.is_symlink() {
+            // follow 模式下 walkdir 已经解析了链接，这里只会遇到 preserve / skip，或者指向不存在的目标
+            match options.symlinks {
+                SymlinkMode::Skip => report.skipped_links.push(relative_name(src, path)),
+                SymlinkMode::Preserve => {
+                    let target = fs::read_link(path)
+                        .map_err(|e| anyhow!("❌ 读取符号链接 {:?} 失败: {}", path, e))?;
+                    check_preserved_link(src, path, &target)?;
+                    create_symlink(&target, &dest_path, path.is_dir()).map_err(|e| {
+                        anyhow!(
+                            "❌ 创建符号链接 {:?} -> {:?} 失败: {}",
+                            dest_path,
+                            target,
+                            e
+                        )
+                    })?;
+                }
+                SymlinkMode::Follow => {
+                    return Err(anyhow!(
+                        "❌ 符号链接 {:?} 指向的目标不存在: {:?}",
+                        path,
+                        fs::read_link(path).unwrap_or_default()
+                    ));
+                }
+            }
+            continue;
+        }
+
+        let metadata = entry
+            .metadata()
+            .map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?;
+        if file_type.is_dir() {
+            fs::create_dir_all(&dest_path)
+                .map_err(|e| anyhow!("❌ 创建 {:?} 失败: {}", dest_path, e))?;
+            if options.preserve_permissions {
+                dir_permissions.push((dest_path, metadata.permissions()));
+            }
+            continue;
+        }
+        if !file_type.is_file() {
+            if options.skip_special {
+                report.special.push(relative_name(src, path));
+                continue;
+            }
+            return Err(anyhow!(
+                "❌ {:?} 是特殊文件 (管道、套接字或设备)，无法复制",
+                path
+            ));
+        }
+
+        if options.hardlinks
+            && let Some(key) = hardlink_key(&metadata)
+        {
+            if let Some(first) = linked.get(&key) {
+                fs::hard_link(first, &dest_path).map_err(|e| {
+                    anyhow!("❌ 创建硬链接 {:?} -> {:?} 失败: {}", dest_path, first, e)
+                })?;
+                report.hardlinked += 1;
+                report.files += 1;
+                continue;
+            }
+            linked.insert(key, dest_path.clone());
+        }
+        fs::copy(path, &dest_path)
+            .map_err(|e| anyhow!("❌ 复制 {:?} 到 {:?} 失败: {}", path, dest_path, e))?;
+        if !options.preserve_permissions {
+            make_writable(&dest_path)
+                .map_err(|e| anyhow!("❌ 设置 {:?} 的权限失败: {}", dest_path, e))?;
+        }
+        report.files += 1;
+    }
+
+    for (dir, permissions) in dir_permissions.into_iter().rev() {
+        fs::set_permissions(&dir, permissions)
+            .map_err(|e| anyhow!("❌ 设置 {:?} 的权限失败: {}", dir, e))?;
+    }
+    report.filtered = filtered;
+    Ok(report)
+}
@@ -0,0 +1,147 @@
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+
+use crate::backend::{ApiEndpointConfig, HttpBackend, IpfsBackend};
+use crate::exit_code::ExitCode;
+
+pub const DAEMON_LOG_FILE_NAME: &str = "ipfs-daemon.log";
+
+// 自动启动 daemon 的可选项
+#[derive(Debug, Clone)]
+pub struct DaemonOptions {
+    pub binary: String,
+    // 使用 HTTP API 时按这个地址探测节点是否就绪
+    pub api: Option<String>,
+    pub api_endpoints: Vec<ApiEndpointConfig>,
+    // 等待 API 就绪的最长时间
+    pub startup_timeout: Duration,
+    // 工作流结束后是否关闭由本程序启动的 daemon
+    pub shutdown: bool,
+}
+
+impl Default for DaemonOptions {
+    fn default() -> Self {
+        Self {
+            binary: "ipfs".to_string(),
+            api: None,
+            api_endpoints: Vec::new(),
+            startup_timeout: Duration::from_secs(60),
+            shutdown: false,
+        }
+    }
+}
+
+// ✅ 由本程序启动的 daemon；drop 时按需关闭 (包括工作流出错提前返回的情况)
+pub struct ManagedDaemon {
+    child: Option<Child>,
+    options: DaemonOptions,
+}
+
+fn is_running(options: &DaemonOptions) -> bool {
+    match &options.api {
+        Some(api) => HttpBackend::connect(api, &options.api_endpoints)
+            .and_then(|mut backend| backend.check_compatibility())
+            .is_ok(),
+        None => Command::new(&options.binary)
+            .arg("id")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false),
+    }
+}
+
+impl ManagedDaemon {
+    // 节点已经在运行时什么也不做；否则启动 `ipfs daemon --init` 并等待 API 就绪
+    pub fn ensure_running(options: DaemonOptions, log_dir: &Path) -> Result<Self> {
+        if is_running(&options) {
+            println!("✅ IPFS daemon 已在运行，无需自动启动");
+            return Ok(Self {
+                child: None,
+                options,
+            });
+        }
+
+        fs::create_dir_all(log_dir)?;
+        let log_path = log_dir.join(DAEMON_LOG_FILE_NAME);
+        let log = File::create(&log_path)?;
+        println!("🚀 正在启动 IPFS daemon (日志: {:?})...", log_path);
+        let child = Command::new(&options.binary)
+            .args(["daemon", "--init"])
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .spawn()
+            .map_err(|e| {
+                ExitCode::NodeUnreachable.wrap(anyhow!(
+                    "❌ 无法启动 `{} daemon`: {}",
+                    options.binary,
+                    e
+                ))
+            })?;
+        let mut daemon = Self {
+            child: Some(child),
+            options,
+        };
+
+        let started = Instant::now();
+        while !is_running(&daemon.options) {
+            if let Some(child) = daemon.child.as_mut()
+                && let Some(status) = child.try_wait()?
+            {
+                daemon.child = None;
+                return Err(ExitCode::NodeUnreachable.wrap(anyhow!(
+                    "❌ IPFS daemon 启动后立即退出 ({})，详见 {:?}",
+                    status,
+                    log_path
+                )));
+            }
+            if started.elapsed() > daemon.options.startup_timeout {
+                daemon.stop();
+                return Err(ExitCode::NodeUnreachable.wrap(anyhow!(
+                    "❌ 等待 IPFS daemon 就绪超时 ({} 秒)，详见 {:?}",
+                    daemon.options.startup_timeout.as_secs(),
+                    log_path
+                )));
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        println!(
+            "✅ IPFS daemon 已就绪 (耗时 {:.1} 秒)",
+            started.elapsed().as_secs_f64()
+        );
+        Ok(daemon)
+    }
+
+    fn stop(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        println!("\n🛑 正在关闭自动启动的 IPFS daemon...");
+        let graceful = Command::new(&self.options.binary)
+            .arg("shutdown")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !graceful {
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+    }
+}
+
+impl Drop for ManagedDaemon {
+    fn drop(&mut self) {
+        if self.options.shutdown {
+            self.stop();
+        }
+    }
+}
@@ -0,0 +1,605 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::{cursor, execute, terminal};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Row, Sparkline, Table};
+use ratatui::{Frame, Terminal};
+use serde_json::Value;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::estimate::format_bytes;
+use crate::ipld::DagCodec;
+use crate::manifest::EncryptionInfo;
+use crate::metrics::path_size;
+use crate::unixfs::AddConfig;
+use crate::verbosity::color_allowed;
+
+// 刷新间隔
+const REFRESH: Duration = Duration::from_millis(250);
+// 吞吐量图保留的秒数
+const THROUGHPUT_WINDOW: usize = 600;
+// 错误和日志面板保留的行数
+const MAX_ERRORS: usize = 100;
+const MAX_LOG_LINES: usize = 200;
+
+// 一个工作线程正在做的事
+#[derive(Debug, Clone)]
+struct Task {
+    operation: &'static str,
+    target: String,
+    since: Instant,
+}
+
+// 每个节点 / 服务的上传和 pin 结果
+#[derive(Debug, Clone, Default)]
+struct ProviderStatus {
+    uploads: u64,
+    pinned: u64,
+    unpinned: u64,
+    failed: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    total_files: u64,
+    total_bytes: u64,
+    files_done: u64,
+    bytes_done: u64,
+    // 线程名 -> 当前任务
+    tasks: BTreeMap<String, Task>,
+    errors: VecDeque<String>,
+    // 每秒发送的字节数
+    throughput: VecDeque<u64>,
+    providers: BTreeMap<String, ProviderStatus>,
+    log: VecDeque<String>,
+}
+
+fn push_limited(lines: &mut VecDeque<String>, line: String, limit: usize) {
+    lines.push_back(line);
+    while lines.len() > limit {
+        lines.pop_front();
+    }
+}
+
+// ✅ --tui 仪表盘的状态：由 DashboardBackend 和 HTTP 请求体的计数器更新，由 DashboardScreen 绘制
+#[derive(Debug)]
+pub struct Dashboard {
+    started: Instant,
+    // 请求体已经发送的字节数 (HTTP 类后端实时更新)
+    sent: Arc<AtomicU64>,
+    state: Mutex<State>,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            sent: Arc::new(AtomicU64::new(0)),
+            state: Mutex::new(State::default()),
+        }
+    }
+}
+
+impl Dashboard {
+    fn with<T>(&self, f: impl FnOnce(&mut State) -> T) -> T {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut state)
+    }
+
+    // 传给 Throttle::with_counter，上传过程中也能看到进度
+    pub fn sent_counter(&self) -> Arc<AtomicU64> {
+        self.sent.clone()
+    }
+
+    // 这次运行预计上传的文件数和字节数
+    pub fn set_totals(&self, files: u64, bytes: u64) {
+        self.with(|state| {
+            state.total_files = files;
+            state.total_bytes = bytes;
+        });
+    }
+
+    fn worker() -> String {
+        let current = thread::current();
+        match current.name() {
+            Some(name) => name.to_string(),
+            None => format!("{:?}", current.id()),
+        }
+    }
+
+    fn begin(&self, operation: &'static str, target: String) {
+        let task = Task {
+            operation,
+            target,
+            since: Instant::now(),
+        };
+        self.with(|state| state.tasks.insert(Self::worker(), task));
+    }
+
+    fn end(&self) {
+        self.with(|state| state.tasks.remove(&Self::worker()));
+    }
+
+    fn record<T>(&self, provider: &str, size: (u64, u64), result: &Result<T>) {
+        self.with(|state| {
+            let status = state.providers.entry(provider.to_string()).or_default();
+            match result {
+                Ok(_) => {
+                    status.uploads += 1;
+                    state.files_done += size.0;
+                    state.bytes_done += size.1;
+                }
+                Err(e) => {
+                    status.failed += 1;
+                    let line = format!("{} {}", chrono::Local::now().format("%H:%M:%S"), e);
+                    push_limited(&mut state.errors, line, MAX_ERRORS);
+                }
+            }
+        });
+    }
+
+    fn record_pin(&self, provider: &str, result: &Result<bool>) {
+        self.with(|state| {
+            let status = state.providers.entry(provider.to_string()).or_default();
+            match result {
+                Ok(true) => status.pinned += 1,
+                Ok(false) => status.unpinned += 1,
+                Err(e) => {
+                    status.failed += 1;
+                    let line = format!("{} {}", chrono::Local::now().format("%H:%M:%S"), e);
+                    push_limited(&mut state.errors, line, MAX_ERRORS);
+                }
+            }
+        });
+    }
+
+    // 已经发送的字节数：命令行后端没有实时计数，使用已完成的上传
+    fn progress_bytes(&self, state: &State) -> u64 {
+        self.sent.load(Ordering::Relaxed).max(state.bytes_done)
+    }
+}
+
+// ✅ 包装任意后端，把当前任务、上传结果、错误和 pin 状态记录到仪表盘
+pub struct DashboardBackend {
+    inner: Box<dyn IpfsBackend>,
+    dashboard: Arc<Dashboard>,
+}
+
+impl DashboardBackend {
+    pub fn new(inner: Box<dyn IpfsBackend>, dashboard: Arc<Dashboard>) -> Self {
+        Self { inner, dashboard }
+    }
+
+    fn upload(
+        &self,
+        operation: &'static str,
+        target: String,
+        size: (u64, u64),
+        f: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        self.dashboard.begin(operation, target);
+        let result = f();
+        self.dashboard.end();
+        let provider = match &result {
+            Ok(cid) => self.inner.served_by(cid),
+            Err(_) => None,
+        };
+        self.dashboard.record(
+            provider.as_deref().unwrap_or(self.inner.name()),
+            size,
+            &result,
+        );
+        result
+    }
+}
+
+impl IpfsBackend for DashboardBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        self.inner.check_compatibility()
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        let target = target_path.display().to_string();
+        self.upload("add", target, path_size(target_path), || {
+            self.inner.add_path(target_path)
+        })
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        let target = format!("{} 字节的数据", data.len());
+        self.upload("add", target, (1, data.len() as u64), || {
+            self.inner.add_bytes(data)
+        })
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        let target = format!("{} 节点", codec);
+        self.upload("dag put", target, (0, 0), || {
+            self.inner.dag_put(node, codec)
+        })
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.inner.add_config()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        self.dashboard.begin("pin ls", cid.to_string());
+        let result = self.inner.is_pinned(cid);
+        self.dashboard.end();
+        let provider = self.inner.served_by(cid);
+        self.dashboard
+            .record_pin(provider.as_deref().unwrap_or(self.inner.name()), &result);
+        result
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        self.dashboard.begin("pin", cid.to_string());
+        let result = self.inner.pin(cid);
+        self.dashboard.end();
+        let provider = self.inner.served_by(cid);
+        self.dashboard.record_pin(
+            provider.as_deref().unwrap_or(self.inner.name()),
+            &result.as_ref().map(|_| true).map_err(|e| anyhow!("{}", e)),
+        );
+        result
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.inner.unpin(cid)
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        self.inner.ls(path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.cat(path)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        self.dashboard.begin("provide", cid.to_string());
+        let result = self.inner.provide(cid, recursive);
+        self.dashboard.end();
+        result
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        self.inner.find_providers(cid, limit)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.inner.served_by(cid)
+    }
+
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        self.inner.encryption()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.inner.node_version()
+    }
+}
+
+fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn draw(frame: &mut Frame, dashboard: &Dashboard, state: &State) {
+    let [progress_area, middle, throughput_area, bottom] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(6),
+        Constraint::Min(5),
+    ])
+    .areas(frame.area());
+
+    // 总进度：按字节计算，没有总量时只显示已发送的字节
+    let elapsed = dashboard.started.elapsed();
+    let sent = dashboard.progress_bytes(state);
+    let ratio = match state.total_bytes {
+        0 => 0.0,
+        total => (sent as f64 / total as f64).min(1.0),
+    };
+    let rate = sent as f64 / elapsed.as_secs_f64().max(1.0);
+    let eta = match (state.total_bytes.checked_sub(sent), rate > 0.0) {
+        (Some(remaining), true) if remaining > 0 => {
+            format_elapsed(Duration::from_secs_f64(remaining as f64 / rate))
+        }
+        _ => "--:--:--".to_string(),
+    };
+    let label = format!(
+        "{}/{} 个文件 · {} / {} · 已用 {} · 剩余 {}",
+        state.files_done,
+        state.total_files,
+        format_bytes(sent),
+        format_bytes(state.total_bytes),
+        format_elapsed(elapsed),
+        eta
+    );
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title(" 总进度 "))
+            .gauge_style(fg(Color::Green))
+            .ratio(ratio)
+            .label(label),
+        progress_area,
+    );
+
+    let [tasks_area, pins_area] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(middle);
+    let tasks: Vec<ListItem> = state
+        .tasks
+        .iter()
+        .map(|(worker, task)| {
+            ListItem::new(format!(
+                "{:<10} {:<8} {} ({}s)",
+                worker,
+                task.operation,
+                task.target,
+                task.since.elapsed().as_secs()
+            ))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(tasks).block(Block::default().borders(Borders::ALL).title(" 当前任务 ")),
+        tasks_area,
+    );
+    let rows = state.providers.iter().map(|(provider, status)| {
+        Row::new(vec![
+            provider.clone(),
+            status.uploads.to_string(),
+            status.pinned.to_string(),
+            status.unpinned.to_string(),
+            status.failed.to_string(),
+        ])
+    });
+    frame.render_widget(
+        Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(vec!["节点", "上传", "已固定", "未固定", "失败"]).style(fg(Color::Yellow)))
+        .block(Block::default().borders(Borders::ALL).title(" Pin 状态 ")),
+        pins_area,
+    );
+
+    // 吞吐量图只显示放得下的最近几秒
+    let width = throughput_area.width.saturating_sub(2) as usize;
+    let samples: Vec<u64> = state
+        .throughput
+        .iter()
+        .skip(state.throughput.len().saturating_sub(width))
+        .copied()
+        .collect();
+    let current = samples.last().copied().unwrap_or(0);
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                " 吞吐量 {}/s (平均 {}/s) ",
+                format_bytes(current),
+                format_bytes(rate as u64)
+            )))
+            .data(&samples)
+            .style(fg(Color::Cyan)),
+        throughput_area,
+    );
+
+    let [errors_area, log_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+    draw_lines(frame, errors_area, " 最近错误 ", &state.errors, Color::Red);
+    draw_lines(frame, log_area, " 日志 ", &state.log, Color::Reset);
+}
+
+// 设置了 NO_COLOR 时只用终端默认颜色
+fn fg(color: Color) -> Style {
+    if color_allowed() {
+        Style::default().fg(color)
+    } else {
+        Style::default()
+    }
+}
+
+// 只显示放得下的最后几行
+fn draw_lines(frame: &mut Frame, area: Rect, title: &str, lines: &VecDeque<String>, color: Color) {
+    let height = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = lines
+        .iter()
+        .skip(lines.len().saturating_sub(height))
+        .map(|line| ListItem::new(line.as_str()))
+        .collect();
+    frame.render_widget(
+        List::new(items)
+            .style(fg(color))
+            .block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+// 标准输出 / 错误重定向到日志文件，结束时恢复
+#[cfg(unix)]
+struct Redirect {
+    saved: [i32; 2],
+}
+
+#[cfg(unix)]
+impl Redirect {
+    fn to(log: &File) -> Result<Self> {
+        use std::os::fd::AsRawFd;
+        io::stdout().flush()?;
+        io::stderr().flush()?;
+        let mut saved = [-1; 2];
+        for (slot, fd) in saved
+            .iter_mut()
+            .zip([libc::STDOUT_FILENO, libc::STDERR_FILENO])
+        {
+            // SAFETY: 只复制和替换本进程的标准输出 / 错误描述符
+            unsafe {
+                *slot = libc::dup(fd);
+                if *slot < 0 || libc::dup2(log.as_raw_fd(), fd) < 0 {
+                    return Err(anyhow!("❌ 重定向输出失败: {}", io::Error::last_os_error()));
+                }
+            }
+        }
+        Ok(Self { saved })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Redirect {
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+        for (saved, fd) in self
+            .saved
+            .iter()
+            .zip([libc::STDOUT_FILENO, libc::STDERR_FILENO])
+        {
+            // SAFETY: 恢复 Redirect::to 中保存的描述符
+            unsafe {
+                libc::dup2(*saved, fd);
+                libc::close(*saved);
+            }
+        }
+    }
+}
+
+// 读取日志文件新增的行：放进日志面板，❌ / ⚠️ 开头的同时放进错误面板
+fn tail_log(log: &mut File, offset: &mut u64, partial: &mut String, state: &mut State) {
+    let mut buf = Vec::new();
+    if log.seek(SeekFrom::Start(*offset)).is_err() || log.read_to_end(&mut buf).is_err() {
+        return;
+    }
+    *offset += buf.len() as u64;
+    partial.push_str(&String::from_utf8_lossy(&buf));
+    while let Some(end) = partial.find('\n') {
+        let line: String = partial.drain(..=end).collect();
+        let line = line.trim_end().to_string();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('❌') || trimmed.starts_with("⚠️") {
+            push_limited(&mut state.errors, line.clone(), MAX_ERRORS);
+        }
+        push_limited(&mut state.log, line, MAX_LOG_LINES);
+    }
+}
+
+// ✅ 全屏仪表盘：程序输出写入日志文件，界面画在终端 (/dev/tty) 上；drop 时恢复终端
+pub struct DashboardScreen {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    log_path: PathBuf,
+    #[cfg(unix)]
+    redirect: Option<Redirect>,
+}
+
+impl DashboardScreen {
+    pub fn show(dashboard: Arc<Dashboard>, log_path: &Path) -> Result<Self> {
+        #[cfg(not(unix))]
+        {
+            let _ = (dashboard, log_path);
+            Err(anyhow!("❌ --tui 目前只支持 Linux 和 macOS"))
+        }
+        #[cfg(unix)]
+        {
+            if let Some(parent) = log_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let log = File::create(log_path)
+                .map_err(|e| anyhow!("❌ 创建日志文件 {:?} 失败: {}", log_path, e))?;
+            let tty = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open("/dev/tty")
+                .map_err(|e| anyhow!("❌ --tui 需要在终端中运行: {}", e))?;
+            println!("📺 仪表盘已启动，完整输出写入 {:?}", log_path);
+            let redirect = Redirect::to(&log)?;
+            let mut terminal = Terminal::new(CrosstermBackend::new(tty))?;
+            execute!(
+                terminal.backend_mut(),
+                terminal::EnterAlternateScreen,
+                cursor::Hide
+            )?;
+            terminal.clear()?;
+
+            let stop = Arc::new(AtomicBool::new(false));
+            let mut reader = File::open(log_path)?;
+            let thread = {
+                let stop = stop.clone();
+                thread::spawn(move || {
+                    let mut offset = 0;
+                    let mut partial = String::new();
+                    let mut last_sample = (Instant::now(), 0u64);
+                    loop {
+                        let stopping = stop.load(Ordering::SeqCst);
+                        dashboard.with(|state| {
+                            tail_log(&mut reader, &mut offset, &mut partial, state);
+                            let sent = dashboard.progress_bytes(state);
+                            if last_sample.0.elapsed() >= Duration::from_secs(1) {
+                                let delta = sent.saturating_sub(last_sample.1);
+                                state.throughput.push_back(delta);
+                                while state.throughput.len() > THROUGHPUT_WINDOW {
+                                    state.throughput.pop_front();
+                                }
+                                last_sample = (Instant::now(), sent);
+                            }
+                            let _ = terminal.draw(|frame| draw(frame, &dashboard, state));
+                        });
+                        if stopping {
+                            break;
+                        }
+                        thread::sleep(REFRESH);
+                    }
+                    let _ = execute!(
+                        terminal.backend_mut(),
+                        terminal::LeaveAlternateScreen,
+                        cursor::Show
+                    );
+                })
+            };
+            Ok(Self {
+                stop,
+                thread: Some(thread),
+                log_path: log_path.to_path_buf(),
+                redirect: Some(redirect),
+            })
+        }
+    }
+}
+
+impl Drop for DashboardScreen {
+    fn drop(&mut self) {
+        // 先让界面线程画完最后一帧并离开全屏，再恢复输出
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        #[cfg(unix)]
+        drop(self.redirect.take());
+        println!("📄 仪表盘期间的完整输出: {:?}", self.log_path);
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+
+use crate::backend::IpfsBackend;
+use crate::links::token_links;
+use crate::manifest::Manifest;
+
+// 示例图片保存在输出目录下的这个文件夹中
+pub const DEMO_IMAGES_DIR: &str = "demo_images";
+
+// 示例图片的边长 (像素)
+const DEMO_IMAGE_SIZE: u32 = 256;
+
+// 色相 (0-360) 转换为 RGB，饱和度和亮度固定
+pub fn hue_to_rgb(hue: f64, value: f64) -> [u8; 3] {
+    let sector = (hue.rem_euclid(360.0)) / 60.0;
+    let fraction = sector - sector.floor();
+    let (p, q, t) = (0.25, 1.0 - 0.75 * fraction, 0.25 + 0.75 * fraction);
+    let (r, g, b) = match sector as u32 {
+        0 => (1.0, t, p),
+        1 => (q, 1.0, p),
+        2 => (p, 1.0, t),
+        3 => (p, q, 1.0),
+        4 => (t, p, 1.0),
+        _ => (1.0, p, q),
+    };
+    [r, g, b].map(|c| (c * value * 255.0).round() as u8)
+}
+
+// 每个 token 的颜色和圆环数量不同：渐变背景 + 中间的同心圆
+fn draw_token(token_id: u64) -> Vec<u8> {
+    let size = DEMO_IMAGE_SIZE as f64;
+    let hue = (token_id as f64 * 137.5) % 360.0;
+    let rings = token_id % 5 + 2;
+    let mut pixels = Vec::with_capacity((DEMO_IMAGE_SIZE * DEMO_IMAGE_SIZE * 3) as usize);
+    for y in 0..DEMO_IMAGE_SIZE {
+        for x in 0..DEMO_IMAGE_SIZE {
+            let (fx, fy) = (x as f64 / size, y as f64 / size);
+            let distance = ((fx - 0.5).powi(2) + (fy - 0.5).powi(2)).sqrt();
+            let color = if distance < 0.35 {
+                // 圆内按距离交替两种颜色
+                let band = (distance / 0.35 * rings as f64) as u64;
+                if band.is_multiple_of(2) {
+                    hue_to_rgb(hue + 180.0, 0.95)
+                } else {
+                    hue_to_rgb(hue + 150.0, 0.6)
+                }
+            } else {
+                hue_to_rgb(hue + (fx + fy) * 40.0, 0.5 + 0.4 * (1.0 - fy))
+            };
+            pixels.extend_from_slice(&color);
+        }
+    }
+    pixels
+}
+
+// 写入 size x size 的 RGB 图片
+pub fn write_png(path: &Path, size: u32, pixels: &[u8]) -> Result<()> {
+    let writer = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(writer, size, size);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()?
+        .write_image_data(pixels)
+        .map_err(|e| anyhow!("❌ 写入图片 {:?} 失败: {}", path, e))
+}
+
+// ✅ 在 dir 中生成 1.png ~ count.png，内容只由 token ID 决定，每次生成的文件相同
+pub fn generate_demo_images(dir: &Path, count: u64) -> Result<Vec<PathBuf>> {
+    if count == 0 {
+        return Err(anyhow!("❌ 示例图片数量必须大于 0"));
+    }
+    // 清理上一次生成的图片，避免数量变少时留下旧文件
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    (1..=count)
+        .map(|token_id| {
+            let path = dir.join(format!("{}.png", token_id));
+            write_png(&path, DEMO_IMAGE_SIZE, &draw_token(token_id))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+// 示例流程结束后的汇总：每个 token 的 URI 和网关地址，以及 pin 状态
+pub fn print_demo_summary(
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+    gateway: &str,
+    elapsed: Duration,
+) {
+    println!("\n🎉 示例流程完成，用时 {:.1} 秒", elapsed.as_secs_f64());
+    println!("   - 图片 CID: {}", manifest.image_cid);
+    println!("   - 元数据 CID: {}", manifest.metadata_cid);
+    println!("   - Base URI: {}", manifest.token_uri);
+    for link in token_links(manifest, gateway, None) {
+        println!("   {} → {}", link.token_uri, link.metadata_url);
+    }
+    match backend.is_pinned(&manifest.metadata_cid) {
+        Ok(true) => println!("📌 元数据文件夹已在节点上固定"),
+        Ok(false) => println!("⚠️  元数据文件夹没有在节点上固定"),
+        Err(e) => println!("⚠️  无法检查 pin 状态: {}", e),
+    }
+    println!("\n工具链可以正常工作，接下来可以用 `batch <图片文件夹>` 上传自己的集合。");
+}
@@ -0,0 +1,142 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::allowlist::AllowlistProofs;
+use crate::hashing::to_hex;
+use crate::manifest::{CollectionKind, Manifest};
+
+pub const DEPLOY_CONFIG_FILE_NAME: &str = "deploy-config.json";
+pub const DEPLOY_ENV_FILE_NAME: &str = "deploy.env";
+
+// deploy-config 的可选项，未提供的 URI 写为空字符串
+#[derive(Debug, Clone, Default)]
+pub struct DeployOptions {
+    // 集合级元数据 (OpenSea contractURI)
+    pub contract_uri: String,
+    // 揭晓前所有 token 共用的占位元数据
+    pub hidden_uri: String,
+    // 默认等于清单中的 token 数
+    pub max_supply: Option<u64>,
+}
+
+// ✅ 部署合约需要的参数；字段名使用 camelCase，Foundry 的 vm.parseJson 和 Hardhat 的 require 可以直接读取
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployConfig {
+    #[serde(rename = "baseURI")]
+    pub base_uri: String,
+    #[serde(rename = "contractURI")]
+    pub contract_uri: String,
+    #[serde(rename = "hiddenURI")]
+    pub hidden_uri: String,
+    pub provenance_hash: String,
+    pub max_supply: u64,
+    pub first_token_id: u64,
+    #[serde(rename = "imagesCID")]
+    pub images_cid: String,
+    #[serde(rename = "metadataCID")]
+    pub metadata_cid: String,
+    // 清单目录中有 allowlist_proofs.json 时写入
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merkle_root: Option<String>,
+}
+
+// ✅ 来源证明哈希：按 token ID 顺序拼接每张图片的 SHA-256 (十六进制)，再整体计算一次 SHA-256
+pub fn provenance_hash(image_hashes: &[String]) -> String {
+    format!("0x{}", to_hex(&Sha256::digest(image_hashes.concat())))
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let data = fs::read(path).map_err(|e| anyhow!("❌ 读取图片 {:?} 失败: {}", path, e))?;
+    Ok(to_hex(&Sha256::digest(data)))
+}
+
+// ✅ 清单对应集合的来源证明哈希；单件的图片在运行目录下，其它在 images/ 下
+pub fn collection_provenance(manifest: &Manifest, dir: &Path) -> Result<String> {
+    let images_dir = match manifest.kind {
+        CollectionKind::Single => dir.to_path_buf(),
+        _ => dir.join("images"),
+    };
+    let mut tokens: Vec<_> = manifest.tokens.iter().collect();
+    tokens.sort_by_key(|token| token.token_id);
+    let image_hashes = tokens
+        .iter()
+        .map(|token| sha256_file(&images_dir.join(&token.image_file)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(provenance_hash(&image_hashes))
+}
+
+// 根据批量清单生成部署参数，图片从运行目录的 images/ 读取
+pub fn build_deploy_config(
+    manifest: &Manifest,
+    dir: &Path,
+    options: &DeployOptions,
+) -> Result<DeployConfig> {
+    if manifest.kind == CollectionKind::Single {
+        return Err(anyhow!(
+            "❌ 单件 NFT 没有 Base URI，deploy-config 只支持批量集合"
+        ));
+    }
+    let mut tokens: Vec<_> = manifest.tokens.iter().collect();
+    tokens.sort_by_key(|token| token.token_id);
+    let token_count = tokens.len() as u64;
+    let max_supply = options.max_supply.unwrap_or(token_count);
+    if max_supply < token_count {
+        return Err(anyhow!(
+            "❌ --max-supply {} 小于集合中的 token 数 {}",
+            max_supply,
+            token_count
+        ));
+    }
+    Ok(DeployConfig {
+        base_uri: manifest.token_uri.clone(),
+        contract_uri: options.contract_uri.clone(),
+        hidden_uri: options.hidden_uri.clone(),
+        provenance_hash: collection_provenance(manifest, dir)?,
+        max_supply,
+        first_token_id: tokens
+            .first()
+            .and_then(|token| token.token_id)
+            .unwrap_or_default(),
+        images_cid: manifest.image_cid.clone(),
+        metadata_cid: manifest.metadata_cid.clone(),
+        merkle_root: AllowlistProofs::load(dir)?.map(|proofs| proofs.root),
+    })
+}
+
+// .env 片段，Foundry 的 vm.envString 和 Hardhat 的 dotenv 都可以读取
+pub fn render_env(config: &DeployConfig) -> String {
+    let mut out = String::new();
+    for (key, value) in [
+        ("BASE_URI", config.base_uri.clone()),
+        ("CONTRACT_URI", config.contract_uri.clone()),
+        ("HIDDEN_URI", config.hidden_uri.clone()),
+        ("PROVENANCE_HASH", config.provenance_hash.clone()),
+        ("MAX_SUPPLY", config.max_supply.to_string()),
+        (
+            "MERKLE_ROOT",
+            config.merkle_root.clone().unwrap_or_default(),
+        ),
+    ] {
+        let _ = writeln!(out, "{}=\"{}\"", key, value);
+    }
+    out
+}
+
+// 写入 deploy-config.json，按需同时写入 deploy.env
+pub fn write_deploy_config(config: &DeployConfig, dir: &Path, env: bool) -> Result<Vec<PathBuf>> {
+    let json_path = dir.join(DEPLOY_CONFIG_FILE_NAME);
+    fs::write(&json_path, serde_json::to_string_pretty(config)?)?;
+    let mut paths = vec![json_path];
+    if env {
+        let env_path = dir.join(DEPLOY_ENV_FILE_NAME);
+        fs::write(&env_path, render_env(config))?;
+        paths.push(env_path);
+    }
+    Ok(paths)
+}
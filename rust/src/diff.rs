@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use crate::backend::IpfsBackend;
+use crate::clone::parse_ipfs_path;
+use crate::manifest::MANIFEST_FILE_NAME;
+
+// 对比的一边：本地元数据文件夹或 IPFS 上的元数据文件夹
+#[derive(Debug, Clone)]
+pub enum MetadataSource {
+    Local(PathBuf),
+    Ipfs(String),
+}
+
+impl MetadataSource {
+    // 本地存在的路径按文件夹处理 (一次运行的目录会使用其中的 metadata/)，其它按 CID 处理
+    pub fn parse(input: &str) -> Result<Self> {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let metadata_dir = path.join("metadata");
+            if path.join(MANIFEST_FILE_NAME).exists() && metadata_dir.is_dir() {
+                return Ok(Self::Local(metadata_dir));
+            }
+            return Ok(Self::Local(path.to_path_buf()));
+        }
+        if path.exists() {
+            return Err(anyhow!("❌ {} 不是文件夹", input));
+        }
+        Ok(Self::Ipfs(parse_ipfs_path(input)?))
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Local(path) => path.display().to_string(),
+            Self::Ipfs(path) => format!("ipfs://{}", path),
+        }
+    }
+}
+
+// 一个元数据文件夹：token (文件名去掉 .json) -> 元数据
+#[derive(Debug, Clone, Default)]
+pub struct MetadataSet {
+    pub tokens: BTreeMap<String, Value>,
+    // 不是 JSON 的文件
+    pub skipped: Vec<String>,
+}
+
+fn token_key(file_name: &str) -> String {
+    file_name
+        .strip_suffix(".json")
+        .unwrap_or(file_name)
+        .to_string()
+}
+
+impl MetadataSet {
+    fn insert(&mut self, name: &str, data: &[u8]) {
+        match serde_json::from_slice(data) {
+            Ok(value) => {
+                self.tokens.insert(token_key(name), value);
+            }
+            Err(_) => self.skipped.push(name.to_string()),
+        }
+    }
+}
+
+// ✅ 读取一边的所有元数据；CID 需要通过节点读取
+pub fn load_metadata_set(
+    source: &MetadataSource,
+    backend: Option<&dyn IpfsBackend>,
+) -> Result<MetadataSet> {
+    let mut set = MetadataSet::default();
+    match source {
+        MetadataSource::Local(dir) => {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                if path.is_file() && !name.starts_with('.') {
+                    set.insert(name, &fs::read(&path)?);
+                }
+            }
+        }
+        MetadataSource::Ipfs(root) => {
+            let backend =
+                backend.ok_or_else(|| anyhow!("❌ 读取 ipfs://{} 需要连接 IPFS 节点", root))?;
+            println!("\n--- 正在读取元数据文件夹: {} ---", root);
+            for entry in backend.ls(root)? {
+                if !entry.is_dir {
+                    set.insert(
+                        &entry.name,
+                        &backend.cat(&format!("{}/{}", root, entry.name))?,
+                    );
+                }
+            }
+        }
+    }
+    Ok(set)
+}
+
+// 一个字段在两边的值，缺失时为 None
+#[derive(Debug, Clone)]
+pub struct FieldChange {
+    pub field: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenChange {
+    pub key: String,
+    pub fields: Vec<FieldChange>,
+}
+
+// ✅ 两个元数据文件夹的差异
+#[derive(Debug, Clone, Default)]
+pub struct MetadataDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<TokenChange>,
+    pub unchanged: usize,
+}
+
+impl MetadataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// 展开成 字段 -> 值；attributes 按 trait_type 展开，顺序变化不算差异
+fn flatten(metadata: &Value) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    let Some(object) = metadata.as_object() else {
+        fields.insert(String::new(), display_value(metadata));
+        return fields;
+    };
+    for (key, value) in object {
+        let attributes = value.as_array().filter(|_| key == "attributes");
+        match attributes {
+            Some(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let name = item
+                        .get("trait_type")
+                        .and_then(Value::as_str)
+                        .map_or_else(|| format!("[{}]", index), str::to_string);
+                    let value = item.get("value").unwrap_or(item);
+                    fields.insert(format!("attributes.{}", name), display_value(value));
+                }
+            }
+            None => {
+                fields.insert(key.clone(), display_value(value));
+            }
+        }
+    }
+    fields
+}
+
+// token 按数字顺序排列 (10 排在 9 之后)
+fn sort_keys(keys: &mut [String]) {
+    keys.sort_by_key(|key| (key.parse::<u64>().ok(), key.clone()));
+}
+
+pub fn diff_metadata(a: &MetadataSet, b: &MetadataSet) -> MetadataDiff {
+    let mut diff = MetadataDiff::default();
+    let keys: BTreeSet<&String> = a.tokens.keys().chain(b.tokens.keys()).collect();
+    for key in keys {
+        let (ta, tb) = match (a.tokens.get(key), b.tokens.get(key)) {
+            (Some(ta), Some(tb)) => (ta, tb),
+            (Some(_), None) => {
+                diff.removed.push(key.clone());
+                continue;
+            }
+            (None, _) => {
+                diff.added.push(key.clone());
+                continue;
+            }
+        };
+        let (fa, fb) = (flatten(ta), flatten(tb));
+        let fields: BTreeSet<&String> = fa.keys().chain(fb.keys()).collect();
+        let changes: Vec<FieldChange> = fields
+            .into_iter()
+            .filter(|field| fa.get(*field) != fb.get(*field))
+            .map(|field| FieldChange {
+                field: field.clone(),
+                a: fa.get(field).cloned(),
+                b: fb.get(field).cloned(),
+            })
+            .collect();
+        if changes.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            diff.changed.push(TokenChange {
+                key: key.clone(),
+                fields: changes,
+            });
+        }
+    }
+    sort_keys(&mut diff.added);
+    sort_keys(&mut diff.removed);
+    diff.changed
+        .sort_by_key(|change| (change.key.parse::<u64>().ok(), change.key.clone()));
+    diff
+}
+
+// 最多列出的 token 数，避免大集合刷屏
+const MAX_LISTED_TOKENS: usize = 50;
+
+fn list_keys(out: &mut String, label: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    let listed: Vec<&str> = keys
+        .iter()
+        .take(MAX_LISTED_TOKENS)
+        .map(String::as_str)
+        .collect();
+    let _ = write!(
+        out,
+        "- {} ({} 个): {}",
+        label,
+        keys.len(),
+        listed.join(", ")
+    );
+    if keys.len() > MAX_LISTED_TOKENS {
+        let _ = write!(out, " 等");
+    }
+    out.push('\n');
+}
+
+// Markdown 表格中的值
+fn cell(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("`{}`", value.replace('|', "\\|")),
+        None => "(无)".to_string(),
+    }
+}
+
+pub fn render_diff(a: &str, b: &str, diff: &MetadataDiff) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# 元数据对比\n");
+    let _ = writeln!(out, "- **A**: `{}`", a);
+    let _ = writeln!(out, "- **B**: `{}`\n", b);
+    if diff.is_empty() {
+        let _ = writeln!(out, "✅ 两边的 {} 个 token 完全一致。", diff.unchanged);
+        return out;
+    }
+    list_keys(&mut out, "新增", &diff.added);
+    list_keys(&mut out, "删除", &diff.removed);
+    let _ = writeln!(
+        out,
+        "- 修改: {} 个，不变: {} 个",
+        diff.changed.len(),
+        diff.unchanged
+    );
+    if !diff.changed.is_empty() {
+        let _ = writeln!(out, "\n| Token | 字段 | A | B |\n| --- | --- | --- | --- |");
+        for token in diff.changed.iter().take(MAX_LISTED_TOKENS) {
+            for field in &token.fields {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    token.key,
+                    field.field,
+                    cell(&field.a),
+                    cell(&field.b)
+                );
+            }
+        }
+        if diff.changed.len() > MAX_LISTED_TOKENS {
+            let _ = writeln!(
+                out,
+                "\n另有 {} 个修改的 token 未列出。",
+                diff.changed.len() - MAX_LISTED_TOKENS
+            );
+        }
+    }
+    out
+}
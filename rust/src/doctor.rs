@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::backend::{ApiEndpointConfig, CliBackend, HttpBackend, IpfsBackend};
+use crate::config::Config;
+use crate::pinning::{PinningServiceConfig, pins_endpoint};
+
+// 可用空间低于这个值时给出警告
+const LOW_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+// 一项检查的结果，失败时附带修复建议
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+impl CheckResult {
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+fn check_binary(binary: &str) -> CheckResult {
+    let name = "ipfs 命令行";
+    match Command::new(binary).args(["version", "-n"]).output() {
+        Ok(output) if output.status.success() => CheckResult::ok(
+            name,
+            format!("Kubo {}", String::from_utf8_lossy(&output.stdout).trim()),
+        ),
+        Ok(output) => CheckResult::fail(
+            name,
+            format!(
+                "`{} version` 执行失败: {}",
+                binary,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            "重新安装 Kubo: https://docs.ipfs.tech/install/command-line/",
+        ),
+        Err(e) => CheckResult::fail(
+            name,
+            format!("找不到 `{}`: {}", binary, e),
+            "安装 Kubo 并确保 `ipfs` 在 PATH 中: https://docs.ipfs.tech/install/command-line/",
+        ),
+    }
+}
+
+fn check_daemon(api: Option<&str>, endpoints: &[ApiEndpointConfig]) -> CheckResult {
+    let name = "IPFS 节点";
+    let (target, backend): (String, Result<Box<dyn IpfsBackend>>) = match api {
+        Some(api) => (
+            format!("API {}", api),
+            HttpBackend::connect(api, endpoints).map(|b| Box::new(b) as Box<dyn IpfsBackend>),
+        ),
+        None => (
+            "本地 ipfs 命令行".to_string(),
+            Ok(Box::new(CliBackend::default())),
+        ),
+    };
+    let fix = match api {
+        Some(_) => {
+            "确认 daemon 已启动，并检查 --api 地址、端口或 unix socket 路径是否正确 (Kubo 默认 /ip4/127.0.0.1/tcp/5001)"
+        }
+        None => "运行 `ipfs daemon` 启动节点；首次使用请先执行 `ipfs init`",
+    };
+    let result = backend.and_then(|mut backend| backend.check_compatibility());
+    match result {
+        Ok(Some(capabilities)) => CheckResult::ok(
+            name,
+            format!("{} 可以连接 (版本: {})", target, capabilities.version),
+        ),
+        Ok(None) => CheckResult::ok(name, format!("{} 可以连接", target)),
+        Err(e) => CheckResult::fail(name, format!("{}: {:#}", target, e), fix),
+    }
+}
+
+fn check_pinning_service(service: &PinningServiceConfig) -> CheckResult {
+    let name = format!("Pinning 服务 {}", service.name);
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return CheckResult::fail(&name, e.to_string(), "检查本机 TLS / 网络配置"),
+    };
+    let response = client
+        .get(pins_endpoint(&service.endpoint))
+        .query(&[("limit", "1")])
+        .bearer_auth(&service.access_token)
+        .send();
+    match response {
+        Ok(res) if res.status().is_success() => CheckResult::ok(&name, "认证有效"),
+        Ok(res) if matches!(res.status().as_u16(), 401 | 403) => CheckResult::fail(
+            &name,
+            format!("认证失败 ({})", res.status()),
+            format!(
+                "在服务控制台重新生成 access token，并更新配置文件中 `{}` 的 access_token",
+                service.name
+            ),
+        ),
+        Ok(res) => CheckResult::fail(
+            &name,
+            format!("服务返回 {}", res.status()),
+            "确认 endpoint 是 Pinning Service API 的根地址 (不包含 /pins)",
+        ),
+        Err(e) => CheckResult::fail(
+            &name,
+            format!("无法连接 {}: {}", service.endpoint, e),
+            "检查网络、代理设置和 endpoint 地址",
+        ),
+    }
+}
+
+fn check_output_dir(output_root: &Path) -> CheckResult {
+    let name = "输出目录";
+    let probe = output_root.join(".doctor_write_test");
+    let result = fs::create_dir_all(output_root)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckResult::ok(name, format!("{:?} 可写", output_root)),
+        Err(e) => CheckResult::fail(
+            name,
+            format!("无法写入 {:?}: {}", output_root, e),
+            "检查目录权限，或用 --output 指定一个可写的目录",
+        ),
+    }
+}
+
+fn check_disk_space(output_root: &Path) -> CheckResult {
+    let name = "磁盘空间";
+    // 输出目录可能还不存在，使用最近的已存在的上级目录
+    let existing = output_root
+        .ancestors()
+        .find(|dir| dir.exists())
+        .unwrap_or(Path::new("."));
+    match fs4::available_space(existing) {
+        Ok(available) if available < LOW_DISK_SPACE => CheckResult::warn(
+            name,
+            format!(
+                "{:?} 所在磁盘仅剩 {} MiB",
+                output_root,
+                available / 1024 / 1024
+            ),
+            "清理磁盘或用 --output 指定其它磁盘；批量上传会在输出目录复制所有图片",
+        ),
+        Ok(available) => CheckResult::ok(
+            name,
+            format!(
+                "可用 {:.1} GiB",
+                available as f64 / 1024.0 / 1024.0 / 1024.0
+            ),
+        ),
+        Err(e) => CheckResult::warn(name, format!("无法获取可用空间: {}", e), "手动确认磁盘空间"),
+    }
+}
+
+// ✅ 依次检查运行环境，打印结果和修复建议
+pub fn run_doctor(config: &Config, api: Option<&str>, output_root: &Path) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    // 使用 HTTP API 时不需要本地命令行
+    if api.is_none() {
+        results.push(check_binary(&CliBackend::default().binary));
+    }
+    results.push(check_daemon(api, &config.api_endpoints));
+    for service in &config.pinning_services {
+        results.push(check_pinning_service(service));
+    }
+    results.push(check_output_dir(output_root));
+    results.push(check_disk_space(output_root));
+
+    println!("\n🩺 环境检查结果:");
+    for result in &results {
+        let icon = match result.status {
+            CheckStatus::Ok => "✅",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        };
+        println!("{} {}: {}", icon, result.name, result.detail);
+        if let Some(fix) = &result.fix {
+            println!("   👉 {}", fix);
+        }
+    }
+    results
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+
+use crate::exit_code::ExitCode;
+use crate::hashing::{HashAlgorithm, hash_file};
+
+// 内容完全相同的一组 token 图片
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    // (token ID, 图片的相对路径)
+    pub tokens: Vec<(u64, String)>,
+}
+
+// 最多列出的重复组
+const MAX_LISTED_GROUPS: usize = 50;
+
+// ✅ 计算每个 token 图片的哈希 (顺序与 images 相同)，并找出内容相同的图片
+// images 为 (token ID, 相对路径, 文件)
+pub fn find_duplicates(
+    images: &[(u64, String, PathBuf)],
+    algorithm: HashAlgorithm,
+) -> Result<(Vec<String>, Vec<DuplicateGroup>)> {
+    let mut hashes = Vec::with_capacity(images.len());
+    let mut by_hash: BTreeMap<String, Vec<(u64, String)>> = BTreeMap::new();
+    for (token_id, relative, path) in images {
+        let hash = hash_file(path, algorithm)
+            .map_err(|e| anyhow!("❌ 计算 {:?} 的哈希失败: {}", path, e))?;
+        by_hash
+            .entry(hash.clone())
+            .or_default()
+            .push((*token_id, relative.clone()));
+        hashes.push(hash);
+    }
+    let groups = by_hash
+        .into_iter()
+        .filter(|(_, tokens)| tokens.len() > 1)
+        .map(|(hash, tokens)| DuplicateGroup { hash, tokens })
+        .collect();
+    Ok((hashes, groups))
+}
+
+pub fn print_duplicates(groups: &[DuplicateGroup]) {
+    for group in groups.iter().take(MAX_LISTED_GROUPS) {
+        let tokens: Vec<String> = group
+            .tokens
+            .iter()
+            .map(|(token_id, image)| format!("#{} ({})", token_id, image))
+            .collect();
+        // 只显示哈希值的前 16 位
+        let (algorithm, hex) = group.hash.split_once(':').unwrap_or(("", &group.hash));
+        let short: String = hex.chars().take(16).collect();
+        println!("   {}:{}…  {}", algorithm, short, tokens.join(", "));
+    }
+    if groups.len() > MAX_LISTED_GROUPS {
+        println!("   ... 另有 {} 组", groups.len() - MAX_LISTED_GROUPS);
+    }
+}
+
+// ✅ 不同 token 的图片内容完全相同时给出警告；strict 时返回错误
+pub fn check_duplicate_content(groups: &[DuplicateGroup], strict: bool) -> Result<()> {
+    if groups.is_empty() {
+        return Ok(());
+    }
+    let count: usize = groups.iter().map(|group| group.tokens.len()).sum();
+    println!(
+        "\n⚠️  {} 个 token 的图片内容完全相同 (共 {} 组)，通常是生成流程出了问题:",
+        count,
+        groups.len()
+    );
+    print_duplicates(groups);
+    if strict {
+        return Err(ExitCode::Validation.wrap(anyhow!(
+            "❌ 发现 {} 组内容相同的图片，按 --strict 在上传前终止",
+            groups.len()
+        )));
+    }
+    println!("💡 确认无误可以忽略；加上 --strict 时会在上传前终止");
+    Ok(())
+}
@@ -0,0 +1,303 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::contract::parse_hex;
+use crate::hashing::to_hex;
+use crate::ipld::DagCodec;
+use crate::layout::relative_path;
+use crate::manifest::EncryptionInfo;
+use crate::unixfs::AddConfig;
+
+pub const ALGORITHM: &str = "aes-256-gcm";
+
+// 加密文件的格式：8 字节标记 + 12 字节随机 nonce + 密文 (末尾 16 字节为认证标签)
+const MAGIC: &[u8; 8] = b"PIUENC01";
+const NONCE_LEN: usize = 12;
+
+// ✅ config.toml 中的 [encryption] 段：上传前用集合密钥加密所有图片和元数据
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    pub enabled: bool,
+    // 32 字节集合密钥 (十六进制)，建议写成 "${COLLECTION_KEY}" 或 "${keyring:collection}"
+    pub key: String,
+}
+
+// ✅ 集合密钥 (AES-256-GCM)
+#[derive(Clone)]
+pub struct CollectionKey([u8; 32]);
+
+impl CollectionKey {
+    pub fn parse(hex: &str) -> Result<Self> {
+        parse_hex(hex)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Self)
+            .ok_or_else(|| {
+                anyhow!("❌ 集合密钥应为 32 字节的十六进制 (可以用 `openssl rand -hex 32` 生成)")
+            })
+    }
+
+    // 密钥的指纹写入清单，解密前用来确认拿到的是同一个密钥
+    pub fn fingerprint(&self) -> String {
+        format!("sha256:{}", to_hex(&Sha256::digest(self.0)))
+    }
+
+    pub fn info(&self) -> EncryptionInfo {
+        EncryptionInfo {
+            algorithm: ALGORITHM.to_string(),
+            key_fingerprint: self.fingerprint(),
+        }
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, data)
+            .map_err(|_| anyhow!("❌ 加密失败"))?;
+        let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if !is_encrypted(data) {
+            return Err(anyhow!("❌ 不是加密文件"));
+        }
+        let (nonce, ciphertext) = data[MAGIC.len()..].split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("❌ 解密失败：密钥不对，或内容被改动过"))
+    }
+
+    // 清单中记录了密钥指纹时，先确认是同一个密钥
+    pub fn check(&self, info: Option<&EncryptionInfo>) -> Result<()> {
+        match info {
+            Some(info) if info.key_fingerprint != self.fingerprint() => Err(anyhow!(
+                "❌ 密钥指纹 {} 与清单中记录的 {} 不一致",
+                self.fingerprint(),
+                info.key_fingerprint
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() + NONCE_LEN && data.starts_with(MAGIC)
+}
+
+// ✅ 解密本地文件或文件夹，写入 out (文件夹保持相同的结构)；返回解密和跳过 (未加密) 的文件
+pub fn decrypt_path(
+    key: &CollectionKey,
+    input: &Path,
+    out: &Path,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let (mut decrypted, mut skipped) = (Vec::new(), Vec::new());
+    for entry in WalkDir::new(input).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let target = if input.is_dir() {
+            out.join(relative_path(entry.path(), input)?)
+        } else {
+            out.to_path_buf()
+        };
+        let data = fs::read(entry.path())?;
+        if !is_encrypted(&data) {
+            skipped.push(entry.path().to_path_buf());
+            continue;
+        }
+        let plain = key
+            .decrypt(&data)
+            .map_err(|e| anyhow!("{} ({:?})", e, entry.path()))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, plain)?;
+        decrypted.push(target);
+    }
+    Ok((decrypted, skipped))
+}
+
+// ✅ 从节点读取 IPFS 路径 (文件或文件夹) 并解密到 out，返回写入的文件
+pub fn fetch_decrypt(
+    backend: &dyn IpfsBackend,
+    key: &CollectionKey,
+    path: &str,
+    out: &Path,
+) -> Result<Vec<PathBuf>> {
+    // 文件的 ls 结果为空，或只有没有名称的数据块
+    let entries: Vec<DirEntry> = backend
+        .ls(path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| !entry.name.is_empty())
+        .collect();
+    if entries.is_empty() {
+        let plain = key
+            .decrypt(&backend.cat(path)?)
+            .map_err(|e| anyhow!("{} ({})", e, path))?;
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(out, plain)?;
+        return Ok(vec![out.to_path_buf()]);
+    }
+    fs::create_dir_all(out)?;
+    let mut written = Vec::new();
+    for entry in entries {
+        written.extend(fetch_decrypt(
+            backend,
+            key,
+            &format!("{}/{}", path, entry.name),
+            &out.join(&entry.name),
+        )?);
+    }
+    Ok(written)
+}
+
+// 加密副本所在的临时目录，用完后删除
+struct EncryptedCopy {
+    root: PathBuf,
+}
+
+impl EncryptedCopy {
+    fn create(key: &CollectionKey, source: &Path) -> Result<(Self, PathBuf)> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let root = env::temp_dir().join(format!(
+            "ipfs-encrypted-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let copy = Self { root };
+        let name = source
+            .file_name()
+            .ok_or_else(|| anyhow!("❌ 无效的路径: {:?}", source))?;
+        let target = copy.root.join(name);
+        for entry in WalkDir::new(source).sort_by_file_name() {
+            let entry = entry?;
+            let path = if source.is_dir() {
+                target.join(relative_path(entry.path(), source)?)
+            } else {
+                target.clone()
+            };
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&path)?;
+            } else if entry.file_type().is_file() {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&path, key.encrypt(&fs::read(entry.path())?)?)?;
+            }
+        }
+        Ok((copy, target))
+    }
+}
+
+impl Drop for EncryptedCopy {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+// ✅ 上传前用集合密钥加密每个文件 (文件夹保持相同的文件名和结构)，
+// IPFS 上只有密文；读取 (ls / cat) 不做处理，返回的仍是密文
+pub struct EncryptedBackend {
+    inner: Box<dyn IpfsBackend>,
+    key: CollectionKey,
+}
+
+impl EncryptedBackend {
+    pub fn new(inner: Box<dyn IpfsBackend>, key: CollectionKey) -> Self {
+        Self { inner, key }
+    }
+}
+
+impl IpfsBackend for EncryptedBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        self.inner.check_compatibility()
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        let (_copy, encrypted) = EncryptedCopy::create(&self.key, target_path)?;
+        self.inner.add_path(&encrypted)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        self.inner.add_bytes(&self.key.encrypt(data)?)
+    }
+
+    // IPLD 节点中的链接需要节点能解析，无法加密
+    fn dag_put(&self, _node: &Value, _codec: DagCodec) -> Result<String> {
+        Err(anyhow!("❌ 加密上传时不能以 IPLD 节点发布元数据 ([ipld])"))
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.inner.add_config()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        self.inner.is_pinned(cid)
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        self.inner.pin(cid)
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.inner.unpin(cid)
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        self.inner.ls(path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.cat(path)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        self.inner.provide(cid, recursive)
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        self.inner.find_providers(cid, limit)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.inner.served_by(cid)
+    }
+
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        Some(self.key.info())
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.inner.node_version()
+    }
+}
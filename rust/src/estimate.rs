@@ -0,0 +1,268 @@
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::metadata::build_batch_metadata;
+use crate::workflow::list_image_files;
+
+// Kubo 默认的分块大小和 balanced 布局每个节点的最大链接数
+const CHUNK_SIZE: u64 = 256 * 1024;
+const LINKS_PER_NODE: u64 = 174;
+
+// 服务商按十进制计算容量
+pub const GB: f64 = 1_000_000_000.0;
+
+// 估算元数据大小时代替图片文件夹 CID，长度与真实的 CIDv1 (base32) 相同
+const PLACEHOLDER_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+// ✅ config.toml 中的一个 [[pricing_tiers]] 条目：服务商套餐的限额和价格
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PricingTier {
+    pub provider: String,
+    pub name: String,
+    // 套餐月费 (美元)
+    pub monthly_usd: f64,
+    // 存储上限 (GB)，不填表示不限
+    pub storage_gb: Option<f64>,
+    // 文件数上限
+    pub max_files: Option<u64>,
+    // 单个文件大小上限 (MB)
+    pub max_file_size_mb: Option<f64>,
+    // 超出存储上限后每 GB 的价格，不填表示不能超出
+    pub overage_per_gb_usd: Option<f64>,
+    // 账户中已经使用的存储和文件数
+    pub used_gb: f64,
+    pub used_files: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileEstimate {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+// ✅ 一个批量集合上传前的估算结果
+#[derive(Debug, Clone, Default)]
+pub struct Estimate {
+    // 图片文件夹中的所有文件 (与上传内容一致)，按大小从大到小排列
+    pub files: Vec<FileEstimate>,
+    pub image_bytes: u64,
+    // 生成的元数据文件数和总大小
+    pub metadata_files: u64,
+    pub metadata_bytes: u64,
+    // 文件名不是数字、无法生成 token 的图片
+    pub invalid_names: Vec<String>,
+    // 预计的 IPFS 块数 (分块 + 中间节点 + 目录)
+    pub blocks: u64,
+}
+
+impl Estimate {
+    pub fn total_bytes(&self) -> u64 {
+        self.image_bytes + self.metadata_bytes
+    }
+
+    pub fn total_files(&self) -> u64 {
+        self.files.len() as u64 + self.metadata_files
+    }
+}
+
+fn file_blocks(bytes: u64) -> u64 {
+    let chunks = bytes.div_ceil(CHUNK_SIZE).max(1);
+    if chunks == 1 {
+        return 1;
+    }
+    // 每一层的节点数向上取整，直到只剩根节点
+    let mut blocks = chunks;
+    let mut level = chunks;
+    while level > 1 {
+        level = level.div_ceil(LINKS_PER_NODE);
+        blocks += level;
+    }
+    blocks
+}
+
+// ✅ 遍历图片文件夹，估算上传的数据量、文件数和块数 (不需要连接节点)
+pub fn estimate_collection(images_input_dir: &Path) -> Result<Estimate> {
+    let mut estimate = Estimate {
+        // 图片文件夹和元数据文件夹本身
+        blocks: 2,
+        ..Estimate::default()
+    };
+    for entry in WalkDir::new(images_input_dir).min_depth(1) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            estimate.blocks += 1;
+            continue;
+        }
+        let bytes = entry.metadata()?.len();
+        estimate.image_bytes += bytes;
+        estimate.blocks += file_blocks(bytes);
+        estimate.files.push(FileEstimate {
+            path: entry.path().to_path_buf(),
+            bytes,
+        });
+    }
+    estimate.files.sort_by_key(|file| Reverse(file.bytes));
+
+    for image_file in list_image_files(images_input_dir)? {
+        let Some(image_filename) = image_file.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let token_id = image_file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok());
+        let Some(token_id) = token_id else {
+            estimate.invalid_names.push(image_filename.to_string());
+            continue;
+        };
+        let metadata = build_batch_metadata(token_id, PLACEHOLDER_CID, image_filename);
+        let bytes = serde_json::to_string_pretty(&metadata)?.len() as u64;
+        estimate.metadata_files += 1;
+        estimate.metadata_bytes += bytes;
+        estimate.blocks += file_blocks(bytes);
+    }
+    Ok(estimate)
+}
+
+// 一个套餐在上传之后的用量和费用
+#[derive(Debug, Clone)]
+pub struct TierProjection<'a> {
+    pub tier: &'a PricingTier,
+    pub storage_gb: f64,
+    pub files: u64,
+    pub monthly_usd: f64,
+    // 超出限额的说明，为空表示这次上传在套餐范围内
+    pub warnings: Vec<String>,
+}
+
+pub fn project_tier<'a>(estimate: &Estimate, tier: &'a PricingTier) -> TierProjection<'a> {
+    let storage_gb = tier.used_gb + estimate.total_bytes() as f64 / GB;
+    let files = tier.used_files + estimate.total_files();
+    let mut monthly_usd = tier.monthly_usd;
+    let mut warnings = Vec::new();
+
+    if let Some(limit) = tier.storage_gb
+        && storage_gb > limit
+    {
+        match tier.overage_per_gb_usd {
+            Some(rate) => {
+                let overage = storage_gb - limit;
+                monthly_usd += overage * rate;
+                warnings.push(format!(
+                    "存储超出套餐 {:.2} GB，按 ${}/GB 计费",
+                    overage, rate
+                ));
+            }
+            None => warnings.push(format!(
+                "存储 {:.2} GB 超出套餐上限 {} GB",
+                storage_gb, limit
+            )),
+        }
+    }
+    if let Some(limit) = tier.max_files
+        && files > limit
+    {
+        warnings.push(format!("文件数 {} 超出套餐上限 {}", files, limit));
+    }
+    if let Some(limit_mb) = tier.max_file_size_mb {
+        let limit = (limit_mb * 1_000_000.0) as u64;
+        let oversized = estimate.files.iter().filter(|f| f.bytes > limit).count();
+        if oversized > 0 {
+            warnings.push(format!(
+                "{} 个文件超过单文件上限 {} MB",
+                oversized, limit_mb
+            ));
+        }
+    }
+    TierProjection {
+        tier,
+        storage_gb,
+        files,
+        monthly_usd,
+        warnings,
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
+pub fn print_estimate(estimate: &Estimate, projections: &[TierProjection], top: usize) {
+    println!("\n📦 上传估算:");
+    println!(
+        "   - 图片: {} 个文件，{}",
+        estimate.files.len(),
+        format_bytes(estimate.image_bytes)
+    );
+    println!(
+        "   - 元数据: {} 个文件，约 {}",
+        estimate.metadata_files,
+        format_bytes(estimate.metadata_bytes)
+    );
+    println!(
+        "   - 合计: {} 个文件，{}，约 {} 个 IPFS 块",
+        estimate.total_files(),
+        format_bytes(estimate.total_bytes()),
+        estimate.blocks
+    );
+    if !estimate.invalid_names.is_empty() {
+        println!(
+            "⚠️  {} 个图片的文件名不是数字，批量上传会失败: {}",
+            estimate.invalid_names.len(),
+            estimate.invalid_names.join(", ")
+        );
+    }
+
+    if top > 0 && !estimate.files.is_empty() {
+        println!("\n📄 最大的 {} 个文件:", top.min(estimate.files.len()));
+        for file in estimate.files.iter().take(top) {
+            println!(
+                "   {:>12}  {}",
+                format_bytes(file.bytes),
+                file.path.display()
+            );
+        }
+    }
+
+    if projections.is_empty() {
+        println!("\n💡 在配置文件中添加 [[pricing_tiers]] 可以估算费用和检查套餐限额");
+        return;
+    }
+    println!("\n💰 套餐估算:");
+    for projection in projections {
+        let tier = projection.tier;
+        let icon = if projection.warnings.is_empty() {
+            "✅"
+        } else {
+            "⚠️ "
+        };
+        println!(
+            "{} {} {}: 上传后 {:.2} GB / {} 个文件，约 ${:.2}/月",
+            icon,
+            tier.provider,
+            tier.name,
+            projection.storage_gb,
+            projection.files,
+            projection.monthly_usd
+        );
+        for warning in &projection.warnings {
+            println!("   - {}", warning);
+        }
+    }
+}
@@ -0,0 +1,478 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::contract::{
+    AbiValue, ContractConfig, encode_call, keccak256, parse_address, parse_hex, to_hex_prefixed,
+};
+
+// 等待交易上链的最长时间
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(180);
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+// ✅ 以太坊 JSON-RPC 客户端，只实现发送合约交易需要的几个方法
+pub struct EthClient {
+    url: String,
+    client: Client,
+}
+
+fn parse_quantity(value: &Value) -> Result<u128> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| anyhow!("❌ RPC 返回的数值格式错误: {}", value))?;
+    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+        .map_err(|_| anyhow!("❌ RPC 返回的数值格式错误: {}", s))
+}
+
+impl EthClient {
+    pub fn new(url: &str) -> Result<Self> {
+        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self {
+            url: url.to_string(),
+            client,
+        })
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .map_err(|e| anyhow!("❌ 无法连接 RPC {}: {}", self.url, e))?;
+        let status = response.status();
+        let text = response.text()?;
+        let parsed: RpcResponse = serde_json::from_str(&text)
+            .map_err(|_| anyhow!("❌ RPC {} 请求失败 ({}): {}", method, status, text))?;
+        if let Some(error) = parsed.error {
+            return Err(anyhow!(
+                "❌ RPC {} 返回错误 ({}): {}",
+                method,
+                error.code,
+                error.message
+            ));
+        }
+        Ok(parsed.result.unwrap_or(Value::Null))
+    }
+
+    pub fn chain_id(&self) -> Result<u64> {
+        Ok(parse_quantity(&self.request("eth_chainId", json!([]))?)? as u64)
+    }
+
+    pub fn nonce(&self, address: &str) -> Result<u128> {
+        parse_quantity(&self.request("eth_getTransactionCount", json!([address, "pending"]))?)
+    }
+
+    // 只读调用，返回原始数据
+    pub fn call(&self, to: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let result = self.request(
+            "eth_call",
+            json!([{ "to": to, "data": to_hex_prefixed(data) }, "latest"]),
+        )?;
+        parse_hex(result.as_str().unwrap_or_default())
+    }
+
+    pub fn estimate_gas(&self, from: &str, to: &str, data: &[u8]) -> Result<u128> {
+        let call = json!({ "from": from, "to": to, "data": to_hex_prefixed(data) });
+        parse_quantity(&self.request("eth_estimateGas", json!([call]))?)
+    }
+
+    pub fn gas_price(&self) -> Result<u128> {
+        parse_quantity(&self.request("eth_gasPrice", json!([]))?)
+    }
+
+    // 最新区块的 baseFeePerGas，不支持 EIP-1559 的链返回 None
+    pub fn base_fee(&self) -> Result<Option<u128>> {
+        let block = self.request("eth_getBlockByNumber", json!(["latest", false]))?;
+        block
+            .get("baseFeePerGas")
+            .filter(|fee| !fee.is_null())
+            .map(parse_quantity)
+            .transpose()
+    }
+
+    pub fn max_priority_fee(&self) -> Result<u128> {
+        parse_quantity(&self.request("eth_maxPriorityFeePerGas", json!([]))?)
+    }
+
+    pub fn send_raw_transaction(&self, raw: &[u8]) -> Result<String> {
+        let result = self.request("eth_sendRawTransaction", json!([to_hex_prefixed(raw)]))?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("❌ RPC 没有返回交易哈希"))
+    }
+
+    // ✅ 等待交易回执，超时返回 None
+    pub fn wait_for_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>> {
+        let started = Instant::now();
+        while started.elapsed() < RECEIPT_TIMEOUT {
+            let receipt = self.request("eth_getTransactionReceipt", json!([tx_hash]))?;
+            if !receipt.is_null() {
+                let field = |name: &str| receipt.get(name).map(parse_quantity).transpose();
+                return Ok(Some(TxReceipt {
+                    success: field("status")? != Some(0),
+                    block_number: field("blockNumber")?.unwrap_or_default() as u64,
+                    gas_used: field("gasUsed")?.unwrap_or_default(),
+                }));
+            }
+            thread::sleep(RECEIPT_POLL_INTERVAL);
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TxReceipt {
+    pub success: bool,
+    pub block_number: u64,
+    pub gas_used: u128,
+}
+
+// RLP 编码的一项
+enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    Uint(u128),
+    List(Vec<Rlp<'a>>),
+}
+
+fn rlp_length_prefix(out: &mut Vec<u8>, len: usize, offset: u8) {
+    if len < 56 {
+        out.push(offset + len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        out.push(offset + 55 + (bytes.len() - start) as u8);
+        out.extend_from_slice(&bytes[start..]);
+    }
+}
+
+fn rlp_encode(item: &Rlp, out: &mut Vec<u8>) {
+    match item {
+        Rlp::Bytes(bytes) => {
+            if bytes.len() == 1 && bytes[0] < 0x80 {
+                out.push(bytes[0]);
+            } else {
+                rlp_length_prefix(out, bytes.len(), 0x80);
+                out.extend_from_slice(bytes);
+            }
+        }
+        // 整数去掉前导 0，0 编码为空字符串
+        Rlp::Uint(value) => {
+            let bytes = value.to_be_bytes();
+            let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+            rlp_encode(&Rlp::Bytes(&bytes[start..]), out);
+        }
+        Rlp::List(items) => {
+            let mut payload = Vec::new();
+            for item in items {
+                rlp_encode(item, &mut payload);
+            }
+            rlp_length_prefix(out, payload.len(), 0xc0);
+            out.extend_from_slice(&payload);
+        }
+    }
+}
+
+// 交易费用：EIP-1559 或旧式 gasPrice
+#[derive(Debug, Clone, Copy)]
+pub enum GasFee {
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+    Legacy {
+        gas_price: u128,
+    },
+}
+
+// ✅ 待签名的交易
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub chain_id: u64,
+    pub nonce: u128,
+    pub fee: GasFee,
+    pub gas_limit: u128,
+    pub to: [u8; 20],
+    pub value: u128,
+    pub data: Vec<u8>,
+}
+
+impl Transaction {
+    // 签名前的字段；EIP-1559 交易带 chainId 和空的 access list
+    fn fields(&self) -> Vec<Rlp<'_>> {
+        let mut fields = Vec::new();
+        if let GasFee::Eip1559 { .. } = self.fee {
+            fields.push(Rlp::Uint(self.chain_id as u128));
+        }
+        fields.push(Rlp::Uint(self.nonce));
+        match self.fee {
+            GasFee::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => fields.extend([
+                Rlp::Uint(max_priority_fee_per_gas),
+                Rlp::Uint(max_fee_per_gas),
+            ]),
+            GasFee::Legacy { gas_price } => fields.push(Rlp::Uint(gas_price)),
+        }
+        fields.extend([
+            Rlp::Uint(self.gas_limit),
+            Rlp::Bytes(&self.to),
+            Rlp::Uint(self.value),
+            Rlp::Bytes(&self.data),
+        ]);
+        if let GasFee::Eip1559 { .. } = self.fee {
+            fields.push(Rlp::List(Vec::new()));
+        }
+        fields
+    }
+
+    // ✅ 签名后的原始交易，可以直接 eth_sendRawTransaction
+    pub fn sign(&self, key: &SigningKey) -> Result<Vec<u8>> {
+        let eip1559 = matches!(self.fee, GasFee::Eip1559 { .. });
+        let prefix: &[u8] = if eip1559 { &[0x02] } else { &[] };
+
+        let mut unsigned = self.fields();
+        if !eip1559 {
+            // EIP-155：签名时附带 chainId, 0, 0
+            unsigned.extend([Rlp::Uint(self.chain_id as u128), Rlp::Uint(0), Rlp::Uint(0)]);
+        }
+        let mut payload = prefix.to_vec();
+        rlp_encode(&Rlp::List(unsigned), &mut payload);
+
+        let (signature, recovery) = key
+            .sign_prehash_recoverable(&keccak256(&payload))
+            .map_err(|e| anyhow!("❌ 交易签名失败: {}", e))?;
+        let (r, s) = (signature.r().to_bytes(), signature.s().to_bytes());
+        let v = recovery.to_byte() as u128;
+        let v = if eip1559 {
+            v
+        } else {
+            self.chain_id as u128 * 2 + 35 + v
+        };
+
+        let mut signed = self.fields();
+        signed.extend([
+            Rlp::Uint(v),
+            Rlp::Bytes(trim_zeros(&r)),
+            Rlp::Bytes(trim_zeros(&s)),
+        ]);
+        let mut raw = prefix.to_vec();
+        rlp_encode(&Rlp::List(signed), &mut raw);
+        Ok(raw)
+    }
+}
+
+// r、s 作为整数编码，去掉前导 0
+fn trim_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+pub fn signing_key(private_key: &str) -> Result<SigningKey> {
+    let bytes = parse_hex(private_key).map_err(|_| anyhow!("❌ 私钥格式错误"))?;
+    SigningKey::from_slice(&bytes).map_err(|_| anyhow!("❌ 私钥格式错误"))
+}
+
+// 私钥对应的地址
+pub fn signer_address(key: &SigningKey) -> String {
+    address_of(key.verifying_key())
+}
+
+// 公钥对应的地址：未压缩公钥 keccak256 的后 20 字节
+pub fn address_of(key: &VerifyingKey) -> String {
+    let public_key = key.to_encoded_point(false);
+    to_hex_prefixed(&keccak256(&public_key.as_bytes()[1..])[12..])
+}
+
+// ✅ 上传前检查 [contract] 配置是否足够发送交易
+pub fn check_transaction_config(contract: &ContractConfig) -> Result<()> {
+    let mut missing = Vec::new();
+    if contract.address.is_empty() {
+        missing.push("address");
+    }
+    if contract.rpc_url.is_empty() {
+        missing.push("rpc_url");
+    }
+    if contract.private_key.is_empty() {
+        missing.push("private_key");
+    }
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "❌ 配置文件的 [contract] 段缺少: {}",
+            missing.join(", ")
+        ));
+    }
+    parse_address(&contract.address)?;
+    signing_key(&contract.private_key)?;
+    encode_call(
+        &contract.set_base_uri_function,
+        &[AbiValue::String(String::new())],
+    )?;
+    Ok(())
+}
+
+// 一次合约交易的结果
+#[derive(Debug, Clone)]
+pub struct SentTransaction {
+    pub from: String,
+    pub hash: String,
+    pub receipt: Option<TxReceipt>,
+}
+
+// ✅ 调用合约的 setBaseURI (函数签名可配置)，等待交易上链
+pub fn set_base_uri(contract: &ContractConfig, base_uri: &str) -> Result<SentTransaction> {
+    let key = signing_key(&contract.private_key)?;
+    let from = signer_address(&key);
+    let to = parse_address(&contract.address)?;
+    let data = encode_call(
+        &contract.set_base_uri_function,
+        &[AbiValue::String(base_uri.to_string())],
+    )?;
+
+    let client = EthClient::new(&contract.rpc_url)?;
+    let chain_id = client.chain_id()?;
+    if let Some(expected) = contract.chain_id.filter(|id| *id != chain_id) {
+        return Err(anyhow!(
+            "❌ RPC 的 chainId 是 {}，配置中是 {}，请检查 rpc_url",
+            chain_id,
+            expected
+        ));
+    }
+    // 预估失败通常说明交易会被回滚 (如账户不是合约 owner)
+    let gas = client
+        .estimate_gas(&from, &contract.address, &data)
+        .map_err(|e| anyhow!("❌ 交易预估失败，账户 {} 可能没有权限: {}", from, e))?;
+    let fee = match client.base_fee()? {
+        Some(base_fee) => {
+            let priority = client.max_priority_fee()?;
+            GasFee::Eip1559 {
+                max_fee_per_gas: base_fee * 2 + priority,
+                max_priority_fee_per_gas: priority,
+            }
+        }
+        None => GasFee::Legacy {
+            gas_price: client.gas_price()?,
+        },
+    };
+    let transaction = Transaction {
+        chain_id,
+        nonce: client.nonce(&from)?,
+        fee,
+        // 多留 20% 余量
+        gas_limit: gas * 6 / 5,
+        to,
+        value: 0,
+        data,
+    };
+    let raw = transaction.sign(&key)?;
+    let hash = client.send_raw_transaction(&raw)?;
+    println!("📨 已发送交易: {}", hash);
+    println!("⏳ 正在等待交易上链...");
+    let receipt = client.wait_for_receipt(&hash)?;
+    Ok(SentTransaction {
+        from,
+        hash,
+        receipt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> SigningKey {
+        signing_key("0x4646464646464646464646464646464646464646464646464646464646464646").unwrap()
+    }
+
+    // EIP-155 规范中的示例交易
+    #[test]
+    fn legacy_transaction_matches_eip155_example() {
+        let transaction = Transaction {
+            chain_id: 1,
+            nonce: 9,
+            fee: GasFee::Legacy {
+                gas_price: 20_000_000_000,
+            },
+            gas_limit: 21000,
+            to: [0x35; 20],
+            value: 1_000_000_000_000_000_000,
+            data: Vec::new(),
+        };
+        let mut unsigned = transaction.fields();
+        unsigned.extend([Rlp::Uint(1), Rlp::Uint(0), Rlp::Uint(0)]);
+        let mut payload = Vec::new();
+        rlp_encode(&Rlp::List(unsigned), &mut payload);
+        assert_eq!(
+            to_hex_prefixed(&keccak256(&payload)),
+            "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(
+            to_hex_prefixed(&transaction.sign(&test_key()).unwrap()),
+            "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+        assert_eq!(
+            signer_address(&test_key()),
+            "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+    }
+
+    // 期望值由 alloy-consensus 1.8.3 (TxEip1559 + encoded_2718) 用同一私钥签名得到
+    #[test]
+    fn eip1559_transaction_matches_alloy() {
+        let transaction = Transaction {
+            chain_id: 11155111,
+            nonce: 42,
+            fee: GasFee::Eip1559 {
+                max_fee_per_gas: 30_000_000_000,
+                max_priority_fee_per_gas: 1_500_000_000,
+            },
+            gas_limit: 60000,
+            to: parse_address("0x5fbdb2315678afecb367f032d93f642f64180aa3").unwrap(),
+            value: 0,
+            data: encode_call(
+                "setBaseURI(string)",
+                &[AbiValue::String("ipfs://bafy/".to_string())],
+            )
+            .unwrap(),
+        };
+        assert_eq!(
+            to_hex_prefixed(&transaction.sign(&test_key()).unwrap()),
+            "0x02f8d383aa36a72a8459682f008506fc23ac0082ea60945fbdb2315678afecb367f032d93f642f64180aa380b86455f804b30000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000c697066733a2f2f626166792f0000000000000000000000000000000000000000c001a03663ee9350b1c97e73fe50437298339d50569b34c2f965002a240d53dac6a193a045073f9930857e1f2888423ab72fa99d82c21fcd9f9ec283b5065fcaf48730ee"
+        );
+    }
+
+    #[test]
+    fn rlp_encodes_long_strings_and_zero() {
+        let mut out = Vec::new();
+        rlp_encode(&Rlp::Uint(0), &mut out);
+        assert_eq!(out, [0x80]);
+
+        let long = [0xab; 56];
+        let mut out = Vec::new();
+        rlp_encode(&Rlp::Bytes(&long), &mut out);
+        assert_eq!(&out[..2], [0xb8, 56]);
+        assert_eq!(&out[2..], long);
+    }
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cancel;
+use crate::timeout::{Operation, TimeoutError};
+
+// ✅ 进程退出码：其它语言的包装脚本按失败类型分支 (对照表见 README 的「退出码」)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    // 其它错误
+    Failure = 1,
+    // 无法连接 IPFS 节点 / 集群，或自动启动的 daemon 没有就绪
+    NodeUnreachable = 2,
+    // 配置或集合检查不通过，还没有上传任何内容
+    Validation = 3,
+    // 已经上传了部分内容后失败或被中断
+    PartialUpload = 4,
+    // 节点、Pinning 服务或存储服务拒绝认证 (HTTP 401 / 403)
+    ProviderAuth = 5,
+    // 请求超过 [timeouts] 中的时间
+    Timeout = 6,
+    // 内容与期望不一致 (cid --expect、verify-local、verify-signature)
+    Mismatch = 7,
+    // 命令行参数错误 (与 sysexits 的 EX_USAGE 相同，避免和 clap 默认的 2 混淆)
+    Usage = 64,
+    // 上传开始前被 Ctrl-C / SIGTERM 中断 (128 + SIGINT)
+    Cancelled = 130,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    // 给错误标上失败类型，显示的内容不变
+    pub fn wrap(self, error: impl Into<anyhow::Error>) -> anyhow::Error {
+        anyhow::Error::new(Failure {
+            code: self,
+            error: error.into(),
+        })
+    }
+
+    // HTTP 401 / 403 是认证失败，其它状态码不单独分类
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => Self::ProviderAuth,
+            _ => Self::Failure,
+        }
+    }
+
+    // 连接检查失败：认证被拒时仍是 ProviderAuth，其它原因都算连不上节点
+    pub fn connect_failure(error: &anyhow::Error) -> Self {
+        match Self::marked(error) {
+            Some(Self::ProviderAuth) => Self::ProviderAuth,
+            _ => Self::NodeUnreachable,
+        }
+    }
+
+    // ✅ 按错误链中最外层的标记决定退出码；上传阶段中已经上传了内容时 (认证失败除外) 统一为 PartialUpload
+    pub fn of(error: &anyhow::Error) -> Self {
+        let code = Self::marked(error).unwrap_or(Self::Failure);
+        match code {
+            Self::ProviderAuth | Self::Validation => code,
+            _ if cancel::has_uploads() => Self::PartialUpload,
+            Self::Failure if cancel::is_cancelled() => Self::Cancelled,
+            _ => code,
+        }
+    }
+
+    // 错误链中最外层的失败类型标记 (包括超时)
+    fn marked(error: &anyhow::Error) -> Option<Self> {
+        error.chain().find_map(|cause| {
+            if let Some(failure) = cause.downcast_ref::<Failure>() {
+                return Some(failure.code);
+            }
+            cause
+                .downcast_ref::<TimeoutError>()
+                .map(|timeout| match timeout.operation {
+                    Operation::Connect => Self::NodeUnreachable,
+                    _ => Self::Timeout,
+                })
+        })
+    }
+}
+
+// 带失败类型的错误：显示和错误链与原来的错误相同
+#[derive(Debug)]
+pub struct Failure {
+    pub code: ExitCode,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for Failure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    // 全局的上传标记在同一个测试中按顺序检查，避免并行测试互相影响
+    #[test]
+    fn partial_upload_only_during_upload_phase() {
+        let failure = || anyhow!("❌ 上传失败");
+        assert_eq!(ExitCode::of(&failure()), ExitCode::Failure);
+
+        cancel::UPLOADED.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(ExitCode::of(&failure()), ExitCode::PartialUpload);
+        assert_eq!(
+            ExitCode::of(&ExitCode::ProviderAuth.wrap(failure())),
+            ExitCode::ProviderAuth
+        );
+
+        // 上传阶段结束后 (如设置 Base URI、写报告) 的失败不是部分上传
+        cancel::finish_uploads();
+        assert_eq!(ExitCode::of(&failure()), ExitCode::Failure);
+        assert_eq!(
+            ExitCode::of(&ExitCode::Timeout.wrap(failure())),
+            ExitCode::Timeout
+        );
+    }
+}
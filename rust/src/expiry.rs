@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::IpfsBackend;
+use crate::catalog::Catalog;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+use crate::pinning::{PinningServiceConfig, manifest_pin_requests};
+use crate::remote_pin::PinningClient;
+
+// ✅ catalog.json 中一次运行的过期时间：测试集合、临时预览等到期后可以统一清理
+// 记录 CID 和 pin 过的服务，运行目录被删除后仍然可以取消固定
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExpiryRecord {
+    // 到期 (或需要复查) 的时间，RFC 3339
+    pub expires_at: String,
+    pub tagged_at: String,
+    pub token_uri: String,
+    // 清单中的根 CID 和单独上传的元数据 CID
+    pub cids: Vec<String>,
+    // pin-remote 用过的 Pinning 服务
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<String>,
+}
+
+impl ExpiryRecord {
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.expires_at)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().is_some_and(|at| at <= now)
+    }
+}
+
+// ✅ 过期时间：相对时长 (30d、12h、2w) 或日期 (2025-12-31，当天 00:00 UTC)、RFC 3339 时间
+pub fn parse_expiry(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(s) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    let (number, unit) = s
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((s, ""), |i| s.split_at(i));
+    let value: i64 = number
+        .parse()
+        .map_err(|_| anyhow!("❌ 无效的过期时间: {} (如 30d、12h、2025-12-31)", s))?;
+    let duration = match unit {
+        "h" => Duration::hours(value),
+        "d" => Duration::days(value),
+        "w" => Duration::weeks(value),
+        _ => {
+            return Err(anyhow!("❌ 无效的过期时间单位 `{}` (可选: h, d, w)", unit));
+        }
+    };
+    Ok(Utc::now() + duration)
+}
+
+// ✅ 在 catalog 中记录一次运行的过期时间，以运行目录为键 (重复标记时覆盖)
+pub fn tag_expiry(catalog: &mut Catalog, manifest: &Manifest, expires_at: DateTime<Utc>) {
+    let record = ExpiryRecord {
+        expires_at: expires_at.to_rfc3339(),
+        tagged_at: Utc::now().to_rfc3339(),
+        token_uri: manifest.token_uri.clone(),
+        cids: manifest_pin_requests(manifest, "", &BTreeMap::new())
+            .into_iter()
+            .map(|request| request.cid)
+            .collect(),
+        services: manifest.remote_pins.keys().cloned().collect(),
+    };
+    catalog
+        .expiry
+        .insert(manifest.output_dir.to_string_lossy().to_string(), record);
+}
+
+// 清理一次过期运行的结果
+#[derive(Debug, Clone, Default)]
+pub struct CleanupReport {
+    pub unpinned: usize,
+    pub remote_removed: usize,
+    pub deleted_dir: bool,
+    pub errors: Vec<String>,
+}
+
+// ✅ 清理过期的运行：在当前节点和记录的 Pinning 服务上取消固定，delete 时再删除本地运行目录
+// 全部成功后才从 catalog 中移除记录，失败的部分下次可以重试
+pub fn cleanup_expired(
+    dir: &str,
+    record: &ExpiryRecord,
+    backend: &dyn IpfsBackend,
+    services: &[PinningServiceConfig],
+    delete: bool,
+) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    // 标记之后才 pin-remote 的服务记录在清单中
+    let path = Path::new(dir);
+    let mut names = record.services.clone();
+    if let Ok(manifest) = Manifest::load(&path.join(MANIFEST_FILE_NAME)) {
+        names.extend(manifest.remote_pins.into_keys());
+    }
+    names.sort();
+    names.dedup();
+    for cid in &record.cids {
+        // 节点上已经没有固定的 CID 跳过
+        if matches!(backend.is_pinned(cid), Ok(false)) {
+            continue;
+        }
+        match backend.unpin(cid) {
+            Ok(()) => report.unpinned += 1,
+            Err(e) => {
+                report
+                    .errors
+                    .push(format!("{} 取消固定 {} 失败: {:#}", backend.name(), cid, e))
+            }
+        }
+    }
+    for name in &names {
+        let Some(service) = services.iter().find(|service| &service.name == name) else {
+            report
+                .errors
+                .push(format!("配置文件中已经没有 Pinning 服务 {}", name));
+            continue;
+        };
+        let client = match PinningClient::new(service) {
+            Ok(client) => client,
+            Err(e) => {
+                report.errors.push(format!("{:#}", e));
+                continue;
+            }
+        };
+        for cid in &record.cids {
+            match client.remove(cid) {
+                Ok(true) => report.remote_removed += 1,
+                Ok(false) => {}
+                Err(e) => report.errors.push(format!("{:#}", e)),
+            }
+        }
+    }
+    if delete && path.is_dir() {
+        match fs::remove_dir_all(path) {
+            Ok(()) => report.deleted_dir = true,
+            Err(e) => report.errors.push(format!("删除 {:?} 失败: {}", path, e)),
+        }
+    }
+    report
+}
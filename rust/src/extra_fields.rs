@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::metadata::{ExtraFields, STANDARD_FIELDS};
+
+// ✅ config.toml 中的 [extra_fields] 段：写入每个 token 元数据的自定义顶层字段
+//
+// [extra_fields]
+// csv = "extra_fields.csv"
+//
+// [extra_fields.fields]
+// compiler = "MetaCore Engine"
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ExtraFieldsConfig {
+    // 所有 token (包括单件) 共用的字段，值可以是任意 TOML 类型
+    pub fields: Map<String, Value>,
+    // 每个 token 单独的字段：表头第一列为 token ID，其余列为字段名
+    pub csv: Option<PathBuf>,
+}
+
+impl ExtraFieldsConfig {
+    pub fn load(&self) -> Result<ExtraFields> {
+        check_field_names(self.fields.keys(), "[extra_fields.fields]")?;
+        let tokens = match &self.csv {
+            Some(path) => load_token_fields(path)?,
+            None => BTreeMap::new(),
+        };
+        Ok(ExtraFields {
+            common: self.fields.clone(),
+            tokens,
+        })
+    }
+}
+
+fn check_field_names<'a>(names: impl IntoIterator<Item = &'a String>, source: &str) -> Result<()> {
+    for name in names {
+        if name.is_empty() || STANDARD_FIELDS.contains(&name.as_str()) {
+            return Err(anyhow!(
+                "❌ {} 中的字段名 \"{}\" 无效 (不能为空，也不能是 {})",
+                source,
+                name,
+                STANDARD_FIELDS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+// CSV 中的值：能原样转回的整数、小数和 true / false 按 JSON 类型写入，其它 (如 "007") 保持字符串
+fn cell_value(cell: &str) -> Value {
+    if let Ok(n) = cell.parse::<i64>()
+        && n.to_string() == cell
+    {
+        return Value::Number(n.into());
+    }
+    if let Ok(x) = cell.parse::<f64>()
+        && x.to_string() == cell
+        && let Some(n) = serde_json::Number::from_f64(x)
+    {
+        return Value::Number(n);
+    }
+    match cell {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(cell.to_string()),
+    }
+}
+
+// ✅ 读取每个 token 的自定义字段 CSV，可以有空行和 # 注释；空单元格表示该 token 没有这个字段
+pub fn load_token_fields(path: &Path) -> Result<BTreeMap<u64, Map<String, Value>>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("❌ 读取自定义字段文件 {:?} 失败: {}", path, e))?;
+    let mut rows = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let split = |line: &str| -> Vec<String> {
+        line.split(',')
+            .map(|cell| cell.trim().trim_matches('"').to_string())
+            .collect()
+    };
+    let Some((_, header)) = rows.next() else {
+        return Ok(BTreeMap::new());
+    };
+    let columns = split(header);
+    let names = &columns[1..];
+    check_field_names(names, &format!("{:?} 的表头", path))?;
+
+    let mut tokens = BTreeMap::new();
+    for (line_number, line) in rows {
+        let cells = split(line);
+        if cells.len() != columns.len() {
+            return Err(anyhow!(
+                "❌ 自定义字段文件第 {} 行有 {} 列，表头有 {} 列: {}",
+                line_number,
+                cells.len(),
+                columns.len(),
+                line
+            ));
+        }
+        let id: u64 = cells[0].parse().map_err(|_| {
+            anyhow!(
+                "❌ 自定义字段文件第 {} 行的 token ID 无效: {}",
+                line_number,
+                cells[0]
+            )
+        })?;
+        let fields: Map<String, Value> = names
+            .iter()
+            .zip(&cells[1..])
+            .filter(|(_, cell)| !cell.is_empty())
+            .map(|(name, cell)| (name.clone(), cell_value(cell)))
+            .collect();
+        if tokens.insert(id, fields).is_some() {
+            return Err(anyhow!("❌ 自定义字段文件中 token {} 出现了多次", id));
+        }
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,230 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use flate2::read::GzDecoder;
+use tempfile::TempDir;
+use zip::ZipArchive;
+
+use crate::estimate::format_bytes;
+
+// macOS 压缩时附带的资源文件夹，不是作品的一部分
+const MACOS_RESOURCE_DIR: &str = "__MACOSX";
+
+// ✅ 批量输入可以直接使用的压缩包格式，按扩展名识别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Tar => "tar",
+            Self::TarGz => "tar.gz",
+        }
+    }
+}
+
+// ✅ 解压后的输入：dir 交给批量流程，临时目录随 root 一起删除
+#[derive(Debug)]
+pub struct ExtractedInput {
+    root: TempDir,
+    pub dir: PathBuf,
+    pub files: usize,
+    pub bytes: u64,
+    // 跳过的链接和特殊文件
+    pub skipped: Vec<String>,
+}
+
+// 压缩包中的路径只能是相对路径且不能包含 ..，防止写到临时目录之外 (zip slip)
+fn safe_path(name: &str) -> Result<Option<PathBuf>> {
+    let path = Path::new(name);
+    let mut safe = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => safe.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(anyhow!("❌ 压缩包中有不安全的路径 {:?}，拒绝解压", name));
+            }
+        }
+    }
+    let skipped = safe.as_os_str().is_empty()
+        || safe
+            .components()
+            .next()
+            .is_some_and(|first| first.as_os_str() == MACOS_RESOURCE_DIR);
+    Ok((!skipped).then_some(safe))
+}
+
+fn write_entry(target: &Path, reader: &mut impl Read) -> Result<u64> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(target)?;
+    Ok(io::copy(reader, &mut file)?)
+}
+
+fn extract_zip(archive: &Path, out: &Path, input: &mut ExtractedInput) -> Result<()> {
+    let mut zip = ZipArchive::new(File::open(archive)?)
+        .map_err(|e| anyhow!("❌ 无法读取 zip {:?}: {}", archive, e))?;
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+        let name = entry.name().to_string();
+        let Some(relative) = safe_path(&name)? else {
+            continue;
+        };
+        if entry.is_dir() {
+            fs::create_dir_all(out.join(&relative))?;
+        } else if entry.is_symlink() {
+            input.skipped.push(name);
+        } else {
+            input.bytes += write_entry(&out.join(&relative), &mut entry)?;
+            input.files += 1;
+        }
+    }
+    Ok(())
+}
+
+fn extract_tar(reader: impl Read, out: &Path, input: &mut ExtractedInput) -> Result<()> {
+    let mut tar = tar::Archive::new(reader);
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let Some(relative) = safe_path(&name)? else {
+            continue;
+        };
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            fs::create_dir_all(out.join(&relative))?;
+        } else if kind.is_file() {
+            input.bytes += write_entry(&out.join(&relative), &mut entry)?;
+            input.files += 1;
+        } else if !kind.is_pax_global_extensions() && !kind.is_pax_local_extensions() {
+            input.skipped.push(name);
+        }
+    }
+    Ok(())
+}
+
+// 压缩包里通常只有一个顶层文件夹 (如 drop-1/)，这时直接使用这个文件夹
+fn content_dir(out: &Path) -> Result<PathBuf> {
+    let entries: Vec<PathBuf> = fs::read_dir(out)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    let visible: Vec<&PathBuf> = entries
+        .iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|s| s.to_str())
+                .is_some_and(|name| !name.starts_with('.'))
+        })
+        .collect();
+    match visible.as_slice() {
+        [only] if only.is_dir() => Ok(only.to_path_buf()),
+        _ => Ok(out.to_path_buf()),
+    }
+}
+
+// ✅ 把 zip / tar / tar.gz 解压到临时目录，返回作为批量输入的文件夹
+pub fn extract_archive(archive: &Path, format: ArchiveFormat) -> Result<ExtractedInput> {
+    // 名称随机且只有当前用户可以访问，不会和其它进程或事先放好的目录冲突
+    let root = tempfile::Builder::new()
+        .prefix("ipfs-input-")
+        .tempdir()
+        .map_err(|e| anyhow!("❌ 创建临时目录 {:?} 失败: {}", env::temp_dir(), e))?;
+    let mut input = ExtractedInput {
+        dir: root.path().to_path_buf(),
+        root,
+        files: 0,
+        bytes: 0,
+        skipped: Vec::new(),
+    };
+    println!(
+        "\n--- 正在解压 {:?} ({}) -> {:?} ---",
+        archive,
+        format.as_str(),
+        input.root.path()
+    );
+    let out = input.root.path().to_path_buf();
+    match format {
+        ArchiveFormat::Zip => extract_zip(archive, &out, &mut input)?,
+        ArchiveFormat::Tar => extract_tar(File::open(archive)?, &out, &mut input)?,
+        ArchiveFormat::TarGz => {
+            extract_tar(GzDecoder::new(File::open(archive)?), &out, &mut input)?
+        }
+    }
+    input.dir = content_dir(&out)?;
+    if !input.skipped.is_empty() {
+        eprintln!(
+            "⚠️  跳过了压缩包中的链接和特殊文件: {}",
+            input.skipped.join(", ")
+        );
+    }
+    println!(
+        "✅ 已解压 {} 个文件 ({})",
+        input.files,
+        format_bytes(input.bytes)
+    );
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 压缩包解压到随机命名的临时目录，ExtractedInput 释放后目录被删除
+    #[test]
+    fn extracts_into_a_private_temp_dir_removed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("drop-1.tar");
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let data = b"{\"name\":\"1\"}";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "drop-1/1.json", &data[..])
+            .unwrap();
+        builder.finish().unwrap();
+        drop(builder);
+
+        let first = extract_archive(&archive, ArchiveFormat::Tar).unwrap();
+        let second = extract_archive(&archive, ArchiveFormat::Tar).unwrap();
+        let root = first.root.path().to_path_buf();
+        assert_ne!(root, second.root.path());
+        assert!(
+            root.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("ipfs-input-")
+        );
+        assert_eq!(first.dir, root.join("drop-1"));
+        assert_eq!(fs::read(first.dir.join("1.json")).unwrap(), data);
+        assert_eq!((first.files, first.bytes), (1, data.len() as u64));
+
+        drop(first);
+        assert!(!root.exists());
+        assert!(second.dir.exists());
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::Manifest;
+
+pub const DEFAULT_STORACHA_ENDPOINT: &str = "https://api.web3.storage";
+pub const DEFAULT_LOTUS_ENDPOINT: &str = "http://127.0.0.1:1234/rpc/v0";
+
+// Filecoin 每个 epoch 30 秒，一天 2880 个
+pub const EPOCHS_PER_DAY: u64 = 2880;
+// 存储提供方接受的最短和最长交易期限
+const MIN_DURATION_DAYS: u64 = 180;
+const MAX_DURATION_DAYS: u64 = 540;
+
+// ✅ 提交存储交易的方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DealService {
+    // web3.storage / Storacha 的 HTTP 接口：上传 CAR，由服务聚合后发起交易
+    #[default]
+    Storacha,
+    // 自己的 Lotus 节点 (JSON-RPC)，直接向指定的存储提供方 (boost) 发起交易
+    Lotus,
+}
+
+impl DealService {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Storacha => "storacha",
+            Self::Lotus => "lotus",
+        }
+    }
+
+    pub fn default_endpoint(&self) -> &'static str {
+        match self {
+            Self::Storacha => DEFAULT_STORACHA_ENDPOINT,
+            Self::Lotus => DEFAULT_LOTUS_ENDPOINT,
+        }
+    }
+}
+
+impl fmt::Display for DealService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DealService {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "storacha" | "web3.storage" => Ok(Self::Storacha),
+            "lotus" | "boost" => Ok(Self::Lotus),
+            _ => Err(anyhow!(
+                "❌ 未知的 Filecoin 交易方式: {} (可选 storacha、lotus)",
+                s
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [filecoin] 段：把运行目录打包成 CAR 后提交 Filecoin 存储交易，做长期归档
+//
+// [filecoin]
+// service = "storacha"
+// token = "${keyring:storacha}"
+//
+// [filecoin]
+// service = "lotus"
+// endpoint = "http://127.0.0.1:1234/rpc/v0"
+// token = "${LOTUS_API_TOKEN}"
+// wallet = "f1..."
+// miners = ["f01234", "f05678"]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct FilecoinConfig {
+    pub service: DealService,
+    // 留空时使用该方式的默认地址
+    pub endpoint: String,
+    // Storacha 的 API token，或 Lotus 的 JSON-RPC token (需要 write 权限)
+    pub token: String,
+    // 单件和批量流程完成后自动打包并提交
+    pub auto: bool,
+    // 以下只用于 lotus：付款钱包、存储提供方、期限 (天)、每 epoch 价格 (attoFIL)
+    pub wallet: String,
+    pub miners: Vec<String>,
+    pub duration_days: u64,
+    pub epoch_price: String,
+    // 使用 DataCap 的验证交易
+    pub verified: bool,
+    // 存储提供方额外保存一份未封装的副本，检索更快
+    pub fast_retrieval: bool,
+}
+
+impl Default for FilecoinConfig {
+    fn default() -> Self {
+        Self {
+            service: DealService::default(),
+            endpoint: String::new(),
+            token: String::new(),
+            auto: false,
+            wallet: String::new(),
+            miners: Vec::new(),
+            duration_days: MAX_DURATION_DAYS,
+            epoch_price: "0".to_string(),
+            verified: false,
+            fast_retrieval: true,
+        }
+    }
+}
+
+impl FilecoinConfig {
+    pub fn endpoint(&self) -> &str {
+        match self.endpoint.trim_end_matches('/') {
+            "" => self.service.default_endpoint(),
+            endpoint => endpoint,
+        }
+    }
+
+    // 交易期限换算成 epoch
+    pub fn duration_epochs(&self) -> u64 {
+        self.duration_days * EPOCHS_PER_DAY
+    }
+
+    // 检查 [filecoin] 中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        match self.service {
+            DealService::Storacha => {
+                if self.token.is_empty() {
+                    problems
+                        .push("storacha 需要 token (可以写成 \"${keyring:storacha}\")".to_string());
+                }
+            }
+            DealService::Lotus => {
+                if self.wallet.is_empty() {
+                    problems.push("lotus 需要付款钱包 wallet".to_string());
+                }
+                if self.miners.is_empty() {
+                    problems.push("lotus 需要至少一个存储提供方 miners".to_string());
+                }
+                if !(MIN_DURATION_DAYS..=MAX_DURATION_DAYS).contains(&self.duration_days) {
+                    problems.push(format!(
+                        "duration_days 应在 {} 到 {} 之间: {}",
+                        MIN_DURATION_DAYS, MAX_DURATION_DAYS, self.duration_days
+                    ));
+                }
+                if self.epoch_price.parse::<u128>().is_err() {
+                    problems.push(format!(
+                        "epoch_price 应是整数 (attoFIL): {:?}",
+                        self.epoch_price
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+// ✅ 一笔存储交易的状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DealState {
+    // 已提交，等待聚合、传输或存储提供方接受
+    Queued,
+    // 交易已上链，正在封装
+    Published,
+    // 数据已封装并被证明，交易生效
+    Active,
+    Expired,
+    Failed,
+}
+
+impl DealState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Published => "published",
+            Self::Active => "active",
+            Self::Expired => "expired",
+            Self::Failed => "failed",
+        }
+    }
+
+    // 不会再变化的状态，刷新时可以跳过
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Expired | Self::Failed)
+    }
+}
+
+impl fmt::Display for DealState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// 一个存储提供方上的一笔交易
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DealInfo {
+    // 存储提供方 (如 f01234)
+    pub storage_provider: String,
+    // 上链后才有的交易 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deal_id: Option<u64>,
+    // lotus 交易提案的 CID，用来查询状态
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal_cid: Option<String>,
+    pub state: DealState,
+    // 失败原因等附加信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// ✅ 一个根 CID 的归档记录：清单中按用途 (image、metadata) 记录，catalog.json 中按 CID 记录
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FilecoinArchive {
+    pub cid: String,
+    pub service: DealService,
+    // 运行目录中的 CAR 文件 (相对路径) 和大小
+    pub car_file: String,
+    pub car_bytes: u64,
+    // 存储提供方计算的 piece CID (CommP)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub piece_cid: Option<String>,
+    pub submitted_at: String,
+    pub updated_at: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deals: Vec<DealInfo>,
+}
+
+impl FilecoinArchive {
+    // 至少有一笔生效的交易
+    pub fn is_active(&self) -> bool {
+        self.deals
+            .iter()
+            .any(|deal| deal.state == DealState::Active)
+    }
+
+    // 还有可能变化的交易 (或服务还没有返回交易)
+    pub fn is_pending(&self) -> bool {
+        self.deals.is_empty() || self.deals.iter().any(|deal| !deal.state.is_final())
+    }
+}
+
+// ✅ 去掉根 CID 已经变化的归档记录 (如重新上传元数据后)，返回去掉的用途
+pub fn drop_stale_archives(manifest: &mut Manifest) -> Vec<String> {
+    let stale: Vec<String> = manifest
+        .filecoin
+        .iter()
+        .filter(|(role, archive)| {
+            let current = match role.as_str() {
+                "image" => &manifest.image_cid,
+                "metadata" => &manifest.metadata_cid,
+                _ => return false,
+            };
+            archive.cid != *current
+        })
+        .map(|(role, _)| role.clone())
+        .collect();
+    for role in &stale {
+        manifest.filecoin.remove(role);
+    }
+    stale
+}
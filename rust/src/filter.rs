@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+
+// 输入文件夹根目录中的忽略规则文件，格式与 .gitignore 相同
+pub const IGNORE_FILE: &str = ".ipfsignore";
+
+// 系统自动生成的文件，复制和批量处理时总是跳过 (.DS_Store 等隐藏文件本来就会跳过)
+pub const DEFAULT_EXCLUDES: [&str; 4] = ["Thumbs.db", "ehthumbs.db", "desktop.ini", "Desktop.ini"];
+
+// 一段路径的匹配：* 匹配任意个字符，? 匹配一个字符，都不跨越 /
+fn match_segment(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| match_segment(rest, &text[i..])),
+        Some(('?', rest)) => !text.is_empty() && match_segment(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && match_segment(rest, &text[1..]),
+    }
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        // ** 匹配任意层文件夹 (包括零层)
+        Some((&"**", rest)) => (0..=path.len()).any(|i| match_segments(rest, &path[i..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((first, path_rest)) => {
+                let pattern: Vec<char> = segment.chars().collect();
+                let text: Vec<char> = first.chars().collect();
+                match_segment(&pattern, &text) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+// ✅ glob 匹配，路径分隔符为 /，支持 *、? 和 **
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    match_segments(&pattern, &path)
+}
+
+// 一条规则，写法与 .gitignore 相同：
+// 不含 / 时匹配任意层级的文件名；含 / 时相对于根目录匹配；以 / 结尾只匹配文件夹；以 ! 开头表示重新包含
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let pattern = line.trim_start_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(Self {
+            pattern: pattern.to_string(),
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            glob_match(&self.pattern, relative)
+        } else {
+            let name = relative.rsplit('/').next().unwrap_or(relative);
+            glob_match(&self.pattern, name)
+        }
+    }
+}
+
+fn parse_rules(patterns: &[String]) -> Result<Vec<Rule>> {
+    patterns
+        .iter()
+        .map(|pattern| Rule::parse(pattern).ok_or_else(|| anyhow!("❌ 无效的 glob: {:?}", pattern)))
+        .collect()
+}
+
+// ✅ 决定文件夹中的哪些文件参与复制和上传：
+// 隐藏文件总是跳过；其余按 排除规则 (默认规则 → .ipfsignore → --exclude) 中最后一条匹配的规则决定；
+// 设置了包含规则时，文件还必须匹配其中一条
+#[derive(Debug, Clone, Default)]
+pub struct FileFilter {
+    include: Vec<Rule>,
+    rules: Vec<Rule>,
+}
+
+impl FileFilter {
+    // 只读取 dir 中的 .ipfsignore，上传时使用 (与 `ipfs add --ignore-rules-path` 一致)
+    pub fn from_ignore_file(dir: &Path) -> Result<Self> {
+        let path = dir.join(IGNORE_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let text =
+            fs::read_to_string(&path).map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?;
+        Ok(Self {
+            include: Vec::new(),
+            rules: text.lines().filter_map(Rule::parse).collect(),
+        })
+    }
+
+    // 批量处理时使用：默认规则、.ipfsignore，再加上命令行或配置中的包含/排除规则
+    pub fn load(dir: &Path, include: &[String], exclude: &[String]) -> Result<Self> {
+        let mut rules: Vec<Rule> = DEFAULT_EXCLUDES
+            .iter()
+            .filter_map(|pattern| Rule::parse(pattern))
+            .collect();
+        rules.extend(Self::from_ignore_file(dir)?.rules);
+        rules.extend(parse_rules(exclude)?);
+        Ok(Self {
+            include: parse_rules(include)?,
+            rules,
+        })
+    }
+
+    // relative 是相对于根目录的路径，分隔符为 /
+    pub fn allows(&self, relative: &str, is_dir: bool) -> bool {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        if name.starts_with('.') {
+            return false;
+        }
+        let excluded = self
+            .rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(relative, is_dir))
+            .is_some_and(|rule| !rule.negated);
+        if excluded {
+            return false;
+        }
+        is_dir
+            || self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|rule| rule.matches(relative, false))
+    }
+
+    // 检查 root 下的一个路径
+    pub fn allows_path(&self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        match path.strip_prefix(root) {
+            Ok(relative) if relative.as_os_str().is_empty() => true,
+            Ok(relative) => self.allows(&relative.to_string_lossy().replace('\\', "/"), is_dir),
+            Err(_) => true,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::monitor::DEFAULT_GATEWAY;
+use crate::patch::metadata_path;
+use crate::report::escape_html;
+
+// 运行目录中的预览页面目录 (与预览图的 previews/ 不同)
+pub const GALLERY_DIR: &str = "preview";
+const GALLERY_FILE: &str = "index.html";
+
+// ✅ config.toml 中的 [gallery] 段：`gallery` 生成的预览页面从哪里加载图片
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GalleryConfig {
+    // ipfs:// 链接通过这个网关显示
+    pub gateway: String,
+    // 直接引用运行目录中的本地图片，不需要网络 (上传前或加密的集合)
+    pub local: bool,
+}
+
+impl Default for GalleryConfig {
+    fn default() -> Self {
+        Self {
+            gateway: DEFAULT_GATEWAY.to_string(),
+            local: false,
+        }
+    }
+}
+
+// 页面中的一个 token
+#[derive(Debug, Clone, Default)]
+pub struct GalleryItem {
+    pub name: String,
+    pub image: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+// 属性值可能是字符串或数字
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// ERC-721 / Metaplex 的 trait_type，TZIP-21 的 name
+fn read_attributes(metadata: &Value) -> Vec<(String, String)> {
+    metadata
+        .get("attributes")
+        .and_then(Value::as_array)
+        .map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|attribute| {
+                    let key = attribute
+                        .get("trait_type")
+                        .or_else(|| attribute.get("name"))
+                        .map(value_text)
+                        .unwrap_or_default();
+                    attribute.get("value").map(|value| (key, value_text(value)))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 运行目录中 token 图片相对预览页面的路径
+fn local_image(manifest: &Manifest, token: &TokenEntry) -> String {
+    match manifest.kind {
+        CollectionKind::Single => format!("../{}", token.image_file),
+        _ => format!("../images/{}", token.image_file),
+    }
+}
+
+// ipfs:// 链接换成网关地址，其它地址 (https:// 等) 保持不变
+fn gateway_image(gateway: &str, uri: &str) -> String {
+    match uri.strip_prefix("ipfs://") {
+        Some(path) => format!(
+            "{}/ipfs/{}",
+            gateway.trim_end_matches('/'),
+            path.trim_start_matches("ipfs/")
+        ),
+        None => uri.to_string(),
+    }
+}
+
+// ✅ 从运行目录中的本地元数据读取每个 token 的名称、图片和属性；读不到元数据时使用清单中的记录
+pub fn gallery_items(manifest: &Manifest, dir: &Path, config: &GalleryConfig) -> Vec<GalleryItem> {
+    manifest
+        .tokens
+        .iter()
+        .map(|token| {
+            let metadata: Value = fs::read_to_string(metadata_path(dir, manifest.kind, token))
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or(Value::Null);
+            let image_uri = metadata
+                .get("image")
+                .and_then(Value::as_str)
+                .unwrap_or(&token.image_uri);
+            GalleryItem {
+                name: metadata
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or(&token.name)
+                    .to_string(),
+                image: if config.local {
+                    local_image(manifest, token)
+                } else {
+                    gateway_image(&config.gateway, image_uri)
+                },
+                attributes: read_attributes(&metadata),
+            }
+        })
+        .collect()
+}
+
+// ✅ 自包含的预览页面：按 token 排列的卡片，可以按名称或属性筛选
+pub fn render_gallery(manifest: &Manifest, items: &[GalleryItem]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"zh\">\n<head>\n<meta charset=\"utf-8\">\n<title>集合预览</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 1.5em; background: #f6f6f6; }\n\
+         #filter { width: 100%; max-width: 420px; padding: 6px; margin-bottom: 1em; }\n\
+         .grid { display: grid; grid-template-columns: repeat(auto-fill, minmax(200px, 1fr)); gap: 12px; }\n\
+         .card { background: #fff; border-radius: 6px; padding: 8px; box-shadow: 0 1px 3px rgba(0,0,0,.12); }\n\
+         .card img { width: 100%; aspect-ratio: 1; object-fit: contain; background: #eee; }\n\
+         .card h3 { font-size: 14px; margin: 6px 0; }\n\
+         .card dl { display: grid; grid-template-columns: auto 1fr; gap: 2px 8px; font-size: 12px; margin: 0; }\n\
+         .card dt { color: #666; }\n\
+         .card dd { margin: 0; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    let _ = writeln!(
+        out,
+        "<h1>集合预览</h1>\n<p>{} 个 token，Base URI <code>{}</code></p>",
+        items.len(),
+        escape_html(&manifest.token_uri)
+    );
+    out += "<input id=\"filter\" placeholder=\"按名称或属性筛选\">\n<div class=\"grid\">\n";
+    for item in items {
+        let mut search = item.name.to_lowercase();
+        for (key, value) in &item.attributes {
+            let _ = write!(search, " {} {}", key.to_lowercase(), value.to_lowercase());
+        }
+        let _ = writeln!(
+            out,
+            "<div class=\"card\" data-search=\"{}\">",
+            escape_html(&search)
+        );
+        let _ = writeln!(
+            out,
+            "<img loading=\"lazy\" src=\"{}\" alt=\"{}\">",
+            escape_html(&item.image),
+            escape_html(&item.name)
+        );
+        let _ = writeln!(out, "<h3>{}</h3>", escape_html(&item.name));
+        if !item.attributes.is_empty() {
+            out += "<dl>";
+            for (key, value) in &item.attributes {
+                let _ = write!(
+                    out,
+                    "<dt>{}</dt><dd>{}</dd>",
+                    escape_html(key),
+                    escape_html(value)
+                );
+            }
+            out += "</dl>\n";
+        }
+        out += "</div>\n";
+    }
+    out += "</div>\n<script>\n\
+            document.getElementById('filter').addEventListener('input', function (e) {\n\
+            \x20 var q = e.target.value.toLowerCase();\n\
+            \x20 document.querySelectorAll('.card').forEach(function (card) {\n\
+            \x20   card.style.display = card.dataset.search.indexOf(q) >= 0 ? '' : 'none';\n\
+            \x20 });\n\
+            });\n\
+            </script>\n</body>\n</html>\n";
+    out
+}
+
+// ✅ 在运行目录中写入 preview/index.html
+pub fn write_gallery(manifest_path: &Path, config: &GalleryConfig) -> Result<PathBuf> {
+    let manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    if manifest.tokens.is_empty() {
+        return Err(anyhow!("❌ 清单 {:?} 中没有 token", manifest_path));
+    }
+    let items = gallery_items(&manifest, dir, config);
+    let gallery_dir = dir.join(GALLERY_DIR);
+    fs::create_dir_all(&gallery_dir)?;
+    let path = gallery_dir.join(GALLERY_FILE);
+    fs::write(&path, render_gallery(&manifest, &items))?;
+    Ok(path)
+}
@@ -0,0 +1,305 @@
+use std::env;
+use std::fs::{self, File};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+
+use crate::backend::{HttpBackend, IpfsBackend};
+
+// 本机没有 ipfs 时下载的 Kubo 版本
+pub const KUBO_VERSION: &str = "v0.37.0";
+// 指定 Kubo 可执行文件的环境变量，优先于 PATH 中的 ipfs
+pub const KUBO_BINARY_ENV: &str = "KUBO_HARNESS_BINARY";
+pub const HARNESS_LOG_FILE_NAME: &str = "daemon.log";
+
+// 临时 Kubo 节点的可选项
+#[derive(Debug, Clone)]
+pub struct HarnessOptions {
+    // 为空时依次尝试 KUBO_HARNESS_BINARY、PATH 中的 ipfs，最后下载 version 指定的版本
+    pub binary: Option<PathBuf>,
+    pub version: String,
+    // 下载的 Kubo 缓存在这里，多次运行只下载一次
+    pub cache_dir: PathBuf,
+    // 以 --offline 启动：不连接其它节点，add / pin / cat 都可以正常使用
+    pub offline: bool,
+    pub startup_timeout: Duration,
+    // 关闭后保留 IPFS_PATH，便于排查问题
+    pub keep_repo: bool,
+}
+
+impl Default for HarnessOptions {
+    fn default() -> Self {
+        Self {
+            binary: None,
+            version: KUBO_VERSION.to_string(),
+            cache_dir: env::temp_dir().join("kubo-harness-cache"),
+            offline: true,
+            startup_timeout: Duration::from_secs(60),
+            keep_repo: false,
+        }
+    }
+}
+
+// ✅ 一次性的 Kubo 节点：独立的 IPFS_PATH 和随机端口，drop 时关闭并删除仓库
+// 用于端到端测试，以及拿真实节点的结果来校验自定义后端
+pub struct KuboHarness {
+    binary: PathBuf,
+    repo: PathBuf,
+    api: String,
+    gateway: String,
+    child: Option<Child>,
+    keep_repo: bool,
+}
+
+fn free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
+
+fn runs(binary: &Path) -> bool {
+    Command::new(binary)
+        .arg("version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+// dist.ipfs.tech 上的平台名
+fn platform() -> Result<(&'static str, &'static str)> {
+    let os = match env::consts::OS {
+        "linux" => "linux",
+        "macos" => "darwin",
+        "windows" => "windows",
+        "freebsd" => "freebsd",
+        other => return Err(anyhow!("❌ 没有适用于 {} 的 Kubo 发行版", other)),
+    };
+    let arch = match env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "386",
+        "arm" => "arm",
+        other => return Err(anyhow!("❌ 没有适用于 {} 架构的 Kubo 发行版", other)),
+    };
+    Ok((os, arch))
+}
+
+// ✅ 下载并解压 Kubo 到 cache_dir/<version>，已下载过则直接使用
+pub fn download_kubo(version: &str, cache_dir: &Path) -> Result<PathBuf> {
+    let (os, arch) = platform()?;
+    let dir = cache_dir.join(version);
+    let binary = dir
+        .join("kubo")
+        .join(if os == "windows" { "ipfs.exe" } else { "ipfs" });
+    if binary.is_file() {
+        return Ok(binary);
+    }
+
+    let ext = if os == "windows" { "zip" } else { "tar.gz" };
+    let url = format!(
+        "https://dist.ipfs.tech/kubo/{0}/kubo_{0}_{1}-{2}.{3}",
+        version, os, arch, ext
+    );
+    println!("⬇️ 正在下载 Kubo {}: {}", version, url);
+    let response = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(600))
+        .build()?
+        .get(&url)
+        .send()?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "❌ 下载 Kubo 失败 ({}): {}",
+            response.status(),
+            url
+        ));
+    }
+    fs::create_dir_all(&dir)?;
+    let archive = dir.join(format!("kubo.{}", ext));
+    fs::write(&archive, response.bytes()?)?;
+
+    // tar 在 Windows 10 之后也能解压 zip
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&dir)
+        .status()
+        .map_err(|e| anyhow!("❌ 无法运行 tar 解压 Kubo: {}", e))?;
+    fs::remove_file(&archive)?;
+    if !status.success() || !binary.is_file() {
+        return Err(anyhow!("❌ 解压 Kubo 失败: {:?}", archive));
+    }
+    println!("✅ Kubo 已下载到 {:?}", binary);
+    Ok(binary)
+}
+
+fn find_binary(options: &HarnessOptions) -> Result<PathBuf> {
+    if let Some(binary) = &options.binary {
+        return Ok(binary.clone());
+    }
+    if let Ok(binary) = env::var(KUBO_BINARY_ENV) {
+        return Ok(PathBuf::from(binary));
+    }
+    let on_path = PathBuf::from("ipfs");
+    if runs(&on_path) {
+        return Ok(on_path);
+    }
+    download_kubo(&options.version, &options.cache_dir)
+}
+
+impl KuboHarness {
+    // ✅ 初始化临时仓库、分配端口并启动 daemon，等到 API 可用后返回
+    pub fn start(options: HarnessOptions) -> Result<Self> {
+        let binary = find_binary(&options)?;
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let repo = env::temp_dir().join(format!("kubo-harness-{}-{}", std::process::id(), nanos));
+        fs::create_dir_all(&repo)?;
+        let mut harness = Self {
+            binary,
+            repo,
+            api: String::new(),
+            gateway: String::new(),
+            child: None,
+            keep_repo: options.keep_repo,
+        };
+
+        // test 配置：不连接 bootstrap 节点、不做 mDNS 发现
+        harness.ipfs(&["init", "--profile", "test"])?;
+        let (api_port, gateway_port, swarm_port) = (free_port()?, free_port()?, free_port()?);
+        harness.ipfs(&[
+            "config",
+            "Addresses.API",
+            &format!("/ip4/127.0.0.1/tcp/{}", api_port),
+        ])?;
+        harness.ipfs(&[
+            "config",
+            "Addresses.Gateway",
+            &format!("/ip4/127.0.0.1/tcp/{}", gateway_port),
+        ])?;
+        harness.ipfs(&[
+            "config",
+            "--json",
+            "Addresses.Swarm",
+            &format!("[\"/ip4/127.0.0.1/tcp/{}\"]", swarm_port),
+        ])?;
+        harness.api = format!("http://127.0.0.1:{}", api_port);
+        harness.gateway = format!("http://127.0.0.1:{}", gateway_port);
+
+        let log_path = harness.repo.join(HARNESS_LOG_FILE_NAME);
+        let log = File::create(&log_path)?;
+        let mut daemon = Command::new(&harness.binary);
+        daemon.arg("daemon").env("IPFS_PATH", &harness.repo);
+        if options.offline {
+            daemon.arg("--offline");
+        }
+        println!(
+            "🚀 正在启动临时 Kubo 节点 (IPFS_PATH: {:?}, API: {})...",
+            harness.repo, harness.api
+        );
+        harness.child = Some(
+            daemon
+                .stdin(Stdio::null())
+                .stdout(log.try_clone()?)
+                .stderr(log)
+                .spawn()
+                .map_err(|e| anyhow!("❌ 无法启动 {:?} daemon: {}", harness.binary, e))?,
+        );
+
+        let started = Instant::now();
+        while HttpBackend::new(&harness.api)
+            .and_then(|mut backend| backend.check_compatibility())
+            .is_err()
+        {
+            if let Some(child) = harness.child.as_mut()
+                && let Some(status) = child.try_wait()?
+            {
+                harness.child = None;
+                // 保留仓库，日志在里面
+                harness.keep_repo = true;
+                return Err(anyhow!(
+                    "❌ 临时 Kubo 节点启动后立即退出 ({})，详见 {:?}",
+                    status,
+                    log_path
+                ));
+            }
+            if started.elapsed() > options.startup_timeout {
+                harness.keep_repo = true;
+                return Err(anyhow!(
+                    "❌ 等待临时 Kubo 节点就绪超时 ({} 秒)，详见 {:?}",
+                    options.startup_timeout.as_secs(),
+                    log_path
+                ));
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+        println!(
+            "✅ 临时 Kubo 节点已就绪 (耗时 {:.1} 秒)",
+            started.elapsed().as_secs_f64()
+        );
+        Ok(harness)
+    }
+
+    // 以这个节点的 IPFS_PATH 运行一条 ipfs 命令，返回标准输出
+    pub fn ipfs(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new(&self.binary)
+            .args(args)
+            .env("IPFS_PATH", &self.repo)
+            .output()
+            .map_err(|e| anyhow!("❌ 无法运行 {:?}: {}", self.binary, e))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "❌ `ipfs {}` 失败: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    // RPC API 地址，可以直接传给 --api
+    pub fn api(&self) -> &str {
+        &self.api
+    }
+
+    pub fn gateway(&self) -> &str {
+        &self.gateway
+    }
+
+    pub fn repo(&self) -> &Path {
+        &self.repo
+    }
+
+    pub fn backend(&self) -> Result<HttpBackend> {
+        HttpBackend::new(&self.api)
+    }
+
+    // ✅ 关闭 daemon 并删除临时仓库；drop 时会自动调用
+    pub fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let graceful = self.ipfs(&["shutdown"]).is_ok();
+            let started = Instant::now();
+            while graceful && started.elapsed() < Duration::from_secs(10) {
+                if matches!(child.try_wait(), Ok(Some(_))) {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        if !self.keep_repo {
+            let _ = fs::remove_dir_all(&self.repo);
+        }
+    }
+}
+
+impl Drop for KuboHarness {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
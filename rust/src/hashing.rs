@@ -0,0 +1,87 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// ✅ 清单和去重缓存使用的内容哈希算法
+// BLAKE3 可以多线程并行计算，处理几十 GB 的集合时比 SHA-256 快很多
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Blake3,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blake3 => "blake3",
+            Self::Sha256 => "sha256",
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "blake3" => Ok(Self::Blake3),
+            "sha256" | "sha-256" => Ok(Self::Sha256),
+            other => Err(anyhow!(
+                "❌ 不支持的哈希算法: {} (可选: blake3, sha256)",
+                other
+            )),
+        }
+    }
+}
+
+// 哈希值带上算法前缀，例如 `blake3:af13...`，清单里混用两种算法时也能分辨
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> Result<String> {
+    let hex = match algorithm {
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            #[cfg(feature = "native")]
+            hasher.update_mmap_rayon(path)?;
+            #[cfg(not(feature = "native"))]
+            hasher.update_reader(File::open(path)?)?;
+            hasher.finalize().to_hex().to_string()
+        }
+        HashAlgorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            let mut file = File::open(path)?;
+            io::copy(&mut file, &mut hasher)?;
+            to_hex(&hasher.finalize())
+        }
+    };
+    Ok(format!("{}:{}", algorithm, hex))
+}
+
+pub fn hash_bytes(data: &[u8], algorithm: HashAlgorithm) -> String {
+    let hex = match algorithm {
+        HashAlgorithm::Blake3 => blake3::hash(data).to_hex().to_string(),
+        HashAlgorithm::Sha256 => to_hex(&Sha256::digest(data)),
+    };
+    format!("{}:{}", algorithm, hex)
+}
+
+// 读取已有哈希值的算法前缀
+pub fn algorithm_of(hash: &str) -> Option<HashAlgorithm> {
+    hash.split_once(':').and_then(|(algo, _)| algo.parse().ok())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::Manifest;
+
+// 触发钩子的时机
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    // 图片 (批量为图片文件夹) 上传完成，元数据尚未生成
+    ImagesUploaded,
+    // 元数据上传完成，清单已生成但输出目录尚未保存
+    MetadataUploaded,
+    // 工作流出错
+    Failed,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ImagesUploaded => "images_uploaded",
+            Self::MetadataUploaded => "metadata_uploaded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HookEvent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "images_uploaded" => Ok(Self::ImagesUploaded),
+            "metadata_uploaded" => Ok(Self::MetadataUploaded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(anyhow!(
+                "❌ 未知的钩子时机: {} (可选 images_uploaded、metadata_uploaded、failed)",
+                s
+            )),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+// ✅ config.toml 中的一个 [[hooks]] 条目：command 和 url 二选一
+//
+// [[hooks]]
+// on = "metadata_uploaded"
+// command = "./scripts/register.sh"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HookConfig {
+    pub on: HookEvent,
+    // 通过 sh -c (Windows 为 cmd /C) 运行，事件 JSON 写入标准输入
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    // 以 POST 请求体发送事件 JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    // 钩子失败时中止工作流 (默认只打印警告)；failed 时机的钩子失败不会改变原来的错误
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl HookConfig {
+    fn target(&self) -> &str {
+        self.command
+            .as_deref()
+            .or(self.url.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+// 检查 [[hooks]] 中的问题，为空表示可以使用
+pub fn problems(hooks: &[HookConfig]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (index, hook) in hooks.iter().enumerate() {
+        match (&hook.command, &hook.url) {
+            (Some(_), Some(_)) => problems.push(format!(
+                "第 {} 个钩子同时设置了 command 和 url，只能二选一",
+                index + 1
+            )),
+            (None, None) => problems.push(format!(
+                "第 {} 个钩子 ({}) 需要设置 command 或 url",
+                index + 1,
+                hook.on
+            )),
+            (Some(command), None) if command.trim().is_empty() => {
+                problems.push(format!("第 {} 个钩子的 command 为空", index + 1))
+            }
+            (None, Some(url)) if !url.starts_with("http://") && !url.starts_with("https://") => {
+                problems.push(format!(
+                    "第 {} 个钩子的 url 应以 http:// 或 https:// 开头: {}",
+                    index + 1,
+                    url
+                ))
+            }
+            _ => {}
+        }
+    }
+    problems
+}
+
+// 传给钩子的事件：命令从标准输入读取，webhook 为请求体
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HookPayload {
+    pub event: HookEvent,
+    // "single" / "batch"
+    pub workflow: String,
+    // 工作流完成前为临时目录，成功后会改名为清单中的 output_dir
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staging_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<Manifest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HookPayload {
+    pub fn images_uploaded(workflow: &str, staging_dir: PathBuf, image_cid: &str) -> Self {
+        Self {
+            event: HookEvent::ImagesUploaded,
+            workflow: workflow.to_string(),
+            staging_dir: Some(staging_dir),
+            image_cid: Some(image_cid.to_string()),
+            manifest: None,
+            error: None,
+        }
+    }
+
+    pub fn metadata_uploaded(workflow: &str, staging_dir: PathBuf, manifest: &Manifest) -> Self {
+        Self {
+            event: HookEvent::MetadataUploaded,
+            workflow: workflow.to_string(),
+            staging_dir: Some(staging_dir),
+            image_cid: Some(manifest.image_cid.clone()),
+            manifest: Some(manifest.clone()),
+            error: None,
+        }
+    }
+
+    pub fn failed(workflow: &str, error: &anyhow::Error) -> Self {
+        Self {
+            event: HookEvent::Failed,
+            workflow: workflow.to_string(),
+            staging_dir: None,
+            image_cid: None,
+            manifest: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+}
+
+fn run_command(command: &str, body: &[u8], timeout: Duration, event: HookEvent) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .env("NFT_HOOK_EVENT", event.as_str())
+        .stdin(Stdio::piped())
+        .spawn()?;
+    // 钩子可以不读标准输入，写入失败 (管道已关闭) 不算错误
+    if let Some(mut stdin) = child.stdin.take() {
+        let body = body.to_vec();
+        thread::spawn(move || {
+            let _ = stdin.write_all(&body);
+        });
+    }
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("超过 {} 秒未结束", timeout.as_secs()));
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    if !status.success() {
+        return Err(anyhow!("退出状态 {}", status));
+    }
+    Ok(())
+}
+
+fn post(url: &str, body: &[u8], timeout: Duration) -> Result<()> {
+    let response = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_vec())
+        .send()?;
+    if !response.status().is_success() {
+        return Err(anyhow!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+// ✅ 依次运行该时机的所有钩子；required 的钩子失败时返回错误
+pub fn run_hooks(hooks: &[HookConfig], payload: &HookPayload) -> Result<()> {
+    let matching: Vec<&HookConfig> = hooks.iter().filter(|h| h.on == payload.event).collect();
+    if matching.is_empty() {
+        return Ok(());
+    }
+    let body = serde_json::to_vec(payload)?;
+    for hook in matching {
+        let timeout = Duration::from_secs(hook.timeout_secs);
+        println!("🪝 运行钩子 ({}): {}", payload.event, hook.target());
+        let result = match (&hook.command, &hook.url) {
+            (Some(command), _) => run_command(command, &body, timeout, payload.event),
+            (None, Some(url)) => post(url, &body, timeout),
+            (None, None) => Err(anyhow!("没有设置 command 或 url")),
+        };
+        match result {
+            Ok(()) => {}
+            Err(e) if hook.required && payload.event != HookEvent::Failed => {
+                return Err(anyhow!("❌ 钩子 {} 失败: {:#}", hook.target(), e));
+            }
+            Err(e) => eprintln!("⚠️  钩子 {} 失败: {:#}", hook.target(), e),
+        }
+    }
+    Ok(())
+}
+
+// 工作流出错时运行 failed 钩子，并原样返回结果
+pub fn on_failure(
+    hooks: &[HookConfig],
+    workflow: &str,
+    result: Result<Manifest>,
+) -> Result<Manifest> {
+    if let Err(e) = &result {
+        let _ = run_hooks(hooks, &HookPayload::failed(workflow, e));
+    }
+    result
+}
@@ -0,0 +1,2649 @@
+// ✅ 英文消息表：源码中的中文格式字符串 -> 英文格式字符串
+//
+// 键与 println! / anyhow! 等使用的格式字符串完全相同 (包括 emoji 和缩进)。英文中的占位符按顺序
+// 对应中文中的占位符，需要调换顺序时写成 {1}、{0:?}；实际的参数值原样保留，不会被翻译。
+// 修改了中文消息而没有同步这里时，该消息在 --lang en 下保持中文；i18n 的测试会列出源码里已经找不到的键
+pub const MESSAGES: &[(&str, &str)] = &[
+    // arweave.rs
+    (
+        "{} 应以 http:// 或 https:// 开头: {:?}",
+        "{} should start with http:// or https://: {:?}",
+    ),
+    (
+        "需要 private_key (可以写成 \"${ARWEAVE_ETH_KEY}\")",
+        "private_key is required (can be written as \"${ARWEAVE_ETH_KEY}\")",
+    ),
+    ("需要付款币种 currency", "the payment currency is required"),
+    // backend/cli.rs
+    ("   - 名称: {}", "   - Name: {}"),
+    (
+        "\n--- 正在执行上传命令: {} add -r {} --cid-version 1 {}{} ---",
+        "\n--- Running upload command: {} add -r {} --cid-version 1 {}{} ---",
+    ),
+    (
+        "⚠️  节点不支持 `ipfs add -Q`，改用 `-q` 并读取最后一行作为根 CID",
+        "⚠️  The node does not support `ipfs add -Q`; using `-q` and reading the last line as the root CID",
+    ),
+    ("✅ 上传成功!", "✅ Upload succeeded!"),
+    ("❌ 上传失败: {}", "❌ Upload failed: {}"),
+    (
+        "❌ 写入 ipfs 标准输入的线程异常退出",
+        "❌ The thread writing to ipfs stdin exited abnormally",
+    ),
+    (
+        "❌ 发布 IPLD 节点失败: {}",
+        "❌ Failed to publish IPLD node: {}",
+    ),
+    ("❌ 执行 `{} {}` 失败: {}", "❌ Running `{} {}` failed: {}"),
+    (
+        "❌ 查询 pin 状态失败: {}",
+        "❌ Failed to query pin status: {}",
+    ),
+    ("❌ 节点没有返回 CID", "❌ The node returned no CID"),
+    ("❌ 读取 {} 失败: {}", "❌ Failed to read {}: {}"),
+    ("❌ 路径不存在: {:?}", "❌ Path does not exist: {:?}"),
+    (
+        "❌ 连接 IPFS 节点失败。请确保你的 IPFS 节点正在运行 (命令: ipfs daemon)，或加上 --autostart-daemon 自动启动。",
+        "❌ Failed to connect to the IPFS node. Make sure your IPFS node is running (command: ipfs daemon), or add --autostart-daemon to start it automatically.",
+    ),
+    ("无效的文件路径", "Invalid file path"),
+    // backend/cluster.rs
+    (
+        "\n--- ipfs-cluster pin 状态 ---",
+        "\n--- ipfs-cluster pin status ---",
+    ),
+    (
+        "\n--- 正在上传(ipfs-cluster): {:?} ---",
+        "\n--- Uploading (ipfs-cluster): {:?} ---",
+    ),
+    (
+        "⚠️  {} 目前只有 {} 个 peer 完成固定，低于 replication_min = {}，其余 peer 可能仍在同步",
+        "⚠️  {} is pinned on only {} peers so far, below replication_min = {}; other peers may still be syncing",
+    ),
+    ("✅ 上传成功! CID: {}", "✅ Upload succeeded! CID: {}"),
+    (
+        "✅ 已连接 ipfs-cluster peer {} (版本: {})",
+        "✅ Connected to ipfs-cluster peer {} (version: {})",
+    ),
+    (
+        "❌ cluster.basic_auth 格式应为 \"用户名:密码\"",
+        "❌ cluster.basic_auth should be \"username:password\"",
+    ),
+    (
+        "❌ ipfs-cluster API 地址需要以 http(s):// 开头: {}",
+        "❌ The ipfs-cluster API address must start with http(s)://: {}",
+    ),
+    (
+        "❌ ipfs-cluster 没有返回 CID: {}",
+        "❌ ipfs-cluster returned no CID: {}",
+    ),
+    (
+        "❌ ipfs-cluster 请求失败 ({}): {}",
+        "❌ ipfs-cluster request failed ({}): {}",
+    ),
+    (
+        "❌ 无法连接 ipfs-cluster {}: {:#}",
+        "❌ Cannot connect to ipfs-cluster {}: {:#}",
+    ),
+    (
+        "📌 {} {} ({}/{} 个 peer 已固定)",
+        "📌 {} {} ({}/{} peers pinned)",
+    ),
+    // backend/failover.rs
+    ("⚠️  节点 {} {} 失败: {:#}", "⚠️  Node {} {} failed: {:#}"),
+    (
+        "⚠️  节点 {} 不可用: {:#}",
+        "⚠️  Node {} is unavailable: {:#}",
+    ),
+    (
+        "⚠️  节点 {} 镜像 pin {} 失败: {:#}",
+        "⚠️  Node {} failed to mirror pin {}: {:#}",
+    ),
+    ("✅ 节点 {} 可用", "✅ Node {} is available"),
+    (
+        "❌ [failover] 中的节点都无法连接",
+        "❌ None of the nodes in [failover] can be reached",
+    ),
+    (
+        "❌ [failover] 中至少需要配置一个节点",
+        "❌ [failover] needs at least one node",
+    ),
+    (
+        "❌ 所有节点 {} 均失败:\n  {}",
+        "❌ All nodes {} failed:\n  {}",
+    ),
+    (
+        "❌ 没有可以取消 pin 的节点",
+        "❌ No node available to unpin",
+    ),
+    (
+        "❌ 没有可以宣告内容的节点",
+        "❌ No node available to provide content",
+    ),
+    ("❌ 没有可查询的节点", "❌ No node available to query"),
+    ("上传", "upload"),
+    ("发布 IPLD 节点", "publish IPLD node"),
+    ("查找提供者", "find providers"),
+    ("读取", "read"),
+    ("🔀 已切换到节点 {}", "🔀 Switched to node {}"),
+    ("🪞 已在节点 {} 上 pin {}", "🪞 Pinned {1} on node {0}"),
+    // backend/filebase.rs
+    (
+        "\n--- 正在上传(Filebase): {:?} ---",
+        "\n--- Uploading (Filebase): {:?} ---",
+    ),
+    (
+        "⚠️  Filebase 返回的 CID {} 与本地计算的 {} 不一致",
+        "⚠️  The CID {} returned by Filebase differs from the locally computed {}",
+    ),
+    (
+        "✅ 使用 Filebase bucket {} ({})",
+        "✅ Using Filebase bucket {} ({})",
+    ),
+    (
+        "❌ Filebase 没有返回 {} 的 CID，请确认 bucket {} 是 IPFS 类型",
+        "❌ Filebase returned no CID for {}; make sure bucket {} is an IPFS bucket",
+    ),
+    (
+        "❌ 缺少 Filebase 密钥，请设置 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY 或在 config.toml 的 [filebase] 中填写",
+        "❌ Missing Filebase keys; set FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY or fill in [filebase] in config.toml",
+    ),
+    // backend/http.rs
+    (
+        "\n--- 正在上传(HTTP API): {:?} ---",
+        "\n--- Uploading (HTTP API): {:?} ---",
+    ),
+    (
+        "📦 {} 个文件 ({})，同时上传 {} 个",
+        "📦 {} files ({}), uploading {} at a time",
+    ),
+    (
+        "📁 文件夹 {:?} 的条目较多，Kubo 会使用 HAMT 分片目录，整个文件夹作为一个请求上传",
+        "📁 Folder {:?} has enough entries for Kubo to use a HAMT-sharded directory; uploading the whole folder in one request",
+    ),
+    (
+        "❌ 上传文件的线程异常退出",
+        "❌ A file upload thread exited unexpectedly",
+    ),
+    ("❌ 文件没有上传: {}", "❌ File was not uploaded: {}"),
+    (
+        "❌ 没有生成根目录节点",
+        "❌ No root directory node was built",
+    ),
+    (
+        "❌ 节点没有返回 {} 的大小",
+        "❌ The node did not return the size of {}",
+    ),
+    (
+        "❌ 节点返回的目录 CID {} 与本地计算的 {} 不一致",
+        "❌ The node returned directory CID {}, which differs from the locally computed {}",
+    ),
+    (
+        "⚠️  节点 (版本: {}) 不提供 commands 接口，跳过兼容性检查: {:#}",
+        "⚠️  The node (version: {}) has no commands endpoint; skipping the compatibility check: {:#}",
+    ),
+    (
+        "❌ IPFS API 请求失败 ({}): {}",
+        "❌ IPFS API request failed ({}): {}",
+    ),
+    (
+        "❌ basic_auth 格式应为 \"用户名:密码\"",
+        "❌ basic_auth should be \"username:password\"",
+    ),
+    (
+        "❌ 无效的 IPFS API 地址 {:?}: {}\n   可以写成 http://127.0.0.1:5001、/ip4/127.0.0.1/tcp/5001、/dns/ipfs.example.com/tcp/443/https 或 unix:///var/run/kubo/api.sock",
+        "❌ Invalid IPFS API address {:?}: {}\n   Accepted forms: http://127.0.0.1:5001, /ip4/127.0.0.1/tcp/5001, /dns/ipfs.example.com/tcp/443/https or unix:///var/run/kubo/api.sock",
+    ),
+    ("地址为空", "the address is empty"),
+    ("缺少 socket 文件路径", "the socket file path is missing"),
+    (
+        "不支持 {}://，Kubo RPC 只能通过 http:// 或 https:// 访问",
+        "{}:// is not supported; Kubo RPC is only reachable over http:// or https://",
+    ),
+    (
+        "缺少端口 (Kubo RPC 默认端口是 5001，如 127.0.0.1:5001)，也不是 [[api_endpoints]] 中的名称",
+        "the port is missing (the default Kubo RPC port is 5001, e.g. 127.0.0.1:5001) and it is not a name in [[api_endpoints]]",
+    ),
+    ("缺少主机名", "the host name is missing"),
+    (
+        "不要把用户名和密码写在地址里，请在 [[api_endpoints]] 中配置 basic_auth",
+        "do not put the user name and password in the address; configure basic_auth in [[api_endpoints]]",
+    ),
+    (
+        "地址中不能带 ? 或 # 部分",
+        "the address must not contain ? or #",
+    ),
+    (
+        "不支持的后缀 /{} (可以是 /http、/https 或 /tls/http)",
+        "unsupported suffix /{} (use /http, /https or /tls/http)",
+    ),
+    ("{} 不是有效的 IPv4 地址", "{} is not a valid IPv4 address"),
+    ("{} 不是有效的 IPv6 地址", "{} is not a valid IPv6 address"),
+    (
+        "不支持的协议 /{} (可以是 /ip4、/ip6、/dns、/dns4、/dns6 或 /unix)",
+        "unsupported protocol /{} (use /ip4, /ip6, /dns, /dns4, /dns6 or /unix)",
+    ),
+    ("{} 不是有效的端口", "{} is not a valid port"),
+    (
+        "multiaddr 应为 /<ip4|ip6|dns>/<主机>/tcp/<端口>[/https] 或 /unix/<路径>",
+        "a multiaddr should look like /<ip4|ip6|dns>/<host>/tcp/<port>[/https] or /unix/<path>",
+    ),
+    (
+        "❌ 无效的请求头名称 {}: {}",
+        "❌ Invalid header name {}: {}",
+    ),
+    (
+        "❌ 无法解析 findprovs 的输出: {}",
+        "❌ Cannot parse findprovs output: {}",
+    ),
+    ("❌ 无法解析 ls 的输出: {}", "❌ Cannot parse ls output: {}"),
+    (
+        "❌ 无法连接 IPFS API {:?}: {:#}",
+        "❌ Cannot connect to IPFS API {:?}: {:#}",
+    ),
+    ("❌ 查找提供者失败: {}", "❌ Failed to find providers: {}"),
+    ("❌ 节点没有返回 CID: {}", "❌ The node returned no CID: {}"),
+    (
+        "❌ 请求头 {} 的值无效: {}",
+        "❌ Invalid value for header {}: {}",
+    ),
+    ("无效的文件夹名: {:?}", "Invalid folder name: {:?}"),
+    // backend/memory.rs
+    (
+        "\n--- 正在上传(内存): {:?} ---",
+        "\n--- Uploading (memory): {:?} ---",
+    ),
+    ("❌ 内存中没有块 {}", "❌ Block {} is not in memory"),
+    (
+        "❌ 内存后端只支持 dag-cbor 编码的 IPLD 节点",
+        "❌ The memory backend only supports dag-cbor IPLD nodes",
+    ),
+    // backend/mod.rs
+    (
+        "❌ {} 后端不支持 pin",
+        "❌ The {} backend does not support pinning",
+    ),
+    (
+        "❌ {} 后端不支持列出目录",
+        "❌ The {} backend does not support listing directories",
+    ),
+    (
+        "❌ {} 后端不支持发布 IPLD 节点",
+        "❌ The {} backend does not support publishing IPLD nodes",
+    ),
+    (
+        "❌ {} 后端不支持取消 pin",
+        "❌ The {} backend does not support unpinning",
+    ),
+    (
+        "❌ {} 后端不支持向 DHT 宣告内容",
+        "❌ The {} backend does not support providing content to the DHT",
+    ),
+    (
+        "❌ {} 后端不支持查找提供者",
+        "❌ The {} backend does not support finding providers",
+    ),
+    (
+        "❌ {} 后端不支持查询 pin 状态",
+        "❌ The {} backend does not support querying pin status",
+    ),
+    (
+        "❌ {} 后端不支持读取文件",
+        "❌ The {} backend does not support reading files",
+    ),
+    // backend/s3.rs
+    (
+        "❌ S3 的 bucket、access key 和 secret key 都不能为空",
+        "❌ The S3 bucket, access key and secret key must not be empty",
+    ),
+    // backup.rs
+    (
+        "\n--- 正在备份 {:?} -> {} ---",
+        "\n--- Backing up {:?} -> {} ---",
+    ),
+    ("prefix 应以 / 结尾: {:?}", "prefix should end with /: {:?}"),
+    ("☁️  {} ({} 字节)", "☁️  {} ({} bytes)"),
+    ("❌ 无效的运行目录: {:?}", "❌ Invalid run directory: {:?}"),
+    (
+        "❌ 未知的备份目标: {} (可选 s3、gcs)",
+        "❌ Unknown backup target: {} (options: s3, gcs)",
+    ),
+    (
+        "需要 access_key 和 secret_key (可以写成 \"${AWS_ACCESS_KEY_ID}\")",
+        "access_key and secret_key are required (can be written as \"${AWS_ACCESS_KEY_ID}\")",
+    ),
+    ("需要 bucket", "bucket is required"),
+    ("📝 将上传 {} ({} 字节)", "📝 Will upload {} ({} bytes)"),
+    // bundler.rs
+    (
+        "   - 付款地址: {} ({})，报价 {}，余额 {}",
+        "   - Payer: {} ({}), quote {}, balance {}",
+    ),
+    (
+        "\n--- 正在镜像到 Arweave ({}，{} 个文件，{} 字节) ---",
+        "\n--- Mirroring to Arweave ({}, {} files, {} bytes) ---",
+    ),
+    (
+        "❌ Irys 余额不足：需要 {}，当前 {} ({})，请先为 {} 充值",
+        "❌ Insufficient Irys balance: need {}, have {} ({}); fund {} first",
+    ),
+    (
+        "❌ Irys 请求失败 ({}): {}",
+        "❌ Irys request failed ({}): {}",
+    ),
+    (
+        "❌ Irys 返回无法解析: {}",
+        "❌ Cannot parse Irys response: {}",
+    ),
+    (
+        "❌ Irys 返回的 ID {} 与本地计算的 {} 不一致",
+        "❌ The ID {} returned by Irys differs from the locally computed {}",
+    ),
+    (
+        "❌ Irys 返回的价格无法解析: {}",
+        "❌ Cannot parse the price returned by Irys: {}",
+    ),
+    (
+        "❌ Irys 返回的余额无法解析: {}",
+        "❌ Cannot parse the balance returned by Irys: {}",
+    ),
+    (
+        "❌ {} ({}) 镜像失败: {:#}",
+        "❌ Mirroring {} ({}) failed: {:#}",
+    ),
+    (
+        "❌ 签名 data item 失败: {}",
+        "❌ Failed to sign data item: {}",
+    ),
+    (
+        "❌ 读取要镜像的文件失败: {}",
+        "❌ Failed to read the file to mirror: {}",
+    ),
+    // cancel.rs
+    (
+        "\n⚠️  收到中断信号：不再开始新的上传，正在进行的上传完成后退出 (再按一次 Ctrl-C 立即退出)",
+        "\n⚠️  Interrupt received: no new uploads will start; exiting after in-flight uploads finish (press Ctrl-C again to exit immediately)",
+    ),
+    (
+        "\n🛑 再次收到中断信号，立即退出",
+        "\n🛑 Interrupt received again, exiting now",
+    ),
+    (
+        "⚠️  写入中断记录失败: {:#}",
+        "⚠️  Failed to write the interruption record: {:#}",
+    ),
+    ("⚠️  取消固定 {} 失败: {:#}", "⚠️  Failed to unpin {}: {:#}"),
+    (
+        "⚠️  无法监听 SIGTERM: {}",
+        "⚠️  Cannot listen for SIGTERM: {}",
+    ),
+    (
+        "❌ 已取消：不再开始新的上传",
+        "❌ Cancelled: no new uploads will start",
+    ),
+    (
+        "📄 已完成的上传记录在: {:?}",
+        "📄 Completed uploads recorded in: {:?}",
+    ),
+    (
+        "🧹 已取消固定 {} 个部分上传的内容",
+        "🧹 Unpinned {} partially uploaded items",
+    ),
+    // checksums.rs
+    ("⚠️  未记录的文件: {}", "⚠️  Unlisted file: {}"),
+    ("❌ {:?} 中没有 {} 或 {}", "❌ {:?} has no {} or {}"),
+    (
+        "❌ {:?} 第 {} 行格式错误: {}",
+        "❌ {:?} line {} is malformed: {}",
+    ),
+    ("❌ 内容不一致: {}", "❌ Content mismatch: {}"),
+    ("❌ 写入 {:?} 失败: {}", "❌ Failed to write {:?}: {}"),
+    ("❌ 文件缺失: {}", "❌ Missing file: {}"),
+    ("❌ 读取 {:?} 失败: {}", "❌ Failed to read {:?}: {}"),
+    (
+        "🔎 已按 {} 检查 {} 个文件",
+        "🔎 Checked {1} files against {0}",
+    ),
+    // cid.rs
+    ("❌ CID 不能为空", "❌ CID must not be empty"),
+    (
+        "❌ CIDv0 只能使用 base58btc，{} 需要先转换为 v1",
+        "❌ CIDv0 can only use base58btc; convert {} to v1 first",
+    ),
+    (
+        "❌ multihash 长度不符: 声明 {} 字节，实际 {} 字节",
+        "❌ Multihash length mismatch: declared {} bytes, actual {} bytes",
+    ),
+    ("❌ 不支持的 CID 版本: {}", "❌ Unsupported CID version: {}"),
+    (
+        "❌ 不支持的编码: {} (可选 {})",
+        "❌ Unsupported encoding: {} (options: {})",
+    ),
+    (
+        "❌ 不是有效的 {} 字符串: {}",
+        "❌ Not a valid {} string: {}",
+    ),
+    (
+        "❌ 只有 dag-pb + sha2-256 的 CID 能转换为 v0，这个 CID 是 {} + {}",
+        "❌ Only dag-pb + sha2-256 CIDs can be converted to v0; this CID is {} + {}",
+    ),
+    ("❌ 无效的 CIDv0: {}", "❌ Invalid CIDv0: {}"),
+    (
+        "❌ 无法识别的 multibase 前缀 '{}': {}",
+        "❌ Unrecognized multibase prefix '{}': {}",
+    ),
+    ("未知", "unknown"),
+    // clone.rs
+    (
+        "   - ⚠️  图片不在同一个文件夹中，清单的 image_cid 为空",
+        "   - ⚠️  Images are not in a single folder; the manifest image_cid is empty",
+    ),
+    (
+        "   - 元数据: {} 个 token，图片: {} 个文件",
+        "   - Metadata: {} tokens, images: {} files",
+    ),
+    ("   - 图片 CID: {}", "   - Image CID: {}"),
+    ("   - 清单: {:?}", "   - Manifest: {:?}"),
+    (
+        "\n--- 正在读取元数据文件夹: {} ---",
+        "\n--- Reading metadata folder: {} ---",
+    ),
+    (
+        "\n✅ 集合已下载到: {:?}",
+        "\n✅ Collection downloaded to: {:?}",
+    ),
+    ("{}: 不是 JSON", "{}: not JSON"),
+    ("{}: 子文件夹", "{}: subfolder"),
+    (
+        "{}: 无法下载的图片地址 `{}`",
+        "{}: image address cannot be downloaded `{}`",
+    ),
+    ("⚠️  跳过了 {} 个条目:", "⚠️  Skipped {} entries:"),
+    (
+        "❌ {} 不是元数据文件夹 (没有找到文件)，请使用批量集合的 Base URI",
+        "❌ {} is not a metadata folder (no files found); use the Base URI of a batch collection",
+    ),
+    ("❌ 无效的 IPFS 地址: {}", "❌ Invalid IPFS address: {}"),
+    (
+        "❌ 输出目录 {:?} 不为空",
+        "❌ Output directory {:?} is not empty",
+    ),
+    // compare.rs
+    ("{} 字节", "{} bytes"),
+    ("只在 A 中", "only in A"),
+    ("只在 B 中", "only in B"),
+    ("缺失", "missing"),
+    // compat.rs
+    (
+        "❌ 当前 IPFS 节点 (版本 {}) 不支持以下命令或参数:\n   - {}\n请升级 Kubo 后重试。",
+        "❌ The current IPFS node (version {}) does not support these commands or options:\n   - {}\nPlease upgrade Kubo and retry.",
+    ),
+    (
+        "❌ 无法解析节点的命令列表: {}",
+        "❌ Cannot parse the node's command list: {}",
+    ),
+    (
+        "⚠️  节点版本 {} 太旧 (低于 Kubo {}.{}.{})，部分功能可能不可用，建议升级",
+        "⚠️  Node version {} is very old (older than Kubo {}.{}.{}); some features may not work, upgrading is recommended",
+    ),
+    (
+        "⚠️  节点 (版本 {}) 不支持 `ipfs add --{}`，已关闭 [add] 中的这个参数",
+        "⚠️  The node (version {}) does not support `ipfs add --{}`; this [add] option has been turned off",
+    ),
+    // config.rs
+    (
+        "⚙️  没有配置文件，使用默认配置",
+        "⚙️  No config file; using the default configuration",
+    ),
+    ("⚙️  读取配置文件: {:?}", "⚙️  Reading config file: {:?}"),
+    (
+        "⚠️  Pinning 服务 {} 没有 access_token，可以运行 `credentials set {}` 保存",
+        "⚠️  Pinning service {} has no access_token; run `credentials set {}` to save one",
+    ),
+    (
+        "❌ 读取配置文件 {:?} 失败: {}",
+        "❌ Failed to read config file {:?}: {}",
+    ),
+    (
+        "❌ 配置文件 {:?} 格式错误: {}",
+        "❌ Config file {:?} is malformed: {}",
+    ),
+    // contract.rs
+    (
+        "❌ 函数 {} 的参数应为 ({})，签名中是 ({})",
+        "❌ Function {} should take ({}), but the signature has ({})",
+    ),
+    (
+        "❌ 十六进制长度不是偶数: {}",
+        "❌ Hex length is not even: {}",
+    ),
+    ("❌ 无效的函数签名: {}", "❌ Invalid function signature: {}"),
+    ("❌ 无效的十六进制: {}", "❌ Invalid hex: {}"),
+    ("❌ 无效的合约地址: {}", "❌ Invalid contract address: {}"),
+    (
+        "❌ 返回数据中的长度过大",
+        "❌ Length in return data is too large",
+    ),
+    ("❌ 返回数据太短", "❌ Return data is too short"),
+    // copy.rs
+    (
+        "❌ {:?} 是特殊文件 (管道、套接字或设备)，无法复制",
+        "❌ {:?} is a special file (pipe, socket or device) and cannot be copied",
+    ),
+    (
+        "❌ 不支持的符号链接处理方式: {} (可选: follow, preserve, skip)",
+        "❌ Unsupported symlink mode: {} (options: follow, preserve, skip)",
+    ),
+    ("❌ 创建 {:?} 失败: {}", "❌ Failed to create {:?}: {}"),
+    (
+        "❌ 创建硬链接 {:?} -> {:?} 失败: {}",
+        "❌ Failed to create hard link {:?} -> {:?}: {}",
+    ),
+    (
+        "❌ 创建符号链接 {:?} -> {:?} 失败: {}",
+        "❌ Failed to create symlink {:?} -> {:?}: {}",
+    ),
+    (
+        "❌ 复制 {:?} 到 {:?} 失败: {}",
+        "❌ Failed to copy {:?} to {:?}: {}",
+    ),
+    (
+        "❌ 符号链接 {:?} 指向的目标不存在: {:?}",
+        "❌ The target of symlink {:?} does not exist: {:?}",
+    ),
+    (
+        "❌ 符号链接 {:?} 指向绝对路径 {:?}，symlinks = \"preserve\" 时只能保留相对路径的链接",
+        "❌ Symlink {:?} points to absolute path {:?}; with symlinks = \"preserve\" only relative links can be kept",
+    ),
+    (
+        "❌ 符号链接 {:?} 指向输入文件夹之外或不存在的 {:?}，保留后会失效，请改用 symlinks = \"follow\"",
+        "❌ Symlink {:?} points to {:?} outside the input folder or missing, and would break if preserved; use symlinks = \"follow\" instead",
+    ),
+    (
+        "❌ 符号链接形成循环: {:?} 指向上层文件夹 {:?}",
+        "❌ Symlink loop: {:?} points to parent folder {:?}",
+    ),
+    (
+        "❌ 设置 {:?} 的权限失败: {}",
+        "❌ Failed to set permissions on {:?}: {}",
+    ),
+    (
+        "❌ 读取符号链接 {:?} 失败: {}",
+        "❌ Failed to read symlink {:?}: {}",
+    ),
+    (
+        "当前平台不支持符号链接",
+        "symlinks are not supported on this platform",
+    ),
+    // daemon.rs
+    (
+        "\n🛑 正在关闭自动启动的 IPFS daemon...",
+        "\n🛑 Shutting down the auto-started IPFS daemon...",
+    ),
+    (
+        "✅ IPFS daemon 已在运行，无需自动启动",
+        "✅ IPFS daemon is already running; no need to start it",
+    ),
+    (
+        "✅ IPFS daemon 已就绪 (耗时 {:.1} 秒)",
+        "✅ IPFS daemon is ready (took {:.1} s)",
+    ),
+    (
+        "❌ IPFS daemon 启动后立即退出 ({})，详见 {:?}",
+        "❌ IPFS daemon exited right after starting ({}); see {:?}",
+    ),
+    (
+        "❌ 无法启动 `{} daemon`: {}",
+        "❌ Cannot start `{} daemon`: {}",
+    ),
+    (
+        "❌ 等待 IPFS daemon 就绪超时 ({} 秒)，详见 {:?}",
+        "❌ Timed out waiting for the IPFS daemon ({} s); see {:?}",
+    ),
+    (
+        "🚀 正在启动 IPFS daemon (日志: {:?})...",
+        "🚀 Starting IPFS daemon (log: {:?})...",
+    ),
+    // dashboard.rs
+    (
+        "❌ --tui 目前只支持 Linux 和 macOS",
+        "❌ --tui currently supports only Linux and macOS",
+    ),
+    (
+        "❌ --tui 需要在终端中运行: {}",
+        "❌ --tui must run in a terminal: {}",
+    ),
+    (
+        "❌ 创建日志文件 {:?} 失败: {}",
+        "❌ Failed to create log file {:?}: {}",
+    ),
+    ("❌ 重定向输出失败: {}", "❌ Failed to redirect output: {}"),
+    (
+        "📄 仪表盘期间的完整输出: {:?}",
+        "📄 Full output during the dashboard: {:?}",
+    ),
+    (
+        "📺 仪表盘已启动，完整输出写入 {:?}",
+        "📺 Dashboard started; full output goes to {:?}",
+    ),
+    // demo.rs
+    ("   - 元数据 CID: {}", "   - Metadata CID: {}"),
+    (
+        "\n工具链可以正常工作，接下来可以用 `batch <图片文件夹>` 上传自己的集合。",
+        "\nThe toolchain works; next, upload your own collection with `batch <image folder>`.",
+    ),
+    (
+        "\n🎉 示例流程完成，用时 {:.1} 秒",
+        "\n🎉 Demo finished in {:.1} s",
+    ),
+    (
+        "⚠️  元数据文件夹没有在节点上固定",
+        "⚠️  The metadata folder is not pinned on the node",
+    ),
+    (
+        "⚠️  无法检查 pin 状态: {}",
+        "⚠️  Cannot check pin status: {}",
+    ),
+    (
+        "❌ 写入图片 {:?} 失败: {}",
+        "❌ Failed to write image {:?}: {}",
+    ),
+    (
+        "❌ 示例图片数量必须大于 0",
+        "❌ The number of demo images must be greater than 0",
+    ),
+    (
+        "📌 元数据文件夹已在节点上固定",
+        "📌 The metadata folder is pinned on the node",
+    ),
+    // deploy.rs
+    (
+        "❌ --max-supply {} 小于集合中的 token 数 {}",
+        "❌ --max-supply {} is less than the {} tokens in the collection",
+    ),
+    (
+        "❌ 单件 NFT 没有 Base URI，deploy-config 只支持批量集合",
+        "❌ A single NFT has no Base URI; deploy-config only supports batch collections",
+    ),
+    (
+        "❌ 读取图片 {:?} 失败: {}",
+        "❌ Failed to read image {:?}: {}",
+    ),
+    // diff.rs
+    ("(无)", "(none)"),
+    ("❌ {} 不是文件夹", "❌ {} is not a folder"),
+    (
+        "❌ 读取 ipfs://{} 需要连接 IPFS 节点",
+        "❌ Reading ipfs://{} requires an IPFS node",
+    ),
+    ("删除", "removed"),
+    ("新增", "added"),
+    // doctor.rs
+    ("IPFS 节点", "IPFS node"),
+    ("Pinning 服务 {}", "Pinning service {}"),
+    ("\n🩺 环境检查结果:", "\n🩺 Environment check results:"),
+    ("`{} version` 执行失败: {}", "`{} version` failed: {}"),
+    ("ipfs 命令行", "ipfs CLI"),
+    ("{:?} 可写", "{:?} is writable"),
+    (
+        "{:?} 所在磁盘仅剩 {} MiB",
+        "Only {1} MiB left on the disk of {0:?}",
+    ),
+    ("{} 可以连接", "{} is reachable"),
+    ("{} 可以连接 (版本: {})", "{} is reachable (version: {})"),
+    ("可用 {:.1} GiB", "{:.1} GiB available"),
+    (
+        "在服务控制台重新生成 access token，并更新配置文件中 `{}` 的 access_token",
+        "Regenerate the access token in the service console and update the access_token of `{}` in the config file",
+    ),
+    (
+        "安装 Kubo 并确保 `ipfs` 在 PATH 中: https://docs.ipfs.tech/install/command-line/",
+        "Install Kubo and make sure `ipfs` is on PATH: https://docs.ipfs.tech/install/command-line/",
+    ),
+    ("手动确认磁盘空间", "Check the disk space manually"),
+    ("找不到 `{}`: {}", "`{}` not found: {}"),
+    ("无法写入 {:?}: {}", "Cannot write to {:?}: {}"),
+    ("无法获取可用空间: {}", "Cannot get free space: {}"),
+    ("无法连接 {}: {}", "Cannot connect to {}: {}"),
+    ("服务返回 {}", "The service returned {}"),
+    ("本地 ipfs 命令行", "local ipfs CLI"),
+    (
+        "检查本机 TLS / 网络配置",
+        "Check the local TLS / network configuration",
+    ),
+    (
+        "检查目录权限，或用 --output 指定一个可写的目录",
+        "Check directory permissions, or use --output to pick a writable directory",
+    ),
+    (
+        "检查网络、代理设置和 endpoint 地址",
+        "Check the network, proxy settings and endpoint address",
+    ),
+    (
+        "清理磁盘或用 --output 指定其它磁盘；批量上传会在输出目录复制所有图片",
+        "Free up disk space or use --output to pick another disk; batch uploads copy every image into the output directory",
+    ),
+    (
+        "确认 daemon 已启动，并检查 --api 地址、端口或 unix socket 路径是否正确 (Kubo 默认 /ip4/127.0.0.1/tcp/5001)",
+        "Make sure the daemon is running and check that the --api address, port or unix socket path is correct (Kubo default /ip4/127.0.0.1/tcp/5001)",
+    ),
+    (
+        "确认 endpoint 是 Pinning Service API 的根地址 (不包含 /pins)",
+        "Make sure the endpoint is the root of the Pinning Service API (without /pins)",
+    ),
+    ("磁盘空间", "Disk space"),
+    ("认证失败 ({})", "Authentication failed ({})"),
+    ("认证有效", "Authentication valid"),
+    ("输出目录", "Output directory"),
+    (
+        "运行 `ipfs daemon` 启动节点；首次使用请先执行 `ipfs init`",
+        "Run `ipfs daemon` to start the node; on first use run `ipfs init` first",
+    ),
+    (
+        "重新安装 Kubo: https://docs.ipfs.tech/install/command-line/",
+        "Reinstall Kubo: https://docs.ipfs.tech/install/command-line/",
+    ),
+    // duplicates.rs
+    ("   ... 另有 {} 组", "   ... and {} more groups"),
+    (
+        "\n⚠️  {} 个 token 的图片内容完全相同 (共 {} 组)，通常是生成流程出了问题:",
+        "\n⚠️  {} tokens have identical image content ({} groups); usually the generation pipeline went wrong:",
+    ),
+    (
+        "❌ 发现 {} 组内容相同的图片，按 --strict 在上传前终止",
+        "❌ Found {} groups of identical images; stopping before upload because of --strict",
+    ),
+    ("❌ 计算 {:?} 的哈希失败: {}", "❌ Failed to hash {:?}: {}"),
+    (
+        "💡 确认无误可以忽略；加上 --strict 时会在上传前终止",
+        "💡 Ignore this if it is intended; with --strict the run stops before upload",
+    ),
+    // encryption.rs
+    ("❌ 不是加密文件", "❌ Not an encrypted file"),
+    (
+        "❌ 加密上传时不能以 IPLD 节点发布元数据 ([ipld])",
+        "❌ Metadata cannot be published as IPLD nodes ([ipld]) when uploading encrypted",
+    ),
+    ("❌ 加密失败", "❌ Encryption failed"),
+    (
+        "❌ 密钥指纹 {} 与清单中记录的 {} 不一致",
+        "❌ Key fingerprint {} differs from {} recorded in the manifest",
+    ),
+    ("❌ 无效的路径: {:?}", "❌ Invalid path: {:?}"),
+    (
+        "❌ 解密失败：密钥不对，或内容被改动过",
+        "❌ Decryption failed: wrong key, or the content has been modified",
+    ),
+    (
+        "❌ 集合密钥应为 32 字节的十六进制 (可以用 `openssl rand -hex 32` 生成)",
+        "❌ The collection key should be 32 bytes of hex (generate one with `openssl rand -hex 32`)",
+    ),
+    // estimate.rs
+    (
+        "   - 元数据: {} 个文件，约 {}",
+        "   - Metadata: {} files, about {}",
+    ),
+    (
+        "   - 合计: {} 个文件，{}，约 {} 个 IPFS 块",
+        "   - Total: {} files, {}, about {} IPFS blocks",
+    ),
+    ("   - 图片: {} 个文件，{}", "   - Images: {} files, {}"),
+    (
+        "\n💡 在配置文件中添加 [[pricing_tiers]] 可以估算费用和检查套餐限额",
+        "\n💡 Add [[pricing_tiers]] to the config file to estimate costs and check plan limits",
+    ),
+    ("\n💰 套餐估算:", "\n💰 Plan estimate:"),
+    ("\n📄 最大的 {} 个文件:", "\n📄 Largest {} files:"),
+    ("\n📦 上传估算:", "\n📦 Upload estimate:"),
+    (
+        "{} {} {}: 上传后 {:.2} GB / {} 个文件，约 ${:.2}/月",
+        "{} {} {}: {:.2} GB / {} files after upload, about ${:.2}/month",
+    ),
+    (
+        "{} 个文件超过单文件上限 {} MB",
+        "{} files exceed the per-file limit of {} MB",
+    ),
+    (
+        "⚠️  {} 个图片的文件名不是数字，批量上传会失败: {}",
+        "⚠️  {} image file names are not numbers; the batch upload will fail: {}",
+    ),
+    (
+        "存储 {:.2} GB 超出套餐上限 {} GB",
+        "{:.2} GB of storage exceeds the plan limit of {} GB",
+    ),
+    (
+        "存储超出套餐 {:.2} GB，按 ${}/GB 计费",
+        "Storage exceeds the plan by {:.2} GB, billed at ${}/GB",
+    ),
+    (
+        "文件数 {} 超出套餐上限 {}",
+        "{} files exceed the plan limit of {}",
+    ),
+    // eth.rs
+    (
+        "⏳ 正在等待交易上链...",
+        "⏳ Waiting for the transaction to be mined...",
+    ),
+    (
+        "❌ RPC {} 请求失败 ({}): {}",
+        "❌ RPC {} request failed ({}): {}",
+    ),
+    (
+        "❌ RPC {} 返回错误 ({}): {}",
+        "❌ RPC {} returned an error ({}): {}",
+    ),
+    (
+        "❌ RPC 没有返回交易哈希",
+        "❌ RPC returned no transaction hash",
+    ),
+    (
+        "❌ RPC 的 chainId 是 {}，配置中是 {}，请检查 rpc_url",
+        "❌ The RPC chainId is {}, but the config says {}; check rpc_url",
+    ),
+    (
+        "❌ RPC 返回的数值格式错误: {}",
+        "❌ RPC returned a malformed number: {}",
+    ),
+    ("❌ 交易签名失败: {}", "❌ Failed to sign transaction: {}"),
+    (
+        "❌ 交易预估失败，账户 {} 可能没有权限: {}",
+        "❌ Transaction estimation failed; account {} may lack permission: {}",
+    ),
+    ("❌ 无法连接 RPC {}: {}", "❌ Cannot connect to RPC {}: {}"),
+    ("❌ 私钥格式错误", "❌ Malformed private key"),
+    (
+        "❌ 配置文件的 [contract] 段缺少: {}",
+        "❌ The [contract] section of the config file is missing: {}",
+    ),
+    ("📨 已发送交易: {}", "📨 Transaction sent: {}"),
+    // expiry.rs
+    ("{} 取消固定 {} 失败: {:#}", "{} failed to unpin {}: {:#}"),
+    (
+        "❌ 无效的过期时间: {} (如 30d、12h、2025-12-31)",
+        "❌ Invalid expiry: {} (e.g. 30d, 12h, 2025-12-31)",
+    ),
+    (
+        "❌ 无效的过期时间单位 `{}` (可选: h, d, w)",
+        "❌ Invalid expiry unit `{}` (options: h, d, w)",
+    ),
+    ("删除 {:?} 失败: {}", "Failed to delete {:?}: {}"),
+    (
+        "配置文件中已经没有 Pinning 服务 {}",
+        "Pinning service {} is no longer in the config file",
+    ),
+    // extra_fields.rs
+    (
+        "❌ {} 中的字段名 \"{}\" 无效 (不能为空，也不能是 {})",
+        "❌ Field name \"{1}\" in {0} is invalid (must not be empty or one of {2})",
+    ),
+    (
+        "❌ 读取自定义字段文件 {:?} 失败: {}",
+        "❌ Failed to read custom field file {:?}: {}",
+    ),
+    // extract.rs
+    (
+        "\n--- 正在解压 {:?} ({}) -> {:?} ---",
+        "\n--- Extracting {:?} ({}) -> {:?} ---",
+    ),
+    (
+        "⚠️  跳过了压缩包中的链接和特殊文件: {}",
+        "⚠️  Skipped links and special files in the archive: {}",
+    ),
+    ("✅ 已解压 {} 个文件 ({})", "✅ Extracted {} files ({})"),
+    (
+        "❌ 创建临时目录 {:?} 失败: {}",
+        "❌ Failed to create temporary directory {:?}: {}",
+    ),
+    (
+        "❌ 压缩包中有不安全的路径 {:?}，拒绝解压",
+        "❌ The archive contains unsafe path {:?}; refusing to extract",
+    ),
+    ("❌ 无法读取 zip {:?}: {}", "❌ Cannot read zip {:?}: {}"),
+    // filecoin.rs
+    (
+        "duration_days 应在 {} 到 {} 之间: {}",
+        "duration_days should be between {} and {}: {}",
+    ),
+    (
+        "epoch_price 应是整数 (attoFIL): {:?}",
+        "epoch_price should be an integer (attoFIL): {:?}",
+    ),
+    ("lotus 需要付款钱包 wallet", "lotus needs a payment wallet"),
+    (
+        "lotus 需要至少一个存储提供方 miners",
+        "lotus needs at least one storage provider in miners",
+    ),
+    (
+        "storacha 需要 token (可以写成 \"${keyring:storacha}\")",
+        "storacha needs a token (can be written as \"${keyring:storacha}\")",
+    ),
+    (
+        "❌ 未知的 Filecoin 交易方式: {} (可选 storacha、lotus)",
+        "❌ Unknown Filecoin deal service: {} (options: storacha, lotus)",
+    ),
+    // filter.rs
+    ("❌ 无效的 glob: {:?}", "❌ Invalid glob: {:?}"),
+    // harness.rs
+    ("✅ Kubo 已下载到 {:?}", "✅ Kubo downloaded to {:?}"),
+    (
+        "✅ 临时 Kubo 节点已就绪 (耗时 {:.1} 秒)",
+        "✅ Temporary Kubo node is ready (took {:.1} s)",
+    ),
+    ("❌ `ipfs {}` 失败: {}", "❌ `ipfs {}` failed: {}"),
+    (
+        "❌ 下载 Kubo 失败 ({}): {}",
+        "❌ Failed to download Kubo ({}): {}",
+    ),
+    (
+        "❌ 临时 Kubo 节点启动后立即退出 ({})，详见 {:?}",
+        "❌ Temporary Kubo node exited right after starting ({}); see {:?}",
+    ),
+    (
+        "❌ 无法启动 {:?} daemon: {}",
+        "❌ Cannot start {:?} daemon: {}",
+    ),
+    (
+        "❌ 无法运行 tar 解压 Kubo: {}",
+        "❌ Cannot run tar to extract Kubo: {}",
+    ),
+    ("❌ 无法运行 {:?}: {}", "❌ Cannot run {:?}: {}"),
+    (
+        "❌ 没有适用于 {} 架构的 Kubo 发行版",
+        "❌ No Kubo release for the {} architecture",
+    ),
+    (
+        "❌ 没有适用于 {} 的 Kubo 发行版",
+        "❌ No Kubo release for {}",
+    ),
+    (
+        "❌ 等待临时 Kubo 节点就绪超时 ({} 秒)，详见 {:?}",
+        "❌ Timed out waiting for the temporary Kubo node ({} s); see {:?}",
+    ),
+    ("❌ 解压 Kubo 失败: {:?}", "❌ Failed to extract Kubo: {:?}"),
+    ("⬇️ 正在下载 Kubo {}: {}", "⬇️ Downloading Kubo {}: {}"),
+    (
+        "🚀 正在启动临时 Kubo 节点 (IPFS_PATH: {:?}, API: {})...",
+        "🚀 Starting temporary Kubo node (IPFS_PATH: {:?}, API: {})...",
+    ),
+    // hashing.rs
+    (
+        "❌ 不支持的哈希算法: {} (可选: blake3, sha256)",
+        "❌ Unsupported hash algorithm: {} (options: blake3, sha256)",
+    ),
+    // hooks.rs
+    ("⚠️  钩子 {} 失败: {:#}", "⚠️  Hook {} failed: {:#}"),
+    (
+        "❌ 未知的钩子时机: {} (可选 images_uploaded、metadata_uploaded、failed)",
+        "❌ Unknown hook event: {} (options: images_uploaded, metadata_uploaded, failed)",
+    ),
+    ("❌ 钩子 {} 失败: {:#}", "❌ Hook {} failed: {:#}"),
+    ("没有设置 command 或 url", "neither command nor url is set"),
+    (
+        "第 {} 个钩子 ({}) 需要设置 command 或 url",
+        "Hook {} ({}) needs command or url",
+    ),
+    (
+        "第 {} 个钩子同时设置了 command 和 url，只能二选一",
+        "Hook {} sets both command and url; choose one",
+    ),
+    (
+        "第 {} 个钩子的 command 为空",
+        "The command of hook {} is empty",
+    ),
+    (
+        "第 {} 个钩子的 url 应以 http:// 或 https:// 开头: {}",
+        "The url of hook {} should start with http:// or https://: {}",
+    ),
+    ("超过 {} 秒未结束", "did not finish within {} seconds"),
+    ("退出状态 {}", "exit status {}"),
+    ("🪝 运行钩子 ({}): {}", "🪝 Running hook ({}): {}"),
+    // i18n/mod.rs
+    (
+        "❌ 不支持的语言: {} (可选: zh, en)",
+        "❌ Unsupported language: {} (choices: zh, en)",
+    ),
+    // init.rs
+    (
+        "   描述中可以使用 {{name}}、{{token_id}} 和 {{image}}",
+        "   The description can use {{name}}, {{token_id}} and {{image}}",
+    ),
+    ("Filebase 的 IPFS bucket", "Filebase IPFS bucket"),
+    ("Kubo API 地址", "Kubo API address"),
+    (
+        "\n--- 创建配置文件：直接回车使用括号中的默认值 ---",
+        "\n--- Create config file: press Enter to accept the default in brackets ---",
+    ),
+    ("\n✅ 配置已写入: {:?}", "\n✅ Config written to: {:?}"),
+    ("{:?} 已存在，覆盖吗", "{:?} already exists; overwrite"),
+    (
+        "⚠️  {:?} 还不存在，上传前请把图片放进这个文件夹",
+        "⚠️  {:?} does not exist yet; put the images in this folder before uploading",
+    ),
+    ("⚠️  请输入 y 或 n", "⚠️  Please enter y or n"),
+    (
+        "❌ 未知的上传方式: {} (可选 {})",
+        "❌ Unknown upload method: {} (options: {})",
+    ),
+    (
+        "❌ 生成的配置格式错误: {}",
+        "❌ Generated config is malformed: {}",
+    ),
+    (
+        "❌ 至少选择一种上传方式",
+        "❌ Choose at least one upload method",
+    ),
+    ("❌ 请输入 721 或 1155", "❌ Please enter 721 or 1155"),
+    (
+        "上传方式 (可用逗号分隔多个)",
+        "Upload methods (comma-separated)",
+    ),
+    (
+        "元数据文件名后缀 (none / json / auto)",
+        "Metadata file name suffix (none / json / auto)",
+    ),
+    (
+        "写入后用内存后端试运行一次吗",
+        "Run a dry run with the memory backend after writing",
+    ),
+    ("合约标准 (721 / 1155)", "Contract standard (721 / 1155)"),
+    ("图片文件夹", "Image folder"),
+    ("描述模板", "Description template"),
+    ("本机的 ipfs 命令行 (默认)", "local ipfs CLI (default)"),
+    (
+        "远程 Kubo 节点的 HTTP API",
+        "HTTP API of a remote Kubo node",
+    ),
+    ("集合名称", "Collection name"),
+    (
+        "👋 已取消，配置文件没有改动",
+        "👋 Cancelled; the config file is unchanged",
+    ),
+    (
+        "💡 Filebase 的密钥放在 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY 中，上传时加上 --filebase <bucket>",
+        "💡 Put the Filebase keys in FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY and add --filebase <bucket> when uploading",
+    ),
+    (
+        "💡 运行 `credentials set pinata` 保存 Pinata 的 JWT",
+        "💡 Run `credentials set pinata` to save the Pinata JWT",
+    ),
+    // inspect.rs
+    (
+        "... (共 {}，只显示了开头，可以用 --out 保存完整内容)",
+        "... ({} total, only the beginning is shown; use --out to save the full content)",
+    ),
+    ("共 {} 项，文件总大小 {}", "{} entries, {} total file size"),
+    ("💾 已保存 {} ({}) 到 {:?}", "💾 Saved {} ({}) to {:?}"),
+    (
+        "📄 {} 没有目录项 (可能是文件，可以用 cat 查看)",
+        "📄 {} has no directory entries (it may be a file; view it with cat)",
+    ),
+    (
+        "📦 {} 是二进制内容 ({}，{})，可以用 --out 保存到文件",
+        "📦 {} is binary content ({}, {}); use --out to save it to a file",
+    ),
+    // ipld.rs
+    ("❌ 无效的数字: {}", "❌ Invalid number: {}"),
+    (
+        "❌ 未知的 IPLD 编码: {} (可选 dag-cbor、dag-json)",
+        "❌ Unknown IPLD codec: {} (options: dag-cbor, dag-json)",
+    ),
+    // layout.rs
+    ("   ... 另有 {} 个问题", "   ... and {} more problems"),
+    ("   {}{}说明", "   {}{}note"),
+    ("(无扩展名)", "(no extension)"),
+    (
+        "\n--- token ID 检查发现 {} 个问题 ---",
+        "\n--- token ID check found {} problems ---",
+    ),
+    (
+        "token_ids = \"csv\" 需要填写 mapping (映射文件)",
+        "token_ids = \"csv\" requires mapping (the mapping file)",
+    ),
+    ("{} (多数为 {}): {}", "{} (mostly {}): {}"),
+    (
+        "❌ [output] 模板 {:?} 中有不支持的占位符 {}}}",
+        "❌ [output] template {:?} has unsupported placeholder {}}}",
+    ),
+    (
+        "❌ [output] 模板 {:?} 生成的运行目录 {:?} 必须是 --output 下的相对路径，且不能包含 ..",
+        "❌ The run directory {1:?} produced by [output] template {0:?} must be a relative path under --output without ..",
+    ),
+    (
+        "❌ token ID 检查发现 {} 个问题，已在生成元数据前终止{}",
+        "❌ token ID check found {} problems; stopped before generating metadata{}",
+    ),
+    (
+        "❌ token_ids = \"csv\" 需要在 [layout] 中填写 mapping",
+        "❌ token_ids = \"csv\" requires mapping in [layout]",
+    ),
+    (
+        "❌ {} 和 {} 的文件名规范化后都是 {}",
+        "❌ {} and {} both normalize to {}",
+    ),
+    (
+        "❌ {} 的名称不是数字 token ID，可以在 [layout] 中设置 strip_prefix，或改用 token_ids = \"index\" / \"csv\"",
+        "❌ The name of {} is not a numeric token ID; set strip_prefix in [layout], or use token_ids = \"index\" / \"csv\"",
+    ),
+    (
+        "❌ 不支持的 token ID 来源: {} (可选: filename, index, csv)",
+        "❌ Unsupported token ID source: {} (options: filename, index, csv)",
+    ),
+    (
+        "❌ 图片文件夹不存在: {:?}",
+        "❌ Image folder does not exist: {:?}",
+    ),
+    ("❌ 子文件夹 {} 中没有文件", "❌ Subfolder {} has no files"),
+    (
+        "❌ 文件名不是有效的 UTF-8: {:?}",
+        "❌ File name is not valid UTF-8: {:?}",
+    ),
+    ("❌ 无效的相对路径: {:?}", "❌ Invalid relative path: {:?}"),
+    (
+        "❌ 无法把 {} 重命名为 {}: 目标已存在",
+        "❌ Cannot rename {} to {}: the target exists",
+    ),
+    (
+        "❌ 映射文件 {:?} 中缺少 {} 项: {}",
+        "❌ Mapping file {:?} is missing {} entries: {}",
+    ),
+    (
+        "❌ 映射文件中 {} 出现了多次",
+        "❌ {} appears more than once in the mapping file",
+    ),
+    (
+        "❌ 映射文件第 {} 行的 token ID 无效: {}",
+        "❌ Invalid token ID on line {} of the mapping file: {}",
+    ),
+    (
+        "❌ 映射文件第 {} 行缺少逗号: {}",
+        "❌ Missing comma on line {} of the mapping file: {}",
+    ),
+    (
+        "❌ 读取映射文件 {:?} 失败: {}",
+        "❌ Failed to read mapping file {:?}: {}",
+    ),
+    ("扩展名不一致", "mismatched extension"),
+    ("无效的 glob: {:?}", "Invalid glob: {:?}"),
+    ("映射文件不存在: {:?}", "Mapping file does not exist: {:?}"),
+    (
+        "有意混用扩展名时设置 allow_mixed_extensions = true",
+        "Set allow_mixed_extensions = true if mixed extensions are intended",
+    ),
+    (
+        "有意留空的 ID 可以在 [layout] 中设置 allow_gaps = true",
+        "IDs left empty on purpose can be allowed with allow_gaps = true in [layout]",
+    ),
+    ("缺少 {} 个 ID", "{} IDs missing"),
+    ("重复", "duplicate"),
+    ("问题", "problem"),
+    // legacy.rs
+    (
+        "⚠️  TypeScript 实现默认使用 CIDv0 上传，重新计算的 CIDv1 可能与原来的上传结果不同",
+        "⚠️  The TypeScript implementation uploads with CIDv0 by default; the recomputed CIDv1 may differ from the original upload",
+    ),
+    (
+        "❌ {:?} 中没有找到元数据文件 `{}`",
+        "❌ Metadata file `{1}` not found in {0:?}",
+    ),
+    (
+        "❌ {:?} 的 image 没有文件名: {}",
+        "❌ The image of {:?} has no file name: {}",
+    ),
+    ("❌ 不是 ipfs:// 链接: {}", "❌ Not an ipfs:// link: {}"),
+    (
+        "❌ 无法判断 {:?} 来自哪个实现，请用 --source 指定",
+        "❌ Cannot tell which implementation {:?} comes from; specify it with --source",
+    ),
+    (
+        "❌ 无法解析元数据 {:?}: {}",
+        "❌ Cannot parse metadata {:?}: {}",
+    ),
+    (
+        "❌ 未知的实现 `{}` (可选: python, typescript, go)",
+        "❌ Unknown implementation `{}` (options: python, typescript, go)",
+    ),
+    ("❌ 目录不存在: {:?}", "❌ Directory does not exist: {:?}"),
+    ("无效的目录名: {:?}", "Invalid directory name: {:?}"),
+    // loadtest.rs
+    (
+        "   - {} 个 URL，{} 请求/秒，持续 {:?}",
+        "   - {} URLs, {} requests/s, for {:?}",
+    ),
+    (
+        "   延迟 p50 {} / p90 {} / p95 {} / p99 {} / max {}",
+        "   Latency p50 {} / p90 {} / p95 {} / p99 {} / max {}",
+    ),
+    ("   状态: {}", "   Status: {}"),
+    (
+        "\n📊 压测结果 (耗时 {:.1}s):",
+        "\n📊 Load test results (took {:.1}s):",
+    ),
+    (
+        "{} {}: {} 个请求，错误率 {:.2}%",
+        "{} {}: {} requests, error rate {:.2}%",
+    ),
+    ("❌ --rps 必须大于 0", "❌ --rps must be greater than 0"),
+    ("❌ 无效的时长: {}", "❌ Invalid duration: {}"),
+    (
+        "❌ 无效的时长单位 `{}` (可选: ms, s, m, h)",
+        "❌ Invalid duration unit `{}` (options: ms, s, m, h)",
+    ),
+    (
+        "❌ 清单中没有可以压测的 token URL",
+        "❌ The manifest has no token URLs to load test",
+    ),
+    ("元数据", "metadata"),
+    ("图片", "image"),
+    ("🚦 开始压测网关: {}", "🚦 Starting gateway load test: {}"),
+    // localization.rs
+    (
+        "default 不是有效的语言代码: \"{}\"",
+        "default is not a valid language code: \"{}\"",
+    ),
+    (
+        "locales 中的语言代码无效: \"{}\"",
+        "Invalid language code in locales: \"{}\"",
+    ),
+    (
+        "{} 是 default 语言，使用元数据本身的文字，不需要写在 locales 中",
+        "{} is the default language and uses the metadata's own text; it does not belong in locales",
+    ),
+    (
+        "❌ 翻译文件 {:?} 的表头应为 token_id,name,description (可以只有 name 或 description): {}",
+        "❌ The header of translation file {:?} should be token_id,name,description (name or description alone is fine): {}",
+    ),
+    (
+        "❌ 读取翻译文件 {:?} 失败: {}",
+        "❌ Failed to read translation file {:?}: {}",
+    ),
+    // main.rs
+    // 顶层错误：前缀不变，错误内容按其它条目翻译
+    ("Error: {:?}", "Error: {:?}"),
+    (
+        "🌐 使用环境变量 IPFS_API 中的节点地址: {}",
+        "🌐 Using the node address from the IPFS_API environment variable: {}",
+    ),
+    ("⏱️  用时 {:.1} 秒", "⏱️  Took {:.1} s"),
+    (
+        "   - {} [{} {}] {} 个 CID，{}",
+        "   - {} [{} {}] {} CIDs, {}",
+    ),
+    (
+        "   - 块数量: {}，数据大小: {} 字节",
+        "   - Blocks: {}, data size: {} bytes",
+    ),
+    ("   - 最大供应量: {}", "   - Max supply: {}"),
+    ("   - 来源证明哈希: {}", "   - Provenance hash: {}"),
+    ("   - 根 CID: {}", "   - Root CID: {}"),
+    (
+        "   - 白名单 Merkle 根: {}",
+        "   - Allowlist Merkle root: {}",
+    ),
+    (
+        "   - 第一个 token 的元数据: {}",
+        "   - Metadata of the first token: {}",
+    ),
+    ("   - 签名时间: {}", "   - Signed at: {}"),
+    ("   - 签名者: {}", "   - Signer: {}"),
+    (
+        "   取消固定 {} 个，删除远程 pin {} 个{}",
+        "   Unpinned {}, removed {} remote pins{}",
+    ),
+    ("1. 登录 Pinata。", "1. Log in to Pinata."),
+    (
+        "2. 上传您本地 `rust/output/collection_[时间戳]/images` 文件夹。",
+        "2. Upload your local `rust/output/collection_[timestamp]/images` folder.",
+    ),
+    (
+        "3. 上传您本地 `rust/output/collection_[时间戳]/metadata` 文件夹。",
+        "3. Upload your local `rust/output/collection_[timestamp]/metadata` folder.",
+    ),
+    (
+        "4. ⚠️  使用 Pinata 返回的【metadata】文件夹的 CID 来设置您合约的 Base URI。",
+        "4. ⚠️  Use the CID of the [metadata] folder returned by Pinata as your contract's Base URI.",
+    ),
+    ("Arweave 镜像", "Arweave mirror"),
+    ("DHT 宣告", "DHT provide"),
+    ("Filecoin 归档", "Filecoin archive"),
+    (
+        "\n--- 正在打包 CAR: {:?} ---",
+        "\n--- Packing CAR: {:?} ---",
+    ),
+    (
+        "\n--- 正在调用合约 {} 的 {} ---",
+        "\n--- Calling {1} on contract {0} ---",
+    ),
+    (
+        "\n--- 试运行：内存后端，不连接节点，内容不会保留 ---",
+        "\n--- Dry run: memory backend, no node connection, nothing is kept ---",
+    ),
+    ("\n⏳ {} 个运行:", "\n⏳ {} runs:"),
+    (
+        "\n✅ 所有 CID 均已固定且可以访问",
+        "\n✅ All CIDs are pinned and reachable",
+    ),
+    ("\n✅ 环境检查通过", "\n✅ Environment check passed"),
+    (
+        "\n✅ 试运行完成，去掉 --memory 即可正式上传",
+        "\n✅ Dry run finished; drop --memory to upload for real",
+    ),
+    ("\n其它写法:", "\nOther forms:"),
+    ("\n📘 操作手册已生成: {:?}", "\n📘 Runbook generated: {:?}"),
+    (
+        "\n🗄️  Filecoin 归档 ({:?}):",
+        "\n🗄️  Filecoin archives ({:?}):",
+    ),
+    (
+        "multihash: {} (0x{:x})，{} 字节",
+        "multihash: {} (0x{:x}), {} bytes",
+    ),
+    (
+        "{}\n⚠️  上传已完成，可以稍后手动把 Base URI 设置为 {}",
+        "{}\n⚠️  The upload is complete; you can set the Base URI to {} manually later",
+    ),
+    ("⏳ {:?} 将在 {} 过期", "⏳ {:?} expires at {}"),
+    ("⏳ 本次运行将在 {} 过期", "⏳ This run expires at {}"),
+    (
+        "⚠️  {:?} 不存在，跳过试运行",
+        "⚠️  {:?} does not exist; skipping the dry run",
+    ),
+    (
+        "⚠️  {} 个根没有提交成功，可以稍后运行 `filecoin submit` 重试",
+        "⚠️  {} roots were not submitted; retry later with `filecoin submit`",
+    ),
+    (
+        "⚠️  {} 中跳过了不是 JSON 的文件: {}",
+        "⚠️  Skipped non-JSON files in {}: {}",
+    ),
+    (
+        "⚠️  交易 {} 还没有上链，请稍后在区块浏览器中确认",
+        "⚠️  Transaction {} is not mined yet; check it later in a block explorer",
+    ),
+    (
+        "⚠️  合约还没有 mint 任何 token，无法判断 .json 后缀，默认不带后缀；部署后请用 verify-onchain 检查",
+        "⚠️  The contract has not minted any token yet, so the .json suffix cannot be detected; defaulting to no suffix. Check with verify-onchain after deployment",
+    ),
+    (
+        "⚠️  本地 ipfs 命令行后端不支持上传限速，请使用 --api 连接节点",
+        "⚠️  The local ipfs CLI backend does not support upload rate limits; use --api to connect to the node",
+    ),
+    (
+        "⚠️  没有生成市场链接：链 {} 不受支持，或清单中的 token 没有数字 ID",
+        "⚠️  No marketplace links generated: chain {} is not supported, or the tokens in the manifest have no numeric ID",
+    ),
+    (
+        "⚠️  跳过未加密的文件: {:?}",
+        "⚠️  Skipping unencrypted file: {:?}",
+    ),
+    (
+        "⚠️  钥匙串中没有 {} 的密钥",
+        "⚠️  No key for {} in the keychain",
+    ),
+    (
+        "✅ Arweave 镜像: 上传 {} 个文件 ({} 字节)，跳过 {} 个已镜像，失败 {} 个",
+        "✅ Arweave mirror: uploaded {} files ({} bytes), skipped {} already mirrored, {} failed",
+    ),
+    (
+        "✅ Base URI 已设置为 {} (区块 {}，gas {})",
+        "✅ Base URI set to {} (block {}, gas {})",
+    ),
+    ("✅ CAR 已生成: {:?}", "✅ CAR generated: {:?}"),
+    ("✅ {:?} 的 CID: {}", "✅ CID of {:?}: {}"),
+    (
+        "✅ {} 个元数据文件都符合{}结构",
+        "✅ All {} metadata files match the {}schema",
+    ),
+    (
+        "✅ {} 的密钥已保存到系统钥匙串",
+        "✅ Key for {} saved to the system keychain",
+    ),
+    (
+        "✅ {} 结构定义已写入: {:?}",
+        "✅ {} schema written to: {:?}",
+    ),
+    ("✅ 与期望的 CID 一致", "✅ Matches the expected CID"),
+    (
+        "✅ 备份 {}: {} {} 个文件 ({} 字节)，{} 个未变化",
+        "✅ Backup {}: {} {} files ({} bytes), {} unchanged",
+    ),
+    ("✅ 已写入: {:?}", "✅ Written: {:?}"),
+    (
+        "✅ 已写入每个地址的证明: {:?}",
+        "✅ Proofs for every address written: {:?}",
+    ),
+    (
+        "✅ 已导入 {:?} ({}，{} 个 token)，Token URI: {}",
+        "✅ Imported {:?} ({}, {} tokens), Token URI: {}",
+    ),
+    (
+        "✅ 已导出 {} 个 token 的链接: {:?}",
+        "✅ Exported links for {} tokens: {:?}",
+    ),
+    ("✅ 成功连接到 IPFS 节点", "✅ Connected to the IPFS node"),
+    (
+        "✅ 成功连接到 IPFS 节点 (版本: {})",
+        "✅ Connected to the IPFS node (version: {})",
+    ),
+    (
+        "✅ 所有文件与校验文件一致，可以放心重新上传",
+        "✅ All files match the checksum files; safe to re-upload",
+    ),
+    (
+        "✅ 本地准备工作已完成！",
+        "✅ Local preparation is complete!",
+    ),
+    (
+        "✅ 根据合约的 tokenURI，元数据文件名{}",
+        "✅ Based on the contract's tokenURI, metadata file names {}",
+    ),
+    ("✅ 没有已过期的运行", "✅ No expired runs"),
+    (
+        "✅ 清单和图片与签名内容一致",
+        "✅ Manifest and images match the signed content",
+    ),
+    (
+        "✅ 签名有效 ({})，签名者: {}",
+        "✅ Signature valid ({}), signer: {}",
+    ),
+    (
+        "✅ 链上 tokenURI 均指向本次上传的元数据",
+        "✅ All on-chain tokenURIs point to the metadata of this upload",
+    ),
+    ("✍️  已签名 ({}): {:?}", "✍️  Signed ({}): {:?}"),
+    (
+        "❌ CID 不一致: 期望 {}，本地计算为 {}",
+        "❌ CID mismatch: expected {}, computed locally {}",
+    ),
+    (
+        "❌ {} 配置有问题:\n  {}",
+        "❌ {} configuration problems:\n  {}",
+    ),
+    (
+        "❌ [add] nocopy 不能和加密一起使用：节点只记录文件路径，加密后的临时文件上传后会被删除",
+        "❌ [add] nocopy cannot be combined with encryption: the node only records file paths, and the encrypted temporary files are deleted after upload",
+    ),
+    (
+        "❌ {:?} 与校验文件不一致: {} 个内容不同，{} 个缺失，{} 个未记录",
+        "❌ {:?} does not match the checksum files: {} differ, {} missing, {} unlisted",
+    ),
+    ("❌ {:?} 中没有加密文件", "❌ No encrypted files in {:?}"),
+    ("❌ {} 个 pin 请求失败", "❌ {} pin requests failed"),
+    (
+        "❌ {} 个 token 的链上 URI 有问题",
+        "❌ The on-chain URI of {} tokens has problems",
+    ),
+    (
+        "❌ {} 个元数据文件中有 {} 个不符合{}结构",
+        "❌ {1} of {0} metadata files do not match the {2}schema",
+    ),
+    ("❌ {} 个文件镜像失败", "❌ {} files failed to mirror"),
+    ("❌ {} 个根提交失败", "❌ {} roots failed to submit"),
+    (
+        "❌ {} 个运行没有清理完，可以稍后重试",
+        "❌ {} runs were not fully cleaned up; retry later",
+    ),
+    ("❌ {} 项检查未通过", "❌ {} checks failed"),
+    (
+        "❌ 交易 {} 在区块 {} 执行失败，请确认 {} 是合约的 owner",
+        "❌ Transaction {} failed in block {}; make sure {} owns the contract",
+    ),
+    ("❌ 密钥不能为空", "❌ The key must not be empty"),
+    ("❌ 巡检发现 {} 个问题", "❌ Monitoring found {} problems"),
+    ("❌ 标签名不能为空: {}", "❌ Tag name must not be empty: {}"),
+    (
+        "❌ 标签格式应为 key=value: {}",
+        "❌ Tags should be key=value: {}",
+    ),
+    (
+        "❌ 没有签名私钥：请使用 --key-file 或在 [signing] 中设置 key",
+        "❌ No signing key: use --key-file or set key in [signing]",
+    ),
+    (
+        "❌ 没有集合密钥：请使用 --key-file 或在 [encryption] 中设置 key",
+        "❌ No collection key: use --key-file or set key in [encryption]",
+    ),
+    (
+        "❌ 清单或图片在签名后被改动过: {}",
+        "❌ The manifest or images changed after signing: {}",
+    ),
+    (
+        "❌ 签名者是 {}，不是预期的 {}",
+        "❌ The signer is {}, not the expected {}",
+    ),
+    (
+        "❌ 试运行失败，请根据上面的输出修改 {:?}",
+        "❌ Dry run failed; fix {:?} based on the output above",
+    ),
+    (
+        "❌ 请指定要计算 CID 的路径，或使用 cid convert / cid inspect",
+        "❌ Specify a path to compute the CID of, or use cid convert / cid inspect",
+    ),
+    ("❌ 读取失败: {}", "❌ Read failed: {}"),
+    (
+        "❌ 读取密钥文件 {:?} 失败: {}",
+        "❌ Failed to read key file {:?}: {}",
+    ),
+    (
+        "❌ 读取私钥文件 {:?} 失败: {}",
+        "❌ Failed to read private key file {:?}: {}",
+    ),
+    (
+        "❌ 输出目录 {:?} 下没有找到 manifest.json",
+        "❌ No manifest.json found under output directory {:?}",
+    ),
+    (
+        "❌ 这次上传会超出套餐限额",
+        "❌ This upload would exceed the plan limits",
+    ),
+    (
+        "下一步是发布到专业的 Pinning 服务 (如 Pinata):",
+        "Next, publish to a professional pinning service (such as Pinata):",
+    ),
+    ("不带后缀", "have no suffix"),
+    ("严格模式的", "strict "),
+    ("使用 .json 后缀", "use the .json suffix"),
+    ("修改前", "before"),
+    ("修改后", "after"),
+    ("准备输入", "ready to prepare"),
+    ("将上传", "will upload"),
+    ("已过期", "expired"),
+    ("摘要:      {}", "Digest:    {}"),
+    ("无效的路径: {:?}", "Invalid path: {:?}"),
+    ("未过期", "not expired"),
+    ("版本:      v{}", "Version:   v{}"),
+    ("编码:      {}", "Codec:     {}"),
+    ("，已删除运行目录", ", run directory deleted"),
+    (
+        "🌳 白名单 Merkle 根: {} ({} 个地址)",
+        "🌳 Allowlist Merkle root: {} ({} addresses)",
+    ),
+    (
+        "🎨 已生成 {} 张示例图片: {:?}",
+        "🎨 Generated {} demo images: {:?}",
+    ),
+    ("🐢 上传限速: {}/s", "🐢 Upload rate limit: {}/s"),
+    ("🐢 单连接限速: {}/s", "🐢 Per-connection rate limit: {}/s"),
+    (
+        "💡 之后可以运行 `batch` 上传 {:?} 中的图片",
+        "💡 Later you can run `batch` to upload the images in {:?}",
+    ),
+    (
+        "💡 以下地址为空，需要时用 {} 填写",
+        "💡 These addresses are empty; fill them in with {} when needed",
+    ),
+    (
+        "💡 同一目录下没有 manifest.json，只验证了签名本身",
+        "💡 No manifest.json in the same directory; only the signature itself was verified",
+    ),
+    (
+        "💡 在配置文件中添加 [contract] (chain、address) 可以同时导出 OpenSea / LooksRare 链接",
+        "💡 Add [contract] (chain, address) to the config file to also export OpenSea / LooksRare links",
+    ),
+    (
+        "💡 配置文件中没有 Pinning 服务，只在当前节点上固定",
+        "💡 No pinning service in the config file; pinning only on the current node",
+    ),
+    ("📄 使用清单: {:?}", "📄 Using manifest: {:?}"),
+    (
+        "📄 对比报告已写入: {:?}",
+        "📄 Comparison report written to: {:?}",
+    ),
+    ("📄 差异报告已写入: {:?}", "📄 Diff report written to: {:?}"),
+    ("📄 迁移报告: {:?}", "📄 Migration report: {:?}"),
+    (
+        "📊 上传报告已生成: {:?}",
+        "📊 Upload report generated: {:?}",
+    ),
+    (
+        "🔐 上传前加密 ({}，密钥指纹 {})",
+        "🔐 Encrypting before upload ({}, key fingerprint {})",
+    ),
+    ("🔑 请输入 {} 的密钥: ", "🔑 Enter the key for {}: "),
+    (
+        "🔓 已下载并解密 {} 个文件到 {:?}",
+        "🔓 Downloaded and decrypted {} files to {:?}",
+    ),
+    (
+        "🔓 已解密 {} 个文件到 {:?}",
+        "🔓 Decrypted {} files to {:?}",
+    ),
+    ("🔗 市场链接: {} ({})", "🔗 Marketplace link: {} ({})"),
+    ("🕐 传输窗口: {}", "🕐 Transfer window: {}"),
+    (
+        "🖼️  预览页面已生成: {:?}",
+        "🖼️  Preview page generated: {:?}",
+    ),
+    ("🗑️  已删除 {} 的密钥", "🗑️  Deleted the key for {}"),
+    (
+        "🧪 使用内存中的后端，上传的内容不会保存到任何节点",
+        "🧪 Using the in-memory backend; uploaded content is not saved on any node",
+    ),
+    // metadata.rs
+    (
+        "attributes 中有空的 trait_type",
+        "attributes has an empty trait_type",
+    ),
+    (
+        "attributes 中的 {} 重复出现",
+        "{} appears more than once in attributes",
+    ),
+    (
+        "attributes[{}] 中的未知字段: {}",
+        "Unknown field in attributes[{}]: {}",
+    ),
+    (
+        "trait_defaults 中有空的属性名",
+        "trait_defaults has an empty trait name",
+    ),
+    ("{} 不是 ipfs:// 链接: {}", "{} is not an ipfs:// link: {}"),
+    (
+        "❌ 不支持的 JSON 格式: {} (可选: pretty, minified, canonical)",
+        "❌ Unsupported JSON style: {} (options: pretty, minified, canonical)",
+    ),
+    (
+        "❌ 不支持的后缀模式: {} (可选: none, json, auto)",
+        "❌ Unsupported suffix mode: {} (options: none, json, auto)",
+    ),
+    ("❌ 不是有效的 JSON: {}", "❌ Not valid JSON: {}"),
+    (
+        "❌ 元数据结构不正确: {}",
+        "❌ Malformed metadata structure: {}",
+    ),
+    ("不支持的协议 {}: {}", "Unsupported scheme {}: {}"),
+    ("为空", "is empty"),
+    ("元数据应为 JSON 对象", "Metadata should be a JSON object"),
+    ("包含空白字符: {}", "Contains whitespace: {}"),
+    ("未知字段: {}", "Unknown field: {}"),
+    (
+        "缺少协议 (如 ipfs://): {}",
+        "Missing scheme (such as ipfs://): {}",
+    ),
+    ("路径格式错误: {}", "Malformed path: {}"),
+    // metrics.rs
+    (
+        "⚠️  处理指标请求失败: {}",
+        "⚠️  Failed to handle metrics request: {}",
+    ),
+    (
+        "❌ 无法监听指标地址 {}: {}",
+        "❌ Cannot listen on metrics address {}: {}",
+    ),
+    (
+        "📈 Prometheus 指标: http://{}/metrics",
+        "📈 Prometheus metrics: http://{}/metrics",
+    ),
+    // migrate.rs
+    ("   - {}: {} (不变)", "   - {}: {} (unchanged)"),
+    ("(多个文件夹)", "(multiple folders)"),
+    (
+        "\n--- 正在重新上传集合 ---",
+        "\n--- Re-uploading the collection ---",
+    ),
+    ("\n🚚 迁移结果 ({}):", "\n🚚 Migration results ({}):"),
+    (
+        "⚠️  图片不在同一个文件夹中，只固定元数据文件夹；可以使用 --re-add 合并",
+        "⚠️  Images are not in a single folder; pinning only the metadata folder. Use --re-add to merge them",
+    ),
+    (
+        "❌ 元数据 {:?} 中找不到图片地址 {}",
+        "❌ Image address {1} not found in metadata {0:?}",
+    ),
+    (
+        "下一步，请在合约中把 Base URI 更新为新的地址。",
+        "Next, update the Base URI in the contract to the new address.",
+    ),
+    ("📌 已在节点上固定: {}", "📌 Pinned on the node: {}"),
+    ("🔗 Base URI 不变: {}", "🔗 Base URI unchanged: {}"),
+    ("🔗 新 Base URI: {}", "🔗 New Base URI: {}"),
+    ("🔗 旧 Base URI: {}", "🔗 Old Base URI: {}"),
+    // monitor.rs
+    (
+        "\n--- 本轮巡检完成，告警 {} 条 ---",
+        "\n--- Monitoring round finished, {} alerts ---",
+    ),
+    ("\n⏰ 下一次巡检时间: {}", "\n⏰ Next check at: {}"),
+    ("\n🔎 正在检查: {:?}", "\n🔎 Checking: {:?}"),
+    (
+        "{} {} {} (pinned: {}, 网关: {}/{})",
+        "{} {} {} (pinned: {}, gateways: {}/{})",
+    ),
+    ("{} 元数据", "{} metadata"),
+    (
+        "❌ cron 表达式没有后续的执行时间",
+        "❌ The cron expression has no upcoming run time",
+    ),
+    (
+        "❌ 无效的 cron 表达式 `{}`: {}",
+        "❌ Invalid cron expression `{}`: {}",
+    ),
+    (
+        "❌ 输出目录不存在: {:?}",
+        "❌ Output directory does not exist: {:?}",
+    ),
+    ("❌ 重新 pin 失败: {:#}", "❌ Re-pin failed: {:#}"),
+    ("多语言", "localization"),
+    ("封面图", "poster"),
+    (
+        "无法通过网关访问: {}",
+        "Not reachable through the gateway: {}",
+    ),
+    ("未被固定", "not pinned"),
+    ("未被固定，已重新 pin", "not pinned, re-pinned"),
+    ("水印图", "watermarked"),
+    ("预览图", "preview"),
+    (
+        "📌 {} ({}) 未被固定，正在重新 pin...",
+        "📌 {} ({}) is not pinned; re-pinning...",
+    ),
+    // onchain.rs
+    ("     清单: {}", "     Manifest: {}"),
+    ("     链上: {}", "     On-chain: {}"),
+    (
+        "Base URI 指向 {}，清单中是 {}，请检查是否填错或是旧的 CID",
+        "The Base URI points to {}, but the manifest has {}; check for a typo or an old CID",
+    ),
+    (
+        "Base URI 结尾缺少 /",
+        "The Base URI is missing a trailing /",
+    ),
+    (
+        "\n--- 正在检查合约 {} 的 {} 个 token ---",
+        "\n--- Checking {1} tokens of contract {0} ---",
+    ),
+    (
+        "\n🔎 链上检查: {} 个 token，{} 个一致，{} 个有问题",
+        "\n🔎 On-chain check: {} tokens, {} match, {} have problems",
+    ),
+    (
+        "⚠️  tokenURI 不是 \"Base URI + token ID\" 的形式，合约可能不会按元数据文件名读取，请确认合约的拼接方式",
+        "⚠️  tokenURI is not of the form \"Base URI + token ID\"; the contract may not read metadata by file name. Check how the contract builds it",
+    ),
+    (
+        "⚠️  单件清单的 URI 需要 mint 时写入，只检查清单中带 token ID 的条目",
+        "⚠️  The URI of a single manifest is set at mint time; checking only manifest entries with a token ID",
+    ),
+    (
+        "❌ 自动判断 .json 后缀需要在 [contract] 中填写 address 和 rpc_url",
+        "❌ Detecting the .json suffix automatically requires address and rpc_url in [contract]",
+    ),
+    (
+        "❌ 读取本地元数据 {:?} 失败: {}",
+        "❌ Failed to read local metadata {:?}: {}",
+    ),
+    (
+        "❌ 配置文件的 [contract] 段需要填写 address 和 rpc_url",
+        "❌ The [contract] section of the config file needs address and rpc_url",
+    ),
+    (
+        "合约在 tokenURI 后加了 .json 后缀，而元数据文件名不带 .json",
+        "The contract appends .json to tokenURI, but the metadata file names have no .json",
+    ),
+    (
+        "合约拼接 tokenURI 时没有加 .json 后缀，而元数据文件名带 .json",
+        "The contract builds tokenURI without .json, but the metadata file names have .json",
+    ),
+    ("无法通过 IPFS 读取: {}", "Cannot read through IPFS: {}"),
+    ("清单中没有这个 token", "This token is not in the manifest"),
+    (
+        "调用 {} 失败 (token 可能还没有 mint): {}",
+        "Calling {} failed (the token may not be minted yet): {}",
+    ),
+    (
+        "链上 URI 与清单不一致",
+        "The on-chain URI differs from the manifest",
+    ),
+    (
+        "链上 URI 的内容不是 JSON",
+        "The content of the on-chain URI is not JSON",
+    ),
+    (
+        "链上 URI 的内容与本地元数据不同",
+        "The content of the on-chain URI differs from the local metadata",
+    ),
+    ("🔍 合约 tokenURI({}) = {}", "🔍 Contract tokenURI({}) = {}"),
+    // patch.rs
+    ("   ... 另有 {} 个 token", "   ... and {} more tokens"),
+    (
+        "\n📝 {} 个 token 的描述{}，{} 个不变",
+        "\n📝 Descriptions of {} tokens {}, {} unchanged",
+    ),
+    ("\n🔗 旧 URI: {}", "\n🔗 Old URI: {}"),
+    (
+        "⚠️  {} 的 CID 已变化，原来的 Filecoin 归档不再对应，可以重新运行 `filecoin submit`",
+        "⚠️  The CID of {} changed; the previous Filecoin archive no longer matches. You can rerun `filecoin submit`",
+    ),
+    (
+        "⚠️  元数据已变化，原来的 Arweave 元数据镜像不再对应，可以重新运行 `arweave`",
+        "⚠️  The metadata changed; the previous Arweave metadata mirror no longer matches. You can rerun `arweave`",
+    ),
+    (
+        "⚠️  原来的元数据是 IPLD 节点，这次改为以 JSON 文件重新发布",
+        "⚠️  The original metadata was IPLD nodes; republishing as JSON files this time",
+    ),
+    (
+        "✅ 已改写 {} 个本地元数据文件",
+        "✅ Rewrote {} local metadata files",
+    ),
+    (
+        "❌ 元数据 {:?} 格式错误: {}",
+        "❌ Metadata {:?} is malformed: {}",
+    ),
+    (
+        "❌ 单件清单中缺少元数据 CID",
+        "❌ The single manifest is missing the metadata CID",
+    ),
+    (
+        "❌ 读取元数据 {:?} 失败: {}",
+        "❌ Failed to read metadata {:?}: {}",
+    ),
+    (
+        "下一步，请在合约中把 URI 更新为新的地址 (图片 CID 没有变化)。",
+        "Next, update the URI in the contract to the new address (the image CID is unchanged).",
+    ),
+    ("将会修改", "will change"),
+    ("已修改", "changed"),
+    ("🔐 已更新校验文件: {}", "🔐 Updated checksum file: {}"),
+    ("🔗 新 URI: {}", "🔗 New URI: {}"),
+    // poster.rs
+    ("无效的文件名: {:?}", "Invalid file name: {:?}"),
+    (
+        "🖼️  {:?} 是 {}，没有找到 {}/{}.*，已生成占位封面图",
+        "🖼️  {:?} is {}; no {}/{}.* found, generated a placeholder poster",
+    ),
+    // preview.rs
+    (
+        "max_width 和 max_height 必须大于 0",
+        "max_width and max_height must be greater than 0",
+    ),
+    (
+        "quality 应在 1-100 之间: {}",
+        "quality should be between 1 and 100: {}",
+    ),
+    (
+        "❌ 不支持的预览图格式: {} (可选: webp, jpeg)",
+        "❌ Unsupported preview format: {} (options: webp, jpeg)",
+    ),
+    (
+        "❌ 写入预览图 {:?} 失败: {}",
+        "❌ Failed to write preview {:?}: {}",
+    ),
+    (
+        "❌ 解码图片 {:?} 失败: {}",
+        "❌ Failed to decode image {:?}: {}",
+    ),
+    (
+        "🔍 {}: {}x{} → {}x{} 预览图",
+        "🔍 {}: {}x{} → {}x{} preview",
+    ),
+    // provide.rs
+    (
+        "\n--- 正在向 DHT 宣告内容 ---",
+        "\n--- Providing content to the DHT ---",
+    ),
+    (
+        "{} 个根 CID 没有确认可被找到，其它节点和网关可能取不回这些内容 (节点是否以 --offline 运行、是否有可连接的 peer？)",
+        "{} root CIDs could not be confirmed as findable; other nodes and gateways may not be able to fetch them (is the node running with --offline, does it have reachable peers?)",
+    ),
+    (
+        "⚠️  {} {} 已宣告，但 DHT 中还找不到提供者",
+        "⚠️  {} {} provided, but no providers found in the DHT yet",
+    ),
+    (
+        "⚠️  查找 {} 的提供者失败: {:#}",
+        "⚠️  Failed to find providers for {}: {:#}",
+    ),
+    (
+        "✅ 所有根 CID 都已固定并向 DHT 宣告",
+        "✅ All root CIDs are pinned and provided to the DHT",
+    ),
+    ("❌ {} {} 宣告失败: {}", "❌ Providing {} {} failed: {}"),
+    ("水印图片", "watermarked images"),
+    ("📣 {} {} 已宣告", "📣 {} {} provided"),
+    (
+        "📣 {} {} 已宣告，找到 {} 个提供者",
+        "📣 {} {} provided, {} providers found",
+    ),
+    // remote_pin.rs
+    (
+        "\n--- 正在 pin 到 {} ({}) ---",
+        "\n--- Pinning to {} ({}) ---",
+    ),
+    (
+        "✅ {} -> {} 已存在 ({:?})",
+        "✅ {} -> {} already exists ({:?})",
+    ),
+    (
+        "❌ Pinata 返回中没有 pin_size_total: {}",
+        "❌ Pinata response has no pin_size_total: {}",
+    ),
+    (
+        "❌ Pinning 服务 {} 删除 {} 失败 ({}): {}",
+        "❌ Pinning service {} failed to delete {} ({}): {}",
+    ),
+    (
+        "❌ Pinning 服务 {} 请求失败 ({}): {}",
+        "❌ Pinning service {} request failed ({}): {}",
+    ),
+    (
+        "❌ Pinning 服务 {} 返回无法解析: {}",
+        "❌ Cannot parse the response of pinning service {}: {}",
+    ),
+    ("❌ {} -> {} 失败: {:#}", "❌ {} -> {} failed: {:#}"),
+    (
+        "❌ 加入 Pinata Group {} 失败 ({}): {}",
+        "❌ Failed to add to Pinata Group {} ({}): {}",
+    ),
+    (
+        "❌ 配置文件中没有 Pinning 服务 ([[pinning_services]])",
+        "❌ No pinning service in the config file ([[pinning_services]])",
+    ),
+    (
+        "❌ 配置文件中没有名为 {} 的 Pinning 服务",
+        "❌ No pinning service named {} in the config file",
+    ),
+    ("🌐 专属网关: {}", "🌐 Dedicated gateway: {}"),
+    (
+        "🏷️  {} -> {} 已更新名称和标签 ({:?})",
+        "🏷️  {} -> {} name and tags updated ({:?})",
+    ),
+    ("🗂️  已加入 Pinata Group {}", "🗂️  Added to Pinata Group {}"),
+    // remote_source.rs
+    (
+        "--s3 需要 access_key 和 secret_key (可以写成 \"${AWS_ACCESS_KEY_ID}\")",
+        "--s3 needs access_key and secret_key (can be written as \"${AWS_ACCESS_KEY_ID}\")",
+    ),
+    (
+        "\n--- 正在获取远程图片 {} -> {:?} ---",
+        "\n--- Fetching remote images {} -> {:?} ---",
+    ),
+    (
+        "✅ 远程图片: 下载 {} 个 ({})，使用缓存 {} 个，移除 {} 个",
+        "✅ Remote images: downloaded {} ({}), {} from cache, {} removed",
+    ),
+    (
+        "❌ S3 来源应以 s3:// 开头: {}",
+        "❌ An S3 source should start with s3://: {}",
+    ),
+    (
+        "❌ S3 来源缺少 bucket: {}",
+        "❌ The S3 source is missing a bucket: {}",
+    ),
+    (
+        "❌ [remote_source] 配置有问题:\n  {}",
+        "❌ [remote_source] configuration problems:\n  {}",
+    ),
+    (
+        "❌ {:?} 第 {} 行不是 HTTP(S) 地址: {}",
+        "❌ {:?} line {} is not an HTTP(S) address: {}",
+    ),
+    (
+        "❌ {:?} 第 {} 行的地址没有文件名，请在地址后面指定: {}",
+        "❌ The address on {:?} line {} has no file name; specify one after the address: {}",
+    ),
+    (
+        "❌ 下载 {} 失败: HTTP {}",
+        "❌ Downloading {} failed: HTTP {}",
+    ),
+    ("❌ 下载 {} 失败: {}", "❌ Downloading {} failed: {}"),
+    (
+        "❌ 两个来源使用了同一个文件名 {:?}: {} 和 {}，请在 URL 列表中指定不同的文件名",
+        "❌ Two sources use the same file name {:?}: {} and {}; give them different file names in the URL list",
+    ),
+    (
+        "❌ 创建缓存目录 {:?} 失败: {}",
+        "❌ Failed to create cache directory {:?}: {}",
+    ),
+    (
+        "❌ 无效的文件名 {:?} (来自 {})",
+        "❌ Invalid file name {:?} (from {})",
+    ),
+    ("❌ 无效的文件名: {:?}", "❌ Invalid file name: {:?}"),
+    (
+        "❌ 读取 URL 列表 {:?} 失败: {}",
+        "❌ Failed to read URL list {:?}: {}",
+    ),
+    // report.rs
+    (
+        "❌ 不支持的报告格式: {} (可选: markdown, html)",
+        "❌ Unsupported report format: {} (options: markdown, html)",
+    ),
+    // rewrite.rs
+    ("   ... 另有 {} 个字段", "   ... and {} more fields"),
+    (
+        "\n📝 {} 个文件中有 {} 个字段{} ({} 个文件)",
+        "\n📝 {1} fields in {0} files {2} ({3} files)",
+    ),
+    ("\n🔗 新 Base URI: {}", "\n🔗 New Base URI: {}"),
+    ("{}: 不是有效的 JSON ({})", "{}: not valid JSON ({})"),
+    ("{}: 缺少 image 字段", "{}: missing image field"),
+    (
+        "⚠️  没有字段以 --from 指定的前缀开头，请检查前缀是否正确",
+        "⚠️  No field starts with the prefix given by --from; check that the prefix is correct",
+    ),
+    ("✅ 已改写 {} 个元数据文件", "✅ Rewrote {} metadata files"),
+    ("❌ --from 不能为空", "❌ --from must not be empty"),
+    (
+        "❌ 元数据文件夹为空: {:?}",
+        "❌ Metadata folder is empty: {:?}",
+    ),
+    (
+        "❌ 改写后的元数据没有通过检查:\n  {}",
+        "❌ The rewritten metadata failed the checks:\n  {}",
+    ),
+    (
+        "❌ 读取文件夹 {:?} 失败: {}",
+        "❌ Failed to read folder {:?}: {}",
+    ),
+    ("将会改写", "will be rewritten"),
+    ("已改写", "rewritten"),
+    ("📄 已更新清单: {:?}", "📄 Manifest updated: {:?}"),
+    // schema.rs
+    (
+        "❌ 不支持的 schema 格式: {} (可选: jsonschema, typescript, python)",
+        "❌ Unsupported schema format: {} (options: jsonschema, typescript, python)",
+    ),
+    (
+        "❌ 不支持的顶层类型: {} (可选: manifest, metadata)",
+        "❌ Unsupported top-level type: {} (options: manifest, metadata)",
+    ),
+    // secrets.rs
+    (
+        "❌ 保存 {} 的凭据失败: {}",
+        "❌ Failed to save credentials for {}: {}",
+    ),
+    (
+        "❌ 删除 {} 的凭据失败: {}",
+        "❌ Failed to delete credentials for {}: {}",
+    ),
+    (
+        "❌ 无法访问系统钥匙串 ({}): {}",
+        "❌ Cannot access the system keychain ({}): {}",
+    ),
+    ("❌ 未闭合的 ${{...}}: {}", "❌ Unclosed ${{...}}: {}"),
+    (
+        "❌ 环境变量 {} 未设置",
+        "❌ Environment variable {} is not set",
+    ),
+    (
+        "❌ 读取 {} 的凭据失败: {}",
+        "❌ Failed to read credentials for {}: {}",
+    ),
+    (
+        "❌ 钥匙串中没有 {} 的凭据，请先运行 `credentials set {}`",
+        "❌ No credentials for {} in the keychain; run `credentials set {}` first",
+    ),
+    // signing.rs
+    ("ed25519 公钥", "ed25519 public key"),
+    ("ed25519 私钥", "ed25519 private key"),
+    (
+        "❌ ed25519 公钥无效: {}",
+        "❌ Invalid ed25519 public key: {}",
+    ),
+    ("❌ {:?} 格式错误: {}", "❌ {:?} is malformed: {}"),
+    (
+        "❌ {} 应为 {} 字节的十六进制",
+        "❌ {} should be {} bytes of hex",
+    ),
+    (
+        "❌ 不支持的签名方式: {} (可选: ed25519, ethereum)",
+        "❌ Unsupported signing method: {} (options: ed25519, ethereum)",
+    ),
+    (
+        "❌ 无法从签名恢复公钥",
+        "❌ Cannot recover the public key from the signature",
+    ),
+    (
+        "❌ 签名原文格式错误: {}",
+        "❌ The signed payload is malformed: {}",
+    ),
+    ("❌ 签名失败: {}", "❌ Signing failed: {}"),
+    (
+        "❌ 签名无效：原文或签名被改动过，或不是该公钥签署的",
+        "❌ Invalid signature: the payload or signature was modified, or it was not signed by this public key",
+    ),
+    (
+        "❌ 签名无效：签名者是 {}，而不是 {}",
+        "❌ Invalid signature: the signer is {}, not {}",
+    ),
+    ("❌ 签名格式错误: {}", "❌ Malformed signature: {}"),
+    ("❌ 签名的 v 值无效: {}", "❌ Invalid signature v value: {}"),
+    ("签名", "signature"),
+    // staging.rs
+    (
+        "❌ 创建输出目录 {:?} 失败: {}",
+        "❌ Failed to create output directory {:?}: {}",
+    ),
+    (
+        "❌ 删除旧的输出目录 {:?} 失败: {}",
+        "❌ Failed to delete the old output directory {:?}: {}",
+    ),
+    (
+        "❌ 无效的输出目录: {:?}",
+        "❌ Invalid output directory: {:?}",
+    ),
+    (
+        "❌ 移开旧的输出目录 {:?} 失败: {}",
+        "❌ Failed to move the old output directory {:?} aside: {}",
+    ),
+    (
+        "❌ 输出目录 {:?} 在运行期间被创建，结果保留在 {:?}",
+        "❌ Output directory {:?} was created during the run; the results are kept in {:?}",
+    ),
+    (
+        "❌ 输出目录已存在: {:?}，为避免覆盖之前的结果已停止 (确认要覆盖请使用 --force)",
+        "❌ Output directory already exists: {:?}; stopped to avoid overwriting previous results (use --force to overwrite)",
+    ),
+    (
+        "❌ 重命名 {:?} 为 {:?} 失败: {}",
+        "❌ Failed to rename {:?} to {:?}: {}",
+    ),
+    // standards.rs
+    ("creators 中有空的 address", "creators has an empty address"),
+    (
+        "creators 的 share 合计应为 100，当前为 {}",
+        "The creators shares should add up to 100, currently {}",
+    ),
+    (
+        "policy_id 应为 56 位十六进制: \"{}\"",
+        "policy_id should be 56 hex characters: \"{}\"",
+    ),
+    (
+        "seller_fee_basis_points 不能超过 10000: {}",
+        "seller_fee_basis_points must not exceed 10000: {}",
+    ),
+    (
+        "symbol 最多 {} 个字符: {}",
+        "symbol can have at most {} characters: {}",
+    ),
+    (
+        "❌ 不支持的元数据标准: {} (可选: erc721, metaplex, tzip21, cip25)",
+        "❌ Unsupported metadata standard: {} (options: erc721, metaplex, tzip21, cip25)",
+    ),
+    (
+        "名称 \"{}\" 超过 Metaplex 链上限制的 {} 字节，mint 时会失败",
+        "Name \"{}\" exceeds the Metaplex on-chain limit of {} bytes; minting will fail",
+    ),
+    // storage_deal.rs
+    ("    {} 交易 {}: {}{}", "    {} deal {}: {}{}"),
+    (
+        "    等待服务发起交易",
+        "    Waiting for the service to make deals",
+    ),
+    (
+        "\n--- 正在提交 Filecoin 存储交易 ({}: {}) ---",
+        "\n--- Submitting Filecoin storage deals ({}: {}) ---",
+    ),
+    (
+        "⚠️  [{}] 本地打包的 CID {} 与清单中的 {} 不一致，跳过",
+        "⚠️  [{}] The locally packed CID {} differs from {} in the manifest; skipping",
+    ),
+    (
+        "⚠️  [{}] 通过 {} 提交，当前配置为 {}，跳过",
+        "⚠️  [{}] Submitted through {}, but the current config uses {}; skipping",
+    ),
+    (
+        "✅ [{}] {} 已提交过 ({})",
+        "✅ [{}] {} was already submitted ({})",
+    ),
+    ("❌ Lotus {} 失败 ({}): {}", "❌ Lotus {} failed ({}): {}"),
+    ("❌ Lotus {} 没有返回结果", "❌ Lotus {} returned no result"),
+    (
+        "❌ Lotus 导入的根 CID {} 与清单中的 {} 不一致",
+        "❌ The root CID {} imported by Lotus differs from {} in the manifest",
+    ),
+    (
+        "❌ [{}] {} 提交失败: {:#}",
+        "❌ [{}] Submitting {} failed: {:#}",
+    ),
+    ("❌ [{}] 打包失败: {:#}", "❌ [{}] Packing failed: {:#}"),
+    (
+        "❌ [{}] 查询 {} 失败: {:#}",
+        "❌ [{}] Querying {} failed: {:#}",
+    ),
+    (
+        "❌ storacha 返回的根 CID {} 与清单中的 {} 不一致",
+        "❌ The root CID {} returned by storacha differs from {} in the manifest",
+    ),
+    ("❌ {} 请求失败 ({}): {}", "❌ {} request failed ({}): {}"),
+    (
+        "❌ {} 返回无法解析: {}",
+        "❌ Cannot parse the {} response: {}",
+    ),
+    (
+        "❌ 清单中没有 Filecoin 归档记录，请先运行 `filecoin submit`",
+        "❌ The manifest has no Filecoin archive records; run `filecoin submit` first",
+    ),
+    (
+        "🗄️  [{}] {} 已提交 ({} 字节，{} 笔交易)",
+        "🗄️  [{}] {} submitted ({} bytes, {} deals)",
+    ),
+    // throttle.rs
+    (
+        "❌ 无效的速率: {} (例如 500K、2M)",
+        "❌ Invalid rate: {} (e.g. 500K, 2M)",
+    ),
+    (
+        "❌ 速率必须大于 0: {}",
+        "❌ The rate must be greater than 0: {}",
+    ),
+    // unixfs.rs
+    (
+        "fscache 只在 nocopy = true 时有效",
+        "fscache only works with nocopy = true",
+    ),
+    (
+        "inline_limit 必须大于 0",
+        "inline_limit must be greater than 0",
+    ),
+    (
+        "concurrency 必须大于 0",
+        "concurrency must be greater than 0",
+    ),
+    (
+        "⚠️  目录 {:?} 的条目较多，Kubo 会改用 HAMT 分片目录，生成的 CID 可能与 ipfs add 不一致",
+        "⚠️  Directory {:?} has enough entries for Kubo to switch to a HAMT-sharded directory, so the CID may differ from ipfs add",
+    ),
+    ("❌ protobuf 数据不完整", "❌ Truncated protobuf data"),
+    ("❌ varint 数据不完整", "❌ Truncated varint data"),
+    ("❌ varint 过长", "❌ Varint too long"),
+    ("❌ {} 不是文件", "❌ {} is not a file"),
+    (
+        "❌ {} 不是目录，找不到 {}",
+        "❌ {} is not a directory; cannot find {}",
+    ),
+    ("❌ {} 中没有 {}", "❌ {1} not found in {0}"),
+    (
+        "❌ 不支持的 protobuf 类型 {}",
+        "❌ Unsupported protobuf type {}",
+    ),
+    (
+        "❌ 不支持的文件类型: {:?}",
+        "❌ Unsupported file type: {:?}",
+    ),
+    (
+        "❌ 只支持 base32 (b 开头) 的 CIDv1: {}",
+        "❌ Only base32 (starting with b) CIDv1 is supported: {}",
+    ),
+    (
+        "❌ 只支持 sha2-256 的 CIDv1",
+        "❌ Only sha2-256 CIDv1 is supported",
+    ),
+    ("❌ 无效的 CID: {}", "❌ Invalid CID: {}"),
+    ("❌ 路径为空", "❌ Empty path"),
+    // update.rs
+    (
+        "\n📝 {} 个 token 的元数据{}，{} 个不变",
+        "\n📝 Metadata of {} tokens {}, {} unchanged",
+    ),
+    ("\n🔗 旧元数据 CID: {}", "\n🔗 Old metadata CID: {}"),
+    ("remove_field 不能删除 {}", "remove_field cannot remove {}"),
+    (
+        "set_field 不能修改 attributes，请使用 set_attribute",
+        "set_field cannot modify attributes; use set_attribute",
+    ),
+    (
+        "set_field 的 field 不能为空",
+        "The field of set_field must not be empty",
+    ),
+    (
+        "set_field 的 {} 必须是字符串: {}",
+        "{} in set_field must be a string: {}",
+    ),
+    (
+        "⚠️  第 {} 项修改没有选中任何 token，请检查 tokens 范围",
+        "⚠️  Change {} did not select any token; check the tokens range",
+    ),
+    (
+        "❌ 元数据 {:?} 是 Metaplex 结构，修改 image / animation_url 后 properties.files 会不一致",
+        "❌ Metadata {:?} uses the Metaplex structure; changing image / animation_url would make properties.files inconsistent",
+    ),
+    (
+        "❌ 无效的 token 范围: {} (如 \"*\"、\"5-20\"、\"1,3,7-9\")",
+        "❌ Invalid token range: {} (e.g. \"*\", \"5-20\", \"1,3,7-9\")",
+    ),
+    (
+        "❌ 清单与当前的加密设置 ([encryption] / --encrypt) 不一致，重新上传的元数据会与原来的不同",
+        "❌ The manifest does not match the current encryption settings ([encryption] / --encrypt); re-uploaded metadata would differ from the original",
+    ),
+    (
+        "❌ 补丁 {:?} 中没有任何修改 ([[ops]])",
+        "❌ Patch {:?} contains no changes ([[ops]])",
+    ),
+    ("❌ 补丁 {:?} 有问题: {}", "❌ Patch {:?} has problems: {}"),
+    (
+        "❌ 补丁 {:?} 格式错误: {}",
+        "❌ Patch {:?} is malformed: {}",
+    ),
+    (
+        "❌ 读取补丁 {:?} 失败: {}",
+        "❌ Failed to read patch {:?}: {}",
+    ),
+    ("🔗 新元数据 CID: {}", "🔗 New metadata CID: {}"),
+    // urls.rs
+    ("\n🔗 访问链接:", "\n🔗 Links:"),
+    (
+        "网关地址应以 http:// 或 https:// 开头: {:?}",
+        "Gateway addresses should start with http:// or https://: {:?}",
+    ),
+    // usage.rs
+    (
+        "     服务商上已完成的 pin: {} 个",
+        "     Completed pins at the provider: {}",
+    ),
+    (
+        "     服务商统计的总大小: {}",
+        "     Total size reported by the provider: {}",
+    ),
+    (
+        "   - {}: {} 次运行，{} 个根 CID，{} 个文件，{}",
+        "   - {}: {} runs, {} root CIDs, {} files, {}",
+    ),
+    ("\n💰 套餐:", "\n💰 Plans:"),
+    ("\n📦 各服务商的用量:", "\n📦 Usage per provider:"),
+    (
+        "\n📦 输出目录中没有已上传的运行",
+        "\n📦 No uploaded runs in the output directory",
+    ),
+    (
+        "{} {} {}: {:.2} GB / {}，{} 个文件，约 ${:.2}/月",
+        "{} {} {}: {:.2} GB / {}, {} files, about ${:.2}/month",
+    ),
+    ("不限", "unlimited"),
+    // validate.rs
+    (
+        "   ... 另有 {} 个文件不符合规则",
+        "   ... and {} more files break the rules",
+    ),
+    (
+        "CMYK 颜色模式，请转换为 RGB (sRGB)",
+        "CMYK color mode; convert to RGB (sRGB)",
+    ),
+    (
+        "\n--- 正在按 [validation] 的规则检查 {} 个文件 ---",
+        "\n--- Checking {} files against the [validation] rules ---",
+    ),
+    (
+        "formats 中有不支持的格式: {}",
+        "formats contains unsupported formats: {}",
+    ),
+    (
+        "max_file_size_mb 必须大于 0",
+        "max_file_size_mb must be greater than 0",
+    ),
+    (
+        "min_{} ({}) 大于 max_{} ({})",
+        "min_{} ({}) is greater than max_{} ({})",
+    ),
+    ("✅ 所有文件都符合规则", "✅ All files follow the rules"),
+    (
+        "❌ {} / {} 个文件不符合 [validation] 的规则，已在上传前终止",
+        "❌ {} / {} files break the [validation] rules; stopped before upload",
+    ),
+    ("尺寸 {} 不是正方形", "Size {} is not square"),
+    (
+        "尺寸 {} 小于最小尺寸 {}x{}",
+        "Size {} is below the minimum {}x{}",
+    ),
+    (
+        "尺寸 {} 超过最大尺寸 {}x{}",
+        "Size {} exceeds the maximum {}x{}",
+    ),
+    (
+        "文件大小 {} 超过上限 {} MB",
+        "File size {} exceeds the limit of {} MB",
+    ),
+    ("无法读取图片尺寸: {}", "Cannot read image size: {}"),
+    (
+        "格式 {} 不在允许的格式中 ({})",
+        "Format {} is not among the allowed formats ({})",
+    ),
+    // watch.rs
+    ("   - 按 Ctrl-C 退出", "   - Press Ctrl-C to exit"),
+    ("   - 输出目录: {:?}", "   - Output directory: {:?}"),
+    ("\n🆕 检测到新文件: {}", "\n🆕 New file detected: {}"),
+    (
+        "\n📄 清单已更新: 共 {} 个 token，最新 Base URI: {}",
+        "\n📄 Manifest updated: {} tokens in total, latest Base URI: {}",
+    ),
+    ("\n🔁 检测到文件变更: {}", "\n🔁 File change detected: {}"),
+    ("⚠️  文件监听出错: {}", "⚠️  File watcher error: {}"),
+    (
+        "⚠️  跳过 {}: 文件名不是数字 token ID",
+        "⚠️  Skipping {}: the file name is not a numeric token ID",
+    ),
+    (
+        "❌ {:?} 中已有的清单与当前的加密设置 ([encryption]) 不一致",
+        "❌ The existing manifest in {:?} does not match the current encryption settings ([encryption])",
+    ),
+    ("❌ 处理 {:?} 失败: {:#}", "❌ Processing {:?} failed: {:#}"),
+    (
+        "❌ 监听的文件夹不存在: {:?}",
+        "❌ The watched folder does not exist: {:?}",
+    ),
+    ("❌ 跳过 {}: {}", "❌ Skipping {}: {}"),
+    ("无效的文件名", "Invalid file name"),
+    ("无效的文件夹名", "Invalid folder name"),
+    ("👀 开始监听文件夹: {:?}", "👀 Watching folder: {:?}"),
+    (
+        "👋 已停止监听，清单保存在 {:?}",
+        "👋 Stopped watching; manifest saved in {:?}",
+    ),
+    // watermark.rs
+    (
+        "opacity 应在 0-1 之间: {}",
+        "opacity should be between 0 and 1: {}",
+    ),
+    (
+        "scale 应在 0-1 之间: {}",
+        "scale should be between 0 and 1: {}",
+    ),
+    (
+        "❌ 不支持的水印位置: {} (可选: center, bottom-right, bottom-left, top-right, top-left, tile)",
+        "❌ Unsupported watermark position: {} (options: center, bottom-right, bottom-left, top-right, top-left, tile)",
+    ),
+    (
+        "❌ 写入水印图片 {:?} 失败: {}",
+        "❌ Failed to write watermarked image {:?}: {}",
+    ),
+    (
+        "❌ 解码水印图片 {:?} 失败: {}",
+        "❌ Failed to decode watermark image {:?}: {}",
+    ),
+    (
+        "文字水印中有不支持的字符 \"{}\"，请改用 PNG 水印",
+        "The text watermark has an unsupported character \"{}\"; use a PNG watermark instead",
+    ),
+    (
+        "水印图片不存在: {:?}",
+        "Watermark image does not exist: {:?}",
+    ),
+    (
+        "需要填写 image (水印 PNG) 或 text (文字水印)",
+        "image (watermark PNG) or text (text watermark) is required",
+    ),
+    // webhook.rs
+    (
+        "⚠️  发送通知到 {} 失败: {:#}",
+        "⚠️  Failed to send notification to {}: {:#}",
+    ),
+    (
+        "⚠️  无法创建 webhook 客户端: {}",
+        "⚠️  Cannot create the webhook client: {}",
+    ),
+    ("🔔 已发送通知: {}", "🔔 Notification sent: {}"),
+    // window.rs
+    (
+        "⏸️  当前时间 {:02}:{:02} 不在传输窗口 {} 内，约 {} 分钟后继续...",
+        "⏸️  Current time {:02}:{:02} is outside transfer window {}; resuming in about {} minutes...",
+    ),
+    (
+        "▶️  进入传输窗口 {}，继续上传",
+        "▶️  Entered transfer window {}; resuming upload",
+    ),
+    (
+        "❌ 传输窗口 `{}` 的开始和结束时间相同",
+        "❌ Transfer window `{}` has the same start and end time",
+    ),
+    (
+        "❌ 无效的传输窗口 `{}`，格式应为 HH:MM-HH:MM",
+        "❌ Invalid transfer window `{}`; the format should be HH:MM-HH:MM",
+    ),
+    // workflow.rs
+    ("   - 元数据标准: {}", "   - Metadata standard: {}"),
+    ("   - 文件后缀模式: {}", "   - File suffix mode: {}"),
+    (
+        "\n--- ✨ 单件流程完成 ✨ ---",
+        "\n--- ✨ Single workflow finished ✨ ---",
+    ),
+    (
+        "\n--- ✨ 批量流程完成 ✨ ---",
+        "\n--- ✨ Batch workflow finished ✨ ---",
+    ),
+    (
+        "\n--- 正在上传 JSON 对象 ---",
+        "\n--- Uploading JSON object ---",
+    ),
+    (
+        "\n--- 正在为每张图片生成元数据 JSON 文件 ---",
+        "\n--- Generating a metadata JSON file for each image ---",
+    ),
+    (
+        "\n🌐 多语言文件夹 CID 已获取: {} ({})",
+        "\n🌐 Localization folder CID obtained: {} ({})",
+    ),
+    (
+        "\n💾 图片和元数据已在本地打包保存至: {:?}",
+        "\n💾 Image and metadata saved locally to: {:?}",
+    ),
+    (
+        "\n💾 所有图片已复制到: {:?}",
+        "\n💾 All images copied to: {:?}",
+    ),
+    ("\n💾 集合已保存至: {:?}", "\n💾 Collection saved to: {:?}"),
+    (
+        "\n📄 元数据文件夹 CID 已获取: {}",
+        "\n📄 Metadata folder CID obtained: {}",
+    ),
+    (
+        "\n🖼️  {}文件夹 CID 已获取: {}",
+        "\n🖼️  {} folder CID obtained: {}",
+    ),
+    ("\n🖼️  图片 CID 已获取: {}", "\n🖼️  Image CID obtained: {}"),
+    (
+        "\n🖼️  图片文件夹 CID 已获取: {}",
+        "\n🖼️  Image folder CID obtained: {}",
+    ),
+    (
+        "\n🧬 IPLD 元数据索引节点 CID 已获取: {}",
+        "\n🧬 IPLD metadata index node CID obtained: {}",
+    ),
+    (
+        "⚠️  {} 的扩展名对应 {}，但文件内容是 {}，元数据中使用 {}",
+        "⚠️  The extension of {} means {}, but the content is {}; using {} in the metadata",
+    ),
+    (
+        "⚠️  {} 缺少 {} 个 token 的翻译，使用 {} 的文字",
+        "⚠️  {} is missing translations for {} tokens; using the {} text",
+    ),
+    (
+        "⚠️  图片文件夹为空，将按 --allow-empty 生成一个空集合",
+        "⚠️  The image folder is empty; generating an empty collection because of --allow-empty",
+    ),
+    (
+        "⚠️  没有可以加水印的图片 (只支持 PNG / JPEG / WebP)",
+        "⚠️  No images to watermark (only PNG / JPEG / WebP are supported)",
+    ),
+    (
+        "✅ JSON 元数据上传成功!\n   - CID: {}",
+        "✅ JSON metadata uploaded!\n   - CID: {}",
+    ),
+    (
+        "✅ 成功生成 {} 个元数据文件到: {:?}",
+        "✅ Generated {} metadata files in: {:?}",
+    ),
+    (
+        "✏️  已按 [naming] 把 {} 个图片重命名为与元数据相同的名称",
+        "✏️  Renamed {} images to match the metadata names per [naming]",
+    ),
+    (
+        "❌ IPLD 节点没有文件名，不能包在目录中上传，请关闭 [ipld] 或 --wrap",
+        "❌ IPLD nodes have no file name and cannot be wrapped in a directory; turn off [ipld] or --wrap",
+    ),
+    (
+        "❌ {} 是符号链接，[copy] symlinks = \"skip\" 时不会被复制，请改用 follow 或移除该文件",
+        "❌ {} is a symlink and is not copied with [copy] symlinks = \"skip\"; use follow or remove the file",
+    ),
+    (
+        "❌ 图片文件夹为空: {:?}，拒绝生成空集合 (如确有需要请使用 --allow-empty)",
+        "❌ Image folder is empty: {:?}; refusing to generate an empty collection (use --allow-empty if this is intended)",
+    ),
+    (
+        "❌ 解析 IPLD 链接 ipfs://{} 失败: {:#}",
+        "❌ Failed to resolve IPLD link ipfs://{}: {:#}",
+    ),
+    (
+        "❌ 读取文件 {:?} 失败: {}",
+        "❌ Failed to read file {:?}: {}",
+    ),
+    (
+        "下一步，您可以在 mint 函数中使用这个元数据 URI: {}",
+        "Next, you can use this metadata URI in the mint function: {}",
+    ),
+    (
+        "下一步，您可以在合约中将 Base URI 设置为: {}",
+        "Next, you can set the contract's Base URI to: {}",
+    ),
+    ("无", "none"),
+    ("无效的图片文件名", "Invalid image file name"),
+    (
+        "特殊文件 (管道、套接字或设备)",
+        "special files (pipes, sockets or devices)",
+    ),
+    ("符号链接", "symlinks"),
+    ("被过滤的文件", "filtered files"),
+    (
+        "💧 已为 {} 张图片生成加水印的副本: {:?}",
+        "💧 Generated watermarked copies of {} images: {:?}",
+    ),
+    (
+        "💧 水印图片 CID 已获取: {}",
+        "💧 Watermarked image CID obtained: {}",
+    ),
+    (
+        "💧 水印图片文件夹 CID 已获取: {}",
+        "💧 Watermarked image folder CID obtained: {}",
+    ),
+    (
+        "📁 元数据已包在目录中上传!\n   - 目录 CID: {}",
+        "📁 Metadata uploaded inside a directory!\n   - Directory CID: {}",
+    ),
+    ("📂 已复制到: {:?}", "📂 Copied to: {:?}"),
+    (
+        "📎 nocopy: 直接写入输出目录 {:?}，上传后请不要移动或删除其中的文件",
+        "📎 nocopy: writing directly to output directory {:?}; do not move or delete its files after upload",
+    ),
+    (
+        "📷 按 --keep-exif 保留图片中的 EXIF / XMP 信息",
+        "📷 Keeping EXIF / XMP data in the images because of --keep-exif",
+    ),
+    ("🔐 已写入校验文件: {}", "🔐 Checksum file written: {}"),
+    (
+        "🔗 {} 个文件建立为硬链接",
+        "🔗 {} files created as hard links",
+    ),
+    ("🖼️  {} CID 已获取: {}", "🖼️  {} CID obtained: {}"),
+    ("🙈 已跳过 {} 个{}: {}", "🙈 Skipped {} {}: {}"),
+    ("🚀 开始处理单个 NFT...", "🚀 Processing a single NFT..."),
+    (
+        "🚀 开始处理批量 NFT 集合...",
+        "🚀 Processing a batch NFT collection...",
+    ),
+    (
+        "🧬 IPLD 元数据节点 ({}，{} 个 CID 链接): {}",
+        "🧬 IPLD metadata node ({}, {} CID links): {}",
+    ),
+    (
+        "🧹 已删除 {} 个文件中的 EXIF / XMP 等信息",
+        "🧹 Removed EXIF / XMP data from {} files",
+    ),
+];
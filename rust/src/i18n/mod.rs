@@ -0,0 +1,583 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use anyhow::{Result, anyhow};
+
+mod en;
+
+// ✅ 命令行输出使用的语言
+// 消息在源码里都是中文，英文通过 en.rs 的消息表在打印时替换；表里没有的消息保持中文
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Lang {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zh => "zh",
+            Self::En => "en",
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Lang {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "zh" | "zh-cn" | "cn" | "chinese" => Ok(Self::Zh),
+            "en" | "en-us" | "en-gb" | "english" => Ok(Self::En),
+            other => Err(anyhow!("❌ 不支持的语言: {} (可选: zh, en)", other)),
+        }
+    }
+}
+
+static LANG: AtomicU8 = AtomicU8::new(0);
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+// ✅ 只输出 ASCII 标记：emoji 换成 [OK] / [ERROR] / [WARN]，全角标点换成半角，方便日志系统和老终端
+pub fn set_ascii(enabled: bool) {
+    ASCII.store(enabled, Ordering::Relaxed);
+}
+
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+// 一条消息模板：字面部分之间夹着占位符，order[i] 是英文第 i 个占位符对应的中文参数下标
+struct Template {
+    zh: Vec<String>,
+    en: Vec<String>,
+    order: Vec<usize>,
+    weight: usize,
+}
+
+// 把格式字符串拆成字面部分和占位符 ({{ 和 }} 还原成单个括号)，字面部分总比占位符多一个
+fn split_template(template: &str) -> (Vec<String>, Vec<String>) {
+    let mut pieces = vec![String::new()];
+    let mut placeholders = Vec::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                pieces.last_mut().unwrap().push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                pieces.last_mut().unwrap().push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    spec.push(c);
+                }
+                placeholders.push(spec);
+                pieces.push(String::new());
+            }
+            c => pieces.last_mut().unwrap().push(c),
+        }
+    }
+    (pieces, placeholders)
+}
+
+fn templates() -> &'static [Template] {
+    static TEMPLATES: OnceLock<Vec<Template>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        en::MESSAGES
+            .iter()
+            .map(|(zh, en)| {
+                let (zh, _) = split_template(zh);
+                let (en, placeholders) = split_template(en);
+                // 英文占位符写了 {1}、{0:?} 时按下标取参数，否则按顺序
+                let mut next = 0;
+                let order = placeholders
+                    .iter()
+                    .map(|spec| {
+                        let index = spec.split(':').next().unwrap_or("");
+                        match index.parse::<usize>() {
+                            Ok(index) => index,
+                            Err(_) => {
+                                next += 1;
+                                next - 1
+                            }
+                        }
+                    })
+                    .collect();
+                let weight = zh.iter().map(|piece| piece.chars().count()).sum();
+                Template {
+                    zh,
+                    en,
+                    order,
+                    weight,
+                }
+            })
+            .filter(|template| template.weight > 0)
+            .collect()
+    })
+}
+
+fn has_cjk(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}'))
+}
+
+// 按模板的字面部分切出消息里各个占位符的实际内容，中间部分取最左边的匹配
+fn match_args<'a>(pieces: &[String], message: &'a str) -> Option<Vec<&'a str>> {
+    let (first, rest) = pieces.split_first()?;
+    if rest.is_empty() {
+        return (message == first).then(Vec::new);
+    }
+    let (last, middle) = rest.split_last()?;
+    let body = message.strip_prefix(first.as_str())?;
+    let body = body.strip_suffix(last.as_str())?;
+    let mut args = Vec::with_capacity(rest.len());
+    let mut remaining = body;
+    for piece in middle {
+        let at = remaining.find(piece.as_str())?;
+        args.push(&remaining[..at]);
+        remaining = &remaining[at + piece.len()..];
+    }
+    args.push(remaining);
+    Some(args)
+}
+
+// 整条消息匹配一个模板，字面部分越长越优先；参数原样保留，
+// 不翻译其中的中文 (可能是用户的文件名、路径或 token 名称)
+fn translate_message(message: &str) -> Option<String> {
+    let mut best: Option<(&Template, Vec<&str>)> = None;
+    for template in templates() {
+        if best
+            .as_ref()
+            .is_some_and(|(b, _)| b.weight >= template.weight)
+        {
+            continue;
+        }
+        if let Some(args) = match_args(&template.zh, message) {
+            best = Some((template, args));
+        }
+    }
+    let (template, args) = best?;
+    let mut out = String::with_capacity(message.len());
+    for (i, piece) in template.en.iter().enumerate() {
+        out.push_str(piece);
+        if let Some(index) = template.order.get(i) {
+            out.push_str(args.get(*index).copied().unwrap_or(""));
+        }
+    }
+    Some(out)
+}
+
+// ✅ 把一条已经格式化好的中文消息翻译成当前语言；找不到时原样返回
+pub fn translate(message: &str) -> Cow<'_, str> {
+    translate_to(lang(), message)
+}
+
+fn translate_to(lang: Lang, message: &str) -> Cow<'_, str> {
+    if lang == Lang::Zh || !has_cjk(message) {
+        return Cow::Borrowed(message);
+    }
+    if let Some(translated) = translate_message(message) {
+        return Cow::Owned(translated);
+    }
+    // 多行消息 (如问题列表) 整体匹配不上时逐行翻译，保留每行的缩进
+    if message.contains('\n') {
+        let lines: Vec<String> = message
+            .split('\n')
+            .map(|line| {
+                let trimmed = line.trim_start();
+                let indent = &line[..line.len() - trimmed.len()];
+                match translate_message(line) {
+                    Some(translated) => translated,
+                    None => match translate_message(trimmed) {
+                        Some(translated) => format!("{}{}", indent, translated),
+                        None => line.to_string(),
+                    },
+                }
+            })
+            .collect();
+        return Cow::Owned(lines.join("\n"));
+    }
+    Cow::Borrowed(message)
+}
+
+// emoji 对应的 ASCII 标记，其余 emoji 统一写成 *
+fn ascii_tag(c: char) -> Option<&'static str> {
+    match c {
+        '✅' | '✔' => Some("[OK]"),
+        '❌' | '✖' => Some("[ERROR]"),
+        '⚠' => Some("[WARN]"),
+        '💡' => Some("[TIP]"),
+        '\u{2300}'..='\u{23ff}'
+        | '\u{25a0}'..='\u{25ff}'
+        | '\u{2600}'..='\u{27bf}'
+        | '\u{2b00}'..='\u{2bff}'
+        | '\u{1f000}'..='\u{1faff}' => Some("*"),
+        _ => None,
+    }
+}
+
+fn ascii_punct(c: char) -> Option<&'static str> {
+    match c {
+        '→' => Some("->"),
+        '←' => Some("<-"),
+        '—' | '–' | '·' => Some("-"),
+        '…' => Some("..."),
+        '，' | '、' => Some(", "),
+        '。' => Some(". "),
+        '：' => Some(": "),
+        '；' => Some("; "),
+        '！' => Some("! "),
+        '？' => Some("? "),
+        '（' => Some(" ("),
+        '）' => Some(") "),
+        '【' | '「' => Some("["),
+        '】' | '」' => Some("]"),
+        '“' | '”' => Some("\""),
+        '‘' | '’' => Some("'"),
+        '×' => Some("x"),
+        _ => None,
+    }
+}
+
+// ✅ 去掉 emoji 和全角标点；emoji 后面跟着的多个空格合并成一个
+pub fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(tag) = ascii_tag(c) {
+            while chars.peek() == Some(&'\u{fe0f}') {
+                chars.next();
+            }
+            out.push_str(tag);
+            if chars.peek() == Some(&' ') {
+                while chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                out.push(' ');
+            }
+        } else if c == '\u{fe0f}' || c == '\u{200d}' {
+            continue;
+        } else if let Some(punct) = ascii_punct(c) {
+            // 全角标点自带的间距换成半角空格，行尾和空白前不再补空格
+            out.push_str(punct.trim_end());
+            let spaced = chars.peek().is_some_and(|next| !next.is_whitespace());
+            if punct.ends_with(' ') && spaced {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// ✅ 打印前的最后一步：翻译，再按需转成 ASCII
+pub fn localize(message: &str) -> Cow<'_, str> {
+    let translated = translate(message);
+    if ascii() {
+        Cow::Owned(to_ascii(&translated))
+    } else {
+        translated
+    }
+}
+
+// ✅ 替换标准库的打印宏：lib.rs 里最先声明本模块，之后的模块和 main.rs 里的 println! 都经过
+// verbosity::emit (按 -q / -v 过滤、翻译、上色)
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::verbosity::emit("", false, true)
+    };
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit(&::std::format!($($arg)*), false, true)
+    };
+}
+
+#[macro_export]
+macro_rules! eprintln {
+    () => {
+        $crate::verbosity::emit("", true, true)
+    };
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit(&::std::format!($($arg)*), true, true)
+    };
+}
+
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit(&::std::format!($($arg)*), false, false)
+    };
+}
+
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit(&::std::format!($($arg)*), true, false)
+    };
+}
+
+// ✅ -v 时才输出的诊断信息 (写到标准错误)
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit_at($crate::verbosity::Verbosity::Verbose, &::std::format!($($arg)*))
+    };
+}
+
+// ✅ -vv 时才输出的诊断信息，如每个节点请求 (写到标准错误)
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::verbosity::emit_at($crate::verbosity::Verbosity::Trace, &::std::format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    // 按 Rust 的转义规则还原字符串字面量的内容；行尾的 \ 连同下一行开头的空白一起跳过
+    fn unescape(raw: &str) -> String {
+        let mut out = String::with_capacity(raw.len());
+        let mut chars = raw.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('0') => out.push('\0'),
+                Some('u') => {
+                    let code: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                    out.extend(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32));
+                }
+                Some('x') => {
+                    let code: String = chars.by_ref().take(2).collect();
+                    out.extend(u8::from_str_radix(&code, 16).ok().map(char::from));
+                }
+                Some('\n') => {
+                    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                }
+                Some(other) => out.push(other),
+                None => {}
+            }
+        }
+        out
+    }
+
+    // 取出一个源文件里所有的字符串字面量 (普通、raw、byte)，跳过注释和字符字面量
+    fn string_literals(source: &str) -> Vec<String> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut literals = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '/' if chars.get(i + 1) == Some(&'/') => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    i += 2;
+                    while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                        i += 1;
+                    }
+                    i += 2;
+                }
+                '\'' => {
+                    // 'a' 和 '\n' 是字符字面量，'a 是生命周期
+                    if chars.get(i + 1) == Some(&'\\') {
+                        i += 2;
+                        while i < chars.len() && chars[i] != '\'' {
+                            i += 1;
+                        }
+                        i += 1;
+                    } else if chars.get(i + 2) == Some(&'\'') {
+                        i += 3;
+                    } else {
+                        i += 1;
+                    }
+                }
+                'r' if !chars[..i]
+                    .last()
+                    .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+                    && matches!(chars.get(i + 1), Some('"' | '#')) =>
+                {
+                    let mut hashes = 0;
+                    let mut j = i + 1;
+                    while chars.get(j) == Some(&'#') {
+                        hashes += 1;
+                        j += 1;
+                    }
+                    if chars.get(j) != Some(&'"') {
+                        i += 1;
+                        continue;
+                    }
+                    let start = j + 1;
+                    let mut end = start;
+                    while end < chars.len()
+                        && !(chars[end] == '"'
+                            && (1..=hashes).all(|k| chars.get(end + k) == Some(&'#')))
+                    {
+                        end += 1;
+                    }
+                    literals.push(chars[start..end.min(chars.len())].iter().collect());
+                    i = end + 1 + hashes;
+                }
+                '"' => {
+                    let start = i + 1;
+                    let mut end = start;
+                    while end < chars.len() && chars[end] != '"' {
+                        if chars[end] == '\\' {
+                            end += 1;
+                        }
+                        end += 1;
+                    }
+                    let raw: String = chars[start..end.min(chars.len())].iter().collect();
+                    literals.push(unescape(&raw));
+                    i = end + 1;
+                }
+                _ => i += 1,
+            }
+        }
+        literals
+    }
+
+    fn collect_literals(dir: &Path, literals: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_literals(&path, literals);
+            } else if path.extension().is_some_and(|ext| ext == "rs")
+                && !path.ends_with("i18n/en.rs")
+            {
+                literals.extend(string_literals(&fs::read_to_string(&path).unwrap()));
+            }
+        }
+    }
+
+    // 消息表的每个中文键都还是源码里的某个格式字符串；改了中文没同步 en.rs 时这里会失败
+    #[test]
+    fn every_template_is_used_in_source() {
+        let mut literals = Vec::new();
+        collect_literals(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut literals,
+        );
+        let literals: std::collections::HashSet<String> = literals.into_iter().collect();
+        let stale: Vec<&str> = en::MESSAGES
+            .iter()
+            .map(|(zh, _)| *zh)
+            .filter(|zh| !literals.contains(*zh))
+            .collect();
+        assert!(stale.is_empty(), "源码里找不到这些消息:\n{:#?}", stale);
+    }
+
+    // 中英文的占位符数量一致，调换顺序时的下标不越界
+    #[test]
+    fn placeholders_match() {
+        for (zh, en) in en::MESSAGES {
+            let (_, zh_args) = split_template(zh);
+            let (_, en_args) = split_template(en);
+            assert_eq!(zh_args.len(), en_args.len(), "{:?} -> {:?}", zh, en);
+        }
+        for template in templates() {
+            let args = template.zh.len() - 1;
+            assert!(template.order.iter().all(|index| *index < args));
+        }
+    }
+
+    // 格式化后的消息能匹配回模板，参数原样保留 (参数里的中文也不翻译)
+    #[test]
+    fn formatted_messages_round_trip() {
+        let cases = [
+            ("✅ 上传成功!", "✅ Upload succeeded!"),
+            (
+                "❌ 上传失败: connection refused",
+                "❌ Upload failed: connection refused",
+            ),
+            ("   - 名称: MetaCore #1", "   - Name: MetaCore #1"),
+            (
+                "❌ 不支持的语言: fr (可选: zh, en)",
+                "❌ Unsupported language: fr (choices: zh, en)",
+            ),
+            // 英文调换了参数顺序
+            (
+                "🪞 已在节点 node-a 上 pin bafy",
+                "🪞 Pinned bafy on node node-a",
+            ),
+            // 参数是用户的中文名称或路径，即使和某条消息相同也不翻译
+            ("   - 名称: 上传成功!", "   - Name: 上传成功!"),
+            (
+                "❌ 上传失败: ./图片/✅ 上传成功!",
+                "❌ Upload failed: ./图片/✅ 上传成功!",
+            ),
+            // 多行消息逐行翻译，保留缩进
+            (
+                "✅ 上传成功!\n   - 名称: a",
+                "✅ Upload succeeded!\n   - Name: a",
+            ),
+            ("没有对应英文的消息", "没有对应英文的消息"),
+            ("plain ascii", "plain ascii"),
+        ];
+        for (zh, en) in cases {
+            assert_eq!(translate_to(Lang::En, zh), en);
+            assert_eq!(translate_to(Lang::Zh, zh), zh);
+        }
+        // 表里的每条模板用示例参数格式化后都能翻译出对应的英文
+        for (zh, en) in en::MESSAGES {
+            let (zh_pieces, _) = split_template(zh);
+            if zh_pieces.iter().all(|piece| piece.is_empty()) {
+                continue;
+            }
+            let (en_pieces, _) = split_template(en);
+            let message = zh_pieces.join("42");
+            let translated = translate_to(Lang::En, &message);
+            for piece in en_pieces {
+                assert!(
+                    translated.contains(piece.as_str()),
+                    "{:?} 翻译成 {:?}，缺少 {:?}",
+                    message,
+                    translated,
+                    piece
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use toml::Value;
+
+use crate::metadata::JsonSuffix;
+
+const DEFAULT_NAME: &str = "MetaCore";
+const DEFAULT_DESCRIPTION: &str = "{name} 是 MetaCore 集合中的一个独特成员。";
+const DEFAULT_IMAGES_DIR: &str = "./images";
+const DEFAULT_KUBO_API: &str = "http://127.0.0.1:5001";
+const PINATA_ENDPOINT: &str = "https://api.pinata.cloud/psa";
+
+// ✅ 向导中可以选择的上传方式
+const PROVIDERS: [(&str, &str); 4] = [
+    ("local", "本机的 ipfs 命令行 (默认)"),
+    ("kubo", "远程 Kubo 节点的 HTTP API"),
+    ("pinata", "Pinata (Pinning Service API)"),
+    ("filebase", "Filebase 的 IPFS bucket"),
+];
+
+// ✅ init 向导的回答
+#[derive(Debug, Clone)]
+pub struct InitAnswers {
+    pub name: String,
+    pub description: String,
+    pub images_dir: PathBuf,
+    pub json_suffix: JsonSuffix,
+    // 按 ERC-1155 的 {id} 规则命名元数据文件
+    pub erc1155: bool,
+    pub providers: Vec<String>,
+    pub kubo_api: Option<String>,
+    // 写入配置后用内存后端试运行一次
+    pub dry_run: bool,
+}
+
+// 打印问题并读取一行，输入已结束时返回空字符串
+fn read_answer(input: &mut impl BufRead, prompt: &str) -> Result<String> {
+    print!("❓ {}: ", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+// 直接回车时使用默认值
+fn ask(input: &mut impl BufRead, question: &str, default: &str) -> Result<String> {
+    let answer = read_answer(input, &format!("{} [{}]", question, default))?;
+    Ok(if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer
+    })
+}
+
+fn confirm(input: &mut impl BufRead, question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    loop {
+        match read_answer(input, &format!("{} [{}]", question, hint))?
+            .to_lowercase()
+            .as_str()
+        {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("⚠️  请输入 y 或 n"),
+        }
+    }
+}
+
+// 按提示反复询问，直到回答能被 parse 接受
+fn ask_parsed<T>(
+    input: &mut impl BufRead,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    loop {
+        match parse(&ask(input, question, default)?) {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("⚠️  {}", e.to_string().trim_start_matches("❌ ")),
+        }
+    }
+}
+
+fn parse_standard(answer: &str) -> Result<bool> {
+    match answer
+        .to_lowercase()
+        .trim_start_matches("erc")
+        .trim_start_matches('-')
+    {
+        "721" => Ok(false),
+        "1155" => Ok(true),
+        _ => Err(anyhow!("❌ 请输入 721 或 1155")),
+    }
+}
+
+fn parse_providers(answer: &str) -> Result<Vec<String>> {
+    let mut providers = Vec::new();
+    for provider in answer.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let provider = provider.to_lowercase();
+        if !PROVIDERS.iter().any(|(name, _)| *name == provider) {
+            return Err(anyhow!(
+                "❌ 未知的上传方式: {} (可选 {})",
+                provider,
+                PROVIDERS.map(|(name, _)| name).join("、")
+            ));
+        }
+        if !providers.contains(&provider) {
+            providers.push(provider);
+        }
+    }
+    if providers.is_empty() {
+        return Err(anyhow!("❌ 至少选择一种上传方式"));
+    }
+    Ok(providers)
+}
+
+// ✅ 逐项询问集合名称、描述模板、图片文件夹、文件名后缀、标准和上传方式
+pub fn ask_answers(input: &mut impl BufRead) -> Result<InitAnswers> {
+    println!("\n--- 创建配置文件：直接回车使用括号中的默认值 ---");
+    let name = ask(input, "集合名称", DEFAULT_NAME)?;
+    println!("   描述中可以使用 {{name}}、{{token_id}} 和 {{image}}");
+    let description = ask(input, "描述模板", DEFAULT_DESCRIPTION)?;
+    let images_dir = PathBuf::from(ask(input, "图片文件夹", DEFAULT_IMAGES_DIR)?);
+    if !images_dir.is_dir() {
+        println!(
+            "⚠️  {:?} 还不存在，上传前请把图片放进这个文件夹",
+            images_dir
+        );
+    }
+    let erc1155 = ask_parsed(input, "合约标准 (721 / 1155)", "721", parse_standard)?;
+    // ERC-1155 的 uri 通常以 {id}.json 结尾，ERC-721 的 Base URI 通常直接拼接 token ID
+    let json_suffix = ask_parsed(
+        input,
+        "元数据文件名后缀 (none / json / auto)",
+        if erc1155 { "json" } else { "none" },
+        |answer| answer.parse::<JsonSuffix>().map_err(|e| anyhow!(e)),
+    )?;
+    for (provider, label) in PROVIDERS {
+        println!("   - {:<9} {}", provider, label);
+    }
+    let providers = ask_parsed(
+        input,
+        "上传方式 (可用逗号分隔多个)",
+        "local",
+        parse_providers,
+    )?;
+    let kubo_api = if providers.iter().any(|p| p == "kubo") {
+        Some(ask(input, "Kubo API 地址", DEFAULT_KUBO_API)?)
+    } else {
+        None
+    };
+    let dry_run = confirm(input, "写入后用内存后端试运行一次吗", true)?;
+    Ok(InitAnswers {
+        name,
+        description,
+        images_dir,
+        json_suffix,
+        erc1155,
+        providers,
+        kubo_api,
+        dry_run,
+    })
+}
+
+// TOML 字符串；配置中的 ${ 会被当作变量引用，字面的 ${ 要写成 $${
+fn quote(s: &str) -> String {
+    Value::String(s.replace("${", "$${")).to_string()
+}
+
+// ✅ 由回答生成 config.toml，只写向导涉及的配置项
+pub fn render_config(answers: &InitAnswers) -> String {
+    let mut out = String::from("# 由 `init` 生成，其它配置项见 docs/readme.md\n\n");
+    out += &format!("json_suffix = {}\n", quote(answers.json_suffix.as_str()));
+    if answers.erc1155 {
+        out += "# ERC-1155 的元数据结构与 ERC-721 相同\n";
+    }
+    out += "metadata_standard = \"erc721\"\n";
+
+    out += "\n[collection]\n";
+    out += &format!("name = {}\n", quote(&answers.name));
+    out += &format!("description = {}\n", quote(&answers.description));
+    out += &format!(
+        "images_dir = {}\n",
+        quote(&answers.images_dir.to_string_lossy())
+    );
+
+    if answers.erc1155 {
+        out += "\n# ERC-1155 客户端把 {id} 替换为 64 位补零的小写十六进制 token ID\n";
+        out += "[naming]\npad_width = 64\nhex = true\n";
+    }
+
+    let has = |provider: &str| answers.providers.iter().any(|p| p == provider);
+    if let Some(api) = &answers.kubo_api {
+        // 同时选择了本机时，本机节点在前，失败后切换到远程节点
+        let mut nodes = Vec::new();
+        if has("local") {
+            nodes.push(quote(DEFAULT_KUBO_API));
+        }
+        nodes.push(quote(api));
+        out += "\n[failover]\n";
+        out += &format!("nodes = [{}]\n", nodes.join(", "));
+        out += "\n# 节点需要认证时填写 basic_auth 或 headers\n";
+        out += &format!("[[api_endpoints]]\nurl = {}\n", quote(api));
+    }
+    if has("pinata") {
+        out += "\n[[pinning_services]]\nname = \"pinata\"\n";
+        out += &format!("endpoint = {}\n", quote(PINATA_ENDPOINT));
+        out += "# 留空时从系统钥匙串读取，也可以写成 \"${PINATA_JWT}\"\n";
+        out += "access_token = \"\"\n";
+    }
+    if has("filebase") {
+        out += "\n# bucket 通过 --filebase 指定，密钥默认读取 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY\n";
+        out += "[filebase]\nprefix = \"\"\n";
+    }
+    out
+}
+
+// ✅ 交互式创建配置文件；已存在时需要确认 (或 --force) 才会覆盖，返回回答
+pub fn run_init(path: &Path, force: bool) -> Result<Option<InitAnswers>> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    if path.exists()
+        && !force
+        && !confirm(&mut input, &format!("{:?} 已存在，覆盖吗", path), false)?
+    {
+        println!("👋 已取消，配置文件没有改动");
+        return Ok(None);
+    }
+    let answers = ask_answers(&mut input)?;
+    let config = render_config(&answers);
+    // 写入前确认生成的内容可以被正常读取
+    toml::from_str::<toml::Table>(&config).map_err(|e| anyhow!("❌ 生成的配置格式错误: {}", e))?;
+    fs::write(path, config).map_err(|e| anyhow!("❌ 写入 {:?} 失败: {}", path, e))?;
+    println!("\n✅ 配置已写入: {:?}", path);
+    let has = |provider: &str| answers.providers.iter().any(|p| p == provider);
+    if has("pinata") {
+        println!("💡 运行 `credentials set pinata` 保存 Pinata 的 JWT");
+    }
+    if has("filebase") {
+        println!(
+            "💡 Filebase 的密钥放在 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY 中，上传时加上 --filebase <bucket>"
+        );
+    }
+    Ok(Some(answers))
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::clone::{parse_ipfs_path, uri_ipfs_path};
+use crate::estimate::format_bytes;
+
+// 超过这个大小的文本只显示开头
+const PREVIEW_LIMIT: usize = 64 * 1024;
+
+// CID、ipfs://、/ipfs/ 或网关地址转换为 `<CID>/<子路径>`
+pub fn inspect_path(input: &str) -> Result<String> {
+    match uri_ipfs_path(input) {
+        Some(path) => Ok(path),
+        None => parse_ipfs_path(input),
+    }
+}
+
+// ls 结果中的一行
+#[derive(Debug, Clone)]
+pub struct TreeLine {
+    pub depth: usize,
+    pub entry: DirEntry,
+    // 目录为其中所有文件的大小之和
+    pub total: u64,
+}
+
+// 递归读取目录，返回目录中所有文件的大小之和；只记录不超过 max_depth 层的行
+fn walk(
+    backend: &dyn IpfsBackend,
+    path: &str,
+    depth: usize,
+    max_depth: usize,
+    lines: &mut Vec<TreeLine>,
+) -> Result<u64> {
+    let mut total = 0;
+    for entry in backend.ls(path)? {
+        let index = lines.len();
+        if depth < max_depth {
+            lines.push(TreeLine {
+                depth,
+                entry: entry.clone(),
+                total: entry.size,
+            });
+        }
+        let size = if entry.is_dir {
+            walk(backend, &entry.cid, depth + 1, max_depth, lines)?
+        } else {
+            entry.size
+        };
+        if depth < max_depth {
+            lines[index].total = size;
+        }
+        total += size;
+    }
+    Ok(total)
+}
+
+// ✅ 列出目录 DAG：名称、大小、子 CID，以及整个目录的文件总大小
+pub fn list_tree(
+    backend: &dyn IpfsBackend,
+    path: &str,
+    recursive: bool,
+) -> Result<(Vec<TreeLine>, u64)> {
+    let mut lines = Vec::new();
+    let max_depth = if recursive { usize::MAX } else { 1 };
+    let total = walk(backend, path, 0, max_depth, &mut lines)?;
+    Ok((lines, total))
+}
+
+pub fn print_tree(path: &str, lines: &[TreeLine], total: u64) {
+    if lines.is_empty() {
+        println!("📄 {} 没有目录项 (可能是文件，可以用 cat 查看)", path);
+        return;
+    }
+    let width = lines
+        .iter()
+        .map(|line| line.depth * 2 + line.entry.name.chars().count() + 1)
+        .max()
+        .unwrap_or(0);
+    println!("📂 {}", path);
+    for line in lines {
+        let name = if line.entry.is_dir {
+            format!("{}/", line.entry.name)
+        } else {
+            line.entry.name.clone()
+        };
+        let label = format!("{}{}", "  ".repeat(line.depth), name);
+        println!(
+            "  {:<width$}  {:>10}  {}",
+            label,
+            format_bytes(line.total),
+            line.entry.cid,
+            width = width
+        );
+    }
+    let top_level = lines.iter().filter(|line| line.depth == 0).count();
+    println!("共 {} 项，文件总大小 {}", top_level, format_bytes(total));
+}
+
+// ✅ 读取文件：JSON 格式化后显示，其它文本原样显示，二进制只显示类型和大小；out 不为空时原样保存
+pub fn cat_file(backend: &dyn IpfsBackend, path: &str, out: Option<&Path>) -> Result<()> {
+    let data = backend.cat(path)?;
+    if let Some(out) = out {
+        fs::write(out, &data).map_err(|e| anyhow!("❌ 写入 {:?} 失败: {}", out, e))?;
+        println!(
+            "💾 已保存 {} ({}) 到 {:?}",
+            path,
+            format_bytes(data.len() as u64),
+            out
+        );
+        return Ok(());
+    }
+    if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&data) {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    match std::str::from_utf8(&data) {
+        Ok(text) if text.len() > PREVIEW_LIMIT => {
+            let end = (0..=PREVIEW_LIMIT)
+                .rev()
+                .find(|&i| text.is_char_boundary(i))
+                .unwrap_or(0);
+            println!("{}", &text[..end]);
+            println!(
+                "... (共 {}，只显示了开头，可以用 --out 保存完整内容)",
+                format_bytes(data.len() as u64)
+            );
+        }
+        Ok(text) => print!("{}", text),
+        Err(_) => {
+            let kind = infer::get(&data)
+                .map(|kind| kind.mime_type())
+                .unwrap_or("application/octet-stream");
+            println!(
+                "📦 {} 是二进制内容 ({}，{})，可以用 --out 保存到文件",
+                path,
+                kind,
+                format_bytes(data.len() as u64)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::cid::CidInfo;
+
+pub const DAG_CBOR: u64 = 0x71;
+pub const DAG_JSON: u64 = 0x0129;
+
+// dag-cbor 中表示 CID 链接的 tag
+const CID_TAG: u64 = 42;
+
+// ✅ IPLD 元数据节点的存储编码
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DagCodec {
+    #[default]
+    #[serde(rename = "dag-cbor")]
+    DagCbor,
+    #[serde(rename = "dag-json")]
+    DagJson,
+}
+
+impl DagCodec {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DagCbor => "dag-cbor",
+            Self::DagJson => "dag-json",
+        }
+    }
+
+    pub fn code(&self) -> u64 {
+        match self {
+            Self::DagCbor => DAG_CBOR,
+            Self::DagJson => DAG_JSON,
+        }
+    }
+}
+
+impl fmt::Display for DagCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DagCodec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dag-cbor" => Ok(Self::DagCbor),
+            "dag-json" => Ok(Self::DagJson),
+            _ => Err(anyhow!(
+                "❌ 未知的 IPLD 编码: {} (可选 dag-cbor、dag-json)",
+                s
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [ipld] 段：元数据以 IPLD 节点 (`ipfs dag put`) 发布，图片等链接为真正的 CID 链接
+//
+// [ipld]
+// enabled = true
+// codec = "dag-cbor"
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct IpldConfig {
+    pub enabled: bool,
+    pub codec: DagCodec,
+}
+
+// dag-json 中的 CID 链接: {"/": "<cid>"}
+pub fn link(cid: &str) -> Value {
+    json!({ "/": cid })
+}
+
+fn as_link(map: &Map<String, Value>) -> Option<&str> {
+    match (map.len(), map.get("/")) {
+        (1, Some(Value::String(cid))) => Some(cid),
+        _ => None,
+    }
+}
+
+// ✅ 把文档中的 ipfs:// 链接换成 CID 链接；resolve 把 `<CID>` 或 `<CID>/<子路径>` 解析为文件自己的 CID，
+// 解析不了的 (如外部链接) 保持字符串。返回替换的数量
+pub fn linkify(
+    value: &mut Value,
+    resolve: &mut dyn FnMut(&str) -> Result<Option<String>>,
+) -> Result<usize> {
+    match value {
+        Value::String(s) => {
+            let Some(path) = s.strip_prefix("ipfs://") else {
+                return Ok(0);
+            };
+            match resolve(path.trim_end_matches('/'))? {
+                Some(cid) => {
+                    *value = link(&cid);
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+        Value::Array(items) => {
+            let mut count = 0;
+            for item in items {
+                count += linkify(item, resolve)?;
+            }
+            Ok(count)
+        }
+        Value::Object(map) => {
+            let mut count = 0;
+            for item in map.values_mut() {
+                count += linkify(item, resolve)?;
+            }
+            Ok(count)
+        }
+        _ => Ok(0),
+    }
+}
+
+fn put_header(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend([major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(value.to_be_bytes());
+        }
+    }
+}
+
+fn put_value(out: &mut Vec<u8>, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                put_header(out, 0, n);
+            } else if let Some(n) = n.as_i64() {
+                put_header(out, 1, (-1 - n) as u64);
+            } else {
+                // dag-cbor 的浮点数一律使用 64 位
+                let n = n.as_f64().ok_or_else(|| anyhow!("❌ 无效的数字: {}", n))?;
+                out.push(0xfb);
+                out.extend(n.to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            put_header(out, 3, s.len() as u64);
+            out.extend(s.as_bytes());
+        }
+        Value::Array(items) => {
+            put_header(out, 4, items.len() as u64);
+            for item in items {
+                put_value(out, item)?;
+            }
+        }
+        Value::Object(map) => {
+            if let Some(cid) = as_link(map) {
+                // CID 链接：tag 42 + 字节串 (0x00 前缀 + 二进制 CID)
+                let cid: CidInfo = cid.parse()?;
+                let mut bytes = vec![0];
+                bytes.extend(cid.to_bytes());
+                put_header(out, 6, CID_TAG);
+                put_header(out, 2, bytes.len() as u64);
+                out.extend(bytes);
+                return Ok(());
+            }
+            // 键先按长度、再按字节排序，相同的内容总是得到相同的字节和 CID
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            put_header(out, 5, keys.len() as u64);
+            for key in keys {
+                put_header(out, 3, key.len() as u64);
+                out.extend(key.as_bytes());
+                put_value(out, &map[key])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// ✅ 把 dag-json 形式的文档编码为 dag-cbor，用于不经过 Kubo 的后端在本地生成节点
+pub fn encode_dag_cbor(value: &Value) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    put_value(&mut out, value)?;
+    Ok(out)
+}
@@ -0,0 +1,778 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::exit_code::ExitCode;
+use crate::filter::FileFilter;
+use crate::poster::POSTERS_DIR;
+
+// ✅ token ID 的来源：文件名中的数字 (默认)、排序后的序号，或 CSV 映射文件
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenIdStrategy {
+    #[default]
+    Filename,
+    Index,
+    Csv,
+}
+
+impl TokenIdStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Filename => "filename",
+            Self::Index => "index",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for TokenIdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenIdStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "filename" | "name" => Ok(Self::Filename),
+            "index" | "sorted" => Ok(Self::Index),
+            "csv" | "mapping" => Ok(Self::Csv),
+            other => Err(format!(
+                "❌ 不支持的 token ID 来源: {} (可选: filename, index, csv)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [layout] 段：批量模式如何从输入文件夹中找出每个 token 的文件和 token ID
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LayoutConfig {
+    // 读取子文件夹：以 token ID 命名的子文件夹 (如 0001/) 是一个 token，其中的文件保持相对路径上传
+    pub recursive: bool,
+    // 子文件夹中作为 token 图片的文件名 (不含扩展名)，按顺序匹配，如 ["front", "image"]；
+    // 都没有匹配时使用按路径排序后的第一个文件
+    pub image_names: Vec<String>,
+    pub token_ids: TokenIdStrategy,
+    // token_ids = "filename" 时先去掉的前缀，如 "ape_" 使 ape_001.png 的 ID 为 1
+    pub strip_prefix: String,
+    // token_ids = "index" 时第一个文件的 ID
+    pub start_id: u64,
+    // token_ids = "csv" 时的映射文件，每行 "文件名或子文件夹名,token ID"
+    pub mapping: Option<PathBuf>,
+    // 只处理匹配这些 glob 的文件，如 ["*.png", "*.mp4"]，为空表示不限制
+    pub include: Vec<String>,
+    // 跳过匹配这些 glob 的文件或文件夹，如 ["*.psd", "drafts/"]，在 .ipfsignore 之后生效
+    pub exclude: Vec<String>,
+    // 允许 token ID 不连续 (默认最小到最大 ID 之间缺少的 ID 视为问题)
+    pub allow_gaps: bool,
+    // 允许 token 图片使用不同的扩展名 (默认如 1.png 和 2.jpg 混用视为问题)
+    pub allow_mixed_extensions: bool,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            recursive: false,
+            image_names: Vec::new(),
+            token_ids: TokenIdStrategy::default(),
+            strip_prefix: String::new(),
+            start_id: 1,
+            mapping: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            allow_gaps: false,
+            allow_mixed_extensions: false,
+        }
+    }
+}
+
+impl LayoutConfig {
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = match (&self.token_ids, &self.mapping) {
+            (TokenIdStrategy::Csv, None) => {
+                vec!["token_ids = \"csv\" 需要填写 mapping (映射文件)".to_string()]
+            }
+            (TokenIdStrategy::Csv, Some(path)) if !path.is_file() => {
+                vec![format!("映射文件不存在: {:?}", path)]
+            }
+            _ => Vec::new(),
+        };
+        for pattern in self.include.iter().chain(&self.exclude) {
+            if pattern.trim().is_empty() || pattern.trim_start().starts_with('#') {
+                problems.push(format!("无效的 glob: {:?}", pattern));
+            }
+        }
+        problems
+    }
+
+    // 输入文件夹的过滤规则：默认规则、.ipfsignore 和 include / exclude
+    pub fn file_filter(&self, images_input_dir: &Path) -> Result<FileFilter> {
+        FileFilter::load(images_input_dir, &self.include, &self.exclude)
+    }
+}
+
+// 一个 token 的文件，路径都相对于输入文件夹，分隔符统一为 /
+#[derive(Debug, Clone)]
+pub struct TokenFiles {
+    pub token_id: u64,
+    // 用作元数据文件名：文件名中的 token ID 原文 (保留前导 0)，其它来源是 token ID 本身
+    pub id_str: String,
+    // 作为 token 图片的文件，如 "1.png" 或 "0001/front.png"
+    pub image: String,
+    // 子文件夹中的其它文件，随图片文件夹一起上传
+    pub extras: Vec<String>,
+}
+
+impl TokenFiles {
+    // 图片和其它文件
+    pub fn files(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.image).chain(&self.extras)
+    }
+}
+
+// 相对路径统一使用 /，与 IPFS 路径一致
+pub fn relative_path(path: &Path, root: &Path) -> Result<String> {
+    let relative = path.strip_prefix(root)?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => part
+                .to_str()
+                .ok_or_else(|| anyhow!("❌ 文件名不是有效的 UTF-8: {:?}", path)),
+            _ => Err(anyhow!("❌ 无效的相对路径: {:?}", relative)),
+        })
+        .collect::<Result<_>>()?;
+    Ok(parts.join("/"))
+}
+
+// 按自然顺序比较名称：数字部分按数值比较，2.png 排在 10.png 之前
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let da = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+            let db = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+            let (na, nb) = (
+                a[..da].trim_start_matches('0'),
+                b[..db].trim_start_matches('0'),
+            );
+            let ordering = na.len().cmp(&nb.len()).then_with(|| na.cmp(nb));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (&a[da..], &b[db..]);
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
+        }
+    }
+}
+
+// ✅ 读取 token ID 映射 CSV：每行 "文件名或子文件夹名,token ID"，可以有表头、空行和 # 注释
+pub fn load_id_mapping(path: &Path) -> Result<BTreeMap<String, u64>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取映射文件 {:?} 失败: {}", path, e))?;
+    let mut mapping = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((name, id)) = line.rsplit_once(',') else {
+            return Err(anyhow!("❌ 映射文件第 {} 行缺少逗号: {}", index + 1, line));
+        };
+        let (name, id) = (name.trim().trim_matches('"'), id.trim().trim_matches('"'));
+        let Ok(id) = id.parse::<u64>() else {
+            if index == 0 {
+                continue;
+            }
+            return Err(anyhow!(
+                "❌ 映射文件第 {} 行的 token ID 无效: {}",
+                index + 1,
+                id
+            ));
+        };
+        if mapping.insert(name.to_string(), id).is_some() {
+            return Err(anyhow!("❌ 映射文件中 {} 出现了多次", name));
+        }
+    }
+    Ok(mapping)
+}
+
+// 输入文件夹中的一项：顶层文件或子文件夹
+struct Candidate {
+    // 文件名或子文件夹名
+    name: String,
+    // 文件名去掉扩展名，子文件夹就是名称本身
+    stem: String,
+    image: String,
+    extras: Vec<String>,
+}
+
+// 按 [layout] 的规则为每一项分配 token ID，返回 (token ID, 元数据文件名)
+fn assign_ids(candidates: &[Candidate], layout: &LayoutConfig) -> Result<Vec<(u64, String)>> {
+    match layout.token_ids {
+        TokenIdStrategy::Filename => candidates
+            .iter()
+            .map(|candidate| {
+                let id_str = candidate
+                    .stem
+                    .strip_prefix(layout.strip_prefix.as_str())
+                    .unwrap_or(&candidate.stem);
+                let id = id_str.parse().map_err(|_| {
+                    anyhow!(
+                        "❌ {} 的名称不是数字 token ID，可以在 [layout] 中设置 strip_prefix，或改用 token_ids = \"index\" / \"csv\"",
+                        candidate.name
+                    )
+                })?;
+                Ok((id, id_str.to_string()))
+            })
+            .collect(),
+        // 调用方已按自然顺序排序
+        TokenIdStrategy::Index => Ok((layout.start_id..)
+            .zip(candidates)
+            .map(|(id, _)| (id, id.to_string()))
+            .collect()),
+        TokenIdStrategy::Csv => {
+            let path = layout
+                .mapping
+                .as_deref()
+                .ok_or_else(|| anyhow!("❌ token_ids = \"csv\" 需要在 [layout] 中填写 mapping"))?;
+            let mapping = load_id_mapping(path)?;
+            let missing: Vec<&str> = candidates
+                .iter()
+                .filter(|c| !mapping.contains_key(&c.name) && !mapping.contains_key(&c.stem))
+                .map(|c| c.name.as_str())
+                .collect();
+            if !missing.is_empty() {
+                return Err(anyhow!(
+                    "❌ 映射文件 {:?} 中缺少 {} 项: {}",
+                    path,
+                    missing.len(),
+                    missing.join(", ")
+                ));
+            }
+            Ok(candidates
+                .iter()
+                .map(|c| {
+                    let id = mapping
+                        .get(&c.name)
+                        .or(mapping.get(&c.stem))
+                        .copied()
+                        .unwrap_or_default();
+                    (id, id.to_string())
+                })
+                .collect())
+        }
+    }
+}
+
+// 子文件夹中的所有文件 (跳过被过滤的文件和 posters/ 中的封面图)，按路径排序
+fn token_dir_files(root: &Path, dir: &Path, filter: &FileFilter) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let is_dir = entry.file_type().is_dir();
+            let posters = is_dir && entry.file_name() == POSTERS_DIR;
+            !posters && filter.allows_path(root, entry.path(), is_dir)
+        });
+    for entry in walker {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(relative_path(entry.path(), root)?);
+        }
+    }
+    Ok(files)
+}
+
+// 按 image_names 的顺序选出 token 图片，没有匹配时使用第一个文件
+fn choose_image(files: &[String], image_names: &[String]) -> usize {
+    let stem = |file: &str| {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        name.rsplit_once('.')
+            .map_or(name, |(stem, _)| stem)
+            .to_string()
+    };
+    image_names
+        .iter()
+        .find_map(|wanted| {
+            files.iter().position(|file| {
+                stem(file).eq_ignore_ascii_case(wanted)
+                    || file.rsplit('/').next() == Some(wanted.as_str())
+            })
+        })
+        .unwrap_or(0)
+}
+
+// ✅ 找出输入文件夹中的所有 token：顶层的文件，以及开启 recursive 时的子文件夹，按 [layout] 的规则分配 token ID；
+// 按 token ID 排序，ID 相同时按名称排序，结果与文件系统的遍历顺序无关
+pub fn discover_tokens(images_input_dir: &Path, layout: &LayoutConfig) -> Result<Vec<TokenFiles>> {
+    if !images_input_dir.is_dir() {
+        return Err(anyhow!("❌ 图片文件夹不存在: {:?}", images_input_dir));
+    }
+    let filter = layout.file_filter(images_input_dir)?;
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(images_input_dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !filter.allows(name, path.is_dir()) {
+            continue;
+        }
+        if path.is_file() {
+            candidates.push(Candidate {
+                name: name.to_string(),
+                stem: path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                image: name.to_string(),
+                extras: Vec::new(),
+            });
+        } else if path.is_dir() && layout.recursive && name != POSTERS_DIR {
+            let mut files = token_dir_files(images_input_dir, &path, &filter)?;
+            if files.is_empty() {
+                return Err(anyhow!("❌ 子文件夹 {} 中没有文件", name));
+            }
+            let image = files.remove(choose_image(&files, &layout.image_names));
+            candidates.push(Candidate {
+                name: name.to_string(),
+                stem: name.to_string(),
+                image,
+                extras: files,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| natural_cmp(&a.name, &b.name).then_with(|| a.name.cmp(&b.name)));
+
+    let ids = assign_ids(&candidates, layout)?;
+    let mut tokens: Vec<TokenFiles> = candidates
+        .into_iter()
+        .zip(ids)
+        .map(|(candidate, (token_id, id_str))| TokenFiles {
+            token_id,
+            id_str,
+            image: candidate.image,
+            extras: candidate.extras,
+        })
+        .collect();
+    tokens.sort_by(|a, b| {
+        a.token_id
+            .cmp(&b.token_id)
+            .then_with(|| a.id_str.cmp(&b.id_str))
+    });
+    Ok(tokens)
+}
+
+// token ID 的一个问题，用于生成元数据前的检查
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdProblem {
+    // 多个文件得到同一个 token ID，如 1.png 和 1.jpg
+    Duplicate {
+        token_id: u64,
+        files: Vec<String>,
+    },
+    // 最小到最大 ID 之间缺少的一段 ID (含两端)
+    Gap {
+        from: u64,
+        to: u64,
+    },
+    // 与大多数 token 图片不同的扩展名
+    MixedExtension {
+        extension: String,
+        files: Vec<String>,
+        majority: String,
+    },
+}
+
+impl IdProblem {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Duplicate { .. } => "重复",
+            Self::Gap { .. } => "缺失",
+            Self::MixedExtension { .. } => "扩展名不一致",
+        }
+    }
+
+    fn ids(&self) -> String {
+        match self {
+            Self::Duplicate { token_id, .. } => token_id.to_string(),
+            Self::Gap { from, to } if from == to => from.to_string(),
+            Self::Gap { from, to } => format!("{}-{}", from, to),
+            Self::MixedExtension { .. } => "-".to_string(),
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Self::Duplicate { files, .. } => files.join(", "),
+            Self::Gap { from, to } => format!("缺少 {} 个 ID", to - from + 1),
+            Self::MixedExtension {
+                extension,
+                files,
+                majority,
+            } => format!("{} (多数为 {}): {}", extension, majority, files.join(", ")),
+        }
+    }
+}
+
+// 图片的扩展名 (小写，含点)，没有扩展名时为空
+fn image_extension(image: &str) -> String {
+    let name = image.rsplit('/').next().unwrap_or(image);
+    name.rsplit_once('.')
+        .map(|(_, extension)| format!(".{}", extension.to_lowercase()))
+        .unwrap_or_default()
+}
+
+// ✅ 找出 token ID 重复、不连续和图片扩展名混用的问题；tokens 已按 token ID 排序
+pub fn id_problems(tokens: &[TokenFiles], layout: &LayoutConfig) -> Vec<IdProblem> {
+    let mut problems = Vec::new();
+    let mut by_id: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+    for token in tokens {
+        by_id
+            .entry(token.token_id)
+            .or_default()
+            .push(token.image.clone());
+    }
+    for (token_id, files) in &by_id {
+        if files.len() > 1 {
+            problems.push(IdProblem::Duplicate {
+                token_id: *token_id,
+                files: files.clone(),
+            });
+        }
+    }
+
+    if !layout.allow_gaps {
+        let ids: Vec<u64> = by_id.keys().copied().collect();
+        for pair in ids.windows(2) {
+            if pair[1] > pair[0] + 1 {
+                problems.push(IdProblem::Gap {
+                    from: pair[0] + 1,
+                    to: pair[1] - 1,
+                });
+            }
+        }
+    }
+
+    if !layout.allow_mixed_extensions {
+        let mut by_extension: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for token in tokens {
+            by_extension
+                .entry(image_extension(&token.image))
+                .or_default()
+                .push(token.image.clone());
+        }
+        // 数量最多的扩展名视为正常，数量相同时取排在前面的
+        if let Some(majority) = by_extension
+            .iter()
+            .max_by(|a, b| a.1.len().cmp(&b.1.len()).then_with(|| b.0.cmp(a.0)))
+            .map(|(extension, _)| extension.clone())
+        {
+            for (extension, files) in by_extension {
+                if extension != majority {
+                    problems.push(IdProblem::MixedExtension {
+                        extension: if extension.is_empty() {
+                            "(无扩展名)".to_string()
+                        } else {
+                            extension
+                        },
+                        files,
+                        majority: majority.clone(),
+                    });
+                }
+            }
+        }
+    }
+    problems
+}
+
+// 按显示宽度补齐 (中文字符占两列)
+fn pad(text: &str, width: usize) -> String {
+    let shown: usize = text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(shown)))
+}
+
+const MAX_LISTED_PROBLEMS: usize = 50;
+
+pub fn print_id_problems(problems: &[IdProblem]) {
+    let id_width = problems
+        .iter()
+        .take(MAX_LISTED_PROBLEMS)
+        .map(|problem| problem.ids().chars().count() + 2)
+        .max()
+        .unwrap_or(0)
+        .max(10);
+    println!("   {}{}说明", pad("问题", 14), pad("token ID", id_width));
+    for problem in problems.iter().take(MAX_LISTED_PROBLEMS) {
+        println!(
+            "   {}{}{}",
+            pad(problem.kind(), 14),
+            pad(&problem.ids(), id_width),
+            problem.detail()
+        );
+    }
+    if problems.len() > MAX_LISTED_PROBLEMS {
+        println!(
+            "   ... 另有 {} 个问题",
+            problems.len() - MAX_LISTED_PROBLEMS
+        );
+    }
+}
+
+// ✅ 生成元数据前的检查：有问题时打印问题表并返回错误，避免生成一个不完整的集合
+pub fn check_token_ids(tokens: &[TokenFiles], layout: &LayoutConfig) -> Result<()> {
+    let problems = id_problems(tokens, layout);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    println!("\n--- token ID 检查发现 {} 个问题 ---", problems.len());
+    print_id_problems(&problems);
+    let mut hints = Vec::new();
+    if problems
+        .iter()
+        .any(|problem| matches!(problem, IdProblem::Gap { .. }))
+    {
+        hints.push("有意留空的 ID 可以在 [layout] 中设置 allow_gaps = true");
+    }
+    if problems
+        .iter()
+        .any(|problem| matches!(problem, IdProblem::MixedExtension { .. }))
+    {
+        hints.push("有意混用扩展名时设置 allow_mixed_extensions = true");
+    }
+    Err(ExitCode::Validation.wrap(anyhow!(
+        "❌ token ID 检查发现 {} 个问题，已在生成元数据前终止{}",
+        problems.len(),
+        if hints.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", hints.join("；"))
+        }
+    )))
+}
+
+// ✅ config.toml 中的 [naming] 段：规范化输出的元数据文件名，按需把图片重命名为同样的名称
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct NamingConfig {
+    // token ID 补零后的宽度，如 4 表示 0001；0 表示保持输入中的写法
+    pub pad_width: usize,
+    // 文件名前缀，如 "token_"
+    pub prefix: String,
+    // 文件名 (含图片扩展名) 转为小写
+    pub lowercase: bool,
+    // 把 images/ 中的图片 (或子文件夹) 重命名为与元数据相同的名称，保留扩展名
+    pub rename_images: bool,
+    // token ID 写成小写十六进制；ERC-1155 的 {id} 替换规则为 hex = true、pad_width = 64
+    pub hex: bool,
+}
+
+impl NamingConfig {
+    // token 的文件名 (不含扩展名)，没有任何规则时就是 id_str
+    pub fn stem(&self, token: &TokenFiles) -> String {
+        let id = match (self.hex, self.pad_width) {
+            (true, width) => format!("{:0width$x}", token.token_id),
+            (false, 0) => token.id_str.clone(),
+            (false, width) => format!("{:0width$}", token.token_id),
+        };
+        let stem = format!("{}{}", self.prefix, id);
+        if self.lowercase {
+            stem.to_lowercase()
+        } else {
+            stem
+        }
+    }
+
+    // 所有 token 的文件名，规范化后重名 (如 1.png 和 01.png 补零后都是 0001) 时返回错误
+    pub fn stems(&self, tokens: &[TokenFiles]) -> Result<Vec<String>> {
+        let stems: Vec<String> = tokens.iter().map(|token| self.stem(token)).collect();
+        let mut seen = BTreeMap::new();
+        for (stem, token) in stems.iter().zip(tokens) {
+            if let Some(other) = seen.insert(stem.as_str(), token.image.as_str()) {
+                return Err(anyhow!(
+                    "❌ {} 和 {} 的文件名规范化后都是 {}",
+                    other,
+                    token.image,
+                    stem
+                ));
+            }
+        }
+        Ok(stems)
+    }
+}
+
+// 路径的第一段：顶层文件名或子文件夹名
+fn top_level(relative: &str) -> &str {
+    relative.split('/').next().unwrap_or(relative)
+}
+
+// ✅ 按 [naming] 重命名 dir 中每个 token 的图片 (顶层文件) 或子文件夹，并更新 token 中的路径；返回重命名的数量
+pub fn rename_token_files(
+    dir: &Path,
+    tokens: &mut [TokenFiles],
+    naming: &NamingConfig,
+) -> Result<usize> {
+    let stems = naming.stems(tokens)?;
+    let mut renames = Vec::new();
+    for (index, (token, stem)) in tokens.iter().zip(stems).enumerate() {
+        let top = top_level(&token.image).to_string();
+        let target = if token.image.contains('/') {
+            stem
+        } else {
+            match top.rsplit_once('.') {
+                Some((_, ext)) if naming.lowercase => format!("{}.{}", stem, ext.to_lowercase()),
+                Some((_, ext)) => format!("{}.{}", stem, ext),
+                None => stem,
+            }
+        };
+        if target != top {
+            renames.push((index, top, target));
+        }
+    }
+    // 先全部改为临时名称再改为目标名称，避免 1.png -> 2.png、2.png -> 3.png 这样的链式覆盖
+    for (index, top, _) in &renames {
+        fs::rename(dir.join(top), dir.join(format!(".rename-{}", index)))?;
+    }
+    for (index, top, target) in &renames {
+        let target_path = dir.join(target);
+        if target_path.exists() {
+            return Err(anyhow!("❌ 无法把 {} 重命名为 {}: 目标已存在", top, target));
+        }
+        fs::rename(dir.join(format!(".rename-{}", index)), &target_path)?;
+        let token = &mut tokens[*index];
+        for path in std::iter::once(&mut token.image).chain(&mut token.extras) {
+            *path = format!("{}{}", target, &path[top.len()..]);
+        }
+    }
+    Ok(renames.len())
+}
+
+// 批量运行目录的默认模板，与之前固定的 collection_<时间戳> 相同
+const DEFAULT_BATCH_DIR: &str = "collection_{timestamp}";
+// 单件运行目录的默认模板：图片文件名 (不含扩展名)
+const DEFAULT_SINGLE_DIR: &str = "{stem}";
+// 没有集合名称时 {collection} 的值
+const DEFAULT_COLLECTION: &str = "collection";
+
+// ✅ config.toml 中的 [output] 段：运行目录 (相对 --output) 的命名，以及图片和元数据额外的输出位置
+// 模板中可以使用 {collection}、{date}、{time}、{timestamp}，单件还可以使用 {stem}；
+// 可以包含子文件夹，如 "{collection}/{date}/{time}"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct OutputLayout {
+    pub batch_dir: String,
+    pub single_dir: String,
+    // 运行完成后把 images/ (单件为图片文件) 复制到 <images_root>/<运行目录>/，如网站的静态文件目录
+    pub images_root: Option<PathBuf>,
+    // 同上，复制 metadata/ (单件为元数据文件)
+    pub metadata_root: Option<PathBuf>,
+}
+
+impl Default for OutputLayout {
+    fn default() -> Self {
+        Self {
+            batch_dir: DEFAULT_BATCH_DIR.to_string(),
+            single_dir: DEFAULT_SINGLE_DIR.to_string(),
+            images_root: None,
+            metadata_root: None,
+        }
+    }
+}
+
+// 集合名称中不能用在路径里的字符换成 -，如 "Moon Cats: S1" -> "moon-cats-s1"
+fn collection_slug(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '.')
+        .filter(|part| !part.is_empty() && part.chars().any(|c| c != '.'))
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        DEFAULT_COLLECTION.to_string()
+    } else {
+        slug
+    }
+}
+
+impl OutputLayout {
+    // ✅ 按模板生成运行目录的相对路径；不能是绝对路径或包含 ..
+    fn render(
+        template: &str,
+        collection: &str,
+        stem: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<PathBuf> {
+        let mut rendered = template
+            .replace("{collection}", &collection_slug(collection))
+            .replace("{date}", &now.format("%Y-%m-%d").to_string())
+            .replace("{time}", &now.format("%H%M%S").to_string())
+            .replace("{timestamp}", &now.format("%Y%m%d_%H%M%S").to_string());
+        if let Some(stem) = stem {
+            rendered = rendered.replace("{stem}", stem);
+        }
+        if let Some(start) = rendered.find('{') {
+            let placeholder = rendered[start..].split('}').next().unwrap_or_default();
+            return Err(anyhow!(
+                "❌ [output] 模板 {:?} 中有不支持的占位符 {}}}",
+                template,
+                placeholder
+            ));
+        }
+        let path = PathBuf::from(&rendered);
+        let safe = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !safe || rendered.trim().is_empty() {
+            return Err(anyhow!(
+                "❌ [output] 模板 {:?} 生成的运行目录 {:?} 必须是 --output 下的相对路径，且不能包含 ..",
+                template,
+                rendered
+            ));
+        }
+        Ok(path)
+    }
+
+    // 批量运行目录
+    pub fn batch_path(&self, collection: &str, now: DateTime<Utc>) -> Result<PathBuf> {
+        Self::render(&self.batch_dir, collection, None, now)
+    }
+
+    // 单件运行目录
+    pub fn single_path(&self, collection: &str, stem: &str, now: DateTime<Utc>) -> Result<PathBuf> {
+        Self::render(&self.single_dir, collection, Some(stem), now)
+    }
+
+    // 上传前检查模板，避免上传完成后才发现目录无法创建
+    pub fn problems(&self) -> Vec<String> {
+        let now = Utc::now();
+        [self.batch_path("", now), self.single_path("", "1", now)]
+            .into_iter()
+            .filter_map(|result| result.err())
+            .map(|e| e.to_string().trim_start_matches("❌ [output] ").to_string())
+            .collect()
+    }
+}
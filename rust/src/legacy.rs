@@ -0,0 +1,324 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+
+use crate::hashing::{HashAlgorithm, hash_file};
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::metadata::NftMetadata;
+use crate::standards::detect_file_mime_type;
+use crate::unixfs::{ImportOptions, import_file, import_path};
+
+// ✅ 兄弟实现 (python/ typescript/ go/) 的输出目录没有 manifest.json，
+// 这里按它们共同的目录结构读取元数据，并转换成本 crate 的清单格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceImpl {
+    Python,
+    TypeScript,
+    Go,
+}
+
+impl SourceImpl {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Python => "python",
+            Self::TypeScript => "typescript",
+            Self::Go => "go",
+        }
+    }
+
+    // 根据路径中的 python/ typescript/ go/ 目录名推断
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let dir = dir.canonicalize().ok()?;
+        dir.components()
+            .rev()
+            .filter_map(|c| c.as_os_str().to_str())
+            .find_map(|name| name.parse().ok())
+    }
+
+    // 各实现上传单件元数据时使用的 JSON 序列化方式
+    fn serialize(&self, metadata: &NftMetadata) -> Result<String> {
+        let compact = serde_json::to_string(metadata)?;
+        Ok(match self {
+            // JSON.stringify 与 serde_json 的紧凑格式相同
+            Self::TypeScript => compact,
+            // json.dumps 默认使用 ", " 和 ": " 分隔，并把非 ASCII 字符转义为 \uXXXX
+            Self::Python => python_style(&compact),
+            // encoding/json 会转义 <、>、& 和 U+2028/U+2029
+            Self::Go => go_style(&compact),
+        })
+    }
+}
+
+impl fmt::Display for SourceImpl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SourceImpl {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "python" | "py" => Ok(Self::Python),
+            "typescript" | "ts" => Ok(Self::TypeScript),
+            "go" => Ok(Self::Go),
+            _ => Err(anyhow!(
+                "❌ 未知的实现 `{}` (可选: python, typescript, go)",
+                s
+            )),
+        }
+    }
+}
+
+// 在紧凑 JSON 的基础上逐字符改写：字符串外的分隔符加空格，字符串内的非 ASCII 转义
+fn python_style(compact: &str) -> String {
+    let mut out = String::with_capacity(compact.len() * 2);
+    let (mut in_string, mut escaped) = (false, false);
+    for c in compact.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            if c.is_ascii() {
+                out.push(c);
+            } else {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+            continue;
+        }
+        out.push(c);
+        match c {
+            '"' => in_string = true,
+            ',' | ':' => out.push(' '),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn go_style(compact: &str) -> String {
+    let mut out = String::with_capacity(compact.len());
+    for c in compact.chars() {
+        match c {
+            '<' => out.push_str("\\u003c"),
+            '>' => out.push_str("\\u003e"),
+            '&' => out.push_str("\\u0026"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn read_metadata(path: &Path) -> Result<NftMetadata> {
+    serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| anyhow!("❌ 无法解析元数据 {:?}: {}", path, e))
+}
+
+// ipfs://<cid>/<文件名> 或 ipfs://<cid>
+fn split_ipfs_uri(uri: &str) -> Result<(&str, Option<&str>)> {
+    let rest = uri
+        .strip_prefix("ipfs://")
+        .ok_or_else(|| anyhow!("❌ 不是 ipfs:// 链接: {}", uri))?;
+    Ok(match rest.split_once('/') {
+        Some((cid, file)) => (cid, Some(file)),
+        None => (rest, None),
+    })
+}
+
+fn modified_at(path: &Path) -> Result<String> {
+    let modified: DateTime<Utc> = fs::metadata(path)?.modified()?.into();
+    Ok(modified.to_rfc3339())
+}
+
+// 元数据文件名是 token ID，可能带 .json 后缀
+fn token_id_of(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+fn import_batch(dir: &Path, source: SourceImpl, algorithm: HashAlgorithm) -> Result<Manifest> {
+    let metadata_dir = dir.join("metadata");
+    let mut files: Vec<(u64, PathBuf)> = fs::read_dir(&metadata_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| token_id_of(&path).map(|id| (id, path)))
+        .collect();
+    files.sort();
+
+    let mut image_cid = None;
+    let mut tokens = Vec::with_capacity(files.len());
+    for (token_id, path) in &files {
+        let metadata = read_metadata(path)?;
+        let (cid, image_file) = split_ipfs_uri(&metadata.image)?;
+        let image_file = image_file
+            .ok_or_else(|| anyhow!("❌ {:?} 的 image 没有文件名: {}", path, metadata.image))?;
+        image_cid.get_or_insert_with(|| cid.to_string());
+        let local_image = dir.join("images").join(image_file);
+        tokens.push(TokenEntry {
+            token_id: Some(*token_id),
+            name: metadata.name,
+            image_file: image_file.to_string(),
+            image_hash: local_image
+                .is_file()
+                .then(|| hash_file(&local_image, algorithm))
+                .transpose()?,
+            animation_uri: metadata.animation_url,
+            original_uri: None,
+            image_uri: metadata.image,
+            mime_type: local_image
+                .is_file()
+                .then(|| detect_file_mime_type(&local_image))
+                .transpose()?,
+            metadata_file: path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            metadata_cid: None,
+            arweave: None,
+        });
+    }
+
+    // 元数据文件夹的 CID 没有被记录下来，按 `ipfs add -r --cid-version 1` 在本地重新计算
+    let metadata_cid = import_path(&metadata_dir, &ImportOptions::default(), &mut |_| Ok(()))?
+        .cid
+        .to_string();
+    Ok(Manifest {
+        kind: CollectionKind::Batch,
+        created_at: modified_at(&metadata_dir)?,
+        backend: source.to_string(),
+        node_version: None,
+        output_dir: dir.to_path_buf(),
+        image_cid: image_cid.unwrap_or_default(),
+        posters_cid: None,
+        previews_cid: None,
+        watermarked_cid: None,
+        localization_cid: None,
+        metadata_cid: metadata_cid.clone(),
+        token_uri: format!("ipfs://{}/", metadata_cid),
+        tokens,
+        served_by: Default::default(),
+        urls: Default::default(),
+        encryption: None,
+        ipld: None,
+        filecoin: Default::default(),
+        remote_pins: Default::default(),
+    })
+}
+
+fn import_single(dir: &Path, source: SourceImpl, algorithm: HashAlgorithm) -> Result<Manifest> {
+    let stem = dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的目录名: {:?}", dir))?;
+    let metadata_path = [dir.join(stem), dir.join(format!("{}.json", stem))]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| anyhow!("❌ {:?} 中没有找到元数据文件 `{}`", dir, stem))?;
+    let metadata = read_metadata(&metadata_path)?;
+    let (image_cid, _) = split_ipfs_uri(&metadata.image)?;
+
+    // 图片是目录中与 stem 同名、带图片后缀的文件 (元数据可能是 `stem` 或 `stem.json`)
+    let image_file = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_file()
+                && path.file_stem().and_then(|s| s.to_str()) == Some(stem)
+                && path
+                    .extension()
+                    .is_some_and(|ext| !ext.eq_ignore_ascii_case("json"))
+        });
+
+    // 按原实现的序列化方式重新计算元数据 CID
+    let json = source.serialize(&metadata)?;
+    let metadata_cid = import_file(json.as_bytes(), &ImportOptions::default(), &mut |_| Ok(()))?
+        .cid
+        .to_string();
+    Ok(Manifest {
+        kind: CollectionKind::Single,
+        created_at: modified_at(&metadata_path)?,
+        backend: source.to_string(),
+        node_version: None,
+        output_dir: dir.to_path_buf(),
+        image_cid: image_cid.to_string(),
+        posters_cid: None,
+        previews_cid: None,
+        watermarked_cid: None,
+        localization_cid: None,
+        metadata_cid: metadata_cid.clone(),
+        token_uri: format!("ipfs://{}", metadata_cid),
+        tokens: vec![TokenEntry {
+            token_id: None,
+            name: metadata.name,
+            image_file: image_file
+                .as_deref()
+                .and_then(|p| p.file_name())
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            image_hash: image_file
+                .as_deref()
+                .map(|p| hash_file(p, algorithm))
+                .transpose()?,
+            mime_type: image_file
+                .as_deref()
+                .map(detect_file_mime_type)
+                .transpose()?,
+            animation_uri: metadata.animation_url,
+            original_uri: None,
+            image_uri: metadata.image,
+            metadata_file: metadata_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            metadata_cid: Some(metadata_cid),
+            arweave: None,
+        }],
+        served_by: Default::default(),
+        urls: Default::default(),
+        encryption: None,
+        ipld: None,
+        filecoin: Default::default(),
+        remote_pins: Default::default(),
+    })
+}
+
+// ✅ 把一个兄弟实现的输出目录转换成清单 (不会写文件)
+pub fn import_output(
+    dir: &Path,
+    source: Option<SourceImpl>,
+    algorithm: HashAlgorithm,
+) -> Result<Manifest> {
+    if !dir.is_dir() {
+        return Err(anyhow!("❌ 目录不存在: {:?}", dir));
+    }
+    let source = source
+        .or_else(|| SourceImpl::detect(dir))
+        .ok_or_else(|| anyhow!("❌ 无法判断 {:?} 来自哪个实现，请用 --source 指定", dir))?;
+    if source == SourceImpl::TypeScript {
+        eprintln!(
+            "⚠️  TypeScript 实现默认使用 CIDv0 上传，重新计算的 CIDv1 可能与原来的上传结果不同"
+        );
+    }
+    if dir.join("images").is_dir() && dir.join("metadata").is_dir() {
+        import_batch(dir, source, algorithm)
+    } else {
+        import_single(dir, source, algorithm)
+    }
+}
@@ -1,37 +1,162 @@
-use std::{fs, path::Path};
+use std::path::Path;
 
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
-use walkdir::WalkDir;
 
-// ✅ 定义元数据结构体
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Attribute {
-    pub trait_type: String,
-    pub value: serde_json::Value,
-}
+// 必须最先声明：println! 等宏按文本顺序生效，之后的模块打印的内容都会经过 i18n::localize
+#[macro_use]
+pub mod i18n;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct NftMetadata {
-    pub name: String,
-    pub description: String,
-    pub image: String,
-    pub attributes: Vec<Attribute>,
-}
+pub mod allowlist;
+pub mod arweave;
+#[cfg(feature = "native")]
+pub mod backend;
+#[cfg(feature = "native")]
+pub mod backup;
+#[cfg(feature = "native")]
+pub mod bundler;
+#[cfg(feature = "native")]
+pub mod cancel;
+#[cfg(feature = "native")]
+pub mod car;
+#[cfg(feature = "native")]
+pub mod catalog;
+#[cfg(feature = "native")]
+pub mod checksums;
+pub mod cid;
+#[cfg(feature = "native")]
+pub mod clone;
+#[cfg(feature = "native")]
+pub mod compare;
+pub mod compat;
+#[cfg(feature = "native")]
+pub mod config;
+pub mod contract;
+pub mod copy;
+#[cfg(feature = "native")]
+pub mod daemon;
+#[cfg(feature = "tui")]
+pub mod dashboard;
+#[cfg(feature = "native")]
+pub mod demo;
+#[cfg(feature = "native")]
+pub mod deploy;
+#[cfg(feature = "native")]
+pub mod diff;
+#[cfg(feature = "native")]
+pub mod doctor;
+#[cfg(feature = "native")]
+pub mod duplicates;
+#[cfg(feature = "native")]
+pub mod encryption;
+#[cfg(feature = "native")]
+pub mod estimate;
+#[cfg(feature = "native")]
+pub mod eth;
+#[cfg(feature = "native")]
+pub mod exit_code;
+#[cfg(feature = "native")]
+pub mod expiry;
+#[cfg(feature = "native")]
+pub mod extra_fields;
+#[cfg(feature = "native")]
+pub mod extract;
+pub mod filecoin;
+pub mod filter;
+#[cfg(feature = "native")]
+pub mod gallery;
+#[cfg(feature = "kubo-harness")]
+pub mod harness;
+pub mod hashing;
+#[cfg(feature = "native")]
+pub mod hooks;
+#[cfg(feature = "native")]
+pub mod init;
+#[cfg(feature = "native")]
+pub mod inspect;
+pub mod ipld;
+#[cfg(feature = "native")]
+pub mod layout;
+pub mod legacy;
+pub mod links;
+#[cfg(feature = "native")]
+pub mod loadtest;
+#[cfg(feature = "native")]
+pub mod localization;
+pub mod manifest;
+pub mod metadata;
+#[cfg(feature = "native")]
+pub mod metrics;
+#[cfg(feature = "native")]
+pub mod migrate;
+#[cfg(feature = "native")]
+pub mod monitor;
+#[cfg(feature = "native")]
+pub mod onchain;
+#[cfg(feature = "native")]
+pub mod patch;
+pub mod pinning;
+#[cfg(feature = "native")]
+pub mod poster;
+#[cfg(feature = "native")]
+pub mod preview;
+#[cfg(feature = "native")]
+pub mod provide;
+#[cfg(feature = "native")]
+pub mod remote_pin;
+#[cfg(feature = "native")]
+pub mod remote_source;
+#[cfg(feature = "native")]
+pub mod report;
+#[cfg(feature = "native")]
+pub mod rewrite;
+#[cfg(feature = "native")]
+pub mod runbook;
+#[cfg(feature = "native")]
+pub mod sanitize;
+pub mod schema;
+#[cfg(feature = "native")]
+pub mod secrets;
+#[cfg(feature = "native")]
+pub mod signing;
+#[cfg(feature = "native")]
+pub mod staging;
+pub mod standards;
+#[cfg(feature = "native")]
+pub mod storage_deal;
+#[cfg(feature = "native")]
+pub mod throttle;
+#[cfg(feature = "native")]
+pub mod timeout;
+pub mod unixfs;
+#[cfg(feature = "native")]
+pub mod update;
+pub mod urls;
+#[cfg(feature = "native")]
+pub mod usage;
+#[cfg(feature = "native")]
+pub mod validate;
+pub mod verbosity;
+#[cfg(feature = "native")]
+pub mod watch;
+#[cfg(feature = "native")]
+pub mod watermark;
+#[cfg(feature = "native")]
+pub mod webhook;
+#[cfg(feature = "native")]
+pub mod window;
+#[cfg(feature = "native")]
+pub mod workflow;
+
+pub use metadata::{Attribute, NftMetadata};
 
 // ✅ 共享的辅助函数
+// 跳过隐藏文件、系统生成的文件 (Thumbs.db 等) 和 src/.ipfsignore 中忽略的文件
 pub fn copy_directory(src: &Path, dst: &Path) -> Result<()> {
-    fs::create_dir_all(dst)?;
-    for entry in WalkDir::new(src) {
-        let entry = entry?;
-        let path = entry.path();
-        let relative_path = path.strip_prefix(src)?;
-        let dest_path = dst.join(relative_path);
-        if path.is_dir() {
-            fs::create_dir_all(&dest_path)?;
-        } else {
-            fs::copy(path, &dest_path)?;
-        }
-    }
+    copy::copy_tree(
+        src,
+        dst,
+        &filter::FileFilter::load(src, &[], &[])?,
+        &copy::CopyOptions::default(),
+    )?;
     Ok(())
 }
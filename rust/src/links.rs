@@ -0,0 +1,136 @@
+use std::fmt::Write as _;
+
+use crate::contract::ContractConfig;
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+
+pub const LINKS_FILE_NAME: &str = "urls.csv";
+
+// OpenSea 链接中的链名称，测试网使用 testnets.opensea.io
+fn opensea_chain(chain: &str) -> Option<(&'static str, &'static str)> {
+    const MAINNET: &str = "https://opensea.io";
+    const TESTNET: &str = "https://testnets.opensea.io";
+    Some(match chain.to_ascii_lowercase().as_str() {
+        "ethereum" | "eth" | "mainnet" => (MAINNET, "ethereum"),
+        "polygon" | "matic" => (MAINNET, "matic"),
+        "arbitrum" => (MAINNET, "arbitrum"),
+        "optimism" => (MAINNET, "optimism"),
+        "base" => (MAINNET, "base"),
+        "avalanche" => (MAINNET, "avalanche"),
+        "zora" => (MAINNET, "zora"),
+        "blast" => (MAINNET, "blast"),
+        "sepolia" => (TESTNET, "sepolia"),
+        "base_sepolia" | "base-sepolia" => (TESTNET, "base_sepolia"),
+        "amoy" => (TESTNET, "amoy"),
+        _ => return None,
+    })
+}
+
+// LooksRare 只支持以太坊主网和 Sepolia
+fn looksrare_host(chain: &str) -> Option<&'static str> {
+    match chain.to_ascii_lowercase().as_str() {
+        "ethereum" | "eth" | "mainnet" => Some("https://looksrare.org"),
+        "sepolia" => Some("https://sepolia.looksrare.org"),
+        _ => None,
+    }
+}
+
+pub fn opensea_url(contract: &ContractConfig, token_id: u64) -> Option<String> {
+    let (host, chain) = opensea_chain(&contract.chain)?;
+    Some(format!(
+        "{}/assets/{}/{}/{}",
+        host, chain, contract.address, token_id
+    ))
+}
+
+pub fn looksrare_url(contract: &ContractConfig, token_id: u64) -> Option<String> {
+    let host = looksrare_host(&contract.chain)?;
+    Some(format!(
+        "{}/collections/{}/{}",
+        host, contract.address, token_id
+    ))
+}
+
+// 链上 tokenURI 返回的地址：单件为 mint 时的 URI，批量为 Base URI + 元数据文件名
+pub fn token_uri(manifest: &Manifest, token: &TokenEntry) -> String {
+    match manifest.kind {
+        CollectionKind::Single => manifest.token_uri.clone(),
+        CollectionKind::Batch | CollectionKind::Watch => {
+            format!("{}{}", manifest.token_uri, token.metadata_file)
+        }
+    }
+}
+
+// ipfs://<cid>/<path> 转换为网关地址
+pub fn ipfs_to_gateway(uri: &str, gateway: &str) -> String {
+    match uri.strip_prefix("ipfs://") {
+        Some(path) => format!("{}/ipfs/{}", gateway.trim_end_matches('/'), path),
+        None => uri.to_string(),
+    }
+}
+
+// 导出文件中的一行
+#[derive(Debug, Clone)]
+pub struct TokenLinks {
+    pub token_id: Option<u64>,
+    pub name: String,
+    pub token_uri: String,
+    pub metadata_url: String,
+    pub image_url: String,
+    pub opensea: Option<String>,
+    pub looksrare: Option<String>,
+}
+
+// ✅ 为清单中的每个 token 生成网关地址；配置了合约时附带市场链接
+pub fn token_links(
+    manifest: &Manifest,
+    gateway: &str,
+    contract: Option<&ContractConfig>,
+) -> Vec<TokenLinks> {
+    manifest
+        .tokens
+        .iter()
+        .map(|token| {
+            let uri = token_uri(manifest, token);
+            let market = |url: fn(&ContractConfig, u64) -> Option<String>| {
+                contract.zip(token.token_id).and_then(|(c, id)| url(c, id))
+            };
+            TokenLinks {
+                token_id: token.token_id,
+                name: token.name.clone(),
+                metadata_url: ipfs_to_gateway(&uri, gateway),
+                image_url: ipfs_to_gateway(&token.image_uri, gateway),
+                token_uri: uri,
+                opensea: market(opensea_url),
+                looksrare: market(looksrare_url),
+            }
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn render_links_csv(links: &[TokenLinks]) -> String {
+    let mut out =
+        String::from("token_id,name,token_uri,metadata_url,image_url,opensea_url,looksrare_url\n");
+    for link in links {
+        let token_id = link.token_id.map(|id| id.to_string()).unwrap_or_default();
+        let fields = [
+            token_id.as_str(),
+            &link.name,
+            &link.token_uri,
+            &link.metadata_url,
+            &link.image_url,
+            link.opensea.as_deref().unwrap_or_default(),
+            link.looksrare.as_deref().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        let _ = writeln!(out, "{}", row.join(","));
+    }
+    out
+}
@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use reqwest::blocking::Client;
+
+use crate::manifest::{CollectionKind, Manifest};
+use crate::monitor::gateway_url;
+
+// 压测的可选项
+#[derive(Debug, Clone)]
+pub struct LoadTestOptions {
+    pub gateway: String,
+    // 每秒发出的请求数 (开环：不等待上一个请求完成)
+    pub rps: u32,
+    pub duration: Duration,
+    pub timeout: Duration,
+    // 同时进行的请求上限
+    pub concurrency: usize,
+}
+
+impl Default for LoadTestOptions {
+    fn default() -> Self {
+        Self {
+            gateway: crate::monitor::DEFAULT_GATEWAY.to_string(),
+            rps: 50,
+            duration: Duration::from_secs(60),
+            timeout: Duration::from_secs(30),
+            concurrency: 256,
+        }
+    }
+}
+
+// 被压测的 URL 类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Target {
+    Metadata,
+    Image,
+}
+
+impl Target {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Metadata => "元数据",
+            Self::Image => "图片",
+        }
+    }
+}
+
+// 一种 URL 的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct TargetStats {
+    pub requests: u64,
+    pub errors: u64,
+    // 状态码 (或 "timeout"、"error") -> 次数
+    pub statuses: BTreeMap<String, u64>,
+    // 从计划发出时间算起的耗时，包含排队时间
+    latencies: Vec<Duration>,
+}
+
+impl TargetStats {
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.requests as f64
+    }
+
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let index = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+        sorted[index.min(sorted.len() - 1)]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadTestReport {
+    pub targets: BTreeMap<Target, TargetStats>,
+    pub elapsed: Duration,
+}
+
+// ✅ 一次运行中所有可以访问的 token URL：元数据和图片
+pub fn token_urls(manifest: &Manifest, gateway: &str) -> Vec<(Target, String)> {
+    let mut urls = Vec::new();
+    for token in &manifest.tokens {
+        let metadata = match (&token.metadata_cid, manifest.kind) {
+            (Some(cid), _) => cid.clone(),
+            (None, CollectionKind::Batch) => {
+                format!("{}/{}", manifest.metadata_cid, token.metadata_file)
+            }
+            (None, _) => continue,
+        };
+        urls.push((Target::Metadata, gateway_url(gateway, &metadata)));
+        if let Some(path) = token.image_uri.strip_prefix("ipfs://") {
+            urls.push((Target::Image, gateway_url(gateway, path)));
+        }
+    }
+    urls
+}
+
+// 支持 "60s"、"2m"、"500ms"，不带单位时按秒计算
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (number, unit) = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .map_or((s, ""), |i| s.split_at(i));
+    let value: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("❌ 无效的时长: {}", s))?;
+    let seconds = match unit {
+        "" | "s" => value,
+        "ms" => value / 1000.0,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(anyhow!("❌ 无效的时长单位 `{}` (可选: ms, s, m, h)", unit)),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+// xorshift64*：只用来随机挑选 URL，不需要密码学强度
+struct Rng(u64);
+
+impl Rng {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self(nanos | 1)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) % n as u64) as usize
+    }
+}
+
+struct Job {
+    target: Target,
+    url: String,
+    // 计划发出的时间；工作线程不够时排队的时间也计入耗时
+    scheduled: Instant,
+}
+
+// ✅ 按固定速率随机请求 token 的元数据和图片，统计错误率和延迟分位数
+pub fn run_load_test(manifest: &Manifest, options: &LoadTestOptions) -> Result<LoadTestReport> {
+    let urls = token_urls(manifest, &options.gateway);
+    if urls.is_empty() {
+        return Err(anyhow!("❌ 清单中没有可以压测的 token URL"));
+    }
+    if options.rps == 0 {
+        return Err(anyhow!("❌ --rps 必须大于 0"));
+    }
+    let client = Client::builder().timeout(options.timeout).build()?;
+
+    println!("\n==============================================");
+    println!("🚦 开始压测网关: {}", options.gateway);
+    println!(
+        "   - {} 个 URL，{} 请求/秒，持续 {:?}",
+        urls.len(),
+        options.rps,
+        options.duration
+    );
+    println!("==============================================");
+
+    let stats: Arc<Mutex<BTreeMap<Target, TargetStats>>> = Arc::default();
+    let (tx, rx) = mpsc::channel::<Job>();
+    let rx = Arc::new(Mutex::new(rx));
+    let interval = Duration::from_secs_f64(1.0 / options.rps as f64);
+    let total = (options.duration.as_secs_f64() * options.rps as f64).round() as u64;
+    let workers: Vec<_> = (0..options.concurrency.clamp(1, total.max(1) as usize))
+        .map(|_| {
+            let rx = rx.clone();
+            let client = client.clone();
+            let stats = stats.clone();
+            thread::spawn(move || {
+                loop {
+                    let job = match rx.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+                        Ok(job) => job,
+                        Err(_) => break,
+                    };
+                    let outcome = client.get(&job.url).send().map(|mut response| {
+                        // 读完整个响应体，耗时才包含传输时间
+                        let _ = io::copy(&mut response, &mut io::sink());
+                        response.status()
+                    });
+                    let latency = job.scheduled.elapsed();
+                    let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+                    let entry = stats.entry(job.target).or_default();
+                    entry.requests += 1;
+                    entry.latencies.push(latency);
+                    let status = match outcome {
+                        Ok(status) => {
+                            if !status.is_success() {
+                                entry.errors += 1;
+                            }
+                            status.as_u16().to_string()
+                        }
+                        Err(e) => {
+                            entry.errors += 1;
+                            if e.is_timeout() { "timeout" } else { "error" }.to_string()
+                        }
+                    };
+                    *entry.statuses.entry(status).or_default() += 1;
+                }
+            })
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut rng = Rng::seeded();
+    for i in 0..total {
+        let scheduled = start + interval.mul_f64(i as f64);
+        if let Some(wait) = scheduled.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+        let (target, url) = &urls[rng.below(urls.len())];
+        tx.send(Job {
+            target: *target,
+            url: url.clone(),
+            scheduled,
+        })?;
+    }
+    drop(tx);
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    let targets = std::mem::take(&mut *stats.lock().unwrap_or_else(|e| e.into_inner()));
+    Ok(LoadTestReport {
+        targets,
+        elapsed: start.elapsed(),
+    })
+}
+
+pub fn print_report(report: &LoadTestReport) {
+    println!("\n📊 压测结果 (耗时 {:.1}s):", report.elapsed.as_secs_f64());
+    let ms = |d: Duration| format!("{:.0}ms", d.as_secs_f64() * 1000.0);
+    for (target, stats) in &report.targets {
+        let icon = if stats.errors == 0 { "✅" } else { "⚠️ " };
+        println!(
+            "{} {}: {} 个请求，错误率 {:.2}%",
+            icon,
+            target.label(),
+            stats.requests,
+            stats.error_rate() * 100.0
+        );
+        println!(
+            "   延迟 p50 {} / p90 {} / p95 {} / p99 {} / max {}",
+            ms(stats.percentile(50.0)),
+            ms(stats.percentile(90.0)),
+            ms(stats.percentile(95.0)),
+            ms(stats.percentile(99.0)),
+            ms(stats.percentile(100.0)),
+        );
+        let statuses: Vec<String> = stats
+            .statuses
+            .iter()
+            .map(|(status, count)| format!("{}×{}", status, count))
+            .collect();
+        println!("   状态: {}", statuses.join(", "));
+    }
+}
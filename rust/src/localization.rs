@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::metadata::{JsonStyle, NftMetadata};
+
+// 输出目录中存放各语言文件的文件夹，整体上传为一个 CID
+pub const LOCALIZATION_DIR: &str = "localization";
+
+// ✅ config.toml 中的 [localization] 段：OpenSea 的多语言扩展
+//
+// [localization]
+// default = "en"
+//
+// [localization.locales]
+// zh = "locales/zh.csv"
+// es = "locales/es"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LocalizationConfig {
+    // 元数据本身的文字所用的语言
+    pub default: String,
+    // 其它语言 -> CSV (表头为 token_id,name,description) 或文件夹 (<token ID>.json)
+    pub locales: BTreeMap<String, PathBuf>,
+}
+
+impl Default for LocalizationConfig {
+    fn default() -> Self {
+        Self {
+            default: "en".to_string(),
+            locales: BTreeMap::new(),
+        }
+    }
+}
+
+fn is_locale_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl LocalizationConfig {
+    // 返回配置中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !is_locale_name(&self.default) {
+            problems.push(format!("default 不是有效的语言代码: \"{}\"", self.default));
+        }
+        if self.locales.contains_key(&self.default) {
+            problems.push(format!(
+                "{} 是 default 语言，使用元数据本身的文字，不需要写在 locales 中",
+                self.default
+            ));
+        }
+        for locale in self.locales.keys() {
+            if !is_locale_name(locale) {
+                problems.push(format!("locales 中的语言代码无效: \"{}\"", locale));
+            }
+        }
+        problems
+    }
+
+    // ✅ 读取每种语言的文字
+    pub fn load(&self) -> Result<Localization> {
+        let mut texts = BTreeMap::new();
+        for (locale, path) in &self.locales {
+            let entries = if path.is_dir() {
+                load_locale_dir(path)?
+            } else {
+                load_locale_csv(path)?
+            };
+            texts.insert(locale.clone(), entries);
+        }
+        Ok(Localization {
+            default: self.default.clone(),
+            texts,
+        })
+    }
+}
+
+// 一种语言中一个 token 的文字，没有的字段使用 default 语言的
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizedText {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+// token ID 写成 007 和 7 是同一个 token
+fn normalize_key(key: &str) -> String {
+    key.parse::<u64>()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|_| key.to_string())
+}
+
+// 读取一种语言的 CSV：第一行是表头，第一列为 token ID (单件为图片文件名去掉扩展名)，
+// 其余列为 name 和 / 或 description；最后一列可以包含逗号
+pub fn load_locale_csv(path: &Path) -> Result<BTreeMap<String, LocalizedText>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取翻译文件 {:?} 失败: {}", path, e))?;
+    let mut rows = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let Some((_, header)) = rows.next() else {
+        return Ok(BTreeMap::new());
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    if columns.len() < 2
+        || columns[1..]
+            .iter()
+            .any(|column| *column != "name" && *column != "description")
+    {
+        return Err(anyhow!(
+            "❌ 翻译文件 {:?} 的表头应为 token_id,name,description (可以只有 name 或 description): {}",
+            path,
+            header
+        ));
+    }
+
+    let mut texts = BTreeMap::new();
+    for (line_number, line) in rows {
+        let cells: Vec<String> = line
+            .splitn(columns.len(), ',')
+            .map(|cell| cell.trim().trim_matches('"').to_string())
+            .collect();
+        if cells.len() != columns.len() {
+            return Err(anyhow!(
+                "❌ 翻译文件 {:?} 第 {} 行应有 {} 列: {}",
+                path,
+                line_number,
+                columns.len(),
+                line
+            ));
+        }
+        let mut text = LocalizedText::default();
+        for (column, cell) in columns[1..].iter().zip(&cells[1..]) {
+            if cell.is_empty() {
+                continue;
+            }
+            match *column {
+                "name" => text.name = Some(cell.clone()),
+                _ => text.description = Some(cell.clone()),
+            }
+        }
+        let key = normalize_key(&cells[0]);
+        if texts.insert(key.clone(), text).is_some() {
+            return Err(anyhow!("❌ 翻译文件 {:?} 中 {} 出现了多次", path, key));
+        }
+    }
+    Ok(texts)
+}
+
+// 读取一种语言的文件夹：每个 <token ID>.json 为 {"name": ..., "description": ...}
+pub fn load_locale_dir(dir: &Path) -> Result<BTreeMap<String, LocalizedText>> {
+    let mut texts = BTreeMap::new();
+    for entry in
+        fs::read_dir(dir).map_err(|e| anyhow!("❌ 读取翻译文件夹 {:?} 失败: {}", dir, e))?
+    {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)?;
+        let text: LocalizedText = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("❌ 翻译文件 {:?} 格式错误: {}", path, e))?;
+        texts.insert(normalize_key(stem), text);
+    }
+    Ok(texts)
+}
+
+// 写入语言文件的一个 token
+pub struct LocalizedToken<'a> {
+    // 翻译文件中的键：token ID，单件为图片文件名去掉扩展名
+    pub key: String,
+    // localization/ 下的子文件夹 (元数据文件名)，单件为空
+    pub folder: String,
+    pub metadata: &'a NftMetadata,
+}
+
+// ✅ 读取好的多语言文字
+#[derive(Debug, Clone, Default)]
+pub struct Localization {
+    pub default: String,
+    // 语言 -> token -> 文字
+    pub texts: BTreeMap<String, BTreeMap<String, LocalizedText>>,
+}
+
+impl Localization {
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    // default 语言在前，其它按字母顺序
+    pub fn locales(&self) -> Vec<String> {
+        std::iter::once(self.default.clone())
+            .chain(self.texts.keys().cloned())
+            .collect()
+    }
+
+    // ✅ 为每个 token 写入每种语言的文件 <dir>/<folder>/<locale>.json (default 语言也写一份)，
+    // 返回每种语言中缺少翻译、使用了 default 语言文字的 token 数
+    pub fn write_files(
+        &self,
+        dir: &Path,
+        tokens: &[LocalizedToken],
+        style: JsonStyle,
+    ) -> Result<BTreeMap<String, usize>> {
+        let mut missing = BTreeMap::new();
+        for token in tokens {
+            let folder = dir.join(&token.folder);
+            fs::create_dir_all(&folder)?;
+            let default = LocalizedText {
+                name: Some(token.metadata.name.clone()),
+                description: Some(token.metadata.description.clone()),
+            };
+            fs::write(
+                folder.join(format!("{}.json", self.default)),
+                style.file_json(&default)?,
+            )?;
+            for (locale, texts) in &self.texts {
+                let text = texts.get(&token.key);
+                if text.is_none() {
+                    *missing.entry(locale.clone()).or_insert(0) += 1;
+                }
+                let text = text.cloned().unwrap_or_default();
+                let text = LocalizedText {
+                    name: text.name.or_else(|| default.name.clone()),
+                    description: text.description.or_else(|| default.description.clone()),
+                };
+                fs::write(
+                    folder.join(format!("{}.json", locale)),
+                    style.file_json(&text)?,
+                )?;
+            }
+        }
+        Ok(missing)
+    }
+
+    // ✅ 写入元数据的 localization 字段，{locale} 由市场替换为语言代码
+    pub fn block(&self, cid: &str, folder: &str) -> Value {
+        let path = if folder.is_empty() {
+            cid.to_string()
+        } else {
+            format!("{}/{}", cid, folder)
+        };
+        json!({
+            "uri": format!("ipfs://{}/{{locale}}.json", path),
+            "default": self.default,
+            "locales": self.locales(),
+        })
+    }
+}
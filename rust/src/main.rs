@@ -1,253 +1,2611 @@
 use anyhow::{Result, anyhow};
-use chrono::Utc;
-use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
-use std::io::{self, Write};
+use chrono::{DateTime, Utc};
+use clap::{ArgAction, Args, Parser, Subcommand};
+use rust::allowlist::{parse_allowlist_csv, write_allowlist};
+use rust::arweave::ArweaveConfig;
+use rust::backend::{
+    CliBackend, ClusterBackend, FailoverBackend, FilebaseBackend, FilebaseConfig, HttpBackend,
+    IpfsBackend, MemoryBackend,
+};
+use rust::backup::{BackupConfig, backup_directory};
+use rust::bundler::mirror_manifest;
+use rust::cancel::{self, CancellableBackend, install_handler};
+use rust::car::write_car;
+use rust::catalog::Catalog;
+use rust::checksums::{print_checksum_report, verify_checksums};
+use rust::cid::{CidInfo, Multibase, codec_name, hash_name, same_content};
+use rust::clone::{CloneOptions, clone_collection, parse_ipfs_path, print_clone_report};
+use rust::compare::{Run, compare_runs, render_comparison};
+use rust::config::{Config, DEFAULT_CONFIG_FILE};
+use rust::contract::ContractConfig;
+use rust::copy::{CopyOptions, SymlinkMode};
+use rust::daemon::{DaemonOptions, ManagedDaemon};
+#[cfg(feature = "tui")]
+use rust::dashboard::{Dashboard, DashboardBackend, DashboardScreen};
+use rust::demo::{DEMO_IMAGES_DIR, generate_demo_images, print_demo_summary};
+use rust::deploy::{DeployOptions, build_deploy_config, write_deploy_config};
+use rust::diff::{MetadataSource, diff_metadata, load_metadata_set, render_diff};
+use rust::doctor::{CheckStatus, run_doctor};
+use rust::encryption::{ALGORITHM, CollectionKey, EncryptedBackend, decrypt_path, fetch_decrypt};
+use rust::estimate::{Estimate, estimate_collection, format_bytes, print_estimate, project_tier};
+use rust::eth::{check_transaction_config, set_base_uri};
+use rust::exit_code::ExitCode;
+use rust::expiry::{ExpiryRecord, cleanup_expired, parse_expiry, tag_expiry};
+use rust::extract::{ArchiveFormat, extract_archive};
+use rust::filecoin::FilecoinConfig;
+use rust::gallery::{GalleryConfig, write_gallery};
+use rust::hooks::{self, on_failure};
+use rust::i18n::{self, Lang};
+use rust::init::run_init;
+use rust::inspect::{cat_file, inspect_path, list_tree, print_tree};
+use rust::layout::{LayoutConfig, NamingConfig, OutputLayout, TokenIdStrategy};
+use rust::legacy::{SourceImpl, import_output};
+use rust::links::{LINKS_FILE_NAME, render_links_csv, token_links};
+use rust::loadtest::{LoadTestOptions, parse_duration, print_report, run_load_test};
+use rust::localization::Localization;
+use rust::manifest::{MANIFEST_FILE_NAME, Manifest};
+use rust::metadata::{ExtraFields, JsonStyle, JsonSuffix, parse_metadata};
+use rust::metrics::{MeteredBackend, Metrics, serve};
+use rust::migrate::{MigrateOptions, migrate_collection, print_migration_report};
+use rust::monitor::{
+    DEFAULT_GATEWAY, MonitorOptions, check_manifest, discover_manifests, parse_schedule,
+    run_checks, run_scheduled,
+};
+use rust::onchain::{
+    DEFAULT_TOKEN_URI_FUNCTION, OnchainOptions, detect_json_suffix, print_onchain_report,
+    verify_onchain,
+};
+use rust::patch::{PatchOptions, patch_descriptions, print_patch_report};
+use rust::pinning::collection_name;
+use rust::preview::PreviewConfig;
+use rust::provide::{ProvideConfig, check_reports, provide_manifest};
+use rust::remote_pin::{pin_manifest, select_services};
+use rust::remote_source::{RemoteSource, fetch_source};
+use rust::report::{ReportFormat, Timings, build_report, write_reports};
+use rust::rewrite::{RewriteOptions, print_rewrite_report, rewrite_metadata_uris};
+use rust::runbook::{RunbookContext, find_previous, manifest_file, write_runbook};
+use rust::schema::{SchemaFormat, SchemaRoot, export_schema};
+use rust::secrets::{delete_credential, store_credential};
+use rust::signing::{
+    SIGNATURE_FILE_NAME, SignatureFile, SignatureScheme, compare_with_manifest, sign_manifest,
+    verify_signature,
+};
+use rust::standards::{MetadataFormat, MetadataStandard};
+use rust::storage_deal::{print_archives, refresh_archives, submit_archives};
+use rust::throttle::{Throttle, ThrottleConfig, parse_rate};
+use rust::timeout::{Operation, TimeoutConfig};
+use rust::unixfs::{ImportOptions, compute_cid};
+use rust::update::{MetadataPatch, UpdateOptions, print_update_report, update_metadata};
+use rust::usage::{applied_tiers, collect_usage, print_usage, query_services};
+use rust::verbosity::{Verbosity, set_verbosity};
+use rust::watch::{WatchOptions, watch_directory};
+use rust::watermark::WatermarkConfig;
+use rust::webhook::report;
+use rust::window::{ScheduleWindow, WindowedBackend};
+use rust::workflow::{BatchOptions, SingleOptions, process_batch_collection, process_single_nft};
+use rust::{eprintln, print, println, verbose};
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use walkdir::WalkDir;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-// ✅ 配置开关
-const USE_JSON_SUFFIX: bool = false;
+const DEFAULT_SINGLE_IMAGE: &str = "../assets/image/IMG_20210626_180340.jpg";
+const DEFAULT_BATCH_DIR: &str = "../assets/batch_images";
 
-// ✅ 定义元数据结构体
-#[derive(Serialize, Deserialize)]
-struct Attribute {
-    trait_type: String,
-    value: serde_json::Value,
+#[derive(Parser)]
+#[command(version, about = "将图片和 NFT 元数据上传到 IPFS")]
+struct Cli {
+    /// 本地输出目录
+    #[arg(long, global = true, default_value = "output")]
+    output: PathBuf,
+
+    /// 通过 HTTP RPC 连接节点 (URL、multiaddr、unix socket 或配置文件中 [[api_endpoints]] 的名称)，不指定时读取环境变量 IPFS_API，都没有时使用本地 ipfs 命令行
+    #[arg(long, global = true)]
+    api: Option<String>,
+
+    /// 通过 ipfs-cluster REST API 上传并固定 (如 http://127.0.0.1:9094)
+    #[arg(long, global = true, conflicts_with = "api")]
+    cluster: Option<String>,
+
+    /// 复制到多少个集群 peer (同时设置 replication-min 和 max，-1 表示所有 peer)
+    #[arg(long, global = true, requires = "cluster", allow_hyphen_values = true)]
+    replication_factor: Option<i32>,
+
+    /// 上传到 Filebase 的 IPFS bucket (S3 兼容 API，密钥见 FILEBASE_ACCESS_KEY / FILEBASE_SECRET_KEY)
+    #[arg(long, global = true, value_name = "BUCKET", conflicts_with_all = ["api", "cluster"])]
+    filebase: Option<String>,
+
+    /// 配置文件路径 (默认读取当前目录下的 config.toml，如存在)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// 在该地址提供 Prometheus 指标 (如 127.0.0.1:9898)，适用于 watch / monitor 等常驻模式
+    #[arg(long, global = true)]
+    metrics_addr: Option<String>,
+
+    /// 上传时显示全屏仪表盘 (进度、当前任务、吞吐量、错误和各节点 pin 状态)，输出写入 <输出目录>/tui_<时间>.log
+    #[cfg(feature = "tui")]
+    #[arg(long, global = true)]
+    tui: bool,
+
+    /// 节点未运行时自动启动 `ipfs daemon` 并等待 API 就绪
+    #[arg(long, global = true)]
+    autostart_daemon: bool,
+
+    /// 工作流结束后关闭自动启动的 daemon (配合 --autostart-daemon，适用于 CI)
+    #[arg(long, global = true, requires = "autostart_daemon")]
+    shutdown_daemon: bool,
+
+    /// 使用内存中的后端：计算与 Kubo 相同的 CID，但内容在退出后丢弃 (离线试运行，不需要节点和网络)
+    #[arg(long, global = true, conflicts_with_all = ["api", "cluster", "filebase"])]
+    memory: bool,
+
+    /// 只在该时间段内上传 (本地时间，如 01:00-06:00)，窗口外自动暂停
+    #[arg(long, global = true)]
+    schedule_window: Option<ScheduleWindow>,
+
+    /// 元数据文件名后缀: none、json，或 auto (读取 [contract] 中合约已有的 tokenURI 判断)，覆盖配置文件
+    #[arg(long, global = true, value_name = "MODE")]
+    json_suffix: Option<JsonSuffix>,
+
+    /// 元数据 JSON 的写法: pretty、minified (无空白) 或 canonical (RFC 8785，键排序、无空白)，覆盖配置文件中的 json_style
+    #[arg(long, global = true, value_name = "STYLE")]
+    json_style: Option<JsonStyle>,
+
+    /// 元数据 JSON 不带缩进和换行 (等同于 --json-style minified)
+    #[arg(long, global = true, conflicts_with_all = ["json_style", "pretty"])]
+    minify: bool,
+
+    /// 元数据 JSON 带缩进 (等同于 --json-style pretty)
+    #[arg(long, global = true, conflicts_with = "json_style")]
+    pretty: bool,
+
+    /// 元数据标准: erc721、metaplex (Solana)、tzip21 (Tezos) 或 cip25 (Cardano)，覆盖配置文件中的 metadata_standard
+    #[arg(long, global = true)]
+    standard: Option<MetadataStandard>,
+
+    /// 为超过 [preview] 最大尺寸的图片生成预览图，元数据的 image 指向预览图 (等同于 [preview] enabled = true)
+    #[arg(long, global = true)]
+    preview: bool,
+
+    /// 保留图片中的 EXIF / XMP 等信息 (默认上传前删除 GPS 位置、设备型号等，避免永久公开在 IPFS 上)
+    #[arg(long, global = true)]
+    keep_exif: bool,
+
+    /// 覆盖已存在的输出目录 (默认拒绝覆盖之前的结果)
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// 被 Ctrl-C / SIGTERM 中断时取消固定本次已上传的内容，让节点可以回收这些块 (默认保留，记录在 cancelled_*.json 中)
+    #[arg(long, global = true)]
+    unpin_on_cancel: bool,
+
+    /// 严格模式：把集合中的可疑问题 (如不同 token 的图片内容相同) 当作错误，在上传前终止
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// 按 [watermark] 为图片生成加水印的副本并单独上传，用于揭晓前的公开预览 (等同于 [watermark] enabled = true)
+    #[arg(long, global = true)]
+    watermark: bool,
+
+    /// 上传前用 [encryption] 中的集合密钥加密图片和元数据，IPFS 上只有密文 (等同于 [encryption] enabled = true)
+    #[arg(long, global = true)]
+    encrypt: bool,
+
+    /// 上传限速 (字节/秒，如 500K、2M)，覆盖配置文件 [throttle] 中的 bytes_per_sec；只对 HTTP 后端生效
+    #[arg(long, global = true, value_name = "RATE", value_parser = parse_rate)]
+    upload_limit: Option<u64>,
+
+    /// 为本次运行设置过期时间 (如 30d、12h、2025-12-31)，记录在 catalog.json 中，到期后用 `pins expired` 清理
+    #[arg(long, global = true, value_name = "WHEN", value_parser = parse_expiry)]
+    expires: Option<DateTime<Utc>>,
+
+    /// 上传后递归固定根 CID，向 DHT 宣告并查找提供者，确认内容能被其它节点取回 (等同于 [provide] enabled = true)
+    #[arg(long, global = true)]
+    provide: bool,
+
+    /// 输出语言: zh 或 en (消息表中没有的消息保持中文，--help 仍为中文)
+    #[arg(long, global = true, env = "IPFS_UPLOADER_LANG", default_value_t = Lang::Zh)]
+    lang: Lang,
+
+    /// 只输出 ASCII：emoji 换成 [OK] / [ERROR] / [WARN] 等标记，全角标点换成半角 (适合日志系统和不支持 emoji 的终端)
+    #[arg(long, global = true, env = "IPFS_UPLOADER_ASCII")]
+    ascii: bool,
+
+    /// 安静模式：标准输出只保留警告和错误 (适合脚本和 CI，结果见输出目录中的清单)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// 输出更多信息：-v 显示配置来源和用时，-vv 再显示每个节点请求 (写到标准错误)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// 工作流一：处理单个 NFT
+    Single {
+        #[arg(default_value = DEFAULT_SINGLE_IMAGE)]
+        image: PathBuf,
+        /// 元数据包在目录中上传 (metadata/<名称>.json)，token URI 带上文件名 (等同于 wrap_single_metadata = true)
+        #[arg(long)]
+        wrap: bool,
+    },
+    /// 工作流二：处理批量 NFT 集合
+    Batch(BatchArgs),
+    /// 生成几张示例图片并跑一遍批量流程，用来确认工具链和节点配置可以正常工作
+    Demo {
+        /// 示例图片数量
+        #[arg(long, default_value_t = 5)]
+        count: u64,
+        /// 汇总中显示的网关
+        #[arg(long, default_value = DEFAULT_GATEWAY)]
+        gateway: String,
+    },
+    /// 监听文件夹，自动上传新放入或修改过的图片
+    Watch {
+        dir: PathBuf,
+        /// 文件事件静默多少毫秒后开始处理
+        #[arg(long, default_value_t = 1000)]
+        debounce_ms: u64,
+    },
+    /// 检查清单中的 CID 是否仍被固定并能通过网关访问
+    Verify {
+        /// 要检查的 manifest.json (默认检查输出目录下的所有清单)
+        manifests: Vec<PathBuf>,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// 列出已上传目录的内容：名称、大小、子 CID 和文件总大小
+    Ls {
+        /// CID、<CID>/<子路径>、ipfs:// 或网关地址
+        path: String,
+        /// 展开所有子目录
+        #[arg(long, short)]
+        recursive: bool,
+    },
+    /// 查看已上传的文件，JSON 会格式化显示
+    Cat {
+        /// CID、<CID>/<子路径>、ipfs:// 或网关地址
+        path: String,
+        /// 原样保存到文件，而不是显示
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// 对已有的清单重新固定根 CID、向 DHT 宣告并报告提供者数量
+    Provide {
+        /// 要宣告的 manifest.json (默认为输出目录下的所有清单)
+        manifests: Vec<PathBuf>,
+    },
+    /// 常驻模式：按 cron 计划定期巡检、重新 pin 并发出告警
+    Monitor {
+        manifests: Vec<PathBuf>,
+        /// cron 表达式 (分 时 日 月 周)
+        #[arg(long, default_value = "0 * * * *")]
+        schedule: String,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// 只修改已有运行的元数据描述，重新上传元数据并更新清单
+    PatchDescriptions {
+        /// manifest.json 或其所在目录
+        manifest: PathBuf,
+        /// 新的描述模板，可用 {name}、{token_id}、{image}、{description} (原描述)
+        #[arg(long)]
+        template: String,
+        /// 只显示会修改的内容，不写文件也不上传
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 按补丁文件 (TOML / JSON) 修改已有运行的元数据属性或字段，只改写受影响的文件，重新上传元数据并显示差异
+    UpdateMetadata {
+        /// manifest.json 或其所在目录
+        manifest: PathBuf,
+        /// 补丁文件，见 README 中的格式
+        #[arg(long)]
+        patch: PathBuf,
+        /// 只显示会修改的内容，不写文件也不上传
+        #[arg(long)]
+        dry_run: bool,
+        /// 同时把差异报告写入该文件 (Markdown)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 从元数据文件夹的 CID 下载整个集合，重建本地的 images/ + metadata/ 目录和清单
+    Clone {
+        /// 元数据文件夹 (ipfs://<cid>、/ipfs/<cid> 或 CID)
+        uri: String,
+        /// 下载到该目录 (默认 <输出目录>/clone_<cid>)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 迁移已部署的集合：下载后在当前节点上固定 (或重新上传)，再 pin 到配置的 Pinning 服务
+    Migrate {
+        /// 元数据文件夹 (ipfs://<cid>、/ipfs/<cid> 或 CID)
+        uri: String,
+        /// 下载到该目录 (默认 <输出目录>/migrate_<cid>)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// 用当前节点重新上传 (CIDv1)，生成新的图片和元数据 CID
+        #[arg(long)]
+        re_add: bool,
+        /// 只 pin 到这些服务 (配置中的 name，默认全部)
+        #[arg(long = "service")]
+        services: Vec<String>,
+        /// pin 名称中的集合名 (默认使用下载目录名)
+        #[arg(long)]
+        collection: Option<String>,
+        /// 额外的标签，可重复 (如 --tag project=my-nft)
+        #[arg(long = "tag", value_parser = parse_tag)]
+        tags: Vec<(String, String)>,
+    },
+    /// 把元数据文件夹中 image / animation_url 的前缀替换为新前缀 (如网关地址 → ipfs://)，检查后重新上传
+    RewriteUris {
+        /// 元数据文件夹 (如 output/collection_xxx/metadata)
+        dir: PathBuf,
+        /// 原来的前缀 (如 https://gateway.pinata.cloud/ipfs/<CID>/)
+        #[arg(long)]
+        from: String,
+        /// 新的前缀 (如 ipfs://<CID>/)
+        #[arg(long)]
+        to: String,
+        /// 只显示会改写的内容，不写文件也不上传
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 巡检一次并生成 mint 当天的操作手册 (runbook.md)
+    Runbook {
+        /// manifest.json 或其所在目录
+        manifest: PathBuf,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// 生成预览页面 (运行目录中的 preview/index.html)，按本地元数据显示每个 token 的图片、名称和属性
+    Gallery {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的一次运行)
+        manifest: Option<PathBuf>,
+        /// 显示 ipfs:// 图片的网关 (覆盖 [gallery] gateway)
+        #[arg(long)]
+        gateway: Option<String>,
+        /// 直接引用运行目录中的本地图片，不经过网关
+        #[arg(long, conflicts_with = "gateway")]
+        local: bool,
+    },
+    /// 生成一次运行的上传报告 (report.md / report.html)，同时检查根 CID 是否仍被固定
+    Report {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的一次运行)
+        manifest: Option<PathBuf>,
+        /// 报告格式: markdown 或 html，可多次指定 (默认使用 [report] formats)
+        #[arg(long, value_name = "FORMAT")]
+        format: Vec<ReportFormat>,
+    },
+    /// 按固定速率随机请求一次运行的元数据和图片 URL，报告错误率和延迟分位数
+    Loadtest {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的一次运行)
+        manifest: Option<PathBuf>,
+        /// 要压测的网关 (如专用网关 https://example.mypinata.cloud)
+        #[arg(long)]
+        gateway: String,
+        /// 每秒请求数
+        #[arg(long, default_value_t = 50)]
+        rps: u32,
+        /// 持续时间 (如 60s、5m)
+        #[arg(long, default_value = "60s", value_parser = parse_duration)]
+        duration: Duration,
+        /// 单个请求的超时时间
+        #[arg(long, default_value = "30s", value_parser = parse_duration)]
+        timeout: Duration,
+        /// 同时进行的请求上限
+        #[arg(long, default_value_t = 256)]
+        concurrency: usize,
+    },
+    /// 对比同一集合的两次运行 (如本地节点 vs Pinata 重新上传)，判断 Base URI 能否互换
+    CompareRuns {
+        /// 运行 A 的 manifest.json 或其所在目录
+        run_a: PathBuf,
+        /// 运行 B 的 manifest.json 或其所在目录
+        run_b: PathBuf,
+        /// 同时把报告写入该文件 (Markdown)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 逐个 token 对比两个元数据文件夹 (本地目录或 CID)，列出新增、删除和修改的字段
+    Diff {
+        /// 本地元数据文件夹、运行目录，或 ipfs://<cid>
+        a: String,
+        b: String,
+        /// 同时把报告写入该文件 (Markdown)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 读取合约中每个 token 的 tokenURI，确认它能通过 IPFS 读取且与本地元数据一致 (需要配置 [contract])
+    VerifyOnchain {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+        /// 起始 token ID (默认检查清单中的所有 token)
+        #[arg(long, requires = "to")]
+        from: Option<u64>,
+        /// 结束 token ID (包含)
+        #[arg(long, requires = "from")]
+        to: Option<u64>,
+        /// 查询 URI 的函数，ERC-1155 合约使用 uri(uint256)
+        #[arg(long, default_value = DEFAULT_TOKEN_URI_FUNCTION)]
+        function: String,
+    },
+    /// 把清单中的 CID pin 到配置的远程 Pinning 服务，带上可读的名称和标签
+    PinRemote {
+        /// manifest.json 或其所在目录
+        manifest: PathBuf,
+        /// 只使用这些服务 (配置中的 name，默认全部)
+        #[arg(long = "service")]
+        services: Vec<String>,
+        /// pin 名称中的集合名 (默认使用输出目录名)
+        #[arg(long)]
+        collection: Option<String>,
+        /// 额外的标签，可重复 (如 --tag project=my-nft)
+        #[arg(long = "tag", value_parser = parse_tag)]
+        tags: Vec<(String, String)>,
+    },
+    /// 管理 pin 的过期时间：为运行设置过期时间，列出并清理已过期的内容
+    Pins {
+        #[command(subcommand)]
+        command: PinsCommand,
+    },
+    /// 把清单中每个 token 的图片和元数据镜像到 Arweave (配置 [arweave])，ar:// 地址写回清单
+    Arweave {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+        /// 已经镜像过的文件也重新上传
+        #[arg(long)]
+        force: bool,
+    },
+    /// 把运行目录 (图片、元数据、清单) 同步到 S3 兼容存储或 GCS (配置 [backup])，只上传新增和变化的文件
+    Backup {
+        /// 运行目录或其中的 manifest.json (默认使用输出目录下最新的运行)
+        dir: Option<PathBuf>,
+        /// 只列出将要上传的文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 把运行目录打包成 CAR 并提交 Filecoin 存储交易 (配置 [filecoin])，跟踪交易状态
+    Filecoin {
+        #[command(subcommand)]
+        command: FilecoinCommand,
+    },
+    /// 导出每个 token 的 URI、网关地址和市场链接 (CSV，链接需要配置 [contract])
+    ExportUrls {
+        /// manifest.json 或其所在目录
+        manifest: PathBuf,
+        /// 生成网关地址使用的网关
+        #[arg(long, default_value = DEFAULT_GATEWAY)]
+        gateway: String,
+        /// 写入该文件 (默认 <清单目录>/urls.csv)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 生成部署合约用的 deploy-config.json (Base URI、来源证明哈希、最大供应量等)，供 Foundry / Hardhat 脚本读取
+    DeployConfig {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+        /// 集合级元数据的地址 (contractURI)
+        #[arg(long, default_value = "")]
+        contract_uri: String,
+        /// 揭晓前的占位元数据地址
+        #[arg(long, default_value = "")]
+        hidden_uri: String,
+        /// 最大供应量 (默认等于集合中的 token 数)
+        #[arg(long)]
+        max_supply: Option<u64>,
+        /// 同时写入 deploy.env
+        #[arg(long)]
+        env: bool,
+        /// 白名单地址 CSV，重新生成 allowlist_proofs.json (默认使用清单目录中已有的)
+        #[arg(long, value_name = "CSV")]
+        allowlist: Option<PathBuf>,
+    },
+    /// 估算批量上传的数据量、文件数和套餐费用 (不需要 IPFS 节点)
+    Estimate {
+        /// 图片文件夹，默认为 [collection] images_dir
+        dir: Option<PathBuf>,
+        /// 列出最大的多少个文件
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+    /// 统计输出目录中各服务商 (上传后端和 Pinning 服务) 保存的 CID 和大小，并与 [[pricing_tiers]] 比较
+    Usage {
+        /// 准备上传的图片文件夹：检查加上这次上传后是否超出套餐
+        #[arg(long)]
+        next: Option<PathBuf>,
+        /// 只按本地清单统计，不查询 Pinning 服务的接口
+        #[arg(long)]
+        offline: bool,
+    },
+    /// 检查运行环境 (ipfs 命令行、节点连接、Pinning 服务认证、输出目录、磁盘空间)
+    Doctor,
+    /// 交互式创建配置文件 (集合名称、描述模板、图片文件夹、后缀、标准和上传方式)，写入 --config 或 config.toml
+    Init,
+    /// 导入 Python / TypeScript / Go 实现的输出目录，生成 manifest.json
+    Import {
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+        /// 来源实现 (python、typescript、go)，默认根据路径推断
+        #[arg(long)]
+        source: Option<SourceImpl>,
+    },
+    /// 清单和元数据的结构定义 (供 Python / TypeScript / Go 实现使用)，以及检查元数据文件
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+    /// 管理保存在系统钥匙串中的 API 密钥
+    Credentials {
+        #[command(subcommand)]
+        command: CredentialsCommand,
+    },
+    /// 将文件或文件夹打包为 CAR 文件 (不需要 IPFS 节点)
+    Car {
+        path: PathBuf,
+        /// CAR 文件路径 (默认 <输出目录>/<名称>.car)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 在本地计算文件或文件夹的 CID，结果与上传后节点返回的 CID 相同；也可以转换和查看 CID (不需要 IPFS 节点)
+    #[command(args_conflicts_with_subcommands = true)]
+    Cid {
+        path: Option<PathBuf>,
+        /// 期望的 CID (如清单中的 images_cid，v0 / v1 和任意编码均可)，不一致时返回错误
+        #[arg(long)]
+        expect: Option<String>,
+        #[command(subcommand)]
+        command: Option<CidCommand>,
+    },
+    /// 按输出目录中的 CHECKSUMS.sha256 / CHECKSUMS.b3 重新检查每个文件 (不需要 IPFS 节点)
+    VerifyLocal {
+        /// 单件或集合的输出目录
+        dir: PathBuf,
+    },
+    /// 用 ed25519 或以太坊私钥签名清单 (根 CID、来源证明哈希、时间)，写入 manifest.sig.json
+    Sign {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+        /// 签名方式: ed25519 或 ethereum (默认使用 [signing] 中的 scheme)
+        #[arg(long)]
+        scheme: Option<SignatureScheme>,
+        /// 从文件读取十六进制私钥 (默认使用 [signing] 中的 key)
+        #[arg(long, value_name = "FILE")]
+        key_file: Option<PathBuf>,
+    },
+    /// 解密从网关下载的加密文件或文件夹 (不需要 IPFS 节点)
+    Decrypt {
+        /// 加密的文件或文件夹
+        input: PathBuf,
+        /// 写入该文件或文件夹
+        #[arg(long)]
+        out: PathBuf,
+        /// 从文件读取十六进制集合密钥 (默认使用 [encryption] 中的 key)
+        #[arg(long, value_name = "FILE")]
+        key_file: Option<PathBuf>,
+        /// 先按该清单中记录的密钥指纹检查密钥
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+    /// 从节点读取加密的文件或文件夹并解密到本地
+    FetchDecrypt {
+        /// ipfs://<cid>[/<路径>]、/ipfs/<cid> 或 CID
+        uri: String,
+        /// 写入该文件或文件夹 (默认 <输出目录>/decrypt_<cid>)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// 从文件读取十六进制集合密钥 (默认使用 [encryption] 中的 key)
+        #[arg(long, value_name = "FILE")]
+        key_file: Option<PathBuf>,
+        /// 先按该清单中记录的密钥指纹检查密钥
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+    /// 验证 manifest.sig.json 的签名，并检查它与清单和图片是否一致 (不需要 IPFS 节点)
+    VerifySignature {
+        /// manifest.sig.json 或其所在目录
+        path: PathBuf,
+        /// 要求签名者为该 ed25519 公钥或以太坊地址
+        #[arg(long)]
+        signer: Option<String>,
+    },
 }
 
-#[derive(Serialize, Deserialize)]
-struct NftMetadata {
-    name: String,
-    description: String,
-    image: String,
-    attributes: Vec<Attribute>,
+#[derive(Subcommand)]
+enum SchemaCommand {
+    /// 导出 manifest.json 和元数据的结构定义
+    Export {
+        /// 输出格式: jsonschema、typescript、python
+        #[arg(long, default_value = "jsonschema")]
+        format: SchemaFormat,
+        /// JSON Schema 的顶层类型: manifest (manifest.json) 或 metadata (单个 token 的元数据)；
+        /// 加上 --strict 时不允许未知字段，链接必须是 ipfs://
+        #[arg(long, default_value = "manifest")]
+        root: SchemaRoot,
+        /// 写入该文件 (默认输出到标准输出)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 按元数据结构检查元数据文件 (文件或文件夹)；加上 --strict 时拒绝未知字段和非 ipfs:// 链接
+    Validate {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
 }
 
-// 核心上传函数 (使用 std::process::Command)
-fn upload_to_ipfs(target_path: &Path) -> Result<String> {
-    if !target_path.exists() {
-        return Err(anyhow!("❌ 路径不存在: {:?}", target_path));
+#[derive(Subcommand)]
+enum CidCommand {
+    /// 在 v0 (Qm…) 和 v1 (bafy…) 之间转换，或换一种编码
+    Convert {
+        #[arg(required = true)]
+        cids: Vec<String>,
+        /// 目标版本: 0 或 1
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(0..=1))]
+        to_version: u64,
+        /// 目标编码: base32 (默认)、base32upper、base36、base58btc、base16、base64、base64url；v0 只能是 base58btc
+        #[arg(long)]
+        base: Option<Multibase>,
+    },
+    /// 显示 CID 的版本、编码、codec 和 multihash，以及其它写法
+    Inspect { cid: String },
+}
+
+#[derive(Subcommand)]
+enum FilecoinCommand {
+    /// 打包图片和元数据的 CAR 并提交交易，交易记录写入清单和 catalog.json
+    Submit {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+        /// 已经提交过的 CID 也重新提交
+        #[arg(long)]
+        force: bool,
+    },
+    /// 查询交易的最新状态 (交易 ID、piece CID)，写回清单和 catalog.json
+    Status {
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PinsCommand {
+    /// 为已有的运行设置过期 (或复查) 时间，重复设置时覆盖
+    Expire {
+        /// 过期时间，如 30d、12h、2w、2025-12-31
+        #[arg(value_parser = parse_expiry)]
+        at: DateTime<Utc>,
+        /// manifest.json 或其所在目录 (默认使用输出目录下最新的清单)
+        manifest: Option<PathBuf>,
+    },
+    /// 列出已过期的运行，--unpin 在当前节点和 Pinning 服务上取消固定
+    Expired {
+        /// 同时列出还没有过期的运行
+        #[arg(long)]
+        all: bool,
+        /// 取消固定已过期的内容 (当前节点和清单中记录的 Pinning 服务)
+        #[arg(long)]
+        unpin: bool,
+        /// 取消固定后再删除本地运行目录
+        #[arg(long, requires = "unpin")]
+        delete: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CredentialsCommand {
+    /// 保存密钥 (交互输入，或从标准输入读取一行)
+    Set {
+        /// 提供方名称，与 [[pinning_services]] 的 name 或 ${keyring:<名称>} 对应
+        provider: String,
+    },
+    /// 删除保存的密钥
+    Delete { provider: String },
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// 图片文件夹，也可以是 .zip、.tar 或 .tar.gz 压缩包 (解压到临时目录后处理)；默认为 [collection] images_dir
+    dir: Option<PathBuf>,
+    /// 集合名称：token 命名为 "<名称> #<ID>"，也用于 [output] 模板中的 {collection} (覆盖 [collection] name)
+    #[arg(long)]
+    name: Option<String>,
+    /// 允许图片文件夹为空并生成空集合
+    #[arg(long)]
+    allow_empty: bool,
+    /// 上传成功后发送交易，把合约的 Base URI 设置为新的元数据文件夹 (需要配置 [contract])
+    #[arg(long)]
+    set_base_uri: bool,
+    /// 白名单地址 CSV，生成 Merkle 根和每个地址的证明 (allowlist_proofs.json)
+    #[arg(long, value_name = "CSV")]
+    allowlist: Option<PathBuf>,
+    /// 读取子文件夹：每个以 token ID 命名的子文件夹是一个 token (等同于 [layout] recursive = true)
+    #[arg(long)]
+    recursive: bool,
+    /// token ID 的来源: filename (文件名中的数字)、index (按名称排序后的序号) 或 csv (映射文件)
+    #[arg(long, value_name = "STRATEGY")]
+    token_ids: Option<TokenIdStrategy>,
+    /// 按文件名分配 ID 时先去掉的前缀，如 ape_
+    #[arg(long, value_name = "PREFIX")]
+    strip_prefix: Option<String>,
+    /// 按序号分配 ID 时第一个文件的 ID
+    #[arg(long, value_name = "ID")]
+    start_id: Option<u64>,
+    /// token ID 映射 CSV (每行 "文件名,token ID")，指定后使用 csv 来源
+    #[arg(long, value_name = "CSV")]
+    id_map: Option<PathBuf>,
+    /// 元数据文件名中 token ID 补零后的宽度，如 4 表示 0001 (覆盖 [naming] pad_width)
+    #[arg(long, value_name = "WIDTH")]
+    pad_width: Option<usize>,
+    /// 把图片重命名为与元数据相同的名称 (等同于 [naming] rename_images = true)
+    #[arg(long)]
+    rename_images: bool,
+    /// 符号链接的处理方式: follow (复制指向的内容)、preserve (保留为链接) 或 skip，覆盖 [copy] symlinks
+    #[arg(long, value_name = "MODE")]
+    symlinks: Option<SymlinkMode>,
+    /// 只处理匹配的文件，如 "*.png" (可多次指定，追加到 [layout] include)
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+    /// 跳过匹配的文件或文件夹，如 "*.psd" (可多次指定，追加到 [layout] exclude)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+    /// 从 URL 列表下载图片 (每行一个 HTTP(S) 地址，可在后面指定文件名)，代替图片文件夹
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["dir", "s3"])]
+    urls: Option<PathBuf>,
+    /// 从 S3 下载 prefix 下的所有图片，如 s3://bucket/art/ (认证配置在 [remote_source])
+    #[arg(long, value_name = "URI", value_parser = RemoteSource::parse_s3, conflicts_with = "dir")]
+    s3: Option<RemoteSource>,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// 用来检查可访问性的网关，可重复指定
+    #[arg(long = "gateway", default_value = DEFAULT_GATEWAY)]
+    gateways: Vec<String>,
+    /// 只报告问题，不自动重新 pin
+    #[arg(long)]
+    no_repin: bool,
+    /// pin 状态缓存的有效期 (秒)，缓存保存在输出目录的 catalog.json 中；0 表示不使用缓存
+    #[arg(long, default_value_t = 3600)]
+    pin_cache_ttl: u64,
+}
+
+impl CheckArgs {
+    fn options(self, timeouts: &TimeoutConfig) -> MonitorOptions {
+        MonitorOptions {
+            gateways: self.gateways,
+            repin: !self.no_repin,
+            gateway_timeout: timeouts.get(Operation::Gateway),
+            pin_cache_ttl: Duration::from_secs(self.pin_cache_ttl),
+        }
     }
+}
 
-    let path_str = target_path
-        .to_str()
-        .ok_or_else(|| anyhow!("无效的文件路径"))?;
+fn manifest_paths(manifests: Vec<PathBuf>, output_root: &Path) -> Result<Vec<PathBuf>> {
+    if manifests.is_empty() {
+        return discover_manifests(output_root);
+    }
+    Ok(manifests)
+}
+
+fn pack_car(path: &Path, out: Option<&Path>, output_root: &Path) -> Result<()> {
+    let name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的路径: {:?}", path))?;
+    let car_path = match out {
+        Some(out) => out.to_path_buf(),
+        None => output_root.join(format!("{}.car", name)),
+    };
+    println!("\n--- 正在打包 CAR: {:?} ---", path);
+    let summary = write_car(path, &car_path, &ImportOptions::default())?;
+    println!("✅ CAR 已生成: {:?}", car_path);
+    println!("   - 根 CID: {}", summary.root);
     println!(
-        "\n--- 正在执行上传命令: ipfs add -r -Q --cid-version 1 {} ---",
-        path_str
+        "   - 块数量: {}，数据大小: {} 字节",
+        summary.blocks, summary.bytes
     );
+    Ok(())
+}
 
-    let output = Command::new("ipfs")
-        .arg("add")
-        .arg("-r") // 递归上传
-        .arg("-Q") // 只输出根 CID
-        .arg("--cid-version")
-        .arg("1")
-        .arg(path_str)
-        .output()?;
+// 本地计算 CID，用来复现或核对已上传内容的 CID
+fn check_cid(path: &Path, expect: Option<&str>) -> Result<()> {
+    if !path.exists() {
+        return Err(anyhow!("❌ 路径不存在: {:?}", path));
+    }
+    let cid = compute_cid(path, &ImportOptions::default())?.to_string();
+    println!("✅ {:?} 的 CID: {}", path, cid);
+    match expect {
+        Some(expected) if !same_content(expected, &cid) => Err(ExitCode::Mismatch.wrap(anyhow!(
+            "❌ CID 不一致: 期望 {}，本地计算为 {}",
+            expected,
+            cid
+        ))),
+        Some(_) => {
+            println!("✅ 与期望的 CID 一致");
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
 
-    if !output.status.success() {
-        return Err(anyhow!(
-            "❌ 上传失败: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+fn run_cid_command(command: &CidCommand) -> Result<()> {
+    match command {
+        CidCommand::Convert {
+            cids,
+            to_version,
+            base,
+        } => {
+            for cid in cids {
+                let parsed: CidInfo = cid.parse()?;
+                let converted = match to_version {
+                    0 => parsed
+                        .to_v0()?
+                        .encode(base.unwrap_or(Multibase::Base58btc))?,
+                    _ => parsed.to_v1().encode(base.unwrap_or(Multibase::Base32))?,
+                };
+                println!("{}", converted);
+            }
+            Ok(())
+        }
+        CidCommand::Inspect { cid } => {
+            let parsed: CidInfo = cid.parse()?;
+            println!("CID:       {}", parsed);
+            println!("版本:      v{}", parsed.version);
+            println!("编码:      {}", parsed.base);
+            println!(
+                "codec:     {} (0x{:x})",
+                codec_name(parsed.codec),
+                parsed.codec
+            );
+            println!(
+                "multihash: {} (0x{:x})，{} 字节",
+                hash_name(parsed.hash),
+                parsed.hash,
+                parsed.digest.len()
+            );
+            let digest: String = parsed.digest.iter().map(|b| format!("{:02x}", b)).collect();
+            println!("摘要:      {}", digest);
+            println!("\n其它写法:");
+            if let Ok(v0) = parsed.to_v0() {
+                println!("  v0:        {}", v0);
+            }
+            let v1 = parsed.to_v1();
+            for base in Multibase::ALL {
+                println!("  {:<10} {}", format!("{}:", base), v1.encode(base)?);
+            }
+            Ok(())
+        }
     }
+}
 
-    let cid = String::from_utf8(output.stdout)?.trim().to_string();
-    println!("✅ 上传成功!");
+fn verify_local(dir: &Path) -> Result<()> {
+    let report = verify_checksums(dir)?;
+    print_checksum_report(&report);
+    if !report.is_ok() {
+        return Err(ExitCode::Mismatch.wrap(anyhow!(
+            "❌ {:?} 与校验文件不一致: {} 个内容不同，{} 个缺失，{} 个未记录",
+            dir,
+            report.mismatched.len(),
+            report.missing.len(),
+            report.unlisted.len()
+        )));
+    }
+    println!("✅ 所有文件与校验文件一致，可以放心重新上传");
+    Ok(())
+}
+
+// 解析 `key=value` 形式的标签
+fn parse_tag(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("❌ 标签格式应为 key=value: {}", s))?;
+    if key.is_empty() {
+        return Err(anyhow!("❌ 标签名不能为空: {}", s));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn pin_remote(
+    config: &Config,
+    manifest_path: &Path,
+    services: &[String],
+    collection: Option<&str>,
+    tags: &[(String, String)],
+) -> Result<()> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let collection = collection.map_or_else(|| collection_name(&manifest), str::to_string);
+    let result = pin_to_services(config, &mut manifest, services, &collection, tags);
+    manifest.save(manifest_path.parent().unwrap_or(Path::new(".")))?;
+    result
+}
+
+// pin 到选择的服务，每个服务的结果记录在清单的 remote_pins 中
+fn pin_to_services(
+    config: &Config,
+    manifest: &mut Manifest,
+    services: &[String],
+    collection: &str,
+    tags: &[(String, String)],
+) -> Result<()> {
+    let mut failed = 0;
+    for service in select_services(&config.pinning_services, services)? {
+        let record = pin_manifest(service, manifest, collection, tags)?;
+        failed += record.failed;
+        manifest.remote_pins.insert(service.name.clone(), record);
+    }
+    if failed > 0 {
+        return Err(anyhow!("❌ {} 个 pin 请求失败", failed));
+    }
+    Ok(())
+}
+
+fn arweave_config(config: &Config) -> Result<&ArweaveConfig> {
+    ensure_valid("[arweave]", config.arweave.problems())?;
+    Ok(&config.arweave)
+}
+
+fn mirror_arweave(config: &Config, manifest_path: &Path, force: bool) -> Result<()> {
+    let (manifest, report) = mirror_manifest(arweave_config(config)?, manifest_path, force)?;
     println!(
-        "   - 名称: {}",
-        target_path.file_name().unwrap().to_str().unwrap()
+        "✅ Arweave 镜像: 上传 {} 个文件 ({} 字节)，跳过 {} 个已镜像，失败 {} 个",
+        report.uploaded, report.bytes, report.skipped, report.failed
     );
-    println!("   - CID: {}", cid);
-    Ok(cid)
+    let first = manifest
+        .tokens
+        .first()
+        .and_then(|token| token.arweave.as_ref());
+    if let Some(uri) = first.and_then(|uris| uris.metadata_uri.as_ref()) {
+        println!(
+            "   - 第一个 token 的元数据: {}",
+            config.arweave.gateway_url(uri)
+        );
+    }
+    if report.failed > 0 {
+        return Err(anyhow!("❌ {} 个文件镜像失败", report.failed));
+    }
+    Ok(())
+}
+
+// [arweave] auto = true 时，单件和批量流程完成后镜像到 Arweave
+fn auto_mirror(config: &Config, manifest: &Manifest) -> Result<()> {
+    if !config.arweave.auto {
+        return Ok(());
+    }
+    mirror_arweave(config, &manifest.output_dir.join(MANIFEST_FILE_NAME), false)
+}
+
+fn backup_config(config: &Config) -> Result<&BackupConfig> {
+    ensure_valid("[backup]", config.backup.problems())?;
+    Ok(&config.backup)
 }
 
-// 上传 JSON 数据的专用函数
-fn upload_json_str_to_ipfs(data: &NftMetadata) -> Result<String> {
-    println!("\n--- 正在上传 JSON 对象 ---");
-    let json_string = serde_json::to_string(data)?;
+fn backup(config: &Config, dir: &Path, dry_run: bool) -> Result<()> {
+    let report = backup_directory(backup_config(config)?, dir, dry_run)?;
+    let action = if dry_run { "将上传" } else { "上传" };
+    println!(
+        "✅ 备份 {}: {} {} 个文件 ({} 字节)，{} 个未变化",
+        report.location, action, report.uploaded, report.bytes, report.unchanged
+    );
+    Ok(())
+}
 
-    let mut child = Command::new("ipfs")
-        .arg("add")
-        .arg("-Q")
-        .arg("--cid-version")
-        .arg("1")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
+// [backup] auto = true 时，单件和批量流程完成后同步运行目录
+// 批量流程上传后的宣告、镜像、归档和签名，按阶段记录耗时
+fn auto_steps(
+    config: &Config,
+    provide: bool,
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+    output_root: &Path,
+    timings: &mut Timings,
+) -> Result<()> {
+    if provide {
+        auto_provide(provide, &config.provide, backend, manifest)?;
+        timings.lap("DHT 宣告");
+    }
+    if config.arweave.auto {
+        auto_mirror(config, manifest)?;
+        timings.lap("Arweave 镜像");
+    }
+    if config.filecoin.auto {
+        auto_archive(config, manifest, output_root)?;
+        timings.lap("Filecoin 归档");
+    }
+    auto_sign(config, manifest)?;
+    Ok(())
+}
 
-    // 将 JSON 字符串写入子进程的标准输入
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(json_string.as_bytes())?;
+// [report] 批量流程完成后在运行目录中写入上传报告
+fn auto_report(
+    config: &Config,
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+    timings: &Timings,
+) -> Result<()> {
+    let run = build_report(manifest, &manifest.output_dir, Some(backend), Some(timings));
+    for path in write_reports(&manifest.output_dir, &run, &config.report.formats)? {
+        println!("📊 上传报告已生成: {:?}", path);
     }
+    Ok(())
+}
 
-    let output = child.wait_with_output()?;
-    if !output.status.success() {
-        return Err(anyhow!(
-            "❌ 上传 JSON 失败: {}",
-            String::from_utf8_lossy(&output.stderr)
+fn auto_backup(config: &Config, manifest: &Manifest) -> Result<()> {
+    if !config.backup.auto {
+        return Ok(());
+    }
+    backup(config, &manifest.output_dir, false)
+}
+
+// --expires：单件和批量流程完成后在 catalog.json 中记录过期时间
+fn auto_expiry(
+    expires: Option<DateTime<Utc>>,
+    manifest: &Manifest,
+    output_root: &Path,
+) -> Result<()> {
+    let Some(expires_at) = expires else {
+        return Ok(());
+    };
+    let mut catalog = Catalog::open(output_root)?;
+    tag_expiry(&mut catalog, manifest, expires_at);
+    catalog.save()?;
+    println!(
+        "⏳ 本次运行将在 {} 过期",
+        expires_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    Ok(())
+}
+
+fn pins(
+    config: &Config,
+    command: &PinsCommand,
+    output_root: &Path,
+    backend: Option<&dyn IpfsBackend>,
+) -> Result<()> {
+    let mut catalog = Catalog::open(output_root)?;
+    match command {
+        PinsCommand::Expire { at, manifest } => {
+            let path = match manifest {
+                Some(path) => manifest_file(path),
+                None => latest_manifest(output_root)?,
+            };
+            tag_expiry(&mut catalog, &Manifest::load(&path)?, *at);
+            catalog.save()?;
+            println!(
+                "⏳ {:?} 将在 {} 过期",
+                path,
+                at.format("%Y-%m-%d %H:%M UTC")
+            );
+            Ok(())
+        }
+        PinsCommand::Expired { all, unpin, delete } => {
+            let now = Utc::now();
+            let expired: Vec<(String, ExpiryRecord)> = catalog
+                .expiry
+                .iter()
+                .filter(|(_, record)| *all || record.is_expired(now))
+                .map(|(dir, record)| (dir.clone(), record.clone()))
+                .collect();
+            if expired.is_empty() {
+                println!("✅ 没有已过期的运行");
+                return Ok(());
+            }
+            println!("\n⏳ {} 个运行:", expired.len());
+            for (dir, record) in &expired {
+                let state = if record.is_expired(now) {
+                    "已过期"
+                } else {
+                    "未过期"
+                };
+                println!(
+                    "   - {} [{} {}] {} 个 CID，{}",
+                    dir,
+                    state,
+                    record.expires_at,
+                    record.cids.len(),
+                    record.token_uri
+                );
+            }
+            let Some(backend) = backend.filter(|_| *unpin) else {
+                return Ok(());
+            };
+            let mut failed = 0;
+            for (dir, record) in expired.iter().filter(|(_, record)| record.is_expired(now)) {
+                println!("\n🧹 {}", dir);
+                let report =
+                    cleanup_expired(dir, record, backend, &config.pinning_services, *delete);
+                println!(
+                    "   取消固定 {} 个，删除远程 pin {} 个{}",
+                    report.unpinned,
+                    report.remote_removed,
+                    if report.deleted_dir {
+                        "，已删除运行目录"
+                    } else {
+                        ""
+                    }
+                );
+                for error in &report.errors {
+                    eprintln!("   ❌ {}", error);
+                }
+                if report.errors.is_empty() {
+                    catalog.expiry.remove(dir);
+                } else {
+                    failed += 1;
+                }
+            }
+            catalog.save()?;
+            if failed > 0 {
+                return Err(anyhow!("❌ {} 个运行没有清理完，可以稍后重试", failed));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn filecoin_config(config: &Config) -> Result<&FilecoinConfig> {
+    ensure_valid("[filecoin]", config.filecoin.problems())?;
+    Ok(&config.filecoin)
+}
+
+fn filecoin(config: &Config, command: &FilecoinCommand, output_root: &Path) -> Result<()> {
+    let filecoin = filecoin_config(config)?;
+    let mut catalog = Catalog::open(output_root)?;
+    match command {
+        FilecoinCommand::Submit { manifest, force } => {
+            let path = match manifest {
+                Some(path) => manifest_file(path),
+                None => latest_manifest(output_root)?,
+            };
+            let failed = submit_archives(filecoin, &path, &mut catalog, *force)?;
+            if failed > 0 {
+                return Err(anyhow!("❌ {} 个根提交失败", failed));
+            }
+            Ok(())
+        }
+        FilecoinCommand::Status { manifest } => {
+            let path = match manifest {
+                Some(path) => manifest_file(path),
+                None => latest_manifest(output_root)?,
+            };
+            let manifest = refresh_archives(filecoin, &path, &mut catalog)?;
+            println!("\n🗄️  Filecoin 归档 ({:?}):", path);
+            print_archives(&manifest);
+            Ok(())
+        }
+    }
+}
+
+// [filecoin] auto = true 时，单件和批量流程完成后打包并提交交易
+fn auto_archive(config: &Config, manifest: &Manifest, output_root: &Path) -> Result<()> {
+    if !config.filecoin.auto {
+        return Ok(());
+    }
+    let mut catalog = Catalog::open(output_root)?;
+    let failed = submit_archives(
+        filecoin_config(config)?,
+        &manifest.output_dir.join(MANIFEST_FILE_NAME),
+        &mut catalog,
+        false,
+    )?;
+    if failed > 0 {
+        eprintln!(
+            "⚠️  {} 个根没有提交成功，可以稍后运行 `filecoin submit` 重试",
+            failed
+        );
+    }
+    Ok(())
+}
+
+fn diff_collections(
+    a: &str,
+    b: &str,
+    out: Option<&Path>,
+    backend: Option<&dyn IpfsBackend>,
+) -> Result<()> {
+    let sources = [MetadataSource::parse(a)?, MetadataSource::parse(b)?];
+    let mut sets = Vec::new();
+    for source in &sources {
+        let set = load_metadata_set(source, backend)?;
+        if !set.skipped.is_empty() {
+            eprintln!(
+                "⚠️  {} 中跳过了不是 JSON 的文件: {}",
+                source.label(),
+                set.skipped.join(", ")
+            );
+        }
+        sets.push(set);
+    }
+    let diff = diff_metadata(&sets[0], &sets[1]);
+    let report = render_diff(&sources[0].label(), &sources[1].label(), &diff);
+    println!("{}", report);
+    if let Some(out) = out {
+        std::fs::write(out, &report)?;
+        println!("📄 对比报告已写入: {:?}", out);
+    }
+    Ok(())
+}
+
+// --minify / --pretty / --json-style 覆盖配置文件中的 json_style
+fn resolve_json_style(cli: &Cli, config: &Config) -> JsonStyle {
+    if cli.minify {
+        JsonStyle::Minified
+    } else if cli.pretty {
+        JsonStyle::Pretty
+    } else {
+        cli.json_style.unwrap_or(config.json_style)
+    }
+}
+
+// clone / migrate 默认下载到 <输出目录>/<前缀>_<cid>
+fn default_download_dir(output_root: &Path, prefix: &str, uri: &str) -> PathBuf {
+    let root = parse_ipfs_path(uri).unwrap_or_else(|_| uri.to_string());
+    let cid = root.split('/').next().unwrap_or(&root);
+    output_root.join(format!("{}_{}", prefix, cid))
+}
+
+// 配置了套餐时，上传前提示会超出的限额
+fn warn_quotas(config: &Config, dir: &Path, output_root: &Path) -> Result<()> {
+    if config.pricing_tiers.is_empty() || !dir.is_dir() {
+        return Ok(());
+    }
+    let estimate = estimate_collection(dir)?;
+    // 按本地清单统计的已用量 (配置中的 used_gb / used_files 更大时以配置为准)
+    let usage = collect_usage(&discover_manifests(output_root)?)?;
+    for tier in &applied_tiers(&usage, &config.pricing_tiers) {
+        let projection = project_tier(&estimate, tier);
+        for warning in &projection.warnings {
+            eprintln!("⚠️  {} {}: {}", tier.provider, tier.name, warning);
+        }
+    }
+    Ok(())
+}
+
+// --tui 时按输入计算预计上传的文件数和字节数 (单件为图片和一个元数据文件)
+#[cfg(feature = "tui")]
+fn dashboard_totals(dashboard: Option<&Dashboard>, input: &Path) -> Result<()> {
+    let Some(dashboard) = dashboard else {
+        return Ok(());
+    };
+    if input.is_dir() {
+        let estimate = estimate_collection(input)?;
+        dashboard.set_totals(
+            estimate.files.len() as u64 + estimate.metadata_files,
+            estimate.total_bytes(),
+        );
+    } else {
+        dashboard.set_totals(2, std::fs::metadata(input)?.len());
+    }
+    Ok(())
+}
+
+// 未指定清单时使用输出目录下最新的一次运行
+fn latest_manifest(output_root: &Path) -> Result<PathBuf> {
+    let mut latest: Option<(String, PathBuf)> = None;
+    for path in discover_manifests(output_root)? {
+        let created_at = Manifest::load(&path)?.created_at;
+        if latest.as_ref().is_none_or(|(at, _)| created_at > *at) {
+            latest = Some((created_at, path));
+        }
+    }
+    latest
+        .map(|(_, path)| path)
+        .ok_or_else(|| anyhow!("❌ 输出目录 {:?} 下没有找到 manifest.json", output_root))
+}
+
+fn open_backend(
+    cli: &Cli,
+    config: &Config,
+    cluster: Option<&ClusterBackend>,
+    throttle: &Throttle,
+) -> Result<Box<dyn IpfsBackend>> {
+    if cli.memory {
+        println!("🧪 使用内存中的后端，上传的内容不会保存到任何节点");
+        return Ok(Box::new(MemoryBackend::new()));
+    }
+    if let Some(cluster) = cluster {
+        return Ok(Box::new(cluster.clone()));
+    }
+    if let Some(bucket) = &cli.filebase {
+        let filebase = FilebaseConfig {
+            timeouts: config.filebase.timeouts.or(&config.timeouts),
+            ..config.filebase.clone()
+        };
+        return Ok(Box::new(
+            FilebaseBackend::new(bucket, &filebase)?.with_throttle(throttle.clone()),
         ));
     }
+    if cli.api.is_none() && !config.failover.nodes.is_empty() {
+        let timeouts = match config.failover.timeout_secs {
+            Some(secs) => TimeoutConfig::uniform(secs).or(&config.timeouts),
+            None => config.timeouts,
+        };
+        let nodes = config
+            .failover
+            .nodes
+            .iter()
+            .map(|node| {
+                let backend =
+                    HttpBackend::connect_with_timeouts(node, &config.api_endpoints, &timeouts)?
+                        .with_throttle(throttle.clone())
+                        .with_add_config(config.add.clone());
+                Ok((node.clone(), Box::new(backend) as Box<dyn IpfsBackend>))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Box::new(FailoverBackend::new(
+            nodes,
+            config.failover.mirror_pins,
+        )?));
+    }
+    Ok(match &cli.api {
+        Some(api) => Box::new(
+            HttpBackend::connect_with_timeouts(api, &config.api_endpoints, &config.timeouts)?
+                .with_throttle(throttle.clone())
+                .with_add_config(config.add.clone()),
+        ),
+        None => {
+            if !throttle.is_unlimited() {
+                eprintln!("⚠️  本地 ipfs 命令行后端不支持上传限速，请使用 --api 连接节点");
+            }
+            Box::new(
+                CliBackend::default()
+                    .with_timeouts(config.timeouts)
+                    .with_add_config(config.add.clone()),
+            )
+        }
+    })
+}
+
+// 使用 ipfs-cluster 时，报告集合根 CID 在每个 peer 上的固定情况
+// 按 --provide / [provide] 在上传后确认内容已向 DHT 宣告
+fn auto_provide(
+    enabled: bool,
+    config: &ProvideConfig,
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+) -> Result<()> {
+    if !enabled {
+        return Ok(());
+    }
+    let reports = provide_manifest(backend, manifest, config)?;
+    check_reports(&reports, config)
+}
+
+fn report_cluster(cluster: Option<&ClusterBackend>, manifest: &Manifest) -> Result<()> {
+    match cluster {
+        Some(cluster) => cluster.report_roots(&[
+            ("图片", &manifest.image_cid),
+            ("元数据", &manifest.metadata_cid),
+        ]),
+        None => Ok(()),
+    }
+}
 
-    let cid = String::from_utf8(output.stdout)?.trim().to_string();
-    println!("✅ JSON 元数据上传成功!\n   - CID: {}", cid);
-    Ok(cid)
+fn export_urls(
+    config: &Config,
+    manifest_path: &Path,
+    gateway: &str,
+    out: Option<&Path>,
+) -> Result<()> {
+    let manifest = Manifest::load(manifest_path)?;
+    let contract = config.contract.is_configured().then_some(&config.contract);
+    let links = token_links(&manifest, gateway, contract);
+    let path = match out {
+        Some(out) => out.to_path_buf(),
+        None => manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(LINKS_FILE_NAME),
+    };
+    std::fs::write(&path, render_links_csv(&links))?;
+    println!("✅ 已导出 {} 个 token 的链接: {:?}", links.len(), path);
+    match contract {
+        None => println!(
+            "💡 在配置文件中添加 [contract] (chain、address) 可以同时导出 OpenSea / LooksRare 链接"
+        ),
+        Some(contract) => {
+            let markets: Vec<&str> = [
+                ("OpenSea", links.iter().any(|l| l.opensea.is_some())),
+                ("LooksRare", links.iter().any(|l| l.looksrare.is_some())),
+            ]
+            .into_iter()
+            .filter_map(|(name, present)| present.then_some(name))
+            .collect();
+            if markets.is_empty() {
+                eprintln!(
+                    "⚠️  没有生成市场链接：链 {} 不受支持，或清单中的 token 没有数字 ID",
+                    contract.chain
+                );
+            } else {
+                println!("🔗 市场链接: {} ({})", markets.join("、"), contract.chain);
+            }
+        }
+    }
+    Ok(())
 }
 
-// 工作流一：处理单个 NFT
-fn process_single_nft(image_path: &Path) -> Result<()> {
-    println!("\n==============================================");
-    println!("🚀 开始处理单个 NFT...");
+fn generate_allowlist(csv: &Path, dir: &Path) -> Result<()> {
+    let (proofs, path) = write_allowlist(csv, dir)?;
     println!(
-        "   - 文件后缀模式: {}",
-        if USE_JSON_SUFFIX { ".json" } else { "无" }
+        "🌳 白名单 Merkle 根: {} ({} 个地址)",
+        proofs.root, proofs.count
     );
-    println!("==============================================");
+    println!("✅ 已写入每个地址的证明: {:?}", path);
+    Ok(())
+}
 
-    let image_cid = upload_to_ipfs(image_path)?;
-    println!("\n🖼️  图片 CID 已获取: {}", image_cid);
+// 集合密钥：--key-file，其次 [encryption] 的 key；给出清单时检查密钥指纹
+fn collection_key(
+    config: &Config,
+    key_file: Option<&Path>,
+    manifest: Option<&Path>,
+) -> Result<CollectionKey> {
+    let key = match key_file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("❌ 读取密钥文件 {:?} 失败: {}", path, e))?,
+        None => config.encryption.key.clone(),
+    };
+    if key.trim().is_empty() {
+        return Err(anyhow!(
+            "❌ 没有集合密钥：请使用 --key-file 或在 [encryption] 中设置 key"
+        ));
+    }
+    let key = CollectionKey::parse(&key)?;
+    if let Some(path) = manifest {
+        key.check(Manifest::load(path)?.encryption.as_ref())?;
+    }
+    Ok(key)
+}
 
-    let image_filename = image_path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| anyhow!("无效的图片文件名"))?;
-    let image_name_without_ext = image_path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| anyhow!("无效的图片文件名"))?;
+fn decrypt(key: &CollectionKey, input: &Path, out: &Path) -> Result<()> {
+    let (decrypted, skipped) = decrypt_path(key, input, out)?;
+    for path in &skipped {
+        println!("⚠️  跳过未加密的文件: {:?}", path);
+    }
+    if decrypted.is_empty() {
+        return Err(anyhow!("❌ {:?} 中没有加密文件", input));
+    }
+    println!("🔓 已解密 {} 个文件到 {:?}", decrypted.len(), out);
+    Ok(())
+}
 
-    let metadata = NftMetadata {
-        name: image_name_without_ext.to_string(),
-        description: format!("这是一个为图片 {} 动态生成的元数据。", image_filename),
-        image: format!("ipfs://{}", image_cid),
-        attributes: vec![Attribute {
-            trait_type: "类型".to_string(),
-            value: serde_json::Value::String("单件艺术品".to_string()),
-        }],
+// 签名用的私钥：--key-file，其次 [signing] 的 key；ethereum 方式最后使用 [contract] 的 private_key
+fn signing_secret(
+    config: &Config,
+    scheme: SignatureScheme,
+    key_file: Option<&Path>,
+) -> Result<String> {
+    let key = match key_file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("❌ 读取私钥文件 {:?} 失败: {}", path, e))?,
+        None => config.signing.key.clone(),
+    };
+    let key = match (key.trim(), scheme) {
+        ("", SignatureScheme::Ethereum) => config.contract.private_key.trim(),
+        (key, _) => key,
     };
+    if key.is_empty() {
+        return Err(anyhow!(
+            "❌ 没有签名私钥：请使用 --key-file 或在 [signing] 中设置 key"
+        ));
+    }
+    Ok(key.to_string())
+}
 
-    let metadata_cid = upload_json_str_to_ipfs(&metadata)?;
+fn sign(
+    config: &Config,
+    manifest_path: &Path,
+    scheme: SignatureScheme,
+    key_file: Option<&Path>,
+) -> Result<()> {
+    let manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let key = signing_secret(config, scheme, key_file)?;
+    let signature = sign_manifest(&manifest, dir, scheme, &key)?;
+    let path = signature.save(dir)?;
+    println!("✍️  已签名 ({}): {:?}", scheme, path);
+    println!("   - 签名者: {}", signature.signer);
+    Ok(())
+}
 
-    let output_dir = PathBuf::from("output").join(image_name_without_ext);
-    fs::create_dir_all(&output_dir)?;
-    fs::copy(image_path, output_dir.join(image_filename))?;
+// [signing] auto = true 时，单件和批量流程完成后签名
+fn auto_sign(config: &Config, manifest: &Manifest) -> Result<()> {
+    if !config.signing.auto {
+        return Ok(());
+    }
+    sign(
+        config,
+        &manifest.output_dir.join(MANIFEST_FILE_NAME),
+        config.signing.scheme,
+        None,
+    )
+}
 
-    let file_name = if USE_JSON_SUFFIX {
-        format!("{}.json", image_name_without_ext)
+fn verify_manifest_signature(path: &Path, expected_signer: Option<&str>) -> Result<()> {
+    let path = if path.is_dir() {
+        path.join(SIGNATURE_FILE_NAME)
     } else {
-        image_name_without_ext.to_string()
+        path.to_path_buf()
     };
-    let mut metadata_file = File::create(output_dir.join(file_name))?;
-    let pretty_json = serde_json::to_string_pretty(&metadata)?;
-    metadata_file.write_all(pretty_json.as_bytes())?;
+    let signature = SignatureFile::load(&path)?;
+    verify_signature(&signature)?;
+    println!(
+        "✅ 签名有效 ({})，签名者: {}",
+        signature.scheme, signature.signer
+    );
+    if let Some(expected) = expected_signer
+        && !expected
+            .trim_start_matches("0x")
+            .eq_ignore_ascii_case(signature.signer.trim_start_matches("0x"))
+    {
+        return Err(ExitCode::Mismatch.wrap(anyhow!(
+            "❌ 签名者是 {}，不是预期的 {}",
+            signature.signer,
+            expected
+        )));
+    }
+    let payload = signature.payload()?;
+    println!("   - 签名时间: {}", payload.signed_at);
+    println!("   - Token URI: {}", payload.token_uri);
+    println!("   - 来源证明哈希: {}", payload.provenance_hash);
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let manifest_path = dir.join(MANIFEST_FILE_NAME);
+    if !manifest_path.is_file() {
+        println!("💡 同一目录下没有 manifest.json，只验证了签名本身");
+        return Ok(());
+    }
+    let changed = compare_with_manifest(&payload, &Manifest::load(&manifest_path)?, dir)?;
+    if !changed.is_empty() {
+        return Err(ExitCode::Mismatch.wrap(anyhow!(
+            "❌ 清单或图片在签名后被改动过: {}",
+            changed.join(", ")
+        )));
+    }
+    println!("✅ 清单和图片与签名内容一致");
+    Ok(())
+}
 
-    println!("\n💾 图片和元数据已在本地打包保存至: {:?}", output_dir);
-    println!("\n--- ✨ 单件流程完成 ✨ ---");
+// schema validate：文件夹中的每个文件都是一个元数据 (批量运行的输出目录使用其中的 metadata/)
+fn validate_metadata_files(paths: &[PathBuf], strict: bool) -> Result<()> {
+    let mut files = Vec::new();
+    for path in paths {
+        let dir = match path.join("metadata") {
+            metadata if metadata.is_dir() => metadata,
+            _ => path.clone(),
+        };
+        if !dir.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", dir, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        entries.sort();
+        files.extend(entries);
+    }
+    let mut invalid = 0;
+    for file in &files {
+        let result = std::fs::read_to_string(file)
+            .map_err(|e| anyhow!("❌ 读取失败: {}", e))
+            .and_then(|content| parse_metadata(&content, strict));
+        if let Err(e) = result {
+            invalid += 1;
+            println!("{:?}: {}", file, e);
+        }
+    }
+    if invalid > 0 {
+        return Err(ExitCode::Validation.wrap(anyhow!(
+            "❌ {} 个元数据文件中有 {} 个不符合{}结构",
+            files.len(),
+            invalid,
+            if strict { "严格模式的" } else { "" }
+        )));
+    }
     println!(
-        "下一步，您可以在 mint 函数中使用这个元数据 URI: ipfs://{}",
-        metadata_cid
+        "✅ {} 个元数据文件都符合{}结构",
+        files.len(),
+        if strict { "严格模式的" } else { "" }
     );
     Ok(())
 }
 
-// 工作流二：处理批量 NFT 集合
-fn process_batch_collection(images_input_dir: &Path) -> Result<()> {
-    println!("\n==============================================");
-    println!("🚀 开始处理批量 NFT 集合...");
+fn deploy_config(manifest_path: &Path, options: &DeployOptions, env: bool) -> Result<()> {
+    let manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let config = build_deploy_config(&manifest, dir, options)?;
+    for path in write_deploy_config(&config, dir, env)? {
+        println!("✅ 已写入: {:?}", path);
+    }
+    println!("   - Base URI: {}", config.base_uri);
+    println!("   - 来源证明哈希: {}", config.provenance_hash);
+    println!("   - 最大供应量: {}", config.max_supply);
+    if let Some(root) = &config.merkle_root {
+        println!("   - 白名单 Merkle 根: {}", root);
+    }
+    let missing: Vec<&str> = [
+        ("--contract-uri", config.contract_uri.is_empty()),
+        ("--hidden-uri", config.hidden_uri.is_empty()),
+    ]
+    .into_iter()
+    .filter_map(|(flag, empty)| empty.then_some(flag))
+    .collect();
+    if !missing.is_empty() {
+        println!("💡 以下地址为空，需要时用 {} 填写", missing.join("、"));
+    }
+    Ok(())
+}
+
+// 确定元数据文件名是否带 .json 后缀；auto 模式读取合约已有 token 的 tokenURI
+fn resolve_json_suffix(mode: JsonSuffix, contract: &ContractConfig) -> Result<bool> {
+    match mode {
+        JsonSuffix::None => Ok(false),
+        JsonSuffix::Json => Ok(true),
+        JsonSuffix::Auto => match detect_json_suffix(contract)? {
+            Some(suffix) => {
+                println!(
+                    "✅ 根据合约的 tokenURI，元数据文件名{}",
+                    if suffix {
+                        "使用 .json 后缀"
+                    } else {
+                        "不带后缀"
+                    }
+                );
+                Ok(suffix)
+            }
+            None => {
+                eprintln!(
+                    "⚠️  合约还没有 mint 任何 token，无法判断 .json 后缀，默认不带后缀；部署后请用 verify-onchain 检查"
+                );
+                Ok(false)
+            }
+        },
+    }
+}
+
+// 上传完成后在合约中设置 Base URI
+fn update_base_uri(contract: &ContractConfig, manifest: &Manifest) -> Result<()> {
     println!(
-        "   - 文件后缀模式: {}",
-        if USE_JSON_SUFFIX { ".json" } else { "无" }
+        "\n--- 正在调用合约 {} 的 {} ---",
+        contract.address, contract.set_base_uri_function
     );
-    println!("==============================================");
+    let sent = set_base_uri(contract, &manifest.token_uri).map_err(|e| {
+        anyhow!(
+            "{}\n⚠️  上传已完成，可以稍后手动把 Base URI 设置为 {}",
+            e,
+            manifest.token_uri
+        )
+    })?;
+    match sent.receipt {
+        Some(receipt) if receipt.success => {
+            println!(
+                "✅ Base URI 已设置为 {} (区块 {}，gas {})",
+                manifest.token_uri, receipt.block_number, receipt.gas_used
+            );
+            Ok(())
+        }
+        Some(receipt) => Err(anyhow!(
+            "❌ 交易 {} 在区块 {} 执行失败，请确认 {} 是合约的 owner",
+            sent.hash,
+            receipt.block_number,
+            sent.from
+        )),
+        None => {
+            eprintln!(
+                "⚠️  交易 {} 还没有上链，请稍后在区块浏览器中确认",
+                sent.hash
+            );
+            Ok(())
+        }
+    }
+}
 
-    let images_folder_cid = upload_to_ipfs(images_input_dir)?;
-    println!("\n🖼️  图片文件夹 CID 已获取: {}", images_folder_cid);
+// 未指定时使用 [collection] images_dir，都没有时使用示例图片文件夹
+fn batch_dir(config: &Config, dir: Option<PathBuf>) -> PathBuf {
+    dir.or_else(|| config.collection.images_dir.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BATCH_DIR))
+}
 
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
-    let collection_output_dir = PathBuf::from("output").join(format!("collection_{}", timestamp));
-    let images_output_dir = collection_output_dir.join("images");
-    let metadata_output_dir = collection_output_dir.join("metadata");
+// ✅ 运行向导写入配置文件，按需用内存后端试运行一次 batch
+fn init(path: Option<&Path>, force: bool) -> Result<()> {
+    let path = path.unwrap_or(Path::new(DEFAULT_CONFIG_FILE));
+    let Some(answers) = run_init(path, force)? else {
+        return Ok(());
+    };
+    if !answers.dry_run {
+        println!(
+            "💡 之后可以运行 `batch` 上传 {:?} 中的图片",
+            answers.images_dir
+        );
+        return Ok(());
+    }
+    if !answers.images_dir.is_dir() {
+        println!("⚠️  {:?} 不存在，跳过试运行", answers.images_dir);
+        return Ok(());
+    }
+    // 新的进程按刚写入的配置运行，与之后正式上传的行为一致
+    println!("\n--- 试运行：内存后端，不连接节点，内容不会保留 ---");
+    let status = std::process::Command::new(std::env::current_exe()?)
+        .arg("--config")
+        .arg(path)
+        .args(["--memory", "batch"])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("❌ 试运行失败，请根据上面的输出修改 {:?}", path));
+    }
+    println!("\n✅ 试运行完成，去掉 --memory 即可正式上传");
+    Ok(())
+}
 
-    copy_directory(images_input_dir, &images_output_dir)?;
-    println!("\n💾 所有图片已复制到: {:?}", images_output_dir);
+// 终端中隐藏输入；管道 / CI 中从标准输入读取一行
+fn read_secret(provider: &str) -> Result<String> {
+    let secret = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("🔑 请输入 {} 的密钥: ", provider))?
+    } else {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        line
+    };
+    let secret = secret.trim().to_string();
+    if secret.is_empty() {
+        return Err(anyhow!("❌ 密钥不能为空"));
+    }
+    Ok(secret)
+}
 
-    println!("\n--- 正在为每张图片生成元数据 JSON 文件 ---");
-    fs::create_dir_all(&metadata_output_dir)?;
+fn main() {
+    // 当前目录的 .env 中的变量可以在配置文件中用 ${VAR} 引用，已存在的环境变量优先
+    dotenvy::dotenv().ok();
+    // 参数错误的退出码改为 64，clap 默认的 2 留给 NodeUnreachable
+    let mut cli = Cli::try_parse().unwrap_or_else(|e| {
+        let _ = e.print();
+        std::process::exit(if e.use_stderr() {
+            ExitCode::Usage.code()
+        } else {
+            0
+        });
+    });
+    i18n::set_lang(cli.lang);
+    i18n::set_ascii(cli.ascii);
+    set_verbosity(Verbosity::from_flags(cli.quiet, cli.verbose));
+    if cli.api.is_none() {
+        cli.api = api_from_env(&cli);
+    }
+    let started = Instant::now();
+    // 错误信息同样经过 eprintln! 翻译，不使用 main 返回 Result 时标准库的默认输出
+    let result = run(cli);
+    if let Err(e) = &result {
+        eprintln!("Error: {:?}", e);
+    }
+    verbose!("⏱️  用时 {:.1} 秒", started.elapsed().as_secs_f64());
+    if let Err(e) = result {
+        std::process::exit(ExitCode::of(&e).code());
+    }
+}
 
-    let mut image_files: Vec<PathBuf> = fs::read_dir(images_input_dir)?
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| path.is_file())
-        .collect();
-    image_files.sort();
-
-    for image_file in &image_files {
-        let token_id_str = image_file
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .ok_or_else(|| anyhow!("无效的文件名"))?;
-        let token_id: u64 = token_id_str.parse()?;
-        let image_filename = image_file
-            .file_name()
-            .and_then(|s| s.to_str())
-            .ok_or_else(|| anyhow!("无效的文件名"))?;
-
-        let metadata = NftMetadata {
-            name: format!("MetaCore #{}", token_id),
-            description: "MetaCore 集合中的一个独特成员。".to_string(),
-            image: format!("ipfs://{}/{}", images_folder_cid, image_filename),
-            attributes: vec![Attribute {
-                trait_type: "ID".to_string(),
-                value: serde_json::Value::Number(token_id.into()),
-            }],
+// 没有 --api 也没有选择其它后端时，节点地址取环境变量 IPFS_API (写法与 --api 相同，也可以写在 .env 中)
+fn api_from_env(cli: &Cli) -> Option<String> {
+    if cli.memory || cli.cluster.is_some() || cli.filebase.is_some() {
+        return None;
+    }
+    let api = std::env::var("IPFS_API").ok()?.trim().to_string();
+    if api.is_empty() {
+        return None;
+    }
+    verbose!("🌐 使用环境变量 IPFS_API 中的节点地址: {}", api);
+    Some(api)
+}
+
+// 配置中某一节的检查结果，有问题时以配置错误 (退出码 3) 结束
+fn ensure_valid(section: &str, problems: Vec<String>) -> Result<()> {
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(ExitCode::Validation.wrap(anyhow!(
+        "❌ {} 配置有问题:\n  {}",
+        section,
+        problems.join("\n  ")
+    )))
+}
+
+// 命令行指定的清单，未指定时使用输出目录下最新的一次运行
+fn resolve_manifest(manifest: Option<&Path>, output_root: &Path) -> Result<PathBuf> {
+    match manifest {
+        Some(path) => Ok(manifest_file(path)),
+        None => latest_manifest(output_root),
+    }
+}
+
+// 生成元数据和上传类命令共用的选项，构造时检查相关的配置
+struct UploadOptions {
+    json_suffix: bool,
+    json_style: JsonStyle,
+    format: MetadataFormat,
+    localization: Localization,
+    preview: PreviewConfig,
+    keep_exif: bool,
+    watermark: WatermarkConfig,
+    single: SingleOptions,
+}
+
+impl UploadOptions {
+    fn new(cli: &Cli, config: &Config) -> Result<Self> {
+        // 只有生成元数据的命令需要确定后缀，auto 模式会查询合约
+        let generates_metadata = matches!(
+            cli.command,
+            None | Some(Commands::Single { .. })
+                | Some(Commands::Batch(_))
+                | Some(Commands::Demo { .. })
+                | Some(Commands::Watch { .. })
+        );
+        let json_suffix = generates_metadata
+            && resolve_json_suffix(
+                cli.json_suffix.unwrap_or(config.json_suffix),
+                &config.contract,
+            )?;
+        let json_style = resolve_json_style(cli, config);
+        let format = MetadataFormat {
+            standard: cli.standard.unwrap_or(config.metadata_standard),
+            metaplex: config.metaplex.clone(),
+            tzip21: config.tzip21.clone(),
+            cip25: config.cip25.clone(),
+            json_style,
+            extra_fields: if generates_metadata {
+                config.extra_fields.load()?
+            } else {
+                ExtraFields::default()
+            },
+            collection: config.collection.clone(),
         };
-        let file_name = if USE_JSON_SUFFIX {
-            format!("{}.json", token_id_str)
+        // 各标准的问题来自同名的配置节，如 [metaplex]、[cip25]
+        ensure_valid(&format!("[{}]", format.standard), format.problems())?;
+        let localization = if generates_metadata && !config.localization.locales.is_empty() {
+            ensure_valid("[localization]", config.localization.problems())?;
+            config.localization.load()?
         } else {
-            token_id_str.to_string()
+            Localization::default()
+        };
+        let preview = PreviewConfig {
+            enabled: cli.preview || config.preview.enabled,
+            ..config.preview.clone()
+        };
+        if preview.enabled {
+            ensure_valid("[preview]", preview.problems())?;
+        }
+        let keep_exif = cli.keep_exif || config.keep_exif;
+        let watermark = WatermarkConfig {
+            enabled: cli.watermark || config.watermark.enabled,
+            ..config.watermark.clone()
+        };
+        if watermark.enabled {
+            ensure_valid("[watermark]", watermark.problems())?;
+        }
+        ensure_valid("[[hooks]]", hooks::problems(&config.hooks))?;
+        ensure_valid("[add]", config.add.problems())?;
+        ensure_valid("[urls]", config.urls.problems())?;
+        if config.arweave.auto {
+            arweave_config(config)?;
+        }
+        if config.filecoin.auto {
+            filecoin_config(config)?;
+        }
+        if config.backup.auto {
+            backup_config(config)?;
+        }
+        ensure_valid("[collection]", config.collection.problems())?;
+        ensure_valid("[output]", config.output.problems())?;
+        ensure_valid("[validation]", config.validation.problems())?;
+        let single = SingleOptions {
+            hash_algorithm: config.hash_algorithm,
+            json_suffix,
+            format: format.clone(),
+            preview: preview.clone(),
+            keep_exif,
+            validation: config.validation.clone(),
+            watermark: watermark.clone(),
+            checksums: config.checksums.clone(),
+            localization: localization.clone(),
+            hooks: config.hooks.clone(),
+            urls: config.urls.clone(),
+            ipld: config.ipld.clone(),
+            wrap_metadata: config.wrap_single_metadata
+                || matches!(cli.command, Some(Commands::Single { wrap: true, .. })),
+            output: config.output.clone(),
+            force: cli.force,
         };
-        let mut file = File::create(metadata_output_dir.join(file_name))?;
-        let pretty_json = serde_json::to_string_pretty(&metadata)?;
-        file.write_all(pretty_json.as_bytes())?;
+        Ok(Self {
+            json_suffix,
+            json_style,
+            format,
+            localization,
+            preview,
+            keep_exif,
+            watermark,
+            single,
+        })
     }
-    println!(
-        "✅ 成功生成 {} 个元数据文件到: {:?}",
-        image_files.len(),
-        metadata_output_dir
-    );
 
-    let metadata_folder_cid = upload_to_ipfs(&metadata_output_dir)?;
-    println!("\n📄 元数据文件夹 CID 已获取: {}", metadata_folder_cid);
-    println!("\n--- ✨ 批量流程完成 ✨ ---");
-    println!(
-        "下一步，您可以在合约中将 Base URI 设置为: ipfs://{}/",
-        metadata_folder_cid
+    // 批量流程的基础选项，各命令再覆盖布局、命名等字段
+    fn batch(&self, cli: &Cli, config: &Config) -> BatchOptions {
+        BatchOptions {
+            hash_algorithm: config.hash_algorithm,
+            json_suffix: self.json_suffix,
+            format: self.format.clone(),
+            preview: self.preview.clone(),
+            keep_exif: self.keep_exif,
+            validation: config.validation.clone(),
+            watermark: self.watermark.clone(),
+            layout: config.layout.clone(),
+            naming: config.naming.clone(),
+            copy: config.copy.clone(),
+            localization: self.localization.clone(),
+            hooks: config.hooks.clone(),
+            urls: config.urls.clone(),
+            ipld: config.ipld.clone(),
+            output: config.output.clone(),
+            force: cli.force,
+            ..BatchOptions::default()
+        }
+    }
+}
+
+// 连接节点后各子命令共用的配置、后端和选项
+struct Session<'a> {
+    cli: &'a Cli,
+    config: &'a Config,
+    backend: &'a dyn IpfsBackend,
+    cluster: Option<&'a ClusterBackend>,
+    provide: bool,
+    options: UploadOptions,
+    #[cfg(feature = "tui")]
+    dashboard: Option<&'a Dashboard>,
+}
+
+fn run(mut cli: Cli) -> Result<()> {
+    // 不需要配置文件和节点的命令
+    if let Some(result) = run_standalone(&cli) {
+        return result;
+    }
+    let config = Config::load_or_default(cli.config.as_deref())?;
+    // 只读写本地文件和配置的命令
+    if let Some(result) = run_offline(&cli, &config) {
+        return result;
+    }
+    let options = UploadOptions::new(&cli, &config)?;
+    // 需要在 main 结束前一直持有，drop 时按 --shutdown-daemon 关闭
+    let _daemon = if cli.autostart_daemon {
+        let options = DaemonOptions {
+            api: cli.api.clone(),
+            api_endpoints: config.api_endpoints.clone(),
+            shutdown: cli.shutdown_daemon,
+            ..DaemonOptions::default()
+        };
+        Some(ManagedDaemon::ensure_running(options, &cli.output)?)
+    } else {
+        None
+    };
+    // 上传类的命令，有的功能只对它们生效
+    let uploads = matches!(
+        cli.command,
+        None | Some(Commands::Single { .. })
+            | Some(Commands::Batch(_))
+            | Some(Commands::Demo { .. })
+            | Some(Commands::Watch { .. })
+            | Some(Commands::UpdateMetadata { .. })
     );
-    Ok(())
+    #[cfg(feature = "tui")]
+    let dashboard = (cli.tui && uploads).then(|| Arc::new(Dashboard::default()));
+    let (backend, cluster) = connect(
+        &cli,
+        &config,
+        uploads,
+        #[cfg(feature = "tui")]
+        dashboard.as_ref(),
+    )?;
+    // 仪表盘在命令结束 (包括出错) 时关闭并恢复终端
+    #[cfg(feature = "tui")]
+    let _screen = match &dashboard {
+        Some(dashboard) => {
+            let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+            let log_path = cli.output.join(format!("tui_{}.log", timestamp));
+            Some(DashboardScreen::show(dashboard.clone(), &log_path)?)
+        }
+        None => None,
+    };
+
+    // --- 根据子命令选择要运行的工作流，未指定时依次运行两个 ---
+    let command = cli.command.take();
+    let session = Session {
+        cli: &cli,
+        config: &config,
+        backend: backend.as_ref(),
+        cluster: cluster.as_ref(),
+        provide: cli.provide || config.provide.enabled,
+        options,
+        #[cfg(feature = "tui")]
+        dashboard: dashboard.as_deref(),
+    };
+    let backend = session.backend;
+    match command {
+        Some(Commands::Single { image, .. }) => single_command(&session, &image),
+        Some(Commands::Batch(args)) => batch_command(&session, args),
+        Some(Commands::Demo { count, gateway }) => demo_command(&session, count, &gateway),
+        Some(Commands::Watch { dir, debounce_ms }) => watch_command(&session, &dir, debounce_ms),
+        Some(Commands::Ls { path, recursive }) => ls_command(backend, &path, recursive),
+        Some(Commands::Cat { path, out }) => {
+            cat_file(backend, &inspect_path(&path)?, out.as_deref())
+        }
+        Some(Commands::Pins { command }) => pins(&config, &command, &cli.output, Some(backend)),
+        Some(Commands::Provide { manifests }) => provide_command(&session, manifests),
+        Some(Commands::Verify { manifests, check }) => verify_command(&session, manifests, check),
+        Some(Commands::PatchDescriptions {
+            manifest,
+            template,
+            dry_run,
+        }) => patch_descriptions_command(&session, &manifest, template, dry_run),
+        Some(Commands::UpdateMetadata {
+            manifest,
+            patch,
+            dry_run,
+            out,
+        }) => update_metadata_command(&session, &manifest, &patch, dry_run, out.as_deref()),
+        Some(Commands::FetchDecrypt {
+            uri,
+            out,
+            key_file,
+            manifest,
+        }) => fetch_decrypt_command(
+            &session,
+            &uri,
+            out,
+            key_file.as_deref(),
+            manifest.as_deref(),
+        ),
+        Some(Commands::Clone { uri, out }) => clone_command(&session, &uri, out),
+        Some(Commands::Migrate {
+            uri,
+            out,
+            re_add,
+            services,
+            collection,
+            tags,
+        }) => migrate_command(&session, &uri, out, re_add, &services, collection, &tags),
+        Some(Commands::RewriteUris {
+            dir,
+            from,
+            to,
+            dry_run,
+        }) => {
+            let options = RewriteOptions {
+                from,
+                to,
+                dry_run,
+                urls: config.urls.clone(),
+            };
+            rewrite_uris_command(backend, &dir, &options)
+        }
+        Some(Commands::Diff { a, b, out }) => {
+            diff_collections(&a, &b, out.as_deref(), Some(backend))
+        }
+        Some(Commands::VerifyOnchain {
+            manifest,
+            from,
+            to,
+            function,
+        }) => verify_onchain_command(&session, manifest.as_deref(), from.zip(to), function),
+        Some(Commands::Report { manifest, format }) => {
+            report_command(&session, manifest.as_deref(), format)
+        }
+        Some(Commands::Runbook { manifest, check }) => runbook_command(&session, &manifest, check),
+        Some(Commands::Monitor {
+            manifests,
+            schedule,
+            check,
+        }) => monitor_command(&session, manifests, &schedule, check),
+        Some(
+            Commands::Arweave { .. }
+            | Commands::Backup { .. }
+            | Commands::Car { .. }
+            | Commands::Cid { .. }
+            | Commands::Doctor
+            | Commands::CompareRuns { .. }
+            | Commands::Credentials { .. }
+            | Commands::Decrypt { .. }
+            | Commands::DeployConfig { .. }
+            | Commands::Estimate { .. }
+            | Commands::ExportUrls { .. }
+            | Commands::Filecoin { .. }
+            | Commands::Gallery { .. }
+            | Commands::Import { .. }
+            | Commands::Init
+            | Commands::Loadtest { .. }
+            | Commands::PinRemote { .. }
+            | Commands::Schema { .. }
+            | Commands::Sign { .. }
+            | Commands::Usage { .. }
+            | Commands::VerifyLocal { .. }
+            | Commands::VerifySignature { .. },
+        ) => {
+            unreachable!("不需要连接节点的命令已在前面处理")
+        }
+        None => default_command(&session),
+    }
 }
 
-fn main() -> Result<()> {
+// 打开后端，按需套上加密、指标、传输窗口、仪表盘和中断处理，最后检查节点连接
+fn connect(
+    cli: &Cli,
+    config: &Config,
+    uploads: bool,
+    #[cfg(feature = "tui")] dashboard: Option<&Arc<Dashboard>>,
+) -> Result<(Box<dyn IpfsBackend>, Option<ClusterBackend>)> {
+    // 全局限速器只创建一次，所有后端共享同一份额度
+    let throttle = Throttle::new(&ThrottleConfig {
+        bytes_per_sec: cli.upload_limit.or(config.throttle.bytes_per_sec),
+        ..config.throttle
+    });
+    #[cfg(feature = "tui")]
+    let throttle = match dashboard {
+        Some(dashboard) => throttle.with_counter(dashboard.sent_counter()),
+        None => throttle,
+    };
+    if let Some(rate) = cli.upload_limit.or(config.throttle.bytes_per_sec) {
+        println!("🐢 上传限速: {}/s", format_bytes(rate));
+    }
+    if let Some(rate) = config.throttle.per_connection_bytes_per_sec {
+        println!("🐢 单连接限速: {}/s", format_bytes(rate));
+    }
+    let cluster = match &cli.cluster {
+        Some(api) => {
+            let mut cluster_config = config.cluster.clone();
+            cluster_config.timeouts = cluster_config.timeouts.or(&config.timeouts);
+            if let Some(factor) = cli.replication_factor {
+                cluster_config.replication_min = Some(factor);
+                cluster_config.replication_max = Some(factor);
+            }
+            Some(ClusterBackend::new(api, cluster_config)?.with_throttle(throttle.clone()))
+        }
+        None => None,
+    };
+    let mut backend = open_backend(cli, config, cluster.as_ref(), &throttle)?;
+    if uploads && (cli.encrypt || config.encryption.enabled) {
+        if config.add.nocopy {
+            return Err(anyhow!(
+                "❌ [add] nocopy 不能和加密一起使用：节点只记录文件路径，加密后的临时文件上传后会被删除"
+            ));
+        }
+        let key = collection_key(config, None, None)?;
+        println!(
+            "🔐 上传前加密 ({}，密钥指纹 {})",
+            ALGORITHM,
+            key.fingerprint()
+        );
+        backend = Box::new(EncryptedBackend::new(backend, key));
+    }
+    if let Some(addr) = &cli.metrics_addr {
+        let metrics = Arc::new(Metrics::default());
+        serve(addr, metrics.clone())?;
+        backend = Box::new(MeteredBackend::new(backend, metrics));
+    }
+    if let Some(window) = cli.schedule_window.or(config.schedule_window) {
+        println!("🕐 传输窗口: {}", window);
+        backend = Box::new(WindowedBackend::new(backend, window));
+    }
+    #[cfg(feature = "tui")]
+    if let Some(dashboard) = dashboard {
+        backend = Box::new(DashboardBackend::new(backend, dashboard.clone()));
+    }
+    // 上传类的命令在中断时停止开始新的上传，并记录已经完成的部分
+    if uploads {
+        install_handler()?;
+        backend = Box::new(CancellableBackend::new(
+            backend,
+            &cli.output,
+            cli.unpin_on_cancel,
+        ));
+    }
+
     // 前置检查
-    let status = Command::new("ipfs").arg("id").output()?.status;
-    if !status.success() {
-        eprintln!("❌ 连接 IPFS 节点失败。");
-        eprintln!("请确保你的 IPFS 节点正在运行 (命令: ipfs daemon)。");
-        return Err(anyhow!("IPFS daemon not running"));
+    match backend.check_compatibility()? {
+        Some(capabilities) => {
+            println!("✅ 成功连接到 IPFS 节点 (版本: {})", capabilities.version)
+        }
+        None => println!("✅ 成功连接到 IPFS 节点"),
+    }
+    Ok((backend, cluster))
+}
+
+// 不读取配置文件、也不连接节点的命令；其它命令返回 None
+fn run_standalone(cli: &Cli) -> Option<Result<()>> {
+    let result = match cli.command.as_ref()? {
+        Commands::Car { path, out } => pack_car(path, out.as_deref(), &cli.output),
+        Commands::Cid {
+            path,
+            expect,
+            command,
+        } => match (command, path) {
+            (Some(command), _) => run_cid_command(command),
+            (None, Some(path)) => check_cid(path, expect.as_deref()),
+            (None, None) => Err(anyhow!(
+                "❌ 请指定要计算 CID 的路径，或使用 cid convert / cid inspect"
+            )),
+        },
+        Commands::VerifyLocal { dir } => verify_local(dir),
+        Commands::VerifySignature { path, signer } => {
+            verify_manifest_signature(path, signer.as_deref())
+        }
+        Commands::Schema {
+            command: SchemaCommand::Validate { paths },
+        } => validate_metadata_files(paths, cli.strict),
+        Commands::Schema {
+            command: SchemaCommand::Export { format, root, out },
+        } => export_schema_command(*format, *root, out.as_deref(), cli.strict),
+        Commands::Credentials { command } => credentials_command(command),
+        Commands::Loadtest {
+            manifest,
+            gateway,
+            rps,
+            duration,
+            timeout,
+            concurrency,
+        } => {
+            let options = LoadTestOptions {
+                gateway: gateway.clone(),
+                rps: *rps,
+                duration: *duration,
+                timeout: *timeout,
+                concurrency: *concurrency,
+            };
+            loadtest_command(manifest.as_deref(), &cli.output, &options)
+        }
+        Commands::CompareRuns { run_a, run_b, out } => {
+            compare_runs_command(run_a, run_b, out.as_deref())
+        }
+        // 两边都是本地文件夹时不需要连接节点
+        Commands::Diff { a, b, out } => match local_diff(a, b) {
+            Ok(true) => diff_collections(a, b, out.as_deref(), None),
+            Ok(false) => return None,
+            Err(e) => Err(e),
+        },
+        Commands::Init => init(cli.config.as_deref(), cli.force),
+        _ => return None,
+    };
+    Some(result)
+}
+
+// 只读写本地文件和配置的命令；其它命令返回 None
+fn run_offline(cli: &Cli, config: &Config) -> Option<Result<()>> {
+    let result = match cli.command.as_ref()? {
+        Commands::Import { dirs, source } => import_command(config, dirs, *source),
+        Commands::PinRemote {
+            manifest,
+            services,
+            collection,
+            tags,
+        } => pin_remote(
+            config,
+            &manifest_file(manifest),
+            services,
+            collection.as_deref(),
+            tags,
+        ),
+        Commands::Arweave { manifest, force } => resolve_manifest(manifest.as_deref(), &cli.output)
+            .and_then(|path| mirror_arweave(config, &path, *force)),
+        Commands::Backup { dir, dry_run } => {
+            backup_command(config, dir.as_deref(), &cli.output, *dry_run)
+        }
+        Commands::Filecoin { command } => filecoin(config, command, &cli.output),
+        // 取消固定需要连接节点，其它 pins 子命令只读写 catalog.json
+        Commands::Pins { command }
+            if !matches!(command, PinsCommand::Expired { unpin: true, .. }) =>
+        {
+            pins(config, command, &cli.output, None)
+        }
+        Commands::ExportUrls {
+            manifest,
+            gateway,
+            out,
+        } => export_urls(config, &manifest_file(manifest), gateway, out.as_deref()),
+        Commands::DeployConfig {
+            manifest,
+            contract_uri,
+            hidden_uri,
+            max_supply,
+            env,
+            allowlist,
+        } => {
+            let options = DeployOptions {
+                contract_uri: contract_uri.clone(),
+                hidden_uri: hidden_uri.clone(),
+                max_supply: *max_supply,
+            };
+            deploy_config_command(
+                manifest.as_deref(),
+                &cli.output,
+                &options,
+                *env,
+                allowlist.as_deref(),
+            )
+        }
+        Commands::Decrypt {
+            input,
+            out,
+            key_file,
+            manifest,
+        } => collection_key(config, key_file.as_deref(), manifest.as_deref())
+            .and_then(|key| decrypt(&key, input, out)),
+        Commands::Sign {
+            manifest,
+            scheme,
+            key_file,
+        } => resolve_manifest(manifest.as_deref(), &cli.output).and_then(|path| {
+            sign(
+                config,
+                &path,
+                scheme.unwrap_or(config.signing.scheme),
+                key_file.as_deref(),
+            )
+        }),
+        Commands::Estimate { dir, top } => estimate_command(config, dir.clone(), *top),
+        Commands::Gallery {
+            manifest,
+            gateway,
+            local,
+        } => gallery_command(
+            config,
+            manifest.as_deref(),
+            &cli.output,
+            gateway.clone(),
+            *local,
+        ),
+        Commands::Usage { next, offline } => {
+            usage_command(config, &cli.output, next.as_deref(), *offline)
+        }
+        Commands::Doctor => doctor_command(cli, config),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn local_diff(a: &str, b: &str) -> Result<bool> {
+    Ok(MetadataSource::parse(a)?.is_local() && MetadataSource::parse(b)?.is_local())
+}
+
+fn export_schema_command(
+    format: SchemaFormat,
+    root: SchemaRoot,
+    out: Option<&Path>,
+    strict: bool,
+) -> Result<()> {
+    let schema = export_schema(format, root, strict);
+    match out {
+        Some(out) => {
+            std::fs::write(out, schema)?;
+            println!("✅ {} 结构定义已写入: {:?}", format, out);
+        }
+        None => print!("{}", schema),
+    }
+    Ok(())
+}
+
+fn credentials_command(command: &CredentialsCommand) -> Result<()> {
+    match command {
+        CredentialsCommand::Set { provider } => {
+            store_credential(provider, &read_secret(provider)?)?;
+            println!("✅ {} 的密钥已保存到系统钥匙串", provider);
+        }
+        CredentialsCommand::Delete { provider } => {
+            if delete_credential(provider)? {
+                println!("🗑️  已删除 {} 的密钥", provider);
+            } else {
+                println!("⚠️  钥匙串中没有 {} 的密钥", provider);
+            }
+        }
     }
-    println!("✅ 成功连接到 IPFS 节点");
+    Ok(())
+}
 
-    let single_image_path = PathBuf::from("../assets/image/IMG_20210626_180340.jpg");
-    let batch_images_path = PathBuf::from("../assets/batch_images");
-    fs::create_dir_all(&batch_images_path)?;
+fn loadtest_command(
+    manifest: Option<&Path>,
+    output_root: &Path,
+    options: &LoadTestOptions,
+) -> Result<()> {
+    let path = resolve_manifest(manifest, output_root)?;
+    println!("📄 使用清单: {:?}", path);
+    print_report(&run_load_test(&Manifest::load(&path)?, options)?);
+    Ok(())
+}
 
-    // --- 在这里选择要运行的工作流 ---
-    process_single_nft(&single_image_path)?;
-    process_batch_collection(&batch_images_path)?;
+fn compare_runs_command(run_a: &Path, run_b: &Path, out: Option<&Path>) -> Result<()> {
+    let load = |path: &Path| -> Result<Run> {
+        let path = manifest_file(path);
+        Ok(Run::new(&path, Manifest::load(&path)?))
+    };
+    let (a, b) = (load(run_a)?, load(run_b)?);
+    let report = render_comparison(&a, &b, &compare_runs(&a, &b));
+    println!("{}", report);
+    if let Some(out) = out {
+        std::fs::write(out, &report)?;
+        println!("📄 对比报告已写入: {:?}", out);
+    }
+    Ok(())
+}
+
+fn import_command(config: &Config, dirs: &[PathBuf], source: Option<SourceImpl>) -> Result<()> {
+    for dir in dirs {
+        let manifest = import_output(dir, source, config.hash_algorithm)?;
+        let path = manifest.save(dir)?;
+        println!(
+            "✅ 已导入 {:?} ({}，{} 个 token)，Token URI: {}",
+            path,
+            manifest.backend,
+            manifest.tokens.len(),
+            manifest.token_uri
+        );
+    }
+    Ok(())
+}
 
+// 未指定目录时备份最新一次运行；指定的是清单文件时备份它所在的目录
+fn backup_command(
+    config: &Config,
+    dir: Option<&Path>,
+    output_root: &Path,
+    dry_run: bool,
+) -> Result<()> {
+    let dir = match dir {
+        Some(path) if path.is_file() => path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        Some(path) => path.to_path_buf(),
+        None => latest_manifest(output_root)?
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf(),
+    };
+    backup(config, &dir, dry_run)
+}
+
+fn deploy_config_command(
+    manifest: Option<&Path>,
+    output_root: &Path,
+    options: &DeployOptions,
+    env: bool,
+    allowlist: Option<&Path>,
+) -> Result<()> {
+    let path = resolve_manifest(manifest, output_root)?;
+    if let Some(csv) = allowlist {
+        generate_allowlist(csv, path.parent().unwrap_or(Path::new(".")))?;
+    }
+    deploy_config(&path, options, env)
+}
+
+fn estimate_command(config: &Config, dir: Option<PathBuf>, top: usize) -> Result<()> {
+    let estimate = estimate_collection(&batch_dir(config, dir))?;
+    let projections: Vec<_> = config
+        .pricing_tiers
+        .iter()
+        .map(|tier| project_tier(&estimate, tier))
+        .collect();
+    print_estimate(&estimate, &projections, top);
+    Ok(())
+}
+
+fn gallery_command(
+    config: &Config,
+    manifest: Option<&Path>,
+    output_root: &Path,
+    gateway: Option<String>,
+    local: bool,
+) -> Result<()> {
+    let manifest_path = resolve_manifest(manifest, output_root)?;
+    let gallery = GalleryConfig {
+        gateway: gateway.unwrap_or(config.gallery.gateway.clone()),
+        local: local || config.gallery.local,
+    };
+    let path = write_gallery(&manifest_path, &gallery)?;
+    println!("🖼️  预览页面已生成: {:?}", path);
+    Ok(())
+}
+
+fn usage_command(
+    config: &Config,
+    output_root: &Path,
+    next: Option<&Path>,
+    offline: bool,
+) -> Result<()> {
+    let mut usage = collect_usage(&discover_manifests(output_root)?)?;
+    if !offline {
+        query_services(&mut usage, &config.pinning_services);
+    }
+    let estimate = match next {
+        Some(dir) => estimate_collection(dir)?,
+        None => Estimate::default(),
+    };
+    let tiers = applied_tiers(&usage, &config.pricing_tiers);
+    let projections: Vec<_> = tiers
+        .iter()
+        .map(|tier| project_tier(&estimate, tier))
+        .collect();
+    print_usage(&usage, &projections);
+    if next.is_some() && projections.iter().any(|p| !p.warnings.is_empty()) {
+        return Err(anyhow!("❌ 这次上传会超出套餐限额"));
+    }
+    Ok(())
+}
+
+fn doctor_command(cli: &Cli, config: &Config) -> Result<()> {
+    let results = run_doctor(config, cli.api.as_deref(), &cli.output);
+    let failed = results
+        .iter()
+        .filter(|r| r.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(anyhow!("❌ {} 项检查未通过", failed));
+    }
+    println!("\n✅ 环境检查通过");
+    Ok(())
+}
+
+// 单件流程：上传后按配置宣告、镜像、归档、签名、备份并记录过期时间
+fn upload_single(session: &Session, image: &Path) -> Result<()> {
+    let (cli, config, backend) = (session.cli, session.config, session.backend);
+    let result = process_single_nft(backend, image, &cli.output, &session.options.single);
+    let manifest = report(
+        &config.webhooks,
+        "single",
+        on_failure(&config.hooks, "single", result),
+    )?;
+    cancel::finish_uploads();
+    report_cluster(session.cluster, &manifest)?;
+    auto_provide(session.provide, &config.provide, backend, &manifest)?;
+    auto_mirror(config, &manifest)?;
+    auto_archive(config, &manifest, &cli.output)?;
+    auto_sign(config, &manifest)?;
+    auto_backup(config, &manifest)?;
+    auto_expiry(cli.expires, &manifest, &cli.output)
+}
+
+// 批量流程：上传后执行自动步骤并写入上传报告
+fn upload_batch(
+    session: &Session,
+    dir: &Path,
+    options: &BatchOptions,
+    timings: &mut Timings,
+) -> Result<Manifest> {
+    let (cli, config, backend) = (session.cli, session.config, session.backend);
+    let result = process_batch_collection(backend, dir, &cli.output, options);
+    let manifest = report(
+        &config.webhooks,
+        "batch",
+        on_failure(&config.hooks, "batch", result),
+    )?;
+    timings.lap("上传");
+    cancel::finish_uploads();
+    report_cluster(session.cluster, &manifest)?;
+    auto_steps(
+        config,
+        session.provide,
+        backend,
+        &manifest,
+        &cli.output,
+        timings,
+    )?;
+    auto_report(config, backend, &manifest, timings)?;
+    auto_backup(config, &manifest)?;
+    auto_expiry(cli.expires, &manifest, &cli.output)?;
+    Ok(manifest)
+}
+
+fn print_next_steps() {
     println!("\n======================================================================");
     println!("✅ 本地准备工作已完成！");
     println!("下一步是发布到专业的 Pinning 服务 (如 Pinata):");
@@ -256,24 +2614,378 @@ fn main() -> Result<()> {
     println!("3. 上传您本地 `rust/output/collection_[时间戳]/metadata` 文件夹。");
     println!("4. ⚠️  使用 Pinata 返回的【metadata】文件夹的 CID 来设置您合约的 Base URI。");
     println!("======================================================================");
+}
 
+fn single_command(session: &Session, image: &Path) -> Result<()> {
+    #[cfg(feature = "tui")]
+    dashboard_totals(session.dashboard, image)?;
+    upload_single(session, image)?;
+    print_next_steps();
     Ok(())
 }
 
-// --- 辅助函数 ---
-fn copy_directory(src: &Path, dst: &Path) -> io::Result<()> {
-    fs::create_dir_all(dst)?;
-    for entry in WalkDir::new(src) {
-        let entry = entry?;
-        let path = entry.path();
-        let relative_path = path.strip_prefix(src).unwrap();
-        let dest_path = dst.join(relative_path);
-        if path.is_dir() {
-            fs::create_dir_all(&dest_path)?;
-        } else {
-            fs::copy(path, &dest_path)?;
-        }
+fn batch_command(session: &Session, args: BatchArgs) -> Result<()> {
+    let (cli, config) = (session.cli, session.config);
+    let mut timings = Timings::default();
+    // 在上传前检查，避免上传完成后才发现无法发送交易
+    if args.set_base_uri {
+        check_transaction_config(&config.contract)?;
+    }
+    if let Some(csv) = &args.allowlist {
+        parse_allowlist_csv(&std::fs::read_to_string(csv)?)?;
+    }
+    // 远程来源先下载到缓存目录，之后与本地文件夹走同一个流程
+    let dir = match args.urls.map(RemoteSource::Urls).or(args.s3) {
+        Some(source) => fetch_source(&config.remote_source, &source, &cli.output)?.dir,
+        None => batch_dir(config, args.dir),
+    };
+    // 压缩包解压到临时目录，extracted 离开作用域时删除
+    let extracted = match ArchiveFormat::detect(&dir) {
+        Some(format) if dir.is_file() => Some(extract_archive(&dir, format)?),
+        _ => None,
+    };
+    let dir = extracted.as_ref().map_or(dir, |input| input.dir.clone());
+    #[cfg(feature = "tui")]
+    dashboard_totals(session.dashboard, &dir)?;
+    warn_quotas(config, &dir, &cli.output)?;
+    let layout = LayoutConfig {
+        recursive: args.recursive || config.layout.recursive,
+        token_ids: match (&args.id_map, args.token_ids) {
+            (Some(_), _) => TokenIdStrategy::Csv,
+            (None, Some(strategy)) => strategy,
+            (None, None) => config.layout.token_ids,
+        },
+        strip_prefix: args
+            .strip_prefix
+            .unwrap_or_else(|| config.layout.strip_prefix.clone()),
+        start_id: args.start_id.unwrap_or(config.layout.start_id),
+        mapping: args.id_map.or_else(|| config.layout.mapping.clone()),
+        include: [config.layout.include.clone(), args.include].concat(),
+        exclude: [config.layout.exclude.clone(), args.exclude].concat(),
+        ..config.layout.clone()
+    };
+    ensure_valid("[layout]", layout.problems())?;
+    let mut options = BatchOptions {
+        allow_empty: args.allow_empty,
+        layout,
+        naming: NamingConfig {
+            pad_width: args.pad_width.unwrap_or(config.naming.pad_width),
+            rename_images: args.rename_images || config.naming.rename_images,
+            ..config.naming.clone()
+        },
+        copy: CopyOptions {
+            symlinks: args.symlinks.unwrap_or(config.copy.symlinks),
+            ..config.copy.clone()
+        },
+        checksums: config.checksums.clone(),
+        strict: cli.strict,
+        ..session.options.batch(cli, config)
+    };
+    if let Some(name) = args.name {
+        options.format.collection.name = name;
+    }
+    timings.lap("准备输入");
+    let manifest = upload_batch(session, &dir, &options, &mut timings)?;
+    if let Some(csv) = &args.allowlist {
+        generate_allowlist(csv, &manifest.output_dir)?;
+    }
+    if args.set_base_uri {
+        return update_base_uri(&config.contract, &manifest);
+    }
+    print_next_steps();
+    Ok(())
+}
+
+fn demo_command(session: &Session, count: u64, gateway: &str) -> Result<()> {
+    let (cli, config, backend) = (session.cli, session.config, session.backend);
+    let started = Instant::now();
+    let images_dir = cli.output.join(DEMO_IMAGES_DIR);
+    let images = generate_demo_images(&images_dir, count)?;
+    println!("🎨 已生成 {} 张示例图片: {:?}", images.len(), images_dir);
+    let options = BatchOptions {
+        layout: LayoutConfig::default(),
+        naming: NamingConfig::default(),
+        copy: CopyOptions::default(),
+        checksums: config.checksums.clone(),
+        output: OutputLayout::default(),
+        strict: cli.strict,
+        ..session.options.batch(cli, config)
+    };
+    let result = process_batch_collection(backend, &images_dir, &cli.output, &options);
+    let manifest = on_failure(&config.hooks, "batch", result)?;
+    print_demo_summary(backend, &manifest, gateway, started.elapsed());
+    Ok(())
+}
+
+fn watch_command(session: &Session, dir: &Path, debounce_ms: u64) -> Result<()> {
+    let (config, options) = (session.config, &session.options);
+    let options = WatchOptions {
+        debounce: Duration::from_millis(debounce_ms),
+        hash_algorithm: config.hash_algorithm,
+        json_suffix: options.json_suffix,
+        format: options.format.clone(),
+        preview: options.preview.clone(),
+        keep_exif: options.keep_exif,
+        validation: config.validation.clone(),
+        urls: config.urls.clone(),
+    };
+    watch_directory(session.backend, dir, &session.cli.output, &options)
+}
+
+fn provide_command(session: &Session, manifests: Vec<PathBuf>) -> Result<()> {
+    let config = ProvideConfig {
+        enabled: true,
+        ..session.config.provide.clone()
+    };
+    let mut reports = Vec::new();
+    for path in manifest_paths(manifests, &session.cli.output)? {
+        println!("\n📦 {:?}", path);
+        reports.extend(provide_manifest(
+            session.backend,
+            &Manifest::load(&path)?,
+            &config,
+        )?);
+    }
+    check_reports(&reports, &config)
+}
+
+fn verify_command(session: &Session, manifests: Vec<PathBuf>, check: CheckArgs) -> Result<()> {
+    let output_root = &session.cli.output;
+    let paths = manifest_paths(manifests, output_root)?;
+    let mut catalog = Catalog::open(output_root)?;
+    let alerts = run_checks(
+        session.backend,
+        &paths,
+        &check.options(&session.config.timeouts),
+        &mut catalog,
+    )?;
+    for path in &paths {
+        report_cluster(session.cluster, &Manifest::load(path)?)?;
+    }
+    if !alerts.is_empty() {
+        return Err(anyhow!("❌ 巡检发现 {} 个问题", alerts.len()));
+    }
+    println!("\n✅ 所有 CID 均已固定且可以访问");
+    Ok(())
+}
+
+fn update_metadata_command(
+    session: &Session,
+    manifest: &Path,
+    patch: &Path,
+    dry_run: bool,
+    out: Option<&Path>,
+) -> Result<()> {
+    let options = UpdateOptions {
+        dry_run,
+        json_style: session.options.json_style,
+        urls: session.config.urls.clone(),
+    };
+    let patch = MetadataPatch::load(patch)?;
+    let update = update_metadata(session.backend, &manifest_file(manifest), &patch, &options)?;
+    let (before, after) = if dry_run || update.diff.changed.is_empty() {
+        ("修改前".to_string(), "修改后".to_string())
+    } else {
+        (update.old_token_uri.clone(), update.new_token_uri.clone())
+    };
+    let report = render_diff(&before, &after, &update.diff);
+    println!("{}", report);
+    if let Some(out) = out {
+        std::fs::write(out, &report)?;
+        println!("📄 差异报告已写入: {:?}", out);
+    }
+    print_update_report(&update, dry_run);
+    Ok(())
+}
+
+fn migrate_command(
+    session: &Session,
+    uri: &str,
+    out: Option<PathBuf>,
+    re_add: bool,
+    services: &[String],
+    collection: Option<String>,
+    tags: &[(String, String)],
+) -> Result<()> {
+    let (cli, config) = (session.cli, session.config);
+    let out = out.unwrap_or_else(|| default_download_dir(&cli.output, "migrate", uri));
+    let options = MigrateOptions {
+        re_add,
+        hash_algorithm: config.hash_algorithm,
+        urls: config.urls.clone(),
+    };
+    let (mut manifest, report) = migrate_collection(session.backend, uri, &out, &options)?;
+    let report_path = report.save(&out)?;
+    print_migration_report(&report);
+    println!("📄 迁移报告: {:?}", report_path);
+
+    if services.is_empty() && config.pinning_services.is_empty() {
+        println!("💡 配置文件中没有 Pinning 服务，只在当前节点上固定");
+        return Ok(());
+    }
+    let collection = collection.unwrap_or_else(|| collection_name(&manifest));
+    let result = pin_to_services(config, &mut manifest, services, &collection, tags);
+    manifest.save(&out)?;
+    result
+}
+
+fn verify_onchain_command(
+    session: &Session,
+    manifest: Option<&Path>,
+    range: Option<(u64, u64)>,
+    function: String,
+) -> Result<()> {
+    let path = resolve_manifest(manifest, &session.cli.output)?;
+    println!("📄 使用清单: {:?}", path);
+    let token_ids = match range {
+        Some((from, to)) => (from..=to).collect(),
+        None => Manifest::load(&path)?
+            .tokens
+            .iter()
+            .filter_map(|token| token.token_id)
+            .collect(),
+    };
+    let options = OnchainOptions {
+        token_ids,
+        function,
+    };
+    let checks = verify_onchain(session.backend, &session.config.contract, &path, &options)?;
+    print_onchain_report(&checks);
+    let failed = checks.iter().filter(|c| c.problem.is_some()).count();
+    if failed > 0 {
+        return Err(anyhow!("❌ {} 个 token 的链上 URI 有问题", failed));
+    }
+    println!("✅ 链上 tokenURI 均指向本次上传的元数据");
+    Ok(())
+}
+
+fn report_command(
+    session: &Session,
+    manifest: Option<&Path>,
+    format: Vec<ReportFormat>,
+) -> Result<()> {
+    let manifest_path = resolve_manifest(manifest, &session.cli.output)?;
+    let loaded = Manifest::load(&manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let formats = if format.is_empty() {
+        session.config.report.formats.clone()
+    } else {
+        format
+    };
+    let run = build_report(&loaded, dir, Some(session.backend), None);
+    for failure in &run.failures {
+        eprintln!("⚠️  {}", failure);
     }
+    for path in write_reports(dir, &run, &formats)? {
+        println!("📊 上传报告已生成: {:?}", path);
+    }
+    Ok(())
+}
+
+fn runbook_command(session: &Session, manifest: &Path, check: CheckArgs) -> Result<()> {
+    let (cli, config) = (session.cli, session.config);
+    let manifest_path = manifest_file(manifest);
+    let loaded = Manifest::load(&manifest_path)?;
+    let options = check.options(&config.timeouts);
+    let mut catalog = Catalog::open(&cli.output)?;
+    let reports = check_manifest(session.backend, &loaded, &options, &mut catalog)?;
+    catalog.save()?;
+    let path = write_runbook(&RunbookContext {
+        manifest: &loaded,
+        manifest_path: &manifest_path,
+        reports: &reports,
+        gateways: &options.gateways,
+        pinning_services: &config.pinning_services,
+        previous: find_previous(&cli.output, &loaded)?,
+    })?;
+    println!("\n📘 操作手册已生成: {:?}", path);
+    Ok(())
+}
+
+fn ls_command(backend: &dyn IpfsBackend, path: &str, recursive: bool) -> Result<()> {
+    let path = inspect_path(path)?;
+    let (lines, total) = list_tree(backend, &path, recursive)?;
+    print_tree(&path, &lines, total);
+    Ok(())
+}
+
+fn patch_descriptions_command(
+    session: &Session,
+    manifest: &Path,
+    template: String,
+    dry_run: bool,
+) -> Result<()> {
+    let options = PatchOptions {
+        template,
+        dry_run,
+        json_style: session.options.json_style,
+        urls: session.config.urls.clone(),
+    };
+    let patch = patch_descriptions(session.backend, &manifest_file(manifest), &options)?;
+    print_patch_report(&patch, dry_run);
+    Ok(())
+}
+
+fn fetch_decrypt_command(
+    session: &Session,
+    uri: &str,
+    out: Option<PathBuf>,
+    key_file: Option<&Path>,
+    manifest: Option<&Path>,
+) -> Result<()> {
+    let key = collection_key(session.config, key_file, manifest)?;
+    let out = out.unwrap_or_else(|| default_download_dir(&session.cli.output, "decrypt", uri));
+    let written = fetch_decrypt(session.backend, &key, &parse_ipfs_path(uri)?, &out)?;
+    println!("🔓 已下载并解密 {} 个文件到 {:?}", written.len(), out);
+    Ok(())
+}
+
+fn clone_command(session: &Session, uri: &str, out: Option<PathBuf>) -> Result<()> {
+    let out = out.unwrap_or_else(|| default_download_dir(&session.cli.output, "clone", uri));
+    let options = CloneOptions {
+        hash_algorithm: session.config.hash_algorithm,
+    };
+    print_clone_report(&clone_collection(session.backend, uri, &out, &options)?);
+    Ok(())
+}
+
+fn rewrite_uris_command(
+    backend: &dyn IpfsBackend,
+    dir: &Path,
+    options: &RewriteOptions,
+) -> Result<()> {
+    let report = rewrite_metadata_uris(backend, dir, options)?;
+    print_rewrite_report(&report, options.dry_run);
+    Ok(())
+}
+
+fn monitor_command(
+    session: &Session,
+    manifests: Vec<PathBuf>,
+    schedule: &str,
+    check: CheckArgs,
+) -> Result<()> {
+    let output_root = &session.cli.output;
+    let paths = manifest_paths(manifests, output_root)?;
+    let schedule = parse_schedule(schedule)?;
+    let mut catalog = Catalog::open(output_root)?;
+    run_scheduled(
+        session.backend,
+        &paths,
+        &schedule,
+        &check.options(&session.config.timeouts),
+        &mut catalog,
+    )
+}
+
+// 未指定子命令时依次运行单件和批量流程
+fn default_command(session: &Session) -> Result<()> {
+    let (cli, config) = (session.cli, session.config);
+    upload_single(session, &PathBuf::from(DEFAULT_SINGLE_IMAGE))?;
+    let mut timings = Timings::default();
+    let options = session.options.batch(cli, config);
+    upload_batch(session, &batch_dir(config, None), &options, &mut timings)?;
+    print_next_steps();
     Ok(())
 }
 
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::arweave::ArweaveUris;
+use crate::filecoin::FilecoinArchive;
+use crate::ipld::DagCodec;
+use crate::pinning::RemotePinRecord;
+use crate::urls::CidUrls;
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+// 字段变化时同步更新 schema.rs，其它语言的实现依赖导出的结构定义
+
+// ✅ 工作流类型
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionKind {
+    Single,
+    Batch,
+    // watch 模式：文件陆续到达，逐个上传
+    Watch,
+}
+
+// 单个 token 的上传记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenEntry {
+    // 单件 NFT 没有数字 ID
+    pub token_id: Option<u64>,
+    pub name: String,
+    pub image_file: String,
+    // 图片内容哈希，带算法前缀 (如 `blake3:...`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_hash: Option<String>,
+    pub image_uri: String,
+    // 按文件内容判断的 MIME 类型，如 image/png、video/mp4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    // 视频、音频等放在 animation_url 的主文件链接，此时 image_uri 是封面图
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_uri: Option<String>,
+    // image_uri 是预览图时原图的链接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_uri: Option<String>,
+    pub metadata_file: String,
+    // 单独上传的元数据 CID (单件和 watch 模式)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_cid: Option<String>,
+    // 镜像到 Arweave 后的 ar:// 地址 (`arweave` 命令)，内容与 IPFS 上相同
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arweave: Option<ArweaveUris>,
+}
+
+// 上传前加密时记录的算法和密钥指纹，不包含密钥本身
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EncryptionInfo {
+    pub algorithm: String,
+    pub key_fingerprint: String,
+}
+
+// ✅ 一次运行的结果清单，保存在输出目录的 manifest.json 中
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Manifest {
+    pub kind: CollectionKind,
+    pub created_at: String,
+    pub backend: String,
+    // 上传时连接的 Kubo 版本；不同版本的默认导入参数可能不同，重现 CID 时参考
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_version: Option<String>,
+    pub output_dir: PathBuf,
+    // 单件: 图片 CID；批量: 图片文件夹 CID
+    pub image_cid: String,
+    // 视频、音频等文件的封面图 (单件: 封面图 CID；批量和 watch: posters 文件夹 CID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posters_cid: Option<String>,
+    // 大图的预览图 (单件: 预览图 CID；批量和 watch: previews 文件夹 CID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previews_cid: Option<String>,
+    // 加水印的公开预览 (单件: 水印图片 CID；批量: watermarked 文件夹 CID，文件名与 images/ 相同)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watermarked_cid: Option<String>,
+    // OpenSea 多语言扩展的各语言文件 (localization 文件夹 CID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localization_cid: Option<String>,
+    // 单件: 元数据 CID；批量: 元数据文件夹 CID
+    pub metadata_cid: String,
+    // 单件: mint 时使用的 token URI；批量: 合约的 Base URI
+    pub token_uri: String,
+    pub tokens: Vec<TokenEntry>,
+    // 使用多节点故障转移时，每个 CID 实际由哪个节点完成上传
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub served_by: BTreeMap<String, String>,
+    // 每个根 CID 的 ipfs://、路径网关、子域名网关等链接，按用途 (image、metadata 等) 记录
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub urls: BTreeMap<String, CidUrls>,
+    // 上传前加密过时才有；IPFS 上的图片和元数据需要集合密钥才能读取
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionInfo>,
+    // 元数据以 IPLD 节点发布时的编码 ([ipld])；为空表示普通 JSON 文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipld: Option<DagCodec>,
+    // Filecoin 长期归档 (`filecoin submit`)：按用途 (image、metadata) 记录 CAR、piece CID 和交易 ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub filecoin: BTreeMap<String, FilecoinArchive>,
+    // `pin-remote` 的结果：服务名 -> 集合名、数量、Pinata Group 和专属网关地址
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub remote_pins: BTreeMap<String, RemotePinRecord>,
+}
+
+impl Manifest {
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(MANIFEST_FILE_NAME);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let path = if path.is_dir() {
+            path.join(MANIFEST_FILE_NAME)
+        } else {
+            path.to_path_buf()
+        };
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
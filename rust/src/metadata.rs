@@ -0,0 +1,637 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+// ✅ 定义元数据结构体
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NftMetadata {
+    pub name: String,
+    pub description: String,
+    pub image: String,
+    // 视频、音频等非图片文件，此时 image 是封面图
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
+    pub attributes: Vec<Attribute>,
+    // 项目自定义的其它顶层字段 (如 dna、edition)，读取已有元数据时原样保留
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+// ERC-721 结构本身的字段，不能作为自定义字段
+pub const STANDARD_FIELDS: [&str; 5] = [
+    "name",
+    "description",
+    "image",
+    "animation_url",
+    "attributes",
+];
+
+// ✅ 写入每个 token 元数据的自定义字段：集合共用的，以及按 token ID 单独指定的
+#[derive(Debug, Clone, Default)]
+pub struct ExtraFields {
+    pub common: serde_json::Map<String, serde_json::Value>,
+    pub tokens: BTreeMap<u64, serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ExtraFields {
+    pub fn is_empty(&self) -> bool {
+        self.common.is_empty() && self.tokens.is_empty()
+    }
+
+    // 加上自定义字段后的元数据：token 单独指定的覆盖共用的，元数据中已有的字段不变
+    pub fn apply(&self, metadata: &NftMetadata, token_id: Option<u64>) -> NftMetadata {
+        let mut metadata = metadata.clone();
+        let own = token_id.and_then(|id| self.tokens.get(&id));
+        for (key, value) in own.into_iter().flatten().chain(&self.common) {
+            if !metadata.extra.contains_key(key) {
+                metadata.extra.insert(key.clone(), value.clone());
+            }
+        }
+        metadata
+    }
+}
+
+// ✅ 以下函数不依赖本地节点和文件系统，原生工作流和 WASM 绑定共用同一套逻辑
+
+// 单个 NFT：链接直接指向图片文件的 CID
+pub fn build_single_metadata(image_filename: &str, image_cid: &str) -> NftMetadata {
+    let image_name_without_ext = image_filename
+        .rsplit_once('.')
+        .map_or(image_filename, |(stem, _)| stem);
+    NftMetadata {
+        name: image_name_without_ext.to_string(),
+        description: format!("这是一个为图片 {} 动态生成的元数据。", image_filename),
+        image: format!("ipfs://{}", image_cid),
+        animation_url: None,
+        attributes: vec![Attribute {
+            trait_type: "类型".to_string(),
+            value: serde_json::Value::String("单件艺术品".to_string()),
+        }],
+        extra: serde_json::Map::new(),
+    }
+}
+
+// 批量集合：链接指向图片文件夹 CID 下的具体文件名
+pub fn build_batch_metadata(
+    token_id: u64,
+    images_folder_cid: &str,
+    image_filename: &str,
+) -> NftMetadata {
+    build_token_metadata(
+        token_id,
+        format!("ipfs://{}/{}", images_folder_cid, image_filename),
+    )
+}
+
+// 集合中的单个 token，图片链接由调用方决定 (文件夹路径或单独的文件 CID)
+pub fn build_token_metadata(token_id: u64, image_uri: String) -> NftMetadata {
+    NftMetadata {
+        name: format!("MetaCore #{}", token_id),
+        description: "MetaCore 集合中的一个独特成员。".to_string(),
+        image: image_uri,
+        animation_url: None,
+        attributes: vec![Attribute {
+            trait_type: "ID".to_string(),
+            value: serde_json::Value::Number(token_id.into()),
+        }],
+        extra: serde_json::Map::new(),
+    }
+}
+
+// ✅ 严格模式下检查元数据 JSON：只允许 ERC-721 的字段 (包括属性中的字段)，链接必须是 ipfs://
+pub fn strict_problems(value: &serde_json::Value) -> Vec<String> {
+    let Some(object) = value.as_object() else {
+        return vec!["元数据应为 JSON 对象".to_string()];
+    };
+    let mut problems: Vec<String> = object
+        .keys()
+        .filter(|key| !STANDARD_FIELDS.contains(&key.as_str()))
+        .map(|key| format!("未知字段: {}", key))
+        .collect();
+    for (index, attribute) in object
+        .get("attributes")
+        .and_then(|attributes| attributes.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        for key in attribute.as_object().into_iter().flat_map(|a| a.keys()) {
+            if key != "trait_type" && key != "value" {
+                problems.push(format!("attributes[{}] 中的未知字段: {}", index, key));
+            }
+        }
+    }
+    for field in ["image", "animation_url"] {
+        if let Some(uri) = object.get(field).and_then(|uri| uri.as_str())
+            && !uri.starts_with("ipfs://")
+        {
+            problems.push(format!("{} 不是 ipfs:// 链接: {}", field, uri));
+        }
+    }
+    problems
+}
+
+// ✅ 解析元数据 JSON；strict 为 true 时按 strict_problems 拒绝未知字段和非 ipfs:// 链接
+pub fn parse_metadata(json: &str, strict: bool) -> anyhow::Result<NftMetadata> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| anyhow!("❌ 不是有效的 JSON: {}", e))?;
+    if strict {
+        let problems = strict_problems(&value);
+        if !problems.is_empty() {
+            return Err(anyhow!("❌ {}", problems.join("; ")));
+        }
+    }
+    serde_json::from_value(value).map_err(|e| anyhow!("❌ 元数据结构不正确: {}", e))
+}
+
+// 主文件移到 animation_url，image 改为封面图
+pub fn route_animation(metadata: &mut NftMetadata, poster_uri: String) {
+    metadata.animation_url = Some(std::mem::replace(&mut metadata.image, poster_uri));
+}
+
+// 描述模板：{name}、{token_id}、{image} 和 {description} (原来的描述) 会被替换
+pub fn render_description(template: &str, metadata: &NftMetadata, token_id: Option<u64>) -> String {
+    template
+        .replace("{name}", &metadata.name)
+        .replace(
+            "{token_id}",
+            &token_id.map(|id| id.to_string()).unwrap_or_default(),
+        )
+        .replace("{image}", &metadata.image)
+        .replace("{description}", &metadata.description)
+}
+
+// ✅ config.toml 中的 [collection] 段：集合名称和描述模板，代替默认的 "MetaCore #<ID>" 和描述
+//
+// [collection]
+// name = "Moon Cats"
+// description = "{name}，Moon Cats 集合中的第 {token_id} 只猫。"
+// images_dir = "./images"
+// builtin_attributes = false
+//
+// [[collection.attributes]]
+// trait_type = "Artist"
+// value = "Alice"
+//
+// [collection.trait_defaults]
+// Background = "None"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CollectionConfig {
+    // 集合中的 token 命名为 "<name> #<ID>"；单件的名称仍是图片文件名
+    pub name: String,
+    // 描述模板，占位符见 render_description
+    pub description: String,
+    // batch 和 estimate 不指定文件夹时读取的图片文件夹
+    pub images_dir: Option<PathBuf>,
+    // 保留默认生成的属性 (批量的 "ID"、单件的 "类型")
+    pub builtin_attributes: bool,
+    // 写入每个 token 的属性，同名的属性以这里为准
+    pub attributes: Vec<Attribute>,
+    // token 没有这个属性时补上的默认值
+    pub trait_defaults: BTreeMap<String, serde_json::Value>,
+}
+
+impl Default for CollectionConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            description: String::new(),
+            images_dir: None,
+            builtin_attributes: true,
+            attributes: Vec::new(),
+            trait_defaults: BTreeMap::new(),
+        }
+    }
+}
+
+impl CollectionConfig {
+    // 用集合名称、描述模板和属性改写生成的元数据，没有填写的保持默认
+    pub fn apply(&self, metadata: &mut NftMetadata, token_id: Option<u64>) {
+        if let Some(id) = token_id.filter(|_| !self.name.is_empty()) {
+            metadata.name = format!("{} #{}", self.name, id);
+        }
+        if !self.description.is_empty() {
+            metadata.description = render_description(&self.description, metadata, token_id);
+        }
+        if !self.builtin_attributes {
+            metadata.attributes.clear();
+        }
+        for attribute in &self.attributes {
+            match metadata
+                .attributes
+                .iter_mut()
+                .find(|a| a.trait_type == attribute.trait_type)
+            {
+                Some(existing) => existing.value = attribute.value.clone(),
+                None => metadata.attributes.push(attribute.clone()),
+            }
+        }
+        for (trait_type, value) in &self.trait_defaults {
+            if !metadata
+                .attributes
+                .iter()
+                .any(|a| &a.trait_type == trait_type)
+            {
+                metadata.attributes.push(Attribute {
+                    trait_type: trait_type.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    // 检查属性配置，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut seen = Vec::new();
+        for attribute in &self.attributes {
+            if attribute.trait_type.is_empty() {
+                problems.push("attributes 中有空的 trait_type".to_string());
+            } else if seen.contains(&&attribute.trait_type) {
+                problems.push(format!("attributes 中的 {} 重复出现", attribute.trait_type));
+            }
+            seen.push(&attribute.trait_type);
+        }
+        if self.trait_defaults.keys().any(|key| key.is_empty()) {
+            problems.push("trait_defaults 中有空的属性名".to_string());
+        }
+        problems
+    }
+}
+
+// ✅ 元数据文件名的后缀：必须与合约拼接 tokenURI 的方式一致
+// auto 会读取合约已有 token 的 tokenURI 来判断
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonSuffix {
+    #[default]
+    None,
+    Json,
+    Auto,
+}
+
+impl JsonSuffix {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Json => "json",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+impl fmt::Display for JsonSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JsonSuffix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "json" | ".json" => Ok(Self::Json),
+            "auto" => Ok(Self::Auto),
+            other => Err(format!(
+                "❌ 不支持的后缀模式: {} (可选: none, json, auto)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ 元数据 JSON 的写法：pretty (默认，带缩进便于阅读)、minified (没有空白，字段顺序不变，
+// 与多数工具的 JSON.stringify 输出相同) 或 canonical (RFC 8785：键按顺序排列、没有空白、
+// 固定的数字格式、末尾没有换行)，canonical 时同样的内容总是得到同样的字节和 CID，
+// 与结构体字段的顺序和工具版本无关
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonStyle {
+    #[default]
+    Pretty,
+    Minified,
+    Canonical,
+}
+
+impl JsonStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pretty => "pretty",
+            Self::Minified => "minified",
+            Self::Canonical => "canonical",
+        }
+    }
+
+    // 写入元数据文件和单独上传 (单件、watch) 的内容，两者字节相同，本地文件的 CID 与上传的一致
+    pub fn file_json<T: Serialize>(&self, data: &T) -> serde_json::Result<String> {
+        match self {
+            Self::Pretty => serde_json::to_string_pretty(data),
+            Self::Minified => serde_json::to_string(data),
+            Self::Canonical => Ok(canonical_json(&serde_json::to_value(data)?)),
+        }
+    }
+}
+
+impl fmt::Display for JsonStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JsonStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "minified" | "minify" | "compact" => Ok(Self::Minified),
+            "canonical" | "jcs" => Ok(Self::Canonical),
+            other => Err(format!(
+                "❌ 不支持的 JSON 格式: {} (可选: pretty, minified, canonical)",
+                other
+            )),
+        }
+    }
+}
+
+// JavaScript 能精确表示的最大整数 (Number.MAX_SAFE_INTEGER + 1)
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+// 数字按 ECMAScript 的 Number.prototype.toString 输出：整数不带小数点，
+// 指数在 [-7, 21) 之外时使用科学计数法，如 1e+21、1e-7；
+// 超过 2^53 的整数先转成 f64 (与 JavaScript 解析后的值相同)，如 2^64-1 写成 18446744073709552000
+fn canonical_number(number: &serde_json::Number) -> String {
+    if let Some(n) = number
+        .as_i64()
+        .filter(|n| n.unsigned_abs() <= MAX_SAFE_INTEGER)
+    {
+        return n.to_string();
+    }
+    let value = number.as_f64().unwrap_or_default();
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    // {:e} 给出能还原该值的最短位数，如 1.25e-7；两个同样短的数字与原值距离相等时
+    // ECMAScript 取末位为偶数的那个，所以再按这个位数四舍六入五成双格式化一次
+    let shortest = format!("{:e}", value.abs());
+    let precision = shortest
+        .split('e')
+        .next()
+        .unwrap_or_default()
+        .len()
+        .saturating_sub(2);
+    let scientific = format!("{:.*e}", precision, value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+    let body = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let exponent = n - 1;
+        let sign = if exponent >= 0 { "+" } else { "-" };
+        let mantissa = if k == 1 {
+            digits.clone()
+        } else {
+            format!("{}.{}", &digits[..1], &digits[1..])
+        };
+        format!("{}e{}{}", mantissa, sign, exponent.abs())
+    };
+    if value < 0.0 {
+        format!("-{}", body)
+    } else {
+        body
+    }
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Null => out.push_str("null"),
+        serde_json::Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        serde_json::Value::Number(n) => out.push_str(&canonical_number(n)),
+        // serde_json 的转义规则与 RFC 8785 相同：只转义引号、反斜杠和控制字符
+        serde_json::Value::String(s) => out.push_str(&serde_json::to_string(s).unwrap_or_default()),
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(map) => {
+            // 键按 UTF-16 编码单元排序
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key).unwrap_or_default());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+// ✅ RFC 8785 (JSON Canonicalization Scheme) 格式的 JSON
+pub fn canonical_json(value: &serde_json::Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+// 元数据文件名：是否带 .json 后缀取决于合约的 tokenURI 拼接方式
+pub fn metadata_file_name(stem: &str, use_json_suffix: bool) -> String {
+    if use_json_suffix {
+        format!("{}.json", stem)
+    } else {
+        stem.to_string()
+    }
+}
+
+// 可以整体改写前缀的 URI 字段
+pub const URI_FIELDS: [&str; 2] = ["image", "animation_url"];
+
+// 一个字段的改写
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+// ✅ 把元数据 JSON 中 image / animation_url 的前缀 from 改为 to
+// 只替换这些字符串值，其它内容和格式保持原样；替换结果不符合预期时重新格式化整个文件
+pub fn rewrite_uri_prefix(
+    content: &str,
+    from: &str,
+    to: &str,
+) -> serde_json::Result<(String, Vec<UriChange>)> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let mut changes = Vec::new();
+    for field in URI_FIELDS {
+        if let Some(serde_json::Value::String(uri)) = value.get(field)
+            && let Some(rest) = uri.strip_prefix(from)
+        {
+            changes.push(UriChange {
+                field,
+                before: uri.clone(),
+                after: format!("{}{}", to, rest),
+            });
+        }
+    }
+    if changes.is_empty() {
+        return Ok((content.to_string(), changes));
+    }
+
+    let mut rewritten = content.to_string();
+    for change in &changes {
+        rewritten = rewritten.replace(
+            &serde_json::to_string(&change.before)?,
+            &serde_json::to_string(&change.after)?,
+        );
+    }
+    for change in &changes {
+        value[change.field] = serde_json::Value::String(change.after.clone());
+    }
+    if serde_json::from_str::<serde_json::Value>(&rewritten).ok() != Some(value.clone()) {
+        rewritten = serde_json::to_string_pretty(&value)?;
+    }
+    Ok((rewritten, changes))
+}
+
+// 检查 URI 的格式，返回问题说明
+pub fn validate_uri(uri: &str) -> Option<String> {
+    if uri.is_empty() {
+        return Some("为空".to_string());
+    }
+    if uri.chars().any(char::is_whitespace) {
+        return Some(format!("包含空白字符: {}", uri));
+    }
+    let Some((scheme, rest)) = uri.split_once("://") else {
+        return Some(format!("缺少协议 (如 ipfs://): {}", uri));
+    };
+    if !["ipfs", "ipns", "ar", "https", "http"].contains(&scheme) {
+        return Some(format!("不支持的协议 {}: {}", scheme, uri));
+    }
+    if rest.is_empty() || rest.starts_with('/') || rest.contains("//") {
+        return Some(format!("路径格式错误: {}", uri));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Number, Value, json};
+
+    use super::*;
+
+    fn float(bits: u64) -> String {
+        canonical_number(&Number::from_f64(f64::from_bits(bits)).unwrap())
+    }
+
+    // RFC 8785 附录 B 的数字格式示例 (IEEE 754 位模式 -> 输出)
+    #[test]
+    fn numbers_match_rfc8785_appendix_b() {
+        let cases = [
+            (0x0000000000000000, "0"),
+            (0x8000000000000000, "0"),
+            (0x0000000000000001, "5e-324"),
+            (0x8000000000000001, "-5e-324"),
+            (0x7fefffffffffffff, "1.7976931348623157e+308"),
+            (0xffefffffffffffff, "-1.7976931348623157e+308"),
+            (0x4340000000000000, "9007199254740992"),
+            (0xc340000000000000, "-9007199254740992"),
+            (0x4430000000000000, "295147905179352830000"),
+            (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+            (0x44b52d02c7e14af6, "1e+23"),
+            (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+            (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+            (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+            (0x444b1ae4d6e2ef50, "1e+21"),
+            (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+            (0x3eb0c6f7a0b5ed8d, "0.000001"),
+            (0x41b3de4355555553, "333333333.3333332"),
+            (0x41b3de4355555554, "333333333.33333325"),
+            (0x41b3de4355555555, "333333333.3333333"),
+            (0x41b3de4355555556, "333333333.3333334"),
+            (0x41b3de4355555557, "333333333.33333343"),
+            (0xbecbf647612f3696, "-0.0000033333333333333333"),
+            (0x43143ff3c1cb0959, "1424953923781206.2"),
+        ];
+        for (bits, expected) in cases {
+            assert_eq!(float(bits), expected, "{:#018x}", bits);
+        }
+    }
+
+    // 超过 2^53 的整数与 JavaScript 一样按 f64 输出
+    #[test]
+    fn large_integers_are_formatted_as_doubles() {
+        let integer = |value: Value| canonical_json(&value);
+        assert_eq!(integer(json!(9007199254740992u64)), "9007199254740992");
+        assert_eq!(integer(json!(-9007199254740992i64)), "-9007199254740992");
+        assert_eq!(integer(json!(9007199254740993u64)), "9007199254740992");
+        assert_eq!(integer(json!(u64::MAX)), "18446744073709552000");
+        assert_eq!(integer(json!(i64::MIN)), "-9223372036854776000");
+    }
+
+    // RFC 8785 3.2.3：键按 UTF-16 编码单元排序
+    #[test]
+    fn keys_are_sorted_by_utf16_code_units() {
+        let value = json!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{80}": "Control",
+            "\u{f6}": "Latin Small Letter O With Diaeresis"
+        });
+        assert_eq!(
+            canonical_json(&value),
+            concat!(
+                "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",",
+                "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",",
+                "\"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}",
+            )
+        );
+    }
+
+    // RFC 8785 3.2.4 的完整示例：数字、字符串转义和键排序
+    #[test]
+    fn matches_rfc8785_example() {
+        let input = r#"{
+  "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+  "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+  "literals": [null, true, false]
+}"#;
+        let value: Value = serde_json::from_str(input).unwrap();
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::ipld::DagCodec;
+use crate::manifest::EncryptionInfo;
+use crate::unixfs::AddConfig;
+
+// 上传耗时直方图的桶 (秒)
+const LATENCY_BUCKETS: &[f64] = &[0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    // 与 LATENCY_BUCKETS 一一对应的累计计数
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS.len()];
+        }
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    // 以下计数都按后端名称区分
+    files_uploaded: BTreeMap<String, u64>,
+    bytes_uploaded: BTreeMap<String, u64>,
+    // (后端, 操作) -> 失败次数
+    failures: BTreeMap<(String, String), u64>,
+    upload_seconds: BTreeMap<String, Histogram>,
+    // (后端, 结果: pinned / unpinned / error) -> 次数
+    pin_checks: BTreeMap<(String, String), u64>,
+}
+
+// ✅ 进程内的 Prometheus 指标，由 MeteredBackend 记录、由 serve 暴露在 /metrics
+#[derive(Debug, Default)]
+pub struct Metrics {
+    registry: Mutex<Registry>,
+}
+
+impl Metrics {
+    fn with<T>(&self, f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut registry)
+    }
+
+    pub fn record_upload(&self, backend: &str, files: u64, bytes: u64, seconds: f64) {
+        self.with(|r| {
+            *r.files_uploaded.entry(backend.to_string()).or_default() += files;
+            *r.bytes_uploaded.entry(backend.to_string()).or_default() += bytes;
+            r.upload_seconds
+                .entry(backend.to_string())
+                .or_default()
+                .observe(seconds);
+        });
+    }
+
+    pub fn record_failure(&self, backend: &str, operation: &str) {
+        self.with(|r| {
+            *r.failures
+                .entry((backend.to_string(), operation.to_string()))
+                .or_default() += 1;
+        });
+    }
+
+    pub fn record_pin_check(&self, backend: &str, result: &str) {
+        self.with(|r| {
+            *r.pin_checks
+                .entry((backend.to_string(), result.to_string()))
+                .or_default() += 1;
+        });
+    }
+
+    // Prometheus 文本格式 (version 0.0.4)
+    pub fn render(&self) -> String {
+        self.with(|r| {
+            let mut out = String::new();
+            write_header(
+                &mut out,
+                "ipfs_uploader_files_uploaded_total",
+                "counter",
+                "已上传的文件数",
+            );
+            for (backend, value) in &r.files_uploaded {
+                let _ = writeln!(
+                    out,
+                    "ipfs_uploader_files_uploaded_total{{backend=\"{}\"}} {}",
+                    backend, value
+                );
+            }
+            write_header(
+                &mut out,
+                "ipfs_uploader_bytes_uploaded_total",
+                "counter",
+                "已上传的字节数",
+            );
+            for (backend, value) in &r.bytes_uploaded {
+                let _ = writeln!(
+                    out,
+                    "ipfs_uploader_bytes_uploaded_total{{backend=\"{}\"}} {}",
+                    backend, value
+                );
+            }
+            write_header(
+                &mut out,
+                "ipfs_uploader_failures_total",
+                "counter",
+                "后端操作失败次数",
+            );
+            for ((backend, operation), value) in &r.failures {
+                let _ = writeln!(
+                    out,
+                    "ipfs_uploader_failures_total{{backend=\"{}\",operation=\"{}\"}} {}",
+                    backend, operation, value
+                );
+            }
+            write_header(
+                &mut out,
+                "ipfs_uploader_upload_duration_seconds",
+                "histogram",
+                "单次上传耗时",
+            );
+            for (backend, histogram) in &r.upload_seconds {
+                let name = "ipfs_uploader_upload_duration_seconds";
+                for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+                    let _ = writeln!(
+                        out,
+                        "{}_bucket{{backend=\"{}\",le=\"{}\"}} {}",
+                        name, backend, bound, count
+                    );
+                }
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{{backend=\"{}\",le=\"+Inf\"}} {}",
+                    name, backend, histogram.count
+                );
+                let _ = writeln!(
+                    out,
+                    "{}_sum{{backend=\"{}\"}} {}",
+                    name, backend, histogram.sum
+                );
+                let _ = writeln!(
+                    out,
+                    "{}_count{{backend=\"{}\"}} {}",
+                    name, backend, histogram.count
+                );
+            }
+            write_header(
+                &mut out,
+                "ipfs_uploader_pin_checks_total",
+                "counter",
+                "pin 状态检查结果",
+            );
+            for ((backend, result), value) in &r.pin_checks {
+                let _ = writeln!(
+                    out,
+                    "ipfs_uploader_pin_checks_total{{backend=\"{}\",result=\"{}\"}} {}",
+                    backend, result, value
+                );
+            }
+            out
+        })
+    }
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+// 统计一个路径下的文件数和总字节数 (跳过隐藏文件，与上传行为一致)
+pub fn path_size(path: &Path) -> (u64, u64) {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .fold((0, 0), |(files, bytes), entry| {
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            (files + 1, bytes + len)
+        })
+}
+
+// ✅ 包装任意后端，记录上传数量、字节数、耗时、失败次数和 pin 检查结果
+pub struct MeteredBackend {
+    inner: Box<dyn IpfsBackend>,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredBackend {
+    pub fn new(inner: Box<dyn IpfsBackend>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn timed<T>(
+        &self,
+        operation: &str,
+        size: (u64, u64),
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let started = Instant::now();
+        let result = f();
+        match &result {
+            Ok(_) => self.metrics.record_upload(
+                self.inner.name(),
+                size.0,
+                size.1,
+                started.elapsed().as_secs_f64(),
+            ),
+            Err(_) => self.metrics.record_failure(self.inner.name(), operation),
+        }
+        result
+    }
+}
+
+impl IpfsBackend for MeteredBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        self.inner.check_compatibility()
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        self.timed("add", path_size(target_path), || {
+            self.inner.add_path(target_path)
+        })
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        self.timed("add", (1, data.len() as u64), || self.inner.add_bytes(data))
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        self.timed("dag_put", (1, 0), || self.inner.dag_put(node, codec))
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.inner.add_config()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        let result = self.inner.is_pinned(cid);
+        let label = match &result {
+            Ok(true) => "pinned",
+            Ok(false) => "unpinned",
+            Err(_) => "error",
+        };
+        self.metrics.record_pin_check(self.inner.name(), label);
+        result
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        let result = self.inner.pin(cid);
+        if result.is_err() {
+            self.metrics.record_failure(self.inner.name(), "pin");
+        }
+        result
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        let result = self.inner.unpin(cid);
+        if result.is_err() {
+            self.metrics.record_failure(self.inner.name(), "unpin");
+        }
+        result
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        self.inner.ls(path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.cat(path)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        let result = self.inner.provide(cid, recursive);
+        if result.is_err() {
+            self.metrics.record_failure(self.inner.name(), "provide");
+        }
+        result
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        self.inner.find_providers(cid, limit)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.inner.served_by(cid)
+    }
+
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        self.inner.encryption()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.inner.node_version()
+    }
+}
+
+// ✅ 在后台线程中提供 GET /metrics
+pub fn serve(addr: &str, metrics: Arc<Metrics>) -> Result<()> {
+    let listener =
+        TcpListener::bind(addr).map_err(|e| anyhow!("❌ 无法监听指标地址 {}: {}", addr, e))?;
+    println!(
+        "📈 Prometheus 指标: http://{}/metrics",
+        listener.local_addr()?
+    );
+    thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            if let Err(e) = handle_connection(stream, &metrics) {
+                eprintln!("⚠️  处理指标请求失败: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, metrics: &Metrics) -> Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, content_type, body) = if method == "GET" && path == "/metrics" {
+        ("200 OK", "text/plain; version=0.0.4", metrics.render())
+    } else {
+        ("404 Not Found", "text/plain", "not found\n".to_string())
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::backend::IpfsBackend;
+use crate::clone::{CloneOptions, clone_collection, print_clone_report};
+use crate::hashing::HashAlgorithm;
+use crate::manifest::Manifest;
+use crate::urls::{UrlConfig, record_urls};
+use crate::workflow::record_served_by;
+
+pub const MIGRATION_REPORT_FILE_NAME: &str = "migration.json";
+
+// migrate 的可选项
+#[derive(Debug, Clone, Default)]
+pub struct MigrateOptions {
+    // 用当前节点重新上传 (CIDv1)，得到新的 CID；否则只 pin 原来的 CID
+    pub re_add: bool,
+    pub hash_algorithm: HashAlgorithm,
+    // 迁移后的清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CidChange {
+    pub role: String,
+    pub old_cid: String,
+    pub new_cid: String,
+}
+
+// ✅ 迁移报告，保存为 migration.json
+#[derive(Serialize, Debug, Clone)]
+pub struct MigrationReport {
+    pub source: String,
+    pub migrated_at: String,
+    pub backend: String,
+    pub re_added: bool,
+    pub changes: Vec<CidChange>,
+    pub old_token_uri: String,
+    pub new_token_uri: String,
+    // 迁移后的清单，之后可以用于 pin-remote、verify 等命令
+    pub manifest: PathBuf,
+}
+
+impl MigrationReport {
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(MIGRATION_REPORT_FILE_NAME);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+// 把元数据中的图片地址改为新的图片文件夹；直接替换 JSON 字符串，保持其它内容不变
+fn rewrite_image_uri(path: &Path, old_uri: &str, new_uri: &str) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let old = serde_json::to_string(old_uri)?;
+    if !content.contains(&old) {
+        return Err(anyhow!("❌ 元数据 {:?} 中找不到图片地址 {}", path, old_uri));
+    }
+    fs::write(
+        path,
+        content.replace(&old, &serde_json::to_string(new_uri)?),
+    )?;
+    Ok(())
+}
+
+// 重新上传下载下来的图片和元数据，更新清单中的 CID
+fn re_add(backend: &dyn IpfsBackend, manifest: &mut Manifest, out: &Path) -> Result<()> {
+    let image_cid = backend.add_path(&out.join("images"))?;
+    let metadata_dir = out.join("metadata");
+    for token in &mut manifest.tokens {
+        let image_uri = format!("ipfs://{}/{}", image_cid, token.image_file);
+        rewrite_image_uri(
+            &metadata_dir.join(&token.metadata_file),
+            &token.image_uri,
+            &image_uri,
+        )?;
+        token.image_uri = image_uri;
+    }
+    manifest.metadata_cid = backend.add_path(&metadata_dir)?;
+    manifest.image_cid = image_cid;
+    manifest.token_uri = format!("ipfs://{}/", manifest.metadata_cid);
+    Ok(())
+}
+
+// ✅ 下载一个已部署的集合，在当前节点上固定 (或重新上传)，并生成新旧 CID 对照报告
+pub fn migrate_collection(
+    backend: &dyn IpfsBackend,
+    uri: &str,
+    out: &Path,
+    options: &MigrateOptions,
+) -> Result<(Manifest, MigrationReport)> {
+    let clone_options = CloneOptions {
+        hash_algorithm: options.hash_algorithm,
+    };
+    let cloned = clone_collection(backend, uri, out, &clone_options)?;
+    print_clone_report(&cloned);
+    let old = cloned.manifest.clone();
+    let mut manifest = cloned.manifest;
+
+    if options.re_add {
+        println!("\n--- 正在重新上传集合 ---");
+        re_add(backend, &mut manifest, out)?;
+    } else {
+        if manifest.image_cid.is_empty() {
+            println!("⚠️  图片不在同一个文件夹中，只固定元数据文件夹；可以使用 --re-add 合并");
+        }
+        for cid in [&manifest.metadata_cid, &manifest.image_cid] {
+            if cid.is_empty() {
+                continue;
+            }
+            backend.pin(cid)?;
+            println!("📌 已在节点上固定: {}", cid);
+        }
+    }
+    manifest.created_at = Utc::now().to_rfc3339();
+    record_served_by(&mut manifest, backend);
+    record_urls(&mut manifest, &options.urls);
+    let manifest_path = manifest.save(out)?;
+
+    let report = MigrationReport {
+        source: uri.to_string(),
+        migrated_at: manifest.created_at.clone(),
+        backend: backend.name().to_string(),
+        re_added: options.re_add,
+        changes: vec![
+            CidChange {
+                role: "images-root".to_string(),
+                old_cid: old.image_cid,
+                new_cid: manifest.image_cid.clone(),
+            },
+            CidChange {
+                role: "metadata-root".to_string(),
+                old_cid: old.metadata_cid,
+                new_cid: manifest.metadata_cid.clone(),
+            },
+        ],
+        old_token_uri: old.token_uri,
+        new_token_uri: manifest.token_uri.clone(),
+        manifest: manifest_path,
+    };
+    Ok((manifest, report))
+}
+
+pub fn print_migration_report(report: &MigrationReport) {
+    println!("\n🚚 迁移结果 ({}):", report.backend);
+    for change in &report.changes {
+        let old = if change.old_cid.is_empty() {
+            "(多个文件夹)"
+        } else {
+            change.old_cid.as_str()
+        };
+        if change.old_cid == change.new_cid {
+            println!("   - {}: {} (不变)", change.role, old);
+        } else {
+            println!("   - {}: {} → {}", change.role, old, change.new_cid);
+        }
+    }
+    if report.old_token_uri == report.new_token_uri {
+        println!("🔗 Base URI 不变: {}", report.old_token_uri);
+    } else {
+        println!("🔗 旧 Base URI: {}", report.old_token_uri);
+        println!("🔗 新 Base URI: {}", report.new_token_uri);
+        println!("下一步，请在合约中把 Base URI 更新为新的地址。");
+    }
+}
@@ -0,0 +1,300 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use cron::Schedule;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::backend::IpfsBackend;
+use crate::catalog::Catalog;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+
+pub const DEFAULT_GATEWAY: &str = "https://ipfs.io";
+pub const ALERTS_FILE_NAME: &str = "alerts.jsonl";
+// 查找清单时展开的最大目录层数 (运行目录模板中的子文件夹)
+const MAX_RUN_DEPTH: usize = 4;
+
+// 巡检的可选项
+#[derive(Debug, Clone)]
+pub struct MonitorOptions {
+    // 用来检查可访问性的网关
+    pub gateways: Vec<String>,
+    // 发现未固定的 CID 时是否自动重新 pin
+    pub repin: bool,
+    // 网关请求的超时，None 表示不限制
+    pub gateway_timeout: Option<Duration>,
+    // pin 状态缓存的有效期，0 表示每次都向节点查询
+    pub pin_cache_ttl: Duration,
+}
+
+impl Default for MonitorOptions {
+    fn default() -> Self {
+        Self {
+            gateways: vec![DEFAULT_GATEWAY.to_string()],
+            repin: true,
+            gateway_timeout: Some(Duration::from_secs(30)),
+            pin_cache_ttl: Duration::from_secs(3600),
+        }
+    }
+}
+
+// 单个 CID 的检查结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CidReport {
+    pub label: String,
+    pub cid: String,
+    pub pinned: bool,
+    pub repinned: bool,
+    // (网关, 是否可访问)
+    pub gateways: Vec<(String, bool)>,
+}
+
+impl CidReport {
+    pub fn is_healthy(&self) -> bool {
+        (self.pinned || self.repinned) && self.gateways.iter().all(|(_, ok)| *ok)
+    }
+}
+
+// ✅ 告警：打印到终端，同时追加到清单目录下的 alerts.jsonl
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Alert {
+    pub at: String,
+    pub manifest: PathBuf,
+    pub cid: String,
+    pub label: String,
+    pub problem: String,
+}
+
+// 清单中需要保持可用的所有 CID (去重)
+pub fn manifest_cids(manifest: &Manifest) -> Vec<(String, String)> {
+    let mut cids: Vec<(String, String)> = Vec::new();
+    let mut push = |label: String, cid: &str| {
+        if !cid.is_empty() && !cids.iter().any(|(_, c)| c == cid) {
+            cids.push((label, cid.to_string()));
+        }
+    };
+    push("图片".to_string(), &manifest.image_cid);
+    push("元数据".to_string(), &manifest.metadata_cid);
+    if let Some(cid) = &manifest.posters_cid {
+        push("封面图".to_string(), cid);
+    }
+    if let Some(cid) = &manifest.previews_cid {
+        push("预览图".to_string(), cid);
+    }
+    if let Some(cid) = &manifest.watermarked_cid {
+        push("水印图".to_string(), cid);
+    }
+    if let Some(cid) = &manifest.localization_cid {
+        push("多语言".to_string(), cid);
+    }
+    for token in &manifest.tokens {
+        if let Some(cid) = &token.metadata_cid {
+            push(format!("{} 元数据", token.name), cid);
+        }
+    }
+    cids
+}
+
+pub fn gateway_url(gateway: &str, cid: &str) -> String {
+    format!("{}/ipfs/{}", gateway.trim_end_matches('/'), cid)
+}
+
+// 检查一个清单：pin 状态 + 网关可访问性，必要时重新 pin
+// 只缓存“已固定”的结果，未固定的 CID 每次都重新查询，以便及时重新 pin
+pub fn check_manifest(
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+    options: &MonitorOptions,
+    catalog: &mut Catalog,
+) -> Result<Vec<CidReport>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(options.gateway_timeout)
+        .build()?;
+
+    let mut reports = Vec::new();
+    for (label, cid) in manifest_cids(manifest) {
+        let pinned = match catalog.cached_pin_status(&cid, options.pin_cache_ttl) {
+            Some(true) => true,
+            _ => {
+                let pinned = backend.is_pinned(&cid)?;
+                catalog.record_pin_status(&cid, pinned);
+                pinned
+            }
+        };
+        let mut repinned = false;
+        if !pinned && options.repin {
+            println!("📌 {} ({}) 未被固定，正在重新 pin...", label, cid);
+            match backend.pin(&cid) {
+                Ok(()) => {
+                    repinned = true;
+                    catalog.record_pin_status(&cid, true);
+                }
+                Err(e) => eprintln!("❌ 重新 pin 失败: {:#}", e),
+            }
+        }
+
+        let gateways = options
+            .gateways
+            .iter()
+            .map(|gateway| {
+                let ok = client
+                    .head(gateway_url(gateway, &cid))
+                    .send()
+                    .map(|res| res.status().is_success())
+                    .unwrap_or(false);
+                (gateway.clone(), ok)
+            })
+            .collect();
+
+        reports.push(CidReport {
+            label,
+            cid,
+            pinned,
+            repinned,
+            gateways,
+        });
+    }
+    Ok(reports)
+}
+
+// 找到输出目录下所有的 manifest.json
+pub fn discover_manifests(output_root: &Path) -> Result<Vec<PathBuf>> {
+    if !output_root.is_dir() {
+        return Err(anyhow!("❌ 输出目录不存在: {:?}", output_root));
+    }
+    // [output] 模板可以包含子文件夹 (如 {collection}/{date}/{time})；运行目录内部和隐藏的临时目录不再展开
+    let mut manifests = Vec::new();
+    let mut walker = WalkDir::new(output_root)
+        .min_depth(1)
+        .max_depth(MAX_RUN_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.file_type().is_dir() && !entry.file_name().to_string_lossy().starts_with('.')
+        });
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path().join(MANIFEST_FILE_NAME);
+        if path.is_file() {
+            manifests.push(path);
+            walker.skip_current_dir();
+        }
+    }
+    manifests.sort();
+    Ok(manifests)
+}
+
+// 巡检一轮所有清单，返回本轮产生的告警
+pub fn run_checks(
+    backend: &dyn IpfsBackend,
+    manifest_paths: &[PathBuf],
+    options: &MonitorOptions,
+    catalog: &mut Catalog,
+) -> Result<Vec<Alert>> {
+    let mut alerts = Vec::new();
+    for path in manifest_paths {
+        let manifest = Manifest::load(path)?;
+        let path = if path.is_dir() {
+            &path.join(MANIFEST_FILE_NAME)
+        } else {
+            path
+        };
+        println!("\n🔎 正在检查: {:?}", path);
+        let reports = check_manifest(backend, &manifest, options, catalog)?;
+        catalog.save()?;
+        for report in reports {
+            let status = if report.is_healthy() { "✅" } else { "❌" };
+            println!(
+                "{} {} {} (pinned: {}, 网关: {}/{})",
+                status,
+                report.label,
+                report.cid,
+                report.pinned || report.repinned,
+                report.gateways.iter().filter(|(_, ok)| *ok).count(),
+                report.gateways.len()
+            );
+
+            let mut problems = Vec::new();
+            if !report.pinned {
+                problems.push(if report.repinned {
+                    "未被固定，已重新 pin".to_string()
+                } else {
+                    "未被固定".to_string()
+                });
+            }
+            for (gateway, ok) in &report.gateways {
+                if !ok {
+                    problems.push(format!(
+                        "无法通过网关访问: {}",
+                        gateway_url(gateway, &report.cid)
+                    ));
+                }
+            }
+            for problem in problems {
+                let alert = Alert {
+                    at: Utc::now().to_rfc3339(),
+                    manifest: path.clone(),
+                    cid: report.cid.clone(),
+                    label: report.label.clone(),
+                    problem,
+                };
+                emit_alert(path.parent().unwrap_or(Path::new(".")), &alert)?;
+                alerts.push(alert);
+            }
+        }
+    }
+    Ok(alerts)
+}
+
+fn emit_alert(output_dir: &Path, alert: &Alert) -> Result<()> {
+    eprintln!(
+        "🚨 [{}] {} {}: {}",
+        alert.at, alert.label, alert.cid, alert.problem
+    );
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output_dir.join(ALERTS_FILE_NAME))?;
+    writeln!(file, "{}", serde_json::to_string(alert)?)?;
+    Ok(())
+}
+
+// 支持标准的 5 段 cron 表达式 (分 时 日 月 周)，内部补上秒字段
+pub fn parse_schedule(expr: &str) -> Result<Schedule> {
+    let expr = expr.trim();
+    let full = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+    Schedule::from_str(&full).map_err(|e| anyhow!("❌ 无效的 cron 表达式 `{}`: {}", expr, e))
+}
+
+// ✅ 常驻模式：按 cron 计划反复巡检
+pub fn run_scheduled(
+    backend: &dyn IpfsBackend,
+    manifest_paths: &[PathBuf],
+    schedule: &Schedule,
+    options: &MonitorOptions,
+    catalog: &mut Catalog,
+) -> Result<()> {
+    loop {
+        let next = schedule
+            .upcoming(Utc)
+            .next()
+            .ok_or_else(|| anyhow!("❌ cron 表达式没有后续的执行时间"))?;
+        println!("\n⏰ 下一次巡检时间: {}", next.to_rfc3339());
+        if let Ok(wait) = (next - Utc::now()).to_std() {
+            std::thread::sleep(wait);
+        }
+
+        let alerts = run_checks(backend, manifest_paths, options, catalog)?;
+        println!("\n--- 本轮巡检完成，告警 {} 条 ---", alerts.len());
+    }
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use crate::backend::IpfsBackend;
+use crate::clone::{parse_ipfs_path, uri_ipfs_path};
+use crate::contract::{
+    AbiValue, ContractConfig, decode_string, encode_call, suffix_from_token_uri,
+};
+use crate::eth::EthClient;
+use crate::links::token_uri;
+use crate::manifest::{CollectionKind, Manifest};
+use crate::patch::metadata_path;
+
+// 默认查询的函数，ERC-1155 合约可以改为 uri(uint256)
+pub const DEFAULT_TOKEN_URI_FUNCTION: &str = "tokenURI(uint256)";
+
+// verify-onchain 的参数
+#[derive(Debug, Clone)]
+pub struct OnchainOptions {
+    pub token_ids: Vec<u64>,
+    pub function: String,
+}
+
+// ✅ 一个 token 的检查结果，没有问题时 problem 为 None
+#[derive(Debug, Clone)]
+pub struct OnchainCheck {
+    pub token_id: u64,
+    pub onchain_uri: Option<String>,
+    pub expected_uri: Option<String>,
+    pub problem: Option<String>,
+}
+
+// 链上 URI 与清单不一致时给出可能的原因
+fn mismatch_hint(onchain: &str, expected: &str) -> String {
+    if expected.strip_suffix(".json") == Some(onchain) {
+        return "合约拼接 tokenURI 时没有加 .json 后缀，而元数据文件名带 .json".to_string();
+    }
+    if onchain.strip_suffix(".json") == Some(expected) {
+        return "合约在 tokenURI 后加了 .json 后缀，而元数据文件名不带 .json".to_string();
+    }
+    let root = |uri: &str| {
+        uri_ipfs_path(uri)
+            .and_then(|path| path.split('/').next().map(str::to_string))
+            .unwrap_or_default()
+    };
+    let (onchain_root, expected_root) = (root(onchain), root(expected));
+    if !onchain_root.is_empty() && onchain_root != expected_root {
+        // Base URI 缺少结尾的 /，CID 和文件名连在一起
+        if onchain_root.starts_with(&expected_root) {
+            return "Base URI 结尾缺少 /".to_string();
+        }
+        return format!(
+            "Base URI 指向 {}，清单中是 {}，请检查是否填错或是旧的 CID",
+            onchain_root, expected_root
+        );
+    }
+    "链上 URI 与清单不一致".to_string()
+}
+
+// 比较链上 URI 读取到的内容和本地元数据，JSON 按值比较，忽略格式差异
+fn compare_content(remote: &[u8], local_path: &Path) -> Result<Option<String>> {
+    let local = fs::read(local_path)
+        .map_err(|e| anyhow!("❌ 读取本地元数据 {:?} 失败: {}", local_path, e))?;
+    let same = match (
+        serde_json::from_slice::<Value>(remote),
+        serde_json::from_slice::<Value>(&local),
+    ) {
+        (Ok(remote), Ok(local)) => remote == local,
+        (Err(_), _) => return Ok(Some("链上 URI 的内容不是 JSON".to_string())),
+        _ => remote == local,
+    };
+    Ok((!same).then(|| "链上 URI 的内容与本地元数据不同".to_string()))
+}
+
+// 调用合约的 tokenURI(id)，返回字符串
+pub fn query_token_uri(
+    client: &EthClient,
+    contract: &ContractConfig,
+    function: &str,
+    token_id: u64,
+) -> Result<String> {
+    let data = encode_call(function, &[AbiValue::Uint(token_id as u128)])?;
+    decode_string(&client.call(&contract.address, &data)?)
+}
+
+// ✅ 读取合约中 token 0 或 1 的 tokenURI，判断元数据文件名是否需要 .json 后缀；
+// 还没有 mint 任何 token 时返回 None
+pub fn detect_json_suffix(contract: &ContractConfig) -> Result<Option<bool>> {
+    if contract.address.is_empty() || contract.rpc_url.is_empty() {
+        return Err(anyhow!(
+            "❌ 自动判断 .json 后缀需要在 [contract] 中填写 address 和 rpc_url"
+        ));
+    }
+    let client = EthClient::new(&contract.rpc_url)?;
+    for token_id in [0, 1] {
+        let Ok(uri) = query_token_uri(&client, contract, DEFAULT_TOKEN_URI_FUNCTION, token_id)
+        else {
+            continue;
+        };
+        println!("🔍 合约 tokenURI({}) = {}", token_id, uri);
+        let suffix = suffix_from_token_uri(&uri, token_id);
+        if suffix.is_none() {
+            eprintln!(
+                "⚠️  tokenURI 不是 \"Base URI + token ID\" 的形式，合约可能不会按元数据文件名读取，请确认合约的拼接方式"
+            );
+        }
+        return Ok(Some(suffix.unwrap_or(false)));
+    }
+    Ok(None)
+}
+
+fn check_token(
+    client: &EthClient,
+    backend: &dyn IpfsBackend,
+    contract: &ContractConfig,
+    manifest: &Manifest,
+    dir: &Path,
+    options: &OnchainOptions,
+    token_id: u64,
+) -> Result<OnchainCheck> {
+    let mut check = OnchainCheck {
+        token_id,
+        onchain_uri: None,
+        expected_uri: None,
+        problem: None,
+    };
+    let onchain = match query_token_uri(client, contract, &options.function, token_id) {
+        Ok(uri) => uri,
+        Err(e) => {
+            check.problem = Some(format!(
+                "调用 {} 失败 (token 可能还没有 mint): {}",
+                options.function, e
+            ));
+            return Ok(check);
+        }
+    };
+    check.onchain_uri = Some(onchain.clone());
+
+    let Some(token) = manifest
+        .tokens
+        .iter()
+        .find(|t| t.token_id == Some(token_id))
+    else {
+        check.problem = Some("清单中没有这个 token".to_string());
+        return Ok(check);
+    };
+    let expected = token_uri(manifest, token);
+    check.expected_uri = Some(expected.clone());
+
+    // 网关地址和 ipfs:// 指向同一路径时视为一致
+    let same_path =
+        uri_ipfs_path(&onchain).is_some_and(|path| parse_ipfs_path(&expected).ok() == Some(path));
+    if !same_path {
+        check.problem = Some(mismatch_hint(&onchain, &expected));
+        return Ok(check);
+    }
+    let remote = match backend.cat(&parse_ipfs_path(&expected)?) {
+        Ok(remote) => remote,
+        Err(e) => {
+            check.problem = Some(format!("无法通过 IPFS 读取: {}", e));
+            return Ok(check);
+        }
+    };
+    check.problem = compare_content(&remote, &metadata_path(dir, manifest.kind, token))?;
+    Ok(check)
+}
+
+// ✅ 读取合约中每个 token 的 tokenURI，确认它指向本次上传的元数据
+pub fn verify_onchain(
+    backend: &dyn IpfsBackend,
+    contract: &ContractConfig,
+    manifest_path: &Path,
+    options: &OnchainOptions,
+) -> Result<Vec<OnchainCheck>> {
+    if contract.address.is_empty() || contract.rpc_url.is_empty() {
+        return Err(anyhow!(
+            "❌ 配置文件的 [contract] 段需要填写 address 和 rpc_url"
+        ));
+    }
+    let manifest = Manifest::load(manifest_path)?;
+    if manifest.kind == CollectionKind::Single {
+        println!("⚠️  单件清单的 URI 需要 mint 时写入，只检查清单中带 token ID 的条目");
+    }
+    let dir = if manifest_path.is_dir() {
+        manifest_path
+    } else {
+        manifest_path.parent().unwrap_or(Path::new("."))
+    };
+    let client = EthClient::new(&contract.rpc_url)?;
+    println!(
+        "\n--- 正在检查合约 {} 的 {} 个 token ---",
+        contract.address,
+        options.token_ids.len()
+    );
+    options
+        .token_ids
+        .iter()
+        .map(|id| check_token(&client, backend, contract, &manifest, dir, options, *id))
+        .collect()
+}
+
+// 最多列出的问题数
+const MAX_LISTED_PROBLEMS: usize = 20;
+
+pub fn print_onchain_report(checks: &[OnchainCheck]) {
+    let problems: Vec<&OnchainCheck> = checks.iter().filter(|c| c.problem.is_some()).collect();
+    for check in problems.iter().take(MAX_LISTED_PROBLEMS) {
+        println!(
+            "❌ #{}: {}",
+            check.token_id,
+            check.problem.as_deref().unwrap_or_default()
+        );
+        if let Some(uri) = &check.onchain_uri {
+            println!("     链上: {}", uri);
+        }
+        if let Some(uri) = check
+            .expected_uri
+            .as_ref()
+            .filter(|u| Some(*u) != check.onchain_uri.as_ref())
+        {
+            println!("     清单: {}", uri);
+        }
+    }
+    if problems.len() > MAX_LISTED_PROBLEMS {
+        println!(
+            "   ... 另有 {} 个问题",
+            problems.len() - MAX_LISTED_PROBLEMS
+        );
+    }
+    println!(
+        "\n🔎 链上检查: {} 个 token，{} 个一致，{} 个有问题",
+        checks.len(),
+        checks.len() - problems.len(),
+        problems.len()
+    );
+}
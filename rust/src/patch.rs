@@ -0,0 +1,222 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+
+use crate::NftMetadata;
+use crate::arweave::clear_metadata_mirrors;
+use crate::backend::IpfsBackend;
+use crate::checksums::refresh_checksums;
+use crate::filecoin::drop_stale_archives;
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::metadata::{JsonStyle, render_description};
+use crate::urls::{UrlConfig, record_urls};
+use crate::workflow::{publish_wrapped_metadata, record_served_by, upload_json_str_to_ipfs};
+
+// patch-descriptions 的可选项
+#[derive(Debug, Clone, Default)]
+pub struct PatchOptions {
+    // 新的描述模板，见 render_description
+    pub template: String,
+    // 只显示会发生的变化，不写文件也不上传
+    pub dry_run: bool,
+    // 改写后的元数据文件使用的 JSON 写法，与生成时一致才不会改变未修改部分的字节
+    pub json_style: JsonStyle,
+    // 更新后的清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct DescriptionChange {
+    pub key: String,
+    pub before: String,
+    pub after: String,
+}
+
+// ✅ 一次描述修改的结果
+#[derive(Debug, Clone)]
+pub struct PatchReport {
+    pub changes: Vec<DescriptionChange>,
+    pub unchanged: usize,
+    pub old_token_uri: String,
+    // dry run 或没有变化时与 old_token_uri 相同
+    pub new_token_uri: String,
+}
+
+pub fn metadata_path(dir: &Path, kind: CollectionKind, token: &TokenEntry) -> PathBuf {
+    match kind {
+        CollectionKind::Single => dir.join(&token.metadata_file),
+        _ => dir.join("metadata").join(&token.metadata_file),
+    }
+}
+
+// 元数据根 CID 对应的本地内容：单件为元数据文件 (包在目录中上传时为该目录)，其它为 metadata/ 文件夹
+pub fn metadata_root_path(dir: &Path, kind: CollectionKind, token: &TokenEntry) -> PathBuf {
+    match (kind, token.metadata_file.split_once('/')) {
+        (CollectionKind::Single, Some((folder, _))) => dir.join(folder),
+        (CollectionKind::Single, None) => dir.join(&token.metadata_file),
+        _ => dir.join("metadata"),
+    }
+}
+
+pub fn token_key(token: &TokenEntry) -> String {
+    match token.token_id {
+        Some(id) => format!("#{}", id),
+        None => token.name.clone(),
+    }
+}
+
+// ✅ 写入改写后的本地元数据文件并重新上传 (图片不动)，更新并保存清单
+// patched 为 (token 在清单中的下标, 元数据文件, 新的元数据)
+pub fn republish_metadata(
+    backend: &dyn IpfsBackend,
+    manifest: &mut Manifest,
+    dir: &Path,
+    patched: &[(usize, PathBuf, NftMetadata)],
+    json_style: JsonStyle,
+    urls: &UrlConfig,
+) -> Result<()> {
+    // 单独上传的元数据 (单件、watch) 写入后逐个重新上传同一份字节；文件夹形式的再整体上传一次
+    for (index, path, metadata) in patched {
+        if manifest.tokens[*index].metadata_cid.is_some() {
+            manifest.tokens[*index].metadata_cid = Some(upload_json_str_to_ipfs(
+                backend, metadata, json_style, path,
+            )?);
+        } else {
+            fs::write(path, json_style.file_json(metadata)?)?;
+        }
+    }
+    println!("✅ 已改写 {} 个本地元数据文件", patched.len());
+    match manifest.kind {
+        CollectionKind::Single => {
+            let cid = manifest.tokens[0]
+                .metadata_cid
+                .clone()
+                .ok_or_else(|| anyhow!("❌ 单件清单中缺少元数据 CID"))?;
+            // 包在目录中上传的元数据重新上传整个目录
+            match publish_wrapped_metadata(backend, dir, &manifest.tokens[0].metadata_file)? {
+                Some((root, uri)) => {
+                    manifest.metadata_cid = root;
+                    manifest.token_uri = uri;
+                }
+                None => {
+                    manifest.token_uri = format!("ipfs://{}", cid);
+                    manifest.metadata_cid = cid;
+                }
+            }
+        }
+        CollectionKind::Batch | CollectionKind::Watch => {
+            manifest.metadata_cid = backend.add_path(&dir.join("metadata"))?;
+            manifest.token_uri = format!("ipfs://{}/", manifest.metadata_cid);
+        }
+    }
+    if manifest.ipld.take().is_some() {
+        println!("⚠️  原来的元数据是 IPLD 节点，这次改为以 JSON 文件重新发布");
+    }
+    for role in drop_stale_archives(manifest) {
+        println!(
+            "⚠️  {} 的 CID 已变化，原来的 Filecoin 归档不再对应，可以重新运行 `filecoin submit`",
+            role
+        );
+    }
+    if clear_metadata_mirrors(manifest) > 0 {
+        println!("⚠️  元数据已变化，原来的 Arweave 元数据镜像不再对应，可以重新运行 `arweave`");
+    }
+    record_served_by(manifest, backend);
+    record_urls(manifest, urls);
+    manifest.save(dir)?;
+    for path in refresh_checksums(dir)? {
+        println!(
+            "🔐 已更新校验文件: {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+    }
+    Ok(())
+}
+
+// ✅ 只修改已有运行的元数据描述：改写本地元数据文件，重新上传元数据 (图片不动)，更新清单
+pub fn patch_descriptions(
+    backend: &dyn IpfsBackend,
+    manifest_path: &Path,
+    options: &PatchOptions,
+) -> Result<PatchReport> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let old_token_uri = manifest.token_uri.clone();
+
+    // 先全部读出并计算新描述，任何一个文件有问题都不做修改
+    let mut patched: Vec<(usize, PathBuf, NftMetadata)> = Vec::new();
+    let mut changes = Vec::new();
+    for (index, token) in manifest.tokens.iter().enumerate() {
+        let path = metadata_path(dir, manifest.kind, token);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("❌ 读取元数据 {:?} 失败: {}", path, e))?;
+        // 自定义字段和其它标准 (如 Metaplex) 的字段在 extra 中原样保留
+        let mut metadata: NftMetadata = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("❌ 元数据 {:?} 格式错误: {}", path, e))?;
+        let description = render_description(&options.template, &metadata, token.token_id);
+        if description == metadata.description {
+            continue;
+        }
+        changes.push(DescriptionChange {
+            key: token_key(token),
+            before: std::mem::replace(&mut metadata.description, description.clone()),
+            after: description,
+        });
+        patched.push((index, path, metadata));
+    }
+    let unchanged = manifest.tokens.len() - changes.len();
+    if options.dry_run || changes.is_empty() {
+        return Ok(PatchReport {
+            changes,
+            unchanged,
+            new_token_uri: old_token_uri.clone(),
+            old_token_uri,
+        });
+    }
+
+    republish_metadata(
+        backend,
+        &mut manifest,
+        dir,
+        &patched,
+        options.json_style,
+        &options.urls,
+    )?;
+
+    Ok(PatchReport {
+        changes,
+        unchanged,
+        old_token_uri,
+        new_token_uri: manifest.token_uri,
+    })
+}
+
+// 最多展示的描述变化条数
+const MAX_LISTED_CHANGES: usize = 5;
+
+pub fn print_patch_report(report: &PatchReport, dry_run: bool) {
+    println!(
+        "\n📝 {} 个 token 的描述{}，{} 个不变",
+        report.changes.len(),
+        if dry_run { "将会修改" } else { "已修改" },
+        report.unchanged
+    );
+    for change in report.changes.iter().take(MAX_LISTED_CHANGES) {
+        println!("   {}:", change.key);
+        println!("     - {}", change.before);
+        println!("     + {}", change.after);
+    }
+    if report.changes.len() > MAX_LISTED_CHANGES {
+        println!(
+            "   ... 另有 {} 个 token",
+            report.changes.len() - MAX_LISTED_CHANGES
+        );
+    }
+    if dry_run || report.changes.is_empty() {
+        return;
+    }
+    println!("\n🔗 旧 URI: {}", report.old_token_uri);
+    println!("🔗 新 URI: {}", report.new_token_uri);
+    println!("下一步，请在合约中把 URI 更新为新的地址 (图片 CID 没有变化)。");
+}
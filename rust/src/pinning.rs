@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::{CollectionKind, Manifest};
+
+// ✅ IPFS Pinning Service API 的数据结构
+// 规范: https://ipfs.github.io/pinning-services-api-spec/
+// Pinata、Filebase 等服务都兼容这套接口
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinRequest {
+    pub cid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub origins: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+}
+
+impl PinRequest {
+    pub fn new(cid: impl Into<String>, name: Option<String>) -> Self {
+        Self {
+            cid: cid.into(),
+            name,
+            origins: Vec::new(),
+            meta: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PinState {
+    Queued,
+    Pinning,
+    Pinned,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinStatus {
+    pub requestid: String,
+    pub status: PinState,
+    pub created: String,
+    pub pin: PinRequest,
+    #[serde(default)]
+    pub delegates: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<BTreeMap<String, String>>,
+}
+
+// GET /pins 的返回
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinResults {
+    pub count: u64,
+    #[serde(default)]
+    pub results: Vec<PinStatus>,
+}
+
+// 由服务的根地址得到 `/pins` 接口地址
+pub fn pins_endpoint(service_url: &str) -> String {
+    format!("{}/pins", service_url.trim_end_matches('/'))
+}
+
+// ✅ config.toml 中的一个 [[pinning_services]] 条目
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PinningServiceConfig {
+    pub name: String,
+    // 服务根地址，例如 https://api.pinata.cloud/psa
+    pub endpoint: String,
+    // 可以写成 "${PINATA_JWT}"；留空时从系统钥匙串按 name 读取
+    #[serde(default)]
+    pub access_token: String,
+    // 附加到每个 pin 的 meta 标签，例如 { project = "my-nft" }
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    // Pinata 专有的 Group 和专属网关，其它服务不需要
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinata: Option<PinataOptions>,
+}
+
+pub const PINATA_API: &str = "https://api.pinata.cloud";
+
+// ✅ [pinning_services.pinata]：pin 完成后加入 Group，并把专属网关地址写入清单
+// name 和 tags 通过 Pinning Service API 写入 Pinata 的 name 和 keyvalues
+//
+// [pinning_services.pinata]
+// group_id = "2b6e4c5a-..."
+// gateway = "https://my-drop.mypinata.cloud"
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PinataOptions {
+    // Pinata 控制台 Groups 页面中的 ID，留空时不分组
+    pub group_id: String,
+    // 专属网关，留空时清单中不记录网关地址
+    pub gateway: String,
+    // Pinata 原生 API 的地址，留空时为 https://api.pinata.cloud
+    pub api: String,
+}
+
+impl PinataOptions {
+    pub fn api(&self) -> &str {
+        match self.api.as_str() {
+            "" => PINATA_API,
+            api => api.trim_end_matches('/'),
+        }
+    }
+
+    // ipfs://<cid>/... 在专属网关上的地址
+    pub fn gateway_url(&self, uri: &str) -> Option<String> {
+        if self.gateway.is_empty() {
+            return None;
+        }
+        let path = uri.strip_prefix("ipfs://").unwrap_or(uri);
+        Some(format!(
+            "{}/ipfs/{}",
+            self.gateway.trim_end_matches('/'),
+            path
+        ))
+    }
+}
+
+// ✅ 清单中记录的一次远程 pin (按服务名)：集合名、数量，以及 Pinata 的 Group 和专属网关地址
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RemotePinRecord {
+    pub collection: String,
+    pub pinned_at: String,
+    pub pinned: usize,
+    pub failed: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    // token_uri 在专属网关上的地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_url: Option<String>,
+}
+
+// 默认的集合名：输出目录名 (如 collection_20250101_120000)
+pub fn collection_name(manifest: &Manifest) -> String {
+    manifest
+        .output_dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("collection")
+        .to_string()
+}
+
+// ✅ 为清单中的每个 CID 生成带名称和标签的 pin 请求
+// 名称形如 `<集合>/metadata-root`、`<集合>/<token ID>`，服务控制台中可以按集合查找
+pub fn manifest_pin_requests(
+    manifest: &Manifest,
+    collection: &str,
+    tags: &BTreeMap<String, String>,
+) -> Vec<PinRequest> {
+    let (image_role, metadata_role) = match manifest.kind {
+        CollectionKind::Single => ("image", "metadata"),
+        _ => ("images-root", "metadata-root"),
+    };
+    let mut entries = vec![
+        (manifest.image_cid.clone(), image_role.to_string(), None),
+        (
+            manifest.metadata_cid.clone(),
+            metadata_role.to_string(),
+            None,
+        ),
+    ];
+    if let Some(cid) = &manifest.posters_cid {
+        let role = match manifest.kind {
+            CollectionKind::Single => "poster",
+            _ => "posters-root",
+        };
+        entries.push((cid.clone(), role.to_string(), None));
+    }
+    if let Some(cid) = &manifest.previews_cid {
+        let role = match manifest.kind {
+            CollectionKind::Single => "preview",
+            _ => "previews-root",
+        };
+        entries.push((cid.clone(), role.to_string(), None));
+    }
+    if let Some(cid) = &manifest.watermarked_cid {
+        let role = match manifest.kind {
+            CollectionKind::Single => "watermarked",
+            _ => "watermarked-root",
+        };
+        entries.push((cid.clone(), role.to_string(), None));
+    }
+    if let Some(cid) = &manifest.localization_cid {
+        entries.push((cid.clone(), "localization-root".to_string(), None));
+    }
+    for token in &manifest.tokens {
+        if let (Some(cid), Some(id)) = (&token.metadata_cid, token.token_id) {
+            entries.push((cid.clone(), id.to_string(), Some(id)));
+        }
+    }
+
+    let mut requests: Vec<PinRequest> = Vec::new();
+    for (cid, label, token_id) in entries {
+        // 同一个 CID 只 pin 一次 (单件的元数据 CID 同时也是 token 的 CID)
+        if cid.is_empty() || requests.iter().any(|r| r.cid == cid) {
+            continue;
+        }
+        let mut request = PinRequest::new(&cid, Some(format!("{}/{}", collection, label)));
+        request
+            .meta
+            .insert("collection".to_string(), collection.to_string());
+        request
+            .meta
+            .insert("created_at".to_string(), manifest.created_at.clone());
+        let role = if token_id.is_some() {
+            "token"
+        } else {
+            label.as_str()
+        };
+        request.meta.insert("role".to_string(), role.to_string());
+        if let Some(id) = token_id {
+            request.meta.insert("token_id".to_string(), id.to_string());
+        }
+        request
+            .meta
+            .extend(tags.iter().map(|(k, v)| (k.clone(), v.clone())));
+        requests.push(request);
+    }
+    requests
+}
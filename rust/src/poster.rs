@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+
+use crate::demo::{hue_to_rgb, write_png};
+use crate::standards::mime_type_from_name;
+
+// 封面图放在素材旁边的这个文件夹中，文件名与素材相同 (扩展名不同)，如 posters/1.png 对应 1.mp4
+pub const POSTERS_DIR: &str = "posters";
+
+// 生成的占位封面图的边长 (像素)
+const POSTER_SIZE: u32 = 512;
+
+// 在素材旁边的 posters/ 中查找同名的封面图
+pub fn find_poster(asset: &Path) -> Option<PathBuf> {
+    let stem = asset.file_stem()?;
+    let dir = asset.parent()?.join(POSTERS_DIR);
+    let mut candidates: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.file_stem() == Some(stem)
+                && path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|name| mime_type_from_name(name).starts_with("image/"))
+        })
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+// 按素材类型选择颜色：视频偏蓝、音频偏紫、3D 模型偏绿
+fn poster_hue(mime: &str) -> f64 {
+    match mime.split('/').next().unwrap_or_default() {
+        "video" => 210.0,
+        "audio" => 280.0,
+        "model" => 140.0,
+        _ => 30.0,
+    }
+}
+
+// 纵向渐变的占位图，同一类型的素材生成的封面相同
+fn draw_poster(mime: &str) -> Vec<u8> {
+    let hue = poster_hue(mime);
+    let mut pixels = Vec::with_capacity((POSTER_SIZE * POSTER_SIZE * 3) as usize);
+    for y in 0..POSTER_SIZE {
+        let color = hue_to_rgb(hue, 0.9 - 0.5 * y as f64 / POSTER_SIZE as f64);
+        for _ in 0..POSTER_SIZE {
+            pixels.extend_from_slice(&color);
+        }
+    }
+    pixels
+}
+
+// ✅ 把素材的封面图放到 dir 中：优先使用 posters/ 中提供的图片，没有时生成占位图
+pub fn prepare_poster(asset: &Path, mime: &str, dir: &Path) -> Result<PathBuf> {
+    let stem = asset
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的文件名: {:?}", asset))?;
+    fs::create_dir_all(dir)?;
+    match find_poster(asset) {
+        Some(poster) => {
+            let target = dir.join(poster.file_name().unwrap_or_default());
+            fs::copy(&poster, &target)?;
+            Ok(target)
+        }
+        None => {
+            let target = dir.join(format!("{}.png", stem));
+            write_png(&target, POSTER_SIZE, &draw_poster(mime))?;
+            println!(
+                "🖼️  {:?} 是 {}，没有找到 {}/{}.*，已生成占位封面图",
+                asset.file_name().unwrap_or_default(),
+                mime,
+                POSTERS_DIR,
+                stem
+            );
+            Ok(target)
+        }
+    }
+}
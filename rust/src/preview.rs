@@ -0,0 +1,147 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+
+// 预览图保存在输出目录下的这个文件夹中，整体上传一次
+pub const PREVIEWS_DIR: &str = "previews";
+
+// ✅ 预览图的编码格式：WebP (无损，默认) 或 JPEG (有损，体积更小)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    #[default]
+    Webp,
+    Jpeg,
+}
+
+impl PreviewFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Jpeg => "jpeg",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Jpeg => "jpg",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Webp => "image/webp",
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+impl fmt::Display for PreviewFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PreviewFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "webp" => Ok(Self::Webp),
+            "jpeg" | "jpg" => Ok(Self::Jpeg),
+            other => Err(format!(
+                "❌ 不支持的预览图格式: {} (可选: webp, jpeg)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [preview] 段：为大图生成缩小的预览图，元数据的 image 指向预览图
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PreviewConfig {
+    pub enabled: bool,
+    // 预览图的最大宽高 (像素)，按比例缩放；原图不超过时直接使用原图
+    pub max_width: u32,
+    pub max_height: u32,
+    pub format: PreviewFormat,
+    // JPEG 质量 (1-100)
+    pub quality: u8,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_width: 1024,
+            max_height: 1024,
+            format: PreviewFormat::default(),
+            quality: 85,
+        }
+    }
+}
+
+impl PreviewConfig {
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.max_width == 0 || self.max_height == 0 {
+            problems.push("max_width 和 max_height 必须大于 0".to_string());
+        }
+        if !(1..=100).contains(&self.quality) {
+            problems.push(format!("quality 应在 1-100 之间: {}", self.quality));
+        }
+        problems
+    }
+}
+
+// 可以生成预览图的类型；GIF 可能是动图，缩放后会丢失动画，保留原图
+pub fn supports_preview(mime: &str) -> bool {
+    matches!(mime, "image/png" | "image/jpeg" | "image/webp")
+}
+
+// ✅ 原图超过最大尺寸时生成预览图 dir/<stem>.<ext>，返回路径；原图已经足够小时返回 None
+pub fn make_preview(source: &Path, dir: &Path, config: &PreviewConfig) -> Result<Option<PathBuf>> {
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的文件名: {:?}", source))?;
+    let image = ImageReader::open(source)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| anyhow!("❌ 解码图片 {:?} 失败: {}", source, e))?;
+    if image.width() <= config.max_width && image.height() <= config.max_height {
+        return Ok(None);
+    }
+    let resized = image.resize(config.max_width, config.max_height, FilterType::Lanczos3);
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.{}", stem, config.format.extension()));
+    let written = match config.format {
+        PreviewFormat::Webp => resized
+            .to_rgba8()
+            .save_with_format(&path, ImageFormat::WebP),
+        PreviewFormat::Jpeg => {
+            let writer = BufWriter::new(File::create(&path)?);
+            JpegEncoder::new_with_quality(writer, config.quality).encode_image(&resized.to_rgb8())
+        }
+    };
+    written.map_err(|e| anyhow!("❌ 写入预览图 {:?} 失败: {}", path, e))?;
+    println!(
+        "🔍 {}: {}x{} → {}x{} 预览图",
+        source.file_name().unwrap_or_default().to_string_lossy(),
+        image.width(),
+        image.height(),
+        resized.width(),
+        resized.height()
+    );
+    Ok(Some(path))
+}
@@ -0,0 +1,140 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::IpfsBackend;
+use crate::manifest::Manifest;
+
+// ✅ config.toml 中的 [provide] 段：上传后固定根 CID 并确认已向 DHT 宣告
+//
+// [provide]
+// enabled = true
+// num_providers = 5
+// required = true
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ProvideConfig {
+    // 上传完成后自动检查 (也可以用 --provide 开启)
+    pub enabled: bool,
+    // 宣告 DAG 中的所有块；关闭时只宣告根，其余块等节点的定期 reprovide
+    pub recursive: bool,
+    // 查找提供者时最多找多少个，0 表示只宣告不查找
+    pub num_providers: usize,
+    // 宣告失败或找不到提供者时让命令失败 (默认只打印警告)
+    pub required: bool,
+}
+
+impl Default for ProvideConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recursive: true,
+            num_providers: 3,
+            required: false,
+        }
+    }
+}
+
+// 一个根 CID 的检查结果
+#[derive(Debug, Clone)]
+pub struct ProvideReport {
+    pub label: String,
+    pub cid: String,
+    // 宣告失败时的错误
+    pub error: Option<String>,
+    // 找到的提供者；没有查找或查找失败时为 None
+    pub providers: Option<Vec<String>>,
+}
+
+impl ProvideReport {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.providers.as_ref().is_none_or(|p| !p.is_empty())
+    }
+}
+
+// 清单中需要能被找到的根 CID
+pub fn manifest_roots(manifest: &Manifest) -> Vec<(&'static str, String)> {
+    let mut roots = vec![
+        ("图片", manifest.image_cid.clone()),
+        ("元数据", manifest.metadata_cid.clone()),
+    ];
+    let optional = [
+        ("封面图", &manifest.posters_cid),
+        ("预览图", &manifest.previews_cid),
+        ("水印图片", &manifest.watermarked_cid),
+        ("多语言", &manifest.localization_cid),
+    ];
+    for (label, cid) in optional {
+        if let Some(cid) = cid {
+            roots.push((label, cid.clone()));
+        }
+    }
+    roots.retain(|(_, cid)| !cid.is_empty());
+    roots
+}
+
+// ✅ 递归固定每个根 CID，向 DHT 宣告，再查找提供者；"已上传" 不等于 "能被取回"
+pub fn provide_manifest(
+    backend: &dyn IpfsBackend,
+    manifest: &Manifest,
+    config: &ProvideConfig,
+) -> Result<Vec<ProvideReport>> {
+    println!("\n--- 正在向 DHT 宣告内容 ---");
+    let mut reports = Vec::new();
+    for (label, cid) in manifest_roots(manifest) {
+        backend.pin(&cid)?;
+        let error = backend
+            .provide(&cid, config.recursive)
+            .err()
+            .map(|e| format!("{:#}", e));
+        let providers = match (&error, config.num_providers) {
+            (None, limit) if limit > 0 => match backend.find_providers(&cid, limit) {
+                Ok(providers) => Some(providers),
+                Err(e) => {
+                    eprintln!("⚠️  查找 {} 的提供者失败: {:#}", cid, e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        let report = ProvideReport {
+            label: label.to_string(),
+            cid,
+            error,
+            providers,
+        };
+        match (&report.error, &report.providers) {
+            (Some(error), _) => eprintln!("❌ {} {} 宣告失败: {}", report.label, report.cid, error),
+            (None, Some(providers)) if providers.is_empty() => eprintln!(
+                "⚠️  {} {} 已宣告，但 DHT 中还找不到提供者",
+                report.label, report.cid
+            ),
+            (None, Some(providers)) => println!(
+                "📣 {} {} 已宣告，找到 {} 个提供者",
+                report.label,
+                report.cid,
+                providers.len()
+            ),
+            (None, None) => println!("📣 {} {} 已宣告", report.label, report.cid),
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+// 按 required 决定检查没有通过时是否返回错误
+pub fn check_reports(reports: &[ProvideReport], config: &ProvideConfig) -> Result<()> {
+    let failed = reports.iter().filter(|r| !r.is_ok()).count();
+    if failed == 0 {
+        println!("✅ 所有根 CID 都已固定并向 DHT 宣告");
+        return Ok(());
+    }
+    let message = format!(
+        "{} 个根 CID 没有确认可被找到，其它节点和网关可能取不回这些内容 (节点是否以 --offline 运行、是否有可连接的 peer？)",
+        failed
+    );
+    if config.required {
+        return Err(anyhow!("❌ {}", message));
+    }
+    eprintln!("⚠️  {}", message);
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::exit_code::ExitCode;
+use crate::manifest::Manifest;
+use crate::pinning::{
+    PinRequest, PinResults, PinStatus, PinataOptions, PinningServiceConfig, RemotePinRecord,
+    manifest_pin_requests, pins_endpoint,
+};
+
+// Pinning Service API 的错误结构
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    reason: String,
+    #[serde(default)]
+    details: Option<String>,
+}
+
+// 一次 pin 的结果
+#[derive(Debug, Clone)]
+pub enum PinOutcome {
+    Created(PinStatus),
+    // 已有同一 CID 的 pin，名称或标签不同，已替换
+    Updated(PinStatus),
+    // 已有名称和标签都相同的 pin
+    Unchanged(PinStatus),
+}
+
+// ✅ 通过 Pinning Service API 在远程服务上 pin
+pub struct PinningClient<'a> {
+    service: &'a PinningServiceConfig,
+    client: Client,
+}
+
+impl<'a> PinningClient<'a> {
+    pub fn new(service: &'a PinningServiceConfig) -> Result<Self> {
+        let client = Client::builder().timeout(Duration::from_secs(60)).build()?;
+        Ok(Self { service, client })
+    }
+
+    fn send<T: for<'de> Deserialize<'de>>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request.bearer_auth(&self.service.access_token).send()?;
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            let message = match serde_json::from_str::<ErrorBody>(&body) {
+                Ok(ErrorBody { error }) => match error.details {
+                    Some(details) => format!("{}: {}", error.reason, details),
+                    None => error.reason,
+                },
+                Err(_) => body,
+            };
+            return Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+                "❌ Pinning 服务 {} 请求失败 ({}): {}",
+                self.service.name,
+                status,
+                message
+            )));
+        }
+        serde_json::from_str(&body)
+            .map_err(|e| anyhow!("❌ Pinning 服务 {} 返回无法解析: {}", self.service.name, e))
+    }
+
+    // 查找该 CID 已有的 pin (包括排队中和失败的)
+    pub fn find(&self, cid: &str) -> Result<Option<PinStatus>> {
+        let request = self
+            .client
+            .get(pins_endpoint(&self.service.endpoint))
+            .query(&[("cid", cid), ("status", "queued,pinning,pinned,failed")]);
+        let results: PinResults = self.send(request)?;
+        Ok(results.results.into_iter().next())
+    }
+
+    // 新建 pin；同一 CID 已经存在时按需替换成新的名称和标签，不会重复 pin
+    pub fn pin(&self, request: &PinRequest) -> Result<PinOutcome> {
+        let url = pins_endpoint(&self.service.endpoint);
+        match self.find(&request.cid)? {
+            Some(existing)
+                if existing.pin.name == request.name && existing.pin.meta == request.meta =>
+            {
+                Ok(PinOutcome::Unchanged(existing))
+            }
+            Some(existing) => {
+                let url = format!("{}/{}", url, existing.requestid);
+                let status = self.send(self.client.post(url).json(request))?;
+                Ok(PinOutcome::Updated(status))
+            }
+            None => {
+                let status = self.send(self.client.post(url).json(request))?;
+                Ok(PinOutcome::Created(status))
+            }
+        }
+    }
+
+    // 服务上已完成的 pin 数量
+    pub fn count_pinned(&self) -> Result<u64> {
+        let request = self
+            .client
+            .get(pins_endpoint(&self.service.endpoint))
+            .query(&[("status", "pinned"), ("limit", "1")]);
+        let results: PinResults = self.send(request)?;
+        Ok(results.count)
+    }
+
+    // Pinata 账户的 pin 总大小 (字节，Pinata 原生 API)
+    pub fn pinata_pinned_bytes(&self, pinata: &PinataOptions) -> Result<u64> {
+        let url = format!("{}/data/userPinnedDataTotal", pinata.api());
+        let total: Value = self.send(self.client.get(url))?;
+        // 大小可能是数字，也可能是数字字符串
+        match &total["pin_size_total"] {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow!("❌ Pinata 返回中没有 pin_size_total: {}", total))
+    }
+
+    // 删除该 CID 的 pin，服务上没有时返回 false
+    pub fn remove(&self, cid: &str) -> Result<bool> {
+        let Some(existing) = self.find(cid)? else {
+            return Ok(false);
+        };
+        let url = format!(
+            "{}/{}",
+            pins_endpoint(&self.service.endpoint),
+            existing.requestid
+        );
+        let response = self
+            .client
+            .delete(url)
+            .bearer_auth(&self.service.access_token)
+            .send()?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+                "❌ Pinning 服务 {} 删除 {} 失败 ({}): {}",
+                self.service.name,
+                cid,
+                status,
+                response.text().unwrap_or_default()
+            )));
+        }
+        Ok(true)
+    }
+
+    // 把 CID 加入 Pinata 的 Group (Pinata 原生 API，不属于 Pinning Service API)
+    pub fn add_to_pinata_group(&self, pinata: &PinataOptions, cids: &[String]) -> Result<()> {
+        let url = format!("{}/groups/{}/cids", pinata.api(), pinata.group_id);
+        let response = self
+            .client
+            .put(url)
+            .bearer_auth(&self.service.access_token)
+            .json(&json!({ "cids": cids }))
+            .send()?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+                "❌ 加入 Pinata Group {} 失败 ({}): {}",
+                pinata.group_id,
+                status,
+                response.text().unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+}
+
+// 按名称选出要使用的服务，names 为空时使用全部
+pub fn select_services<'a>(
+    services: &'a [PinningServiceConfig],
+    names: &[String],
+) -> Result<Vec<&'a PinningServiceConfig>> {
+    for name in names {
+        if !services.iter().any(|s| &s.name == name) {
+            return Err(anyhow!("❌ 配置文件中没有名为 {} 的 Pinning 服务", name));
+        }
+    }
+    let selected: Vec<_> = services
+        .iter()
+        .filter(|s| names.is_empty() || names.contains(&s.name))
+        .collect();
+    if selected.is_empty() {
+        return Err(anyhow!(
+            "❌ 配置文件中没有 Pinning 服务 ([[pinning_services]])"
+        ));
+    }
+    Ok(selected)
+}
+
+// ✅ 把清单中的 CID pin 到一个服务，逐个打印结果，返回写入清单的记录
+// Pinata 配置了 group_id 时把成功的 CID 加入 Group，配置了 gateway 时记录专属网关地址
+pub fn pin_manifest(
+    service: &PinningServiceConfig,
+    manifest: &Manifest,
+    collection: &str,
+    tags: &[(String, String)],
+) -> Result<RemotePinRecord> {
+    println!(
+        "\n--- 正在 pin 到 {} ({}) ---",
+        service.name, service.endpoint
+    );
+    let mut service_tags = service.tags.clone();
+    service_tags.extend(tags.iter().cloned());
+    let client = PinningClient::new(service)?;
+    let mut record = RemotePinRecord {
+        collection: collection.to_string(),
+        pinned_at: Utc::now().to_rfc3339(),
+        ..RemotePinRecord::default()
+    };
+    let mut pinned = Vec::new();
+    for request in manifest_pin_requests(manifest, collection, &service_tags) {
+        let name = request.name.as_deref().unwrap_or_default();
+        let outcome = client.pin(&request);
+        if outcome.is_ok() {
+            pinned.push(request.cid.clone());
+        }
+        match outcome {
+            Ok(PinOutcome::Created(status)) => {
+                println!("📌 {} -> {} ({:?})", name, request.cid, status.status)
+            }
+            Ok(PinOutcome::Updated(status)) => {
+                println!(
+                    "🏷️  {} -> {} 已更新名称和标签 ({:?})",
+                    name, request.cid, status.status
+                )
+            }
+            Ok(PinOutcome::Unchanged(status)) => {
+                println!(
+                    "✅ {} -> {} 已存在 ({:?})",
+                    name, request.cid, status.status
+                )
+            }
+            Err(e) => {
+                record.failed += 1;
+                eprintln!("❌ {} -> {} 失败: {:#}", name, request.cid, e);
+            }
+        }
+    }
+    record.pinned = pinned.len();
+    if let Some(pinata) = &service.pinata {
+        if !pinata.group_id.is_empty() && !pinned.is_empty() {
+            match client.add_to_pinata_group(pinata, &pinned) {
+                Ok(()) => {
+                    println!("🗂️  已加入 Pinata Group {}", pinata.group_id);
+                    record.group_id = Some(pinata.group_id.clone());
+                }
+                Err(e) => {
+                    record.failed += 1;
+                    eprintln!("{:#}", e);
+                }
+            }
+        }
+        record.gateway_url = pinata.gateway_url(&manifest.token_uri);
+        if let Some(url) = &record.gateway_url {
+            println!("🌐 专属网关: {}", url);
+        }
+    }
+    Ok(record)
+}
@@ -0,0 +1,426 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::S3Client;
+use crate::estimate::format_bytes;
+use crate::exit_code::ExitCode;
+use crate::hashing::{HashAlgorithm, hash_bytes};
+
+// 缓存目录中记录每个文件来源的索引 (隐藏文件，批量流程不会把它当作图片)
+const INDEX_FILE_NAME: &str = ".sources.json";
+// 未配置 cache_dir 时放在输出目录下
+const DEFAULT_CACHE_DIR: &str = ".remote-cache";
+
+// ✅ config.toml 中的 [remote_source] 段：batch --urls / --s3 下载远程图片时使用的缓存目录和 S3 认证
+//
+// [remote_source]
+// region = "eu-west-1"
+// access_key = "${AWS_ACCESS_KEY_ID}"
+// secret_key = "${AWS_SECRET_ACCESS_KEY}"
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RemoteSourceConfig {
+    // 下载缓存，留空时为 <输出目录>/.remote-cache；再次运行时未变化的文件不会重新下载
+    pub cache_dir: Option<PathBuf>,
+    // 以下只用于 --s3：留空时使用 AWS 的地址 (MinIO、R2 等需要填写)
+    pub endpoint: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl RemoteSourceConfig {
+    fn region(&self) -> &str {
+        match self.region.as_str() {
+            "" => "us-east-1",
+            region => region,
+        }
+    }
+
+    fn endpoint(&self) -> String {
+        match self.endpoint.as_str() {
+            "" => format!("https://s3.{}.amazonaws.com", self.region()),
+            endpoint => endpoint.to_string(),
+        }
+    }
+
+    // 检查 [remote_source] 中的问题，为空表示可以使用
+    pub fn problems(&self, source: &RemoteSource) -> Vec<String> {
+        let mut problems = Vec::new();
+        if matches!(source, RemoteSource::S3 { .. })
+            && (self.access_key.is_empty() || self.secret_key.is_empty())
+        {
+            problems.push(
+                "--s3 需要 access_key 和 secret_key (可以写成 \"${AWS_ACCESS_KEY_ID}\")"
+                    .to_string(),
+            );
+        }
+        problems
+    }
+}
+
+// ✅ 批量流程的远程图片来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    // 每行一个 HTTP(S) 地址的列表文件，可以在地址后面用空格隔开指定保存的文件名
+    Urls(PathBuf),
+    // s3://<bucket>/<prefix>，prefix 下的对象按相对路径下载 (子文件夹保留)
+    S3 { bucket: String, prefix: String },
+}
+
+impl RemoteSource {
+    pub fn parse_s3(uri: &str) -> Result<Self> {
+        let path = uri
+            .strip_prefix("s3://")
+            .ok_or_else(|| anyhow!("❌ S3 来源应以 s3:// 开头: {}", uri))?;
+        let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+        if bucket.is_empty() {
+            return Err(anyhow!("❌ S3 来源缺少 bucket: {}", uri));
+        }
+        Ok(Self::S3 {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Urls(path) => format!("{:?}", path),
+            Self::S3 { bucket, prefix } => format!("s3://{}/{}", bucket, prefix),
+        }
+    }
+
+    // 每个来源一个缓存子目录：类型 + 来源标识的哈希
+    fn cache_name(&self) -> String {
+        let (kind, identity) = match self {
+            Self::Urls(path) => (
+                "urls",
+                fs::canonicalize(path)
+                    .unwrap_or_else(|_| path.clone())
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            Self::S3 { .. } => ("s3", self.label()),
+        };
+        let hash = hash_bytes(identity.as_bytes(), HashAlgorithm::Sha256);
+        let hash = hash.trim_start_matches("sha256:");
+        format!("{}-{}", kind, &hash[..16])
+    }
+}
+
+// 需要下载的一个文件
+#[derive(Debug, Clone)]
+struct Asset {
+    // 缓存目录中的相对路径
+    name: String,
+    // URL 或 S3 key
+    source: String,
+    // S3 列表中的 ETag，URL 要下载后才知道
+    etag: Option<String>,
+}
+
+// 索引中的一条记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CachedAsset {
+    source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    size: u64,
+}
+
+// 下载结果：dir 是交给批量流程的本地文件夹
+#[derive(Debug, Clone)]
+pub struct FetchReport {
+    pub dir: PathBuf,
+    pub downloaded: usize,
+    pub cached: usize,
+    pub removed: usize,
+    pub bytes: u64,
+}
+
+// URL 列表：跳过空行和 # 注释，文件名默认取地址最后一段
+fn read_url_list(path: &Path) -> Result<Vec<Asset>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取 URL 列表 {:?} 失败: {}", path, e))?;
+    let mut assets = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (url, name) = match line.split_once(char::is_whitespace) {
+            Some((url, name)) => (url, Some(name.trim().to_string())),
+            None => (line, None),
+        };
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(anyhow!(
+                "❌ {:?} 第 {} 行不是 HTTP(S) 地址: {}",
+                path,
+                number + 1,
+                url
+            ));
+        }
+        let name = match name {
+            Some(name) => name,
+            None => url_file_name(url).ok_or_else(|| {
+                anyhow!(
+                    "❌ {:?} 第 {} 行的地址没有文件名，请在地址后面指定: {}",
+                    path,
+                    number + 1,
+                    url
+                )
+            })?,
+        };
+        assets.push(Asset {
+            name,
+            source: url.to_string(),
+            etag: None,
+        });
+    }
+    Ok(assets)
+}
+
+// 地址路径的最后一段 (去掉查询参数并解码 %XX)
+fn url_file_name(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let (_, after_scheme) = path.split_once("://")?;
+    let (_, path) = after_scheme.split_once('/')?;
+    let name = percent_decode(path.rsplit('/').next()?);
+    (!name.is_empty()).then_some(name)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+// 文件名不能跳出缓存目录，也不能是隐藏文件 (批量流程会跳过)
+fn check_names(assets: &[Asset]) -> Result<()> {
+    let mut seen: BTreeMap<&str, &str> = BTreeMap::new();
+    for asset in assets {
+        let invalid = asset.name.is_empty()
+            || asset.name.starts_with('/')
+            || asset.name.contains('\\')
+            || asset
+                .name
+                .split('/')
+                .any(|part| part.is_empty() || part == ".." || part.starts_with('.'));
+        if invalid {
+            return Err(anyhow!(
+                "❌ 无效的文件名 {:?} (来自 {})",
+                asset.name,
+                asset.source
+            ));
+        }
+        if let Some(other) = seen.insert(&asset.name, &asset.source) {
+            return Err(anyhow!(
+                "❌ 两个来源使用了同一个文件名 {:?}: {} 和 {}，请在 URL 列表中指定不同的文件名",
+                asset.name,
+                other,
+                asset.source
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn load_index(dir: &Path) -> BTreeMap<String, CachedAsset> {
+    fs::read_to_string(dir.join(INDEX_FILE_NAME))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(dir: &Path, index: &BTreeMap<String, CachedAsset>) -> Result<()> {
+    fs::write(
+        dir.join(INDEX_FILE_NAME),
+        serde_json::to_string_pretty(index)?,
+    )?;
+    Ok(())
+}
+
+// 先写入同目录下的隐藏临时文件，完整下载后再重命名，中断时不会留下半个文件
+fn write_download(path: &Path, response: &mut reqwest::blocking::Response) -> Result<u64> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("❌ 无效的文件名: {:?}", path))?;
+    let partial = path.with_file_name(format!(".{}.part", name));
+    let mut file = File::create(&partial)?;
+    let size = response.copy_to(&mut file)?;
+    drop(file);
+    fs::rename(&partial, path)?;
+    Ok(size)
+}
+
+// ✅ 把远程来源下载到本地缓存目录，返回交给批量流程的文件夹；缓存中未变化的文件不会重新下载
+pub fn fetch_source(
+    config: &RemoteSourceConfig,
+    source: &RemoteSource,
+    output_root: &Path,
+) -> Result<FetchReport> {
+    let problems = config.problems(source);
+    if !problems.is_empty() {
+        return Err(ExitCode::Validation.wrap(anyhow!(
+            "❌ [remote_source] 配置有问题:\n  {}",
+            problems.join("\n  ")
+        )));
+    }
+    let cache_root = config
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| output_root.join(DEFAULT_CACHE_DIR));
+    let dir = cache_root.join(source.cache_name());
+    fs::create_dir_all(&dir).map_err(|e| anyhow!("❌ 创建缓存目录 {:?} 失败: {}", dir, e))?;
+    println!("\n--- 正在获取远程图片 {} -> {:?} ---", source.label(), dir);
+
+    let s3 = match source {
+        RemoteSource::S3 { bucket, .. } => Some(S3Client::new(
+            &config.endpoint(),
+            config.region(),
+            bucket,
+            &config.access_key,
+            &config.secret_key,
+        )?),
+        RemoteSource::Urls(_) => None,
+    };
+    let assets = match (source, &s3) {
+        (RemoteSource::Urls(path), _) => read_url_list(path)?,
+        (RemoteSource::S3 { prefix, .. }, Some(client)) => client
+            .list(prefix)?
+            .into_iter()
+            .filter(|object| !object.key.ends_with('/'))
+            .map(|object| Asset {
+                name: object.key[prefix.len()..]
+                    .trim_start_matches('/')
+                    .to_string(),
+                source: object.key,
+                etag: Some(object.etag),
+            })
+            .collect(),
+        (RemoteSource::S3 { .. }, None) => unreachable!(),
+    };
+    check_names(&assets)?;
+
+    let http = Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(None)
+        .build()?;
+    let mut index = load_index(&dir);
+    let mut report = FetchReport {
+        dir: dir.clone(),
+        downloaded: 0,
+        cached: 0,
+        removed: 0,
+        bytes: 0,
+    };
+
+    // 列表中已经没有的文件从缓存中删除，避免被当作图片上传
+    let stale: Vec<String> = index
+        .keys()
+        .filter(|name| !assets.iter().any(|asset| &asset.name == *name))
+        .cloned()
+        .collect();
+    for name in stale {
+        let _ = fs::remove_file(dir.join(&name));
+        index.remove(&name);
+        report.removed += 1;
+    }
+
+    for asset in &assets {
+        let path = dir.join(&asset.name);
+        let cached = index
+            .get(&asset.name)
+            .filter(|cached| cached.source == asset.source && path.is_file());
+        let entry = match (&s3, cached) {
+            // S3 列表中的 ETag 没变就是同一份内容
+            (Some(_), Some(cached)) if cached.etag == asset.etag => None,
+            (Some(client), _) => {
+                let size = write_download(&path, &mut client.get(&asset.source)?)?;
+                Some(CachedAsset {
+                    source: asset.source.clone(),
+                    etag: asset.etag.clone(),
+                    size,
+                })
+            }
+            // 没有 ETag 的地址视为内容不变
+            (None, Some(cached)) if cached.etag.is_none() => None,
+            (None, cached) => {
+                let mut request = http.get(&asset.source);
+                if let Some(etag) = cached.and_then(|cached| cached.etag.as_ref()) {
+                    request = request.header("If-None-Match", etag);
+                }
+                let mut response = request
+                    .send()
+                    .map_err(|e| anyhow!("❌ 下载 {} 失败: {}", asset.source, e))?;
+                match response.status() {
+                    StatusCode::NOT_MODIFIED => None,
+                    status if status.is_success() => {
+                        let etag = response
+                            .headers()
+                            .get("etag")
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string);
+                        let size = write_download(&path, &mut response)?;
+                        Some(CachedAsset {
+                            source: asset.source.clone(),
+                            etag,
+                            size,
+                        })
+                    }
+                    status => {
+                        return Err(anyhow!("❌ 下载 {} 失败: HTTP {}", asset.source, status));
+                    }
+                }
+            }
+        };
+        match entry {
+            Some(entry) => {
+                println!("⬇️  {} ({})", asset.name, format_bytes(entry.size));
+                report.downloaded += 1;
+                report.bytes += entry.size;
+                index.insert(asset.name.clone(), entry);
+                // 每个文件下载后都保存索引，中断后重新运行只下载剩下的
+                save_index(&dir, &index)?;
+            }
+            None => report.cached += 1,
+        }
+    }
+    save_index(&dir, &index)?;
+    println!(
+        "✅ 远程图片: 下载 {} 个 ({})，使用缓存 {} 个，移除 {} 个",
+        report.downloaded,
+        format_bytes(report.bytes),
+        report.cached,
+        report.removed
+    );
+    Ok(report)
+}
@@ -0,0 +1,416 @@
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::IpfsBackend;
+use crate::estimate::format_bytes;
+use crate::manifest::Manifest;
+use crate::metrics::path_size;
+use crate::usage::manifest_roots;
+
+pub const REPORT_MARKDOWN_FILE: &str = "report.md";
+pub const REPORT_HTML_FILE: &str = "report.html";
+
+// ✅ 运行报告的格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[serde(alias = "md")]
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Markdown => REPORT_MARKDOWN_FILE,
+            Self::Html => REPORT_HTML_FILE,
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            other => Err(format!(
+                "❌ 不支持的报告格式: {} (可选: markdown, html)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [report] 段：批量上传完成后在运行目录中写入的报告，formats 为空表示不生成
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ReportConfig {
+    pub formats: Vec<ReportFormat>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            formats: vec![ReportFormat::Markdown, ReportFormat::Html],
+        }
+    }
+}
+
+// ✅ 按阶段记录耗时：每次 lap 记录从上一次到现在的时间
+#[derive(Debug, Clone)]
+pub struct Timings {
+    started: Instant,
+    last: Instant,
+    pub laps: Vec<(String, Duration)>,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last: now,
+            laps: Vec::new(),
+        }
+    }
+}
+
+impl Timings {
+    pub fn lap(&mut self, phase: &str) {
+        let now = Instant::now();
+        self.laps.push((phase.to_string(), now - self.last));
+        self.last = now;
+    }
+
+    pub fn total(&self) -> Duration {
+        self.last - self.started
+    }
+}
+
+// 一个根 CID 的本地大小和在上传节点上的固定状态
+#[derive(Debug, Clone)]
+pub struct RootStatus {
+    pub role: &'static str,
+    pub cid: String,
+    pub files: u64,
+    pub bytes: u64,
+    // 保存这个 CID 的节点 (故障转移时为实际完成上传的节点)
+    pub node: String,
+    // 没有检查或检查失败时为 None
+    pub pinned: Option<bool>,
+}
+
+// ✅ 一次运行的报告内容
+#[derive(Debug, Clone)]
+pub struct RunReport<'a> {
+    pub manifest: &'a Manifest,
+    pub roots: Vec<RootStatus>,
+    pub timings: Vec<(String, Duration)>,
+    pub total: Option<Duration>,
+    pub failures: Vec<String>,
+}
+
+impl RunReport<'_> {
+    pub fn files(&self) -> u64 {
+        self.roots.iter().map(|root| root.files).sum()
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.roots.iter().map(|root| root.bytes).sum()
+    }
+}
+
+// ✅ 汇总清单、本地文件大小和节点上的固定状态；timings 为空表示不是在上传时生成的
+pub fn build_report<'a>(
+    manifest: &'a Manifest,
+    dir: &Path,
+    backend: Option<&dyn IpfsBackend>,
+    timings: Option<&Timings>,
+) -> RunReport<'a> {
+    let mut failures = Vec::new();
+    let mut roots = Vec::new();
+    for (role, cid, path) in manifest_roots(manifest, dir) {
+        let (files, bytes) = path_size(&path);
+        let pinned = match backend.map(|backend| backend.is_pinned(&cid)) {
+            Some(Ok(pinned)) => Some(pinned),
+            Some(Err(e)) => {
+                failures.push(format!("检查 {} ({}) 的固定状态失败: {:#}", role, cid, e));
+                None
+            }
+            None => None,
+        };
+        if pinned == Some(false) {
+            failures.push(format!("{} ({}) 没有固定在上传节点上", role, cid));
+        }
+        roots.push(RootStatus {
+            role,
+            node: manifest
+                .served_by
+                .get(&cid)
+                .cloned()
+                .unwrap_or_else(|| manifest.backend.clone()),
+            cid,
+            files,
+            bytes,
+            pinned,
+        });
+    }
+    for (service, record) in &manifest.remote_pins {
+        if record.failed > 0 {
+            failures.push(format!("{} 上有 {} 个 pin 失败", service, record.failed));
+        }
+    }
+    RunReport {
+        manifest,
+        roots,
+        timings: timings.map(|t| t.laps.clone()).unwrap_or_default(),
+        total: timings.map(Timings::total),
+        failures,
+    }
+}
+
+fn pin_status(pinned: Option<bool>) -> &'static str {
+    match pinned {
+        Some(true) => "✅ 已固定",
+        Some(false) => "❌ 未固定",
+        None => "未检查",
+    }
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.1} 秒", duration.as_secs_f64())
+}
+
+// 各服务商的固定状态：(服务商, 状态)
+fn provider_rows(report: &RunReport) -> Vec<(String, String)> {
+    let manifest = report.manifest;
+    let mut rows = Vec::new();
+    let mut nodes: Vec<&str> = report.roots.iter().map(|root| root.node.as_str()).collect();
+    nodes.sort();
+    nodes.dedup();
+    for node in nodes {
+        let roots: Vec<&RootStatus> = report.roots.iter().filter(|r| r.node == node).collect();
+        let pinned = roots.iter().filter(|r| r.pinned == Some(true)).count();
+        let checked = roots.iter().filter(|r| r.pinned.is_some()).count();
+        let status = if checked == 0 {
+            format!("{} 个根 CID，未检查", roots.len())
+        } else {
+            format!("{}/{} 个根 CID 已固定", pinned, roots.len())
+        };
+        rows.push((node.to_string(), status));
+    }
+    for (service, record) in &manifest.remote_pins {
+        let mut status = format!(
+            "{} 个 pin 成功，{} 个失败 ({})",
+            record.pinned, record.failed, record.pinned_at
+        );
+        if let Some(group) = &record.group_id {
+            let _ = write!(status, "，Group {}", group);
+        }
+        rows.push((service.clone(), status));
+    }
+    for (role, archive) in &manifest.filecoin {
+        let states: Vec<&str> = archive
+            .deals
+            .iter()
+            .map(|deal| deal.state.as_str())
+            .collect();
+        let status = if states.is_empty() {
+            "已提交，还没有交易".to_string()
+        } else {
+            format!("交易: {}", states.join(", "))
+        };
+        rows.push((format!("Filecoin {} ({})", archive.service, role), status));
+    }
+    rows
+}
+
+// ✅ Markdown 报告，可以直接贴到发布检查清单中
+pub fn render_markdown(report: &RunReport) -> String {
+    let manifest = report.manifest;
+    let mut out = String::new();
+    let _ = writeln!(out, "# 上传报告\n");
+    let _ = writeln!(out, "- 运行目录: `{}`", manifest.output_dir.display());
+    let _ = writeln!(out, "- 上传时间: {}", manifest.created_at);
+    let _ = writeln!(out, "- 上传后端: {}", manifest.backend);
+    let _ = writeln!(out, "- Token 数量: {}", manifest.tokens.len());
+    let _ = writeln!(
+        out,
+        "- 文件: {} 个，共 {}",
+        report.files(),
+        format_bytes(report.bytes())
+    );
+    let _ = writeln!(out, "- Base URI: `{}`\n", manifest.token_uri);
+
+    let _ = writeln!(out, "## 根 CID\n");
+    let _ = writeln!(out, "| 用途 | CID | 文件 | 大小 | 节点 | 状态 |");
+    let _ = writeln!(out, "| --- | --- | --- | --- | --- | --- |");
+    for root in &report.roots {
+        let _ = writeln!(
+            out,
+            "| {} | `{}` | {} | {} | {} | {} |",
+            root.role,
+            root.cid,
+            root.files,
+            format_bytes(root.bytes),
+            root.node,
+            pin_status(root.pinned)
+        );
+    }
+
+    let _ = writeln!(out, "\n## 服务商\n");
+    for (provider, status) in provider_rows(report) {
+        let _ = writeln!(out, "- {}: {}", provider, status);
+    }
+
+    let _ = writeln!(out, "\n## 问题\n");
+    if report.failures.is_empty() {
+        let _ = writeln!(out, "没有发现问题。");
+    }
+    for failure in &report.failures {
+        let _ = writeln!(out, "- [ ] {}", failure);
+    }
+
+    let _ = writeln!(out, "\n## 耗时\n");
+    if report.timings.is_empty() {
+        let _ = writeln!(out, "报告不是在上传时生成的，没有耗时记录。");
+    }
+    for (phase, duration) in &report.timings {
+        let _ = writeln!(out, "- {}: {}", phase, seconds(*duration));
+    }
+    if let Some(total) = report.total {
+        let _ = writeln!(out, "- 合计: {}", seconds(total));
+    }
+    out
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ✅ 单个 HTML 文件的报告，不依赖外部样式和脚本
+pub fn render_html(report: &RunReport) -> String {
+    let manifest = report.manifest;
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"zh\">\n<head>\n<meta charset=\"utf-8\">\n<title>上传报告</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; }\n\
+         table { border-collapse: collapse; width: 100%; }\n\
+         th, td { border: 1px solid #ddd; padding: 4px 8px; text-align: left; }\n\
+         code { word-break: break-all; }\n\
+         </style>\n</head>\n<body>\n<h1>上传报告</h1>\n<ul>\n",
+    );
+    let items = [
+        ("运行目录", manifest.output_dir.display().to_string()),
+        ("上传时间", manifest.created_at.clone()),
+        ("上传后端", manifest.backend.clone()),
+        ("Token 数量", manifest.tokens.len().to_string()),
+        (
+            "文件",
+            format!("{} 个，共 {}", report.files(), format_bytes(report.bytes())),
+        ),
+    ];
+    for (label, value) in items {
+        let _ = writeln!(out, "<li>{}: {}</li>", label, escape_html(&value));
+    }
+    let _ = writeln!(
+        out,
+        "<li>Base URI: <code>{}</code></li>\n</ul>",
+        escape_html(&manifest.token_uri)
+    );
+
+    out += "<h2>根 CID</h2>\n<table>\n<tr><th>用途</th><th>CID</th><th>文件</th><th>大小</th><th>节点</th><th>状态</th></tr>\n";
+    for root in &report.roots {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            root.role,
+            escape_html(&root.cid),
+            root.files,
+            format_bytes(root.bytes),
+            escape_html(&root.node),
+            pin_status(root.pinned)
+        );
+    }
+    out += "</table>\n<h2>服务商</h2>\n<ul>\n";
+    for (provider, status) in provider_rows(report) {
+        let _ = writeln!(
+            out,
+            "<li>{}: {}</li>",
+            escape_html(&provider),
+            escape_html(&status)
+        );
+    }
+    out += "</ul>\n<h2>问题</h2>\n";
+    if report.failures.is_empty() {
+        out += "<p>没有发现问题。</p>\n";
+    } else {
+        out += "<ul>\n";
+        for failure in &report.failures {
+            let _ = writeln!(out, "<li>{}</li>", escape_html(failure));
+        }
+        out += "</ul>\n";
+    }
+    out += "<h2>耗时</h2>\n";
+    if report.timings.is_empty() {
+        out += "<p>报告不是在上传时生成的，没有耗时记录。</p>\n";
+    } else {
+        out += "<table>\n";
+        for (phase, duration) in &report.timings {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(phase),
+                seconds(*duration)
+            );
+        }
+        if let Some(total) = report.total {
+            let _ = writeln!(out, "<tr><th>合计</th><th>{}</th></tr>", seconds(total));
+        }
+        out += "</table>\n";
+    }
+    out += "</body>\n</html>\n";
+    out
+}
+
+// ✅ 按配置的格式把报告写到运行目录
+pub fn write_reports(
+    dir: &Path,
+    report: &RunReport,
+    formats: &[ReportFormat],
+) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for format in formats {
+        let path = dir.join(format.file_name());
+        let content = match format {
+            ReportFormat::Markdown => render_markdown(report),
+            ReportFormat::Html => render_html(report),
+        };
+        fs::write(&path, content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use crate::arweave::clear_metadata_mirrors;
+use crate::backend::IpfsBackend;
+use crate::filecoin::drop_stale_archives;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+use crate::metadata::{UriChange, rewrite_uri_prefix, validate_uri};
+use crate::urls::{UrlConfig, record_urls};
+use crate::workflow::record_served_by;
+
+// rewrite-uris 的参数
+#[derive(Debug, Clone, Default)]
+pub struct RewriteOptions {
+    // 要替换的前缀，如 https://gateway.pinata.cloud/ipfs/bafy.../
+    pub from: String,
+    // 新的前缀，如 ipfs://bafy.../
+    pub to: String,
+    // 只检查和显示，不写文件也不上传
+    pub dry_run: bool,
+    // 更新后的清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+}
+
+// ✅ 一次改写的结果
+#[derive(Debug, Clone, Default)]
+pub struct RewriteReport {
+    pub files: usize,
+    pub changes: Vec<(String, UriChange)>,
+    // 重新上传后的元数据文件夹 CID 和 Base URI
+    pub metadata_cid: Option<String>,
+    pub token_uri: Option<String>,
+    // 同时更新的清单
+    pub manifest: Option<PathBuf>,
+}
+
+// 元数据文件夹中的文件 (跳过隐藏文件和子文件夹)，按文件名排序
+fn metadata_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| anyhow!("❌ 读取文件夹 {:?} 失败: {}", dir, e))?
+    {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .is_none_or(|name| name.starts_with('.'));
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// 元数据文件夹是一次运行的 metadata/ 时，同时更新旁边的清单
+fn update_manifest(
+    backend: &dyn IpfsBackend,
+    dir: &Path,
+    metadata_cid: &str,
+    changes: &[(String, UriChange)],
+    urls: &UrlConfig,
+) -> Result<Option<PathBuf>> {
+    let Some(run_dir) = dir.parent().filter(|_| dir.ends_with("metadata")) else {
+        return Ok(None);
+    };
+    if !run_dir.join(MANIFEST_FILE_NAME).exists() {
+        return Ok(None);
+    }
+    let mut manifest = Manifest::load(run_dir)?;
+    manifest.metadata_cid = metadata_cid.to_string();
+    manifest.token_uri = format!("ipfs://{}/", metadata_cid);
+    for token in &mut manifest.tokens {
+        let image_change = changes
+            .iter()
+            .find(|(file, change)| *file == token.metadata_file && change.field == "image");
+        if let Some((_, change)) = image_change {
+            token.image_uri = change.after.clone();
+        }
+    }
+    if manifest.ipld.take().is_some() {
+        println!("⚠️  原来的元数据是 IPLD 节点，这次改为以 JSON 文件重新发布");
+    }
+    for role in drop_stale_archives(&mut manifest) {
+        println!(
+            "⚠️  {} 的 CID 已变化，原来的 Filecoin 归档不再对应，可以重新运行 `filecoin submit`",
+            role
+        );
+    }
+    if clear_metadata_mirrors(&mut manifest) > 0 {
+        println!("⚠️  元数据已变化，原来的 Arweave 元数据镜像不再对应，可以重新运行 `arweave`");
+    }
+    record_served_by(&mut manifest, backend);
+    record_urls(&mut manifest, urls);
+    Ok(Some(manifest.save(run_dir)?))
+}
+
+// ✅ 改写元数据文件夹中所有 image / animation_url 的前缀，检查后重新上传
+pub fn rewrite_metadata_uris(
+    backend: &dyn IpfsBackend,
+    dir: &Path,
+    options: &RewriteOptions,
+) -> Result<RewriteReport> {
+    if options.from.is_empty() {
+        return Err(anyhow!("❌ --from 不能为空"));
+    }
+    let files = metadata_files(dir)?;
+    if files.is_empty() {
+        return Err(anyhow!("❌ 元数据文件夹为空: {:?}", dir));
+    }
+
+    // 先在内存中改写并检查所有文件，有任何问题都不修改
+    let mut rewritten = Vec::new();
+    let mut changes = Vec::new();
+    let mut problems = Vec::new();
+    for path in &files {
+        let name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let content = fs::read_to_string(path)?;
+        let (content, file_changes) = match rewrite_uri_prefix(&content, &options.from, &options.to)
+        {
+            Ok(result) => result,
+            Err(e) => {
+                problems.push(format!("{}: 不是有效的 JSON ({})", name, e));
+                continue;
+            }
+        };
+        // 其它项目的元数据字段不一定相同，只要求有 image
+        let metadata: Value = serde_json::from_str(&content)?;
+        if metadata.get("image").and_then(Value::as_str).is_none() {
+            problems.push(format!("{}: 缺少 image 字段", name));
+        }
+        for change in &file_changes {
+            if let Some(problem) = validate_uri(&change.after) {
+                problems.push(format!("{}: {} {}", name, change.field, problem));
+            }
+        }
+        if !file_changes.is_empty() {
+            rewritten.push((path.clone(), content));
+        }
+        changes.extend(
+            file_changes
+                .into_iter()
+                .map(|change| (name.clone(), change)),
+        );
+    }
+    if !problems.is_empty() {
+        return Err(anyhow!(
+            "❌ 改写后的元数据没有通过检查:\n  {}",
+            problems.join("\n  ")
+        ));
+    }
+
+    let mut report = RewriteReport {
+        files: files.len(),
+        changes,
+        ..RewriteReport::default()
+    };
+    if options.dry_run || rewritten.is_empty() {
+        return Ok(report);
+    }
+    for (path, content) in &rewritten {
+        fs::write(path, content)?;
+    }
+    println!("✅ 已改写 {} 个元数据文件", rewritten.len());
+
+    let metadata_cid = backend.add_path(dir)?;
+    report.manifest = update_manifest(backend, dir, &metadata_cid, &report.changes, &options.urls)?;
+    report.token_uri = Some(format!("ipfs://{}/", metadata_cid));
+    report.metadata_cid = Some(metadata_cid);
+    Ok(report)
+}
+
+// 最多展示的改写条数
+const MAX_LISTED_CHANGES: usize = 5;
+
+pub fn print_rewrite_report(report: &RewriteReport, dry_run: bool) {
+    let changed_files = {
+        let mut files: Vec<&str> = report.changes.iter().map(|(f, _)| f.as_str()).collect();
+        files.dedup();
+        files.len()
+    };
+    println!(
+        "\n📝 {} 个文件中有 {} 个字段{} ({} 个文件)",
+        report.files,
+        report.changes.len(),
+        if dry_run { "将会改写" } else { "已改写" },
+        changed_files
+    );
+    for (file, change) in report.changes.iter().take(MAX_LISTED_CHANGES) {
+        println!("   {} {}:", file, change.field);
+        println!("     - {}", change.before);
+        println!("     + {}", change.after);
+    }
+    if report.changes.len() > MAX_LISTED_CHANGES {
+        println!(
+            "   ... 另有 {} 个字段",
+            report.changes.len() - MAX_LISTED_CHANGES
+        );
+    }
+    if report.changes.is_empty() {
+        println!("⚠️  没有字段以 --from 指定的前缀开头，请检查前缀是否正确");
+    }
+    if let Some(token_uri) = &report.token_uri {
+        println!("\n🔗 新 Base URI: {}", token_uri);
+        if let Some(manifest) = &report.manifest {
+            println!("📄 已更新清单: {:?}", manifest);
+        }
+        println!("下一步，请在合约中把 Base URI 更新为新的地址。");
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::manifest::{CollectionKind, MANIFEST_FILE_NAME, Manifest};
+use crate::monitor::{CidReport, discover_manifests, gateway_url};
+use crate::pinning::PinningServiceConfig;
+
+pub const RUNBOOK_FILE_NAME: &str = "runbook.md";
+
+// 生成 runbook 需要的上下文
+pub struct RunbookContext<'a> {
+    pub manifest: &'a Manifest,
+    pub manifest_path: &'a Path,
+    // 本次生成时的巡检结果
+    pub reports: &'a [CidReport],
+    pub gateways: &'a [String],
+    pub pinning_services: &'a [PinningServiceConfig],
+    // 同类型的上一次运行，用于回滚
+    pub previous: Option<(PathBuf, Manifest)>,
+}
+
+// 常见 Pinning 服务的控制台地址
+fn dashboard_url(endpoint: &str) -> Option<&'static str> {
+    let endpoint = endpoint.to_ascii_lowercase();
+    if endpoint.contains("pinata") {
+        Some("https://app.pinata.cloud/ipfs/files")
+    } else if endpoint.contains("filebase") {
+        Some("https://console.filebase.com/")
+    } else if endpoint.contains("infura") {
+        Some("https://app.infura.io/")
+    } else {
+        None
+    }
+}
+
+// 在输出目录中找到同类型、比当前更早的最近一次运行
+pub fn find_previous(
+    output_root: &Path,
+    current: &Manifest,
+) -> Result<Option<(PathBuf, Manifest)>> {
+    let mut previous = None;
+    for path in discover_manifests(output_root)? {
+        let Ok(manifest) = Manifest::load(&path) else {
+            continue;
+        };
+        if manifest.kind == current.kind
+            && manifest.created_at < current.created_at
+            && previous
+                .as_ref()
+                .is_none_or(|(_, p): &(PathBuf, Manifest)| p.created_at < manifest.created_at)
+        {
+            previous = Some((path, manifest));
+        }
+    }
+    Ok(previous)
+}
+
+fn contract_commands(out: &mut String, manifest: &Manifest) {
+    let token_uri = &manifest.token_uri;
+    match manifest.kind {
+        CollectionKind::Single => {
+            let _ = writeln!(out, "```bash");
+            let _ = writeln!(
+                out,
+                "cast send $CONTRACT \"safeMint(address,string)\" $TO \"{}\" --rpc-url $RPC_URL --private-key $PRIVATE_KEY",
+                token_uri
+            );
+            let _ = writeln!(out, "```");
+        }
+        CollectionKind::Batch | CollectionKind::Watch => {
+            let _ = writeln!(out, "```bash");
+            let _ = writeln!(
+                out,
+                "cast send $CONTRACT \"setBaseURI(string)\" \"{}\" --rpc-url $RPC_URL --private-key $PRIVATE_KEY",
+                token_uri
+            );
+            let _ = writeln!(out, "# 确认链上的 tokenURI");
+            let sample_id = manifest
+                .tokens
+                .first()
+                .and_then(|token| token.token_id)
+                .unwrap_or(1);
+            let _ = writeln!(
+                out,
+                "cast call $CONTRACT \"tokenURI(uint256)(string)\" {} --rpc-url $RPC_URL",
+                sample_id
+            );
+            let _ = writeln!(out, "```");
+        }
+    }
+}
+
+// ✅ 生成一次运行的 mint 当天操作手册 (Markdown)
+pub fn render_runbook(ctx: &RunbookContext) -> String {
+    let manifest = ctx.manifest;
+    let mut out = String::new();
+    let _ = writeln!(out, "# Mint 操作手册\n");
+    let _ = writeln!(out, "- 清单: `{}`", ctx.manifest_path.display());
+    let _ = writeln!(out, "- 类型: {:?}", manifest.kind);
+    let _ = writeln!(out, "- 上传时间: {}", manifest.created_at);
+    let _ = writeln!(out, "- 上传后端: {}", manifest.backend);
+    let _ = writeln!(out, "- Token 数量: {}", manifest.tokens.len());
+    let _ = writeln!(out, "- 图片 CID: `{}`", manifest.image_cid);
+    let _ = writeln!(out, "- 元数据 CID: `{}`", manifest.metadata_cid);
+    let _ = writeln!(out, "- Token URI: `{}`\n", manifest.token_uri);
+
+    let _ = writeln!(out, "## 检查清单\n");
+    for report in ctx.reports {
+        let mark = if report.is_healthy() { "x" } else { " " };
+        let pinned = if report.pinned {
+            "已固定"
+        } else if report.repinned {
+            "已重新固定"
+        } else {
+            "未固定"
+        };
+        let reachable = report.gateways.iter().filter(|(_, ok)| *ok).count();
+        let _ = writeln!(
+            out,
+            "- [{}] {} `{}`：{}，网关可访问 {}/{}",
+            mark,
+            report.label,
+            report.cid,
+            pinned,
+            reachable,
+            report.gateways.len()
+        );
+    }
+    let _ = writeln!(
+        out,
+        "- [ ] 在合约中设置 URI 后，抽查几个 tokenURI 能否正常显示\n"
+    );
+
+    let _ = writeln!(out, "## 网关地址\n");
+    for gateway in ctx.gateways {
+        let _ = writeln!(out, "- {}", gateway_url(gateway, &manifest.image_cid));
+        let _ = writeln!(out, "- {}", gateway_url(gateway, &manifest.metadata_cid));
+    }
+    if let Some(token) = manifest.tokens.first() {
+        let _ = writeln!(out, "\n示例 token `{}`: `{}`", token.name, token.image_uri);
+    }
+
+    let _ = writeln!(out, "\n## IPNS\n");
+    let _ = writeln!(
+        out,
+        "本次运行没有发布 IPNS 名称，合约直接引用上面的 CID。\n"
+    );
+
+    let _ = writeln!(out, "## Pinning 服务\n");
+    if ctx.pinning_services.is_empty() {
+        let _ = writeln!(out, "配置文件中没有 Pinning 服务。");
+    }
+    for service in ctx.pinning_services {
+        let _ = write!(out, "- {}: API `{}`", service.name, service.endpoint);
+        if let Some(url) = dashboard_url(&service.endpoint) {
+            let _ = write!(out, "，控制台 {}", url);
+        }
+        let _ = writeln!(out);
+    }
+
+    let _ = writeln!(out, "\n## 合约调用\n");
+    let _ = writeln!(
+        out,
+        "需要先设置 `CONTRACT`、`RPC_URL`、`PRIVATE_KEY` (单件还需要 `TO`) 环境变量。\n"
+    );
+    contract_commands(&mut out, manifest);
+
+    let _ = writeln!(out, "\n## 回滚\n");
+    match &ctx.previous {
+        Some((path, previous)) => {
+            let _ = writeln!(
+                out,
+                "上一次同类型的运行是 `{}` ({})，出现问题时可以把 URI 改回 `{}`：\n",
+                path.display(),
+                previous.created_at,
+                previous.token_uri
+            );
+            contract_commands(&mut out, previous);
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "输出目录中没有更早的同类型运行。如需回滚，请暂停 mint，修正后重新上传并用新的 URI 调用上面的命令。"
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "\n已经 mint 出去的 token 若使用不可变的 tokenURI，回滚只能影响之后的 mint。"
+    );
+    out
+}
+
+// 写到清单所在目录
+pub fn write_runbook(ctx: &RunbookContext) -> Result<PathBuf> {
+    let dir = if ctx.manifest_path.is_dir() {
+        ctx.manifest_path
+    } else {
+        ctx.manifest_path.parent().unwrap_or(Path::new("."))
+    };
+    let path = dir.join(RUNBOOK_FILE_NAME);
+    fs::write(&path, render_runbook(ctx))?;
+    Ok(path)
+}
+
+// 清单路径可能是目录
+pub fn manifest_file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(MANIFEST_FILE_NAME)
+    } else {
+        path.to_path_buf()
+    }
+}
@@ -0,0 +1,260 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use exif::{In, Reader, Tag};
+use walkdir::WalkDir;
+
+// 清理的结果：删除了哪些内容 (空表示文件中没有需要删除的信息)
+#[derive(Debug, Clone, Default)]
+pub struct StripReport {
+    pub removed: Vec<String>,
+    // 删除了非默认的方向信息，部分查看器显示的方向会改变
+    pub orientation_lost: bool,
+}
+
+impl StripReport {
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty()
+    }
+}
+
+// 从 EXIF 中找出值得提醒的内容：GPS 位置、设备型号、拍摄时间
+fn describe_exif(tiff: &[u8], report: &mut StripReport) {
+    let Ok(exif) = Reader::new().read_raw(tiff.to_vec()) else {
+        report.removed.push("EXIF".to_string());
+        return;
+    };
+    let text = |tag: Tag| {
+        exif.get_field(tag, In::PRIMARY)
+            .map(|field| {
+                field
+                    .display_value()
+                    .to_string()
+                    .trim_matches('"')
+                    .trim()
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty())
+    };
+    let mut details = Vec::new();
+    if exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_some() {
+        details.push("GPS 位置".to_string());
+    }
+    let device: Vec<String> = [Tag::Make, Tag::Model]
+        .into_iter()
+        .filter_map(text)
+        .collect();
+    if !device.is_empty() {
+        details.push(format!("设备 {}", device.join(" ")));
+    }
+    if let Some(time) = text(Tag::DateTimeOriginal).or_else(|| text(Tag::DateTime)) {
+        details.push(format!("拍摄时间 {}", time));
+    }
+    if exif
+        .get_field(Tag::Orientation, In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .is_some_and(|orientation| orientation != 1)
+    {
+        report.orientation_lost = true;
+    }
+    if details.is_empty() {
+        report.removed.push("EXIF".to_string());
+    } else {
+        report
+            .removed
+            .push(format!("EXIF ({})", details.join(", ")));
+    }
+}
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADERS: [&[u8]; 2] = [
+    b"http://ns.adobe.com/xap/1.0/\0",
+    b"http://ns.adobe.com/xmp/extension/\0",
+];
+
+// ✅ JPEG：删除 APP1 (EXIF / XMP)、厂商自定义的 APP 段和注释，保留 JFIF (APP0)、ICC 颜色配置 (APP2) 和 Adobe (APP14)
+// 只删除段，不重新编码图像数据
+fn strip_jpeg(data: &[u8], report: &mut StripReport) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return Err(anyhow!("JPEG 段结构损坏 (偏移 {})", pos));
+        }
+        let marker = data[pos + 1];
+        // 扫描数据开始后不再有元数据段，余下部分原样保留
+        if marker == 0xDA {
+            break;
+        }
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            return Err(anyhow!("JPEG 段长度错误 (偏移 {})", pos));
+        }
+        let payload = &data[pos + 4..end];
+        let keep = match marker {
+            0xE1 => {
+                if let Some(tiff) = payload.strip_prefix(EXIF_HEADER) {
+                    describe_exif(tiff, report);
+                } else if XMP_HEADERS.iter().any(|h| payload.starts_with(h)) {
+                    report.removed.push("XMP".to_string());
+                } else {
+                    report.removed.push("APP1".to_string());
+                }
+                false
+            }
+            0xE0 | 0xE2 | 0xEE => true,
+            0xE3..=0xEF => {
+                let vendor: String = payload
+                    .iter()
+                    .take_while(|b| b.is_ascii_graphic())
+                    .map(|b| *b as char)
+                    .collect();
+                report.removed.push(if vendor.is_empty() {
+                    format!("APP{}", marker - 0xE0)
+                } else {
+                    format!("APP{} ({})", marker - 0xE0, vendor)
+                });
+                false
+            }
+            0xFE => {
+                report.removed.push("注释".to_string());
+                false
+            }
+            _ => true,
+        };
+        if keep {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Ok(out)
+}
+
+// ✅ PNG：删除 eXIf、文本块 (tEXt / zTXt / iTXt，XMP 也保存在 iTXt 中) 和修改时间 tIME
+fn strip_png(data: &[u8], report: &mut StripReport) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..8]);
+    let mut pos = 8;
+    while pos + 12 <= data.len() {
+        let length = u32::from_be_bytes(data[pos..pos + 4].try_into()?) as usize;
+        let end = pos + 12 + length;
+        if end > data.len() {
+            return Err(anyhow!("PNG 块长度错误 (偏移 {})", pos));
+        }
+        let kind = &data[pos + 4..pos + 8];
+        let payload = &data[pos + 8..pos + 8 + length];
+        let keyword = || {
+            String::from_utf8_lossy(payload.split(|b| *b == 0).next().unwrap_or_default())
+                .to_string()
+        };
+        match kind {
+            b"eXIf" => describe_exif(payload, report),
+            b"iTXt" if keyword() == "XML:com.adobe.xmp" => report.removed.push("XMP".to_string()),
+            b"tEXt" | b"zTXt" | b"iTXt" => report.removed.push(format!("文本 {}", keyword())),
+            b"tIME" => report.removed.push("修改时间".to_string()),
+            _ => out.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Ok(out)
+}
+
+// VP8X 标志位中表示带 EXIF 和 XMP 的两位
+const WEBP_EXIF_FLAG: u8 = 0x08;
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+// ✅ WebP：删除 EXIF 和 XMP 块，同时清除 VP8X 中对应的标志位
+fn strip_webp(data: &[u8], report: &mut StripReport) -> Result<Vec<u8>> {
+    let mut chunks = Vec::with_capacity(data.len());
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let kind = &data[pos..pos + 4];
+        let length = u32::from_le_bytes(data[pos + 4..pos + 8].try_into()?) as usize;
+        let end = (pos + 8 + length + length % 2).min(data.len());
+        if pos + 8 + length > data.len() {
+            return Err(anyhow!("WebP 块长度错误 (偏移 {})", pos));
+        }
+        let payload = &data[pos + 8..pos + 8 + length];
+        match kind {
+            b"EXIF" => describe_exif(payload.strip_prefix(EXIF_HEADER).unwrap_or(payload), report),
+            b"XMP " => report.removed.push("XMP".to_string()),
+            b"VP8X" => {
+                let start = chunks.len();
+                chunks.extend_from_slice(&data[pos..end]);
+                if let Some(flags) = chunks.get_mut(start + 8) {
+                    *flags &= !(WEBP_EXIF_FLAG | WEBP_XMP_FLAG);
+                }
+            }
+            _ => chunks.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+    let mut out = Vec::with_capacity(chunks.len() + 12);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(chunks.len() as u32 + 4).to_le_bytes());
+    out.extend_from_slice(b"WEBP");
+    out.extend_from_slice(&chunks);
+    Ok(out)
+}
+
+// 按文件头选择格式；不支持的格式 (GIF、视频等) 返回 None
+pub fn strip_metadata(data: &[u8]) -> Result<Option<(Vec<u8>, StripReport)>> {
+    let mut report = StripReport::default();
+    let stripped = if data.starts_with(&[0xFF, 0xD8]) {
+        strip_jpeg(data, &mut report)?
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        strip_png(data, &mut report)?
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        strip_webp(data, &mut report)?
+    } else {
+        return Ok(None);
+    };
+    Ok(Some((stripped, report)))
+}
+
+// ✅ 清理文件中的 EXIF / XMP 等信息并覆盖原文件，返回删除的内容；没有需要删除的信息时不改动文件
+pub fn strip_file(path: &Path) -> Result<StripReport> {
+    let data = fs::read(path).map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?;
+    let Some((stripped, report)) =
+        strip_metadata(&data).map_err(|e| anyhow!("❌ 清理 {:?} 的元信息失败: {}", path, e))?
+    else {
+        return Ok(StripReport::default());
+    };
+    if !report.is_empty() {
+        fs::write(path, stripped)?;
+    }
+    Ok(report)
+}
+
+// 清理并打印删除的内容
+pub fn strip_and_report(path: &Path) -> Result<StripReport> {
+    let report = strip_file(path)?;
+    if !report.is_empty() {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        println!("🧹 {}: 已删除 {}", name, report.removed.join("、"));
+        if report.orientation_lost {
+            eprintln!(
+                "⚠️  {} 的 EXIF 中带有旋转方向，删除后部分查看器显示的方向会改变，建议先把图片旋转为正向后再上传",
+                name
+            );
+        }
+    }
+    Ok(report)
+}
+
+// ✅ 清理目录 (含子目录) 中的所有文件，返回清理过的文件数
+pub fn strip_dir(dir: &Path) -> Result<usize> {
+    let mut count = 0;
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_file() && !strip_and_report(entry.path())?.is_empty() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
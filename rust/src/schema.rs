@@ -0,0 +1,623 @@
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde_json::{Map, Value, json};
+
+// ✅ 清单 (manifest.json) 和 NFT 元数据的跨语言结构定义
+// Python / TypeScript / Go 实现通过 `schema export` 生成的类型读写同样的 JSON；
+// 修改 manifest.rs 或 metadata.rs 中的字段时需要同步更新这里
+
+#[derive(Debug, Clone, Copy)]
+pub enum FieldType {
+    String,
+    // ipfs:// 链接，严格模式的 JSON Schema 检查前缀
+    IpfsUri,
+    // 非负整数 (u64)
+    Integer,
+    // 任意 JSON 值
+    Any,
+    // 引用下面定义的另一个类型
+    Ref(&'static str),
+    Array(&'static FieldType),
+    // 键为字符串的对象
+    Map(&'static FieldType),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    pub ty: FieldType,
+    // 值可以是 null
+    pub nullable: bool,
+    // 字段可以省略
+    pub optional: bool,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TypeBody {
+    Struct(&'static [FieldSpec]),
+    // 字符串枚举
+    Enum(&'static [&'static str]),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TypeSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub body: TypeBody,
+}
+
+const fn field(name: &'static str, ty: FieldType, description: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        ty,
+        nullable: false,
+        optional: false,
+        description,
+    }
+}
+
+const fn nullable(mut spec: FieldSpec) -> FieldSpec {
+    spec.nullable = true;
+    spec
+}
+
+const fn optional(mut spec: FieldSpec) -> FieldSpec {
+    spec.optional = true;
+    spec
+}
+
+pub const SCHEMA_ID: &str =
+    "https://github.com/qiaopengjun5162/polyglot-ipfs-uploader/schema/manifest.schema.json";
+pub const METADATA_SCHEMA_ID: &str =
+    "https://github.com/qiaopengjun5162/polyglot-ipfs-uploader/schema/metadata.schema.json";
+
+// ✅ JSON Schema 的顶层类型：清单或单个 token 的元数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchemaRoot {
+    #[default]
+    Manifest,
+    Metadata,
+}
+
+impl SchemaRoot {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manifest => "manifest",
+            Self::Metadata => "metadata",
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Manifest => "Manifest",
+            Self::Metadata => "NftMetadata",
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Manifest => SCHEMA_ID,
+            Self::Metadata => METADATA_SCHEMA_ID,
+        }
+    }
+}
+
+impl fmt::Display for SchemaRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SchemaRoot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "manifest" => Ok(Self::Manifest),
+            "metadata" | "nftmetadata" => Ok(Self::Metadata),
+            other => Err(anyhow!(
+                "❌ 不支持的顶层类型: {} (可选: manifest, metadata)",
+                other
+            )),
+        }
+    }
+}
+
+// 被引用的类型放在前面，生成 Python 代码时不需要前向引用
+pub const TYPES: &[TypeSpec] = &[
+    TypeSpec {
+        name: "CollectionKind",
+        description: "工作流类型",
+        body: TypeBody::Enum(&["single", "batch", "watch"]),
+    },
+    TypeSpec {
+        name: "DagCodec",
+        description: "IPLD 元数据节点的存储编码",
+        body: TypeBody::Enum(&["dag-cbor", "dag-json"]),
+    },
+    TypeSpec {
+        name: "DealService",
+        description: "提交 Filecoin 存储交易的方式",
+        body: TypeBody::Enum(&["storacha", "lotus"]),
+    },
+    TypeSpec {
+        name: "DealState",
+        description: "一笔 Filecoin 存储交易的状态",
+        body: TypeBody::Enum(&["queued", "published", "active", "expired", "failed"]),
+    },
+    TypeSpec {
+        name: "Attribute",
+        description: "NFT 元数据中的一个属性",
+        body: TypeBody::Struct(&[
+            field("trait_type", FieldType::String, "属性名"),
+            field("value", FieldType::Any, "属性值，字符串或数字"),
+        ]),
+    },
+    TypeSpec {
+        name: "NftMetadata",
+        description: "上传到 IPFS 的 NFT 元数据 JSON，可以带有项目自定义的其它顶层字段 (如 dna、edition)",
+        body: TypeBody::Struct(&[
+            field("name", FieldType::String, "token 名称"),
+            field("description", FieldType::String, "描述"),
+            field(
+                "image",
+                FieldType::IpfsUri,
+                "图片链接，ipfs://<CID>[/<文件名>]",
+            ),
+            optional(field(
+                "animation_url",
+                FieldType::IpfsUri,
+                "视频、音频等主文件的链接，此时 image 是封面图",
+            )),
+            field(
+                "attributes",
+                FieldType::Array(&FieldType::Ref("Attribute")),
+                "属性列表",
+            ),
+        ]),
+    },
+    TypeSpec {
+        name: "ArweaveUris",
+        description: "一个 token 在 Arweave 上的镜像地址",
+        body: TypeBody::Struct(&[
+            optional(field(
+                "image_uri",
+                FieldType::String,
+                "image_file 的 ar://<交易 ID>",
+            )),
+            optional(field(
+                "metadata_uri",
+                FieldType::String,
+                "元数据文件的 ar://<交易 ID>",
+            )),
+        ]),
+    },
+    TypeSpec {
+        name: "TokenEntry",
+        description: "单个 token 的上传记录",
+        body: TypeBody::Struct(&[
+            nullable(field(
+                "token_id",
+                FieldType::Integer,
+                "token ID，单件 NFT 为 null",
+            )),
+            field("name", FieldType::String, "token 名称"),
+            field("image_file", FieldType::String, "图片文件名"),
+            optional(field(
+                "image_hash",
+                FieldType::String,
+                "图片内容哈希，带算法前缀，如 blake3:...",
+            )),
+            field("image_uri", FieldType::String, "元数据中的 image 链接"),
+            optional(field(
+                "mime_type",
+                FieldType::String,
+                "按文件内容判断的 MIME 类型，如 image/png",
+            )),
+            optional(field(
+                "animation_uri",
+                FieldType::String,
+                "视频、音频等主文件的 animation_url 链接，此时 image_uri 是封面图",
+            )),
+            optional(field(
+                "original_uri",
+                FieldType::String,
+                "image_uri 是预览图时原图的链接",
+            )),
+            field("metadata_file", FieldType::String, "本地元数据文件名"),
+            optional(field(
+                "metadata_cid",
+                FieldType::String,
+                "单独上传的元数据 CID (单件、watch 模式和 IPLD 元数据)",
+            )),
+            optional(field(
+                "arweave",
+                FieldType::Ref("ArweaveUris"),
+                "镜像到 Arweave 后的 ar:// 地址",
+            )),
+        ]),
+    },
+    TypeSpec {
+        name: "EncryptionInfo",
+        description: "上传前加密时使用的算法和密钥指纹",
+        body: TypeBody::Struct(&[
+            field("algorithm", FieldType::String, "加密算法，如 aes-256-gcm"),
+            field(
+                "key_fingerprint",
+                FieldType::String,
+                "集合密钥的指纹 (sha256:<十六进制>)",
+            ),
+        ]),
+    },
+    TypeSpec {
+        name: "CidUrls",
+        description: "一个根 CID 的各种访问链接",
+        body: TypeBody::Struct(&[
+            field("cid", FieldType::String, "CID"),
+            field("ipfs", FieldType::String, "ipfs://<cid>，钱包和市场使用"),
+            field(
+                "path_gateway",
+                FieldType::String,
+                "路径网关地址 <网关>/ipfs/<cid>",
+            ),
+            optional(field(
+                "subdomain_base32",
+                FieldType::String,
+                "子域名网关地址，CID 为 base32 v1",
+            )),
+            optional(field(
+                "subdomain_base36",
+                FieldType::String,
+                "子域名网关地址，CID 为 base36 v1",
+            )),
+            optional(field(
+                "custom",
+                FieldType::Array(&FieldType::String),
+                "配置的自定义网关上的地址",
+            )),
+        ]),
+    },
+    TypeSpec {
+        name: "DealInfo",
+        description: "一个存储提供方上的一笔 Filecoin 交易",
+        body: TypeBody::Struct(&[
+            field(
+                "storage_provider",
+                FieldType::String,
+                "存储提供方，如 f01234",
+            ),
+            optional(field("deal_id", FieldType::Integer, "上链后的交易 ID")),
+            optional(field(
+                "proposal_cid",
+                FieldType::String,
+                "lotus 交易提案的 CID",
+            )),
+            field("state", FieldType::Ref("DealState"), "交易状态"),
+            optional(field("message", FieldType::String, "失败原因等附加信息")),
+        ]),
+    },
+    TypeSpec {
+        name: "FilecoinArchive",
+        description: "一个根 CID 的 Filecoin 归档记录",
+        body: TypeBody::Struct(&[
+            field("cid", FieldType::String, "归档的根 CID"),
+            field("service", FieldType::Ref("DealService"), "提交交易的方式"),
+            field(
+                "car_file",
+                FieldType::String,
+                "运行目录中的 CAR 文件 (相对路径)",
+            ),
+            field("car_bytes", FieldType::Integer, "CAR 文件大小 (字节)"),
+            optional(field(
+                "piece_cid",
+                FieldType::String,
+                "存储提供方计算的 piece CID",
+            )),
+            field("submitted_at", FieldType::String, "提交时间 (RFC 3339)"),
+            field("updated_at", FieldType::String, "最近一次查询状态的时间"),
+            optional(field(
+                "deals",
+                FieldType::Array(&FieldType::Ref("DealInfo")),
+                "存储交易",
+            )),
+        ]),
+    },
+    TypeSpec {
+        name: "RemotePinRecord",
+        description: "一个 Pinning 服务上的 pin 结果",
+        body: TypeBody::Struct(&[
+            field("collection", FieldType::String, "pin 名称中的集合名"),
+            field("pinned_at", FieldType::String, "pin 时间 (RFC 3339)"),
+            field("pinned", FieldType::Integer, "成功的 pin 数量"),
+            field("failed", FieldType::Integer, "失败的 pin 数量"),
+            optional(field("group_id", FieldType::String, "加入的 Pinata Group")),
+            optional(field(
+                "gateway_url",
+                FieldType::String,
+                "token_uri 在 Pinata 专属网关上的地址",
+            )),
+        ]),
+    },
+    TypeSpec {
+        name: "Manifest",
+        description: "一次运行的结果清单，保存在输出目录的 manifest.json 中",
+        body: TypeBody::Struct(&[
+            field("kind", FieldType::Ref("CollectionKind"), "工作流类型"),
+            field("created_at", FieldType::String, "生成时间 (RFC 3339)"),
+            field("backend", FieldType::String, "上传使用的后端"),
+            field("output_dir", FieldType::String, "本地输出目录"),
+            field(
+                "image_cid",
+                FieldType::String,
+                "单件: 图片 CID；批量: 图片文件夹 CID",
+            ),
+            optional(field(
+                "posters_cid",
+                FieldType::String,
+                "视频、音频等文件的封面图，单件: 封面图 CID；批量: posters 文件夹 CID",
+            )),
+            optional(field(
+                "previews_cid",
+                FieldType::String,
+                "大图的预览图，单件: 预览图 CID；批量: previews 文件夹 CID",
+            )),
+            optional(field(
+                "watermarked_cid",
+                FieldType::String,
+                "加水印的公开预览，单件: 水印图片 CID；批量: watermarked 文件夹 CID",
+            )),
+            optional(field(
+                "localization_cid",
+                FieldType::String,
+                "OpenSea 多语言扩展的各语言文件 (localization 文件夹 CID)",
+            )),
+            field(
+                "metadata_cid",
+                FieldType::String,
+                "单件: 元数据 CID；批量: 元数据文件夹 CID",
+            ),
+            field(
+                "token_uri",
+                FieldType::String,
+                "单件: mint 时使用的 token URI；批量: 合约的 Base URI",
+            ),
+            field(
+                "tokens",
+                FieldType::Array(&FieldType::Ref("TokenEntry")),
+                "每个 token 的记录",
+            ),
+            optional(field(
+                "served_by",
+                FieldType::Map(&FieldType::String),
+                "多节点故障转移时，CID -> 实际完成上传的节点",
+            )),
+            optional(field(
+                "urls",
+                FieldType::Map(&FieldType::Ref("CidUrls")),
+                "用途 (image、metadata 等) -> 该根 CID 的访问链接",
+            )),
+            optional(field(
+                "encryption",
+                FieldType::Ref("EncryptionInfo"),
+                "上传前加密过时的算法和密钥指纹",
+            )),
+            optional(field(
+                "ipld",
+                FieldType::Ref("DagCodec"),
+                "元数据以 IPLD 节点发布时的编码，为空表示普通 JSON 文件",
+            )),
+            optional(field(
+                "filecoin",
+                FieldType::Map(&FieldType::Ref("FilecoinArchive")),
+                "用途 (image、metadata) -> Filecoin 归档和交易",
+            )),
+            optional(field(
+                "remote_pins",
+                FieldType::Map(&FieldType::Ref("RemotePinRecord")),
+                "Pinning 服务名 -> pin-remote 的结果",
+            )),
+        ]),
+    },
+];
+
+// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    JsonSchema,
+    TypeScript,
+    Python,
+}
+
+impl SchemaFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JsonSchema => "jsonschema",
+            Self::TypeScript => "typescript",
+            Self::Python => "python",
+        }
+    }
+}
+
+impl fmt::Display for SchemaFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SchemaFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonschema" | "json-schema" | "json" => Ok(Self::JsonSchema),
+            "typescript" | "ts" => Ok(Self::TypeScript),
+            "python" | "py" => Ok(Self::Python),
+            other => Err(anyhow!(
+                "❌ 不支持的 schema 格式: {} (可选: jsonschema, typescript, python)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ 生成指定格式的结构定义；root 和 strict 只影响 JSON Schema，
+// strict 时所有结构不允许未知字段、ipfs:// 链接检查前缀 (与 parse_metadata 的严格模式一致)
+pub fn export_schema(format: SchemaFormat, root: SchemaRoot, strict: bool) -> String {
+    match format {
+        SchemaFormat::JsonSchema => json_schema(root, strict),
+        SchemaFormat::TypeScript => typescript(),
+        SchemaFormat::Python => python(),
+    }
+}
+
+const GENERATED_NOTICE: &str = "由 `cargo run -- schema export` 生成，请勿手动修改";
+
+fn json_type(ty: &FieldType, strict: bool) -> Value {
+    match ty {
+        FieldType::String => json!({ "type": "string" }),
+        FieldType::IpfsUri if strict => json!({ "type": "string", "pattern": "^ipfs://" }),
+        FieldType::IpfsUri => json!({ "type": "string" }),
+        FieldType::Integer => json!({ "type": "integer", "minimum": 0 }),
+        FieldType::Any => json!({}),
+        FieldType::Ref(name) => json!({ "$ref": format!("#/$defs/{}", name) }),
+        FieldType::Array(item) => json!({ "type": "array", "items": json_type(item, strict) }),
+        FieldType::Map(value) => {
+            json!({ "type": "object", "additionalProperties": json_type(value, strict) })
+        }
+    }
+}
+
+fn json_schema(root: SchemaRoot, strict: bool) -> String {
+    let mut defs = Map::new();
+    for spec in TYPES {
+        let def = match spec.body {
+            TypeBody::Enum(values) => json!({
+                "description": spec.description,
+                "type": "string",
+                "enum": values,
+            }),
+            TypeBody::Struct(fields) => {
+                let mut properties = Map::new();
+                for field in fields {
+                    let mut schema = json_type(&field.ty, strict);
+                    if field.nullable {
+                        schema = json!({ "anyOf": [schema, { "type": "null" }] });
+                    }
+                    schema["description"] = json!(field.description);
+                    properties.insert(field.name.to_string(), schema);
+                }
+                let required: Vec<&str> = fields
+                    .iter()
+                    .filter(|field| !field.optional)
+                    .map(|field| field.name)
+                    .collect();
+                let mut def = json!({
+                    "description": spec.description,
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                });
+                if strict {
+                    def["additionalProperties"] = json!(false);
+                }
+                def
+            }
+        };
+        defs.insert(spec.name.to_string(), def);
+    }
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": root.id(),
+        "$comment": GENERATED_NOTICE,
+        "$ref": format!("#/$defs/{}", root.type_name()),
+        "$defs": defs,
+    });
+    serde_json::to_string_pretty(&schema).unwrap_or_default() + "\n"
+}
+
+fn ts_type(ty: &FieldType) -> String {
+    match ty {
+        FieldType::String | FieldType::IpfsUri => "string".to_string(),
+        FieldType::Integer => "number".to_string(),
+        FieldType::Any => "unknown".to_string(),
+        FieldType::Ref(name) => name.to_string(),
+        FieldType::Array(item) => format!("{}[]", ts_type(item)),
+        FieldType::Map(value) => format!("Record<string, {}>", ts_type(value)),
+    }
+}
+
+fn typescript() -> String {
+    let mut out = format!("// {}\n", GENERATED_NOTICE);
+    for spec in TYPES {
+        let _ = writeln!(out, "\n/** {} */", spec.description);
+        match spec.body {
+            TypeBody::Enum(values) => {
+                let variants: Vec<String> = values.iter().map(|v| format!("\"{}\"", v)).collect();
+                let _ = writeln!(out, "export type {} = {};", spec.name, variants.join(" | "));
+            }
+            TypeBody::Struct(fields) => {
+                let _ = writeln!(out, "export interface {} {{", spec.name);
+                for field in fields {
+                    let mut ty = ts_type(&field.ty);
+                    if field.nullable {
+                        ty.push_str(" | null");
+                    }
+                    let _ = writeln!(out, "  /** {} */", field.description);
+                    let _ = writeln!(
+                        out,
+                        "  {}{}: {};",
+                        field.name,
+                        if field.optional { "?" } else { "" },
+                        ty
+                    );
+                }
+                out.push_str("}\n");
+            }
+        }
+    }
+    out
+}
+
+fn py_type(ty: &FieldType) -> String {
+    match ty {
+        FieldType::String | FieldType::IpfsUri => "str".to_string(),
+        FieldType::Integer => "int".to_string(),
+        FieldType::Any => "Any".to_string(),
+        FieldType::Ref(name) => name.to_string(),
+        FieldType::Array(item) => format!("list[{}]", py_type(item)),
+        FieldType::Map(value) => format!("dict[str, {}]", py_type(value)),
+    }
+}
+
+fn python() -> String {
+    let mut out = format!("# {}\n", GENERATED_NOTICE);
+    out.push_str("from typing import Any, Literal, NotRequired, TypedDict\n");
+    for spec in TYPES {
+        match spec.body {
+            TypeBody::Enum(values) => {
+                let variants: Vec<String> = values.iter().map(|v| format!("\"{}\"", v)).collect();
+                let _ = writeln!(out, "\n# {}", spec.description);
+                let _ = writeln!(out, "{} = Literal[{}]", spec.name, variants.join(", "));
+            }
+            TypeBody::Struct(fields) => {
+                let _ = writeln!(out, "\n\nclass {}(TypedDict):", spec.name);
+                let _ = writeln!(out, "    \"\"\"{}\"\"\"\n", spec.description);
+                for field in fields {
+                    let mut ty = py_type(&field.ty);
+                    if field.nullable {
+                        ty.push_str(" | None");
+                    }
+                    if field.optional {
+                        ty = format!("NotRequired[{}]", ty);
+                    }
+                    let _ = writeln!(out, "    # {}", field.description);
+                    let _ = writeln!(out, "    {}: {}", field.name, ty);
+                }
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,72 @@
+use std::env;
+
+use anyhow::{Result, anyhow};
+use keyring::Entry;
+
+// 系统钥匙串中的服务名，凭据按提供方名称 (如 pinata) 分别保存
+pub const KEYRING_SERVICE: &str = "polyglot-ipfs-uploader";
+
+fn entry(provider: &str) -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, provider)
+        .map_err(|e| anyhow!("❌ 无法访问系统钥匙串 ({}): {}", provider, e))
+}
+
+// ✅ 保存到系统钥匙串 (macOS Keychain、Windows 凭据管理器、Linux Secret Service)
+pub fn store_credential(provider: &str, secret: &str) -> Result<()> {
+    entry(provider)?
+        .set_password(secret)
+        .map_err(|e| anyhow!("❌ 保存 {} 的凭据失败: {}", provider, e))
+}
+
+// 钥匙串中没有该提供方时返回 None
+pub fn load_credential(provider: &str) -> Result<Option<String>> {
+    match entry(provider)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow!("❌ 读取 {} 的凭据失败: {}", provider, e)),
+    }
+}
+
+pub fn delete_credential(provider: &str) -> Result<bool> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(anyhow!("❌ 删除 {} 的凭据失败: {}", provider, e)),
+    }
+}
+
+// 解析一个 `${...}` 引用：`keyring:<提供方>` 从钥匙串读取，其它按环境变量 (包括 .env) 读取
+fn resolve_reference(reference: &str) -> Result<String> {
+    if let Some(provider) = reference.strip_prefix("keyring:") {
+        return load_credential(provider)?.ok_or_else(|| {
+            anyhow!(
+                "❌ 钥匙串中没有 {} 的凭据，请先运行 `credentials set {}`",
+                provider,
+                provider
+            )
+        });
+    }
+    env::var(reference).map_err(|_| anyhow!("❌ 环境变量 {} 未设置", reference))
+}
+
+// ✅ 替换字符串中的 `${VAR}` 和 `${keyring:<提供方>}`，`$${` 表示字面的 `${`
+pub fn interpolate(value: &str) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("❌ 未闭合的 ${{...}}: {}", value))?;
+        out.push_str(&resolve_reference(&rest[start + 2..start + end])?);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
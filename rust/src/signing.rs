@@ -0,0 +1,269 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use ed25519_dalek::{Signer, Verifier};
+use k256::ecdsa::{RecoveryId, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{keccak256, parse_hex, to_hex_prefixed};
+use crate::deploy::collection_provenance;
+use crate::eth::{address_of, signing_key};
+use crate::exit_code::ExitCode;
+use crate::hashing::to_hex;
+use crate::manifest::{CollectionKind, Manifest};
+use crate::metadata::canonical_json;
+
+pub const SIGNATURE_FILE_NAME: &str = "manifest.sig.json";
+
+// ✅ 清单签名方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    // 32 字节种子 (十六进制)，签名者为公钥
+    #[default]
+    Ed25519,
+    // 以太坊私钥，EIP-191 personal_sign，签名者为地址
+    Ethereum,
+}
+
+impl SignatureScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519",
+            Self::Ethereum => "ethereum",
+        }
+    }
+}
+
+impl fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SignatureScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ed25519" => Ok(Self::Ed25519),
+            "ethereum" | "eth" | "eip191" => Ok(Self::Ethereum),
+            other => Err(anyhow!(
+                "❌ 不支持的签名方式: {} (可选: ed25519, ethereum)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [signing] 段
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SigningConfig {
+    pub scheme: SignatureScheme,
+    // 十六进制私钥，建议写成 "${MANIFEST_SIGNING_KEY}" 或 "${keyring:signing}"；
+    // ethereum 方式为空时使用 [contract] 的 private_key
+    pub key: String,
+    // 单件和批量流程完成后自动签名
+    pub auto: bool,
+}
+
+// ✅ 被签名的内容：集合的根 CID、来源证明哈希和时间
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignedPayload {
+    pub kind: CollectionKind,
+    pub image_cid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posters_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previews_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watermarked_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localization_cid: Option<String>,
+    pub metadata_cid: String,
+    pub token_uri: String,
+    pub token_count: usize,
+    pub provenance_hash: String,
+    pub created_at: String,
+    pub signed_at: String,
+}
+
+impl SignedPayload {
+    // dir 为清单所在的运行目录，用来重新计算来源证明哈希
+    pub fn from_manifest(manifest: &Manifest, dir: &Path) -> Result<Self> {
+        Ok(Self {
+            kind: manifest.kind,
+            image_cid: manifest.image_cid.clone(),
+            posters_cid: manifest.posters_cid.clone(),
+            previews_cid: manifest.previews_cid.clone(),
+            watermarked_cid: manifest.watermarked_cid.clone(),
+            localization_cid: manifest.localization_cid.clone(),
+            metadata_cid: manifest.metadata_cid.clone(),
+            token_uri: manifest.token_uri.clone(),
+            token_count: manifest.tokens.len(),
+            provenance_hash: collection_provenance(manifest, dir)?,
+            created_at: manifest.created_at.clone(),
+            signed_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    // 签名的原文：RFC 8785 格式的 JSON，其它语言可以逐字节重现
+    pub fn message(&self) -> Result<String> {
+        Ok(canonical_json(&serde_json::to_value(self)?))
+    }
+}
+
+// ✅ 写入运行目录的签名文件；第三方只需要这个文件就能验证签名者
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignatureFile {
+    pub scheme: SignatureScheme,
+    // ed25519 公钥 (十六进制) 或以太坊地址
+    pub signer: String,
+    // 被签名的原文 (SignedPayload 的 RFC 8785 JSON)
+    pub message: String,
+    // ed25519 为 64 字节；ethereum 为 65 字节 r‖s‖v (v 为 27/28)
+    pub signature: String,
+}
+
+impl SignatureFile {
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(SIGNATURE_FILE_NAME);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow!("❌ 写入 {:?} 失败: {}", path, e))?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?;
+        serde_json::from_str(&content).map_err(|e| anyhow!("❌ {:?} 格式错误: {}", path, e))
+    }
+
+    pub fn payload(&self) -> Result<SignedPayload> {
+        serde_json::from_str(&self.message).map_err(|e| anyhow!("❌ 签名原文格式错误: {}", e))
+    }
+}
+
+// EIP-191 personal_sign 的消息哈希
+fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+fn fixed_bytes<const N: usize>(hex: &str, what: &str) -> Result<[u8; N]> {
+    parse_hex(hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow!("❌ {} 应为 {} 字节的十六进制", what, N))
+}
+
+// ✅ 用私钥签名清单
+pub fn sign_manifest(
+    manifest: &Manifest,
+    dir: &Path,
+    scheme: SignatureScheme,
+    key: &str,
+) -> Result<SignatureFile> {
+    let message = SignedPayload::from_manifest(manifest, dir)?.message()?;
+    let (signer, signature) = match scheme {
+        SignatureScheme::Ed25519 => {
+            let key = ed25519_dalek::SigningKey::from_bytes(&fixed_bytes(key, "ed25519 私钥")?);
+            let signature = key.sign(message.as_bytes());
+            (
+                to_hex(key.verifying_key().as_bytes()),
+                to_hex(&signature.to_bytes()),
+            )
+        }
+        SignatureScheme::Ethereum => {
+            let key = signing_key(key)?;
+            let (signature, recovery) = key
+                .sign_prehash_recoverable(&eip191_hash(message.as_bytes()))
+                .map_err(|e| anyhow!("❌ 签名失败: {}", e))?;
+            let mut bytes = signature.to_bytes().to_vec();
+            bytes.push(27 + recovery.to_byte());
+            (address_of(key.verifying_key()), to_hex_prefixed(&bytes))
+        }
+    };
+    Ok(SignatureFile {
+        scheme,
+        signer,
+        message,
+        signature,
+    })
+}
+
+// ✅ 验证签名与原文、签名者一致
+pub fn verify_signature(file: &SignatureFile) -> Result<()> {
+    let message = file.message.as_bytes();
+    match file.scheme {
+        SignatureScheme::Ed25519 => {
+            let key = ed25519_dalek::VerifyingKey::from_bytes(&fixed_bytes(
+                &file.signer,
+                "ed25519 公钥",
+            )?)
+            .map_err(|e| anyhow!("❌ ed25519 公钥无效: {}", e))?;
+            let signature =
+                ed25519_dalek::Signature::from_bytes(&fixed_bytes(&file.signature, "签名")?);
+            key.verify(message, &signature).map_err(|_| {
+                ExitCode::Mismatch.wrap(anyhow!(
+                    "❌ 签名无效：原文或签名被改动过，或不是该公钥签署的"
+                ))
+            })
+        }
+        SignatureScheme::Ethereum => {
+            let bytes: [u8; 65] = fixed_bytes(&file.signature, "签名")?;
+            let v = bytes[64];
+            let recovery = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v })
+                .ok_or_else(|| anyhow!("❌ 签名的 v 值无效: {}", v))?;
+            let signature = k256::ecdsa::Signature::from_slice(&bytes[..64])
+                .map_err(|e| anyhow!("❌ 签名格式错误: {}", e))?;
+            let key =
+                VerifyingKey::recover_from_prehash(&eip191_hash(message), &signature, recovery)
+                    .map_err(|_| anyhow!("❌ 无法从签名恢复公钥"))?;
+            let recovered = address_of(&key);
+            if !recovered.eq_ignore_ascii_case(&file.signer) {
+                return Err(ExitCode::Mismatch.wrap(anyhow!(
+                    "❌ 签名无效：签名者是 {}，而不是 {}",
+                    recovered,
+                    file.signer
+                )));
+            }
+            Ok(())
+        }
+    }
+}
+
+// ✅ 签名原文与运行目录中的清单、图片是否一致，返回不一致的字段
+pub fn compare_with_manifest(
+    payload: &SignedPayload,
+    manifest: &Manifest,
+    dir: &Path,
+) -> Result<Vec<String>> {
+    let current = SignedPayload {
+        signed_at: payload.signed_at.clone(),
+        ..SignedPayload::from_manifest(manifest, dir)?
+    };
+    let (signed, current) = (
+        serde_json::to_value(payload)?,
+        serde_json::to_value(&current)?,
+    );
+    let mut fields: Vec<&String> = signed
+        .as_object()
+        .into_iter()
+        .chain(current.as_object())
+        .flat_map(|object| object.keys())
+        .collect();
+    fields.sort();
+    fields.dedup();
+    Ok(fields
+        .into_iter()
+        .filter(|field| signed.get(field.as_str()) != current.get(field.as_str()))
+        .cloned()
+        .collect())
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+
+// ✅ 先写入同一父目录下的隐藏临时目录，成功后整体重命名为目标目录：
+// 中途失败或被中断时不会留下写了一半的输出，已有的输出也不会被覆盖 (除非 --force)
+#[derive(Debug)]
+pub struct StagedDir {
+    target: PathBuf,
+    staging: PathBuf,
+    force: bool,
+    committed: bool,
+}
+
+impl StagedDir {
+    pub fn create(target: &Path, force: bool) -> Result<Self> {
+        if target.exists() && !force {
+            return Err(anyhow!(
+                "❌ 输出目录已存在: {:?}，为避免覆盖之前的结果已停止 (确认要覆盖请使用 --force)",
+                target
+            ));
+        }
+        let name = target
+            .file_name()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("❌ 无效的输出目录: {:?}", target))?;
+        let parent = target.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(parent)
+            .map_err(|e| anyhow!("❌ 创建输出目录 {:?} 失败: {}", parent, e))?;
+        let staging = parent.join(format!(".{}.partial-{}", name, std::process::id()));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir(&staging)
+            .map_err(|e| anyhow!("❌ 创建临时目录 {:?} 失败: {}", staging, e))?;
+        Ok(Self {
+            target: target.to_path_buf(),
+            staging,
+            force,
+            committed: false,
+        })
+    }
+
+    // ✅ 直接写入目标目录：[add] nocopy 时节点记录的是文件路径，上传后不能再改名；
+    // 出错时同样删除写了一半的目录，--force 时旧目录在开始前就被删除
+    pub fn in_place(target: &Path, force: bool) -> Result<Self> {
+        if target.exists() {
+            if !force {
+                return Err(anyhow!(
+                    "❌ 输出目录已存在: {:?}，为避免覆盖之前的结果已停止 (确认要覆盖请使用 --force)",
+                    target
+                ));
+            }
+            fs::remove_dir_all(target)
+                .map_err(|e| anyhow!("❌ 删除旧的输出目录 {:?} 失败: {}", target, e))?;
+        }
+        fs::create_dir_all(target)
+            .map_err(|e| anyhow!("❌ 创建输出目录 {:?} 失败: {}", target, e))?;
+        Ok(Self {
+            target: target.to_path_buf(),
+            staging: target.to_path_buf(),
+            force,
+            committed: false,
+        })
+    }
+
+    // 写入时使用的临时目录
+    pub fn path(&self) -> &Path {
+        &self.staging
+    }
+
+    // 成功后的最终目录 (写入清单)
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    // ✅ 把临时目录重命名为目标目录；--force 时先把旧目录移开，替换成功后再删除
+    pub fn commit(mut self) -> Result<PathBuf> {
+        if self.staging == self.target {
+            self.committed = true;
+            return Ok(self.target.clone());
+        }
+        let mut replaced = None;
+        if self.target.exists() {
+            if !self.force {
+                // 保留已经写好的结果
+                self.committed = true;
+                return Err(anyhow!(
+                    "❌ 输出目录 {:?} 在运行期间被创建，结果保留在 {:?}",
+                    self.target,
+                    self.staging
+                ));
+            }
+            let old = self.staging.with_extension("old");
+            fs::rename(&self.target, &old)
+                .map_err(|e| anyhow!("❌ 移开旧的输出目录 {:?} 失败: {}", self.target, e))?;
+            replaced = Some(old);
+        }
+        if let Err(e) = fs::rename(&self.staging, &self.target) {
+            if let Some(old) = &replaced {
+                let _ = fs::rename(old, &self.target);
+            }
+            return Err(anyhow!(
+                "❌ 重命名 {:?} 为 {:?} 失败: {}",
+                self.staging,
+                self.target,
+                e
+            ));
+        }
+        self.committed = true;
+        if let Some(old) = replaced {
+            fs::remove_dir_all(&old)
+                .map_err(|e| anyhow!("❌ 删除旧的输出目录 {:?} 失败: {}", old, e))?;
+        }
+        Ok(self.target.clone())
+    }
+}
+
+// 没有提交 (出错返回) 时删除临时目录
+impl Drop for StagedDir {
+    fn drop(&mut self) {
+        if !self.committed && self.staging.exists() {
+            let _ = fs::remove_dir_all(&self.staging);
+        }
+    }
+}
@@ -0,0 +1,601 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::metadata::{Attribute, CollectionConfig, ExtraFields, JsonStyle, NftMetadata};
+
+// ✅ 生成元数据使用的标准，默认是 ERC-721 / OpenSea 的结构
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataStandard {
+    #[default]
+    Erc721,
+    Metaplex,
+    // Tezos
+    Tzip21,
+    // Cardano
+    Cip25,
+}
+
+impl MetadataStandard {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Erc721 => "erc721",
+            Self::Metaplex => "metaplex",
+            Self::Tzip21 => "tzip21",
+            Self::Cip25 => "cip25",
+        }
+    }
+}
+
+impl fmt::Display for MetadataStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MetadataStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "erc721" | "erc-721" | "opensea" => Ok(Self::Erc721),
+            "metaplex" | "solana" => Ok(Self::Metaplex),
+            "tzip21" | "tzip-21" | "tezos" => Ok(Self::Tzip21),
+            "cip25" | "cip-25" | "cardano" => Ok(Self::Cip25),
+            other => Err(format!(
+                "❌ 不支持的元数据标准: {} (可选: erc721, metaplex, tzip21, cip25)",
+                other
+            )),
+        }
+    }
+}
+
+// Metaplex 的创作者和版税分成
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Creator {
+    pub address: String,
+    // 百分比，所有创作者合计 100
+    pub share: u8,
+}
+
+// ✅ config.toml 中的 [metaplex] 段
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct MetaplexConfig {
+    // 集合的代号，最多 10 个字符
+    pub symbol: String,
+    // 二级市场版税，500 表示 5%
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+    pub external_url: String,
+    // 填写后 image 和 properties.files 使用网关地址，部分 Solana 钱包不识别 ipfs://
+    pub gateway: String,
+}
+
+// Metaplex 的链上字段长度限制
+const METAPLEX_MAX_SYMBOL_LEN: usize = 10;
+const METAPLEX_MAX_NAME_LEN: usize = 32;
+
+impl MetaplexConfig {
+    // 返回配置中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.symbol.len() > METAPLEX_MAX_SYMBOL_LEN {
+            problems.push(format!(
+                "symbol 最多 {} 个字符: {}",
+                METAPLEX_MAX_SYMBOL_LEN, self.symbol
+            ));
+        }
+        if self.seller_fee_basis_points > 10_000 {
+            problems.push(format!(
+                "seller_fee_basis_points 不能超过 10000: {}",
+                self.seller_fee_basis_points
+            ));
+        }
+        let total: u32 = self.creators.iter().map(|c| c.share as u32).sum();
+        if !self.creators.is_empty() && total != 100 {
+            problems.push(format!("creators 的 share 合计应为 100，当前为 {}", total));
+        }
+        if self.creators.iter().any(|c| c.address.is_empty()) {
+            problems.push("creators 中有空的 address".to_string());
+        }
+        problems
+    }
+}
+
+// ✅ config.toml 中的 [tzip21] 段 (Tezos)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Tzip21Config {
+    pub symbol: String,
+    // 创作者的 tz 地址
+    pub creators: Vec<String>,
+    pub tags: Vec<String>,
+    // 版权声明，如 "CC-BY-4.0"
+    pub rights: String,
+}
+
+// ✅ config.toml 中的 [cip25] 段 (Cardano)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Cip25Config {
+    // 铸造策略 ID (56 位十六进制)
+    pub policy_id: String,
+    // 资产名前缀，资产名为前缀 + token ID；不填时使用去掉空格和符号的名称
+    pub asset_name_prefix: String,
+}
+
+impl Cip25Config {
+    pub fn problems(&self) -> Vec<String> {
+        let valid =
+            self.policy_id.len() == 56 && self.policy_id.chars().all(|c| c.is_ascii_hexdigit());
+        if valid {
+            Vec::new()
+        } else {
+            vec![format!(
+                "policy_id 应为 56 位十六进制: \"{}\"",
+                self.policy_id
+            )]
+        }
+    }
+}
+
+// ✅ 选择的标准和各标准的配置
+#[derive(Debug, Clone, Default)]
+pub struct MetadataFormat {
+    pub standard: MetadataStandard,
+    pub metaplex: MetaplexConfig,
+    pub tzip21: Tzip21Config,
+    pub cip25: Cip25Config,
+    // 元数据文件和单独上传的 JSON 的写法
+    pub json_style: JsonStyle,
+    // 写入每个 token 元数据的自定义字段 ([extra_fields])
+    pub extra_fields: ExtraFields,
+    // 集合名称和描述模板 ([collection])
+    pub collection: CollectionConfig,
+}
+
+// 常见媒体文件的 MIME 类型，按扩展名判断
+pub fn mime_type_from_name(file_name: &str) -> &'static str {
+    let ext = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "avif" => "image/avif",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "glb" => "model/gltf-binary",
+        "gltf" => "model/gltf+json",
+        "html" | "htm" => "text/html",
+        _ => "application/octet-stream",
+    }
+}
+
+// 判断文件类型时读取的文件头长度
+const MIME_SNIFF_BYTES: u64 = 8192;
+
+// ✅ 按文件内容 (magic bytes) 判断 MIME 类型；SVG、glTF、HTML 等文本格式没有特征字节，按扩展名判断
+pub fn detect_mime_type(data: &[u8], file_name: &str) -> String {
+    infer::get(data)
+        .map(|kind| kind.mime_type().to_string())
+        .unwrap_or_else(|| mime_type_from_name(file_name).to_string())
+}
+
+// 读取文件头判断 MIME 类型
+pub fn detect_file_mime_type(path: &Path) -> io::Result<String> {
+    let mut head = Vec::new();
+    File::open(path)?
+        .take(MIME_SNIFF_BYTES)
+        .read_to_end(&mut head)?;
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    Ok(detect_mime_type(&head, file_name))
+}
+
+// image 是预览图时，ERC-721 元数据中记录原图链接的属性名
+pub const ORIGINAL_IMAGE_TRAIT: &str = "原图";
+
+// ✅ 视频、音频、3D 模型和 HTML 放在 animation_url，image 使用封面图
+pub fn is_animation_type(mime: &str) -> bool {
+    matches!(
+        mime.split('/').next().unwrap_or_default(),
+        "video" | "audio" | "model"
+    ) || mime == "text/html"
+}
+
+// 元数据引用的文件：token 的主文件，以及 image 改为指向的封面图或预览图
+#[derive(Debug, Clone, Default)]
+pub struct MediaInfo {
+    pub file_name: String,
+    pub mime_type: String,
+    // image 指向封面图或预览图时该文件的 MIME 类型，image 就是主文件时为 None
+    pub display_mime_type: Option<String>,
+    // image 是预览图时原图的链接
+    pub original_uri: Option<String>,
+}
+
+impl MediaInfo {
+    // image 字段对应文件的类型
+    pub fn image_mime_type(&self) -> &str {
+        self.display_mime_type.as_deref().unwrap_or(&self.mime_type)
+    }
+
+    // 主文件的地址：有封面图时是 animation_url，有预览图时是原图，否则是 image
+    fn artifact_uri(&self, metadata: &NftMetadata) -> String {
+        metadata
+            .animation_url
+            .clone()
+            .or_else(|| self.original_uri.clone())
+            .unwrap_or_else(|| metadata.image.clone())
+    }
+}
+
+// Metaplex properties.category
+fn metaplex_category(mime: &str) -> &'static str {
+    match mime.split('/').next().unwrap_or_default() {
+        "video" => "video",
+        "audio" => "audio",
+        "model" => "vr",
+        "text" => "html",
+        _ => "image",
+    }
+}
+
+fn gateway_uri(uri: &str, gateway: &str) -> String {
+    match uri.strip_prefix("ipfs://") {
+        Some(path) if !gateway.is_empty() => {
+            format!("{}/ipfs/{}", gateway.trim_end_matches('/'), path)
+        }
+        _ => uri.to_string(),
+    }
+}
+
+// Metaplex 的 properties.files 中的一项
+#[derive(Serialize, Debug, Clone)]
+pub struct MetaplexFile {
+    pub uri: String,
+    #[serde(rename = "type")]
+    pub mime_type: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MetaplexProperties {
+    pub files: Vec<MetaplexFile>,
+    pub category: String,
+    pub creators: Vec<Creator>,
+}
+
+// ✅ Metaplex Token Metadata 标准的链下 JSON
+#[derive(Serialize, Debug, Clone)]
+pub struct MetaplexMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub seller_fee_basis_points: u16,
+    pub image: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub external_url: String,
+    pub attributes: Vec<Attribute>,
+    pub properties: MetaplexProperties,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+const METAPLEX_FIELDS: [&str; 9] = [
+    "name",
+    "symbol",
+    "description",
+    "seller_fee_basis_points",
+    "image",
+    "animation_url",
+    "external_url",
+    "attributes",
+    "properties",
+];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Tzip21Format {
+    pub uri: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Tzip21Attribute {
+    pub name: String,
+    pub value: String,
+}
+
+// ✅ TZIP-21 (Tezos) 的 token 元数据
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tzip21Metadata {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub symbol: String,
+    pub decimals: u8,
+    pub is_boolean_amount: bool,
+    pub artifact_uri: String,
+    pub display_uri: String,
+    pub thumbnail_uri: String,
+    pub formats: Vec<Tzip21Format>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub creators: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub rights: String,
+    pub attributes: Vec<Tzip21Attribute>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+const TZIP21_FIELDS: [&str; 13] = [
+    "name",
+    "description",
+    "symbol",
+    "decimals",
+    "isBooleanAmount",
+    "artifactUri",
+    "displayUri",
+    "thumbnailUri",
+    "formats",
+    "creators",
+    "tags",
+    "rights",
+    "attributes",
+];
+
+// 自定义字段中与标准本身的字段重名的不写入
+fn custom_fields(metadata: &NftMetadata, reserved: &[&str]) -> serde_json::Map<String, Value> {
+    metadata
+        .extra
+        .iter()
+        .filter(|(key, _)| !reserved.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+// 按标准生成的元数据，序列化时保持各标准的字段顺序
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum MetadataDocument {
+    Erc721(NftMetadata),
+    Metaplex(MetaplexMetadata),
+    Tzip21(Tzip21Metadata),
+    // CIP-25 的键是策略 ID 和资产名，没有固定结构
+    Cip25(Value),
+}
+
+fn attribute_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// CIP-25 v1 的字符串最长 64 字节，更长的拆成字符串数组
+const CIP25_MAX_STRING_BYTES: usize = 64;
+
+fn cip25_string(s: &str) -> Value {
+    if s.len() <= CIP25_MAX_STRING_BYTES {
+        return Value::String(s.to_string());
+    }
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for c in s.chars() {
+        if current.len() + c.len_utf8() > CIP25_MAX_STRING_BYTES {
+            chunks.push(Value::String(std::mem::take(&mut current)));
+        }
+        current.push(c);
+    }
+    chunks.push(Value::String(current));
+    Value::Array(chunks)
+}
+
+impl MetadataFormat {
+    // ✅ 按选择的标准生成要写入文件和上传的 JSON
+    pub fn render(
+        &self,
+        metadata: &NftMetadata,
+        media: &MediaInfo,
+        token_id: Option<u64>,
+    ) -> MetadataDocument {
+        let metadata = &self.extra_fields.apply(metadata, token_id);
+        match self.standard {
+            MetadataStandard::Erc721 => {
+                let mut metadata = metadata.clone();
+                // ERC-721 没有文件列表，原图链接放在属性中
+                if let Some(uri) = &media.original_uri {
+                    metadata.attributes.push(Attribute {
+                        trait_type: ORIGINAL_IMAGE_TRAIT.to_string(),
+                        value: Value::String(uri.clone()),
+                    });
+                }
+                MetadataDocument::Erc721(metadata)
+            }
+            MetadataStandard::Metaplex => {
+                MetadataDocument::Metaplex(self.render_metaplex(metadata, media))
+            }
+            MetadataStandard::Tzip21 => {
+                MetadataDocument::Tzip21(self.render_tzip21(metadata, media))
+            }
+            MetadataStandard::Cip25 => {
+                MetadataDocument::Cip25(self.render_cip25(metadata, media, token_id))
+            }
+        }
+    }
+
+    fn render_tzip21(&self, metadata: &NftMetadata, media: &MediaInfo) -> Tzip21Metadata {
+        let config = &self.tzip21;
+        let artifact = media.artifact_uri(metadata);
+        let mut formats = vec![Tzip21Format {
+            uri: artifact.clone(),
+            mime_type: media.mime_type.clone(),
+        }];
+        if let Some(display_mime) = &media.display_mime_type {
+            formats.push(Tzip21Format {
+                uri: metadata.image.clone(),
+                mime_type: display_mime.clone(),
+            });
+        }
+        Tzip21Metadata {
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            symbol: config.symbol.clone(),
+            decimals: 0,
+            is_boolean_amount: true,
+            artifact_uri: artifact,
+            display_uri: metadata.image.clone(),
+            thumbnail_uri: metadata.image.clone(),
+            formats,
+            creators: config.creators.clone(),
+            tags: config.tags.clone(),
+            rights: config.rights.clone(),
+            attributes: metadata
+                .attributes
+                .iter()
+                .map(|attribute| Tzip21Attribute {
+                    name: attribute.trait_type.clone(),
+                    value: attribute_text(&attribute.value),
+                })
+                .collect(),
+            extra: custom_fields(metadata, &TZIP21_FIELDS),
+        }
+    }
+
+    // CIP-25 资产名：前缀 + token ID，或去掉空格和符号的名称
+    pub fn cip25_asset_name(&self, name: &str, token_id: Option<u64>) -> String {
+        match token_id {
+            Some(id) if !self.cip25.asset_name_prefix.is_empty() => {
+                format!("{}{}", self.cip25.asset_name_prefix, id)
+            }
+            _ => name.chars().filter(|c| c.is_ascii_alphanumeric()).collect(),
+        }
+    }
+
+    fn render_cip25(
+        &self,
+        metadata: &NftMetadata,
+        media: &MediaInfo,
+        token_id: Option<u64>,
+    ) -> Value {
+        let attributes: serde_json::Map<String, Value> = metadata
+            .attributes
+            .iter()
+            .map(|a| {
+                (
+                    a.trait_type.clone(),
+                    cip25_string(&attribute_text(&a.value)),
+                )
+            })
+            .collect();
+        let mut asset = json!({
+            "name": cip25_string(&metadata.name),
+            "image": cip25_string(&metadata.image),
+            "mediaType": media.image_mime_type(),
+            "description": cip25_string(&metadata.description),
+            "files": [{
+                "name": cip25_string(&media.file_name),
+                "mediaType": media.mime_type,
+                "src": cip25_string(&media.artifact_uri(metadata)),
+            }],
+            "attributes": attributes,
+        });
+        if let Some(asset) = asset.as_object_mut() {
+            for (key, value) in &metadata.extra {
+                let value = match value {
+                    Value::String(s) => cip25_string(s),
+                    other => other.clone(),
+                };
+                asset.entry(key.clone()).or_insert(value);
+            }
+        }
+        let asset_name = self.cip25_asset_name(&metadata.name, token_id);
+        json!({
+            "721": {
+                &self.cip25.policy_id: { asset_name: asset },
+                "version": "1.0",
+            }
+        })
+    }
+
+    fn render_metaplex(&self, metadata: &NftMetadata, media: &MediaInfo) -> MetaplexMetadata {
+        let config = &self.metaplex;
+        let image = gateway_uri(&metadata.image, &config.gateway);
+        let animation_url = metadata
+            .animation_url
+            .as_deref()
+            .map(|uri| gateway_uri(uri, &config.gateway));
+        let mut files = vec![MetaplexFile {
+            uri: image.clone(),
+            mime_type: media.image_mime_type().to_string(),
+        }];
+        let original = media
+            .original_uri
+            .as_deref()
+            .map(|uri| gateway_uri(uri, &config.gateway));
+        if let Some(uri) = animation_url.as_ref().or(original.as_ref()) {
+            files.push(MetaplexFile {
+                uri: uri.clone(),
+                mime_type: media.mime_type.clone(),
+            });
+        }
+        MetaplexMetadata {
+            name: metadata.name.clone(),
+            symbol: config.symbol.clone(),
+            description: metadata.description.clone(),
+            seller_fee_basis_points: config.seller_fee_basis_points,
+            image,
+            animation_url,
+            external_url: config.external_url.clone(),
+            attributes: metadata.attributes.clone(),
+            properties: MetaplexProperties {
+                files,
+                category: metaplex_category(&media.mime_type).to_string(),
+                creators: config.creators.clone(),
+            },
+            extra: custom_fields(metadata, &METAPLEX_FIELDS),
+        }
+    }
+
+    // 生成前检查配置，返回问题说明
+    pub fn problems(&self) -> Vec<String> {
+        match self.standard {
+            MetadataStandard::Erc721 | MetadataStandard::Tzip21 => Vec::new(),
+            MetadataStandard::Metaplex => self.metaplex.problems(),
+            MetadataStandard::Cip25 => self.cip25.problems(),
+        }
+    }
+
+    // 名称超过标准限制时的提醒
+    pub fn name_warning(&self, name: &str) -> Option<String> {
+        (self.standard == MetadataStandard::Metaplex && name.len() > METAPLEX_MAX_NAME_LEN).then(
+            || {
+                format!(
+                    "名称 \"{}\" 超过 Metaplex 链上限制的 {} 字节，mint 时会失败",
+                    name, METAPLEX_MAX_NAME_LEN
+                )
+            },
+        )
+    }
+}
@@ -0,0 +1,501 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::car::{CarSummary, write_car, write_car_bytes};
+use crate::catalog::Catalog;
+use crate::cid::same_content;
+use crate::exit_code::ExitCode;
+use crate::filecoin::{DealInfo, DealService, DealState, FilecoinArchive, FilecoinConfig};
+use crate::ipld::link;
+use crate::manifest::{CollectionKind, Manifest};
+use crate::patch::{metadata_path, metadata_root_path};
+use crate::unixfs::ImportOptions;
+
+// 运行目录下存放 CAR 文件的子目录
+pub const CAR_DIR: &str = "car";
+
+// web3.storage / Storacha 的 GET /status/<cid>
+#[derive(Deserialize)]
+struct StorachaStatus {
+    #[serde(default)]
+    deals: Vec<StorachaDeal>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StorachaDeal {
+    #[serde(default)]
+    deal_id: Option<u64>,
+    #[serde(default)]
+    storage_provider: Option<String>,
+    status: String,
+    #[serde(default)]
+    piece_cid: Option<String>,
+    #[serde(default)]
+    status_text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StorachaUpload {
+    cid: String,
+}
+
+// Lotus JSON-RPC 的返回
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CidLink {
+    #[serde(rename = "/")]
+    cid: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImportResult {
+    root: CidLink,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LotusDealInfo {
+    state: u64,
+    #[serde(default)]
+    message: String,
+    #[serde(rename = "DealID", default)]
+    deal_id: u64,
+    #[serde(rename = "PieceCID", default)]
+    piece_cid: Option<CidLink>,
+}
+
+fn storacha_state(status: &str) -> DealState {
+    match status {
+        "Active" => DealState::Active,
+        "Published" => DealState::Published,
+        "Terminated" => DealState::Expired,
+        _ => DealState::Queued,
+    }
+}
+
+// Lotus (go-fil-markets) 的 StorageDeal* 状态码
+fn lotus_state(code: u64) -> DealState {
+    match code {
+        7 => DealState::Active,
+        8 => DealState::Expired,
+        1 | 2 | 9 | 10 | 11 | 26 => DealState::Failed,
+        // Sealing、Finalizing、AwaitingPreCommit：交易已上链
+        5 | 6 | 29 => DealState::Published,
+        _ => DealState::Queued,
+    }
+}
+
+// ✅ 向 Storacha 或 Lotus 提交存储交易并查询状态
+pub struct DealClient<'a> {
+    config: &'a FilecoinConfig,
+    client: Client,
+}
+
+impl<'a> DealClient<'a> {
+    pub fn new(config: &'a FilecoinConfig) -> Result<Self> {
+        // CAR 可能很大，上传不设总超时
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(None)
+            .build()?;
+        Ok(Self { config, client })
+    }
+
+    fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let request = match self.config.token.as_str() {
+            "" => request,
+            token => request.bearer_auth(token),
+        };
+        let response = request.send()?;
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            return Err(ExitCode::from_status(status.as_u16()).wrap(anyhow!(
+                "❌ {} 请求失败 ({}): {}",
+                self.config.service,
+                status,
+                body.trim()
+            )));
+        }
+        serde_json::from_str(&body)
+            .map_err(|e| anyhow!("❌ {} 返回无法解析: {}", self.config.service, e))
+    }
+
+    fn rpc<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: RpcResponse<T> =
+            self.send(self.client.post(self.config.endpoint()).json(&body))?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(anyhow!(
+                "❌ Lotus {} 失败 ({}): {}",
+                method,
+                error.code,
+                error.message
+            )),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(anyhow!("❌ Lotus {} 没有返回结果", method)),
+        }
+    }
+
+    // ✅ 提交 CAR；Storacha 之后由服务发起交易，Lotus 向每个存储提供方各发起一笔
+    pub fn submit(&self, car_path: &Path, cid: &str) -> Result<Vec<DealInfo>> {
+        match self.config.service {
+            DealService::Storacha => {
+                let url = format!("{}/car", self.config.endpoint());
+                let request = self
+                    .client
+                    .post(url)
+                    .header("Content-Type", "application/vnd.ipld.car")
+                    .body(File::open(car_path)?);
+                let upload: StorachaUpload = self.send(request)?;
+                if !same_content(&upload.cid, cid) {
+                    return Err(anyhow!(
+                        "❌ storacha 返回的根 CID {} 与清单中的 {} 不一致",
+                        upload.cid,
+                        cid
+                    ));
+                }
+                Ok(Vec::new())
+            }
+            DealService::Lotus => {
+                // Lotus 从本机路径读取 CAR，节点需要能访问运行目录
+                let path = fs::canonicalize(car_path)?;
+                let imported: ImportResult = self.rpc(
+                    "Filecoin.ClientImport",
+                    json!([{ "Path": path, "IsCAR": true }]),
+                )?;
+                if !same_content(&imported.root.cid, cid) {
+                    return Err(anyhow!(
+                        "❌ Lotus 导入的根 CID {} 与清单中的 {} 不一致",
+                        imported.root.cid,
+                        cid
+                    ));
+                }
+                Ok(self
+                    .config
+                    .miners
+                    .iter()
+                    .map(|miner| self.start_deal(cid, miner))
+                    .collect())
+            }
+        }
+    }
+
+    // 发起失败的交易也记录下来，查询状态时不会重试
+    fn start_deal(&self, cid: &str, miner: &str) -> DealInfo {
+        let params = json!([{
+            "Data": { "TransferType": "graphsync", "Root": link(cid) },
+            "Wallet": self.config.wallet,
+            "Miner": miner,
+            "EpochPrice": self.config.epoch_price,
+            "MinBlocksDuration": self.config.duration_epochs(),
+            "FastRetrieval": self.config.fast_retrieval,
+            "VerifiedDeal": self.config.verified,
+        }]);
+        let result: Result<CidLink> = self.rpc("Filecoin.ClientStartDeal", params);
+        let (proposal_cid, state, message) = match result {
+            Ok(proposal) => (Some(proposal.cid), DealState::Queued, None),
+            Err(e) => (None, DealState::Failed, Some(format!("{:#}", e))),
+        };
+        DealInfo {
+            storage_provider: miner.to_string(),
+            deal_id: None,
+            proposal_cid,
+            state,
+            message,
+        }
+    }
+
+    // ✅ 查询交易的最新状态
+    pub fn refresh(&self, archive: &mut FilecoinArchive) -> Result<()> {
+        match archive.service {
+            DealService::Storacha => {
+                let url = format!("{}/status/{}", self.config.endpoint(), archive.cid);
+                let status: StorachaStatus = self.send(self.client.get(url))?;
+                if let Some(piece_cid) = status.deals.iter().find_map(|d| d.piece_cid.clone()) {
+                    archive.piece_cid = Some(piece_cid);
+                }
+                archive.deals = status
+                    .deals
+                    .into_iter()
+                    .map(|deal| DealInfo {
+                        storage_provider: deal.storage_provider.unwrap_or_default(),
+                        deal_id: deal.deal_id,
+                        proposal_cid: None,
+                        state: storacha_state(&deal.status),
+                        message: deal.status_text,
+                    })
+                    .collect();
+            }
+            DealService::Lotus => {
+                for deal in &mut archive.deals {
+                    let Some(proposal) = deal.proposal_cid.as_deref() else {
+                        continue;
+                    };
+                    if deal.state.is_final() {
+                        continue;
+                    }
+                    let info: LotusDealInfo =
+                        self.rpc("Filecoin.ClientGetDealInfo", json!([link(proposal)]))?;
+                    deal.state = lotus_state(info.state);
+                    deal.deal_id = (info.deal_id > 0).then_some(info.deal_id);
+                    deal.message = (!info.message.is_empty()).then_some(info.message);
+                    if let Some(piece_cid) = info.piece_cid {
+                        archive.piece_cid = Some(piece_cid.cid);
+                    }
+                }
+            }
+        }
+        archive.updated_at = Utc::now().to_rfc3339();
+        Ok(())
+    }
+}
+
+// 要打包的本地内容
+enum ArchiveSource {
+    // 文件或文件夹，与上传时一样导入
+    Path(PathBuf),
+    // 单件的元数据：上传的是紧凑格式的 JSON，本地文件可能是便于阅读的格式
+    Json(PathBuf),
+}
+
+// 清单中要归档的根：用途、CID 和运行目录中对应的本地内容
+fn archive_roots(manifest: &Manifest, dir: &Path) -> Vec<(&'static str, String, ArchiveSource)> {
+    match (manifest.kind, manifest.tokens.first()) {
+        (CollectionKind::Single, Some(token)) => vec![
+            (
+                "image",
+                manifest.image_cid.clone(),
+                ArchiveSource::Path(dir.join(&token.image_file)),
+            ),
+            (
+                "metadata",
+                manifest.metadata_cid.clone(),
+                if token.metadata_file.contains('/') {
+                    ArchiveSource::Path(metadata_root_path(dir, manifest.kind, token))
+                } else {
+                    ArchiveSource::Json(metadata_path(dir, manifest.kind, token))
+                },
+            ),
+        ],
+        (CollectionKind::Single, None) => Vec::new(),
+        _ => vec![
+            (
+                "image",
+                manifest.image_cid.clone(),
+                ArchiveSource::Path(dir.join("images")),
+            ),
+            (
+                "metadata",
+                manifest.metadata_cid.clone(),
+                ArchiveSource::Path(dir.join("metadata")),
+            ),
+        ],
+    }
+}
+
+// 去掉字符串以外的空白：pretty 写法的元数据文件还原成上传时的紧凑格式，键的顺序不变
+fn compact_json(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let (mut in_string, mut escaped) = (false, false);
+    for c in text.chars() {
+        if in_string {
+            match (escaped, c) {
+                (true, _) => escaped = false,
+                (false, '\\') => escaped = true,
+                (false, '"') => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_whitespace() {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+// 打包成 CAR；JSON 先按紧凑格式 (pretty)、再按原样 (canonical) 尝试，直到根 CID 与清单一致
+fn pack(source: &ArchiveSource, cid: &str, car_path: &Path) -> Result<CarSummary> {
+    let options = ImportOptions::default();
+    let path = match source {
+        ArchiveSource::Path(path) => return write_car(path, car_path, &options),
+        ArchiveSource::Json(path) => path,
+    };
+    let text = fs::read_to_string(path)?;
+    let packed = write_car_bytes(compact_json(&text).as_bytes(), car_path, &options)?;
+    if same_content(&packed.root.to_string(), cid) {
+        return Ok(packed);
+    }
+    write_car_bytes(text.as_bytes(), car_path, &options)
+}
+
+// ✅ 把清单的图片和元数据打包成 CAR 并提交交易，记录到清单和 catalog.json；返回失败的数量
+// 已经提交过的 CID 直接沿用 catalog 中的记录，force 时重新提交
+pub fn submit_archives(
+    config: &FilecoinConfig,
+    manifest_path: &Path,
+    catalog: &mut Catalog,
+    force: bool,
+) -> Result<usize> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let client = DealClient::new(config)?;
+    println!(
+        "\n--- 正在提交 Filecoin 存储交易 ({}: {}) ---",
+        config.service,
+        config.endpoint()
+    );
+    let mut failed = 0;
+    for (role, cid, source) in archive_roots(&manifest, dir) {
+        if let Some(archive) = catalog.filecoin.get(&cid).filter(|_| !force) {
+            println!("✅ [{}] {} 已提交过 ({})", role, cid, archive.submitted_at);
+            manifest.filecoin.insert(role.to_string(), archive.clone());
+            continue;
+        }
+        let car_file = format!("{}/{}.car", CAR_DIR, role);
+        let car_path = dir.join(&car_file);
+        let summary = match pack(&source, &cid, &car_path) {
+            Ok(summary) => summary,
+            Err(e) => {
+                failed += 1;
+                eprintln!("❌ [{}] 打包失败: {:#}", role, e);
+                continue;
+            }
+        };
+        // 元数据以 IPLD 节点发布、使用了特殊导入参数等情况下，本地打包的 CID 会不同
+        if !same_content(&summary.root.to_string(), &cid) {
+            eprintln!(
+                "⚠️  [{}] 本地打包的 CID {} 与清单中的 {} 不一致，跳过",
+                role, summary.root, cid
+            );
+            let _ = fs::remove_file(&car_path);
+            continue;
+        }
+        match client.submit(&car_path, &cid) {
+            Ok(deals) => {
+                let now = Utc::now().to_rfc3339();
+                let archive = FilecoinArchive {
+                    cid: cid.clone(),
+                    service: config.service,
+                    car_file,
+                    car_bytes: fs::metadata(&car_path)?.len(),
+                    piece_cid: None,
+                    submitted_at: now.clone(),
+                    updated_at: now,
+                    deals,
+                };
+                println!(
+                    "🗄️  [{}] {} 已提交 ({} 字节，{} 笔交易)",
+                    role,
+                    cid,
+                    archive.car_bytes,
+                    archive.deals.len()
+                );
+                catalog.filecoin.insert(cid, archive.clone());
+                manifest.filecoin.insert(role.to_string(), archive);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("❌ [{}] {} 提交失败: {:#}", role, cid, e);
+            }
+        }
+    }
+    catalog.save()?;
+    manifest.save(dir)?;
+    Ok(failed)
+}
+
+// ✅ 刷新清单中所有归档的交易状态，同步写回清单和 catalog.json
+pub fn refresh_archives(
+    config: &FilecoinConfig,
+    manifest_path: &Path,
+    catalog: &mut Catalog,
+) -> Result<Manifest> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    if manifest.filecoin.is_empty() {
+        return Err(anyhow!(
+            "❌ 清单中没有 Filecoin 归档记录，请先运行 `filecoin submit`"
+        ));
+    }
+    let client = DealClient::new(config)?;
+    for (role, archive) in &mut manifest.filecoin {
+        if archive.service != config.service {
+            eprintln!(
+                "⚠️  [{}] 通过 {} 提交，当前配置为 {}，跳过",
+                role, archive.service, config.service
+            );
+            continue;
+        }
+        if !archive.is_pending() {
+            continue;
+        }
+        match client.refresh(archive) {
+            Ok(()) => {
+                catalog
+                    .filecoin
+                    .insert(archive.cid.clone(), archive.clone());
+            }
+            Err(e) => eprintln!("❌ [{}] 查询 {} 失败: {:#}", role, archive.cid, e),
+        }
+    }
+    catalog.save()?;
+    manifest.save(dir)?;
+    Ok(manifest)
+}
+
+// 打印清单中的归档和交易
+pub fn print_archives(manifest: &Manifest) {
+    for (role, archive) in &manifest.filecoin {
+        let mark = if archive.is_active() { "✅" } else { "⏳" };
+        println!(
+            "{} [{}] {} ({}，piece: {})",
+            mark,
+            role,
+            archive.cid,
+            archive.service,
+            archive.piece_cid.as_deref().unwrap_or("-")
+        );
+        if archive.deals.is_empty() {
+            println!("    等待服务发起交易");
+        }
+        for deal in &archive.deals {
+            let deal_id = deal
+                .deal_id
+                .map_or_else(|| "-".to_string(), |id| id.to_string());
+            println!(
+                "    {} 交易 {}: {}{}",
+                deal.storage_provider,
+                deal_id,
+                deal.state,
+                deal.message
+                    .as_deref()
+                    .map(|m| format!(" ({})", m))
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+// 每次最多读取这么多字节再计算限速，速率很低时也能保持平滑
+const MAX_READ: usize = 16 * 1024;
+
+// ✅ config.toml 中的 [throttle] 段：上传限速 (字节/秒)，不设置表示不限速
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct ThrottleConfig {
+    // 所有连接合计
+    pub bytes_per_sec: Option<u64>,
+    // 单个请求 (连接)
+    pub per_connection_bytes_per_sec: Option<u64>,
+}
+
+// 支持 "500K"、"2M"、"1.5M"、"1048576" (按 1000 进位)
+pub fn parse_rate(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let trimmed = s
+        .trim_end_matches("/s")
+        .trim_end_matches(['B', 'b'])
+        .trim_end();
+    let (number, multiplier) = match trimmed.chars().last() {
+        Some('K' | 'k') => (&trimmed[..trimmed.len() - 1], 1_000.0),
+        Some('M' | 'm') => (&trimmed[..trimmed.len() - 1], 1_000_000.0),
+        Some('G' | 'g') => (&trimmed[..trimmed.len() - 1], 1_000_000_000.0),
+        _ => (trimmed, 1.0),
+    };
+    let value: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("❌ 无效的速率: {} (例如 500K、2M)", s))?;
+    if value <= 0.0 {
+        return Err(anyhow!("❌ 速率必须大于 0: {}", s));
+    }
+    Ok((value * multiplier) as u64)
+}
+
+// 按固定速率放行字节；空闲期间不积累额度，避免恢复后出现突发
+#[derive(Debug)]
+struct RateLimiter {
+    bytes_per_sec: u64,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    // 记录已经发送的字节，必要时等待到速率允许的时间
+    fn consume(&self, bytes: usize) {
+        let wait = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let start = (*next).max(now);
+            *next = start + Duration::from_secs_f64(bytes as f64 / self.bytes_per_sec as f64);
+            *next - now
+        };
+        thread::sleep(wait);
+    }
+}
+
+// ✅ 上传限速：全局限速器在所有后端之间共享，单连接限速每个请求单独计算
+#[derive(Debug, Clone, Default)]
+pub struct Throttle {
+    global: Option<Arc<RateLimiter>>,
+    per_connection: Option<u64>,
+    // 已经发送的字节数，供 --tui 显示实时吞吐量
+    sent: Option<Arc<AtomicU64>>,
+}
+
+impl Throttle {
+    pub fn new(config: &ThrottleConfig) -> Self {
+        Self {
+            global: config
+                .bytes_per_sec
+                .filter(|rate| *rate > 0)
+                .map(|rate| Arc::new(RateLimiter::new(rate))),
+            per_connection: config.per_connection_bytes_per_sec.filter(|rate| *rate > 0),
+            sent: None,
+        }
+    }
+
+    pub fn with_counter(mut self, sent: Arc<AtomicU64>) -> Self {
+        self.sent = Some(sent);
+        self
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.global.is_none() && self.per_connection.is_none()
+    }
+
+    // 开始一个新的请求，同一个请求中的所有文件共用单连接的额度
+    pub fn connection(&self) -> ConnectionThrottle {
+        let mut limiters: Vec<Arc<RateLimiter>> = self.global.iter().cloned().collect();
+        if let Some(rate) = self.per_connection {
+            limiters.push(Arc::new(RateLimiter::new(rate)));
+        }
+        ConnectionThrottle {
+            limiters,
+            sent: self.sent.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionThrottle {
+    limiters: Vec<Arc<RateLimiter>>,
+    sent: Option<Arc<AtomicU64>>,
+}
+
+impl ConnectionThrottle {
+    // 不限速也不计数时，请求体可以直接使用文件
+    pub fn is_unlimited(&self) -> bool {
+        self.limiters.is_empty() && self.sent.is_none()
+    }
+
+    pub fn reader<R: Read>(&self, inner: R) -> ThrottledReader<R> {
+        ThrottledReader {
+            inner,
+            limiters: self.limiters.clone(),
+            sent: self.sent.clone(),
+        }
+    }
+}
+
+// 读取时按限速等待并计数，作为请求体使用
+pub struct ThrottledReader<R> {
+    inner: R,
+    limiters: Vec<Arc<RateLimiter>>,
+    sent: Option<Arc<AtomicU64>>,
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = if self.limiters.is_empty() {
+            self.inner.read(buf)?
+        } else {
+            let len = buf.len().min(MAX_READ);
+            let read = self.inner.read(&mut buf[..len])?;
+            for limiter in &self.limiters {
+                limiter.consume(read);
+            }
+            read
+        };
+        if let Some(sent) = &self.sent {
+            sent.fetch_add(read as u64, Ordering::Relaxed);
+        }
+        Ok(read)
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// 需要限制时间的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    // 建立到节点 / 网关的连接 (命令行后端为 `ipfs id`)
+    Connect,
+    // 上传 (ipfs add、S3 PUT)
+    Add,
+    // 固定和取消固定
+    Pin,
+    // 其它节点请求：版本、pin 状态、ls、cat 等
+    Request,
+    // 从公共网关读取
+    Gateway,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Connect => "connect",
+            Self::Add => "add",
+            Self::Pin => "pin",
+            Self::Request => "request",
+            Self::Gateway => "gateway",
+        }
+    }
+
+    // 未配置时的默认超时 (秒)
+    fn default_secs(&self) -> u64 {
+        match self {
+            Self::Connect => 10,
+            Self::Add => 3600,
+            Self::Pin => 600,
+            Self::Request => 120,
+            Self::Gateway => 30,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ✅ config.toml 中的 [timeouts] 段 (秒)，0 表示不限制；
+// [[api_endpoints]]、[cluster]、[filebase] 中的 timeouts 会覆盖这里的同名项
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct TimeoutConfig {
+    pub connect_secs: Option<u64>,
+    pub add_secs: Option<u64>,
+    pub pin_secs: Option<u64>,
+    pub request_secs: Option<u64>,
+    pub gateway_secs: Option<u64>,
+}
+
+impl TimeoutConfig {
+    // 旧的 timeout_secs：同时限制上传、固定和其它请求
+    pub fn uniform(secs: u64) -> Self {
+        Self {
+            add_secs: Some(secs),
+            pin_secs: Some(secs),
+            request_secs: Some(secs),
+            ..Self::default()
+        }
+    }
+
+    // 没有设置的项使用 fallback 中的值
+    pub fn or(&self, fallback: &TimeoutConfig) -> Self {
+        Self {
+            connect_secs: self.connect_secs.or(fallback.connect_secs),
+            add_secs: self.add_secs.or(fallback.add_secs),
+            pin_secs: self.pin_secs.or(fallback.pin_secs),
+            request_secs: self.request_secs.or(fallback.request_secs),
+            gateway_secs: self.gateway_secs.or(fallback.gateway_secs),
+        }
+    }
+
+    // 某个操作的超时，None 表示不限制
+    pub fn get(&self, operation: Operation) -> Option<Duration> {
+        let secs = match operation {
+            Operation::Connect => self.connect_secs,
+            Operation::Add => self.add_secs,
+            Operation::Pin => self.pin_secs,
+            Operation::Request => self.request_secs,
+            Operation::Gateway => self.gateway_secs,
+        }
+        .unwrap_or_else(|| operation.default_secs());
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    // 按操作设置单个请求的超时
+    pub fn apply(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+        operation: Operation,
+    ) -> reqwest::blocking::RequestBuilder {
+        match self.get(operation) {
+            Some(limit) => request.timeout(limit),
+            None => request,
+        }
+    }
+
+    // 请求失败时，超时转换为 TimeoutError，其它错误原样返回
+    pub fn check(
+        &self,
+        error: reqwest::Error,
+        operation: Operation,
+        target: &str,
+    ) -> anyhow::Error {
+        if !error.is_timeout() {
+            return error.into();
+        }
+        let operation = if error.is_connect() {
+            Operation::Connect
+        } else {
+            operation
+        };
+        TimeoutError {
+            operation,
+            limit: self.get(operation).unwrap_or_default(),
+            target: target.to_string(),
+        }
+        .into()
+    }
+}
+
+// ✅ 操作超时；调用方可以用 `error.downcast_ref::<TimeoutError>()` 区分超时和其它失败
+#[derive(Debug, Clone)]
+pub struct TimeoutError {
+    pub operation: Operation,
+    pub limit: Duration,
+    pub target: String,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "⏱️  {} 超时 ({} 秒): {}，可以在 [timeouts] 中调整 {}_secs (0 表示不限制)",
+            self.operation,
+            self.limit.as_secs(),
+            self.target,
+            self.operation
+        )
+    }
+}
+
+impl std::error::Error for TimeoutError {}
@@ -0,0 +1,773 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::filter::FileFilter;
+
+// multicodec 编号
+pub const DAG_PB: u64 = 0x70;
+pub const RAW: u64 = 0x55;
+const SHA2_256: u64 = 0x12;
+
+// 与 `ipfs add --cid-version 1` 的默认参数一致
+pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+pub const DEFAULT_MAX_LINKS: usize = 174;
+// Kubo 在目录节点超过这个大小时会改用 HAMT 分片目录
+const HAMT_SHARDING_SIZE: usize = 256 * 1024;
+
+// ✅ 上传时显式传给节点的导入参数，与上面的默认值一致，不受节点配置 (Import.*) 影响，
+// 相同的内容在任何节点上、任何时候上传都得到相同的 CID
+pub const ADD_OPTIONS: [(&str, &str); 3] = [
+    ("chunker", "size-262144"),
+    ("raw-leaves", "true"),
+    ("hash", "sha2-256"),
+];
+
+// Kubo 的 --inline-limit 默认值
+pub const DEFAULT_INLINE_LIMIT: usize = 32;
+// 上传文件夹时默认同时上传的文件数
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+// ✅ config.toml 中的 [add] 段：Kubo 的高级导入参数，只对命令行和 HTTP API 后端生效
+//
+// [add]
+// nocopy = true
+// inline = true
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AddConfig {
+    // 单个文件包在一个目录中上传 (--wrap-with-directory)，链接为 ipfs://<目录>/<文件名>
+    pub wrap_with_directory: bool,
+    // 不把文件复制进节点的 blockstore，只记录文件路径 (--nocopy，需要开启 Experimental.FilestoreEnabled)
+    pub nocopy: bool,
+    // 先在 filestore 中查找已有的块 (--fscache)
+    pub fscache: bool,
+    // 不超过 inline_limit 字节的块直接写进 CID (--inline)
+    pub inline: bool,
+    pub inline_limit: usize,
+    // HTTP API 后端上传文件夹时同时上传的文件数
+    pub concurrency: usize,
+}
+
+impl Default for AddConfig {
+    fn default() -> Self {
+        Self {
+            wrap_with_directory: false,
+            nocopy: false,
+            fscache: false,
+            inline: false,
+            inline_limit: DEFAULT_INLINE_LIMIT,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+impl AddConfig {
+    // 上传本地路径时的额外参数；包一层目录只对单个文件有意义
+    pub fn path_options(&self, is_file: bool) -> Vec<(&'static str, String)> {
+        let mut options = Vec::new();
+        if self.wrap_with_directory && is_file {
+            options.push(("wrap-with-directory", "true".to_string()));
+        }
+        if self.nocopy {
+            options.push(("nocopy", "true".to_string()));
+        }
+        if self.fscache {
+            options.push(("fscache", "true".to_string()));
+        }
+        options.extend(self.bytes_options());
+        options
+    }
+
+    // 上传内存中的数据 (元数据 JSON 等) 时的额外参数；这些数据没有本地路径，不使用 filestore
+    pub fn bytes_options(&self) -> Vec<(&'static str, String)> {
+        if !self.inline {
+            return Vec::new();
+        }
+        vec![
+            ("inline", "true".to_string()),
+            ("inline-limit", self.inline_limit.to_string()),
+        ]
+    }
+
+    // 开启的参数名，用来检查节点是否支持
+    pub fn flags(&self) -> Vec<&'static str> {
+        self.path_options(true)
+            .into_iter()
+            .map(|(flag, _)| flag)
+            .collect()
+    }
+
+    // 关闭一个参数 (节点不支持时)；不用 nocopy 时 fscache 也没有意义
+    pub fn disable(&mut self, flag: &str) {
+        match flag {
+            "wrap-with-directory" => self.wrap_with_directory = false,
+            "nocopy" => {
+                self.nocopy = false;
+                self.fscache = false;
+            }
+            "fscache" => self.fscache = false,
+            "inline" | "inline-limit" => self.inline = false,
+            _ => {}
+        }
+    }
+
+    // 检查 [add] 中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.fscache && !self.nocopy {
+            problems.push("fscache 只在 nocopy = true 时有效".to_string());
+        }
+        if self.inline && self.inline_limit == 0 {
+            problems.push("inline_limit 必须大于 0".to_string());
+        }
+        if self.concurrency == 0 {
+            problems.push("concurrency 必须大于 0".to_string());
+        }
+        problems
+    }
+}
+
+// UnixFS Data.Type
+const TYPE_DIRECTORY: u64 = 1;
+const TYPE_FILE: u64 = 2;
+const TYPE_SYMLINK: u64 = 4;
+
+// ✅ CIDv1 (sha2-256)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cid {
+    pub codec: u64,
+    pub digest: [u8; 32],
+}
+
+impl Cid {
+    pub fn hash(codec: u64, data: &[u8]) -> Self {
+        Self {
+            codec,
+            digest: Sha256::digest(data).into(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(36);
+        put_varint(&mut bytes, 1);
+        put_varint(&mut bytes, self.codec);
+        put_varint(&mut bytes, SHA2_256);
+        put_varint(&mut bytes, self.digest.len() as u64);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+}
+
+impl Cid {
+    // 只支持 CIDv1 + sha2-256
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = bytes;
+        let version = read_varint(&mut reader)?;
+        let codec = read_varint(&mut reader)?;
+        let hash = read_varint(&mut reader)?;
+        let len = read_varint(&mut reader)?;
+        if version != 1 || hash != SHA2_256 || len != 32 || reader.len() != 32 {
+            return Err(anyhow!("❌ 只支持 sha2-256 的 CIDv1"));
+        }
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(reader);
+        Ok(Self { codec, digest })
+    }
+}
+
+impl FromStr for Cid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let encoded = s
+            .strip_prefix('b')
+            .ok_or_else(|| anyhow!("❌ 只支持 base32 (b 开头) 的 CIDv1: {}", s))?;
+        let bytes = base32_decode(encoded).ok_or_else(|| anyhow!("❌ 无效的 CID: {}", s))?;
+        Self::from_bytes(&bytes).map_err(|e| anyhow!("{} ({})", e, s))
+    }
+}
+
+// 默认的 base32 字符串形式，例如 bafy...
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b{}", base32_lower(&self.to_bytes()))
+    }
+}
+
+fn base32_lower(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::with_capacity(data.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in encoded.bytes() {
+        let value = match c.to_ascii_lowercase() {
+            b'a'..=b'z' => c.to_ascii_lowercase() - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+pub fn read_varint(reader: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = reader
+            .split_first()
+            .ok_or_else(|| anyhow!("❌ varint 数据不完整"))?;
+        *reader = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(anyhow!("❌ varint 过长"))
+}
+
+pub fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+// --- protobuf 编码 (只用到 varint 和 bytes 两种类型) ---
+
+fn put_uint_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    put_varint(buf, field << 3);
+    put_varint(buf, value);
+}
+
+fn put_bytes_field(buf: &mut Vec<u8>, field: u64, data: &[u8]) {
+    put_varint(buf, (field << 3) | 2);
+    put_varint(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+// 一个编码好的块
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub cid: Cid,
+    pub data: Vec<u8>,
+}
+
+impl Block {
+    pub fn new(codec: u64, data: Vec<u8>) -> Self {
+        Self {
+            cid: Cid::hash(codec, &data),
+            data,
+        }
+    }
+}
+
+// dag-pb 链接；tsize 是子 DAG 所有块的累计大小
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub name: String,
+    pub cid: Cid,
+    pub tsize: u64,
+}
+
+// dag-pb 规范要求先写 Links (字段 2) 再写 Data (字段 1)
+pub fn encode_pb_node(links: &[Link], data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for link in links {
+        let mut encoded = Vec::new();
+        put_bytes_field(&mut encoded, 1, &link.cid.to_bytes());
+        put_bytes_field(&mut encoded, 2, link.name.as_bytes());
+        put_uint_field(&mut encoded, 3, link.tsize);
+        put_bytes_field(&mut buf, 2, &encoded);
+    }
+    put_bytes_field(&mut buf, 1, data);
+    buf
+}
+
+// 读取一个 protobuf 字段：返回 (字段号, varint 值或 bytes 内容)
+enum PbValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+fn read_pb_field<'a>(reader: &mut &'a [u8]) -> Result<(u64, PbValue<'a>)> {
+    let key = read_varint(reader)?;
+    match key & 7 {
+        0 => Ok((key >> 3, PbValue::Varint(read_varint(reader)?))),
+        2 => {
+            let len = read_varint(reader)? as usize;
+            if reader.len() < len {
+                return Err(anyhow!("❌ protobuf 数据不完整"));
+            }
+            let (value, rest) = reader.split_at(len);
+            *reader = rest;
+            Ok((key >> 3, PbValue::Bytes(value)))
+        }
+        wire => Err(anyhow!("❌ 不支持的 protobuf 类型 {}", wire)),
+    }
+}
+
+// ✅ 解析 dag-pb 节点，返回 (链接, Data 字段)
+pub fn decode_pb_node(mut data: &[u8]) -> Result<(Vec<Link>, Vec<u8>)> {
+    let (mut links, mut node_data) = (Vec::new(), Vec::new());
+    while !data.is_empty() {
+        match read_pb_field(&mut data)? {
+            (2, PbValue::Bytes(mut encoded)) => {
+                let mut link = Link {
+                    name: String::new(),
+                    cid: Cid::hash(RAW, &[]),
+                    tsize: 0,
+                };
+                while !encoded.is_empty() {
+                    match read_pb_field(&mut encoded)? {
+                        (1, PbValue::Bytes(hash)) => link.cid = Cid::from_bytes(hash)?,
+                        (2, PbValue::Bytes(name)) => {
+                            link.name = String::from_utf8_lossy(name).into_owned()
+                        }
+                        (3, PbValue::Varint(tsize)) => link.tsize = tsize,
+                        _ => {}
+                    }
+                }
+                links.push(link);
+            }
+            (1, PbValue::Bytes(bytes)) => node_data = bytes.to_vec(),
+            _ => {}
+        }
+    }
+    Ok((links, node_data))
+}
+
+// UnixFS 节点的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Directory,
+    File,
+    Symlink,
+    // HAMT 分片目录等暂不支持读取的类型
+    Other(u64),
+}
+
+// dag-pb 节点 Data 字段中的 UnixFS 信息
+#[derive(Debug, Clone)]
+pub struct UnixfsData {
+    pub kind: NodeKind,
+    // 文件的总大小
+    pub filesize: u64,
+    // 内联在节点中的文件内容，或符号链接的目标
+    pub data: Vec<u8>,
+}
+
+// ✅ 解析 UnixFS Data 字段
+pub fn decode_unixfs_data(mut data: &[u8]) -> Result<UnixfsData> {
+    let mut unixfs = UnixfsData {
+        kind: NodeKind::Other(0),
+        filesize: 0,
+        data: Vec::new(),
+    };
+    while !data.is_empty() {
+        match read_pb_field(&mut data)? {
+            (1, PbValue::Varint(kind)) => {
+                unixfs.kind = match kind {
+                    TYPE_DIRECTORY => NodeKind::Directory,
+                    TYPE_FILE => NodeKind::File,
+                    TYPE_SYMLINK => NodeKind::Symlink,
+                    other => NodeKind::Other(other),
+                }
+            }
+            (2, PbValue::Bytes(bytes)) => unixfs.data = bytes.to_vec(),
+            (3, PbValue::Varint(size)) => unixfs.filesize = size,
+            _ => {}
+        }
+    }
+    Ok(unixfs)
+}
+
+// 按 CID 读取块的函数：内存等
+pub type BlockSource<'a> = dyn Fn(&Cid) -> Result<Vec<u8>> + 'a;
+
+// ✅ 解析 `<CID>/<子路径>` (可以带 /ipfs/ 前缀)，逐级在目录节点中查找，返回最终的 CID
+pub fn resolve_path(get: &BlockSource, path: &str) -> Result<Cid> {
+    let path = path.trim_start_matches("/ipfs/").trim_matches('/');
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut cid: Cid = segments
+        .next()
+        .ok_or_else(|| anyhow!("❌ 路径为空"))?
+        .parse()?;
+    for segment in segments {
+        if cid.codec != DAG_PB {
+            return Err(anyhow!("❌ {} 不是目录，找不到 {}", cid, segment));
+        }
+        let (links, _) = decode_pb_node(&get(&cid)?)?;
+        cid = links
+            .iter()
+            .find(|link| link.name == segment)
+            .map(|link| link.cid)
+            .ok_or_else(|| anyhow!("❌ {} 中没有 {}", path, segment))?;
+    }
+    Ok(cid)
+}
+
+// ✅ 读取文件的全部内容 (raw 叶子或 dag-pb 文件节点)
+pub fn read_file(get: &BlockSource, cid: &Cid) -> Result<Vec<u8>> {
+    let block = get(cid)?;
+    if cid.codec == RAW {
+        return Ok(block);
+    }
+    let (links, data) = decode_pb_node(&block)?;
+    let unixfs = decode_unixfs_data(&data)?;
+    if unixfs.kind != NodeKind::File {
+        return Err(anyhow!("❌ {} 不是文件", cid));
+    }
+    let mut content = unixfs.data;
+    for link in links {
+        content.extend(read_file(get, &link.cid)?);
+    }
+    Ok(content)
+}
+
+fn file_data(filesize: u64, blocksizes: &[u64]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    put_uint_field(&mut buf, 1, TYPE_FILE);
+    put_uint_field(&mut buf, 3, filesize);
+    for size in blocksizes {
+        put_uint_field(&mut buf, 4, *size);
+    }
+    buf
+}
+
+// 不写入 mode 和 mtime (UnixFS 1.5 的可选字段)，文件的权限和修改时间不影响 CID
+fn directory_data() -> Vec<u8> {
+    let mut buf = Vec::new();
+    put_uint_field(&mut buf, 1, TYPE_DIRECTORY);
+    buf
+}
+
+// ✅ 用子节点的链接拼出目录节点；链接需要按名称的字节序排列
+pub fn directory_block(links: &[Link]) -> Block {
+    Block::new(DAG_PB, encode_pb_node(links, &directory_data()))
+}
+
+// ✅ 符号链接节点：只保存链接的目标路径，不读取目标的内容
+pub fn symlink_block(target: &str) -> Block {
+    Block::new(
+        DAG_PB,
+        encode_pb_node(&[], &symlink_data(target.as_bytes())),
+    )
+}
+
+// Kubo 按链接名称加 CID 的长度估算目录大小，达到 HAMT_SHARDING_SIZE 时改用分片目录
+pub fn needs_sharding<'a>(names: impl IntoIterator<Item = &'a str>) -> bool {
+    // CIDv1 + sha2-256 的二进制长度：版本、codec、哈希类型、摘要长度各 1 字节，加 32 字节摘要
+    const CID_LEN: usize = 36;
+    names
+        .into_iter()
+        .map(|name| name.len() + CID_LEN)
+        .sum::<usize>()
+        >= HAMT_SHARDING_SIZE
+}
+
+fn symlink_data(target: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    put_uint_field(&mut buf, 1, TYPE_SYMLINK);
+    put_bytes_field(&mut buf, 2, target);
+    buf
+}
+
+// 导入参数
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub chunk_size: usize,
+    pub max_links: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_links: DEFAULT_MAX_LINKS,
+        }
+    }
+}
+
+// ✅ 只计算 CID，不保存块
+pub fn compute_cid(path: &Path, options: &ImportOptions) -> Result<Cid> {
+    Ok(import_path(path, options, &mut |_| Ok(()))?.cid)
+}
+
+// 导入后的一个节点：CID、累计块大小、文件内容大小
+#[derive(Debug, Clone, Copy)]
+pub struct Imported {
+    pub cid: Cid,
+    pub tsize: u64,
+    pub size: u64,
+}
+
+// 每生成一个块就交给调用方 (写入磁盘等)，内存中不保留块列表
+pub type BlockSink<'a> = dyn FnMut(Block) -> Result<()> + 'a;
+
+// 读满一个分块；返回读到的字节数，0 表示已到结尾
+fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+// ✅ 按 balanced 布局流式导入文件 (raw 叶子)，只保留每一层尚未填满的链接
+pub fn import_file(
+    mut reader: impl Read,
+    options: &ImportOptions,
+    sink: &mut BlockSink,
+) -> Result<Imported> {
+    let mut levels: Vec<Vec<Imported>> = vec![Vec::new()];
+    let mut buf = vec![0u8; options.chunk_size];
+    let mut first = true;
+    loop {
+        let n = read_chunk(&mut reader, &mut buf)?;
+        // 空文件也对应一个空的 raw 块
+        if n == 0 && !first {
+            break;
+        }
+        first = false;
+        let block = Block::new(RAW, buf[..n].to_vec());
+        levels[0].push(Imported {
+            cid: block.cid,
+            tsize: n as u64,
+            size: n as u64,
+        });
+        sink(block)?;
+
+        let mut level = 0;
+        while levels[level].len() == options.max_links {
+            flush_level(&mut levels, level, sink)?;
+            level += 1;
+        }
+        if n < options.chunk_size {
+            break;
+        }
+    }
+
+    // 自底向上收拢，直到只剩一个根
+    let mut level = 0;
+    loop {
+        let has_higher = levels[level + 1..].iter().any(|l| !l.is_empty());
+        match levels[level].len() {
+            1 if !has_higher => return Ok(levels[level][0]),
+            0 => level += 1,
+            _ => {
+                flush_level(&mut levels, level, sink)?;
+                level += 1;
+            }
+        }
+    }
+}
+
+// 把某一层的链接打包成一个文件节点，放到上一层
+fn flush_level(levels: &mut Vec<Vec<Imported>>, level: usize, sink: &mut BlockSink) -> Result<()> {
+    let children = std::mem::take(&mut levels[level]);
+    let sizes: Vec<u64> = children.iter().map(|c| c.size).collect();
+    let size = sizes.iter().sum();
+    let links: Vec<Link> = children
+        .iter()
+        .map(|c| Link {
+            name: String::new(),
+            cid: c.cid,
+            tsize: c.tsize,
+        })
+        .collect();
+    let block = Block::new(DAG_PB, encode_pb_node(&links, &file_data(size, &sizes)));
+    let tsize = block.data.len() as u64 + children.iter().map(|c| c.tsize).sum::<u64>();
+    if levels.len() == level + 1 {
+        levels.push(Vec::new());
+    }
+    levels[level + 1].push(Imported {
+        cid: block.cid,
+        tsize,
+        size,
+    });
+    sink(block)
+}
+
+// ✅ 和 `ipfs add -r` 一样导入文件或文件夹：跳过隐藏文件和 .ipfsignore 中忽略的文件，符号链接保存为 symlink 节点
+pub fn import_path(path: &Path, options: &ImportOptions, sink: &mut BlockSink) -> Result<Imported> {
+    let filter = if path.is_dir() {
+        FileFilter::from_ignore_file(path)?
+    } else {
+        FileFilter::default()
+    };
+    import_entry(path, "", &filter, options, sink)
+}
+
+// relative 是相对于导入根目录的路径，用于匹配忽略规则
+fn import_entry(
+    path: &Path,
+    relative: &str,
+    filter: &FileFilter,
+    options: &ImportOptions,
+    sink: &mut BlockSink,
+) -> Result<Imported> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        let block = symlink_block(&fs::read_link(path)?.to_string_lossy());
+        let imported = Imported {
+            cid: block.cid,
+            tsize: block.data.len() as u64,
+            size: 0,
+        };
+        sink(block)?;
+        return Ok(imported);
+    }
+    if meta.is_file() {
+        return import_file(File::open(path)?, options, sink);
+    }
+    if !meta.is_dir() {
+        return Err(anyhow!("❌ 不支持的文件类型: {:?}", path));
+    }
+
+    let child_path = |name: &str| {
+        if relative.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", relative, name)
+        }
+    };
+    let mut entries: Vec<(String, fs::DirEntry)> = fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .map(|entry| (entry.file_name().to_string_lossy().into_owned(), entry))
+        .filter(|(name, entry)| {
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+            filter.allows(&child_path(name), is_dir)
+        })
+        .collect();
+    // dag-pb 要求链接按名称的字节序排列，与文件系统返回的顺序无关
+    entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+    let mut links = Vec::with_capacity(entries.len());
+    let mut size = 0;
+    for (name, entry) in entries {
+        let child = import_entry(&entry.path(), &child_path(&name), filter, options, sink)?;
+        size += child.size;
+        links.push(Link {
+            name,
+            cid: child.cid,
+            tsize: child.tsize,
+        });
+    }
+    // 与 HTTP 后端判断是否整体上传时用同一个估算，结果才与 Kubo 一致
+    if needs_sharding(links.iter().map(|link| link.name.as_str())) {
+        eprintln!(
+            "⚠️  目录 {:?} 的条目较多，Kubo 会改用 HAMT 分片目录，生成的 CID 可能与 ipfs add 不一致",
+            path
+        );
+    }
+    let block = directory_block(&links);
+    let imported = Imported {
+        cid: block.cid,
+        tsize: block.data.len() as u64 + links.iter().map(|l| l.tsize).sum::<u64>(),
+        size,
+    };
+    sink(block)?;
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    const FILES: [(&str, &str); 4] = [
+        ("a.txt", "alpha"),
+        ("sub/b.json", r#"{"b":1}"#),
+        ("sub/deeper/c.txt", "gamma"),
+        ("z.txt", ""),
+    ];
+
+    fn build(root: &Path, order: &[usize], modified: SystemTime, readonly: bool) {
+        fs::create_dir_all(root.join("empty")).unwrap();
+        for &index in order {
+            let (name, content) = FILES[index];
+            let path = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let file = File::create(&path).unwrap();
+            io::Write::write_all(&mut &file, content.as_bytes()).unwrap();
+            file.set_modified(modified).unwrap();
+            let mut permissions = file.metadata().unwrap().permissions();
+            permissions.set_readonly(readonly);
+            fs::set_permissions(&path, permissions).unwrap();
+        }
+    }
+
+    // ✅ CID 只取决于文件名和内容：创建顺序、修改时间和权限不同的两棵树得到同一个 CID
+    #[test]
+    fn cid_ignores_creation_order_mtime_and_permissions() {
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("first/collection");
+        let second = temp.path().join("second/collection");
+        build(&first, &[0, 1, 2, 3], SystemTime::UNIX_EPOCH, false);
+        build(
+            &second,
+            &[3, 2, 1, 0],
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            true,
+        );
+
+        let options = ImportOptions::default();
+        let cid = compute_cid(&first, &options).unwrap();
+        assert_eq!(cid, compute_cid(&second, &options).unwrap());
+
+        // 内容变了 CID 也会变
+        let changed = second.join("a.txt");
+        let mut permissions = fs::metadata(&changed).unwrap().permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        fs::set_permissions(&changed, permissions).unwrap();
+        fs::write(&changed, "beta").unwrap();
+        assert_ne!(cid, compute_cid(&second, &options).unwrap());
+    }
+
+    // 估算大小恰好达到 256KiB 时就分片 (Kubo 用的是 >=)
+    #[test]
+    fn sharding_starts_at_the_threshold() {
+        // 每个名称 28 字节，加上 36 字节的 CID 正好 64 字节
+        let names: Vec<String> = (0..HAMT_SHARDING_SIZE / 64)
+            .map(|i| format!("{:028}", i))
+            .collect();
+        assert!(needs_sharding(names.iter().map(String::as_str)));
+        assert!(!needs_sharding(names[1..].iter().map(String::as_str)));
+    }
+}
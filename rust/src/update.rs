@@ -0,0 +1,379 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::NftMetadata;
+use crate::backend::IpfsBackend;
+use crate::diff::{MetadataDiff, MetadataSet, diff_metadata};
+use crate::manifest::Manifest;
+use crate::metadata::{Attribute, JsonStyle, STANDARD_FIELDS};
+use crate::patch::{metadata_path, republish_metadata};
+use crate::urls::UrlConfig;
+
+// ✅ 要修改的 token："*" 表示全部，或 "5-20"、"1,3,7-9" 这样的 ID 列表
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum TokenSelector {
+    #[default]
+    All,
+    // 包含两端的 ID 区间
+    Ranges(Vec<(u64, u64)>),
+}
+
+impl TokenSelector {
+    // 单件 NFT 没有数字 ID，只有 "*" 能选中
+    pub fn matches(&self, token_id: Option<u64>) -> bool {
+        match (self, token_id) {
+            (Self::All, _) => true,
+            (Self::Ranges(ranges), Some(id)) => ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&id)),
+            (Self::Ranges(_), None) => false,
+        }
+    }
+}
+
+impl FromStr for TokenSelector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s == "*" || s.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+        let invalid = || {
+            anyhow!(
+                "❌ 无效的 token 范围: {} (如 \"*\"、\"5-20\"、\"1,3,7-9\")",
+                s
+            )
+        };
+        let mut ranges = Vec::new();
+        for part in s.split(',') {
+            let part = part.trim();
+            let (start, end) = part.split_once('-').unwrap_or((part, part));
+            let start: u64 = start.trim().parse().map_err(|_| invalid())?;
+            let end: u64 = end.trim().parse().map_err(|_| invalid())?;
+            if start > end {
+                return Err(invalid());
+            }
+            ranges.push((start, end));
+        }
+        Ok(Self::Ranges(ranges))
+    }
+}
+
+impl TryFrom<String> for TokenSelector {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+// 值必须是字符串的字段；attributes 用 set_attribute / remove_attribute 修改
+const TEXT_FIELDS: [&str; 4] = ["name", "description", "image", "animation_url"];
+
+// ✅ 补丁文件中的一项修改
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOperation {
+    // 修改属性的值，token 没有该属性时添加
+    SetAttribute {
+        #[serde(default)]
+        tokens: TokenSelector,
+        trait_type: String,
+        value: Value,
+    },
+    RemoveAttribute {
+        #[serde(default)]
+        tokens: TokenSelector,
+        trait_type: String,
+    },
+    // 修改 name、description、image、animation_url 或自定义字段 (如 dna)，自定义字段的值可以是任意 JSON
+    SetField {
+        #[serde(default)]
+        tokens: TokenSelector,
+        field: String,
+        value: Value,
+    },
+    // 删除自定义字段或 animation_url
+    RemoveField {
+        #[serde(default)]
+        tokens: TokenSelector,
+        field: String,
+    },
+}
+
+impl PatchOperation {
+    fn tokens(&self) -> &TokenSelector {
+        match self {
+            Self::SetAttribute { tokens, .. }
+            | Self::RemoveAttribute { tokens, .. }
+            | Self::SetField { tokens, .. }
+            | Self::RemoveField { tokens, .. } => tokens,
+        }
+    }
+
+    // 对一个 token 的元数据执行修改
+    fn apply(&self, metadata: &mut NftMetadata) {
+        match self {
+            Self::SetAttribute {
+                trait_type, value, ..
+            } => match metadata
+                .attributes
+                .iter_mut()
+                .find(|attribute| attribute.trait_type == *trait_type)
+            {
+                Some(attribute) => attribute.value = value.clone(),
+                None => metadata.attributes.push(Attribute {
+                    trait_type: trait_type.clone(),
+                    value: value.clone(),
+                }),
+            },
+            Self::RemoveAttribute { trait_type, .. } => metadata
+                .attributes
+                .retain(|attribute| attribute.trait_type != *trait_type),
+            Self::SetField { field, value, .. } => {
+                // 加载补丁时已经检查过字段名和值的类型
+                let text = value.as_str().unwrap_or_default().to_string();
+                match field.as_str() {
+                    "name" => metadata.name = text,
+                    "description" => metadata.description = text,
+                    "image" => metadata.image = text,
+                    "animation_url" => metadata.animation_url = Some(text),
+                    _ => {
+                        metadata.extra.insert(field.clone(), value.clone());
+                    }
+                }
+            }
+            Self::RemoveField { field, .. } => {
+                if field == "animation_url" {
+                    metadata.animation_url = None;
+                } else {
+                    metadata.extra.remove(field);
+                }
+            }
+        }
+    }
+
+    // 补丁文件中的问题
+    fn problem(&self) -> Option<String> {
+        match self {
+            Self::SetField { field, value, .. } => {
+                if field == "attributes" {
+                    Some("set_field 不能修改 attributes，请使用 set_attribute".to_string())
+                } else if TEXT_FIELDS.contains(&field.as_str()) && !value.is_string() {
+                    Some(format!("set_field 的 {} 必须是字符串: {}", field, value))
+                } else if field.is_empty() {
+                    Some("set_field 的 field 不能为空".to_string())
+                } else {
+                    None
+                }
+            }
+            Self::RemoveField { field, .. } => (STANDARD_FIELDS.contains(&field.as_str())
+                && field != "animation_url")
+                .then(|| format!("remove_field 不能删除 {}", field)),
+            _ => None,
+        }
+    }
+}
+
+// Metaplex 的 properties.files 中也记录了 image 和 animation_url，只改一处会不一致
+fn has_metaplex_files(metadata: &NftMetadata) -> bool {
+    metadata
+        .extra
+        .get("properties")
+        .is_some_and(|properties| properties.get("files").is_some())
+}
+
+// ✅ 补丁文件 (TOML 或 JSON)，按顺序执行其中的修改：
+//
+// [[ops]]
+// op = "set_attribute"
+// tokens = "5-20"
+// trait_type = "Background"
+// value = "Gold"
+#[derive(Deserialize, Debug, Clone)]
+pub struct MetadataPatch {
+    pub ops: Vec<PatchOperation>,
+}
+
+impl MetadataPatch {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).map_err(|e| anyhow!("❌ 读取补丁 {:?} 失败: {}", path, e))?;
+        let patch: Self = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .map_err(|e| anyhow!("❌ 补丁 {:?} 格式错误: {}", path, e))?
+        } else {
+            toml::from_str(&content).map_err(|e| anyhow!("❌ 补丁 {:?} 格式错误: {}", path, e))?
+        };
+        if patch.ops.is_empty() {
+            return Err(anyhow!("❌ 补丁 {:?} 中没有任何修改 ([[ops]])", path));
+        }
+        for op in &patch.ops {
+            if let Some(problem) = op.problem() {
+                return Err(anyhow!("❌ 补丁 {:?} 有问题: {}", path, problem));
+            }
+        }
+        Ok(patch)
+    }
+}
+
+// update-metadata 的可选项
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOptions {
+    // 只显示会发生的变化，不写文件也不上传
+    pub dry_run: bool,
+    // 改写后的元数据文件使用的 JSON 写法，与生成时一致才不会改变未修改部分的字节
+    pub json_style: JsonStyle,
+    // 更新后的清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+}
+
+// ✅ 一次元数据更新的结果
+#[derive(Debug, Clone)]
+pub struct UpdateReport {
+    // 修改前后的对比，token 以元数据文件名 (去掉 .json) 标识
+    pub diff: MetadataDiff,
+    pub old_metadata_cid: String,
+    pub old_token_uri: String,
+    // dry run 或没有变化时与旧值相同
+    pub new_metadata_cid: String,
+    pub new_token_uri: String,
+    // 没有选中任何 token 的修改 (补丁中的序号，从 1 开始)
+    pub unmatched: Vec<usize>,
+}
+
+fn diff_key(file: &str) -> String {
+    file.strip_suffix(".json").unwrap_or(file).to_string()
+}
+
+// ✅ 按补丁修改已有运行的元数据：只改写受影响的元数据文件，重新上传元数据 (图片不动)，更新清单
+pub fn update_metadata(
+    backend: &dyn IpfsBackend,
+    manifest_path: &Path,
+    patch: &MetadataPatch,
+    options: &UpdateOptions,
+) -> Result<UpdateReport> {
+    let mut manifest = Manifest::load(manifest_path)?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let old_metadata_cid = manifest.metadata_cid.clone();
+    let old_token_uri = manifest.token_uri.clone();
+    if !options.dry_run && manifest.encryption != backend.encryption() {
+        return Err(anyhow!(
+            "❌ 清单与当前的加密设置 ([encryption] / --encrypt) 不一致，重新上传的元数据会与原来的不同"
+        ));
+    }
+
+    // 先全部读出并修改，任何一个文件有问题都不写入
+    let mut before = MetadataSet::default();
+    let mut after = MetadataSet::default();
+    let mut patched: Vec<(usize, PathBuf, NftMetadata)> = Vec::new();
+    for (index, token) in manifest.tokens.iter().enumerate() {
+        let path = metadata_path(dir, manifest.kind, token);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("❌ 读取元数据 {:?} 失败: {}", path, e))?;
+        let mut metadata: NftMetadata = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("❌ 元数据 {:?} 格式错误: {}", path, e))?;
+        let (old_image, old_animation) = (metadata.image.clone(), metadata.animation_url.clone());
+        let original = serde_json::to_value(&metadata)?;
+        for op in &patch.ops {
+            if op.tokens().matches(token.token_id) {
+                op.apply(&mut metadata);
+            }
+        }
+        let updated = serde_json::to_value(&metadata)?;
+        let key = diff_key(&token.metadata_file);
+        before.tokens.insert(key.clone(), original.clone());
+        after.tokens.insert(key, updated.clone());
+        if updated == original {
+            continue;
+        }
+        // 自定义字段在 extra 中原样保留；Metaplex 的文件列表不会跟着修改
+        if has_metaplex_files(&metadata)
+            && (metadata.image != old_image || metadata.animation_url != old_animation)
+        {
+            return Err(anyhow!(
+                "❌ 元数据 {:?} 是 Metaplex 结构，修改 image / animation_url 后 properties.files 会不一致",
+                path
+            ));
+        }
+        patched.push((index, path, metadata));
+    }
+    let diff = diff_metadata(&before, &after);
+    let unmatched: Vec<usize> = patch
+        .ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| {
+            !manifest
+                .tokens
+                .iter()
+                .any(|token| op.tokens().matches(token.token_id))
+        })
+        .map(|(index, _)| index + 1)
+        .collect();
+    if options.dry_run || patched.is_empty() {
+        return Ok(UpdateReport {
+            diff,
+            new_metadata_cid: old_metadata_cid.clone(),
+            new_token_uri: old_token_uri.clone(),
+            old_metadata_cid,
+            old_token_uri,
+            unmatched,
+        });
+    }
+
+    // name 和 image 也记录在清单中
+    for (index, _, metadata) in &patched {
+        let token = &mut manifest.tokens[*index];
+        token.name = metadata.name.clone();
+        token.image_uri = metadata.image.clone();
+        token.animation_uri = metadata.animation_url.clone();
+    }
+    republish_metadata(
+        backend,
+        &mut manifest,
+        dir,
+        &patched,
+        options.json_style,
+        &options.urls,
+    )?;
+
+    Ok(UpdateReport {
+        diff,
+        old_metadata_cid,
+        old_token_uri,
+        new_metadata_cid: manifest.metadata_cid,
+        new_token_uri: manifest.token_uri,
+        unmatched,
+    })
+}
+
+pub fn print_update_report(report: &UpdateReport, dry_run: bool) {
+    let changed = report.diff.changed.len();
+    println!(
+        "\n📝 {} 个 token 的元数据{}，{} 个不变",
+        changed,
+        if dry_run { "将会修改" } else { "已修改" },
+        report.diff.unchanged
+    );
+    for index in &report.unmatched {
+        println!(
+            "⚠️  第 {} 项修改没有选中任何 token，请检查 tokens 范围",
+            index
+        );
+    }
+    if dry_run || changed == 0 {
+        return;
+    }
+    println!("\n🔗 旧元数据 CID: {}", report.old_metadata_cid);
+    println!("🔗 新元数据 CID: {}", report.new_metadata_cid);
+    println!("🔗 新 URI: {}", report.new_token_uri);
+    println!("下一步，请在合约中把 URI 更新为新的地址 (图片 CID 没有变化)。");
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cid::{CidInfo, Multibase};
+use crate::manifest::Manifest;
+
+pub const DEFAULT_PATH_GATEWAY: &str = "https://ipfs.io";
+pub const DEFAULT_SUBDOMAIN_GATEWAY: &str = "https://dweb.link";
+
+// 子域名中一段的最大长度 (DNS label)
+const MAX_LABEL_LEN: usize = 63;
+
+// ✅ config.toml 中的 [urls] 段：清单中为每个根 CID 记录的链接写法
+//
+// [urls]
+// custom_gateways = ["https://my-collection.mypinata.cloud"]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct UrlConfig {
+    // 写入清单并在运行结束时打印 (默认开启)
+    pub enabled: bool,
+    // 路径网关：<网关>/ipfs/<cid>
+    pub path_gateway: String,
+    // 子域名网关：https://<cid>.ipfs.<网关主机>，浏览器按来源隔离不同的 CID
+    pub subdomain_gateway: String,
+    // 自己的网关 (如 Pinata 专属网关)，按路径网关的形式生成
+    pub custom_gateways: Vec<String>,
+}
+
+impl Default for UrlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path_gateway: DEFAULT_PATH_GATEWAY.to_string(),
+            subdomain_gateway: DEFAULT_SUBDOMAIN_GATEWAY.to_string(),
+            custom_gateways: Vec::new(),
+        }
+    }
+}
+
+// 一个 CID 的各种链接：钱包和市场通常用 ipfs://，浏览器用网关地址
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CidUrls {
+    pub cid: String,
+    pub ipfs: String,
+    pub path_gateway: String,
+    // CID 写成小写 base32 v1；太长放不进子域名时没有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdomain_base32: Option<String>,
+    // CID 写成 base36，比 base32 短，适合较长的 CID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdomain_base36: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<String>,
+}
+
+fn path_url(gateway: &str, cid: &str) -> String {
+    format!("{}/ipfs/{}", gateway.trim_end_matches('/'), cid)
+}
+
+// https://dweb.link + <label> -> https://<label>.ipfs.dweb.link
+fn subdomain_url(gateway: &str, label: &str) -> Option<String> {
+    if label.len() > MAX_LABEL_LEN {
+        return None;
+    }
+    let gateway = gateway.trim_end_matches('/');
+    let (scheme, host) = gateway.split_once("://").unwrap_or(("https", gateway));
+    Some(format!("{}://{}.ipfs.{}", scheme, label, host))
+}
+
+impl UrlConfig {
+    // 检查 [urls] 中的问题，为空表示可以使用
+    pub fn problems(&self) -> Vec<String> {
+        let gateways = [&self.path_gateway, &self.subdomain_gateway]
+            .into_iter()
+            .chain(&self.custom_gateways);
+        gateways
+            .filter(|gateway| !gateway.starts_with("http://") && !gateway.starts_with("https://"))
+            .map(|gateway| format!("网关地址应以 http:// 或 https:// 开头: {:?}", gateway))
+            .collect()
+    }
+
+    // ✅ 生成一个 CID 的所有链接；子域名中的 CID 需要不区分大小写，v0 会先转换为 v1
+    pub fn urls(&self, cid: &str) -> CidUrls {
+        let v1 = cid.parse::<CidInfo>().ok().map(|info| info.to_v1());
+        let label = |base: Multibase| {
+            v1.as_ref()
+                .and_then(|v1| v1.encode(base).ok())
+                .and_then(|label| subdomain_url(&self.subdomain_gateway, &label))
+        };
+        CidUrls {
+            cid: cid.to_string(),
+            ipfs: format!("ipfs://{}", cid),
+            path_gateway: path_url(&self.path_gateway, cid),
+            subdomain_base32: label(Multibase::Base32),
+            subdomain_base36: label(Multibase::Base36),
+            custom: self
+                .custom_gateways
+                .iter()
+                .map(|gateway| path_url(gateway, cid))
+                .collect(),
+        }
+    }
+}
+
+// ✅ 按清单中当前的根 CID 重新生成链接；CID 变化后 (重新上传元数据等) 旧链接不会留在清单中
+pub fn record_urls(manifest: &mut Manifest, config: &UrlConfig) {
+    manifest.urls.clear();
+    if !config.enabled {
+        return;
+    }
+    let roots = [
+        ("image", Some(&manifest.image_cid)),
+        ("metadata", Some(&manifest.metadata_cid)),
+        ("posters", manifest.posters_cid.as_ref()),
+        ("previews", manifest.previews_cid.as_ref()),
+        ("watermarked", manifest.watermarked_cid.as_ref()),
+        ("localization", manifest.localization_cid.as_ref()),
+    ];
+    let urls: Vec<(String, CidUrls)> = roots
+        .into_iter()
+        .filter_map(|(role, cid)| cid.filter(|cid| !cid.is_empty()).map(|cid| (role, cid)))
+        .map(|(role, cid)| (role.to_string(), config.urls(cid)))
+        .collect();
+    manifest.urls.extend(urls);
+}
+
+// 打印清单中记录的链接
+pub fn print_urls(manifest: &Manifest) {
+    if manifest.urls.is_empty() {
+        return;
+    }
+    println!("\n🔗 访问链接:");
+    for (role, urls) in &manifest.urls {
+        println!("  [{}] {}", role, urls.cid);
+        println!("    {}", urls.ipfs);
+        println!("    {}", urls.path_gateway);
+        for url in urls
+            .subdomain_base32
+            .iter()
+            .chain(&urls.subdomain_base36)
+            .chain(&urls.custom)
+        {
+            println!("    {}", url);
+        }
+    }
+}
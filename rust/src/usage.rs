@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::estimate::{GB, PricingTier, TierProjection, format_bytes};
+use crate::localization::LOCALIZATION_DIR;
+use crate::manifest::{CollectionKind, Manifest};
+use crate::metrics::path_size;
+use crate::patch::metadata_root_path;
+use crate::pinning::{PinataOptions, PinningServiceConfig};
+use crate::poster::POSTERS_DIR;
+use crate::preview::PREVIEWS_DIR;
+use crate::remote_pin::PinningClient;
+use crate::watermark::WATERMARKED_DIR;
+
+// 内存后端上传的内容不会保存，不计入用量
+const MEMORY_BACKEND: &str = "memory";
+
+// ✅ 一个服务商 (节点后端或 Pinning 服务) 上的用量
+#[derive(Debug, Clone, Default)]
+pub struct ProviderUsage {
+    pub provider: String,
+    // 涉及的运行目录数
+    pub runs: usize,
+    // 按 CID 去重后的根，同一内容上传多次只算一次
+    pub cids: BTreeSet<String>,
+    // 按运行目录中的本地文件统计
+    pub files: u64,
+    pub bytes: u64,
+    // 服务商接口返回的数据 (Pinata 的总大小、Pinning Service API 的 pin 数)
+    pub reported_bytes: Option<u64>,
+    pub reported_pins: Option<u64>,
+}
+
+impl ProviderUsage {
+    // 与套餐比较时使用的存储量：服务商返回的优先
+    pub fn used_bytes(&self) -> u64 {
+        self.reported_bytes.unwrap_or(self.bytes)
+    }
+
+    // 这个服务商的套餐，used_gb / used_files 不低于统计到的用量
+    pub fn apply_to(&self, tier: &PricingTier) -> PricingTier {
+        PricingTier {
+            used_gb: tier.used_gb.max(self.used_bytes() as f64 / GB),
+            used_files: tier.used_files.max(self.files),
+            ..tier.clone()
+        }
+    }
+}
+
+// ✅ 清单中的根 CID (用途、CID) 和运行目录中对应的本地内容
+pub fn manifest_roots(manifest: &Manifest, dir: &Path) -> Vec<(&'static str, String, PathBuf)> {
+    let mut roots = match (manifest.kind, manifest.tokens.first()) {
+        (CollectionKind::Single, Some(token)) => vec![
+            (
+                "image",
+                manifest.image_cid.clone(),
+                dir.join(&token.image_file),
+            ),
+            (
+                "metadata",
+                manifest.metadata_cid.clone(),
+                metadata_root_path(dir, manifest.kind, token),
+            ),
+        ],
+        (CollectionKind::Single, None) => Vec::new(),
+        _ => vec![
+            ("image", manifest.image_cid.clone(), dir.join("images")),
+            (
+                "metadata",
+                manifest.metadata_cid.clone(),
+                dir.join("metadata"),
+            ),
+        ],
+    };
+    let folders = [
+        (&manifest.posters_cid, POSTERS_DIR),
+        (&manifest.previews_cid, PREVIEWS_DIR),
+        (&manifest.watermarked_cid, WATERMARKED_DIR),
+        (&manifest.localization_cid, LOCALIZATION_DIR),
+    ];
+    for (cid, folder) in folders {
+        if let Some(cid) = cid
+            && dir.join(folder).exists()
+        {
+            roots.push((folder, cid.clone(), dir.join(folder)));
+        }
+    }
+    roots.retain(|(_, cid, path)| !cid.is_empty() && path.exists());
+    roots
+}
+
+// 一个根由哪些服务商保存：上传的后端 (故障转移时为实际完成上传的节点) 和 pin 过的 Pinning 服务
+fn root_providers(manifest: &Manifest, cid: &str) -> Vec<String> {
+    let mut providers = Vec::new();
+    match manifest.served_by.get(cid) {
+        Some(node) => providers.push(node.clone()),
+        None if manifest.backend != MEMORY_BACKEND => providers.push(manifest.backend.clone()),
+        None => {}
+    }
+    providers.extend(
+        manifest
+            .remote_pins
+            .iter()
+            .filter(|(_, record)| record.pinned > 0)
+            .map(|(service, _)| service.clone()),
+    );
+    providers
+}
+
+// ✅ 按清单统计每个服务商上的 CID 和大小 (不需要网络)
+pub fn collect_usage(manifests: &[PathBuf]) -> Result<Vec<ProviderUsage>> {
+    let mut usage: BTreeMap<String, ProviderUsage> = BTreeMap::new();
+    for path in manifests {
+        let manifest = Manifest::load(path)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut seen_run = BTreeSet::new();
+        for (_, cid, root) in manifest_roots(&manifest, dir) {
+            for provider in root_providers(&manifest, &cid) {
+                let entry = usage
+                    .entry(provider.clone())
+                    .or_insert_with(|| ProviderUsage {
+                        provider: provider.clone(),
+                        ..ProviderUsage::default()
+                    });
+                if seen_run.insert(provider) {
+                    entry.runs += 1;
+                }
+                if entry.cids.insert(cid.clone()) {
+                    let (files, bytes) = path_size(&root);
+                    entry.files += files;
+                    entry.bytes += bytes;
+                }
+            }
+        }
+    }
+    Ok(usage.into_values().collect())
+}
+
+// Pinata 服务的原生 API 设置：没有配置 [pinning_services.pinata] 时按 endpoint 识别，使用默认地址
+fn pinata_options(service: &PinningServiceConfig) -> Option<PinataOptions> {
+    service.pinata.clone().or_else(|| {
+        service
+            .endpoint
+            .contains("pinata.cloud")
+            .then(PinataOptions::default)
+    })
+}
+
+// ✅ 向 Pinning 服务查询账户中的 pin 数 (Pinata 还有总大小)，查询失败只提示
+pub fn query_services(usage: &mut Vec<ProviderUsage>, services: &[PinningServiceConfig]) {
+    for service in services {
+        let client = match PinningClient::new(service) {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("⚠️  {:#}", e);
+                continue;
+            }
+        };
+        if !usage.iter().any(|entry| entry.provider == service.name) {
+            usage.push(ProviderUsage {
+                provider: service.name.clone(),
+                ..ProviderUsage::default()
+            });
+        }
+        let Some(entry) = usage
+            .iter_mut()
+            .find(|entry| entry.provider == service.name)
+        else {
+            continue;
+        };
+        match client.count_pinned() {
+            Ok(count) => entry.reported_pins = Some(count),
+            Err(e) => eprintln!("⚠️  {:#}", e),
+        }
+        if let Some(pinata) = pinata_options(service) {
+            match client.pinata_pinned_bytes(&pinata) {
+                Ok(bytes) => entry.reported_bytes = Some(bytes),
+                Err(e) => eprintln!("⚠️  {:#}", e),
+            }
+        }
+    }
+}
+
+// ✅ 把统计到的用量填入对应服务商 (名称不区分大小写) 的套餐
+pub fn applied_tiers(usage: &[ProviderUsage], tiers: &[PricingTier]) -> Vec<PricingTier> {
+    tiers
+        .iter()
+        .map(|tier| {
+            usage
+                .iter()
+                .find(|entry| entry.provider.eq_ignore_ascii_case(&tier.provider))
+                .map_or_else(|| tier.clone(), |entry| entry.apply_to(tier))
+        })
+        .collect()
+}
+
+pub fn print_usage(usage: &[ProviderUsage], projections: &[TierProjection]) {
+    if usage.is_empty() {
+        println!("\n📦 输出目录中没有已上传的运行");
+    } else {
+        println!("\n📦 各服务商的用量:");
+    }
+    for entry in usage {
+        println!(
+            "   - {}: {} 次运行，{} 个根 CID，{} 个文件，{}",
+            entry.provider,
+            entry.runs,
+            entry.cids.len(),
+            entry.files,
+            format_bytes(entry.bytes)
+        );
+        if let Some(bytes) = entry.reported_bytes {
+            println!("     服务商统计的总大小: {}", format_bytes(bytes));
+        }
+        if let Some(pins) = entry.reported_pins {
+            println!("     服务商上已完成的 pin: {} 个", pins);
+        }
+    }
+    if projections.is_empty() {
+        return;
+    }
+    println!("\n💰 套餐:");
+    for projection in projections {
+        let tier = projection.tier;
+        let limit = tier
+            .storage_gb
+            .map_or("不限".to_string(), |gb| format!("{} GB", gb));
+        let icon = if projection.warnings.is_empty() {
+            "✅"
+        } else {
+            "⚠️ "
+        };
+        println!(
+            "{} {} {}: {:.2} GB / {}，{} 个文件，约 ${:.2}/月",
+            icon,
+            tier.provider,
+            tier.name,
+            projection.storage_gb,
+            limit,
+            projection.files,
+            projection.monthly_usd
+        );
+        for warning in &projection.warnings {
+            println!("   - {}", warning);
+        }
+    }
+}
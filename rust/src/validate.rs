@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use image::ImageReader;
+use serde::{Deserialize, Serialize};
+
+use crate::estimate::format_bytes;
+use crate::exit_code::ExitCode;
+use crate::standards::{detect_file_mime_type, mime_type_from_name};
+
+// ✅ config.toml 中的 [validation] 段：上传前逐个检查文件，不满足规则时在上传前终止
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ValidationConfig {
+    // 允许的格式 (扩展名，如 ["png", "jpg", "mp4"])，按文件内容判断，为空表示不限制
+    pub formats: Vec<String>,
+    // 图片宽高的范围 (像素)
+    pub min_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    // 单个文件的大小上限 (MB)
+    pub max_file_size_mb: Option<f64>,
+    // 图片必须是正方形
+    pub square: bool,
+    // 拒绝 CMYK 图片，浏览器和钱包显示的颜色会偏差
+    pub reject_cmyk: bool,
+}
+
+// 扩展名无法识别时的类型
+const UNKNOWN_MIME: &str = "application/octet-stream";
+
+impl ValidationConfig {
+    // 是否配置了任何规则
+    pub fn is_active(&self) -> bool {
+        !self.formats.is_empty()
+            || self.checks_dimensions()
+            || self.max_file_size_mb.is_some()
+            || self.reject_cmyk
+    }
+
+    fn checks_dimensions(&self) -> bool {
+        self.min_width.is_some()
+            || self.min_height.is_some()
+            || self.max_width.is_some()
+            || self.max_height.is_some()
+            || self.square
+    }
+
+    // 允许的格式对应的 MIME 类型
+    fn allowed_mime_types(&self) -> Vec<&'static str> {
+        self.formats
+            .iter()
+            .map(|format| mime_type_from_name(&format!(".{}", format)))
+            .collect()
+    }
+
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for format in &self.formats {
+            if mime_type_from_name(&format!(".{}", format)) == UNKNOWN_MIME {
+                problems.push(format!("formats 中有不支持的格式: {}", format));
+            }
+        }
+        for (name, min, max) in [
+            ("width", self.min_width, self.max_width),
+            ("height", self.min_height, self.max_height),
+        ] {
+            if let (Some(min), Some(max)) = (min, max)
+                && min > max
+            {
+                problems.push(format!(
+                    "min_{} ({}) 大于 max_{} ({})",
+                    name, min, name, max
+                ));
+            }
+        }
+        if self.max_file_size_mb.is_some_and(|mb| mb <= 0.0) {
+            problems.push("max_file_size_mb 必须大于 0".to_string());
+        }
+        problems
+    }
+}
+
+// 一个文件不满足的规则
+#[derive(Debug, Clone)]
+pub struct FileViolations {
+    pub path: PathBuf,
+    pub problems: Vec<String>,
+}
+
+// JPEG 帧头 (SOF) 中的颜色通道数，4 表示 CMYK
+fn jpeg_components(data: &[u8]) -> Option<u8> {
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        // SOF0-SOF15，排除 DHT (C4)、JPG (C8) 和 DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            return data.get(pos + 9).copied();
+        }
+        if marker == 0xDA {
+            return None;
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+// ✅ 检查一个文件，返回不满足的规则，为空表示通过
+pub fn validate_file(path: &Path, config: &ValidationConfig) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+    let size = fs::metadata(path)
+        .map_err(|e| anyhow!("❌ 读取 {:?} 失败: {}", path, e))?
+        .len();
+    if let Some(limit_mb) = config.max_file_size_mb {
+        let limit = (limit_mb * 1_000_000.0) as u64;
+        if size > limit {
+            problems.push(format!(
+                "文件大小 {} 超过上限 {} MB",
+                format_bytes(size),
+                limit_mb
+            ));
+        }
+    }
+
+    let mime = detect_file_mime_type(path)?;
+    let allowed = config.allowed_mime_types();
+    if !allowed.is_empty() && !allowed.contains(&mime.as_str()) {
+        problems.push(format!(
+            "格式 {} 不在允许的格式中 ({})",
+            mime,
+            config.formats.join(", ")
+        ));
+    }
+
+    // 尺寸和颜色只检查图片，视频、音频等文件跳过
+    if !mime.starts_with("image/") {
+        return Ok(problems);
+    }
+    if config.checks_dimensions() {
+        let dimensions = ImageReader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(|e| e.to_string())
+            .and_then(|reader| reader.into_dimensions().map_err(|e| e.to_string()));
+        match dimensions {
+            Ok((width, height)) => {
+                let size = format!("{}x{}", width, height);
+                if config.min_width.is_some_and(|min| width < min)
+                    || config.min_height.is_some_and(|min| height < min)
+                {
+                    problems.push(format!(
+                        "尺寸 {} 小于最小尺寸 {}x{}",
+                        size,
+                        config.min_width.unwrap_or_default(),
+                        config.min_height.unwrap_or_default()
+                    ));
+                }
+                if config.max_width.is_some_and(|max| width > max)
+                    || config.max_height.is_some_and(|max| height > max)
+                {
+                    problems.push(format!(
+                        "尺寸 {} 超过最大尺寸 {}x{}",
+                        size,
+                        config.max_width.map_or("-".to_string(), |w| w.to_string()),
+                        config.max_height.map_or("-".to_string(), |h| h.to_string())
+                    ));
+                }
+                if config.square && width != height {
+                    problems.push(format!("尺寸 {} 不是正方形", size));
+                }
+            }
+            Err(e) => problems.push(format!("无法读取图片尺寸: {}", e)),
+        }
+    }
+    if config.reject_cmyk && mime == "image/jpeg" {
+        let data = fs::read(path)?;
+        if jpeg_components(&data) == Some(4) {
+            problems.push("CMYK 颜色模式，请转换为 RGB (sRGB)".to_string());
+        }
+    }
+    Ok(problems)
+}
+
+// 检查所有文件，返回不通过的文件
+pub fn validate_files(files: &[PathBuf], config: &ValidationConfig) -> Result<Vec<FileViolations>> {
+    let mut violations = Vec::new();
+    for path in files {
+        let problems = validate_file(path, config)?;
+        if !problems.is_empty() {
+            violations.push(FileViolations {
+                path: path.clone(),
+                problems,
+            });
+        }
+    }
+    Ok(violations)
+}
+
+// 最多列出的文件数
+const MAX_LISTED_FILES: usize = 50;
+
+pub fn print_violations(violations: &[FileViolations]) {
+    for violation in violations.iter().take(MAX_LISTED_FILES) {
+        let name = violation
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        println!("❌ {}: {}", name, violation.problems.join("; "));
+    }
+    if violations.len() > MAX_LISTED_FILES {
+        println!(
+            "   ... 另有 {} 个文件不符合规则",
+            violations.len() - MAX_LISTED_FILES
+        );
+    }
+}
+
+// ✅ 上传前的检查：有文件不符合 [validation] 的规则时打印逐个文件的问题并返回错误
+pub fn check_files(files: &[PathBuf], config: &ValidationConfig) -> Result<()> {
+    if !config.is_active() || files.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "\n--- 正在按 [validation] 的规则检查 {} 个文件 ---",
+        files.len()
+    );
+    let violations = validate_files(files, config)?;
+    if violations.is_empty() {
+        println!("✅ 所有文件都符合规则");
+        return Ok(());
+    }
+    print_violations(&violations);
+    Err(ExitCode::Validation.wrap(anyhow!(
+        "❌ {} / {} 个文件不符合 [validation] 的规则，已在上传前终止",
+        violations.len(),
+        files.len()
+    )))
+}
@@ -0,0 +1,140 @@
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::i18n;
+
+// ✅ 输出的详细程度：-q 只留警告和错误，-v 多输出配置来源和用时，-vv 再输出每个节点请求
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Trace,
+}
+
+impl Verbosity {
+    // -q 和 -v 的次数换算成级别
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            _ => Self::Trace,
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+// -q 时缩进的详情行跟随上一条消息：警告显示，它下面的详情也显示
+static LAST_SHOWN: AtomicBool = AtomicBool::new(true);
+
+pub fn set_verbosity(level: Verbosity) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Trace,
+    }
+}
+
+// 消息开头的 emoji 决定它的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Error,
+    Warning,
+    Success,
+    Prompt,
+    Detail,
+    Other,
+}
+
+fn kind_of(message: &str) -> Kind {
+    let line = message.trim_start_matches('\n');
+    if line.is_empty() || line.starts_with([' ', '\t']) {
+        return Kind::Detail;
+    }
+    match line.chars().next() {
+        Some('❌') => Kind::Error,
+        Some('⚠') => Kind::Warning,
+        Some('✅') => Kind::Success,
+        Some('❓') => Kind::Prompt,
+        _ => Kind::Other,
+    }
+}
+
+// ✅ 标准输出在 -q 时只保留警告、错误和交互提问；标准错误总是输出
+fn visible(kind: Kind, stderr: bool) -> bool {
+    if stderr || verbosity() > Verbosity::Quiet {
+        return true;
+    }
+    match kind {
+        Kind::Error | Kind::Warning | Kind::Prompt => {
+            LAST_SHOWN.store(true, Ordering::Relaxed);
+            true
+        }
+        Kind::Detail => LAST_SHOWN.load(Ordering::Relaxed),
+        Kind::Success | Kind::Other => {
+            LAST_SHOWN.store(false, Ordering::Relaxed);
+            false
+        }
+    }
+}
+
+// NO_COLOR (https://no-color.org) 设置为非空值或 TERM=dumb 时不使用颜色
+pub fn color_allowed() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+    !no_color && !dumb
+}
+
+// 只有输出到终端时才上色，重定向到文件、管道或仪表盘日志时保持纯文本
+fn use_color(stderr: bool) -> bool {
+    let terminal = if stderr {
+        io::stderr().is_terminal()
+    } else {
+        io::stdout().is_terminal()
+    };
+    terminal && color_allowed()
+}
+
+fn paint(text: Cow<'_, str>, kind: Kind, stderr: bool) -> Cow<'_, str> {
+    let code = match kind {
+        Kind::Error => "31",
+        Kind::Warning => "33",
+        Kind::Success => "32",
+        _ => return text,
+    };
+    if !use_color(stderr) {
+        return text;
+    }
+    Cow::Owned(format!("\x1b[{}m{}\x1b[0m", code, text))
+}
+
+// ✅ 打印宏的出口：按级别过滤，翻译，再按终端决定是否上色
+pub fn emit(message: &str, stderr: bool, newline: bool) {
+    let kind = kind_of(message);
+    if !visible(kind, stderr) {
+        return;
+    }
+    let text = paint(i18n::localize(message), kind, stderr);
+    match (stderr, newline) {
+        (false, true) => std::println!("{}", text),
+        (false, false) => std::print!("{}", text),
+        (true, true) => std::eprintln!("{}", text),
+        (true, false) => std::eprint!("{}", text),
+    }
+}
+
+// -v / -vv 的诊断信息写到标准错误，不影响脚本读取标准输出
+pub fn emit_at(level: Verbosity, message: &str) {
+    if verbosity() >= level {
+        emit(message, true, true);
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::backend::IpfsBackend;
+use crate::cancel;
+use crate::hashing::{HashAlgorithm, algorithm_of, hash_file};
+use crate::manifest::{CollectionKind, MANIFEST_FILE_NAME, Manifest, TokenEntry};
+use crate::metadata::{build_token_metadata, metadata_file_name};
+use crate::poster::POSTERS_DIR;
+use crate::preview::{PREVIEWS_DIR, PreviewConfig};
+use crate::sanitize::strip_and_report;
+use crate::standards::MetadataFormat;
+use crate::urls::{UrlConfig, record_urls};
+use crate::validate::{ValidationConfig, validate_file};
+use crate::workflow::{
+    file_ref, prepare_media, record_served_by, strip_display_image, upload_json_str_to_ipfs,
+    warn_name,
+};
+
+// 等待文件事件时检查中断标记的间隔
+const CANCEL_POLL: Duration = Duration::from_millis(500);
+
+// watch 模式的可选项
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    // 文件事件静默多久后才开始处理，避免上传写了一半的文件
+    pub debounce: Duration,
+    pub hash_algorithm: HashAlgorithm,
+    // 元数据文件名是否带 .json 后缀
+    pub json_suffix: bool,
+    // 元数据标准 (ERC-721、Metaplex)
+    pub format: MetadataFormat,
+    // 大图的预览图
+    pub preview: PreviewConfig,
+    // 保留图片中的 EXIF / XMP 等信息 (默认上传前删除)
+    pub keep_exif: bool,
+    // 上传前检查文件的规则
+    pub validation: ValidationConfig,
+    // 清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(1000),
+            hash_algorithm: HashAlgorithm::default(),
+            json_suffix: false,
+            format: MetadataFormat::default(),
+            preview: PreviewConfig::default(),
+            keep_exif: false,
+            validation: ValidationConfig::default(),
+            urls: UrlConfig::default(),
+        }
+    }
+}
+
+// ✅ 持续监听一个文件夹：新文件或修改过的文件会被上传、生成元数据并追加到清单中
+pub fn watch_directory(
+    backend: &dyn IpfsBackend,
+    watch_dir: &Path,
+    output_root: &Path,
+    options: &WatchOptions,
+) -> Result<()> {
+    if !watch_dir.is_dir() {
+        return Err(anyhow!("❌ 监听的文件夹不存在: {:?}", watch_dir));
+    }
+    let dir_name = watch_dir
+        .canonicalize()?
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("无效的文件夹名"))?;
+
+    let mut session = WatchSession::open(
+        backend,
+        &output_root.join(format!("watch_{}", dir_name)),
+        options,
+    )?;
+
+    println!("\n==============================================");
+    println!("👀 开始监听文件夹: {:?}", watch_dir);
+    println!("   - 输出目录: {:?}", session.output_dir);
+    println!("   - 按 Ctrl-C 退出");
+    println!("==============================================");
+
+    // 先补齐监听开始前就已存在、但还没有记录在清单里的文件
+    let existing: BTreeSet<PathBuf> = fs::read_dir(watch_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    session.process(existing)?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(watch_dir, RecursiveMode::NonRecursive)?;
+
+    while !cancel::is_cancelled() {
+        // 等待第一个事件 (定时检查是否被中断)，然后一直收集到静默为止
+        let first = match rx.recv_timeout(CANCEL_POLL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        let mut pending = BTreeSet::new();
+        collect_event_paths(first, &mut pending);
+        while let Ok(event) = rx.recv_timeout(options.debounce) {
+            collect_event_paths(event, &mut pending);
+        }
+        session.process(pending)?;
+    }
+    println!("👋 已停止监听，清单保存在 {:?}", session.output_dir);
+    Ok(())
+}
+
+fn collect_event_paths(event: notify::Result<notify::Event>, pending: &mut BTreeSet<PathBuf>) {
+    match event {
+        Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+            pending.extend(event.paths);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️  文件监听出错: {}", e),
+    }
+}
+
+struct WatchSession<'a> {
+    backend: &'a dyn IpfsBackend,
+    output_dir: PathBuf,
+    manifest: Manifest,
+    hash_algorithm: HashAlgorithm,
+    json_suffix: bool,
+    format: MetadataFormat,
+    preview: PreviewConfig,
+    keep_exif: bool,
+    validation: ValidationConfig,
+    urls: UrlConfig,
+    // 已处理文件的 (大小, 修改时间)，用来过滤重复事件
+    seen: HashMap<PathBuf, (u64, SystemTime)>,
+}
+
+impl<'a> WatchSession<'a> {
+    fn open(
+        backend: &'a dyn IpfsBackend,
+        output_dir: &Path,
+        options: &WatchOptions,
+    ) -> Result<Self> {
+        fs::create_dir_all(output_dir.join("images"))?;
+        fs::create_dir_all(output_dir.join("metadata"))?;
+
+        // 继续之前的 watch 会话，新的 token 追加到已有清单
+        let manifest = if output_dir.join(MANIFEST_FILE_NAME).exists() {
+            let manifest = Manifest::load(output_dir)?;
+            // 同一个集合不能混用加密和未加密 (或不同密钥) 的内容
+            if manifest.encryption != backend.encryption() {
+                return Err(anyhow!(
+                    "❌ {:?} 中已有的清单与当前的加密设置 ([encryption]) 不一致",
+                    output_dir
+                ));
+            }
+            manifest
+        } else {
+            Manifest {
+                kind: CollectionKind::Watch,
+                created_at: Utc::now().to_rfc3339(),
+                backend: backend.name().to_string(),
+                node_version: backend.node_version(),
+                output_dir: output_dir.to_path_buf(),
+                image_cid: String::new(),
+                posters_cid: None,
+                previews_cid: None,
+                watermarked_cid: None,
+                localization_cid: None,
+                metadata_cid: String::new(),
+                token_uri: String::new(),
+                tokens: Vec::new(),
+                served_by: Default::default(),
+                urls: Default::default(),
+                encryption: backend.encryption(),
+                ipld: None,
+                filecoin: BTreeMap::new(),
+                remote_pins: BTreeMap::new(),
+            }
+        };
+
+        Ok(Self {
+            backend,
+            output_dir: output_dir.to_path_buf(),
+            manifest,
+            hash_algorithm: options.hash_algorithm,
+            json_suffix: options.json_suffix,
+            format: options.format.clone(),
+            preview: options.preview.clone(),
+            keep_exif: options.keep_exif,
+            validation: options.validation.clone(),
+            urls: options.urls.clone(),
+            seen: HashMap::new(),
+        })
+    }
+
+    fn process(&mut self, paths: BTreeSet<PathBuf>) -> Result<()> {
+        let mut changed = false;
+        for path in paths {
+            // 被中断时剩下的文件留到下次启动时补齐 (清单只在这里更新，不会写入一半)
+            if cancel::is_cancelled() {
+                return Ok(());
+            }
+            match self.process_file(&path) {
+                Ok(true) => changed = true,
+                Ok(false) => {}
+                // 单个文件失败不应该中断整个监听
+                Err(e) => eprintln!("❌ 处理 {:?} 失败: {:#}", path, e),
+            }
+        }
+        if !changed {
+            return Ok(());
+        }
+
+        // 重新生成文件夹 CID，清单里始终保留一个最新的 Base URI
+        self.manifest.image_cid = self.backend.add_path(&self.output_dir.join("images"))?;
+        let posters_dir = self.output_dir.join(POSTERS_DIR);
+        if posters_dir.is_dir() {
+            self.manifest.posters_cid = Some(self.backend.add_path(&posters_dir)?);
+        }
+        let previews_dir = self.output_dir.join(PREVIEWS_DIR);
+        if previews_dir.is_dir() {
+            self.manifest.previews_cid = Some(self.backend.add_path(&previews_dir)?);
+        }
+        self.manifest.metadata_cid = self.backend.add_path(&self.output_dir.join("metadata"))?;
+        self.manifest.token_uri = format!("ipfs://{}/", self.manifest.metadata_cid);
+        self.manifest.tokens.sort_by_key(|token| token.token_id);
+        record_served_by(&mut self.manifest, self.backend);
+        record_urls(&mut self.manifest, &self.urls);
+        self.manifest.save(&self.output_dir)?;
+        println!(
+            "\n📄 清单已更新: 共 {} 个 token，最新 Base URI: {}",
+            self.manifest.tokens.len(),
+            self.manifest.token_uri
+        );
+        Ok(())
+    }
+
+    // 返回是否有新的上传
+    fn process_file(&mut self, path: &Path) -> Result<bool> {
+        let Some(image_filename) = path.file_name().and_then(|s| s.to_str()) else {
+            return Ok(false);
+        };
+        if !path.is_file() || image_filename.starts_with('.') {
+            return Ok(false);
+        }
+
+        let meta = fs::metadata(path)?;
+        let fingerprint = (meta.len(), meta.modified()?);
+        if self.seen.get(path) == Some(&fingerprint) {
+            return Ok(false);
+        }
+
+        let token_id_str = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("无效的文件名"))?;
+        let Ok(token_id) = token_id_str.parse::<u64>() else {
+            println!("⚠️  跳过 {}: 文件名不是数字 token ID", image_filename);
+            self.seen.insert(path.to_path_buf(), fingerprint);
+            return Ok(false);
+        };
+
+        // 不符合 [validation] 规则的文件跳过，修改后会重新检查
+        if self.validation.is_active() {
+            let problems = validate_file(path, &self.validation)?;
+            if !problems.is_empty() {
+                println!("❌ 跳过 {}: {}", image_filename, problems.join("; "));
+                self.seen.insert(path.to_path_buf(), fingerprint);
+                return Ok(false);
+            }
+        }
+
+        // 先复制到 images/ 并清理 EXIF，变更检测和上传都使用清理后的文件
+        let images_dir = self.output_dir.join("images");
+        let image_path = images_dir.join(image_filename);
+        fs::copy(path, &image_path)?;
+        if !self.keep_exif {
+            strip_and_report(&image_path)?;
+        }
+        let existing = self
+            .manifest
+            .tokens
+            .iter()
+            .position(|token| token.image_file == image_filename);
+        // 清单里已有且内容哈希相同，说明是上次会话处理过的文件
+        if let Some(index) = existing {
+            let recorded = self.manifest.tokens[index].image_hash.as_deref();
+            let algorithm = recorded
+                .and_then(algorithm_of)
+                .unwrap_or(self.hash_algorithm);
+            if recorded == Some(hash_file(&image_path, algorithm)?.as_str()) {
+                self.seen.insert(path.to_path_buf(), fingerprint);
+                return Ok(false);
+            }
+            println!("\n🔁 检测到文件变更: {}", image_filename);
+            self.manifest.tokens.remove(index);
+        } else {
+            println!("\n🆕 检测到新文件: {}", image_filename);
+        }
+
+        let image_cid = self.backend.add_path(&image_path)?;
+        let (mut media, display) =
+            prepare_media(path, image_filename, &self.output_dir, &self.preview)?;
+        strip_display_image(display.as_ref(), self.keep_exif)?;
+        let add = self.backend.add_config();
+        let image_uri = format!("ipfs://{}", file_ref(&image_cid, image_filename, &add));
+        let mut metadata = build_token_metadata(token_id, image_uri);
+        self.format.collection.apply(&mut metadata, Some(token_id));
+        if let Some(display) = &display {
+            let cid = self.backend.add_path(display.path())?;
+            let uri = format!("ipfs://{}", file_ref(&cid, display.file_name(), &add));
+            display.apply(&mut metadata, &mut media, uri);
+        }
+        warn_name(&self.format, &metadata.name);
+        let document = self.format.render(&metadata, &media, Some(token_id));
+        let file_name = metadata_file_name(token_id_str, self.json_suffix);
+        let metadata_cid = upload_json_str_to_ipfs(
+            self.backend,
+            &document,
+            self.format.json_style,
+            &self.output_dir.join("metadata").join(&file_name),
+        )?;
+
+        self.manifest.tokens.push(TokenEntry {
+            token_id: Some(token_id),
+            name: metadata.name,
+            image_file: image_filename.to_string(),
+            image_hash: Some(hash_file(&image_path, self.hash_algorithm)?),
+            image_uri: metadata.image,
+            mime_type: Some(media.mime_type),
+            animation_uri: metadata.animation_url,
+            original_uri: media.original_uri,
+            metadata_file: file_name,
+            metadata_cid: Some(metadata_cid),
+            arweave: None,
+        });
+        self.seen.insert(path.to_path_buf(), fingerprint);
+        Ok(true)
+    }
+}
@@ -0,0 +1,357 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageFormat, ImageReader, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::preview::supports_preview;
+use crate::standards::detect_file_mime_type;
+
+// 加水印的副本保存在输出目录下的这个文件夹中，文件名与 images/ 相同，整体上传一次
+pub const WATERMARKED_DIR: &str = "watermarked";
+
+// ✅ 水印的位置，tile 表示平铺整张图片
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum WatermarkPosition {
+    Center,
+    #[default]
+    BottomRight,
+    BottomLeft,
+    TopRight,
+    TopLeft,
+    Tile,
+}
+
+impl WatermarkPosition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Center => "center",
+            Self::BottomRight => "bottom-right",
+            Self::BottomLeft => "bottom-left",
+            Self::TopRight => "top-right",
+            Self::TopLeft => "top-left",
+            Self::Tile => "tile",
+        }
+    }
+}
+
+impl fmt::Display for WatermarkPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WatermarkPosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "center" => Ok(Self::Center),
+            "bottom-right" => Ok(Self::BottomRight),
+            "bottom-left" => Ok(Self::BottomLeft),
+            "top-right" => Ok(Self::TopRight),
+            "top-left" => Ok(Self::TopLeft),
+            "tile" => Ok(Self::Tile),
+            other => Err(format!(
+                "❌ 不支持的水印位置: {} (可选: center, bottom-right, bottom-left, top-right, top-left, tile)",
+                other
+            )),
+        }
+    }
+}
+
+// ✅ config.toml 中的 [watermark] 段：为公开预览生成加水印的副本，原图保留用于揭晓
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WatermarkConfig {
+    pub enabled: bool,
+    // 水印 PNG (带透明通道)，填写后忽略 text
+    pub image: Option<PathBuf>,
+    // 文字水印，只支持 ASCII 字母、数字和常用符号 (小写按大写显示)
+    pub text: String,
+    pub position: WatermarkPosition,
+    // 不透明度 (0-1)
+    pub opacity: f32,
+    // 水印宽度占图片宽度的比例 (0-1)
+    pub scale: f32,
+}
+
+impl Default for WatermarkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            image: None,
+            text: String::new(),
+            position: WatermarkPosition::default(),
+            opacity: 0.5,
+            scale: 0.3,
+        }
+    }
+}
+
+impl WatermarkConfig {
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        match &self.image {
+            Some(path) if !path.is_file() => {
+                problems.push(format!("水印图片不存在: {:?}", path));
+            }
+            Some(_) => {}
+            None if self.text.trim().is_empty() => {
+                problems.push("需要填写 image (水印 PNG) 或 text (文字水印)".to_string());
+            }
+            None => {
+                let unsupported: String =
+                    self.text.chars().filter(|c| glyph(*c).is_none()).collect();
+                if !unsupported.is_empty() {
+                    problems.push(format!(
+                        "文字水印中有不支持的字符 \"{}\"，请改用 PNG 水印",
+                        unsupported
+                    ));
+                }
+            }
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            problems.push(format!("opacity 应在 0-1 之间: {}", self.opacity));
+        }
+        if !(self.scale > 0.0 && self.scale <= 1.0) {
+            problems.push(format!("scale 应在 0-1 之间: {}", self.scale));
+        }
+        problems
+    }
+}
+
+// 内置 5x7 点阵字体，每行 5 位，最高位在左
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+fn glyph(c: char) -> Option<[u8; 7]> {
+    let rows = match c.to_ascii_uppercase() {
+        ' ' => [0, 0, 0, 0, 0, 0, 0],
+        '0' => [14, 17, 19, 21, 25, 17, 14],
+        '1' => [4, 12, 4, 4, 4, 4, 14],
+        '2' => [14, 17, 1, 2, 4, 8, 31],
+        '3' => [31, 2, 4, 2, 1, 17, 14],
+        '4' => [2, 6, 10, 18, 31, 2, 2],
+        '5' => [31, 16, 30, 1, 1, 17, 14],
+        '6' => [6, 8, 16, 30, 17, 17, 14],
+        '7' => [31, 1, 2, 4, 8, 8, 8],
+        '8' => [14, 17, 17, 14, 17, 17, 14],
+        '9' => [14, 17, 17, 15, 1, 2, 12],
+        'A' => [14, 17, 17, 31, 17, 17, 17],
+        'B' => [30, 17, 17, 30, 17, 17, 30],
+        'C' => [14, 17, 16, 16, 16, 17, 14],
+        'D' => [28, 18, 17, 17, 17, 18, 28],
+        'E' => [31, 16, 16, 30, 16, 16, 31],
+        'F' => [31, 16, 16, 30, 16, 16, 16],
+        'G' => [14, 17, 16, 23, 17, 17, 15],
+        'H' => [17, 17, 17, 31, 17, 17, 17],
+        'I' => [14, 4, 4, 4, 4, 4, 14],
+        'J' => [7, 2, 2, 2, 2, 18, 12],
+        'K' => [17, 18, 20, 24, 20, 18, 17],
+        'L' => [16, 16, 16, 16, 16, 16, 31],
+        'M' => [17, 27, 21, 21, 17, 17, 17],
+        'N' => [17, 17, 25, 21, 19, 17, 17],
+        'O' => [14, 17, 17, 17, 17, 17, 14],
+        'P' => [30, 17, 17, 30, 16, 16, 16],
+        'Q' => [14, 17, 17, 17, 21, 18, 13],
+        'R' => [30, 17, 17, 30, 20, 18, 17],
+        'S' => [15, 16, 16, 14, 1, 1, 30],
+        'T' => [31, 4, 4, 4, 4, 4, 4],
+        'U' => [17, 17, 17, 17, 17, 17, 14],
+        'V' => [17, 17, 17, 17, 17, 10, 4],
+        'W' => [17, 17, 17, 21, 21, 21, 10],
+        'X' => [17, 17, 10, 4, 10, 17, 17],
+        'Y' => [17, 17, 17, 10, 4, 4, 4],
+        'Z' => [31, 1, 2, 4, 8, 16, 31],
+        '.' => [0, 0, 0, 0, 0, 12, 12],
+        ',' => [0, 0, 0, 0, 12, 4, 8],
+        '-' => [0, 0, 0, 31, 0, 0, 0],
+        '_' => [0, 0, 0, 0, 0, 0, 31],
+        '!' => [4, 4, 4, 4, 4, 0, 4],
+        '?' => [14, 17, 1, 2, 4, 0, 4],
+        ':' => [0, 12, 12, 0, 12, 12, 0],
+        '/' => [1, 1, 2, 4, 8, 16, 16],
+        '#' => [10, 10, 31, 10, 31, 10, 10],
+        '&' => [12, 18, 20, 8, 21, 18, 13],
+        '@' => [14, 17, 1, 13, 21, 21, 14],
+        '(' => [2, 4, 8, 8, 8, 4, 2],
+        ')' => [8, 4, 2, 2, 2, 4, 8],
+        '\'' => [12, 4, 8, 0, 0, 0, 0],
+        '©' => [14, 17, 23, 21, 23, 17, 14],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+// 文字按 1 像素一个点绘制：白色文字加右下方的黑色阴影，深色和浅色背景上都能看清
+fn render_text(text: &str) -> RgbaImage {
+    let glyphs: Vec<[u8; 7]> = text.trim().chars().filter_map(glyph).collect();
+    let cell = GLYPH_WIDTH + 1;
+    let mut canvas = RgbaImage::new(glyphs.len() as u32 * cell + 1, GLYPH_HEIGHT + 1);
+    for (shadow, color) in [(1, Rgba([0, 0, 0, 160])), (0, Rgba([255, 255, 255, 255]))] {
+        for (index, rows) in glyphs.iter().enumerate() {
+            for (y, row) in rows.iter().enumerate() {
+                for x in 0..GLYPH_WIDTH {
+                    if row & (1 << (GLYPH_WIDTH - 1 - x)) != 0 {
+                        canvas.put_pixel(
+                            index as u32 * cell + x + shadow,
+                            y as u32 + shadow,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    canvas
+}
+
+// 加载好的水印，批量处理时只加载一次
+pub struct Watermark {
+    overlay: RgbaImage,
+    // 文字点阵放大时使用最近邻，保持边缘清晰
+    pixelated: bool,
+    position: WatermarkPosition,
+    opacity: f32,
+    scale: f32,
+}
+
+impl Watermark {
+    pub fn load(config: &WatermarkConfig) -> Result<Self> {
+        let (overlay, pixelated) = match &config.image {
+            Some(path) => {
+                let overlay = ImageReader::open(path)?
+                    .with_guessed_format()?
+                    .decode()
+                    .map_err(|e| anyhow!("❌ 解码水印图片 {:?} 失败: {}", path, e))?;
+                (overlay.to_rgba8(), false)
+            }
+            None => (render_text(&config.text), true),
+        };
+        Ok(Self {
+            overlay,
+            pixelated,
+            position: config.position,
+            opacity: config.opacity,
+            scale: config.scale,
+        })
+    }
+
+    // 按图片宽度缩放水印并应用不透明度
+    fn scaled_overlay(&self, width: u32, height: u32) -> RgbaImage {
+        let target_width = ((width as f32 * self.scale) as u32).max(1);
+        let target_height = ((self.overlay.height() as f32 * target_width as f32
+            / self.overlay.width() as f32) as u32)
+            .clamp(1, height.max(1));
+        let filter = if self.pixelated {
+            FilterType::Nearest
+        } else {
+            FilterType::Lanczos3
+        };
+        let mut overlay = imageops::resize(&self.overlay, target_width, target_height, filter);
+        for pixel in overlay.pixels_mut() {
+            pixel[3] = (pixel[3] as f32 * self.opacity).round() as u8;
+        }
+        overlay
+    }
+
+    // ✅ 在图片上叠加水印，返回新的图片
+    pub fn apply(&self, image: &RgbaImage) -> RgbaImage {
+        let (width, height) = image.dimensions();
+        let overlay = self.scaled_overlay(width, height);
+        let (ow, oh) = (overlay.width() as i64, overlay.height() as i64);
+        let (w, h) = (width as i64, height as i64);
+        let margin = (w.min(h) / 40).max(1);
+        let mut output = image.clone();
+        let positions: Vec<(i64, i64)> = match self.position {
+            WatermarkPosition::Center => vec![((w - ow) / 2, (h - oh) / 2)],
+            WatermarkPosition::BottomRight => vec![(w - ow - margin, h - oh - margin)],
+            WatermarkPosition::BottomLeft => vec![(margin, h - oh - margin)],
+            WatermarkPosition::TopRight => vec![(w - ow - margin, margin)],
+            WatermarkPosition::TopLeft => vec![(margin, margin)],
+            WatermarkPosition::Tile => {
+                // 水印之间留出与水印同样大小的空隙，奇数行错开半个水印
+                let (step_x, step_y) = (ow * 2, oh * 3);
+                let mut positions = Vec::new();
+                let mut row = 0;
+                let mut y = oh / 2;
+                while y < h {
+                    let mut x = if row % 2 == 0 { 0 } else { -ow };
+                    while x < w {
+                        positions.push((x, y));
+                        x += step_x;
+                    }
+                    y += step_y;
+                    row += 1;
+                }
+                positions
+            }
+        };
+        for (x, y) in positions {
+            imageops::overlay(&mut output, &overlay, x, y);
+        }
+        output
+    }
+}
+
+// JPEG 副本的编码质量
+const WATERMARK_JPEG_QUALITY: u8 = 90;
+
+// ✅ 为一张图片生成加水印的副本 dir/<文件名>，格式与原图相同；GIF、视频等不支持的文件返回 None
+pub fn watermark_file(source: &Path, dir: &Path, watermark: &Watermark) -> Result<Option<PathBuf>> {
+    let mime = detect_file_mime_type(source)?;
+    if !supports_preview(&mime) {
+        return Ok(None);
+    }
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| anyhow!("无效的文件名: {:?}", source))?;
+    let reader = ImageReader::open(source)?.with_guessed_format()?;
+    let format = reader.format();
+    let image = reader
+        .decode()
+        .map_err(|e| anyhow!("❌ 解码图片 {:?} 失败: {}", source, e))?;
+    let marked = watermark.apply(&image.to_rgba8());
+    fs::create_dir_all(dir)?;
+    let path = dir.join(file_name);
+    let written = match format {
+        Some(ImageFormat::Jpeg) => {
+            let writer = BufWriter::new(File::create(&path)?);
+            JpegEncoder::new_with_quality(writer, WATERMARK_JPEG_QUALITY)
+                .encode_image(&DynamicImage::ImageRgba8(marked).to_rgb8())
+        }
+        Some(format) => marked.save_with_format(&path, format),
+        None => marked.save_with_format(&path, ImageFormat::Png),
+    };
+    written.map_err(|e| anyhow!("❌ 写入水印图片 {:?} 失败: {}", path, e))?;
+    Ok(Some(path))
+}
+
+// 为每张图片生成加水印的副本，保持文件相对于 source_root 的路径，返回生成的文件数
+pub fn watermark_files(
+    files: &[PathBuf],
+    source_root: &Path,
+    dir: &Path,
+    watermark: &Watermark,
+) -> Result<usize> {
+    let mut count = 0;
+    for file in files {
+        let subdir = file
+            .strip_prefix(source_root)
+            .ok()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""));
+        if watermark_file(file, &dir.join(subdir), watermark)?.is_some() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::manifest::Manifest;
+
+// 通知的格式：通用 JSON、Slack incoming webhook、Discord webhook
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    #[default]
+    Json,
+    Slack,
+    Discord,
+}
+
+// ✅ config.toml 中的一个 [[webhooks]] 条目
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+}
+
+// 一次工作流结束时发送的事件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorkflowEvent {
+    // "single" / "batch"
+    pub workflow: String,
+    pub success: bool,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl WorkflowEvent {
+    pub fn completed(workflow: &str, manifest: &Manifest) -> Self {
+        Self {
+            workflow: workflow.to_string(),
+            success: true,
+            summary: format!(
+                "✅ {} 上传完成: {} 个 token，输出目录 {}",
+                workflow,
+                manifest.tokens.len(),
+                manifest.output_dir.display()
+            ),
+            image_cid: Some(manifest.image_cid.clone()),
+            metadata_cid: Some(manifest.metadata_cid.clone()),
+            token_uri: Some(manifest.token_uri.clone()),
+            error: None,
+        }
+    }
+
+    pub fn failed(workflow: &str, error: &anyhow::Error) -> Self {
+        Self {
+            workflow: workflow.to_string(),
+            success: false,
+            summary: format!("❌ {} 上传失败", workflow),
+            image_cid: None,
+            metadata_cid: None,
+            token_uri: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    // Slack / Discord 直接显示的文本
+    fn text(&self) -> String {
+        let mut lines = vec![self.summary.clone()];
+        if let Some(cid) = &self.image_cid {
+            lines.push(format!("图片 CID: {}", cid));
+        }
+        if let Some(cid) = &self.metadata_cid {
+            lines.push(format!("元数据 CID: {}", cid));
+        }
+        if let Some(uri) = &self.token_uri {
+            lines.push(format!("Token URI: {}", uri));
+        }
+        if let Some(error) = &self.error {
+            lines.push(format!("错误: {}", error));
+        }
+        lines.join("\n")
+    }
+}
+
+fn payload(kind: WebhookKind, event: &WorkflowEvent) -> Result<Value> {
+    Ok(match kind {
+        WebhookKind::Json => serde_json::to_value(event)?,
+        WebhookKind::Slack => json!({ "text": event.text() }),
+        WebhookKind::Discord => json!({ "content": event.text() }),
+    })
+}
+
+fn send(
+    client: &reqwest::blocking::Client,
+    hook: &WebhookConfig,
+    event: &WorkflowEvent,
+) -> Result<()> {
+    let response = client
+        .post(&hook.url)
+        .json(&payload(hook.kind, event)?)
+        .send()?;
+    if !response.status().is_success() {
+        return Err(anyhow!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+// ✅ 依次通知所有 webhook；通知失败只打印警告，不影响上传结果
+pub fn notify_all(hooks: &[WebhookConfig], event: &WorkflowEvent) {
+    if hooks.is_empty() {
+        return;
+    }
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("⚠️  无法创建 webhook 客户端: {}", e);
+            return;
+        }
+    };
+    for hook in hooks {
+        match send(&client, hook, event) {
+            Ok(()) => println!("🔔 已发送通知: {}", hook.url),
+            Err(e) => eprintln!("⚠️  发送通知到 {} 失败: {:#}", hook.url, e),
+        }
+    }
+}
+
+// 根据工作流结果发送通知，并原样返回结果
+pub fn report(
+    hooks: &[WebhookConfig],
+    workflow: &str,
+    result: Result<Manifest>,
+) -> Result<Manifest> {
+    let event = match &result {
+        Ok(manifest) => WorkflowEvent::completed(workflow, manifest),
+        Err(e) => WorkflowEvent::failed(workflow, e),
+    };
+    notify_all(hooks, &event);
+    result
+}
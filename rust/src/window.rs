@@ -0,0 +1,184 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chrono::{Local, NaiveTime, Timelike};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::compat::NodeCapabilities;
+use crate::ipld::DagCodec;
+use crate::manifest::EncryptionInfo;
+use crate::unixfs::AddConfig;
+
+// ✅ 允许传输的时间段 (本地时间)，例如 01:00-06:00；结束早于开始表示跨越午夜
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ScheduleWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    // 距离窗口开始还要等多久；已在窗口内时返回 0
+    pub fn wait_from(&self, time: NaiveTime) -> Duration {
+        if self.contains(time) {
+            return Duration::ZERO;
+        }
+        let seconds = (self.start - time).num_seconds().rem_euclid(24 * 3600);
+        Duration::from_secs(seconds as u64)
+    }
+
+    // 阻塞直到进入窗口
+    pub fn wait(&self) {
+        let now = Local::now().time();
+        let wait = self.wait_from(now);
+        if wait.is_zero() {
+            return;
+        }
+        println!(
+            "⏸️  当前时间 {:02}:{:02} 不在传输窗口 {} 内，约 {} 分钟后继续...",
+            now.hour(),
+            now.minute(),
+            self,
+            wait.as_secs().div_ceil(60)
+        );
+        thread::sleep(wait);
+        println!("▶️  进入传输窗口 {}，继续上传", self);
+    }
+}
+
+impl fmt::Display for ScheduleWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+impl FromStr for ScheduleWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || anyhow!("❌ 无效的传输窗口 `{}`，格式应为 HH:MM-HH:MM", s);
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+        if start == end {
+            return Err(anyhow!("❌ 传输窗口 `{}` 的开始和结束时间相同", s));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+// 在 config.toml 中写成字符串: schedule_window = "01:00-06:00"
+impl Serialize for ScheduleWindow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ScheduleWindow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+// ✅ 包装任意后端：每次上传前等待进入传输窗口，窗口外自动暂停
+// 已经开始的单次上传不会被中断
+pub struct WindowedBackend {
+    inner: Box<dyn IpfsBackend>,
+    window: ScheduleWindow,
+}
+
+impl WindowedBackend {
+    pub fn new(inner: Box<dyn IpfsBackend>, window: ScheduleWindow) -> Self {
+        Self { inner, window }
+    }
+}
+
+impl IpfsBackend for WindowedBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check_compatibility(&mut self) -> Result<Option<NodeCapabilities>> {
+        self.inner.check_compatibility()
+    }
+
+    fn add_path(&self, target_path: &Path) -> Result<String> {
+        self.window.wait();
+        self.inner.add_path(target_path)
+    }
+
+    fn add_bytes(&self, data: &[u8]) -> Result<String> {
+        self.window.wait();
+        self.inner.add_bytes(data)
+    }
+
+    fn dag_put(&self, node: &Value, codec: DagCodec) -> Result<String> {
+        self.window.wait();
+        self.inner.dag_put(node, codec)
+    }
+
+    fn add_config(&self) -> AddConfig {
+        self.inner.add_config()
+    }
+
+    fn is_pinned(&self, cid: &str) -> Result<bool> {
+        self.inner.is_pinned(cid)
+    }
+
+    fn pin(&self, cid: &str) -> Result<()> {
+        self.window.wait();
+        self.inner.pin(cid)
+    }
+
+    fn unpin(&self, cid: &str) -> Result<()> {
+        self.inner.unpin(cid)
+    }
+
+    fn ls(&self, path: &str) -> Result<Vec<DirEntry>> {
+        self.inner.ls(path)
+    }
+
+    fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.cat(path)
+    }
+
+    fn provide(&self, cid: &str, recursive: bool) -> Result<()> {
+        self.inner.provide(cid, recursive)
+    }
+
+    fn find_providers(&self, cid: &str, limit: usize) -> Result<Vec<String>> {
+        self.inner.find_providers(cid, limit)
+    }
+
+    fn served_by(&self, cid: &str) -> Option<String> {
+        self.inner.served_by(cid)
+    }
+
+    fn encryption(&self) -> Option<EncryptionInfo> {
+        self.inner.encryption()
+    }
+
+    fn node_version(&self) -> Option<String> {
+        self.inner.node_version()
+    }
+}
@@ -0,0 +1,1141 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::backend::{DirEntry, IpfsBackend};
+use crate::checksums::{ChecksumConfig, write_checksums};
+use crate::cid::CidInfo;
+use crate::copy::{CopyOptions, CopyReport, copy_tree};
+use crate::copy_directory;
+use crate::duplicates::{check_duplicate_content, find_duplicates};
+use crate::hashing::{HashAlgorithm, hash_file};
+use crate::hooks::{HookConfig, HookPayload, run_hooks};
+use crate::ipld::{DagCodec, IpldConfig, link, linkify};
+use crate::layout::{
+    LayoutConfig, NamingConfig, OutputLayout, check_token_ids, discover_tokens, rename_token_files,
+};
+use crate::localization::{LOCALIZATION_DIR, Localization, LocalizedToken};
+use crate::manifest::{CollectionKind, Manifest, TokenEntry};
+use crate::metadata::{
+    JsonStyle, NftMetadata, build_batch_metadata, build_single_metadata, metadata_file_name,
+    route_animation,
+};
+use crate::poster::{POSTERS_DIR, prepare_poster};
+use crate::preview::{PREVIEWS_DIR, PreviewConfig, make_preview, supports_preview};
+use crate::sanitize::{strip_and_report, strip_dir};
+use crate::staging::StagedDir;
+use crate::standards::{
+    MediaInfo, MetadataFormat, detect_file_mime_type, is_animation_type, mime_type_from_name,
+};
+use crate::unixfs::AddConfig;
+use crate::urls::{UrlConfig, print_urls, record_urls};
+use crate::validate::{ValidationConfig, check_files};
+use crate::watermark::{WATERMARKED_DIR, Watermark, WatermarkConfig, watermark_files};
+
+// 单件工作流的可选项
+#[derive(Debug, Clone, Default)]
+pub struct SingleOptions {
+    pub hash_algorithm: HashAlgorithm,
+    // 元数据文件名是否带 .json 后缀
+    pub json_suffix: bool,
+    // 元数据标准 (ERC-721、Metaplex)
+    pub format: MetadataFormat,
+    // 大图的预览图
+    pub preview: PreviewConfig,
+    // 保留图片中的 EXIF / XMP 等信息 (默认上传前删除)
+    pub keep_exif: bool,
+    // 上传前检查文件的规则
+    pub validation: ValidationConfig,
+    // 加水印的公开预览
+    pub watermark: WatermarkConfig,
+    // 输出目录中的校验文件
+    pub checksums: ChecksumConfig,
+    // OpenSea 多语言扩展的各语言文字
+    pub localization: Localization,
+    // 上传过程中运行的钩子
+    pub hooks: Vec<HookConfig>,
+    // 清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+    // 元数据以 IPLD 节点发布
+    pub ipld: IpldConfig,
+    // 元数据包在目录中上传 (metadata/<名称>.json)，token URI 带上文件名
+    pub wrap_metadata: bool,
+    // 运行目录的命名和图片 / 元数据额外的输出位置
+    pub output: OutputLayout,
+    // 覆盖已存在的输出目录
+    pub force: bool,
+}
+
+// 批量工作流的可选项
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    // 允许输入文件夹为空 (默认拒绝生成空集合)
+    pub allow_empty: bool,
+    pub hash_algorithm: HashAlgorithm,
+    // 元数据文件名是否带 .json 后缀
+    pub json_suffix: bool,
+    // 元数据标准 (ERC-721、Metaplex)
+    pub format: MetadataFormat,
+    // 大图的预览图
+    pub preview: PreviewConfig,
+    // 保留图片中的 EXIF / XMP 等信息 (默认上传前删除)
+    pub keep_exif: bool,
+    // 上传前检查文件的规则
+    pub validation: ValidationConfig,
+    // 加水印的公开预览
+    pub watermark: WatermarkConfig,
+    // 子文件夹的读取方式和 token 图片的选择规则
+    pub layout: LayoutConfig,
+    // 元数据文件名的补零、前缀和大小写，以及是否把图片重命名为同样的名称
+    pub naming: NamingConfig,
+    // 复制输入文件夹时对符号链接、权限和特殊文件的处理
+    pub copy: CopyOptions,
+    // 输出目录中的校验文件
+    pub checksums: ChecksumConfig,
+    // OpenSea 多语言扩展的各语言文字
+    pub localization: Localization,
+    // 上传过程中运行的钩子
+    pub hooks: Vec<HookConfig>,
+    // 清单中为每个根 CID 记录的链接
+    pub urls: UrlConfig,
+    // 元数据以 IPLD 节点发布
+    pub ipld: IpldConfig,
+    // 运行目录的命名和图片 / 元数据额外的输出位置
+    pub output: OutputLayout,
+    // 覆盖已存在的输出目录
+    pub force: bool,
+    // 不同 token 的图片内容相同时终止 (默认只警告)
+    pub strict: bool,
+}
+
+// 打印写入的校验文件
+fn print_checksums(written: &[PathBuf]) {
+    for path in written {
+        println!(
+            "🔐 已写入校验文件: {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+    }
+}
+
+// 打印复制时跳过的文件
+fn print_copy_report(report: &CopyReport) {
+    for (label, paths) in [
+        ("被过滤的文件", &report.filtered),
+        ("符号链接", &report.skipped_links),
+        ("特殊文件 (管道、套接字或设备)", &report.special),
+    ] {
+        if !paths.is_empty() {
+            println!(
+                "🙈 已跳过 {} 个{}: {}",
+                paths.len(),
+                label,
+                paths.join(", ")
+            );
+        }
+    }
+    if report.hardlinked > 0 {
+        println!("🔗 {} 个文件建立为硬链接", report.hardlinked);
+    }
+}
+
+// 名称超过所选标准的限制时提醒
+pub fn warn_name(format: &MetadataFormat, name: &str) {
+    if let Some(warning) = format.name_warning(name) {
+        eprintln!("⚠️  {}", warning);
+    }
+}
+
+// ✅ 按文件内容判断 MIME 类型，与扩展名不一致时提醒 (市场按类型决定如何展示)
+pub fn detect_media_type(path: &Path, file_name: &str) -> Result<String> {
+    let mime_type =
+        detect_file_mime_type(path).map_err(|e| anyhow!("❌ 读取文件 {:?} 失败: {}", path, e))?;
+    let by_name = mime_type_from_name(file_name);
+    if by_name != "application/octet-stream" && by_name != mime_type {
+        eprintln!(
+            "⚠️  {} 的扩展名对应 {}，但文件内容是 {}，元数据中使用 {}",
+            file_name, by_name, mime_type, mime_type
+        );
+    }
+    Ok(mime_type)
+}
+
+// 元数据的 image 改为指向的图片 (主文件本身不适合直接作为 image 时)
+#[derive(Debug, Clone)]
+pub enum DisplayImage {
+    // 视频、音频等的封面图，主文件移到 animation_url
+    Poster(PathBuf),
+    // 大图缩小后的预览图，原图链接另外记录
+    Preview(PathBuf),
+}
+
+impl DisplayImage {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Poster(path) | Self::Preview(path) => path,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Poster(_) => "封面图",
+            Self::Preview(_) => "预览图",
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.path()
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+    }
+
+    // 把 image 改为这张图片的链接
+    pub fn apply(&self, metadata: &mut NftMetadata, media: &mut MediaInfo, uri: String) {
+        match self {
+            Self::Poster(_) => route_animation(metadata, uri),
+            Self::Preview(_) => {
+                media.original_uri = Some(std::mem::replace(&mut metadata.image, uri));
+            }
+        }
+    }
+}
+
+// ✅ 判断主文件的类型：视频、音频、3D 模型和 HTML 准备封面图 (output_dir/posters)，
+// 开启预览图时为超过尺寸的图片生成预览图 (output_dir/previews)
+pub fn prepare_media(
+    path: &Path,
+    file_name: &str,
+    output_dir: &Path,
+    preview: &PreviewConfig,
+) -> Result<(MediaInfo, Option<DisplayImage>)> {
+    let mime_type = detect_media_type(path, file_name)?;
+    // 子文件夹中的文件 (如 0001/front.png)，封面图和预览图也放在同名的子文件夹中
+    let subdir = Path::new(file_name).parent().unwrap_or(Path::new(""));
+    let display = if is_animation_type(&mime_type) {
+        Some(DisplayImage::Poster(prepare_poster(
+            path,
+            &mime_type,
+            &output_dir.join(POSTERS_DIR).join(subdir),
+        )?))
+    } else if preview.enabled && supports_preview(&mime_type) {
+        make_preview(path, &output_dir.join(PREVIEWS_DIR).join(subdir), preview)?
+            .map(DisplayImage::Preview)
+    } else {
+        None
+    };
+    let display_mime_type = display
+        .as_ref()
+        .map(|display| detect_file_mime_type(display.path()))
+        .transpose()?;
+    let media = MediaInfo {
+        file_name: file_name.to_string(),
+        mime_type,
+        display_mime_type,
+        original_uri: None,
+    };
+    Ok((media, display))
+}
+
+// 与 relative 在同一文件夹中的另一个文件，如 ("0001/front.png", "front.webp") -> "0001/front.webp"
+fn sibling_path(relative: &str, file_name: &str) -> String {
+    match relative.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, file_name),
+        None => file_name.to_string(),
+    }
+}
+
+// 上传前删除封面图中的 EXIF 等信息 (生成的封面和预览图本身不带这些信息)
+pub fn strip_display_image(display: Option<&DisplayImage>, keep_exif: bool) -> Result<()> {
+    if let Some(DisplayImage::Poster(path)) = display
+        && !keep_exif
+    {
+        strip_and_report(path)?;
+    }
+    Ok(())
+}
+
+// ✅ 按 [watermark] 为图片生成加水印的副本，放在 output_dir/watermarked/ 中；原图不变，用于揭晓
+// 没有可以加水印的图片 (视频、GIF 等) 时返回 None
+fn make_watermarked(
+    files: &[PathBuf],
+    source_root: &Path,
+    output_dir: &Path,
+    config: &WatermarkConfig,
+) -> Result<Option<PathBuf>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    let dir = output_dir.join(WATERMARKED_DIR);
+    let count = watermark_files(files, source_root, &dir, &Watermark::load(config)?)?;
+    if count == 0 {
+        println!("⚠️  没有可以加水印的图片 (只支持 PNG / JPEG / WebP)");
+        return Ok(None);
+    }
+    println!("💧 已为 {} 张图片生成加水印的副本: {:?}", count, dir);
+    Ok(Some(dir))
+}
+
+// ✅ 按 [localization] 写入各语言的文件并整体上传，返回 localization 文件夹的 CID；
+// 元数据要引用这个 CID，所以必须在元数据之前上传
+fn publish_localization(
+    backend: &dyn IpfsBackend,
+    localization: &Localization,
+    output_dir: &Path,
+    tokens: &[LocalizedToken],
+    style: JsonStyle,
+) -> Result<Option<String>> {
+    if localization.is_empty() {
+        return Ok(None);
+    }
+    let dir = output_dir.join(LOCALIZATION_DIR);
+    let missing = localization.write_files(&dir, tokens, style)?;
+    for (locale, count) in missing {
+        eprintln!(
+            "⚠️  {} 缺少 {} 个 token 的翻译，使用 {} 的文字",
+            locale, count, localization.default
+        );
+    }
+    let cid = backend.add_path(&dir)?;
+    println!(
+        "\n🌐 多语言文件夹 CID 已获取: {} ({})",
+        cid,
+        localization.locales().join(", ")
+    );
+    Ok(Some(cid))
+}
+
+// 单件元数据包在目录中上传时，运行目录中存放元数据的子目录
+pub const WRAPPED_METADATA_DIR: &str = "metadata";
+
+// ✅ 单件元数据包在目录中上传时 (metadata_file 为 "metadata/<名称>.json")，上传这个目录，
+// 返回目录的 CID 和带文件名的 token URI；没有包在目录中时返回 None
+pub fn publish_wrapped_metadata(
+    backend: &dyn IpfsBackend,
+    dir: &Path,
+    metadata_file: &str,
+) -> Result<Option<(String, String)>> {
+    let Some((folder, name)) = metadata_file.split_once('/') else {
+        return Ok(None);
+    };
+    let root = backend.add_path(&dir.join(folder))?;
+    let uri = format!("ipfs://{}/{}", root, name);
+    Ok(Some((root, uri)))
+}
+
+// 上传 JSON 数据的专用函数：只序列化一次，写入 path 的和上传的是同一份字节，
+// 重新上传本地保存的文件得到的 CID 与发布的相同
+pub fn upload_json_str_to_ipfs<T: Serialize>(
+    backend: &dyn IpfsBackend,
+    data: &T,
+    style: JsonStyle,
+    path: &Path,
+) -> Result<String> {
+    println!("\n--- 正在上传 JSON 对象 ---");
+    let json_string = style.file_json(data)?;
+    fs::write(path, &json_string)?;
+    let cid = backend.add_bytes(json_string.as_bytes())?;
+    println!("✅ JSON 元数据上传成功!\n   - CID: {}", cid);
+    Ok(cid)
+}
+
+// ✅ 把 `<CID>/<子路径>` 解析为文件自己的 CID；同一个文件夹只列出一次
+#[derive(Default)]
+pub struct LinkResolver {
+    listings: HashMap<String, Vec<DirEntry>>,
+}
+
+impl LinkResolver {
+    pub fn resolve(&mut self, backend: &dyn IpfsBackend, path: &str) -> Result<Option<String>> {
+        let mut parts = path.split('/');
+        let root = parts.next().unwrap_or_default();
+        if root.parse::<CidInfo>().is_err() {
+            return Ok(None);
+        }
+        let mut current = root.to_string();
+        for name in parts {
+            if !self.listings.contains_key(&current) {
+                let entries = backend
+                    .ls(&current)
+                    .map_err(|e| anyhow!("❌ 解析 IPLD 链接 ipfs://{} 失败: {:#}", path, e))?;
+                self.listings.insert(current.clone(), entries);
+            }
+            match self.listings[&current]
+                .iter()
+                .find(|entry| entry.name == name)
+            {
+                Some(entry) => current = entry.cid.clone(),
+                // 模板 (如多语言的 {locale}.json) 等不存在的路径保持字符串
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(current))
+    }
+}
+
+// ✅ [ipld] 时把元数据文档发布为 IPLD 节点，文档中的 ipfs:// 链接换成 CID 链接
+pub fn dag_put_document<T: Serialize>(
+    backend: &dyn IpfsBackend,
+    document: &T,
+    codec: DagCodec,
+    resolver: &mut LinkResolver,
+) -> Result<String> {
+    let mut node = serde_json::to_value(document)?;
+    let links = linkify(&mut node, &mut |path| resolver.resolve(backend, path))?;
+    let cid = backend.dag_put(&node, codec)?;
+    println!(
+        "🧬 IPLD 元数据节点 ({}，{} 个 CID 链接): {}",
+        codec, links, cid
+    );
+    Ok(cid)
+}
+
+// 工作流一：处理单个 NFT
+pub fn process_single_nft(
+    backend: &dyn IpfsBackend,
+    image_path: &Path,
+    output_root: &Path,
+    options: &SingleOptions,
+) -> Result<Manifest> {
+    println!("\n==============================================");
+    println!("🚀 开始处理单个 NFT...");
+    println!(
+        "   - 文件后缀模式: {}",
+        if options.json_suffix { ".json" } else { "无" }
+    );
+    println!("   - 元数据标准: {}", options.format.standard);
+    println!("==============================================");
+
+    let image_filename = image_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的图片文件名"))?;
+    let image_name_without_ext = image_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("无效的图片文件名"))?;
+
+    check_files(&[image_path.to_path_buf()], &options.validation)?;
+    if options.wrap_metadata && options.ipld.enabled {
+        return Err(anyhow!(
+            "❌ IPLD 节点没有文件名，不能包在目录中上传，请关闭 [ipld] 或 --wrap"
+        ));
+    }
+
+    // 上传的是输出目录中的副本，EXIF 等信息只从副本中删除；先写入临时目录，成功后再替换
+    let relative = options.output.single_path(
+        &options.format.collection.name,
+        image_name_without_ext,
+        Utc::now(),
+    )?;
+    check_exports(&options.output, &relative, options.force)?;
+    let staged = stage_output(backend, &output_root.join(&relative), options.force)?;
+    let output_dir = staged.path().to_path_buf();
+    let local_image = output_dir.join(image_filename);
+    fs::copy(image_path, &local_image)?;
+    if !options.keep_exif {
+        strip_and_report(&local_image)?;
+    }
+
+    let image_cid = backend.add_path(&local_image)?;
+    println!("\n🖼️  图片 CID 已获取: {}", image_cid);
+    let add = backend.add_config();
+    run_hooks(
+        &options.hooks,
+        &HookPayload::images_uploaded("single", output_dir.clone(), &image_cid),
+    )?;
+    let watermarked_cid = match make_watermarked(
+        std::slice::from_ref(&local_image),
+        &output_dir,
+        &output_dir,
+        &options.watermark,
+    )? {
+        Some(dir) => {
+            let cid = backend.add_path(&dir.join(image_filename))?;
+            println!("💧 水印图片 CID 已获取: {}", cid);
+            Some(cid)
+        }
+        None => None,
+    };
+
+    let (mut media, display) =
+        prepare_media(image_path, image_filename, &output_dir, &options.preview)?;
+    strip_display_image(display.as_ref(), options.keep_exif)?;
+    let mut metadata =
+        build_single_metadata(image_filename, &file_ref(&image_cid, image_filename, &add));
+    options.format.collection.apply(&mut metadata, None);
+    let (mut posters_cid, mut previews_cid) = (None, None);
+    if let Some(display) = &display {
+        let cid = backend.add_path(display.path())?;
+        println!("🖼️  {} CID 已获取: {}", display.label(), cid);
+        let uri = format!("ipfs://{}", file_ref(&cid, display.file_name(), &add));
+        display.apply(&mut metadata, &mut media, uri);
+        match display {
+            DisplayImage::Poster(_) => posters_cid = Some(cid),
+            DisplayImage::Preview(_) => previews_cid = Some(cid),
+        }
+    }
+    warn_name(&options.format, &metadata.name);
+    let localization_cid = publish_localization(
+        backend,
+        &options.localization,
+        &output_dir,
+        &[LocalizedToken {
+            key: image_name_without_ext.to_string(),
+            folder: String::new(),
+            metadata: &metadata,
+        }],
+        options.format.json_style,
+    )?;
+    if let Some(cid) = &localization_cid {
+        metadata.extra.insert(
+            "localization".to_string(),
+            options.localization.block(cid, ""),
+        );
+    }
+    let document = options.format.render(&metadata, &media, None);
+
+    // 包在目录中时文件名总是带 .json 后缀
+    let file_name = if options.wrap_metadata {
+        format!(
+            "{}/{}",
+            WRAPPED_METADATA_DIR,
+            metadata_file_name(image_name_without_ext, true)
+        )
+    } else {
+        metadata_file_name(image_name_without_ext, options.json_suffix)
+    };
+    let metadata_path = output_dir.join(&file_name);
+    if let Some(parent) = metadata_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let metadata_cid = if options.ipld.enabled {
+        let json = options.format.json_style.file_json(&document)?;
+        fs::write(&metadata_path, json)?;
+        dag_put_document(
+            backend,
+            &document,
+            options.ipld.codec,
+            &mut LinkResolver::default(),
+        )?
+    } else {
+        upload_json_str_to_ipfs(
+            backend,
+            &document,
+            options.format.json_style,
+            &metadata_path,
+        )?
+    };
+    let (root_cid, token_uri) = match publish_wrapped_metadata(backend, &output_dir, &file_name)? {
+        Some((root, uri)) => {
+            println!("📁 元数据已包在目录中上传!\n   - 目录 CID: {}", root);
+            (root, uri)
+        }
+        None => (metadata_cid.clone(), format!("ipfs://{}", metadata_cid)),
+    };
+
+    let mut manifest = Manifest {
+        kind: CollectionKind::Single,
+        created_at: Utc::now().to_rfc3339(),
+        backend: backend.name().to_string(),
+        node_version: backend.node_version(),
+        output_dir: staged.target().to_path_buf(),
+        image_cid,
+        posters_cid,
+        previews_cid,
+        watermarked_cid,
+        localization_cid,
+        metadata_cid: root_cid,
+        token_uri,
+        tokens: vec![TokenEntry {
+            token_id: None,
+            name: metadata.name.clone(),
+            image_file: image_filename.to_string(),
+            image_hash: Some(hash_file(&local_image, options.hash_algorithm)?),
+            image_uri: metadata.image.clone(),
+            mime_type: Some(media.mime_type),
+            animation_uri: metadata.animation_url.clone(),
+            original_uri: media.original_uri,
+            metadata_file: file_name,
+            metadata_cid: Some(metadata_cid),
+            arweave: None,
+        }],
+        served_by: Default::default(),
+        urls: Default::default(),
+        encryption: backend.encryption(),
+        ipld: options.ipld.enabled.then_some(options.ipld.codec),
+        filecoin: BTreeMap::new(),
+        remote_pins: BTreeMap::new(),
+    };
+    record_served_by(&mut manifest, backend);
+    record_urls(&mut manifest, &options.urls);
+    run_hooks(
+        &options.hooks,
+        &HookPayload::metadata_uploaded("single", output_dir.clone(), &manifest),
+    )?;
+    manifest.save(&output_dir)?;
+    print_checksums(&write_checksums(&output_dir, &options.checksums)?);
+    let output_dir = staged.commit()?;
+
+    println!("\n💾 图片和元数据已在本地打包保存至: {:?}", output_dir);
+    export_outputs(
+        &options.output,
+        &relative,
+        &output_dir.join(image_filename),
+        &output_dir.join(&manifest.tokens[0].metadata_file),
+    )?;
+    print_urls(&manifest);
+    println!("\n--- ✨ 单件流程完成 ✨ ---");
+    println!(
+        "下一步，您可以在 mint 函数中使用这个元数据 URI: {}",
+        manifest.token_uri
+    );
+    Ok(manifest)
+}
+
+// 工作流二：处理批量 NFT 集合
+pub fn process_batch_collection(
+    backend: &dyn IpfsBackend,
+    images_input_dir: &Path,
+    output_root: &Path,
+    options: &BatchOptions,
+) -> Result<Manifest> {
+    println!("\n==============================================");
+    println!("🚀 开始处理批量 NFT 集合...");
+    println!(
+        "   - 文件后缀模式: {}",
+        if options.json_suffix { ".json" } else { "无" }
+    );
+    println!("   - 元数据标准: {}", options.format.standard);
+    println!("==============================================");
+
+    // 先检查输入，避免上传一个空文件夹或不符合规则的文件
+    let mut token_files = discover_tokens(images_input_dir, &options.layout)?;
+    check_token_ids(&token_files, &options.layout)?;
+    let stems = options.naming.stems(&token_files)?;
+    if token_files.is_empty() {
+        if !options.allow_empty {
+            return Err(anyhow!(
+                "❌ 图片文件夹为空: {:?}，拒绝生成空集合 (如确有需要请使用 --allow-empty)",
+                images_input_dir
+            ));
+        }
+        println!("⚠️  图片文件夹为空，将按 --allow-empty 生成一个空集合");
+    }
+    let input_files: Vec<PathBuf> = token_files
+        .iter()
+        .flat_map(|token| token.files())
+        .map(|file| images_input_dir.join(file))
+        .collect();
+    check_files(&input_files, &options.validation)?;
+
+    let relative = options
+        .output
+        .batch_path(&options.format.collection.name, Utc::now())?;
+    check_exports(&options.output, &relative, options.force)?;
+    let staged = stage_output(backend, &output_root.join(&relative), options.force)?;
+    let collection_output_dir = staged.path().to_path_buf();
+    let images_output_dir = collection_output_dir.join("images");
+    let metadata_output_dir = collection_output_dir.join("metadata");
+
+    // 上传的是输出目录中的副本，EXIF 等信息只从副本中删除
+    let copied = copy_tree(
+        images_input_dir,
+        &images_output_dir,
+        &options.layout.file_filter(images_input_dir)?,
+        &options.copy,
+    )?;
+    println!("\n💾 所有图片已复制到: {:?}", images_output_dir);
+    print_copy_report(&copied);
+    if let Some(link) = token_files
+        .iter()
+        .flat_map(|token| token.files())
+        .find(|file| copied.skipped_links.contains(file))
+    {
+        return Err(anyhow!(
+            "❌ {} 是符号链接，[copy] symlinks = \"skip\" 时不会被复制，请改用 follow 或移除该文件",
+            link
+        ));
+    }
+    if options.naming.rename_images {
+        let renamed = rename_token_files(&images_output_dir, &mut token_files, &options.naming)?;
+        if renamed > 0 {
+            println!(
+                "✏️  已按 [naming] 把 {} 个图片重命名为与元数据相同的名称",
+                renamed
+            );
+        }
+    }
+    if options.keep_exif {
+        println!("📷 按 --keep-exif 保留图片中的 EXIF / XMP 信息");
+    } else {
+        let stripped = strip_dir(&images_output_dir)?;
+        if stripped > 0 {
+            println!("🧹 已删除 {} 个文件中的 EXIF / XMP 等信息", stripped);
+        }
+    }
+    let image_files: Vec<PathBuf> = token_files
+        .iter()
+        .map(|token| images_output_dir.join(&token.image))
+        .collect();
+    // 在删除 EXIF 之后比较，只有元数据不同的图片也算相同
+    let (image_hashes, duplicates) = find_duplicates(
+        &token_files
+            .iter()
+            .zip(&image_files)
+            .map(|(token, file)| (token.token_id, token.image.clone(), file.clone()))
+            .collect::<Vec<_>>(),
+        options.hash_algorithm,
+    )?;
+    check_duplicate_content(&duplicates, options.strict)?;
+
+    let images_folder_cid = backend.add_path(&images_output_dir)?;
+    println!("\n🖼️  图片文件夹 CID 已获取: {}", images_folder_cid);
+    run_hooks(
+        &options.hooks,
+        &HookPayload::images_uploaded("batch", collection_output_dir.clone(), &images_folder_cid),
+    )?;
+    let watermarked_cid = match make_watermarked(
+        &image_files,
+        &images_output_dir,
+        &collection_output_dir,
+        &options.watermark,
+    )? {
+        Some(dir) => {
+            let cid = backend.add_path(&dir)?;
+            println!("💧 水印图片文件夹 CID 已获取: {}", cid);
+            Some(cid)
+        }
+        None => None,
+    };
+
+    println!("\n--- 正在为每张图片生成元数据 JSON 文件 ---");
+    fs::create_dir_all(&metadata_output_dir)?;
+
+    // 视频、音频等文件的封面图放到 posters/，大图的预览图放到 previews/，各自整体上传一次
+    let mut media = Vec::with_capacity(image_files.len());
+    for (image_file, token) in image_files.iter().zip(&token_files) {
+        media.push(prepare_media(
+            image_file,
+            &token.image,
+            &collection_output_dir,
+            &options.preview,
+        )?);
+    }
+    let upload_folder = |is_kind: fn(&DisplayImage) -> bool, dir: &str| -> Result<Option<String>> {
+        let Some(display) = media
+            .iter()
+            .find_map(|(_, d)| d.as_ref().filter(|d| is_kind(d)))
+        else {
+            return Ok(None);
+        };
+        let cid = backend.add_path(&collection_output_dir.join(dir))?;
+        println!("\n🖼️  {}文件夹 CID 已获取: {}", display.label(), cid);
+        Ok(Some(cid))
+    };
+    let posters_cid = upload_folder(|d| matches!(d, DisplayImage::Poster(_)), POSTERS_DIR)?;
+    let previews_cid = upload_folder(|d| matches!(d, DisplayImage::Preview(_)), PREVIEWS_DIR)?;
+
+    let mut prepared = Vec::with_capacity(image_files.len());
+    for (((token, stem), (mut media, display)), image_hash) in
+        token_files.iter().zip(&stems).zip(media).zip(image_hashes)
+    {
+        let token_id = token.token_id;
+        let image_filename = token.image.as_str();
+
+        let mut metadata = build_batch_metadata(token_id, &images_folder_cid, image_filename);
+        options
+            .format
+            .collection
+            .apply(&mut metadata, Some(token_id));
+        if let Some(display) = &display {
+            let folder_cid = match display {
+                DisplayImage::Poster(_) => &posters_cid,
+                DisplayImage::Preview(_) => &previews_cid,
+            };
+            // 封面图和预览图与原文件在各自文件夹中的相对位置相同
+            let uri = format!(
+                "ipfs://{}/{}",
+                folder_cid.as_deref().unwrap_or_default(),
+                sibling_path(image_filename, display.file_name())
+            );
+            display.apply(&mut metadata, &mut media, uri);
+        }
+        warn_name(&options.format, &metadata.name);
+        prepared.push((token, stem, metadata, media, image_hash));
+    }
+
+    let localization_cid = publish_localization(
+        backend,
+        &options.localization,
+        &collection_output_dir,
+        &prepared
+            .iter()
+            .map(|(token, stem, metadata, _, _)| LocalizedToken {
+                key: token.token_id.to_string(),
+                folder: stem.to_string(),
+                metadata,
+            })
+            .collect::<Vec<_>>(),
+        options.format.json_style,
+    )?;
+
+    let mut tokens = Vec::with_capacity(prepared.len());
+    let mut resolver = LinkResolver::default();
+    // [ipld] 时每个 token 一个节点，再由一个 "文件名 -> 节点" 的索引节点作为 Base URI
+    let mut ipld_index = BTreeMap::new();
+    for (token, stem, mut metadata, media, image_hash) in prepared {
+        let token_id = token.token_id;
+        if let Some(cid) = &localization_cid {
+            metadata.extra.insert(
+                "localization".to_string(),
+                options.localization.block(cid, stem),
+            );
+        }
+        let file_name = metadata_file_name(stem, options.json_suffix);
+        let mut file = File::create(metadata_output_dir.join(&file_name))?;
+        let document = options.format.render(&metadata, &media, Some(token_id));
+        let json = options.format.json_style.file_json(&document)?;
+        file.write_all(json.as_bytes())?;
+        let metadata_cid = if options.ipld.enabled {
+            let cid = dag_put_document(backend, &document, options.ipld.codec, &mut resolver)?;
+            ipld_index.insert(file_name.clone(), link(&cid));
+            Some(cid)
+        } else {
+            None
+        };
+
+        tokens.push(TokenEntry {
+            token_id: Some(token_id),
+            name: metadata.name,
+            image_file: token.image.clone(),
+            image_hash: Some(image_hash),
+            image_uri: metadata.image,
+            mime_type: Some(media.mime_type),
+            animation_uri: metadata.animation_url,
+            original_uri: media.original_uri,
+            metadata_file: file_name,
+            metadata_cid,
+            arweave: None,
+        });
+    }
+    println!(
+        "✅ 成功生成 {} 个元数据文件到: {:?}",
+        image_files.len(),
+        metadata_output_dir
+    );
+
+    let metadata_folder_cid = if options.ipld.enabled {
+        let cid = backend.dag_put(&serde_json::to_value(&ipld_index)?, options.ipld.codec)?;
+        println!("\n🧬 IPLD 元数据索引节点 CID 已获取: {}", cid);
+        cid
+    } else {
+        let cid = backend.add_path(&metadata_output_dir)?;
+        println!("\n📄 元数据文件夹 CID 已获取: {}", cid);
+        cid
+    };
+
+    let mut manifest = Manifest {
+        kind: CollectionKind::Batch,
+        created_at: Utc::now().to_rfc3339(),
+        backend: backend.name().to_string(),
+        node_version: backend.node_version(),
+        output_dir: staged.target().to_path_buf(),
+        image_cid: images_folder_cid,
+        posters_cid,
+        previews_cid,
+        watermarked_cid,
+        localization_cid,
+        metadata_cid: metadata_folder_cid.clone(),
+        token_uri: format!("ipfs://{}/", metadata_folder_cid),
+        tokens,
+        served_by: Default::default(),
+        urls: Default::default(),
+        encryption: backend.encryption(),
+        ipld: options.ipld.enabled.then_some(options.ipld.codec),
+        filecoin: BTreeMap::new(),
+        remote_pins: BTreeMap::new(),
+    };
+    record_served_by(&mut manifest, backend);
+    record_urls(&mut manifest, &options.urls);
+    run_hooks(
+        &options.hooks,
+        &HookPayload::metadata_uploaded("batch", collection_output_dir.clone(), &manifest),
+    )?;
+    manifest.save(&collection_output_dir)?;
+    print_checksums(&write_checksums(
+        &collection_output_dir,
+        &options.checksums,
+    )?);
+    let collection_output_dir = staged.commit()?;
+    println!("\n💾 集合已保存至: {:?}", collection_output_dir);
+    export_outputs(
+        &options.output,
+        &relative,
+        &collection_output_dir.join("images"),
+        &collection_output_dir.join("metadata"),
+    )?;
+
+    print_urls(&manifest);
+
+    println!("\n--- ✨ 批量流程完成 ✨ ---");
+    println!(
+        "下一步，您可以在合约中将 Base URI 设置为: {}",
+        manifest.token_uri
+    );
+    Ok(manifest)
+}
+
+// [output] images_root / metadata_root 中这次运行的目录
+fn export_targets(output: &OutputLayout, relative: &Path) -> Vec<PathBuf> {
+    [&output.images_root, &output.metadata_root]
+        .into_iter()
+        .flatten()
+        .map(|root| root.join(relative))
+        .collect()
+}
+
+// 上传前检查额外的输出位置，已存在时与运行目录一样需要 --force
+fn check_exports(output: &OutputLayout, relative: &Path, force: bool) -> Result<()> {
+    match export_targets(output, relative)
+        .into_iter()
+        .find(|target| target.exists())
+    {
+        Some(target) if !force => Err(anyhow!(
+            "❌ 输出目录已存在: {:?}，为避免覆盖之前的结果已停止 (确认要覆盖请使用 --force)",
+            target
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ✅ 把图片和元数据 (文件夹或单个文件) 复制到 [output] images_root / metadata_root 下同名的运行目录
+fn export_outputs(
+    output: &OutputLayout,
+    relative: &Path,
+    images: &Path,
+    metadata: &Path,
+) -> Result<()> {
+    for (root, source) in [
+        (&output.images_root, images),
+        (&output.metadata_root, metadata),
+    ] {
+        let Some(root) = root else {
+            continue;
+        };
+        let target = root.join(relative);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        if source.is_dir() {
+            copy_directory(source, &target)?;
+        } else if let Some(name) = source.file_name() {
+            fs::create_dir_all(&target)?;
+            fs::copy(source, target.join(name))?;
+        }
+        println!("📂 已复制到: {:?}", target);
+    }
+    Ok(())
+}
+
+// 输出目录先写入临时目录，成功后再改名；[add] nocopy 时节点引用的是文件路径，只能直接写入
+fn stage_output(backend: &dyn IpfsBackend, target: &Path, force: bool) -> Result<StagedDir> {
+    if backend.add_config().nocopy {
+        println!(
+            "📎 nocopy: 直接写入输出目录 {:?}，上传后请不要移动或删除其中的文件",
+            target
+        );
+        return StagedDir::in_place(target, force);
+    }
+    StagedDir::create(target, force)
+}
+
+// [add] wrap_with_directory 时单个文件的 CID 是外层目录，链接需要带上文件名
+pub fn file_ref(cid: &str, file_name: &str, add: &AddConfig) -> String {
+    if add.wrap_with_directory {
+        format!("{}/{}", cid, file_name)
+    } else {
+        cid.to_string()
+    }
+}
+
+// ✅ 记录清单中每个 CID 实际由哪个节点完成上传 (只有多节点故障转移时才有)
+pub fn record_served_by(manifest: &mut Manifest, backend: &dyn IpfsBackend) {
+    let token_cids = manifest
+        .tokens
+        .iter()
+        .filter_map(|token| token.metadata_cid.clone());
+    let cids: Vec<String> = [manifest.image_cid.clone(), manifest.metadata_cid.clone()]
+        .into_iter()
+        .chain(manifest.posters_cid.clone())
+        .chain(manifest.previews_cid.clone())
+        .chain(manifest.watermarked_cid.clone())
+        .chain(manifest.localization_cid.clone())
+        .chain(token_cids)
+        .filter(|cid| !cid.is_empty())
+        .collect();
+    for cid in cids {
+        if let Some(node) = backend.served_by(&cid) {
+            manifest.served_by.insert(cid, node);
+        }
+    }
+}
+
+// 列出输入文件夹顶层的所有文件，按文件名排序
+pub fn list_image_files(images_input_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !images_input_dir.is_dir() {
+        return Err(anyhow!("❌ 图片文件夹不存在: {:?}", images_input_dir));
+    }
+    let mut image_files: Vec<PathBuf> = fs::read_dir(images_input_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    image_files.sort();
+    Ok(image_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::backend::MemoryBackend;
+    use crate::metadata::JsonStyle;
+    use crate::unixfs::{ImportOptions, compute_cid};
+
+    // 保存的文件和上传的字节相同：重新计算保存文件的 CID 与返回的 CID 一致
+    #[test]
+    fn saved_metadata_matches_uploaded_cid() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = MemoryBackend::new();
+        let metadata = json!({
+            "name": "Token #1",
+            "description": "测试",
+            "attributes": [{ "trait_type": "Level", "value": 2.50 }],
+        });
+        let mut cids = Vec::new();
+        for style in [JsonStyle::Pretty, JsonStyle::Minified, JsonStyle::Canonical] {
+            let path = dir.path().join(format!("{}.json", style.as_str()));
+            let cid = upload_json_str_to_ipfs(&backend, &metadata, style, &path).unwrap();
+            let local = compute_cid(&path, &ImportOptions::default()).unwrap();
+            assert_eq!(local.to_string(), cid, "{}", style.as_str());
+            cids.push(cid);
+        }
+        // 带缩进的文件与去掉空白的文件内容不同
+        assert_ne!(cids[0], cids[1]);
+    }
+
+    fn ipfs_path(uri: &str) -> &str {
+        uri.strip_prefix("ipfs://").unwrap()
+    }
+
+    // 单件流程：图片和元数据都能从后端读回，manifest.json 与返回的清单一致
+    #[test]
+    fn single_nft_against_memory_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let images = crate::demo::generate_demo_images(&dir.path().join("images"), 1).unwrap();
+        let backend = MemoryBackend::new();
+        let output = dir.path().join("output");
+        let manifest =
+            process_single_nft(&backend, &images[0], &output, &SingleOptions::default()).unwrap();
+
+        assert_eq!(manifest.kind, CollectionKind::Single);
+        assert_eq!(manifest.backend, "memory");
+        assert_eq!(manifest.tokens.len(), 1);
+        let local = compute_cid(&images[0], &ImportOptions::default()).unwrap();
+        assert_eq!(manifest.image_cid, local.to_string());
+        assert_eq!(
+            manifest.token_uri,
+            format!("ipfs://{}", manifest.metadata_cid)
+        );
+        assert_eq!(
+            manifest.tokens[0].metadata_cid.as_deref(),
+            Some(manifest.metadata_cid.as_str())
+        );
+
+        // 后端里的元数据指向上传的图片，图片内容与原文件相同
+        let metadata: serde_json::Value =
+            serde_json::from_slice(&backend.cat(&manifest.metadata_cid).unwrap()).unwrap();
+        assert_eq!(metadata["image"], format!("ipfs://{}", manifest.image_cid));
+        let image = backend
+            .cat(ipfs_path(metadata["image"].as_str().unwrap()))
+            .unwrap();
+        assert_eq!(image, fs::read(&images[0]).unwrap());
+        let pins = backend.pins();
+        assert!(pins.contains(&manifest.image_cid));
+        assert!(pins.contains(&manifest.metadata_cid));
+
+        // 保存的清单可以读回，并与返回值一致
+        let saved = Manifest::load(&manifest.output_dir).unwrap();
+        assert_eq!(saved.kind, CollectionKind::Single);
+        assert_eq!(saved.image_cid, manifest.image_cid);
+        assert_eq!(saved.metadata_cid, manifest.metadata_cid);
+        assert_eq!(saved.token_uri, manifest.token_uri);
+        assert!(manifest.output_dir.starts_with(&output));
+    }
+
+    // 批量流程：元数据文件夹里每个 token 一个文件，图片链接都能在后端解析
+    #[test]
+    fn batch_collection_against_memory_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let images_dir = dir.path().join("images");
+        crate::demo::generate_demo_images(&images_dir, 3).unwrap();
+        let backend = MemoryBackend::new();
+        let output = dir.path().join("output");
+        let manifest =
+            process_batch_collection(&backend, &images_dir, &output, &BatchOptions::default())
+                .unwrap();
+
+        assert_eq!(manifest.kind, CollectionKind::Batch);
+        assert_eq!(manifest.backend, "memory");
+        assert_eq!(manifest.tokens.len(), 3);
+        assert_eq!(
+            manifest.token_uri,
+            format!("ipfs://{}/", manifest.metadata_cid)
+        );
+        // 图片文件夹的 CID 与本地重新计算的一致
+        let local = compute_cid(
+            &manifest.output_dir.join("images"),
+            &ImportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(manifest.image_cid, local.to_string());
+
+        let mut names: Vec<String> = backend
+            .ls(&manifest.metadata_cid)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["1", "2", "3"]);
+
+        for token in &manifest.tokens {
+            let id = token.token_id.unwrap();
+            let path = format!("{}/{}", manifest.metadata_cid, token.metadata_file);
+            let metadata: serde_json::Value =
+                serde_json::from_slice(&backend.cat(&path).unwrap()).unwrap();
+            assert_eq!(metadata["image"], token.image_uri);
+            assert_eq!(
+                token.image_uri,
+                format!("ipfs://{}/{}.png", manifest.image_cid, id)
+            );
+            let image = backend.cat(ipfs_path(&token.image_uri)).unwrap();
+            assert_eq!(image, fs::read(images_dir.join(&token.image_file)).unwrap());
+        }
+        assert!(backend.pins().contains(&manifest.metadata_cid));
+
+        let saved = Manifest::load(&manifest.output_dir).unwrap();
+        assert_eq!(saved.kind, CollectionKind::Batch);
+        assert_eq!(saved.image_cid, manifest.image_cid);
+        assert_eq!(saved.metadata_cid, manifest.metadata_cid);
+        assert_eq!(saved.tokens.len(), 3);
+        assert_eq!(saved.tokens[2].image_uri, manifest.tokens[2].image_uri);
+    }
+}
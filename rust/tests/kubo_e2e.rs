@@ -0,0 +1,57 @@
+// tests/kubo_e2e.rs
+
+// 对真实的 Kubo 节点上传文件和文件夹，CID 要与本地 unixfs 计算的一致
+// cargo test --features kubo-harness --test kubo_e2e
+#![cfg(feature = "kubo-harness")]
+
+use std::fs;
+use std::path::Path;
+
+use rust::backend::IpfsBackend;
+use rust::harness::{HarnessOptions, KuboHarness};
+use rust::unixfs::{ImportOptions, compute_cid};
+
+fn write_tree(root: &Path) {
+    fs::create_dir_all(root.join("nested/deeper")).unwrap();
+    fs::create_dir_all(root.join("empty")).unwrap();
+    fs::write(root.join("a.txt"), "hello kubo\n").unwrap();
+    fs::write(root.join("nested/b.json"), r#"{"name":"b"}"#).unwrap();
+    fs::write(root.join("nested/deeper/empty.txt"), "").unwrap();
+    // 超过一个分块，产生中间节点
+    let big: Vec<u8> = (0..700_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(root.join("nested/deeper/big.bin"), big).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("nested/b.json", root.join("link.json")).unwrap();
+}
+
+#[test]
+fn uploads_match_local_cids() {
+    let dir = tempfile::tempdir().unwrap();
+    let tree = dir.path().join("tree");
+    write_tree(&tree);
+    let options = ImportOptions::default();
+
+    let harness = KuboHarness::start(HarnessOptions::default()).unwrap();
+    // 不做兼容性检查：文件夹整体经 /add 上传
+    let plain = harness.backend().unwrap();
+    // 做过兼容性检查：文件夹按本地计划逐块写入 (block/put)
+    let mut checked = harness.backend().unwrap();
+    checked.check_compatibility().unwrap();
+
+    for backend in [&plain, &checked] {
+        let file = tree.join("nested/deeper/big.bin");
+        let cid = backend.add_path(&file).unwrap();
+        assert_eq!(cid, compute_cid(&file, &options).unwrap().to_string());
+
+        let cid = backend.add_path(&tree).unwrap();
+        assert_eq!(cid, compute_cid(&tree, &options).unwrap().to_string());
+        assert!(backend.is_pinned(&cid).unwrap());
+    }
+
+    // 节点上读回的内容与本地文件相同
+    let root = compute_cid(&tree, &options).unwrap().to_string();
+    let data = harness
+        .ipfs(&["cat", &format!("{}/nested/b.json", root)])
+        .unwrap();
+    assert_eq!(data, r#"{"name":"b"}"#);
+}